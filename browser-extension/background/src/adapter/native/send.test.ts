@@ -1,3 +1,4 @@
+import { NATIVE_PROTOCOL_VERSION } from '@launcherg/shared'
 import { describe, expect, it, vi } from 'vitest'
 import { createNativeMessenger } from './send'
 
@@ -28,4 +29,16 @@ describe('ネイティブメッセンジャー送受信(JSON)', () => {
     const res = await messenger.sendJson({ request_id: 'rid-4', message: { case: 'HealthCheck', value: {} } })
     expect(res).toBeNull()
   })
+
+  it('protocol_version を付けて送信する', async () => {
+    mockChromeSendNativeMessage({ success: true, error: '', request_id: 'rid-5' })
+    const messenger = createNativeMessenger(host)
+    await messenger.sendJson({ request_id: 'rid-5', message: { case: 'HealthCheck', value: {} } })
+    const sendNativeMessage = (globalThis as any).chrome.runtime.sendNativeMessage
+    expect(sendNativeMessage.mock.calls[0][1]).toEqual({
+      protocol_version: NATIVE_PROTOCOL_VERSION,
+      request_id: 'rid-5',
+      message: { case: 'HealthCheck', value: {} },
+    })
+  })
 })
@@ -1,5 +1,6 @@
-import type { NativeMessageTs } from '@launcherg/shared/typeshare/native-messaging'
-import { logger } from '@launcherg/shared'
+import type { NativeMessageTs, NativeResponseTs } from '@launcherg/shared/typeshare/native-messaging'
+import { logger, NATIVE_PROTOCOL_VERSION } from '@launcherg/shared'
+import { NativeErrorCodeTs } from '@launcherg/shared/typeshare/native-messaging'
 
 const log = logger('background:native')
 
@@ -41,17 +42,20 @@ export function createNativeMessenger(nativeHostName: string) {
       if (!isObjectRecord(message))
         return reject(new Error('Encoded JSON message is not an object'))
 
-      const requestId = message.request_id
-      log.debug('Sending native message(JSON)', { type: message.message.case, requestId, message })
+      const payload: NativeMessageTs = { ...message, protocol_version: message.protocol_version ?? NATIVE_PROTOCOL_VERSION }
+      const requestId = payload.request_id
+      log.debug('Sending native message(JSON)', { type: payload.message.case, requestId, message: payload })
 
       const startTime = Date.now()
       chrome.runtime.sendNativeMessage(
         nativeHostName,
-        message,
+        payload,
         (response) => {
           if (chrome.runtime.lastError)
             return reject(new Error(chrome.runtime.lastError.message))
-          log.debug('Received native message(JSON)', { ellapsed: Date.now() - startTime, type: message.message.case, response })
+          log.debug('Received native message(JSON)', { ellapsed: Date.now() - startTime, type: payload.message.case, response })
+          if (isObjectRecord(response) && (response as NativeResponseTs).error_code === NativeErrorCodeTs.UnsupportedProtocolVersion)
+            log.warn('Native host does not support this protocol version. Please update the desktop app.', { requestId, error: (response as NativeResponseTs).error })
           resolve(response as TRes)
         },
       )
@@ -31,4 +31,30 @@ describe('ステータス取得（getStatus）ユースケース', () => {
     expect(res.success).toBe(true)
     expect(res.response?.case).toBe('statusResult')
   })
+
+  it('extension_ids があれば接続済み拡張機能として使う', async () => {
+    const context = buildTestContext({
+      idGenerator: { generate: () => 'rid' },
+      nativeMessenger: {
+        sendJson: vi.fn(async (): Promise<NativeResponseTs> => ({
+          success: true,
+          error: '',
+          request_id: 'rid',
+          response: {
+            case: 'StatusResult',
+            value: {
+              total_synced: 0,
+              connected_extensions: 2,
+              extension_ids: ['ext-a', 'ext-b'],
+              is_running: true,
+              connection_status: 'connected',
+              error_message: '',
+            },
+          },
+        })),
+      },
+    })
+    const res: any = await handleGetStatus(context, 'req-1', {})
+    expect(res.response?.value.status.connectedExtensions).toEqual(['ext-a', 'ext-b'])
+  })
 })
@@ -21,7 +21,8 @@ export async function handleGetStatus(
         ? new Date(Number(syncStatus.last_sync.seconds) * 1000).toISOString()
         : '',
       totalSynced: Number(syncStatus.total_synced),
-      connectedExtensions: Array.from({ length: syncStatus.connected_extensions || 0 }).map((_, i) => `ext-${i + 1}`),
+      connectedExtensions: syncStatus.extension_ids
+        ?? Array.from({ length: syncStatus.connected_extensions || 0 }).map((_, i) => `ext-${i + 1}`),
       isRunning: syncStatus.is_running,
       connectionStatus: String(syncStatus.connection_status),
       errorMessage: syncStatus.error_message,
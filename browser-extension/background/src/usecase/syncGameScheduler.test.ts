@@ -1,8 +1,9 @@
 import type { SyncCoordinator } from '../shared/types'
-import type { NativeResponseTs } from '@launcherg/shared/typeshare/native-messaging'
+import type { NativeResponseTs, SyncSessionStatusTs } from '@launcherg/shared/typeshare/native-messaging'
+import { NativeErrorCodeTs, SyncStoreTs } from '@launcherg/shared/typeshare/native-messaging'
 import { describe, expect, it, vi } from 'vitest'
 import { buildTestContext } from '../../test/helpers/context'
-import { syncGame } from './syncGameScheduler'
+import { SYNC_CHUNK_SIZE, syncGame } from './syncGameScheduler'
 
 function createQueuedCoordinator(): SyncCoordinator {
   let tail = Promise.resolve<void>(undefined)
@@ -255,4 +256,94 @@ describe('ゲーム同期ユースケース（syncGameScheduler）', () => {
 
     expect(sendJson).toHaveBeenCalledTimes(2)
   })
+
+  it('上限を超える要求は同期セッションに分け、受信済みでないチャンクだけ送り直す', async () => {
+    const notificationsCreate = vi.fn(async () => {})
+    const sessionStatus = (received: number[], committed: boolean): SyncSessionStatusTs => ({
+      session_id: 'session-1',
+      store: SyncStoreTs.Dlsite,
+      total_chunks: 3,
+      received_chunks: received,
+      success_count: 0,
+      new_count: committed ? 5 : 0,
+      error_count: 0,
+      committed,
+    })
+    const sent: string[] = []
+    let begins = 0
+    let commits = 0
+    const sendJson = vi.fn(async (message: any): Promise<NativeResponseTs | null> => {
+      const { case: kind, value } = message.message
+      sent.push(kind === 'SyncDlsiteGamesChunk' ? `chunk:${value.chunk_index}` : kind)
+      const base = { success: true, error: '', request_id: message.request_id }
+      switch (kind) {
+        case 'BeginSyncSession':
+          begins += 1
+          return { ...base, response: { case: 'SyncSessionResult', value: sessionStatus(begins === 1 ? [] : [0, 2], false) } }
+        case 'SyncDlsiteGamesChunk':
+          // 1 回目のチャンク 1 はホストが落ちて応答がない
+          if (value.chunk_index === 1 && begins === 1)
+            return null
+          return { ...base, response: { case: 'SyncGamesResult', value: { success_count: value.games.length, new_count: 0, error_count: 0, errors: [], synced_games: [] } } }
+        case 'CommitSyncSession':
+          commits += 1
+          if (commits === 1)
+            return { ...base, success: false, error: 'missing', error_code: NativeErrorCodeTs.SyncSessionIncomplete }
+          return { ...base, response: { case: 'SyncSessionResult', value: sessionStatus([0, 1, 2], true) } }
+        default:
+          return null
+      }
+    })
+
+    const context = buildTestContext({
+      idGenerator: { generate: () => 'session-1' },
+      nativeMessenger: { sendJson },
+      browser: {
+        ...buildTestContext().browser,
+        notifications: { create: notificationsCreate },
+      },
+    })
+
+    await syncGame(context, {
+      type: 'dlsite',
+      games: Array.from({ length: SYNC_CHUNK_SIZE * 2 + 1 }, (_v, i) => ({ id: `RJ${i}`, category: 'maniax' })) as any,
+    })
+
+    expect(sent).toEqual([
+      'BeginSyncSession',
+      'chunk:0',
+      'chunk:1',
+      'chunk:2',
+      'CommitSyncSession',
+      'BeginSyncSession',
+      'chunk:1',
+      'CommitSyncSession',
+    ])
+    expect(sendJson.mock.calls[1][0].message.value.games).toHaveLength(SYNC_CHUNK_SIZE)
+    expect(notificationsCreate).toHaveBeenCalledTimes(1)
+  })
+
+  it('fullLibrary はライブラリ全体を送るときだけホストへ伝える', async () => {
+    const sendJson = vi.fn(async (_message: any) => ({
+      success: true,
+      error: '',
+      request_id: 'res-full',
+      response: { case: 'SyncGamesResult', value: { success_count: 1, new_count: 0, unchanged_count: 1, error_count: 0, errors: [], synced_games: [], missing_games: ['RJ9'] } },
+    } satisfies NativeResponseTs))
+
+    const context = buildTestContext({ nativeMessenger: { sendJson } })
+
+    await syncGame(context, {
+      type: 'dlsite',
+      games: [{ id: 'RJ1', category: 'maniax' }] as any,
+      fullLibrary: true,
+    })
+    await syncGame(context, {
+      type: 'dlsite',
+      games: [{ id: 'RJ1', category: 'maniax' }] as any,
+    })
+
+    expect(sendJson.mock.calls[0][0].message.value.full_library).toBe(true)
+    expect(sendJson.mock.calls[1][0].message.value.full_library).toBe(false)
+  })
 })
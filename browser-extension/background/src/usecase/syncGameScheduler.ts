@@ -1,12 +1,23 @@
 import type { DlsiteGame as ExtDlsiteGame, DmmGame as ExtDmmGame, EgsInfo as ExtEgsInfo } from '@launcherg/shared'
-import type { NativeResponseTs } from '@launcherg/shared/typeshare/native-messaging'
+import type { NativeResponseTs, SyncSessionStatusTs } from '@launcherg/shared/typeshare/native-messaging'
 import type { HandlerContext } from '../shared/types'
+import { NativeErrorCodeTs, SyncStoreTs } from '@launcherg/shared/typeshare/native-messaging'
 
+/**
+ * fullLibrary はライブラリ全体を送るときだけ true にする。
+ * ホストは含まれなかった紐付けに欠落の印を付けるので、ページ単位の取得では付けない
+ */
 export type SyncGameRequest
-  = | { type: 'dmm', games: ExtDmmGame[] }
-    | { type: 'dlsite', games: ExtDlsiteGame[] }
+  = | { type: 'dmm', games: ExtDmmGame[], fullLibrary?: boolean }
+    | { type: 'dlsite', games: ExtDlsiteGame[], fullLibrary?: boolean }
+
+/** 1 メッセージで送る作品数の上限。これを超えると同期セッションに分けて送る */
+export const SYNC_CHUNK_SIZE = 200
+/** 同期セッションで未受信のチャンクを送り直す回数 */
+const SYNC_SESSION_ATTEMPTS = 3
 
 export function syncGame(context: HandlerContext, request: SyncGameRequest): Promise<void> {
+  const fullLibrary = request.fullLibrary === true
   const toTypeshareEgsInfo = (egs: ExtEgsInfo | null) => {
     if (!egs)
       return undefined
@@ -31,6 +42,56 @@ export function syncGame(context: HandlerContext, request: SyncGameRequest): Pro
     return res as NativeResponseTs | null
   }
 
+  const newCountOf = (res: NativeResponseTs | null) =>
+    res && res.success && res.response?.case === 'SyncGamesResult' ? (res.response.value?.new_count ?? 0) : 0
+
+  const sessionStatusOf = (res: NativeResponseTs | null): SyncSessionStatusTs | null =>
+    res && res.success && res.response?.case === 'SyncSessionResult' ? res.response.value : null
+
+  // ホストはメッセージごとに起動し直されるが、受信済みチャンクは DB に残るので
+  // 同じ session_id で Begin し直せば足りないチャンクだけ送ればよい
+  const sendInChunks = async <T>(
+    store: SyncStoreTs,
+    games: T[],
+    buildChunk: (sessionId: string, chunkIndex: number, games: T[]) => any,
+  ): Promise<number> => {
+    const chunks: T[][] = []
+    for (let i = 0; i < games.length; i += SYNC_CHUNK_SIZE)
+      chunks.push(games.slice(i, i + SYNC_CHUNK_SIZE))
+    const sessionId = context.idGenerator.generate()
+
+    for (let attempt = 0; attempt < SYNC_SESSION_ATTEMPTS; attempt += 1) {
+      const begun = sessionStatusOf(await sendNative(buildMessage({
+        case: 'BeginSyncSession',
+        value: { session_id: sessionId, store, extension_id: context.extensionId, total_chunks: chunks.length },
+      })))
+      if (!begun)
+        return 0
+      if (begun.committed)
+        return begun.new_count
+
+      const received = new Set(begun.received_chunks)
+      for (const [index, chunk] of chunks.entries()) {
+        if (received.has(index))
+          continue
+        const res = await sendNative(buildMessage(buildChunk(sessionId, index, chunk)))
+        if (res?.error_code === NativeErrorCodeTs.SyncSessionNotFound)
+          return 0
+      }
+
+      const commitRes = await sendNative(buildMessage({
+        case: 'CommitSyncSession',
+        value: { session_id: sessionId, full_library: fullLibrary },
+      }))
+      const committed = sessionStatusOf(commitRes)
+      if (committed)
+        return committed.new_count
+      if (commitRes?.error_code !== NativeErrorCodeTs.SyncSessionIncomplete)
+        return 0
+    }
+    return 0
+  }
+
   const notifyIfNew = async (count: number) => {
     if (!count || count < 1)
       return
@@ -64,16 +125,22 @@ export function syncGame(context: HandlerContext, request: SyncGameRequest): Pro
           }
         : undefined,
     }))
+    if (nativeGames.length > SYNC_CHUNK_SIZE) {
+      await notifyIfNew(await sendInChunks(SyncStoreTs.Dmm, nativeGames, (sessionId, chunkIndex, chunk) => ({
+        case: 'SyncDmmGamesChunk',
+        value: { session_id: sessionId, chunk_index: chunkIndex, games: chunk },
+      })))
+      return
+    }
     const msg = buildMessage({
       case: 'SyncDmmGames',
       value: {
         games: nativeGames,
         extension_id: context.extensionId,
+        full_library: fullLibrary,
       },
     })
-    const res = await sendNative(msg)
-    const count = res && res.success && res.response?.case === 'SyncGamesResult' ? (res.response.value?.new_count ?? 0) : 0
-    await notifyIfNew(count)
+    await notifyIfNew(newCountOf(await sendNative(msg)))
   }
 
   const processDlsiteBatch = async (games: ExtDlsiteGame[]) => {
@@ -89,16 +156,22 @@ export function syncGame(context: HandlerContext, request: SyncGameRequest): Pro
       title: g.title,
       image_url: g.imageUrl,
     }))
+    if (nativeGames.length > SYNC_CHUNK_SIZE) {
+      await notifyIfNew(await sendInChunks(SyncStoreTs.Dlsite, nativeGames, (sessionId, chunkIndex, chunk) => ({
+        case: 'SyncDlsiteGamesChunk',
+        value: { session_id: sessionId, chunk_index: chunkIndex, games: chunk },
+      })))
+      return
+    }
     const msg = buildMessage({
       case: 'SyncDlsiteGames',
       value: {
         games: nativeGames,
         extension_id: context.extensionId,
+        full_library: fullLibrary,
       },
     })
-    const res = await sendNative(msg)
-    const count = res && res.success && res.response?.case === 'SyncGamesResult' ? (res.response.value?.new_count ?? 0) : 0
-    await notifyIfNew(count)
+    await notifyIfNew(newCountOf(await sendNative(msg)))
   }
 
   return context.syncCoordinator.runExclusive(async () => {
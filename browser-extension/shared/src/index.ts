@@ -4,6 +4,7 @@ export * from './download-intents'
 export * from './logger'
 export * from './models'
 export * from './native-bridge'
+export * from './native-protocol'
 export * from './notification'
 export * from './page-hook'
 export * from './utils'
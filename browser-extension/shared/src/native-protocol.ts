@@ -0,0 +1,3 @@
+// Native Messaging Host とのプロトコルバージョン。
+// ホストは一つ前のバージョンまで受け付けるため、上げるときはホスト側の protocol.rs と合わせること
+export const NATIVE_PROTOCOL_VERSION = 2
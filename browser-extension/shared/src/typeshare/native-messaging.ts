@@ -24,9 +24,18 @@ export interface DlsiteGameTs {
 	egs_info?: EgsInfoTs;
 }
 
+export interface DlsiteSyncChunkRequestTs {
+	session_id: string;
+	/** 0 始まり */
+	chunk_index: number;
+	games: DlsiteGameTs[];
+}
+
 export interface DlsiteSyncGamesRequestTs {
 	games: DlsiteGameTs[];
 	extension_id: string;
+	/** ライブラリ全体を送ったとき true。含まれなかった紐付けに欠落の印を付ける */
+	full_library?: boolean;
 }
 
 export interface DmmPackKeyTs {
@@ -45,9 +54,18 @@ export interface DmmGameTs {
 	parent_pack?: DmmPackKeyTs;
 }
 
+export interface DmmSyncChunkRequestTs {
+	session_id: string;
+	/** 0 始まり */
+	chunk_index: number;
+	games: DmmGameTs[];
+}
+
 export interface DmmSyncGamesRequestTs {
 	games: DmmGameTs[];
 	extension_id: string;
+	/** ライブラリ全体を送ったとき true。含まれなかった紐付けに欠落の印を付ける */
+	full_library?: boolean;
 }
 
 export interface DownloadCompletedTs {
@@ -90,11 +108,38 @@ export interface GetStatusRequestTs {
 }
 
 export interface HealthCheckRequestTs {
+	/** 拡張機能が使いたいメッセージケース。空ならホストが対応する全ケースを返す */
+	capabilities?: string[];
 }
 
 export interface HealthCheckResultTs {
 	message: string;
 	version: string;
+	/** ホストが受け付けるプロトコルバージョン */
+	supported_protocol_versions?: number[];
+	/** 要求されたうちホストが対応しているメッセージケース */
+	capabilities?: string[];
+}
+
+export enum NativeErrorCodeTs {
+	/** JSON として読めない */
+	InvalidJson = "InvalidJson",
+	/** 既知のケースだが内容がスキーマに合わない */
+	InvalidMessage = "InvalidMessage",
+	/** ホストが知らないメッセージケース */
+	UnknownMessageCase = "UnknownMessageCase",
+	/** ホストが対応していないプロトコルバージョン */
+	UnsupportedProtocolVersion = "UnsupportedProtocolVersion",
+	/** 1MB を超えるメッセージ */
+	MessageTooLarge = "MessageTooLarge",
+	/** リクエストは受理したが処理に失敗した */
+	HandlerFailed = "HandlerFailed",
+	/** 同期セッションが見つからない（期限切れを含む）。BeginSyncSession からやり直す */
+	SyncSessionNotFound = "SyncSessionNotFound",
+	/** 同期セッションの内容と合わない（ストアやチャンク数の違い、確定済みへの追加） */
+	SyncSessionConflict = "SyncSessionConflict",
+	/** 未受信のチャンクがあるため確定できない */
+	SyncSessionIncomplete = "SyncSessionIncomplete",
 }
 
 export type NativeMessageCase = 
@@ -103,9 +148,15 @@ export type NativeMessageCase =
 	| { case: "DownloadsCompleted", value: DownloadsCompletedRequestTs }
 	| { case: "GetStatus", value: GetStatusRequestTs }
 	| { case: "SetConfig", value: ExtensionConfigTs }
-	| { case: "HealthCheck", value: HealthCheckRequestTs };
+	| { case: "HealthCheck", value: HealthCheckRequestTs }
+	| { case: "BeginSyncSession", value: SyncSessionBeginRequestTs }
+	| { case: "SyncDmmGamesChunk", value: DmmSyncChunkRequestTs }
+	| { case: "SyncDlsiteGamesChunk", value: DlsiteSyncChunkRequestTs }
+	| { case: "CommitSyncSession", value: SyncSessionCommitRequestTs };
 
 export interface NativeMessageTs {
+	/** 省略時はバージョン導入前の v1 として扱う */
+	protocol_version?: number;
 	request_id: string;
 	message: NativeMessageCase;
 }
@@ -114,21 +165,66 @@ export type NativeResponseCase =
 	| { case: "SyncGamesResult", value: SyncBatchResultTs }
 	| { case: "StatusResult", value: SyncStatusTs }
 	| { case: "ConfigResult", value: ConfigUpdateResultTs }
-	| { case: "HealthCheckResult", value: HealthCheckResultTs };
+	| { case: "HealthCheckResult", value: HealthCheckResultTs }
+	| { case: "SyncSessionResult", value: SyncSessionStatusTs };
 
 export interface NativeResponseTs {
 	success: boolean;
 	error: string;
 	request_id: string;
 	response?: NativeResponseCase;
+	/** 失敗時の分類。v1 の応答には含めない */
+	error_code?: NativeErrorCodeTs;
+	/** 応答を組み立てたプロトコルバージョン。v1 の応答には含めない */
+	protocol_version?: number;
 }
 
 export interface SyncBatchResultTs {
 	success_count: number;
 	new_count: number;
+	/** 同期前から紐付いていた件数 */
+	unchanged_count?: number;
 	error_count: number;
 	errors: string[];
 	synced_games: string[];
+	/** full_library のとき、今回の同期に含まれず欠落の印を付けた store_id */
+	missing_games?: string[];
+}
+
+export enum SyncStoreTs {
+	Dmm = "Dmm",
+	Dlsite = "Dlsite",
+}
+
+/** 分割同期の開始。同じ session_id で送り直すと受信済みチャンクを返して再開する */
+export interface SyncSessionBeginRequestTs {
+	/** 拡張機能が採番する ID */
+	session_id: string;
+	store: SyncStoreTs;
+	extension_id: string;
+	total_chunks: number;
+}
+
+export interface SyncSessionCommitRequestTs {
+	session_id: string;
+	/** 全チャンクでライブラリ全体になるとき true。セッション開始以降に見えなかった紐付けに欠落の印を付ける */
+	full_library?: boolean;
+}
+
+/** BeginSyncSession / CommitSyncSession の応答 */
+export interface SyncSessionStatusTs {
+	session_id: string;
+	store: SyncStoreTs;
+	total_chunks: number;
+	/** 反映済みの chunk_index（昇順） */
+	received_chunks: number[];
+	success_count: number;
+	new_count: number;
+	unchanged_count?: number;
+	error_count: number;
+	committed: boolean;
+	/** full_library で確定したとき、欠落の印を付けた store_id */
+	missing_games?: string[];
 }
 
 export interface TimestampTs {
@@ -140,6 +236,8 @@ export interface SyncStatusTs {
 	last_sync?: TimestampTs;
 	total_synced: number;
 	connected_extensions: number;
+	/** 最近同期してきた拡張機能の ID。connected_extensions はこの件数 */
+	extension_ids?: string[];
 	is_running: boolean;
 	connection_status: string;
 	error_message: string;
@@ -4,14 +4,18 @@ import { createSessionCookie, requireSession } from '@server/lib/cookies'
 import { sha256Hex } from '@server/lib/crypto'
 import {
   clearDeviceSnapshots,
+  countDeviceSnapshots,
+  deleteDeviceSnapshots,
   findDeviceById,
   findDeviceBySecretHash,
   insertDevice,
   insertRemoteShareImage,
+  isSyncBaseCurrent,
   listDeviceSnapshots,
   listRemoteShareImagesByDedupeKeys,
   remoteShareDedupeKeyForWork,
   remoteShareImageKeyForDedupeKey,
+  snapshotWorkIdsToRemove,
   toPublicWorkItem,
   upsertDeviceSnapshots,
 } from '@server/lib/db'
@@ -141,6 +145,10 @@ async function handlePrepareSyncWorks(
     unauthorized('deviceSecret is invalid')
   }
 
+  if (!isSyncBaseCurrent(body.mode, device.lastSyncedAt, body.baseSyncedAt)) {
+    return resyncRequired('baseSyncedAt does not match the last sync')
+  }
+
   const dedupeKeys = body.works
     .filter(work => Boolean(work.thumbnail))
     .map(work => remoteShareDedupeKeyForWork(work))
@@ -196,6 +204,10 @@ async function handleCommitSyncWorks(
     unauthorized('deviceSecret is invalid')
   }
 
+  if (!isSyncBaseCurrent(body.mode, device.lastSyncedAt, body.baseSyncedAt)) {
+    return resyncRequired('baseSyncedAt does not match the last sync')
+  }
+
   const uploadedMap = new Map<string, string>()
   for (const uploadedImage of body.uploadedImages) {
     uploadedMap.set(uploadedImage.dedupeKey, uploadedImage.imageKey)
@@ -241,15 +253,32 @@ async function handleCommitSyncWorks(
       existing.thumbnailHeight !== nextHeight
   })
 
+  const removedWorkIds = snapshotWorkIdsToRemove(
+    body.mode,
+    existingRows.map(row => row.workId),
+    body.works,
+    body.removedWorkIds,
+  )
+  const removedCount = await deleteDeviceSnapshots(env.DB, deviceId, removedWorkIds)
   await upsertDeviceSnapshots(env.DB, deviceId, worksToUpsert, imageKeys, syncedAt)
 
+  // 端末が持っている件数と合わなければ、差分の取りこぼしがあるので全件を送り直してもらう
+  const remainingCount = await countDeviceSnapshots(env.DB, deviceId)
+  const needsResync = body.totalCount !== undefined && remainingCount !== body.totalCount
+
   return Response.json({
     deviceId,
     syncedCount: body.works.length,
+    removedCount,
     lastSyncedAt: syncedAt,
+    resyncRequired: needsResync,
   })
 }
 
+function resyncRequired(message: string): Response {
+  return Response.json({ code: 'resync_required', message }, { status: 409 })
+}
+
 async function handleImageRequest(request: Request, env: Env, url: URL): Promise<Response> {
   const parts = url.pathname.split('/')
   const deviceId = parts[3]
@@ -1,6 +1,8 @@
 import {
+  isSyncBaseCurrent,
   remoteShareDedupeKeyForWork,
   remoteShareImageKeyForDedupeKey,
+  snapshotWorkIdsToRemove,
   toPublicWorkItem,
 } from '@server/lib/db'
 
@@ -56,3 +58,28 @@ describe('remoteShare helpers', () => {
     expect(remoteShareImageKeyForDedupeKey('egs:123')).toBe('remote-share/egs%3A123/thumbnail')
   })
 })
+
+describe('差分同期', () => {
+  const work = (workId: string) => ({ workId, title: workId })
+
+  it('full では送られなかった作品を消す', () => {
+    expect(
+      snapshotWorkIdsToRemove('full', ['w1', 'w2', 'w3'], [work('w1'), work('w3')], []),
+    ).toEqual(['w2'])
+  })
+
+  it('delta では墓標のうち残っている作品だけを消す', () => {
+    expect(
+      snapshotWorkIdsToRemove('delta', ['w1', 'w2', 'w3'], [work('w3')], ['w2', 'w3', 'missing']),
+    ).toEqual(['w2'])
+  })
+
+  it('delta は前回の同期時刻が一致しなければ反映しない', () => {
+    const lastSyncedAt = '2026-01-01T00:00:00.000Z'
+    expect(isSyncBaseCurrent('delta', lastSyncedAt, lastSyncedAt)).toBe(true)
+    expect(isSyncBaseCurrent('delta', lastSyncedAt, '2025-12-31T00:00:00.000Z')).toBe(false)
+    expect(isSyncBaseCurrent('delta', lastSyncedAt, undefined)).toBe(false)
+    expect(isSyncBaseCurrent('delta', null, lastSyncedAt)).toBe(false)
+    expect(isSyncBaseCurrent('full', null, undefined)).toBe(true)
+  })
+})
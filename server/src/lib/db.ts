@@ -1,5 +1,6 @@
 import type {
   DeviceWorksListItem,
+  RemoteShareSyncMode,
   RemoteShareWorkInput,
 } from '@server/shared/schema'
 
@@ -108,6 +109,76 @@ export async function clearDeviceSnapshots(
     .run()
 }
 
+export async function deleteDeviceSnapshots(
+  db: D1Database,
+  deviceId: string,
+  workIds: string[],
+): Promise<number> {
+  let deleted = 0
+  const uniqueIds = Array.from(new Set(workIds))
+
+  for (let index = 0; index < uniqueIds.length; index += 99) {
+    const chunk = uniqueIds.slice(index, index + 99)
+    const placeholders = chunk.map((_, itemIndex) => `?${itemIndex + 2}`).join(', ')
+    const result = await db
+      .prepare(
+        `DELETE FROM device_work_snapshots
+         WHERE device_id = ?1 AND work_id IN (${placeholders})`,
+      )
+      .bind(deviceId, ...chunk)
+      .run()
+    deleted += result.meta.changes ?? 0
+  }
+
+  return deleted
+}
+
+export async function countDeviceSnapshots(
+  db: D1Database,
+  deviceId: string,
+): Promise<number> {
+  const row = await db
+    .prepare('SELECT COUNT(*) AS count FROM device_work_snapshots WHERE device_id = ?1')
+    .bind(deviceId)
+    .first<{ count: number }>()
+
+  return row?.count ?? 0
+}
+
+/**
+ * 同期で消す作品。full では送られなかった作品、delta では墓標として送られた作品
+ */
+export function snapshotWorkIdsToRemove(
+  mode: RemoteShareSyncMode,
+  existingWorkIds: string[],
+  works: RemoteShareWorkInput[],
+  removedWorkIds: string[],
+): string[] {
+  const sent = new Set(works.map(work => work.workId))
+  if (mode === 'full') {
+    return existingWorkIds.filter(workId => !sent.has(workId))
+  }
+
+  // 同じ同期で送り直された作品は消さない
+  const existing = new Set(existingWorkIds)
+  return removedWorkIds.filter(workId => existing.has(workId) && !sent.has(workId))
+}
+
+/**
+ * delta は端末が前回受け取った lastSyncedAt を起点にしていなければ反映できない
+ */
+export function isSyncBaseCurrent(
+  mode: RemoteShareSyncMode,
+  lastSyncedAt: string | null,
+  baseSyncedAt: string | null | undefined,
+): boolean {
+  if (mode === 'full') {
+    return true
+  }
+
+  return lastSyncedAt !== null && baseSyncedAt === lastSyncedAt
+}
+
 export async function upsertDeviceSnapshots(
   db: D1Database,
   deviceId: string,
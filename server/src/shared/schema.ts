@@ -42,8 +42,13 @@ export const deviceSessionInputSchema = z.object({
   deviceSecret: deviceSecretSchema,
 })
 
+// full: works が端末の全作品。delta: 前回の同期（baseSyncedAt）からの差分だけ
+export const remoteShareSyncModeSchema = z.enum(['full', 'delta'])
+
 export const deviceWorksSyncPrepareInputSchema = z.object({
   deviceSecret: deviceSecretSchema,
+  mode: remoteShareSyncModeSchema.default('full'),
+  baseSyncedAt: z.string().nullable().optional(),
   works: z.array(remoteShareWorkInputSchema),
 })
 
@@ -73,7 +78,13 @@ export const remoteShareUploadedImageSchema = z.object({
 
 export const deviceWorksSyncCommitInputSchema = z.object({
   deviceSecret: deviceSecretSchema,
+  mode: remoteShareSyncModeSchema.default('full'),
+  baseSyncedAt: z.string().nullable().optional(),
   works: z.array(remoteShareWorkInputSchema),
+  // 端末から消えた作品（墓標）。delta のときだけ使う
+  removedWorkIds: z.array(z.string().trim().min(1)).default([]),
+  // 反映後に残るはずの作品数。合わなければ resyncRequired を返す
+  totalCount: z.number().int().nonnegative().optional(),
   uploadedImages: z.array(remoteShareUploadedImageSchema),
 })
 
@@ -91,7 +102,9 @@ export const deviceWorksListOutputSchema = z.object({
 export const deviceWorksSyncCommitOutputSchema = z.object({
   deviceId: deviceIdSchema,
   syncedCount: z.number().int().nonnegative(),
+  removedCount: z.number().int().nonnegative(),
   lastSyncedAt: z.string(),
+  resyncRequired: z.boolean(),
 })
 
 export type DeviceRegisterInput = z.infer<typeof deviceRegisterInputSchema>
@@ -104,5 +117,6 @@ export type DeviceWorksSyncCommitOutput = z.infer<typeof deviceWorksSyncCommitOu
 export type DeviceWorksListItem = z.infer<typeof deviceWorksListItemSchema>
 export type DeviceWorksListOutput = z.infer<typeof deviceWorksListOutputSchema>
 export type RemoteShareWorkInput = z.infer<typeof remoteShareWorkInputSchema>
+export type RemoteShareSyncMode = z.infer<typeof remoteShareSyncModeSchema>
 export type RemoteShareUploadTarget = z.infer<typeof remoteShareUploadTargetSchema>
 export type RemoteShareUploadedImage = z.infer<typeof remoteShareUploadedImageSchema>
@@ -1,7 +1,7 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use domain::all_game_cache::{AllGameCache, AllGameCacheOne};
 use domain::game_matcher::matcher::{Matcher, MatcherConfig};
-use domain::game_matcher::GameMatcher;
+use domain::game_matcher::{content_hash, GameMatcher, MatcherIndex};
 use rand::prelude::*;
 use std::time::Duration;
 
@@ -118,5 +118,34 @@ fn bench_find_candidates(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_find_candidates);
+fn bench_index_startup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matcher_index_startup");
+    group
+        .sample_size(10)
+        .measurement_time(Duration::from_secs(10));
+
+    // 起動時のインデックス用意: 毎回構築する場合と、永続化済みを読み込む場合の比較
+    let name_len = 20;
+    let pool = japanese_char_pool();
+    for &num in &[40_000usize, 120_000] {
+        let cache = build_cache(num, name_len, &pool);
+        let encoded = MatcherIndex::build(&cache).encode();
+
+        group.throughput(Throughput::Elements(num as u64));
+        group.bench_with_input(BenchmarkId::new("build", num), &num, |b, &_n| {
+            b.iter(|| MatcherIndex::build(&cache))
+        });
+        group.bench_with_input(BenchmarkId::new("decode", num), &num, |b, &_n| {
+            b.iter(|| MatcherIndex::decode(&encoded).unwrap())
+        });
+        // キャッシュ更新時の鮮度判定（変更がなければ再構築しない）
+        group.bench_with_input(BenchmarkId::new("content_hash", num), &num, |b, &_n| {
+            b.iter(|| content_hash(&cache))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_find_candidates, bench_index_startup);
 criterion_main!(benches);
@@ -1,4 +1,4 @@
-#[derive(derive_new::new, Debug, Clone)]
+#[derive(derive_new::new, Debug, Clone, PartialEq)]
 pub struct AllGameCacheOne {
     pub id: i32,
     pub gamename: String,
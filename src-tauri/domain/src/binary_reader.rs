@@ -0,0 +1,70 @@
+//! ショートカットや実行ファイルなど、リトルエンディアンのバイナリを位置を指定して読む
+
+/// 範囲外を読んだら壊れたファイルとして扱うための薄いラッパー
+#[derive(Clone, Copy)]
+pub struct Reader<'a>(pub &'a [u8]);
+
+impl<'a> Reader<'a> {
+    pub fn slice(&self, offset: usize, len: usize) -> anyhow::Result<&'a [u8]> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.0.get(offset..end))
+            .ok_or_else(|| anyhow::anyhow!("unexpected end of data at {offset:#x}"))
+    }
+
+    /// `offset` から終わりまで。範囲外なら空
+    pub fn rest(&self, offset: usize) -> &'a [u8] {
+        self.0.get(offset..).unwrap_or_default()
+    }
+
+    pub fn u8(&self, offset: usize) -> anyhow::Result<u8> {
+        Ok(self.slice(offset, 1)?[0])
+    }
+
+    pub fn u16(&self, offset: usize) -> anyhow::Result<u16> {
+        let b = self.slice(offset, 2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    pub fn u32(&self, offset: usize) -> anyhow::Result<u32> {
+        let b = self.slice(offset, 4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// UTF-16 の `count` 文字分
+    pub fn utf16(&self, offset: usize, count: usize) -> anyhow::Result<String> {
+        let units: Vec<u16> = self
+            .slice(offset, count * 2)?
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        Ok(String::from_utf16_lossy(&units))
+    }
+
+    /// NUL で終わる UTF-16 の文字列
+    pub fn utf16z(&self, offset: usize) -> anyhow::Result<String> {
+        let count = self
+            .rest(offset)
+            .chunks_exact(2)
+            .position(|c| c == [0, 0])
+            .ok_or_else(|| anyhow::anyhow!("unterminated string at {offset:#x}"))?;
+        self.utf16(offset, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reader_reads_little_endian_and_strings() {
+        let bytes = [0x34, 0x12, b'a', 0, 0x42, 0x30, 0, 0];
+        let r = Reader(&bytes);
+
+        assert_eq!(r.u16(0).unwrap(), 0x1234);
+        assert_eq!(r.utf16z(2).unwrap(), "aあ");
+        assert!(r.u32(6).is_err());
+        assert!(r.slice(usize::MAX, 2).is_err());
+        assert!(r.rest(100).is_empty());
+    }
+}
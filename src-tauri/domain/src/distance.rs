@@ -120,27 +120,42 @@ pub fn get_comparable_distance(a: &str, b: &str) -> f32 {
     1.0 - (distance_value as f32 / a.len().max(b.len()) as f32)
 }
 
-/// 閾値を用いた上限付きスコア。到達不可能なら None。
-pub fn get_comparable_distance_bounded(a: &str, b: &str, min_similarity: f32) -> Option<f32> {
-    let max_len = a.len().max(b.len());
-    if max_len == 0 {
-        return Some(1.0);
-    }
-
-    // s = 1 - d/max_len > min_similarity を満たす整数 d の最大値
+/// `min_similarity` を超えるスコアを得るために許容される最大編集距離。
+/// s = 1 - d/max_len > min_similarity を満たす整数 d の最大値を返す。
+pub fn max_distance_for_similarity(max_len: usize, min_similarity: f32) -> usize {
     let raw = (1.0 - min_similarity) * (max_len as f32);
     let mut d_max = raw.floor() as isize;
     if (raw - (d_max as f32)).abs() < 1e-6 {
         d_max -= 1;
     }
     if d_max < 0 {
-        d_max = 0;
+        0
+    } else {
+        d_max as usize
+    }
+}
+
+/// 閾値を用いた上限付きスコア。到達不可能なら None。
+pub fn get_comparable_distance_bounded(a: &str, b: &str, min_similarity: f32) -> Option<f32> {
+    get_distance_and_score_bounded(a, b, min_similarity).map(|(_, score)| score)
+}
+
+/// `get_comparable_distance_bounded` と同じ判定で、編集距離とスコアの組を返す。
+pub fn get_distance_and_score_bounded(
+    a: &str,
+    b: &str,
+    min_similarity: f32,
+) -> Option<(usize, f32)> {
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return Some((0, 1.0));
     }
 
+    let d_max = max_distance_for_similarity(max_len, min_similarity);
     let distance = Distance::new(a, b);
-    let d = distance.onp_bounded(d_max as usize)?;
+    let d = distance.onp_bounded(d_max)?;
     let score = 1.0 - (d as f32 / max_len as f32);
-    Some(score)
+    Some((d, score))
 }
 
 #[cfg(test)]
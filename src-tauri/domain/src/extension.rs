@@ -18,7 +18,7 @@ pub struct SyncStatus {
 }
 
 /// 拡張機能の設定
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExtensionConfig {
     /// 自動同期の有効・無効
     pub auto_sync: bool,
@@ -30,6 +30,60 @@ pub struct ExtensionConfig {
     pub debug_mode: bool,
 }
 
+impl Default for ExtensionConfig {
+    fn default() -> Self {
+        Self {
+            auto_sync: true,
+            allowed_domains: vec!["dlsoft.dmm.co.jp".into(), "www.dlsite.com".into()],
+            sync_interval_minutes: 30,
+            debug_mode: false,
+        }
+    }
+}
+
+impl ExtensionConfig {
+    /// 同期間隔の下限（分）
+    pub const MIN_SYNC_INTERVAL_MINUTES: u32 = 1;
+
+    /// 保存前の正規化。ドメインは前後の空白と末尾のドットを落として小文字にし、空要素と重複を除く
+    pub fn normalized(&self) -> anyhow::Result<ExtensionConfig> {
+        if self.sync_interval_minutes < Self::MIN_SYNC_INTERVAL_MINUTES {
+            anyhow::bail!(
+                "sync_interval_minutes must be at least {}",
+                Self::MIN_SYNC_INTERVAL_MINUTES
+            );
+        }
+        let mut allowed_domains: Vec<String> = Vec::new();
+        for domain in self.allowed_domains.iter() {
+            let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+            if domain.is_empty() {
+                continue;
+            }
+            if domain.contains(char::is_whitespace) || domain.contains('/') {
+                anyhow::bail!("invalid domain: {}", domain);
+            }
+            if !allowed_domains.contains(&domain) {
+                allowed_domains.push(domain);
+            }
+        }
+        Ok(ExtensionConfig {
+            allowed_domains,
+            ..self.clone()
+        })
+    }
+}
+
+/// Native Messaging Host が集計した同期の統計
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NativeHostSyncStats {
+    /// 最後に同期が完了した時刻
+    pub last_sync_at: Option<chrono::DateTime<chrono::Local>>,
+    /// 登録済みの DMM / DLsite 作品数の合計
+    pub total_synced: u32,
+    /// 最近同期してきた拡張機能の ID
+    pub extension_ids: Vec<String>,
+}
+
 /// 拡張機能の接続ステータス
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(i32)]
@@ -80,3 +134,36 @@ pub trait NativeMessagingHostClientFactory {
     /// クライアントを生成する
     fn create(&self) -> Result<Self::Client, Box<dyn std::error::Error + Send + Sync>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(domains: &[&str], interval: u32) -> ExtensionConfig {
+        ExtensionConfig {
+            auto_sync: true,
+            allowed_domains: domains.iter().map(|d| d.to_string()).collect(),
+            sync_interval_minutes: interval,
+            debug_mode: false,
+        }
+    }
+
+    #[test]
+    fn test_normalized_trims_and_dedups_domains() {
+        let normalized = config(&[" DLsite.com ", "", "dlsite.com.", "games.dmm.co.jp"], 30)
+            .normalized()
+            .unwrap();
+        assert_eq!(
+            normalized.allowed_domains,
+            vec!["dlsite.com".to_string(), "games.dmm.co.jp".to_string()]
+        );
+        assert_eq!(normalized.sync_interval_minutes, 30);
+    }
+
+    #[test]
+    fn test_normalized_rejects_invalid_values() {
+        assert!(config(&["dlsite.com"], 0).normalized().is_err());
+        assert!(config(&["https://dlsite.com/"], 10).normalized().is_err());
+        assert!(config(&["a b"], 10).normalized().is_err());
+    }
+}
@@ -0,0 +1,385 @@
+//! インストール先のファイル構成からゲームエンジンを見分ける
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameEngine {
+    Kirikiri,
+    SiglusEngine,
+    Bgi,
+    CatSystem2,
+    Artemis,
+    #[serde(rename = "renpy")]
+    RenPy,
+    RpgMakerMv,
+    RpgMakerMz,
+    /// RPG ツクール XP / VX / VX Ace
+    Rgss,
+    Unity,
+    TyranoScript,
+    NScripter,
+    Yuris,
+    LiveMaker,
+    Qlie,
+}
+
+impl GameEngine {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameEngine::Kirikiri => "kirikiri",
+            GameEngine::SiglusEngine => "siglus_engine",
+            GameEngine::Bgi => "bgi",
+            GameEngine::CatSystem2 => "cat_system2",
+            GameEngine::Artemis => "artemis",
+            GameEngine::RenPy => "renpy",
+            GameEngine::RpgMakerMv => "rpg_maker_mv",
+            GameEngine::RpgMakerMz => "rpg_maker_mz",
+            GameEngine::Rgss => "rgss",
+            GameEngine::Unity => "unity",
+            GameEngine::TyranoScript => "tyrano_script",
+            GameEngine::NScripter => "n_scripter",
+            GameEngine::Yuris => "yuris",
+            GameEngine::LiveMaker => "live_maker",
+            GameEngine::Qlie => "qlie",
+        }
+    }
+
+    /// エンジンに同梱される、起動対象ではない実行ファイル名（小文字）
+    pub fn helper_exe_names(&self) -> &'static [&'static str] {
+        match self {
+            GameEngine::Unity => &["unitycrashhandler32.exe", "unitycrashhandler64.exe"],
+            GameEngine::RenPy => &["python.exe", "pythonw.exe", "zsync.exe", "zsyncmake.exe"],
+            GameEngine::RpgMakerMv | GameEngine::RpgMakerMz | GameEngine::TyranoScript => {
+                &["nwjc.exe", "notification_helper.exe"]
+            }
+            GameEngine::CatSystem2 => &["cs2conf.exe"],
+            _ => &[],
+        }
+    }
+}
+
+impl std::str::FromStr for GameEngine {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ALL_ENGINES
+            .iter()
+            .copied()
+            .find(|engine| engine.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("unknown game engine: {s}"))
+    }
+}
+
+const ALL_ENGINES: [GameEngine; 15] = [
+    GameEngine::Kirikiri,
+    GameEngine::SiglusEngine,
+    GameEngine::Bgi,
+    GameEngine::CatSystem2,
+    GameEngine::Artemis,
+    GameEngine::RenPy,
+    GameEngine::RpgMakerMv,
+    GameEngine::RpgMakerMz,
+    GameEngine::Rgss,
+    GameEngine::Unity,
+    GameEngine::TyranoScript,
+    GameEngine::NScripter,
+    GameEngine::Yuris,
+    GameEngine::LiveMaker,
+    GameEngine::Qlie,
+];
+
+/// エンジンを見分けた結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedEngine {
+    pub engine: GameEngine,
+    /// 目印が見つかったゲーム本体のフォルダ。インストール先からの相対パスで、直下なら空文字
+    pub base_dir: String,
+    /// `base_dir` 直下にあるはずの起動 exe のファイル名（拡張子なし、小文字）
+    pub main_exe_stems: Vec<String>,
+}
+
+enum Pattern {
+    /// ファイル名が一致する。本体はそのファイルのフォルダ
+    File(&'static str),
+    /// 拡張子が一致する。本体はそのファイルのフォルダ
+    Extension(&'static str),
+    /// 指定した名前のフォルダの中に拡張子が一致するファイルがある。本体はそのフォルダの親
+    InDir(&'static str, &'static str),
+    /// 指定した名前のフォルダがある。本体はそのフォルダの親
+    Dir(&'static str),
+    /// パスの末尾が一致する。本体は一致した部分を除いたフォルダ
+    PathSuffix(&'static str),
+}
+
+/// 他のエンジンと取り違えにくいものから順に並べる（Ren'Py や Unity の中に .int や .pfs が紛れることがある）
+const SIGNATURES: [(GameEngine, Pattern); 24] = [
+    (GameEngine::Unity, Pattern::File("unityplayer.dll")),
+    (GameEngine::RenPy, Pattern::Dir("renpy")),
+    (GameEngine::RenPy, Pattern::InDir("game", "rpa")),
+    (GameEngine::RenPy, Pattern::InDir("game", "rpyc")),
+    (
+        GameEngine::RpgMakerMz,
+        Pattern::PathSuffix("js/rmmz_core.js"),
+    ),
+    (
+        GameEngine::RpgMakerMv,
+        Pattern::PathSuffix("www/js/rpg_core.js"),
+    ),
+    (
+        GameEngine::RpgMakerMv,
+        Pattern::PathSuffix("js/rpg_core.js"),
+    ),
+    (
+        GameEngine::TyranoScript,
+        Pattern::PathSuffix("tyrano/tyrano.js"),
+    ),
+    (GameEngine::Rgss, Pattern::Extension("rgss3a")),
+    (GameEngine::Rgss, Pattern::Extension("rgss2a")),
+    (GameEngine::Rgss, Pattern::Extension("rgssad")),
+    (GameEngine::SiglusEngine, Pattern::File("scene.pck")),
+    (GameEngine::SiglusEngine, Pattern::File("gameexe.dat")),
+    (GameEngine::Kirikiri, Pattern::Extension("xp3")),
+    (GameEngine::CatSystem2, Pattern::Extension("int")),
+    (GameEngine::Artemis, Pattern::Extension("pfs")),
+    (GameEngine::Bgi, Pattern::File("sysprg.arc")),
+    (GameEngine::Bgi, Pattern::File("bgi.gdb")),
+    (GameEngine::NScripter, Pattern::File("nscript.dat")),
+    (GameEngine::Yuris, Pattern::InDir("pac", "ypf")),
+    (GameEngine::NScripter, Pattern::Extension("nsa")),
+    (GameEngine::Yuris, Pattern::Extension("ypf")),
+    (GameEngine::LiveMaker, Pattern::File("live.dll")),
+    (GameEngine::Qlie, Pattern::InDir("gamedata", "pack")),
+];
+
+/// インストール先以下のファイル一覧からエンジンを見分ける
+///
+/// `files` はインストール先からの相対パスで、区切りは `/`、小文字にしておく。
+/// 同じエンジンの目印が複数あれば一番浅いフォルダを本体とみなす。
+pub fn detect_engine(files: &[String]) -> Option<DetectedEngine> {
+    SIGNATURES.iter().find_map(|(engine, pattern)| {
+        let base_dir = files
+            .iter()
+            .filter_map(|file| match_pattern(pattern, file))
+            .min_by_key(|dir| (dir.matches('/').count(), dir.len()))?;
+        Some(DetectedEngine {
+            engine: *engine,
+            main_exe_stems: main_exe_stems(*engine, &base_dir, files),
+            base_dir,
+        })
+    })
+}
+
+fn match_pattern(pattern: &Pattern, file: &str) -> Option<String> {
+    let (dir, name) = match file.rfind('/') {
+        Some(i) => (&file[..i], &file[i + 1..]),
+        None => ("", file),
+    };
+    let has_extension = |ext: &str| {
+        name.rsplit_once('.')
+            .is_some_and(|(stem, e)| !stem.is_empty() && e == ext)
+    };
+    match pattern {
+        Pattern::File(expected) => (name == *expected).then(|| dir.to_string()),
+        Pattern::Extension(ext) => has_extension(ext).then(|| dir.to_string()),
+        Pattern::InDir(dir_name, ext) => {
+            let (parent, last) = split_last(dir);
+            (last == *dir_name && has_extension(ext)).then(|| parent.to_string())
+        }
+        Pattern::Dir(dir_name) => {
+            let mut base = String::new();
+            for component in dir.split('/') {
+                if component == *dir_name {
+                    return Some(base);
+                }
+                if !base.is_empty() {
+                    base.push('/');
+                }
+                base.push_str(component);
+            }
+            None
+        }
+        Pattern::PathSuffix(suffix) => {
+            if file == *suffix {
+                Some(String::new())
+            } else {
+                file.strip_suffix(suffix)
+                    .and_then(|rest| rest.strip_suffix('/'))
+                    .map(|rest| rest.to_string())
+            }
+        }
+    }
+}
+
+fn split_last(dir: &str) -> (&str, &str) {
+    match dir.rfind('/') {
+        Some(i) => (&dir[..i], &dir[i + 1..]),
+        None => ("", dir),
+    }
+}
+
+/// 本体フォルダ直下の名前。直下でなければ None
+fn child_of<'a>(base_dir: &str, file: &'a str) -> Option<&'a str> {
+    if base_dir.is_empty() {
+        Some(file)
+    } else {
+        file.strip_prefix(base_dir)?.strip_prefix('/')
+    }
+}
+
+fn main_exe_stems(engine: GameEngine, base_dir: &str, files: &[String]) -> Vec<String> {
+    let mut stems: Vec<String> = match engine {
+        // Unity は `<exe名>_Data`、Ren'Py は `<exe名>.py` が exe の隣に置かれる
+        GameEngine::Unity | GameEngine::RenPy => files
+            .iter()
+            .filter_map(|file| child_of(base_dir, file))
+            .filter_map(|rest| match engine {
+                GameEngine::Unity => rest.split_once('/')?.0.strip_suffix("_data"),
+                _ => rest.strip_suffix(".py").filter(|stem| !stem.contains('/')),
+            })
+            .map(|stem| stem.to_string())
+            .collect(),
+        GameEngine::RpgMakerMv | GameEngine::RpgMakerMz | GameEngine::Rgss => {
+            vec!["game".to_string()]
+        }
+        GameEngine::SiglusEngine => vec!["siglusengine".to_string()],
+        _ => vec![],
+    };
+    stems.sort();
+    stems.dedup();
+    stems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn detect_engine_目印のファイルからエンジンと本体フォルダを見分ける() {
+        let cases = [
+            (
+                vec!["game.exe", "data.xp3", "patch.xp3"],
+                GameEngine::Kirikiri,
+                "",
+            ),
+            (
+                vec![
+                    "start.exe",
+                    "startdata/gamedata/siglusengine.exe",
+                    "startdata/gamedata/scene.pck",
+                ],
+                GameEngine::SiglusEngine,
+                "startdata/gamedata",
+            ),
+            (
+                vec!["bgi.exe", "sysprg.arc", "data01000.arc"],
+                GameEngine::Bgi,
+                "",
+            ),
+            (
+                vec!["cs2.exe", "scene.int", "image.int"],
+                GameEngine::CatSystem2,
+                "",
+            ),
+            (
+                vec!["game/artemis.exe", "game/root.pfs"],
+                GameEngine::Artemis,
+                "game",
+            ),
+            (
+                vec![
+                    "sakura.exe",
+                    "sakura.py",
+                    "renpy/common/00start.rpyc",
+                    "game/archive.rpa",
+                ],
+                GameEngine::RenPy,
+                "",
+            ),
+            (
+                vec!["game.exe", "www/js/rpg_core.js"],
+                GameEngine::RpgMakerMv,
+                "",
+            ),
+            (
+                vec!["game.exe", "js/rmmz_core.js", "js/rpg_core.js"],
+                GameEngine::RpgMakerMz,
+                "",
+            ),
+            (vec!["game.exe", "game.rgss3a"], GameEngine::Rgss, ""),
+            (
+                vec![
+                    "cyanbrain.exe",
+                    "unityplayer.dll",
+                    "cyanbrain_data/resources.assets",
+                ],
+                GameEngine::Unity,
+                "",
+            ),
+            (
+                vec!["tyrano/tyrano.js", "data/scenario/first.ks"],
+                GameEngine::TyranoScript,
+                "",
+            ),
+            (vec!["nscr.exe", "arc.nsa"], GameEngine::NScripter, ""),
+            (vec!["yu-ris.exe", "pac/bn.ypf"], GameEngine::Yuris, ""),
+            (vec!["game.exe", "live.dll"], GameEngine::LiveMaker, ""),
+            (
+                vec!["game.exe", "gamedata/data0.pack"],
+                GameEngine::Qlie,
+                "",
+            ),
+        ];
+
+        for (paths, engine, base_dir) in cases {
+            let detected = detect_engine(&files(&paths)).expect("engine");
+            assert_eq!(detected.engine, engine, "{paths:?}");
+            assert_eq!(detected.base_dir, base_dir, "{paths:?}");
+        }
+    }
+
+    #[test]
+    fn detect_engine_目印がなければnone() {
+        assert_eq!(
+            detect_engine(&files(&["game.exe", "readme.txt", "data/bgm.ogg"])),
+            None
+        );
+    }
+
+    #[test]
+    fn detect_engine_unityとrenpyは起動exeの名前もわかる() {
+        let unity = detect_engine(&files(&[
+            "cyanbrain.exe",
+            "unitycrashhandler64.exe",
+            "unityplayer.dll",
+            "cyanbrain_data/globalgamemanagers",
+            "monobleedingedge/etc/mono/config",
+        ]))
+        .unwrap();
+        assert_eq!(unity.main_exe_stems, vec!["cyanbrain".to_string()]);
+
+        let renpy = detect_engine(&files(&[
+            "sakura-1.0-pc/sakura.exe",
+            "sakura-1.0-pc/sakura.py",
+            "sakura-1.0-pc/renpy/bootstrap.py",
+            "sakura-1.0-pc/lib/py3-windows-x86_64/python.exe",
+        ]))
+        .unwrap();
+        assert_eq!(renpy.base_dir, "sakura-1.0-pc");
+        assert_eq!(renpy.main_exe_stems, vec!["sakura".to_string()]);
+    }
+
+    #[test]
+    fn from_str_as_strと往復できる() {
+        for engine in ALL_ENGINES {
+            assert_eq!(engine.as_str().parse::<GameEngine>().unwrap(), engine);
+            assert_eq!(
+                serde_json::to_string(&engine).unwrap(),
+                format!("\"{}\"", engine.as_str())
+            );
+        }
+    }
+}
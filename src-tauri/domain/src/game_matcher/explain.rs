@@ -0,0 +1,104 @@
+use derive_new::new;
+
+use crate::all_game_cache::AllGameCacheOne;
+
+/// クエリごとに保持する枝刈り候補の最大件数
+pub const MAX_PRUNED_PER_QUERY: usize = 20;
+
+/// マッチングに使うクエリ文字列の出どころ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuerySource {
+    /// 親ディレクトリ名
+    ParentDir,
+    /// ファイル名（拡張子なし）
+    Filename,
+    /// 名前検索など、パス由来でないもの
+    Other,
+}
+
+/// 出どころ付きのクエリ
+#[derive(new, Debug, Clone, PartialEq, Eq)]
+pub struct MatchQuery {
+    pub source: QuerySource,
+    pub value: String,
+}
+
+/// 候補を採用したルール
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchRule {
+    /// `MatcherConfig::exact_mappings` による固定対応
+    ExactMapping,
+    /// 正規化キーの完全一致
+    NormalizedIndex,
+    /// 片方がもう片方を含む（距離計算で閾値を超えたもの）
+    Partial,
+    /// n-gram フィルタ + ONP 距離による近似一致
+    Fuzzy,
+}
+
+/// 採用された候補の説明
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateExplanation {
+    pub game: AllGameCacheOne,
+    pub score: f32,
+    pub rule: MatchRule,
+    /// 最高スコアを出したクエリ
+    pub query: MatchQuery,
+    pub normalized_query: String,
+    pub normalized_gamename: String,
+    /// クエリと共有したユニーク 2-gram 数（n-gram フィルタを通った場合のみ）
+    pub ngram_overlap: Option<usize>,
+    /// 編集距離（距離計算を行った場合のみ）
+    pub distance: Option<usize>,
+}
+
+/// 候補から外れた理由
+#[derive(Debug, Clone, PartialEq)]
+pub enum PruneReason {
+    /// `MatcherConfig::ignore_game_ids` に含まれる
+    IgnoredGameId,
+    /// n-gram の共有数が下限に届かない
+    NGramOverlap { overlap: usize, required: usize },
+    /// 許容最大距離を超えることが確定した
+    DistanceExceeded { overlap: usize, max_distance: usize },
+    /// 距離は計算できたがスコアが閾値以下
+    BelowThreshold {
+        overlap: usize,
+        distance: usize,
+        score: f32,
+    },
+}
+
+/// 候補から外れたゲームとその理由
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrunedCandidate {
+    pub game: AllGameCacheOne,
+    pub query: MatchQuery,
+    pub reason: PruneReason,
+}
+
+/// `GameMatcher::explain_candidates` の結果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchExplanation {
+    /// スコア降順（`find_candidates` と同じ並び）
+    pub candidates: Vec<CandidateExplanation>,
+    pub pruned: Vec<PrunedCandidate>,
+}
+
+impl MatchExplanation {
+    pub fn best(&self) -> Option<&CandidateExplanation> {
+        self.candidates.first()
+    }
+}
+
+impl PruneReason {
+    /// 表示順のための優先度（大きいほど「惜しい」）
+    pub(crate) fn closeness(&self) -> (u8, usize) {
+        match self {
+            PruneReason::BelowThreshold { overlap, .. } => (3, *overlap),
+            PruneReason::DistanceExceeded { overlap, .. } => (2, *overlap),
+            PruneReason::NGramOverlap { overlap, .. } => (1, *overlap),
+            PruneReason::IgnoredGameId => (0, 0),
+        }
+    }
+}
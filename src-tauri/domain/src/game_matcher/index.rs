@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+
+use super::ngram::{BigramKey, NGramIndex};
+use super::normalizer::normalize;
+use crate::all_game_cache::{AllGameCache, AllGameCacheOne};
+
+/// 永続化フォーマットのバージョン。正規化や n-gram の仕様を変えたら上げる。
+pub const MATCHER_INDEX_VERSION: u32 = 1;
+
+const MAGIC: &[u8; 4] = b"LGMI";
+const NGRAM_Q: usize = 2;
+
+/// Matcher が検索に使う構築済みインデックス
+#[derive(Debug, Clone)]
+pub struct MatcherIndex {
+    /// 元の AllGameCache のハッシュ（`content_hash`）
+    pub content_hash: u64,
+    /// 正規化済みのゲーム名（元キャッシュと同じ並び）
+    pub normalized: AllGameCache,
+    /// 正規化キー -> id（完全一致用）
+    pub normalized_index: HashMap<String, i32>,
+    pub ngram: NGramIndex,
+}
+
+/// 永続化されたインデックスの読み書き
+#[mockall::automock]
+pub trait MatcherIndexStore {
+    /// `content_hash` とバージョンが一致するインデックスがあれば返す
+    fn load(&self, content_hash: u64) -> anyhow::Result<Option<MatcherIndex>>;
+    fn save(&self, index: &MatcherIndex) -> anyhow::Result<()>;
+}
+
+/// AllGameCache の内容（並び順を含む）から決まる 64bit ハッシュ（FNV-1a）。
+/// プロセスやビルドをまたいで同じ値になる必要があるため std の Hasher は使わない。
+pub fn content_hash(cache: &[AllGameCacheOne]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash = OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for b in bytes {
+            hash ^= *b as u64;
+            hash = hash.wrapping_mul(PRIME);
+        }
+    };
+    feed(&MATCHER_INDEX_VERSION.to_le_bytes());
+    for g in cache {
+        feed(&g.id.to_le_bytes());
+        feed(&(g.gamename.len() as u32).to_le_bytes());
+        feed(g.gamename.as_bytes());
+    }
+    hash
+}
+
+impl MatcherIndex {
+    pub fn build(cache: &[AllGameCacheOne]) -> Self {
+        let normalized: AllGameCache = cache
+            .iter()
+            .map(|game| AllGameCacheOne {
+                id: game.id,
+                gamename: normalize(&game.gamename),
+            })
+            .collect();
+        let normalized_index = Self::build_normalized_index(&normalized);
+        let ngram = NGramIndex::build(&normalized, NGRAM_Q);
+        Self {
+            content_hash: content_hash(cache),
+            normalized,
+            normalized_index,
+            ngram,
+        }
+    }
+
+    /// 元キャッシュ上の位置（`ngram.id_to_pos`）
+    pub fn position(&self, id: i32) -> Option<usize> {
+        self.ngram.id_to_pos.get(&id).copied()
+    }
+
+    fn build_normalized_index(normalized: &[AllGameCacheOne]) -> HashMap<String, i32> {
+        let mut m = HashMap::with_capacity(normalized.len());
+        for g in normalized.iter() {
+            m.insert(g.gamename.clone(), g.id);
+        }
+        m
+    }
+
+    /// ヘッダ（バージョン, content_hash）だけを読む。本体のデコード前に鮮度を判定するため。
+    pub fn peek_header(bytes: &[u8]) -> anyhow::Result<(u32, u64)> {
+        let mut r = Reader::new(bytes);
+        if r.take(4)? != MAGIC {
+            return Err(anyhow::anyhow!("matcher index: invalid magic"));
+        }
+        let version = r.u32()?;
+        let hash = r.u64()?;
+        Ok((version, hash))
+    }
+
+    /// バイナリ表現へ変換する。
+    /// 正規化済み名と n-gram の postings を保存し、id -> 位置/長さ と完全一致用 index は読み込み時に復元する。
+    pub fn encode(&self) -> Vec<u8> {
+        let names_len: usize = self.normalized.iter().map(|g| g.gamename.len() + 8).sum();
+        let postings_len: usize = self.ngram.postings.values().map(|v| 12 + v.len() * 4).sum();
+        let mut out = Vec::with_capacity(32 + names_len + postings_len);
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&MATCHER_INDEX_VERSION.to_le_bytes());
+        out.extend_from_slice(&self.content_hash.to_le_bytes());
+        out.extend_from_slice(&(self.ngram.q as u32).to_le_bytes());
+
+        out.extend_from_slice(&(self.normalized.len() as u32).to_le_bytes());
+        for g in self.normalized.iter() {
+            out.extend_from_slice(&g.id.to_le_bytes());
+            out.extend_from_slice(&(g.gamename.len() as u32).to_le_bytes());
+            out.extend_from_slice(g.gamename.as_bytes());
+        }
+
+        out.extend_from_slice(&(self.ngram.postings.len() as u32).to_le_bytes());
+        for (key, ids) in self.ngram.postings.iter() {
+            out.extend_from_slice(&key.to_le_bytes());
+            out.extend_from_slice(&(ids.len() as u32).to_le_bytes());
+            for id in ids {
+                out.extend_from_slice(&id.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    pub fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        let (version, content_hash) = Self::peek_header(bytes)?;
+        if version != MATCHER_INDEX_VERSION {
+            return Err(anyhow::anyhow!(
+                "matcher index: unsupported version {} (expected {})",
+                version,
+                MATCHER_INDEX_VERSION
+            ));
+        }
+        let mut r = Reader::new(bytes);
+        r.take(4 + 4 + 8)?;
+        let q = r.u32()? as usize;
+        if q != NGRAM_Q {
+            return Err(anyhow::anyhow!(
+                "matcher index: unsupported q {} (expected {})",
+                q,
+                NGRAM_Q
+            ));
+        }
+
+        // 件数はファイルの値をそのまま信じず、残りのバイト数で入りうる件数までしか先に確保しない
+        let count = r.u32()? as usize;
+        let capacity = count.min(r.remaining() / 8);
+        let mut normalized: AllGameCache = Vec::with_capacity(capacity);
+        let mut id_to_len: HashMap<i32, usize> = HashMap::with_capacity(capacity);
+        let mut id_to_pos: HashMap<i32, usize> = HashMap::with_capacity(capacity);
+        for pos in 0..count {
+            let id = r.i32()?;
+            let len = r.u32()? as usize;
+            let gamename = std::str::from_utf8(r.take(len)?)
+                .map_err(|e| anyhow::anyhow!("matcher index: invalid utf-8: {}", e))?
+                .to_string();
+            id_to_len.insert(id, gamename.chars().count());
+            id_to_pos.insert(id, pos);
+            normalized.push(AllGameCacheOne::new(id, gamename));
+        }
+
+        let posting_count = r.u32()? as usize;
+        let mut postings: HashMap<BigramKey, Vec<i32>> =
+            HashMap::with_capacity(posting_count.min(r.remaining() / 12));
+        for _ in 0..posting_count {
+            let key = r.u64()?;
+            let n = r.u32()? as usize;
+            let mut ids = Vec::with_capacity(n.min(r.remaining() / 4));
+            for _ in 0..n {
+                ids.push(r.i32()?);
+            }
+            postings.insert(key, ids);
+        }
+        if !r.is_empty() {
+            return Err(anyhow::anyhow!("matcher index: trailing bytes"));
+        }
+
+        let normalized_index = Self::build_normalized_index(&normalized);
+        Ok(Self {
+            content_hash,
+            normalized,
+            normalized_index,
+            ngram: NGramIndex {
+                q,
+                postings,
+                id_to_len,
+                id_to_pos,
+            },
+        })
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow::anyhow!("matcher index: unexpected end of data"))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn i32(&mut self) -> anyhow::Result<i32> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> anyhow::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache() -> AllGameCache {
+        vec![
+            AllGameCacheOne::new(1, "ＳＡＫＵＲＡ さくらのうた".to_string()),
+            AllGameCacheOne::new(2, "Another Game".to_string()),
+            AllGameCacheOne::new(3, "あ".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let index = MatcherIndex::build(&cache());
+        let decoded = MatcherIndex::decode(&index.encode()).unwrap();
+
+        assert_eq!(decoded.content_hash, index.content_hash);
+        assert_eq!(decoded.normalized, index.normalized);
+        assert_eq!(decoded.normalized_index, index.normalized_index);
+        assert_eq!(decoded.ngram.q, index.ngram.q);
+        assert_eq!(decoded.ngram.postings, index.ngram.postings);
+        assert_eq!(decoded.ngram.id_to_len, index.ngram.id_to_len);
+        assert_eq!(decoded.ngram.id_to_pos, index.ngram.id_to_pos);
+        assert_eq!(decoded.normalized[0].gamename, "sakura さくらのうた");
+    }
+
+    #[test]
+    fn test_content_hash_changes_with_content() {
+        let base = cache();
+        let mut renamed = cache();
+        renamed[1].gamename = "Another Game 2".to_string();
+        let mut reordered = cache();
+        reordered.swap(0, 1);
+
+        assert_eq!(content_hash(&base), content_hash(&cache()));
+        assert_ne!(content_hash(&base), content_hash(&renamed));
+        assert_ne!(content_hash(&base), content_hash(&reordered));
+    }
+
+    #[test]
+    fn test_decode_rejects_broken_data() {
+        let bytes = MatcherIndex::build(&cache()).encode();
+
+        assert!(MatcherIndex::decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(MatcherIndex::decode(b"XXXX").is_err());
+
+        let mut other_version = bytes.clone();
+        other_version[4..8].copy_from_slice(&(MATCHER_INDEX_VERSION + 1).to_le_bytes());
+        assert!(MatcherIndex::decode(&other_version).is_err());
+        assert_eq!(
+            MatcherIndex::peek_header(&other_version).unwrap().0,
+            MATCHER_INDEX_VERSION + 1
+        );
+
+        let mut other_q = bytes.clone();
+        other_q[16..20].copy_from_slice(&3u32.to_le_bytes());
+        assert!(MatcherIndex::decode(&other_q).is_err());
+    }
+
+    #[test]
+    fn test_decode_huge_count_fails_without_allocating() {
+        let bytes = MatcherIndex::build(&cache()).encode();
+
+        let mut huge_games = bytes[..20].to_vec();
+        huge_games.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(MatcherIndex::decode(&huge_games).is_err());
+
+        // postings 件数が巨大でも残りのバイト数以上は確保しない
+        let mut huge_postings = MatcherIndex::build(&[]).encode();
+        let len = huge_postings.len();
+        huge_postings[len - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(MatcherIndex::decode(&huge_postings).is_err());
+    }
+}
@@ -1,10 +1,16 @@
 use super::config::{EQUALLY_FILENAME_GAME_ID_PAIR, IGNORE_GAME_ID};
-use super::ngram::NGramIndex;
+use super::explain::{
+    CandidateExplanation, MatchExplanation, MatchQuery, MatchRule, PruneReason, PrunedCandidate,
+    MAX_PRUNED_PER_QUERY,
+};
+use super::index::{content_hash, MatcherIndex, MatcherIndexStore};
 use super::normalizer::normalize;
 use crate::all_game_cache::{AllGameCache, AllGameCacheOne};
-use crate::distance::get_comparable_distance_bounded;
+use crate::distance::{
+    get_comparable_distance_bounded, get_distance_and_score_bounded, max_distance_for_similarity,
+};
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
 
 /// ゲームマッチング設定
 #[derive(Debug, Clone)]
@@ -38,10 +44,33 @@ impl Default for MatcherConfig {
 pub trait GameMatcher {
     /// 複数の文字列でゲーム候補を検索する
     fn find_candidates(&self, queries: &[String]) -> Vec<(AllGameCacheOne, f32)>;
+    /// `find_candidates` と同じ判定を行い、採用ルール・距離・枝刈り理由を含めて返す
+    /// （`find_candidates` とは別に、正規化後のクエリごとに判定をキャッシュする）
+    fn explain_candidates(&self, queries: &[MatchQuery]) -> MatchExplanation;
     /// AllGameCache を更新する（読み書き分離のため RwLock を用いる）
     fn update_all_game_cache(&self, new_cache: AllGameCache);
 }
 
+/// `explain_candidates` の 1 クエリ分の判定。採用候補はゲームごとに 1 件
+#[derive(Debug, Clone, Default)]
+struct QueryEvaluation {
+    candidates: Vec<CandidateExplanation>,
+    pruned: Vec<PrunedCandidate>,
+}
+
+impl QueryEvaluation {
+    /// キャッシュした判定を、今回のクエリ（出どころ）のものにする
+    fn for_query(mut self, query: &MatchQuery) -> Self {
+        for candidate in &mut self.candidates {
+            candidate.query = query.clone();
+        }
+        for pruned in &mut self.pruned {
+            pruned.query = query.clone();
+        }
+        self
+    }
+}
+
 /// シンプルなマッチャー実装
 /// 元実装と同じ動作を再現
 pub struct Matcher {
@@ -49,28 +78,24 @@ pub struct Matcher {
     config: MatcherConfig,
     // query -> Vec<(game, score)> のキャッシュ（閾値以上のマッチのみ）
     query_cache: RwLock<HashMap<String, Vec<(AllGameCacheOne, f32)>>>,
-    // 正規化キー -> id の O(1) 近似 index（完全一致用）
-    normalized_index: RwLock<HashMap<String, i32>>,
-    ngram_index: RwLock<NGramIndex>,
+    // 正規化後の query -> explain_candidates 用の判定のキャッシュ
+    explain_cache: RwLock<HashMap<String, QueryEvaluation>>,
+    // 正規化済み名・完全一致 index・2-gram index。
+    // 遅延構築の場合は初回検索時に用意する（None = 未構築）
+    index: RwLock<Option<Arc<MatcherIndex>>>,
+    store: Option<Arc<dyn MatcherIndexStore + Send + Sync>>,
 }
 
 impl Matcher {
     pub fn new(game_cache: AllGameCache, config: MatcherConfig) -> Self {
-        let normalized_game_cache: AllGameCache = game_cache
-            .iter()
-            .map(|game| AllGameCacheOne {
-                id: game.id,
-                gamename: normalize(&game.gamename),
-            })
-            .collect();
-        let index = Self::build_normalized_index(&normalized_game_cache);
-        let ngram_index = NGramIndex::build(&normalized_game_cache, 2);
+        let index = MatcherIndex::build(&game_cache);
         Self {
             game_cache: RwLock::new(game_cache),
             config,
             query_cache: RwLock::new(HashMap::new()),
-            normalized_index: RwLock::new(index),
-            ngram_index: RwLock::new(ngram_index),
+            explain_cache: RwLock::new(HashMap::new()),
+            index: RwLock::new(Some(Arc::new(index))),
+            store: None,
         }
     }
 
@@ -78,11 +103,38 @@ impl Matcher {
         Self::new(game_cache, MatcherConfig::default())
     }
 
+    /// 永続化したインデックスを使う Matcher を作る。
+    /// インデックスは初回の検索（または `warm_up`）で読み込み、キャッシュ内容が変わっていれば再構築して保存する。
+    pub fn with_index_store(
+        game_cache: AllGameCache,
+        config: MatcherConfig,
+        store: Arc<dyn MatcherIndexStore + Send + Sync>,
+    ) -> Self {
+        Self {
+            game_cache: RwLock::new(game_cache),
+            config,
+            query_cache: RwLock::new(HashMap::new()),
+            explain_cache: RwLock::new(HashMap::new()),
+            index: RwLock::new(None),
+            store: Some(store),
+        }
+    }
+
+    /// インデックスを今すぐ用意する（起動後にバックグラウンドで呼ぶ想定）
+    pub fn warm_up(&self) {
+        if let Ok(cache_guard) = self.game_cache.read() {
+            let _ = self.ensure_index(&cache_guard);
+        }
+    }
+
     /// キャッシュをクリアする
     pub fn clear_cache(&self) {
         if let Ok(mut cache) = self.query_cache.write() {
             cache.clear();
         }
+        if let Ok(mut cache) = self.explain_cache.write() {
+            cache.clear();
+        }
     }
 
     /// キャッシュサイズを取得
@@ -90,11 +142,47 @@ impl Matcher {
         self.query_cache.read().map_or(0, |cache| cache.len())
     }
 
+    /// 構築済みインデックスを返す。未構築なら永続化ストアから読むか構築する。
+    /// 呼び出し側は `game_cache` の read lock を保持していること（ロック順: game_cache -> index）。
+    fn ensure_index(&self, cache_snapshot: &[AllGameCacheOne]) -> Option<Arc<MatcherIndex>> {
+        if let Ok(guard) = self.index.read() {
+            if let Some(index) = guard.as_ref() {
+                return Some(index.clone());
+            }
+        }
+        let mut guard = self.index.write().ok()?;
+        if let Some(index) = guard.as_ref() {
+            return Some(index.clone());
+        }
+        let index = Arc::new(self.load_or_build_index(cache_snapshot));
+        *guard = Some(index.clone());
+        Some(index)
+    }
+
+    fn load_or_build_index(&self, cache: &[AllGameCacheOne]) -> MatcherIndex {
+        let hash = content_hash(cache);
+        if let Some(store) = &self.store {
+            match store.load(hash) {
+                Ok(Some(index)) => return index,
+                Ok(None) => {}
+                Err(e) => log::warn!("failed to load matcher index: {}", e),
+            }
+        }
+        let index = MatcherIndex::build(cache);
+        if let Some(store) = &self.store {
+            if let Err(e) = store.save(&index) {
+                log::warn!("failed to save matcher index: {}", e);
+            }
+        }
+        index
+    }
+
     /// スナップショットを使ってマッチを計算（クエリキャッシュを利用）
     fn get_matches_for_query_with_snapshot(
         &self,
         query: &str,
         cache_snapshot: &[AllGameCacheOne],
+        index: &MatcherIndex,
     ) -> Vec<(AllGameCacheOne, f32)> {
         let query = normalize(query);
         // キャッシュを確認（read lock）
@@ -105,15 +193,13 @@ impl Matcher {
         }
 
         // 正規化キーの O(1) 近似（完全一致短絡）
-        if let Ok(idx) = self.normalized_index.read() {
-            if let Some(&game_id) = idx.get(&query) {
-                if let Some(game) = cache_snapshot.iter().find(|g| g.id == game_id) {
-                    let res = vec![(game.clone(), 1.0)];
-                    if let Ok(mut cache) = self.query_cache.write() {
-                        cache.insert(query.to_string(), res.clone());
-                    }
-                    return res;
+        if let Some(&game_id) = index.normalized_index.get(&query) {
+            if let Some(game) = index.position(game_id).and_then(|pos| cache_snapshot.get(pos)) {
+                let res = vec![(game.clone(), 1.0)];
+                if let Ok(mut cache) = self.query_cache.write() {
+                    cache.insert(query.to_string(), res.clone());
                 }
+                return res;
             }
         }
 
@@ -121,37 +207,22 @@ impl Matcher {
         let mut matches = Vec::new();
 
         // 2-gram フィルタで候補を絞る（外部モジュール）
-        let mut candidate_ids: Vec<i32> = Vec::new();
-        if let Ok(ng) = self.ngram_index.read() {
-            candidate_ids = ng.filter_candidates(
-                &query,
-                self.config.similarity_threshold,
-                &self.config.ignore_game_ids,
-            );
-        }
-
-        // フィルタで候補が無ければ距離計算をスキップ
-        if candidate_ids.is_empty() {
-            if let Ok(mut cache) = self.query_cache.write() {
-                cache.insert(query.to_string(), matches.clone());
-            }
-            return matches;
-        }
+        let candidate_ids = index.ngram.filter_candidates(
+            &query,
+            self.config.similarity_threshold,
+            &self.config.ignore_game_ids,
+        );
 
         // 候補にのみ距離計算を適用
-        if let Ok(ng) = self.ngram_index.read() {
-            for id in candidate_ids {
-                if let Some(&pos) = ng.id_to_pos.get(&id) {
-                    if let Some(game) = cache_snapshot.get(pos) {
-                        if let Some(score) = get_comparable_distance_bounded(
-                            &query,
-                            &game.gamename,
-                            self.config.similarity_threshold,
-                        ) {
-                            if score > self.config.similarity_threshold {
-                                matches.push((game.clone(), score));
-                            }
-                        }
+        for id in candidate_ids {
+            if let Some(game) = index.position(id).and_then(|pos| cache_snapshot.get(pos)) {
+                if let Some(score) = get_comparable_distance_bounded(
+                    &query,
+                    &game.gamename,
+                    self.config.similarity_threshold,
+                ) {
+                    if score > self.config.similarity_threshold {
+                        matches.push((game.clone(), score));
                     }
                 }
             }
@@ -165,15 +236,167 @@ impl Matcher {
         matches
     }
 
-    fn build_normalized_index(snapshot: &AllGameCache) -> HashMap<String, i32> {
-        let mut m = HashMap::new();
-        for g in snapshot.iter() {
-            // ここでは既に AllGameCache は正規化済みを前提とするか、
-            // そうでなければ normalize を適用する（normalize は `super::normalizer`）。
-            // 安全側でそのままキー化する。
-            m.insert(g.gamename.clone(), g.id);
+    /// 1 クエリ分の判定を返す。正規化後のクエリが同じなら前回の判定を使い回し、
+    /// クエリの出どころだけ今回のものに付け替える
+    fn evaluate_query_with_snapshot(
+        &self,
+        query: &MatchQuery,
+        cache_snapshot: &[AllGameCacheOne],
+        index: &MatcherIndex,
+    ) -> QueryEvaluation {
+        let normalized_query = normalize(&query.value);
+        if let Ok(cache) = self.explain_cache.read() {
+            if let Some(cached) = cache.get(&normalized_query) {
+                return cached.clone().for_query(query);
+            }
+        }
+        let evaluation =
+            self.evaluate_query_uncached(query, normalized_query.clone(), cache_snapshot, index);
+        if let Ok(mut cache) = self.explain_cache.write() {
+            cache.insert(normalized_query, evaluation.clone());
+        }
+        evaluation
+    }
+
+    /// 1 クエリ分の判定を `get_matches_for_query_with_snapshot` と同じ順序で行う
+    fn evaluate_query_uncached(
+        &self,
+        query: &MatchQuery,
+        normalized_query: String,
+        cache_snapshot: &[AllGameCacheOne],
+        index: &MatcherIndex,
+    ) -> QueryEvaluation {
+        let mut evaluation = QueryEvaluation::default();
+
+        // 正規化キーの完全一致
+        if let Some(&game_id) = index.normalized_index.get(&normalized_query) {
+            if let Some(pos) = index.position(game_id) {
+                if let (Some(game), Some(normalized)) =
+                    (cache_snapshot.get(pos), index.normalized.get(pos))
+                {
+                    evaluation.candidates.push(CandidateExplanation {
+                        game: game.clone(),
+                        score: 1.0,
+                        rule: MatchRule::NormalizedIndex,
+                        query: query.clone(),
+                        normalized_query,
+                        normalized_gamename: normalized.gamename.clone(),
+                        ngram_overlap: None,
+                        distance: None,
+                    });
+                    return evaluation;
+                }
+            }
         }
-        m
+
+        let threshold = self.config.similarity_threshold;
+        // 枝刈りしたものは上限件数に絞ってからゲームを複製する
+        let mut query_pruned: Vec<(&AllGameCacheOne, PruneReason)> = Vec::new();
+        for candidate in index.ngram.evaluate_candidates(
+            &normalized_query,
+            threshold,
+            &self.config.ignore_game_ids,
+        ) {
+            let Some(pos) = index.position(candidate.id) else {
+                continue;
+            };
+            let (Some(game), Some(normalized)) =
+                (cache_snapshot.get(pos), index.normalized.get(pos))
+            else {
+                continue;
+            };
+            let normalized_gamename = &normalized.gamename;
+            let overlap = candidate.overlap;
+            let reason = if candidate.ignored {
+                Some(PruneReason::IgnoredGameId)
+            } else if !candidate.passed() {
+                Some(PruneReason::NGramOverlap {
+                    overlap,
+                    required: candidate.required,
+                })
+            } else {
+                match get_distance_and_score_bounded(&normalized_query, &game.gamename, threshold)
+                {
+                    None => Some(PruneReason::DistanceExceeded {
+                        overlap,
+                        max_distance: max_distance_for_similarity(
+                            normalized_query.len().max(game.gamename.len()),
+                            threshold,
+                        ),
+                    }),
+                    Some((distance, score)) if score <= threshold => {
+                        Some(PruneReason::BelowThreshold {
+                            overlap,
+                            distance,
+                            score,
+                        })
+                    }
+                    Some((distance, score)) => {
+                        let rule = if self.is_partial(&normalized_query, normalized_gamename) {
+                            MatchRule::Partial
+                        } else {
+                            MatchRule::Fuzzy
+                        };
+                        evaluation.candidates.push(CandidateExplanation {
+                            game: game.clone(),
+                            score,
+                            rule,
+                            query: query.clone(),
+                            normalized_query: normalized_query.clone(),
+                            normalized_gamename: normalized_gamename.clone(),
+                            ngram_overlap: Some(overlap),
+                            distance: Some(distance),
+                        });
+                        None
+                    }
+                }
+            };
+            if let Some(reason) = reason {
+                query_pruned.push((game, reason));
+            }
+        }
+
+        // 閾値に近かったものから残す
+        query_pruned.sort_by(|(a_game, a), (b_game, b)| {
+            b.closeness()
+                .cmp(&a.closeness())
+                .then(a_game.id.cmp(&b_game.id))
+        });
+        query_pruned.truncate(MAX_PRUNED_PER_QUERY);
+        evaluation.pruned = query_pruned
+            .into_iter()
+            .map(|(game, reason)| PrunedCandidate {
+                game: game.clone(),
+                query: query.clone(),
+                reason,
+            })
+            .collect();
+        evaluation
+    }
+
+    /// 片方がもう片方を含み、短い方が `partial_min_length` 文字以上なら部分一致とみなす
+    fn is_partial(&self, normalized_query: &str, normalized_gamename: &str) -> bool {
+        let (shorter, longer) = if normalized_query.chars().count()
+            <= normalized_gamename.chars().count()
+        {
+            (normalized_query, normalized_gamename)
+        } else {
+            (normalized_gamename, normalized_query)
+        };
+        shorter != longer
+            && shorter.chars().count() >= self.config.partial_min_length
+            && longer.contains(shorter)
+    }
+
+    /// exact_mappings の id を元キャッシュ上のゲームに解決する
+    fn find_by_id<'a>(
+        cache_snapshot: &'a [AllGameCacheOne],
+        index: &MatcherIndex,
+        game_id: i32,
+    ) -> Option<&'a AllGameCacheOne> {
+        index
+            .position(game_id)
+            .and_then(|pos| cache_snapshot.get(pos))
     }
 }
 
@@ -185,10 +408,13 @@ impl GameMatcher for Matcher {
             Err(_) => return Vec::new(),
         };
         let cache_snapshot: &[AllGameCacheOne] = &cache_guard;
+        let Some(index) = self.ensure_index(cache_snapshot) else {
+            return Vec::new();
+        };
         // 1. 完全一致チェック
         for query in queries {
             if let Some(&game_id) = self.config.exact_mappings.get(query) {
-                if let Some(game) = cache_snapshot.iter().find(|g| g.id == game_id) {
+                if let Some(game) = Self::find_by_id(cache_snapshot, &index, game_id) {
                     return vec![(game.clone(), 1.0)];
                 }
             }
@@ -198,7 +424,7 @@ impl GameMatcher for Matcher {
         let mut game_scores: HashMap<i32, f32> = HashMap::new();
 
         for query in queries {
-            let matches = self.get_matches_for_query_with_snapshot(query, cache_snapshot);
+            let matches = self.get_matches_for_query_with_snapshot(query, cache_snapshot, &index);
             for (game, score) in matches {
                 // 各ゲームの最高スコアを保持
                 let current_score = game_scores.get(&game.id).unwrap_or(&0.0);
@@ -210,10 +436,7 @@ impl GameMatcher for Matcher {
         let mut candidates: Vec<(AllGameCacheOne, f32)> = game_scores
             .into_iter()
             .filter_map(|(game_id, score)| {
-                cache_snapshot
-                    .iter()
-                    .find(|g| g.id == game_id)
-                    .map(|game| (game.clone(), score))
+                Self::find_by_id(cache_snapshot, &index, game_id).map(|game| (game.clone(), score))
             })
             .collect();
 
@@ -224,23 +447,97 @@ impl GameMatcher for Matcher {
         candidates
     }
 
+    fn explain_candidates(&self, queries: &[MatchQuery]) -> MatchExplanation {
+        let cache_guard = match self.game_cache.read() {
+            Ok(g) => g,
+            Err(_) => return MatchExplanation::default(),
+        };
+        let cache_snapshot: &[AllGameCacheOne] = &cache_guard;
+        let Some(index) = self.ensure_index(cache_snapshot) else {
+            return MatchExplanation::default();
+        };
+
+        // 1. 完全一致チェック（成立したら他のクエリは評価しない）
+        for query in queries {
+            if let Some(&game_id) = self.config.exact_mappings.get(&query.value) {
+                if let Some(game) = Self::find_by_id(cache_snapshot, &index, game_id) {
+                    return MatchExplanation {
+                        candidates: vec![CandidateExplanation {
+                            game: game.clone(),
+                            score: 1.0,
+                            rule: MatchRule::ExactMapping,
+                            query: query.clone(),
+                            normalized_query: normalize(&query.value),
+                            normalized_gamename: normalize(&game.gamename),
+                            ngram_overlap: None,
+                            distance: None,
+                        }],
+                        pruned: Vec::new(),
+                    };
+                }
+            }
+        }
+
+        // 2. クエリごとに評価し、ゲームごとに最高スコアを保持
+        let mut best: HashMap<i32, CandidateExplanation> = HashMap::new();
+        let mut pruned: Vec<PrunedCandidate> = Vec::new();
+        for query in queries {
+            let evaluation = self.evaluate_query_with_snapshot(query, cache_snapshot, &index);
+            for candidate in evaluation.candidates {
+                let replace = match best.get(&candidate.game.id) {
+                    Some(current) => candidate.score > current.score,
+                    None => true,
+                };
+                if replace {
+                    best.insert(candidate.game.id, candidate);
+                }
+            }
+            pruned.extend(evaluation.pruned);
+        }
+
+        // 別クエリで採用されたゲームは枝刈り一覧から除く
+        pruned.retain(|p| !best.contains_key(&p.game.id));
+
+        use std::cmp::Ordering;
+        let mut candidates: Vec<CandidateExplanation> = best.into_values().collect();
+        candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+
+        MatchExplanation { candidates, pruned }
+    }
+
     fn update_all_game_cache(&self, new_cache: AllGameCache) {
+        // 内容が変わっていなければインデックスもクエリキャッシュもそのまま使える
+        let new_hash = content_hash(&new_cache);
+        let unchanged = self
+            .index
+            .read()
+            .ok()
+            .and_then(|guard| guard.as_ref().map(|index| index.content_hash == new_hash))
+            .unwrap_or(false);
+        if unchanged {
+            return;
+        }
+
+        // 未構築（遅延）のままなら次回の検索で構築させる
+        let lazy = match self.index.read() {
+            Ok(guard) => guard.is_none(),
+            Err(_) => true,
+        };
+        let new_index = if lazy {
+            None
+        } else {
+            Some(Arc::new(self.load_or_build_index(&new_cache)))
+        };
+
+        // ロック順: game_cache -> index
         if let Ok(mut cache_guard) = self.game_cache.write() {
             *cache_guard = new_cache;
+            if let Ok(mut index_guard) = self.index.write() {
+                *index_guard = new_index;
+            }
         }
         // クエリキャッシュは無効化（内容が変わるため）
         self.clear_cache();
-        // 正規化インデックスを再構築
-        if let Ok(cache_guard) = self.game_cache.read() {
-            let new_index = Self::build_normalized_index(&cache_guard);
-            if let Ok(mut idx) = self.normalized_index.write() {
-                *idx = new_index;
-            }
-            let ng = NGramIndex::build(&cache_guard, 2);
-            if let Ok(mut n) = self.ngram_index.write() {
-                *n = ng;
-            }
-        }
     }
 }
 
@@ -248,6 +545,8 @@ impl GameMatcher for Matcher {
 mod tests {
     use super::*;
     use crate::all_game_cache::AllGameCacheOne;
+    use crate::game_matcher::explain::QuerySource;
+    use crate::game_matcher::index::MockMatcherIndexStore;
 
     fn create_test_cache() -> AllGameCache {
         vec![
@@ -393,4 +692,228 @@ mod tests {
         // キャッシュサイズは変わらない（すべてキャッシュから取得）
         assert_eq!(cache_size_after_first, cache_size_after_second);
     }
+
+    #[test]
+    fn test_explain_exact_mapping() {
+        let cache = create_test_cache();
+        let matcher = Matcher::with_default_config(cache);
+
+        let queries = vec![
+            MatchQuery::new(QuerySource::ParentDir, "unknown".to_string()),
+            MatchQuery::new(QuerySource::Filename, "pieces".to_string()),
+        ];
+        let explanation = matcher.explain_candidates(&queries);
+
+        let best = explanation.best().unwrap();
+        assert_eq!(best.game.id, 27123);
+        assert_eq!(best.rule, MatchRule::ExactMapping);
+        assert_eq!(best.query.source, QuerySource::Filename);
+        assert!(explanation.pruned.is_empty());
+    }
+
+    #[test]
+    fn test_explain_normalized_index() {
+        let cache = create_test_cache();
+        let matcher = Matcher::with_default_config(cache);
+
+        let queries = vec![MatchQuery::new(
+            QuerySource::ParentDir,
+            "ＡＮＯＴＨＥＲ game".to_string(),
+        )];
+        let explanation = matcher.explain_candidates(&queries);
+
+        let best = explanation.best().unwrap();
+        assert_eq!(best.game.id, 2);
+        assert_eq!(best.rule, MatchRule::NormalizedIndex);
+        assert_eq!(best.normalized_query, "another game");
+    }
+
+    #[test]
+    fn test_explain_fuzzy_matches_find_candidates() {
+        let cache = vec![
+            AllGameCacheOne::new(1, "さくらのうたまつり".to_string()),
+            AllGameCacheOne::new(2, "さくらのうた".to_string()),
+            AllGameCacheOne::new(3, "まったく別のゲーム".to_string()),
+        ];
+        let mut config = MatcherConfig::default();
+        config.similarity_threshold = 0.5;
+        config.partial_min_length = 3;
+        let matcher = Matcher::new(cache, config);
+
+        let raw = vec!["さくらのうたまつリ".to_string()];
+        let queries = vec![MatchQuery::new(QuerySource::Filename, raw[0].clone())];
+        let found = matcher.find_candidates(&raw);
+        let explanation = matcher.explain_candidates(&queries);
+
+        let found_ids: Vec<i32> = found.iter().map(|(g, _)| g.id).collect();
+        let explained_ids: Vec<i32> = explanation.candidates.iter().map(|c| c.game.id).collect();
+        assert_eq!(found_ids, explained_ids);
+
+        let best = explanation.best().unwrap();
+        assert_eq!(best.game.id, 1);
+        assert_eq!(best.rule, MatchRule::Fuzzy);
+        // ONP 距離は挿入・削除のみなので 1 文字の置換は 2
+        assert_eq!(best.distance, Some(2));
+        assert!(best.ngram_overlap.unwrap() > 0);
+    }
+
+    #[test]
+    fn test_explain_partial_and_pruned() {
+        let cache = vec![
+            AllGameCacheOne::new(1, "ひまわりのきょうかい".to_string()),
+            AllGameCacheOne::new(2, "ひまわり".to_string()),
+            AllGameCacheOne::new(3, "きょうかいのながいなつやすみ".to_string()),
+        ];
+        let mut config = MatcherConfig::default();
+        config.partial_min_length = 4;
+        let matcher = Matcher::new(cache, config);
+
+        let queries = vec![MatchQuery::new(
+            QuerySource::ParentDir,
+            "ひまわりのきょうか".to_string(),
+        )];
+        let explanation = matcher.explain_candidates(&queries);
+
+        let partial = explanation
+            .candidates
+            .iter()
+            .find(|c| c.game.id == 1)
+            .unwrap();
+        assert_eq!(partial.rule, MatchRule::Partial);
+
+        // 2-gram を共有するが閾値に届かない候補は理由付きで枝刈りされる
+        let pruned = explanation
+            .pruned
+            .iter()
+            .find(|p| p.game.id == 3)
+            .unwrap();
+        assert_eq!(pruned.query.source, QuerySource::ParentDir);
+        assert!(matches!(pruned.reason, PruneReason::NGramOverlap { .. }));
+        assert!(explanation
+            .pruned
+            .iter()
+            .all(|p| explanation.candidates.iter().all(|c| c.game.id != p.game.id)));
+    }
+
+    #[test]
+    fn test_explain_does_not_touch_query_cache() {
+        let cache = create_test_cache();
+        let matcher = Matcher::with_default_config(cache);
+
+        let queries = vec![MatchQuery::new(QuerySource::Other, "piece".to_string())];
+        matcher.explain_candidates(&queries);
+
+        assert_eq!(matcher.cache_size(), 0);
+    }
+
+    #[test]
+    fn test_explain_reuses_evaluation_per_normalized_query() {
+        let cache = create_test_cache();
+        let matcher = Matcher::with_default_config(cache);
+
+        let first = matcher.explain_candidates(&[MatchQuery::new(
+            QuerySource::ParentDir,
+            "piece".to_string(),
+        )]);
+        assert_eq!(matcher.explain_cache.read().unwrap().len(), 1);
+
+        // 同じクエリなら判定は使い回し、出どころだけ今回のものになる
+        let second = matcher.explain_candidates(&[MatchQuery::new(
+            QuerySource::Filename,
+            "piece".to_string(),
+        )]);
+        assert_eq!(matcher.explain_cache.read().unwrap().len(), 1);
+        assert_eq!(first.candidates.len(), second.candidates.len());
+        assert_eq!(first.pruned.len(), second.pruned.len());
+        assert!(second
+            .candidates
+            .iter()
+            .all(|c| c.query.source == QuerySource::Filename));
+        assert!(second
+            .pruned
+            .iter()
+            .all(|p| p.query.source == QuerySource::Filename));
+
+        matcher.clear_cache();
+        assert_eq!(matcher.explain_cache.read().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_index_store_loads_lazily_and_skips_rebuild() {
+        let cache = create_test_cache();
+        let prebuilt = MatcherIndex::build(&cache);
+        let expected_hash = prebuilt.content_hash;
+
+        let mut store = MockMatcherIndexStore::new();
+        store
+            .expect_load()
+            .withf(move |hash| *hash == expected_hash)
+            .times(1)
+            .returning(move |_| Ok(Some(prebuilt.clone())));
+        store.expect_save().never();
+
+        let matcher = Matcher::with_index_store(cache, MatcherConfig::default(), Arc::new(store));
+        // 初回検索までは読み込まない（load は一度だけ）
+        let first = matcher.find_candidates(&vec!["テストゲーム".to_string()]);
+        let second = matcher.find_candidates(&vec!["another game".to_string()]);
+
+        assert_eq!(first[0].0.id, 1);
+        assert_eq!(second[0].0.id, 2);
+    }
+
+    #[test]
+    fn test_index_store_rebuilds_and_saves_when_stale() {
+        let cache = create_test_cache();
+        let expected_hash = content_hash(&cache);
+
+        let mut store = MockMatcherIndexStore::new();
+        store.expect_load().times(1).returning(|_| Ok(None));
+        store
+            .expect_save()
+            .withf(move |index| index.content_hash == expected_hash)
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let matcher = Matcher::with_index_store(cache, MatcherConfig::default(), Arc::new(store));
+        matcher.warm_up();
+        let candidates = matcher.find_candidates(&vec!["テストゲーム".to_string()]);
+
+        assert_eq!(candidates[0].0.id, 1);
+    }
+
+    #[test]
+    fn test_update_with_same_content_keeps_index() {
+        let cache = create_test_cache();
+        let mut store = MockMatcherIndexStore::new();
+        store.expect_load().times(1).returning(|_| Ok(None));
+        store.expect_save().times(1).returning(|_| Ok(()));
+
+        let matcher = Matcher::with_index_store(
+            cache.clone(),
+            MatcherConfig::default(),
+            Arc::new(store),
+        );
+        matcher.warm_up();
+        matcher.find_candidates(&vec!["piece".to_string()]);
+        let cache_size = matcher.cache_size();
+
+        // 同じ内容なら再構築も保存もしない（クエリキャッシュも維持）
+        matcher.update_all_game_cache(cache);
+        assert_eq!(matcher.cache_size(), cache_size);
+    }
+
+    #[test]
+    fn test_update_with_new_content_rebuilds_index() {
+        let matcher = Matcher::with_default_config(create_test_cache());
+        assert!(matcher
+            .find_candidates(&vec!["新しいゲーム".to_string()])
+            .is_empty());
+
+        let mut updated = create_test_cache();
+        updated.push(AllGameCacheOne::new(3, "新しいゲーム".to_string()));
+        matcher.update_all_game_cache(updated);
+
+        let candidates = matcher.find_candidates(&vec!["新しいゲーム".to_string()]);
+        assert_eq!(candidates[0].0.id, 3);
+    }
 }
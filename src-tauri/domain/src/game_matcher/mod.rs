@@ -1,9 +1,13 @@
 pub mod config;
+pub mod explain;
 pub mod file_info;
+pub mod index;
 pub mod matcher;
 pub mod ngram;
 pub mod normalizer;
 
+pub use explain::*;
 pub use file_info::*;
+pub use index::*;
 pub use matcher::*;
 pub use normalizer::*;
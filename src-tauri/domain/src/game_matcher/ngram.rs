@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::all_game_cache::AllGameCache;
+use crate::distance::max_distance_for_similarity;
 
 pub type BigramKey = u64;
 
@@ -58,6 +59,21 @@ impl NGramIndex {
         min_similarity: f32,
         ignore_game_ids: &[i32],
     ) -> Vec<i32> {
+        self.evaluate_candidates(query, min_similarity, ignore_game_ids)
+            .into_iter()
+            .filter(|e| e.passed())
+            .map(|e| e.id)
+            .collect()
+    }
+
+    /// q-gram を 1 つ以上共有する全 id について、フィルタの判定材料を返す。
+    /// `filter_candidates` はこの結果のうち `passed` なものだけを採用する。
+    pub fn evaluate_candidates(
+        &self,
+        query: &str,
+        min_similarity: f32,
+        ignore_game_ids: &[i32],
+    ) -> Vec<NGramEvaluation> {
         let q = self.q;
         let query_chars: Vec<char> = query.chars().collect();
         let query_len = query_chars.len();
@@ -70,24 +86,6 @@ impl NGramIndex {
             }
         }
 
-        // d_max を算出
-        let calc_dmax = |ly: usize| -> usize {
-            let max_len = query_len.max(ly);
-            if max_len == 0 {
-                return 0;
-            }
-            let raw = (1.0 - min_similarity) * (max_len as f32);
-            let mut d = raw.floor() as isize;
-            if (raw - (d as f32)).abs() < 1e-6 {
-                d -= 1;
-            }
-            if d < 0 {
-                0
-            } else {
-                d as usize
-            }
-        };
-
         // 重複 q-gram を排除した overlap 集計
         let mut overlap_counts: HashMap<i32, u16> = HashMap::new();
         for g in grams.iter() {
@@ -98,23 +96,39 @@ impl NGramIndex {
             }
         }
 
-        let mut candidates: Vec<i32> = Vec::new();
+        let mut evaluations: Vec<NGramEvaluation> = Vec::with_capacity(overlap_counts.len());
         for (&id, &overlap) in overlap_counts.iter() {
-            if ignore_game_ids.contains(&id) {
-                continue;
-            }
             let ly = *self.id_to_len.get(&id).unwrap_or(&0);
-            let dmax = calc_dmax(ly);
+            // d_max を算出
+            let dmax = max_distance_for_similarity(query_len.max(ly), min_similarity);
             let ly_q = ly.saturating_sub(q - 1);
             let lq_unique = grams.len();
-            let mut t = (lq_unique.min(ly_q) as isize) - (q as isize * dmax as isize);
-            if t <= 0 {
-                t = 0;
-            }
-            if (overlap as isize) >= t {
-                candidates.push(id);
-            }
+            let t = (lq_unique.min(ly_q) as isize) - (q as isize * dmax as isize);
+            evaluations.push(NGramEvaluation {
+                id,
+                overlap: overlap as usize,
+                required: t.max(0) as usize,
+                ignored: ignore_game_ids.contains(&id),
+            });
         }
-        candidates
+        evaluations
+    }
+}
+
+/// n-gram フィルタにおける 1 候補分の判定結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NGramEvaluation {
+    pub id: i32,
+    /// クエリと共有するユニーク q-gram 数
+    pub overlap: usize,
+    /// 距離計算に進むために必要な最小 overlap
+    pub required: usize,
+    /// `ignore_game_ids` に含まれていたか
+    pub ignored: bool,
+}
+
+impl NGramEvaluation {
+    pub fn passed(&self) -> bool {
+        !self.ignored && self.overlap >= self.required
     }
 }
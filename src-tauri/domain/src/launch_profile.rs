@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{file::LnkMetadata, wine::WineConfig, works::Work, Id, StrId};
+
+/// ラッパーに渡す引数のうち、起動する実行ファイルに置き換える部分
+pub const WRAPPER_TARGET_PLACEHOLDER: &str = "{target}";
+/// ラッパーに渡す引数のうち、プロファイルの引数に置き換える部分
+pub const WRAPPER_ARGUMENTS_PLACEHOLDER: &str = "{args}";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchEnvVar {
+    pub key: String,
+    pub value: String,
+}
+
+/// Locale Emulator や ntleas のように、実行ファイルを引数に取って起動するコマンド
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchWrapper {
+    pub path: String,
+    /// `{target}` と `{args}` を置き換える。`{target}` がなければ末尾に実行ファイルと引数を足す
+    pub arguments: Option<String>,
+}
+
+/// 作品ごとの起動設定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchProfile {
+    pub id: Id<LaunchProfile>,
+    pub work_id: StrId<Work>,
+    pub name: String,
+    /// ショートカットに書かれた引数の後ろに足す
+    pub arguments: Option<String>,
+    /// 未指定ならショートカットの作業フォルダ、それもなければ実行ファイルのあるフォルダ
+    pub working_dir: Option<String>,
+    pub env: Vec<LaunchEnvVar>,
+    pub run_as_admin: bool,
+    pub wrapper: Option<LaunchWrapper>,
+    /// 起動時にプロファイルを指定しなければこれを使う。作品ごとに高々1つ
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewLaunchProfile {
+    pub work_id: StrId<Work>,
+    pub name: String,
+    pub arguments: Option<String>,
+    pub working_dir: Option<String>,
+    pub env: Vec<LaunchEnvVar>,
+    pub run_as_admin: bool,
+    pub wrapper: Option<LaunchWrapper>,
+    pub is_default: bool,
+}
+
+impl NewLaunchProfile {
+    /// 管理者として実行するときは昇格先に環境変数を渡せないので、上書きと併用できない
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.name.trim().is_empty() {
+            anyhow::bail!("launch profile name is empty");
+        }
+        for var in &self.env {
+            if var.key.is_empty() || var.key.contains('=') {
+                anyhow::bail!("invalid environment variable name: {:?}", var.key);
+            }
+        }
+        if self.run_as_admin && !self.env.is_empty() {
+            anyhow::bail!("environment overrides cannot be used with run as admin");
+        }
+        if let Some(wrapper) = &self.wrapper {
+            if wrapper.path.trim().is_empty() {
+                anyhow::bail!("wrapper path is empty");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LaunchProfile {
+    /// ショートカットをそのまま開けばよいか。管理者権限の有無だけならショートカットで足りる
+    pub fn uses_shortcut(&self) -> bool {
+        self.arguments.is_none()
+            && self.working_dir.is_none()
+            && self.env.is_empty()
+            && self.wrapper.is_none()
+    }
+
+    /// ショートカット `link` のリンク先を起動するコマンドを組み立てる。
+    /// ショートカットの引数と作業フォルダも引き継ぐ
+    pub fn command(&self, link: &LnkMetadata) -> LaunchCommand {
+        let target_path = link.path.as_str();
+        let working_dir = self
+            .working_dir
+            .clone()
+            .or_else(|| link.working_dir.clone())
+            .or_else(|| default_working_dir(target_path));
+        let arguments = join_arguments(link.arguments.as_deref(), self.arguments.as_deref());
+        let (program, parameters) = match &self.wrapper {
+            None => (target_path.to_string(), arguments),
+            Some(wrapper) => (
+                wrapper.path.clone(),
+                Some(wrapper_parameters(
+                    wrapper,
+                    target_path,
+                    arguments.as_deref(),
+                )),
+            ),
+        };
+        LaunchCommand {
+            program,
+            parameters,
+            working_dir,
+            env: self.env.clone(),
+            run_as_admin: self.run_as_admin,
+            wine: None,
+        }
+    }
+}
+
+/// ショートカットの引数の後ろにプロファイルの引数を足す
+fn join_arguments(link: Option<&str>, profile: Option<&str>) -> Option<String> {
+    let joined = [link, profile]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(joined).filter(|joined| !joined.is_empty())
+}
+
+fn default_working_dir(target_path: &str) -> Option<String> {
+    std::path::Path::new(target_path)
+        .parent()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .filter(|dir| !dir.is_empty())
+}
+
+fn wrapper_parameters(
+    wrapper: &LaunchWrapper,
+    target_path: &str,
+    arguments: Option<&str>,
+) -> String {
+    let target = format!("\"{target_path}\"");
+    let arguments = arguments.unwrap_or_default();
+    let template = wrapper.arguments.as_deref().unwrap_or_default();
+    let parameters = if template.contains(WRAPPER_TARGET_PLACEHOLDER) {
+        template
+            .replace(WRAPPER_TARGET_PLACEHOLDER, &target)
+            .replace(WRAPPER_ARGUMENTS_PLACEHOLDER, arguments)
+    } else {
+        [template, &target, arguments]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    parameters.trim().to_string()
+}
+
+/// プロファイルを当てて起動するときのコマンド
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchCommand {
+    pub program: String,
+    /// そのままコマンドラインに渡す
+    pub parameters: Option<String>,
+    pub working_dir: Option<String>,
+    pub env: Vec<LaunchEnvVar>,
+    pub run_as_admin: bool,
+    /// Wine で起動するときの作品ごとの設定。Windows では使わない
+    pub wine: Option<WineConfig>,
+}
+
+impl LaunchCommand {
+    /// プロファイルを当てずにリンク先をそのまま起動するコマンド
+    pub fn direct(target_path: &str) -> Self {
+        LaunchCommand {
+            program: target_path.to_string(),
+            parameters: None,
+            working_dir: default_working_dir(target_path),
+            env: vec![],
+            run_as_admin: false,
+            wine: None,
+        }
+    }
+
+    /// プロファイルを当てずにショートカットのリンク先を、書かれた引数と作業フォルダで起動するコマンド
+    pub fn from_link(link: &LnkMetadata) -> Self {
+        LaunchCommand {
+            parameters: link.arguments.clone(),
+            working_dir: link
+                .working_dir
+                .clone()
+                .or_else(|| default_working_dir(&link.path)),
+            ..LaunchCommand::direct(&link.path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> LaunchProfile {
+        LaunchProfile {
+            id: Id::new(1),
+            work_id: StrId::new("w".into()),
+            name: "既定".into(),
+            arguments: None,
+            working_dir: None,
+            env: vec![],
+            run_as_admin: false,
+            wrapper: None,
+            is_default: true,
+        }
+    }
+
+    fn link(path: &str) -> LnkMetadata {
+        LnkMetadata {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn command_作業フォルダの既定は実行ファイルのフォルダ() {
+        let profile = LaunchProfile {
+            arguments: Some("-window".into()),
+            ..profile()
+        };
+        let command = profile.command(&link("C:/games/a/game.exe"));
+        assert_eq!(command.program, "C:/games/a/game.exe");
+        assert_eq!(command.parameters.as_deref(), Some("-window"));
+        assert_eq!(command.working_dir.as_deref(), Some("C:/games/a"));
+        assert!(!profile.uses_shortcut());
+    }
+
+    #[test]
+    fn command_ショートカットの引数と作業フォルダを引き継ぐ() {
+        let shortcut = LnkMetadata {
+            arguments: Some("-nolauncher".into()),
+            working_dir: Some("C:/games/a/bin".into()),
+            ..link("C:/games/a/game.exe")
+        };
+        let command = profile().command(&shortcut);
+        assert_eq!(command.parameters.as_deref(), Some("-nolauncher"));
+        assert_eq!(command.working_dir.as_deref(), Some("C:/games/a/bin"));
+        assert_eq!(LaunchCommand::from_link(&shortcut), command);
+
+        // プロファイルの引数は後ろに足し、作業フォルダはプロファイルを優先する
+        let profile = LaunchProfile {
+            arguments: Some("-window".into()),
+            working_dir: Some("D:/saves".into()),
+            ..profile()
+        };
+        let command = profile.command(&shortcut);
+        assert_eq!(command.parameters.as_deref(), Some("-nolauncher -window"));
+        assert_eq!(command.working_dir.as_deref(), Some("D:/saves"));
+    }
+
+    #[test]
+    fn command_ラッパーの引数に実行ファイルを埋め込む() {
+        let profile = LaunchProfile {
+            arguments: Some("-window".into()),
+            wrapper: Some(LaunchWrapper {
+                path: "C:/tools/LEProc.exe".into(),
+                arguments: Some("-run {target} {args}".into()),
+            }),
+            ..profile()
+        };
+        let command = profile.command(&link("C:/games/a/game.exe"));
+        assert_eq!(command.program, "C:/tools/LEProc.exe");
+        assert_eq!(
+            command.parameters.as_deref(),
+            Some("-run \"C:/games/a/game.exe\" -window")
+        );
+    }
+
+    #[test]
+    fn command_置き換えがなければ末尾に実行ファイルを足す() {
+        let profile = LaunchProfile {
+            wrapper: Some(LaunchWrapper {
+                path: "C:/tools/ntleas.exe".into(),
+                arguments: None,
+            }),
+            ..profile()
+        };
+        let command = profile.command(&link("C:/games/a/game.exe"));
+        assert_eq!(
+            command.parameters.as_deref(),
+            Some("\"C:/games/a/game.exe\"")
+        );
+    }
+
+    #[test]
+    fn validate_管理者実行と環境変数は併用できない() {
+        let new_profile = NewLaunchProfile {
+            work_id: StrId::new("w".into()),
+            name: "既定".into(),
+            arguments: None,
+            working_dir: None,
+            env: vec![LaunchEnvVar {
+                key: "LANG".into(),
+                value: "ja_JP".into(),
+            }],
+            run_as_admin: true,
+            wrapper: None,
+            is_default: true,
+        };
+        assert!(new_profile.validate().is_err());
+        assert!(NewLaunchProfile {
+            run_as_admin: false,
+            ..new_profile.clone()
+        }
+        .validate()
+        .is_ok());
+        assert!(NewLaunchProfile {
+            name: " ".into(),
+            run_as_admin: false,
+            ..new_profile
+        }
+        .validate()
+        .is_err());
+    }
+}
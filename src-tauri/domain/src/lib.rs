@@ -4,28 +4,41 @@ use derive_new::new;
 use serde::{Deserialize, Serialize};
 
 pub mod all_game_cache;
+pub mod binary_reader;
 pub mod distance;
 pub mod erogamescape;
 pub mod explored_cache;
 pub mod extension;
 pub mod file;
+pub mod game_engine;
 pub mod game_matcher;
 pub mod icon;
+pub mod launch_profile;
 pub mod native_host_log;
 pub mod network;
+pub mod play_status;
 pub mod process;
 pub mod pubsub;
+pub mod remote_launch;
+pub mod remote_share;
+pub mod save_data;
 pub mod save_image_queue;
+pub mod sync_session;
 pub mod thumbnail;
 pub mod work_download_path;
+pub mod work_duplicate;
 pub mod work_link_pending_exe;
+pub mod work_override;
 pub mod work_parent_pack;
+pub mod work_query;
 pub mod works;
 
 pub mod repository;
 pub mod scan;
 pub mod service;
+pub mod shortcut;
 pub mod windows;
+pub mod wine;
 
 #[derive(new, Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Id<T> {
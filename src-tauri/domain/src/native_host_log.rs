@@ -23,6 +23,7 @@ pub enum HostLogType {
     Response = 31,
     EndProcessImageQueue = 32,
     AppSignalDispatchFailed = 33,
+    SyncGamesCompleted = 34,
 }
 
 #[derive(Debug, Clone)]
@@ -31,5 +32,229 @@ pub struct NativeHostLogRow {
     pub level: HostLogLevel,
     pub r#type: HostLogType,
     pub message: String,
+    pub request_id: Option<String>,
+    pub duration_ms: Option<i64>,
+    /// リクエストに含まれた件数（同期なら作品数）
+    pub item_count: Option<i64>,
+    pub error_count: Option<i64>,
     pub created_at: DateTime<Local>,
 }
+
+/// 追加するログ。message は保存時に `MAX_MESSAGE_BYTES` で切り詰める
+#[derive(Debug, Clone)]
+pub struct NewHostLog {
+    pub level: HostLogLevel,
+    pub r#type: HostLogType,
+    pub message: String,
+    pub request_id: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub item_count: Option<i64>,
+    pub error_count: Option<i64>,
+}
+
+impl NewHostLog {
+    pub fn new(level: HostLogLevel, r#type: HostLogType, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            r#type,
+            message: message.into(),
+            request_id: None,
+            duration_ms: None,
+            item_count: None,
+            error_count: None,
+        }
+    }
+
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    pub fn duration(mut self, duration: std::time::Duration) -> Self {
+        self.duration_ms = Some(i64::try_from(duration.as_millis()).unwrap_or(i64::MAX));
+        self
+    }
+
+    pub fn item_count(mut self, item_count: usize) -> Self {
+        self.item_count = Some(item_count as i64);
+        self
+    }
+
+    pub fn error_count(mut self, error_count: usize) -> Self {
+        self.error_count = Some(error_count as i64);
+        self
+    }
+}
+
+/// 1 件のログに残す message の上限。大きな同期のペイロードをそのまま残さない
+pub const MAX_MESSAGE_BYTES: usize = 8 * 1024;
+
+/// `max_bytes` を超える message を文字の境界で切り詰め、切り捨てたバイト数を書き添える
+pub fn truncate_message(message: &str, max_bytes: usize) -> String {
+    if message.len() <= max_bytes {
+        return message.to_string();
+    }
+    let mut end = max_bytes;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}... (truncated {} bytes)",
+        &message[..end],
+        message.len() - end
+    )
+}
+
+/// ログの絞り込み条件。すべて省略時は全件
+#[derive(Debug, Clone, Default)]
+pub struct HostLogFilter {
+    pub level: Option<HostLogLevel>,
+    pub r#type: Option<HostLogType>,
+    pub request_id: Option<String>,
+    /// この時刻以降（含む）
+    pub since: Option<DateTime<Local>>,
+    /// この時刻より前
+    pub until: Option<DateTime<Local>>,
+}
+
+/// ログの保持期間と件数。どちらかを超えた古いログから消す。
+/// 同期完了ログは GetStatus の集計に使うので対象外
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostLogRetention {
+    pub max_age_days: i64,
+    pub max_rows: i64,
+}
+
+impl Default for HostLogRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: 30,
+            max_rows: 50_000,
+        }
+    }
+}
+
+/// `SyncGamesCompleted` ログのメッセージ。GetStatus の集計に使うため書式を固定する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncCompletedLog {
+    /// "dmm" | "dlsite"
+    pub store: String,
+    pub extension_id: String,
+    pub success_count: u32,
+    pub new_count: u32,
+}
+
+impl HostLogLevel {
+    pub fn from_i64(v: i64) -> Option<Self> {
+        match v {
+            0 => Some(HostLogLevel::Debug),
+            1 => Some(HostLogLevel::Info),
+            2 => Some(HostLogLevel::Warn),
+            3 => Some(HostLogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+impl HostLogType {
+    pub fn from_i64(v: i64) -> Option<Self> {
+        match v {
+            0 => Some(HostLogType::Unknown),
+            1 => Some(HostLogType::ReceiveDmmSyncGamesRequest),
+            2 => Some(HostLogType::ReceiveDlsiteSyncGamesRequest),
+            10 => Some(HostLogType::ImageQueueWorkerStarted),
+            11 => Some(HostLogType::ImageQueueWorkerFinished),
+            20 => Some(HostLogType::ImageQueueItemStarted),
+            21 => Some(HostLogType::ImageQueueItemSucceeded),
+            22 => Some(HostLogType::ImageQueueItemFailed),
+            30 => Some(HostLogType::ReceiveRequest),
+            31 => Some(HostLogType::Response),
+            32 => Some(HostLogType::EndProcessImageQueue),
+            33 => Some(HostLogType::AppSignalDispatchFailed),
+            34 => Some(HostLogType::SyncGamesCompleted),
+            _ => None,
+        }
+    }
+}
+
+impl SyncCompletedLog {
+    pub fn to_message(&self) -> String {
+        format!(
+            "store={} extension_id={} success={} new={}",
+            self.store, self.extension_id, self.success_count, self.new_count
+        )
+    }
+
+    pub fn parse(message: &str) -> Option<Self> {
+        let mut store = None;
+        let mut extension_id = None;
+        let mut success_count = None;
+        let mut new_count = None;
+        for token in message.split_whitespace() {
+            let (key, value) = token.split_once('=')?;
+            match key {
+                "store" => store = Some(value.to_string()),
+                "extension_id" => extension_id = Some(value.to_string()),
+                "success" => success_count = value.parse().ok(),
+                "new" => new_count = value.parse().ok(),
+                _ => {}
+            }
+        }
+        Some(Self {
+            store: store?,
+            extension_id: extension_id?,
+            success_count: success_count?,
+            new_count: new_count?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_completed_log_roundtrip() {
+        let log = SyncCompletedLog {
+            store: "dmm".into(),
+            extension_id: "abcdef".into(),
+            success_count: 10,
+            new_count: 2,
+        };
+        assert_eq!(SyncCompletedLog::parse(&log.to_message()), Some(log));
+    }
+
+    #[test]
+    fn test_truncate_message_keeps_char_boundary() {
+        assert_eq!(truncate_message("short", 10), "short");
+        // "あ" は 3 バイト。4 バイト目で切ると文字の途中になる
+        assert_eq!(
+            truncate_message("ああ", 4),
+            "あ... (truncated 3 bytes)".to_string()
+        );
+    }
+
+    #[test]
+    fn test_log_enums_roundtrip() {
+        for typ in [
+            HostLogType::Unknown,
+            HostLogType::ReceiveRequest,
+            HostLogType::SyncGamesCompleted,
+        ] {
+            assert_eq!(
+                HostLogType::from_i64(typ as i64).map(|t| t as i64),
+                Some(typ as i64)
+            );
+        }
+        assert!(HostLogLevel::from_i64(9).is_none());
+    }
+
+    #[test]
+    fn test_sync_completed_log_parse_rejects_other_messages() {
+        assert_eq!(
+            SyncCompletedLog::parse("id: r1, request: HealthCheck"),
+            None
+        );
+        assert_eq!(SyncCompletedLog::parse("store=dmm success=1 new=0"), None);
+    }
+}
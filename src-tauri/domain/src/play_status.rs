@@ -0,0 +1,191 @@
+//! 作品ごとの遊んだ状況（ステータス、点数、始めた日と終えた日、ルートごとの攻略）
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::{works::Work, Id, StrId};
+
+/// 付けられる点数の上限。下限は 0
+pub const MAX_PLAY_SCORE: i32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayStatus {
+    Unplayed,
+    Playing,
+    Finished,
+    Dropped,
+    OnHold,
+}
+
+impl PlayStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlayStatus::Unplayed => "unplayed",
+            PlayStatus::Playing => "playing",
+            PlayStatus::Finished => "finished",
+            PlayStatus::Dropped => "dropped",
+            PlayStatus::OnHold => "on_hold",
+        }
+    }
+}
+
+impl std::str::FromStr for PlayStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unplayed" => Ok(PlayStatus::Unplayed),
+            "playing" => Ok(PlayStatus::Playing),
+            "finished" => Ok(PlayStatus::Finished),
+            "dropped" => Ok(PlayStatus::Dropped),
+            "on_hold" => Ok(PlayStatus::OnHold),
+            _ => anyhow::bail!("unknown play status: {s}"),
+        }
+    }
+}
+
+/// 作品ごとの遊んだ状況。まだ記録がなければ未プレイとして扱う
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkPlayState {
+    pub work_id: StrId<Work>,
+    pub status: PlayStatus,
+    /// 0 から `MAX_PLAY_SCORE` まで
+    pub score: Option<i32>,
+    pub started_on: Option<NaiveDate>,
+    pub finished_on: Option<NaiveDate>,
+}
+
+impl WorkPlayState {
+    /// 変更を検証し、ステータスに合わせて空の日付を `today` で埋めた状態を返す
+    pub fn apply(
+        work_id: StrId<Work>,
+        update: PlayStateUpdate,
+        today: NaiveDate,
+    ) -> anyhow::Result<Self> {
+        if let Some(score) = update.score {
+            if !(0..=MAX_PLAY_SCORE).contains(&score) {
+                anyhow::bail!("play score must be between 0 and {MAX_PLAY_SCORE}: {score}");
+            }
+        }
+        let mut started_on = update.started_on;
+        let mut finished_on = update.finished_on;
+        match update.status {
+            PlayStatus::Playing => {
+                started_on.get_or_insert(today);
+            }
+            PlayStatus::Finished => {
+                finished_on.get_or_insert(today);
+            }
+            _ => {}
+        }
+        if let (Some(started_on), Some(finished_on)) = (started_on, finished_on) {
+            if finished_on < started_on {
+                anyhow::bail!("finished date {finished_on} is before started date {started_on}");
+            }
+        }
+        Ok(Self {
+            work_id,
+            status: update.status,
+            score: update.score,
+            started_on,
+            finished_on,
+        })
+    }
+}
+
+/// 画面から渡される変更。日付を空にするとステータスに合わせて今日が入る
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayStateUpdate {
+    pub status: PlayStatus,
+    pub score: Option<i32>,
+    pub started_on: Option<NaiveDate>,
+    pub finished_on: Option<NaiveDate>,
+}
+
+/// ステータスを変えた履歴の1件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayStatusChange {
+    pub id: Id<PlayStatusChange>,
+    pub work_id: StrId<Work>,
+    pub status: PlayStatus,
+    pub changed_at: DateTime<Local>,
+}
+
+/// ヒロインやルートごとの攻略のチェック
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkRoute {
+    pub id: Id<WorkRoute>,
+    pub work_id: StrId<Work>,
+    pub name: String,
+    pub completed_at: Option<DateTime<Local>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewWorkRoute {
+    pub work_id: StrId<Work>,
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn update(status: PlayStatus) -> PlayStateUpdate {
+        PlayStateUpdate {
+            status,
+            score: None,
+            started_on: None,
+            finished_on: None,
+        }
+    }
+
+    #[test]
+    fn apply_ステータスに合わせて空の日付を埋める() {
+        let work_id = StrId::new("w".to_string());
+        let today = date("2024-05-01");
+
+        let playing =
+            WorkPlayState::apply(work_id.clone(), update(PlayStatus::Playing), today).unwrap();
+        assert_eq!(playing.started_on, Some(today));
+        assert_eq!(playing.finished_on, None);
+
+        let finished = WorkPlayState::apply(
+            work_id.clone(),
+            PlayStateUpdate {
+                started_on: Some(date("2024-04-01")),
+                ..update(PlayStatus::Finished)
+            },
+            today,
+        )
+        .unwrap();
+        assert_eq!(finished.started_on, Some(date("2024-04-01")));
+        assert_eq!(finished.finished_on, Some(today));
+
+        let dropped = WorkPlayState::apply(work_id, update(PlayStatus::Dropped), today).unwrap();
+        assert_eq!(dropped.started_on, None);
+        assert_eq!(dropped.finished_on, None);
+    }
+
+    #[test]
+    fn apply_範囲外の点数や逆順の日付は受け付けない() {
+        let work_id = StrId::new("w".to_string());
+        let today = date("2024-05-01");
+
+        let too_high = PlayStateUpdate {
+            score: Some(MAX_PLAY_SCORE + 1),
+            ..update(PlayStatus::Finished)
+        };
+        assert!(WorkPlayState::apply(work_id.clone(), too_high, today).is_err());
+
+        let reversed = PlayStateUpdate {
+            started_on: Some(date("2024-05-02")),
+            finished_on: Some(date("2024-05-01")),
+            ..update(PlayStatus::Finished)
+        };
+        assert!(WorkPlayState::apply(work_id, reversed, today).is_err());
+    }
+}
@@ -3,6 +3,9 @@ use derive_new::new;
 use serde::Serialize;
 use typeshare::typeshare;
 
+use crate::game_matcher::{
+    CandidateExplanation, MatchExplanation, MatchRule, PruneReason, PrunedCandidate, QuerySource,
+};
 use crate::service::app_signal_router::{AppSignal, AppSignalEvent, AppSignalSource};
 
 #[typeshare]
@@ -78,6 +81,47 @@ pub struct EnrichResultPayload {
     pub path: String,
     pub title: Option<String>,
     pub egs_id: Option<i32>,
+    pub explanation: Option<MatchExplanationPayload>,
+}
+
+#[typeshare]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchCandidateExplanationPayload {
+    pub egs_id: i32,
+    pub gamename: String,
+    pub score: f32,
+    pub rule: String,         // "exactMapping" | "normalizedIndex" | "partial" | "fuzzy"
+    pub query_source: String, // "parentDir" | "filename" | "other"
+    pub query: String,
+    pub normalized_query: String,
+    pub normalized_gamename: String,
+    pub ngram_overlap: Option<i32>,
+    pub distance: Option<i32>,
+}
+
+#[typeshare]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchPrunedCandidatePayload {
+    pub egs_id: i32,
+    pub gamename: String,
+    pub query_source: String,
+    pub query: String,
+    pub reason: String, // "ignoredGameId" | "ngramOverlap" | "distanceExceeded" | "belowThreshold"
+    pub ngram_overlap: Option<i32>,
+    pub ngram_required: Option<i32>,
+    pub distance: Option<i32>,
+    pub max_distance: Option<i32>,
+    pub score: Option<f32>,
+}
+
+#[typeshare]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchExplanationPayload {
+    pub candidates: Vec<MatchCandidateExplanationPayload>,
+    pub pruned: Vec<MatchPrunedCandidatePayload>,
 }
 
 #[typeshare]
@@ -125,6 +169,25 @@ pub struct ImageQueueItemErrorPayload {
     pub message: String,
 }
 
+// 共有ページからの起動要求をデスクトップで確認するためのイベントペイロード
+#[typeshare]
+#[derive(new, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteLaunchConfirmationPayload {
+    pub request_id: String,
+    pub work_id: String,
+    pub work_title: Option<String>,
+    pub timeout_seconds: i32,
+}
+
+#[typeshare]
+#[derive(new, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteLaunchConfirmationClosedPayload {
+    pub request_id: String,
+    pub decision: String, // "approved" | "rejected" | "timed-out"
+}
+
 #[typeshare]
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -163,6 +226,93 @@ pub enum AppSignalEventPayload {
     },
 }
 
+fn query_source_name(source: QuerySource) -> String {
+    match source {
+        QuerySource::ParentDir => "parentDir",
+        QuerySource::Filename => "filename",
+        QuerySource::Other => "other",
+    }
+    .to_string()
+}
+
+impl From<CandidateExplanation> for MatchCandidateExplanationPayload {
+    fn from(value: CandidateExplanation) -> Self {
+        let rule = match value.rule {
+            MatchRule::ExactMapping => "exactMapping",
+            MatchRule::NormalizedIndex => "normalizedIndex",
+            MatchRule::Partial => "partial",
+            MatchRule::Fuzzy => "fuzzy",
+        };
+        Self {
+            egs_id: value.game.id,
+            gamename: value.game.gamename,
+            score: value.score,
+            rule: rule.to_string(),
+            query_source: query_source_name(value.query.source),
+            query: value.query.value,
+            normalized_query: value.normalized_query,
+            normalized_gamename: value.normalized_gamename,
+            ngram_overlap: value.ngram_overlap.map(|v| v as i32),
+            distance: value.distance.map(|v| v as i32),
+        }
+    }
+}
+
+impl From<PrunedCandidate> for MatchPrunedCandidatePayload {
+    fn from(value: PrunedCandidate) -> Self {
+        let mut payload = Self {
+            egs_id: value.game.id,
+            gamename: value.game.gamename,
+            query_source: query_source_name(value.query.source),
+            query: value.query.value,
+            reason: String::new(),
+            ngram_overlap: None,
+            ngram_required: None,
+            distance: None,
+            max_distance: None,
+            score: None,
+        };
+        match value.reason {
+            PruneReason::IgnoredGameId => {
+                payload.reason = "ignoredGameId".into();
+            }
+            PruneReason::NGramOverlap { overlap, required } => {
+                payload.reason = "ngramOverlap".into();
+                payload.ngram_overlap = Some(overlap as i32);
+                payload.ngram_required = Some(required as i32);
+            }
+            PruneReason::DistanceExceeded {
+                overlap,
+                max_distance,
+            } => {
+                payload.reason = "distanceExceeded".into();
+                payload.ngram_overlap = Some(overlap as i32);
+                payload.max_distance = Some(max_distance as i32);
+            }
+            PruneReason::BelowThreshold {
+                overlap,
+                distance,
+                score,
+            } => {
+                payload.reason = "belowThreshold".into();
+                payload.ngram_overlap = Some(overlap as i32);
+                payload.distance = Some(distance as i32);
+                payload.score = Some(score);
+            }
+        }
+        payload
+    }
+}
+
+impl From<MatchExplanation> for MatchExplanationPayload {
+    fn from(value: MatchExplanation) -> Self {
+        Self {
+            candidates: value.candidates.into_iter().map(Into::into).collect(),
+            pruned: value.pruned.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 impl From<AppSignalSource> for AppSignalSourcePayload {
     fn from(value: AppSignalSource) -> Self {
         match value {
@@ -285,6 +435,10 @@ pub enum PubSubEvent {
     AppSignalRefetchWork(AppSignalPayload),
     #[serde(rename = "appSignal:refetchWorks")]
     AppSignalRefetchWorks(AppSignalPayload),
+    #[serde(rename = "remoteLaunchConfirmationRequested")]
+    RemoteLaunchConfirmationRequested(RemoteLaunchConfirmationPayload),
+    #[serde(rename = "remoteLaunchConfirmationClosed")]
+    RemoteLaunchConfirmationClosed(RemoteLaunchConfirmationClosedPayload),
 }
 
 impl PubSubEvent {
@@ -311,6 +465,10 @@ impl PubSubEvent {
             PubSubEvent::AppSignalShowErrorMessage(..) => "appSignal:showErrorMessage",
             PubSubEvent::AppSignalRefetchWork(..) => "appSignal:refetchWork",
             PubSubEvent::AppSignalRefetchWorks(..) => "appSignal:refetchWorks",
+            PubSubEvent::RemoteLaunchConfirmationRequested(..) => {
+                "remoteLaunchConfirmationRequested"
+            }
+            PubSubEvent::RemoteLaunchConfirmationClosed(..) => "remoteLaunchConfirmationClosed",
         }
     }
 }
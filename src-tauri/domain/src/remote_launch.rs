@@ -0,0 +1,240 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::Id;
+
+/// 共有ページからの起動要求をどう扱うか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteLaunchPolicy {
+    /// すべて断る
+    Disabled,
+    /// 許可リストにある作品だけ起動する
+    Allowlist,
+    /// デスクトップで確認してから起動する。時間内に答えがなければ断る。
+    /// 許可リストにある作品は確認しない
+    #[default]
+    AskFirst,
+}
+
+impl RemoteLaunchPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemoteLaunchPolicy::Disabled => "disabled",
+            RemoteLaunchPolicy::Allowlist => "allowlist",
+            RemoteLaunchPolicy::AskFirst => "ask_first",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "disabled" => Some(RemoteLaunchPolicy::Disabled),
+            "allowlist" => Some(RemoteLaunchPolicy::Allowlist),
+            "ask_first" => Some(RemoteLaunchPolicy::AskFirst),
+            _ => None,
+        }
+    }
+}
+
+pub const DEFAULT_CONFIRM_TIMEOUT_SECONDS: u32 = 30;
+pub const MIN_CONFIRM_TIMEOUT_SECONDS: u32 = 5;
+pub const MAX_CONFIRM_TIMEOUT_SECONDS: u32 = 300;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteLaunchSettings {
+    pub policy: RemoteLaunchPolicy,
+    /// 確認せずに起動してよい作品
+    pub allowed_work_ids: Vec<String>,
+    /// `AskFirst` の確認を待つ秒数
+    pub confirm_timeout_seconds: u32,
+}
+
+impl Default for RemoteLaunchSettings {
+    fn default() -> Self {
+        Self {
+            policy: RemoteLaunchPolicy::default(),
+            allowed_work_ids: vec![],
+            confirm_timeout_seconds: DEFAULT_CONFIRM_TIMEOUT_SECONDS,
+        }
+    }
+}
+
+impl RemoteLaunchSettings {
+    /// 設定だけで決まる判断。`None` ならデスクトップでの確認が要る
+    pub fn evaluate(&self, work_id: &str) -> Option<RemoteLaunchDecision> {
+        let allowlisted = self.allowed_work_ids.iter().any(|id| id == work_id);
+        match self.policy {
+            RemoteLaunchPolicy::Disabled => Some(RemoteLaunchDecision::Disabled),
+            RemoteLaunchPolicy::Allowlist if allowlisted => Some(RemoteLaunchDecision::Allowlisted),
+            RemoteLaunchPolicy::Allowlist => Some(RemoteLaunchDecision::NotAllowlisted),
+            RemoteLaunchPolicy::AskFirst if allowlisted => Some(RemoteLaunchDecision::Allowlisted),
+            RemoteLaunchPolicy::AskFirst => None,
+        }
+    }
+}
+
+/// 起動要求に対する判断。ack の `decision` にそのまま載せる
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteLaunchDecision {
+    Allowlisted,
+    NotAllowlisted,
+    Disabled,
+    /// デスクトップで許可された
+    Approved,
+    /// デスクトップで断られた
+    Rejected,
+    /// 確認の時間内に答えがなかった
+    TimedOut,
+}
+
+impl RemoteLaunchDecision {
+    pub fn is_permitted(&self) -> bool {
+        matches!(
+            self,
+            RemoteLaunchDecision::Allowlisted | RemoteLaunchDecision::Approved
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemoteLaunchDecision::Allowlisted => "allowlisted",
+            RemoteLaunchDecision::NotAllowlisted => "not-allowlisted",
+            RemoteLaunchDecision::Disabled => "disabled",
+            RemoteLaunchDecision::Approved => "approved",
+            RemoteLaunchDecision::Rejected => "rejected",
+            RemoteLaunchDecision::TimedOut => "timed-out",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            RemoteLaunchDecision::Allowlisted,
+            RemoteLaunchDecision::NotAllowlisted,
+            RemoteLaunchDecision::Disabled,
+            RemoteLaunchDecision::Approved,
+            RemoteLaunchDecision::Rejected,
+            RemoteLaunchDecision::TimedOut,
+        ]
+        .into_iter()
+        .find(|decision| decision.as_str() == value)
+    }
+}
+
+/// 起動要求の結果。ack の `status` にそのまま載せる
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteLaunchStatus {
+    Queued,
+    NotFound,
+    Error,
+    /// 判断で断った
+    Denied,
+}
+
+impl RemoteLaunchStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemoteLaunchStatus::Queued => "queued",
+            RemoteLaunchStatus::NotFound => "not-found",
+            RemoteLaunchStatus::Error => "error",
+            RemoteLaunchStatus::Denied => "denied",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            RemoteLaunchStatus::Queued,
+            RemoteLaunchStatus::NotFound,
+            RemoteLaunchStatus::Error,
+            RemoteLaunchStatus::Denied,
+        ]
+        .into_iter()
+        .find(|status| status.as_str() == value)
+    }
+}
+
+/// 起動要求の監査ログに追加する行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewRemoteLaunchAudit {
+    pub work_id: String,
+    /// 要求の時点の作品名。作品が消えてもログで分かるように残す
+    pub work_title: Option<String>,
+    pub policy: RemoteLaunchPolicy,
+    pub decision: RemoteLaunchDecision,
+    pub status: RemoteLaunchStatus,
+    pub error_message: Option<String>,
+    pub requested_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteLaunchAudit {
+    pub id: Id<RemoteLaunchAudit>,
+    pub work_id: String,
+    pub work_title: Option<String>,
+    pub policy: RemoteLaunchPolicy,
+    pub decision: RemoteLaunchDecision,
+    pub status: RemoteLaunchStatus,
+    pub error_message: Option<String>,
+    pub requested_at: DateTime<Local>,
+    pub finished_at: DateTime<Local>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(policy: RemoteLaunchPolicy) -> RemoteLaunchSettings {
+        RemoteLaunchSettings {
+            policy,
+            allowed_work_ids: vec!["work-1".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn evaluate_disabledはすべて断る() {
+        let decision = settings(RemoteLaunchPolicy::Disabled).evaluate("work-1");
+        assert_eq!(decision, Some(RemoteLaunchDecision::Disabled));
+    }
+
+    #[test]
+    fn evaluate_allowlistは許可リストの作品だけ通す() {
+        let settings = settings(RemoteLaunchPolicy::Allowlist);
+        assert_eq!(
+            settings.evaluate("work-1"),
+            Some(RemoteLaunchDecision::Allowlisted)
+        );
+        assert_eq!(
+            settings.evaluate("work-2"),
+            Some(RemoteLaunchDecision::NotAllowlisted)
+        );
+    }
+
+    #[test]
+    fn evaluate_ask_firstは許可リストにない作品だけ確認する() {
+        let settings = settings(RemoteLaunchPolicy::AskFirst);
+        assert_eq!(
+            settings.evaluate("work-1"),
+            Some(RemoteLaunchDecision::Allowlisted)
+        );
+        assert_eq!(settings.evaluate("work-2"), None);
+    }
+
+    #[test]
+    fn decision_文字列と相互に変換できる() {
+        for decision in [
+            RemoteLaunchDecision::Allowlisted,
+            RemoteLaunchDecision::NotAllowlisted,
+            RemoteLaunchDecision::Disabled,
+            RemoteLaunchDecision::Approved,
+            RemoteLaunchDecision::Rejected,
+            RemoteLaunchDecision::TimedOut,
+        ] {
+            assert_eq!(
+                RemoteLaunchDecision::parse(decision.as_str()),
+                Some(decision)
+            );
+        }
+        assert!(RemoteLaunchDecision::Approved.is_permitted());
+        assert!(!RemoteLaunchDecision::TimedOut.is_permitted());
+    }
+}
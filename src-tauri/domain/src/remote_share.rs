@@ -0,0 +1,128 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Local};
+
+/// リモート共有へ最後に送った作品の状態。
+/// 次の同期ではハッシュが変わった作品と、ここにあって手元から消えた作品（墓標）だけを送る
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteShareWorkState {
+    pub work_id: String,
+    /// 送った内容（作品情報とサムネイル）のハッシュ
+    pub content_hash: String,
+    /// 内容に関わらず次の同期で送り直す。サムネイルを送れなかったときやサーバーと食い違ったときに立てる
+    pub dirty: bool,
+    pub synced_at: DateTime<Local>,
+}
+
+/// 同期に成功した作品と、そのとき送った内容のハッシュ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncedRemoteShareWork {
+    pub work_id: String,
+    pub content_hash: String,
+    pub dirty: bool,
+}
+
+/// 手元の作品と前回送った内容を比べて決めた、今回送る差分
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RemoteShareDelta {
+    /// 増えた作品、内容が変わった作品、dirty が立っている作品。`current` の順
+    pub changed_work_ids: Vec<String>,
+    /// 前回送ったが手元から消えた作品。work_id の昇順
+    pub removed_work_ids: Vec<String>,
+    pub unchanged_count: usize,
+}
+
+impl RemoteShareDelta {
+    /// `current` は手元の作品の (work_id, content_hash)
+    pub fn plan(current: &[(String, String)], states: &[RemoteShareWorkState]) -> Self {
+        let by_id: HashMap<&str, &RemoteShareWorkState> =
+            states.iter().map(|s| (s.work_id.as_str(), s)).collect();
+        let mut delta = Self::default();
+        for (work_id, content_hash) in current {
+            match by_id.get(work_id.as_str()) {
+                Some(state) if !state.dirty && state.content_hash == *content_hash => {
+                    delta.unchanged_count += 1
+                }
+                _ => delta.changed_work_ids.push(work_id.clone()),
+            }
+        }
+
+        let current_ids: HashSet<&str> = current.iter().map(|(id, _)| id.as_str()).collect();
+        let mut removed = states
+            .iter()
+            .filter(|s| !current_ids.contains(s.work_id.as_str()))
+            .map(|s| s.work_id.clone())
+            .collect::<Vec<_>>();
+        removed.sort();
+        delta.removed_work_ids = removed;
+        delta
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changed_work_ids.is_empty() && self.removed_work_ids.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RemoteShareSyncError {
+    /// サーバーの内容が手元の記録と食い違っている。全件を送り直せば解消する
+    #[error("remote share server requires a full resync: {0}")]
+    ResyncRequired(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(work_id: &str, content_hash: &str, dirty: bool) -> RemoteShareWorkState {
+        RemoteShareWorkState {
+            work_id: work_id.into(),
+            content_hash: content_hash.into(),
+            dirty,
+            synced_at: Local::now(),
+        }
+    }
+
+    fn current(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(id, hash)| (id.to_string(), hash.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn plan_変わった作品と消えた作品だけを選ぶ() {
+        let delta = RemoteShareDelta::plan(
+            &current(&[("w1", "h1"), ("w2", "h2-new"), ("w4", "h4")]),
+            &[
+                state("w1", "h1", false),
+                state("w2", "h2", false),
+                state("w3", "h3", false),
+            ],
+        );
+
+        assert_eq!(delta.changed_work_ids, vec!["w2", "w4"]);
+        assert_eq!(delta.removed_work_ids, vec!["w3"]);
+        assert_eq!(delta.unchanged_count, 1);
+        assert!(!delta.is_empty());
+    }
+
+    #[test]
+    fn plan_dirtyなら内容が同じでも送り直す() {
+        let delta = RemoteShareDelta::plan(
+            &current(&[("w1", "h1"), ("w2", "h2")]),
+            &[state("w1", "h1", true), state("w2", "h2", false)],
+        );
+
+        assert_eq!(delta.changed_work_ids, vec!["w1"]);
+        assert!(delta.removed_work_ids.is_empty());
+        assert_eq!(delta.unchanged_count, 1);
+    }
+
+    #[test]
+    fn plan_変化がなければ空() {
+        let delta = RemoteShareDelta::plan(&current(&[("w1", "h1")]), &[state("w1", "h1", false)]);
+        assert!(delta.is_empty());
+        assert_eq!(delta.unchanged_count, 1);
+    }
+}
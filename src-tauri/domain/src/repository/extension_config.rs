@@ -0,0 +1,11 @@
+use anyhow::Result;
+
+use crate::extension::ExtensionConfig;
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait ExtensionConfigRepository {
+    /// 保存済みの設定。一度も保存していなければ None
+    async fn get(&mut self) -> Result<Option<ExtensionConfig>>;
+    async fn upsert(&mut self, config: &ExtensionConfig) -> Result<()>;
+}
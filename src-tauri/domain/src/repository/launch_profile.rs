@@ -0,0 +1,21 @@
+use anyhow::Result;
+
+use crate::launch_profile::{LaunchProfile, NewLaunchProfile};
+use crate::{works::Work, Id, StrId};
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait LaunchProfileRepository {
+    /// 既定のプロファイルを先頭に、作成順で返す
+    async fn list_by_work_id(&mut self, work_id: StrId<Work>) -> Result<Vec<LaunchProfile>>;
+    async fn find_by_id(&mut self, id: Id<LaunchProfile>) -> Result<Option<LaunchProfile>>;
+    async fn find_default_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+    ) -> Result<Option<LaunchProfile>>;
+    /// `is_default` なら同じ作品の他のプロファイルを既定から外す
+    async fn insert(&mut self, profile: &NewLaunchProfile) -> Result<Id<LaunchProfile>>;
+    /// `is_default` なら同じ作品の他のプロファイルを既定から外す。作品は付け替えない
+    async fn update(&mut self, id: Id<LaunchProfile>, profile: &NewLaunchProfile) -> Result<()>;
+    async fn delete(&mut self, id: Id<LaunchProfile>) -> Result<()>;
+}
@@ -1,11 +1,13 @@
 use std::sync::Arc;
-use tauri::async_runtime::Mutex;
+use tokio::sync::Mutex;
 
 #[derive(Clone)]
 pub struct TestRepositories {
     pub explored_cache: Arc<Mutex<crate::repository::explored_cache::MockExploredCacheRepository>>,
     pub all_game_cache: Arc<Mutex<crate::repository::all_game_cache::MockAllGameCacheRepository>>,
     pub app_settings: Arc<Mutex<crate::repository::app_settings::MockAppSettingsRepository>>,
+    pub extension_config:
+        Arc<Mutex<crate::repository::extension_config::MockExtensionConfigRepository>>,
     pub image_queue: Arc<Mutex<crate::repository::save_image_queue::MockImageSaveQueueRepository>>,
     pub host_log: Arc<Mutex<crate::repository::native_host_log::MockNativeHostLogRepository>>,
     pub dmm_work: Arc<Mutex<crate::repository::works::MockDmmWorkRepository>>,
@@ -20,6 +22,20 @@ pub struct TestRepositories {
     pub work_link_pending_exe:
         Arc<Mutex<crate::work_link_pending_exe::MockWorkLinkPendingExeRepository>>,
     pub erogamescape: Arc<Mutex<crate::repository::erogamescape::MockErogamescapeRepository>>,
+    pub sync_session: Arc<Mutex<crate::repository::sync_session::MockSyncSessionRepository>>,
+    pub remote_share_state:
+        Arc<Mutex<crate::repository::remote_share_state::MockRemoteShareStateRepository>>,
+    pub remote_launch: Arc<Mutex<crate::repository::remote_launch::MockRemoteLaunchRepository>>,
+    pub launch_profile: Arc<Mutex<crate::repository::launch_profile::MockLaunchProfileRepository>>,
+    pub wine_config: Arc<Mutex<crate::repository::wine_config::MockWineConfigRepository>>,
+    pub save_location: Arc<Mutex<crate::repository::save_location::MockSaveLocationRepository>>,
+    pub work_play_state:
+        Arc<Mutex<crate::repository::work_play_state::MockWorkPlayStateRepository>>,
+    pub work_route: Arc<Mutex<crate::repository::work_route::MockWorkRouteRepository>>,
+    pub work_duplicate:
+        Arc<Mutex<crate::repository::work_duplicate::MockWorkDuplicateRepository>>,
+    pub work_override:
+        Arc<Mutex<crate::repository::work_override::MockWorkOverrideRepository>>,
 }
 
 impl Default for TestRepositories {
@@ -28,6 +44,7 @@ impl Default for TestRepositories {
             explored_cache: Arc::new(Mutex::new(Default::default())),
             all_game_cache: Arc::new(Mutex::new(Default::default())),
             app_settings: Arc::new(Mutex::new(Default::default())),
+            extension_config: Arc::new(Mutex::new(Default::default())),
             image_queue: Arc::new(Mutex::new(Default::default())),
             host_log: Arc::new(Mutex::new(Default::default())),
             dmm_work: Arc::new(Mutex::new(Default::default())),
@@ -39,6 +56,16 @@ impl Default for TestRepositories {
             work_like: Arc::new(Mutex::new(Default::default())),
             work_link_pending_exe: Arc::new(Mutex::new(Default::default())),
             erogamescape: Arc::new(Mutex::new(Default::default())),
+            sync_session: Arc::new(Mutex::new(Default::default())),
+            remote_share_state: Arc::new(Mutex::new(Default::default())),
+            remote_launch: Arc::new(Mutex::new(Default::default())),
+            launch_profile: Arc::new(Mutex::new(Default::default())),
+            wine_config: Arc::new(Mutex::new(Default::default())),
+            save_location: Arc::new(Mutex::new(Default::default())),
+            work_play_state: Arc::new(Mutex::new(Default::default())),
+            work_route: Arc::new(Mutex::new(Default::default())),
+            work_duplicate: Arc::new(Mutex::new(Default::default())),
+            work_override: Arc::new(Mutex::new(Default::default())),
         }
     }
 }
@@ -49,6 +76,7 @@ impl crate::repository::RepositoriesExt for TestRepositories {
     type DlsiteWorkRepo = TestRepositories;
     type AllGameCacheRepo = TestRepositories;
     type AppSettingsRepo = TestRepositories;
+    type ExtensionConfigRepo = TestRepositories;
     type ExploredCacheRepo = TestRepositories;
     type ImageQueueRepo = TestRepositories;
     type HostLogRepo = TestRepositories;
@@ -58,6 +86,16 @@ impl crate::repository::RepositoriesExt for TestRepositories {
     type WorkLnkRepo = TestRepositories;
     type WorkLikeRepo = TestRepositories;
     type WorkLinkPendingExeRepo = TestRepositories;
+    type SyncSessionRepo = TestRepositories;
+    type RemoteShareStateRepo = TestRepositories;
+    type RemoteLaunchRepo = TestRepositories;
+    type LaunchProfileRepo = TestRepositories;
+    type WineConfigRepo = TestRepositories;
+    type SaveLocationRepo = TestRepositories;
+    type WorkPlayStateRepo = TestRepositories;
+    type WorkRouteRepo = TestRepositories;
+    type WorkDuplicateRepo = TestRepositories;
+    type WorkOverrideRepo = TestRepositories;
     fn work(&self) -> Self::WorkRepo {
         self.clone()
     }
@@ -73,6 +111,39 @@ impl crate::repository::RepositoriesExt for TestRepositories {
     fn app_settings(&self) -> Self::AppSettingsRepo {
         self.clone()
     }
+    fn extension_config(&self) -> Self::ExtensionConfigRepo {
+        self.clone()
+    }
+    fn sync_session(&self) -> Self::SyncSessionRepo {
+        self.clone()
+    }
+    fn remote_share_state(&self) -> Self::RemoteShareStateRepo {
+        self.clone()
+    }
+    fn remote_launch(&self) -> Self::RemoteLaunchRepo {
+        self.clone()
+    }
+    fn launch_profile(&self) -> Self::LaunchProfileRepo {
+        self.clone()
+    }
+    fn wine_config(&self) -> Self::WineConfigRepo {
+        self.clone()
+    }
+    fn save_location(&self) -> Self::SaveLocationRepo {
+        self.clone()
+    }
+    fn work_play_state(&self) -> Self::WorkPlayStateRepo {
+        self.clone()
+    }
+    fn work_route(&self) -> Self::WorkRouteRepo {
+        self.clone()
+    }
+    fn work_duplicate(&self) -> Self::WorkDuplicateRepo {
+        self.clone()
+    }
+    fn work_override(&self) -> Self::WorkOverrideRepo {
+        self.clone()
+    }
     fn explored_cache(&self) -> Self::ExploredCacheRepo {
         self.clone()
     }
@@ -115,6 +186,18 @@ impl crate::repository::works::WorkRepository for TestRepositories {
     async fn list_all_details(&mut self) -> anyhow::Result<Vec<crate::works::WorkDetails>> {
         self.work.lock().await.list_all_details().await
     }
+    async fn query_details(
+        &mut self,
+        query: &crate::work_query::WorkQuery,
+    ) -> anyhow::Result<crate::work_query::WorkPage> {
+        self.work.lock().await.query_details(query).await
+    }
+    async fn count_facets(
+        &mut self,
+        filter: &crate::work_query::WorkListFilter,
+    ) -> anyhow::Result<crate::work_query::WorkFacets> {
+        self.work.lock().await.count_facets(filter).await
+    }
     async fn find_details_by_work_id(
         &mut self,
         work_id: crate::StrId<crate::works::Work>,
@@ -173,6 +256,30 @@ impl crate::repository::works::WorkRepository for TestRepositories {
             .upsert_work_thumbnail_size(work_id, width, height)
             .await
     }
+
+    async fn list_work_ids_missing_thumbnail_variants(
+        &mut self,
+    ) -> anyhow::Result<Vec<crate::StrId<crate::works::Work>>> {
+        self.work
+            .lock()
+            .await
+            .list_work_ids_missing_thumbnail_variants()
+            .await
+    }
+
+    async fn replace_work_thumbnail_variants(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+        variants: Vec<crate::thumbnail::WorkThumbnailVariant>,
+        placeholder_color: Option<String>,
+        perceptual_hash: Option<u64>,
+    ) -> anyhow::Result<()> {
+        self.work
+            .lock()
+            .await
+            .replace_work_thumbnail_variants(work_id, variants, placeholder_color, perceptual_hash)
+            .await
+    }
     async fn update_last_play_at_by_work_id(
         &mut self,
         work_id: crate::StrId<crate::works::Work>,
@@ -196,6 +303,17 @@ impl crate::repository::works::WorkRepository for TestRepositories {
             .update_install_by_work_id(work_id, install_at, original_path)
             .await
     }
+    async fn update_install_engine_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+        engine: Option<crate::game_engine::GameEngine>,
+    ) -> anyhow::Result<()> {
+        self.work
+            .lock()
+            .await
+            .update_install_engine_by_work_id(work_id, engine)
+            .await
+    }
 }
 
 impl crate::repository::erogamescape::ErogamescapeRepository for TestRepositories {
@@ -255,6 +373,46 @@ impl crate::repository::works::DmmWorkRepository for TestRepositories {
     ) -> anyhow::Result<Option<crate::works::DmmWork>> {
         self.dmm_work.lock().await.find_by_work_id(work_id).await
     }
+    async fn count(&mut self) -> anyhow::Result<i64> {
+        self.dmm_work.lock().await.count().await
+    }
+    async fn find_existing_store_ids(
+        &mut self,
+        store_ids: &[String],
+    ) -> anyhow::Result<Vec<String>> {
+        self.dmm_work
+            .lock()
+            .await
+            .find_existing_store_ids(store_ids)
+            .await
+    }
+    async fn mark_seen(
+        &mut self,
+        store_ids: &[String],
+        seen_at: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<()> {
+        self.dmm_work
+            .lock()
+            .await
+            .mark_seen(store_ids, seen_at)
+            .await
+    }
+    async fn flag_missing_not_seen_since(
+        &mut self,
+        since: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<Vec<String>> {
+        self.dmm_work
+            .lock()
+            .await
+            .flag_missing_not_seen_since(since)
+            .await
+    }
+    async fn list_missing(&mut self) -> anyhow::Result<Vec<crate::works::MissingStoreWork>> {
+        self.dmm_work.lock().await.list_missing().await
+    }
+    async fn delete_missing(&mut self, store_ids: &[String]) -> anyhow::Result<u64> {
+        self.dmm_work.lock().await.delete_missing(store_ids).await
+    }
 }
 
 impl crate::repository::works::DlsiteWorkRepository for TestRepositories {
@@ -285,6 +443,50 @@ impl crate::repository::works::DlsiteWorkRepository for TestRepositories {
             .find_by_store_id(store_id)
             .await
     }
+    async fn count(&mut self) -> anyhow::Result<i64> {
+        self.dlsite_work.lock().await.count().await
+    }
+    async fn find_existing_store_ids(
+        &mut self,
+        store_ids: &[String],
+    ) -> anyhow::Result<Vec<String>> {
+        self.dlsite_work
+            .lock()
+            .await
+            .find_existing_store_ids(store_ids)
+            .await
+    }
+    async fn mark_seen(
+        &mut self,
+        store_ids: &[String],
+        seen_at: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<()> {
+        self.dlsite_work
+            .lock()
+            .await
+            .mark_seen(store_ids, seen_at)
+            .await
+    }
+    async fn flag_missing_not_seen_since(
+        &mut self,
+        since: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<Vec<String>> {
+        self.dlsite_work
+            .lock()
+            .await
+            .flag_missing_not_seen_since(since)
+            .await
+    }
+    async fn list_missing(&mut self) -> anyhow::Result<Vec<crate::works::MissingStoreWork>> {
+        self.dlsite_work.lock().await.list_missing().await
+    }
+    async fn delete_missing(&mut self, store_ids: &[String]) -> anyhow::Result<u64> {
+        self.dlsite_work
+            .lock()
+            .await
+            .delete_missing(store_ids)
+            .await
+    }
 }
 
 impl crate::repository::all_game_cache::AllGameCacheRepository for TestRepositories {
@@ -327,7 +529,11 @@ impl crate::repository::app_settings::AppSettingsRepository for TestRepositories
         &mut self,
         settings: &crate::repository::app_settings::AppStorageSettings,
     ) -> anyhow::Result<()> {
-        self.app_settings.lock().await.set_storage_settings(settings).await
+        self.app_settings
+            .lock()
+            .await
+            .set_storage_settings(settings)
+            .await
     }
 }
 
@@ -361,6 +567,31 @@ impl crate::repository::save_image_queue::ImageSaveQueueRepository for TestRepos
     ) -> anyhow::Result<Vec<crate::save_image_queue::ImageSaveQueueRow>> {
         self.image_queue.lock().await.list(unfinished, limit).await
     }
+    async fn list_due(
+        &mut self,
+        limit: i64,
+    ) -> anyhow::Result<Vec<crate::save_image_queue::ImageSaveQueueRow>> {
+        self.image_queue.lock().await.list_due(limit).await
+    }
+    async fn list_dst_paths_not_succeeded(
+        &mut self,
+        preprocess: crate::save_image_queue::ImagePreprocess,
+    ) -> anyhow::Result<Vec<String>> {
+        self.image_queue
+            .lock()
+            .await
+            .list_dst_paths_not_succeeded(preprocess)
+            .await
+    }
+    async fn next_attempt_at(&mut self) -> anyhow::Result<Option<chrono::DateTime<chrono::Local>>> {
+        self.image_queue.lock().await.next_attempt_at().await
+    }
+    async fn list_failed(
+        &mut self,
+        limit: i64,
+    ) -> anyhow::Result<Vec<crate::save_image_queue::ImageSaveQueueRow>> {
+        self.image_queue.lock().await.list_failed(limit).await
+    }
     async fn count(&mut self, unfinished: bool) -> anyhow::Result<i64> {
         self.image_queue.lock().await.count(unfinished).await
     }
@@ -377,6 +608,30 @@ impl crate::repository::save_image_queue::ImageSaveQueueRepository for TestRepos
     ) -> anyhow::Result<()> {
         self.image_queue.lock().await.mark_failed(id, error).await
     }
+    async fn mark_retry(
+        &mut self,
+        id: crate::Id<crate::save_image_queue::ImageSaveQueueRow>,
+        error: &str,
+        delay: std::time::Duration,
+    ) -> anyhow::Result<()> {
+        self.image_queue
+            .lock()
+            .await
+            .mark_retry(id, error, delay)
+            .await
+    }
+    async fn requeue_failed(
+        &mut self,
+        ids: Vec<crate::Id<crate::save_image_queue::ImageSaveQueueRow>>,
+    ) -> anyhow::Result<u64> {
+        self.image_queue.lock().await.requeue_failed(ids).await
+    }
+    async fn discard_failed(
+        &mut self,
+        ids: Vec<crate::Id<crate::save_image_queue::ImageSaveQueueRow>>,
+    ) -> anyhow::Result<u64> {
+        self.image_queue.lock().await.discard_failed(ids).await
+    }
 }
 
 impl crate::repository::native_host_log::NativeHostLogRepository for TestRepositories {
@@ -392,25 +647,35 @@ impl crate::repository::native_host_log::NativeHostLogRepository for TestReposit
             .insert_log(level, typ, message)
             .await
     }
+    async fn insert(&mut self, log: &crate::native_host_log::NewHostLog) -> anyhow::Result<()> {
+        self.host_log.lock().await.insert(log).await
+    }
     async fn list_logs(
         &mut self,
         limit: i64,
         offset: i64,
-        level: Option<crate::native_host_log::HostLogLevel>,
-        typ: Option<crate::native_host_log::HostLogType>,
+        filter: &crate::native_host_log::HostLogFilter,
     ) -> anyhow::Result<Vec<crate::native_host_log::NativeHostLogRow>> {
         self.host_log
             .lock()
             .await
-            .list_logs(limit, offset, level, typ)
+            .list_logs(limit, offset, filter)
             .await
     }
     async fn count_logs(
         &mut self,
-        level: Option<crate::native_host_log::HostLogLevel>,
-        typ: Option<crate::native_host_log::HostLogType>,
+        filter: &crate::native_host_log::HostLogFilter,
     ) -> anyhow::Result<i64> {
-        self.host_log.lock().await.count_logs(level, typ).await
+        self.host_log.lock().await.count_logs(filter).await
+    }
+    async fn delete_older_than(
+        &mut self,
+        before: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<u64> {
+        self.host_log.lock().await.delete_older_than(before).await
+    }
+    async fn delete_exceeding(&mut self, keep: i64) -> anyhow::Result<u64> {
+        self.host_log.lock().await.delete_exceeding(keep).await
     }
 }
 
@@ -586,3 +851,344 @@ impl crate::repository::manager::RepositoryManager<TestRepositories> for TestRep
         futures::FutureExt::boxed(async move { f(self.repos.clone()).await })
     }
 }
+
+impl crate::repository::extension_config::ExtensionConfigRepository for TestRepositories {
+    async fn get(&mut self) -> anyhow::Result<Option<crate::extension::ExtensionConfig>> {
+        self.extension_config.lock().await.get().await
+    }
+    async fn upsert(&mut self, config: &crate::extension::ExtensionConfig) -> anyhow::Result<()> {
+        self.extension_config.lock().await.upsert(config).await
+    }
+}
+
+impl crate::repository::sync_session::SyncSessionRepository for TestRepositories {
+    async fn get(
+        &mut self,
+        id: &crate::StrId<crate::sync_session::SyncSession>,
+    ) -> anyhow::Result<Option<crate::sync_session::SyncSession>> {
+        self.sync_session.lock().await.get(id).await
+    }
+    async fn insert(
+        &mut self,
+        session: &crate::sync_session::NewSyncSession,
+    ) -> anyhow::Result<()> {
+        self.sync_session.lock().await.insert(session).await
+    }
+    async fn list_chunks(
+        &mut self,
+        id: &crate::StrId<crate::sync_session::SyncSession>,
+    ) -> anyhow::Result<Vec<crate::sync_session::SyncSessionChunk>> {
+        self.sync_session.lock().await.list_chunks(id).await
+    }
+    async fn insert_chunk(
+        &mut self,
+        id: &crate::StrId<crate::sync_session::SyncSession>,
+        chunk: &crate::sync_session::SyncSessionChunk,
+    ) -> anyhow::Result<()> {
+        self.sync_session.lock().await.insert_chunk(id, chunk).await
+    }
+    async fn mark_committed(
+        &mut self,
+        id: &crate::StrId<crate::sync_session::SyncSession>,
+    ) -> anyhow::Result<()> {
+        self.sync_session.lock().await.mark_committed(id).await
+    }
+    async fn delete_stale(
+        &mut self,
+        before: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<u64> {
+        self.sync_session.lock().await.delete_stale(before).await
+    }
+}
+
+impl crate::repository::remote_share_state::RemoteShareStateRepository for TestRepositories {
+    async fn list(
+        &mut self,
+        device_id: &str,
+    ) -> anyhow::Result<Vec<crate::remote_share::RemoteShareWorkState>> {
+        self.remote_share_state.lock().await.list(device_id).await
+    }
+    async fn upsert_synced(
+        &mut self,
+        device_id: &str,
+        works: &[crate::remote_share::SyncedRemoteShareWork],
+    ) -> anyhow::Result<()> {
+        self.remote_share_state
+            .lock()
+            .await
+            .upsert_synced(device_id, works)
+            .await
+    }
+    async fn delete(&mut self, device_id: &str, work_ids: &[String]) -> anyhow::Result<u64> {
+        self.remote_share_state
+            .lock()
+            .await
+            .delete(device_id, work_ids)
+            .await
+    }
+    async fn mark_all_dirty(&mut self, device_id: &str) -> anyhow::Result<u64> {
+        self.remote_share_state
+            .lock()
+            .await
+            .mark_all_dirty(device_id)
+            .await
+    }
+    async fn delete_other_devices(&mut self, device_id: &str) -> anyhow::Result<u64> {
+        self.remote_share_state
+            .lock()
+            .await
+            .delete_other_devices(device_id)
+            .await
+    }
+}
+
+impl crate::repository::remote_launch::RemoteLaunchRepository for TestRepositories {
+    async fn get_settings(&mut self) -> anyhow::Result<crate::remote_launch::RemoteLaunchSettings> {
+        self.remote_launch.lock().await.get_settings().await
+    }
+    async fn set_settings(
+        &mut self,
+        settings: &crate::remote_launch::RemoteLaunchSettings,
+    ) -> anyhow::Result<()> {
+        self.remote_launch.lock().await.set_settings(settings).await
+    }
+    async fn add_allowed_work(&mut self, work_id: &str) -> anyhow::Result<()> {
+        self.remote_launch
+            .lock()
+            .await
+            .add_allowed_work(work_id)
+            .await
+    }
+    async fn insert_audit(
+        &mut self,
+        audit: &crate::remote_launch::NewRemoteLaunchAudit,
+    ) -> anyhow::Result<()> {
+        self.remote_launch.lock().await.insert_audit(audit).await
+    }
+    async fn list_audits(
+        &mut self,
+        limit: i64,
+        offset: i64,
+    ) -> anyhow::Result<Vec<crate::remote_launch::RemoteLaunchAudit>> {
+        self.remote_launch
+            .lock()
+            .await
+            .list_audits(limit, offset)
+            .await
+    }
+}
+
+impl crate::repository::launch_profile::LaunchProfileRepository for TestRepositories {
+    async fn list_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<Vec<crate::launch_profile::LaunchProfile>> {
+        self.launch_profile
+            .lock()
+            .await
+            .list_by_work_id(work_id)
+            .await
+    }
+    async fn find_by_id(
+        &mut self,
+        id: crate::Id<crate::launch_profile::LaunchProfile>,
+    ) -> anyhow::Result<Option<crate::launch_profile::LaunchProfile>> {
+        self.launch_profile.lock().await.find_by_id(id).await
+    }
+    async fn find_default_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<Option<crate::launch_profile::LaunchProfile>> {
+        self.launch_profile
+            .lock()
+            .await
+            .find_default_by_work_id(work_id)
+            .await
+    }
+    async fn insert(
+        &mut self,
+        profile: &crate::launch_profile::NewLaunchProfile,
+    ) -> anyhow::Result<crate::Id<crate::launch_profile::LaunchProfile>> {
+        self.launch_profile.lock().await.insert(profile).await
+    }
+    async fn update(
+        &mut self,
+        id: crate::Id<crate::launch_profile::LaunchProfile>,
+        profile: &crate::launch_profile::NewLaunchProfile,
+    ) -> anyhow::Result<()> {
+        self.launch_profile.lock().await.update(id, profile).await
+    }
+    async fn delete(
+        &mut self,
+        id: crate::Id<crate::launch_profile::LaunchProfile>,
+    ) -> anyhow::Result<()> {
+        self.launch_profile.lock().await.delete(id).await
+    }
+}
+
+impl crate::repository::wine_config::WineConfigRepository for TestRepositories {
+    async fn find_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<Option<crate::wine::WineConfig>> {
+        self.wine_config.lock().await.find_by_work_id(work_id).await
+    }
+    async fn upsert(&mut self, config: &crate::wine::WineConfig) -> anyhow::Result<()> {
+        self.wine_config.lock().await.upsert(config).await
+    }
+    async fn delete_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<()> {
+        self.wine_config.lock().await.delete_by_work_id(work_id).await
+    }
+}
+
+impl crate::repository::save_location::SaveLocationRepository for TestRepositories {
+    async fn list_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<Vec<crate::save_data::SaveLocation>> {
+        self.save_location
+            .lock()
+            .await
+            .list_by_work_id(work_id)
+            .await
+    }
+    async fn insert(
+        &mut self,
+        location: &crate::save_data::NewSaveLocation,
+    ) -> anyhow::Result<crate::Id<crate::save_data::SaveLocation>> {
+        self.save_location.lock().await.insert(location).await
+    }
+    async fn delete(
+        &mut self,
+        id: crate::Id<crate::save_data::SaveLocation>,
+    ) -> anyhow::Result<()> {
+        self.save_location.lock().await.delete(id).await
+    }
+    async fn delete_detected_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<()> {
+        self.save_location
+            .lock()
+            .await
+            .delete_detected_by_work_id(work_id)
+            .await
+    }
+}
+
+impl crate::repository::work_play_state::WorkPlayStateRepository for TestRepositories {
+    async fn find_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<Option<crate::play_status::WorkPlayState>> {
+        self.work_play_state
+            .lock()
+            .await
+            .find_by_work_id(work_id)
+            .await
+    }
+    async fn upsert(&mut self, state: &crate::play_status::WorkPlayState) -> anyhow::Result<()> {
+        self.work_play_state.lock().await.upsert(state).await
+    }
+    async fn insert_status_change(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+        status: crate::play_status::PlayStatus,
+        changed_at: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<crate::Id<crate::play_status::PlayStatusChange>> {
+        self.work_play_state
+            .lock()
+            .await
+            .insert_status_change(work_id, status, changed_at)
+            .await
+    }
+    async fn list_status_changes_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<Vec<crate::play_status::PlayStatusChange>> {
+        self.work_play_state
+            .lock()
+            .await
+            .list_status_changes_by_work_id(work_id)
+            .await
+    }
+}
+
+impl crate::repository::work_route::WorkRouteRepository for TestRepositories {
+    async fn list_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<Vec<crate::play_status::WorkRoute>> {
+        self.work_route.lock().await.list_by_work_id(work_id).await
+    }
+    async fn insert(
+        &mut self,
+        route: &crate::play_status::NewWorkRoute,
+    ) -> anyhow::Result<crate::Id<crate::play_status::WorkRoute>> {
+        self.work_route.lock().await.insert(route).await
+    }
+    async fn update_completed_at(
+        &mut self,
+        id: crate::Id<crate::play_status::WorkRoute>,
+        completed_at: Option<chrono::DateTime<chrono::Local>>,
+    ) -> anyhow::Result<()> {
+        self.work_route
+            .lock()
+            .await
+            .update_completed_at(id, completed_at)
+            .await
+    }
+    async fn delete(&mut self, id: crate::Id<crate::play_status::WorkRoute>) -> anyhow::Result<()> {
+        self.work_route.lock().await.delete(id).await
+    }
+}
+
+impl crate::repository::work_duplicate::WorkDuplicateRepository for TestRepositories {
+    async fn list_duplicate_sources(
+        &mut self,
+    ) -> anyhow::Result<Vec<crate::work_duplicate::DuplicateSource>> {
+        self.work_duplicate.lock().await.list_duplicate_sources().await
+    }
+    async fn merge(
+        &mut self,
+        survivor: crate::StrId<crate::works::Work>,
+        duplicate: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<crate::work_duplicate::WorkMergeResult> {
+        self.work_duplicate
+            .lock()
+            .await
+            .merge(survivor, duplicate)
+            .await
+    }
+}
+
+impl crate::repository::work_override::WorkOverrideRepository for TestRepositories {
+    async fn get_by_work_id(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+    ) -> anyhow::Result<crate::work_override::WorkOverrides> {
+        self.work_override.lock().await.get_by_work_id(work_id).await
+    }
+    async fn set(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+        field: crate::work_override::WorkOverrideField,
+        value: &str,
+    ) -> anyhow::Result<()> {
+        self.work_override
+            .lock()
+            .await
+            .set(work_id, field, value)
+            .await
+    }
+    async fn clear(
+        &mut self,
+        work_id: crate::StrId<crate::works::Work>,
+        field: crate::work_override::WorkOverrideField,
+    ) -> anyhow::Result<()> {
+        self.work_override.lock().await.clear(work_id, field).await
+    }
+}
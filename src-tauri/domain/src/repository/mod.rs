@@ -2,14 +2,25 @@ pub mod all_game_cache;
 pub mod app_settings;
 pub mod erogamescape;
 pub mod explored_cache;
+pub mod extension_config;
+pub mod launch_profile;
 pub mod manager;
 pub mod mock;
 pub mod native_host_log;
+pub mod remote_launch;
+pub mod remote_share_state;
 pub mod save_image_queue;
+pub mod save_location;
+pub mod sync_session;
+pub mod wine_config;
 pub mod work_download_path;
+pub mod work_duplicate;
 pub mod work_like;
 pub mod work_lnk;
+pub mod work_override;
 pub mod work_parent_packs;
+pub mod work_play_state;
+pub mod work_route;
 pub mod works;
 
 pub trait RepositoriesExt {
@@ -27,6 +38,17 @@ pub trait RepositoriesExt {
     type WorkLikeRepo: work_like::WorkLikeRepository;
     type WorkLinkPendingExeRepo: crate::work_link_pending_exe::WorkLinkPendingExeRepository;
     type AppSettingsRepo: app_settings::AppSettingsRepository;
+    type ExtensionConfigRepo: extension_config::ExtensionConfigRepository;
+    type SyncSessionRepo: sync_session::SyncSessionRepository;
+    type RemoteShareStateRepo: remote_share_state::RemoteShareStateRepository;
+    type RemoteLaunchRepo: remote_launch::RemoteLaunchRepository;
+    type LaunchProfileRepo: launch_profile::LaunchProfileRepository;
+    type WineConfigRepo: wine_config::WineConfigRepository;
+    type SaveLocationRepo: save_location::SaveLocationRepository;
+    type WorkPlayStateRepo: work_play_state::WorkPlayStateRepository;
+    type WorkRouteRepo: work_route::WorkRouteRepository;
+    type WorkDuplicateRepo: work_duplicate::WorkDuplicateRepository;
+    type WorkOverrideRepo: work_override::WorkOverrideRepository;
 
     fn work(&self) -> Self::WorkRepo;
     fn dmm_work(&self) -> Self::DmmWorkRepo;
@@ -42,4 +64,15 @@ pub trait RepositoriesExt {
     fn work_like(&self) -> Self::WorkLikeRepo;
     fn work_link_pending_exe(&self) -> Self::WorkLinkPendingExeRepo;
     fn app_settings(&self) -> Self::AppSettingsRepo;
+    fn extension_config(&self) -> Self::ExtensionConfigRepo;
+    fn sync_session(&self) -> Self::SyncSessionRepo;
+    fn remote_share_state(&self) -> Self::RemoteShareStateRepo;
+    fn remote_launch(&self) -> Self::RemoteLaunchRepo;
+    fn launch_profile(&self) -> Self::LaunchProfileRepo;
+    fn wine_config(&self) -> Self::WineConfigRepo;
+    fn save_location(&self) -> Self::SaveLocationRepo;
+    fn work_play_state(&self) -> Self::WorkPlayStateRepo;
+    fn work_route(&self) -> Self::WorkRouteRepo;
+    fn work_duplicate(&self) -> Self::WorkDuplicateRepo;
+    fn work_override(&self) -> Self::WorkOverrideRepo;
 }
@@ -1,4 +1,8 @@
-use crate::native_host_log::{HostLogLevel, HostLogType, NativeHostLogRow};
+use chrono::{DateTime, Local};
+
+use crate::native_host_log::{
+    HostLogFilter, HostLogLevel, HostLogType, NativeHostLogRow, NewHostLog,
+};
 
 #[trait_variant::make(Send)]
 #[mockall::automock]
@@ -9,16 +13,19 @@ pub trait NativeHostLogRepository {
         typ: HostLogType,
         message: &str,
     ) -> anyhow::Result<()>;
+    /// request_id や所要時間などの列も埋めて追加する
+    async fn insert(&mut self, log: &NewHostLog) -> anyhow::Result<()>;
+    /// 新しい順
     async fn list_logs(
         &mut self,
         limit: i64,
         offset: i64,
-        level: Option<HostLogLevel>,
-        typ: Option<HostLogType>,
+        filter: &HostLogFilter,
     ) -> anyhow::Result<Vec<NativeHostLogRow>>;
-    async fn count_logs(
-        &mut self,
-        level: Option<HostLogLevel>,
-        typ: Option<HostLogType>,
-    ) -> anyhow::Result<i64>;
+    async fn count_logs(&mut self, filter: &HostLogFilter) -> anyhow::Result<i64>;
+    /// created_at が `before` より古いログを消す。
+    /// 同期完了ログ（`SyncGamesCompleted`）は GetStatus の最終同期や拡張の集計に使うので消さない
+    async fn delete_older_than(&mut self, before: DateTime<Local>) -> anyhow::Result<u64>;
+    /// 同期完了ログ以外を新しい順に `keep` 件残して消す
+    async fn delete_exceeding(&mut self, keep: i64) -> anyhow::Result<u64>;
 }
@@ -0,0 +1,17 @@
+use anyhow::Result;
+
+use crate::remote_launch::{NewRemoteLaunchAudit, RemoteLaunchAudit, RemoteLaunchSettings};
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait RemoteLaunchRepository {
+    /// 未保存なら既定値を返す。許可リストは work_id の昇順
+    async fn get_settings(&mut self) -> Result<RemoteLaunchSettings>;
+    /// 許可リストは丸ごと置き換える。存在しない作品は無視する
+    async fn set_settings(&mut self, settings: &RemoteLaunchSettings) -> Result<()>;
+    /// 作品を許可リストに加える。既にあれば何もしない
+    async fn add_allowed_work(&mut self, work_id: &str) -> Result<()>;
+    async fn insert_audit(&mut self, audit: &NewRemoteLaunchAudit) -> Result<()>;
+    /// 新しい順に返す
+    async fn list_audits(&mut self, limit: i64, offset: i64) -> Result<Vec<RemoteLaunchAudit>>;
+}
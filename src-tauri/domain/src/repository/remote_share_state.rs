@@ -0,0 +1,22 @@
+use anyhow::Result;
+
+use crate::remote_share::{RemoteShareWorkState, SyncedRemoteShareWork};
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait RemoteShareStateRepository {
+    /// work_id の昇順で返す
+    async fn list(&mut self, device_id: &str) -> Result<Vec<RemoteShareWorkState>>;
+    /// 記録済みの作品はハッシュと dirty を上書きする
+    async fn upsert_synced(
+        &mut self,
+        device_id: &str,
+        works: &[SyncedRemoteShareWork],
+    ) -> Result<()>;
+    /// 消した件数を返す
+    async fn delete(&mut self, device_id: &str, work_ids: &[String]) -> Result<u64>;
+    /// 全作品に dirty を立てる。新たに立てた件数を返す
+    async fn mark_all_dirty(&mut self, device_id: &str) -> Result<u64>;
+    /// 端末を登録し直して使わなくなった、他の device_id の記録を消す
+    async fn delete_other_devices(&mut self, device_id: &str) -> Result<u64>;
+}
@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
 use crate::{
     save_image_queue::{ImagePreprocess, ImageSaveQueueRow, ImageSrcType},
     Id,
@@ -18,7 +22,30 @@ pub trait ImageSaveQueueRepository {
         unfinished: bool,
         limit: i64,
     ) -> anyhow::Result<Vec<ImageSaveQueueRow>>;
+    /// 未完了かつ next_attempt_at を過ぎた（今すぐ処理してよい）ものを古い順に返す
+    async fn list_due(&mut self, limit: i64) -> anyhow::Result<Vec<ImageSaveQueueRow>>;
+    /// バックオフ待ちの未完了のうち、最も早い next_attempt_at を返す（なければ None）
+    async fn next_attempt_at(&mut self) -> anyhow::Result<Option<DateTime<Local>>>;
+    /// デッドレター（リトライを打ち切ったもの）を新しい順に返す
+    async fn list_failed(&mut self, limit: i64) -> anyhow::Result<Vec<ImageSaveQueueRow>>;
+    /// `preprocess` の項目のうち、まだ成功していない（未完了かデッドレターの）ものの出力先を返す
+    async fn list_dst_paths_not_succeeded(
+        &mut self,
+        preprocess: ImagePreprocess,
+    ) -> anyhow::Result<Vec<String>>;
     async fn count(&mut self, unfinished: bool) -> anyhow::Result<i64>;
     async fn mark_finished(&mut self, id: Id<ImageSaveQueueRow>) -> anyhow::Result<()>;
+    /// 試行回数を増やし、`delay` 後に再試行させる
+    async fn mark_retry(
+        &mut self,
+        id: Id<ImageSaveQueueRow>,
+        error: &str,
+        delay: Duration,
+    ) -> anyhow::Result<()>;
+    /// 試行回数を増やし、デッドレターにする
     async fn mark_failed(&mut self, id: Id<ImageSaveQueueRow>, error: &str) -> anyhow::Result<()>;
+    /// デッドレターを未完了へ戻す（試行回数もリセット）。戻した件数を返す
+    async fn requeue_failed(&mut self, ids: Vec<Id<ImageSaveQueueRow>>) -> anyhow::Result<u64>;
+    /// デッドレターを削除する。削除した件数を返す
+    async fn discard_failed(&mut self, ids: Vec<Id<ImageSaveQueueRow>>) -> anyhow::Result<u64>;
 }
@@ -0,0 +1,16 @@
+use anyhow::Result;
+
+use crate::save_data::{NewSaveLocation, SaveLocation};
+use crate::{works::Work, Id, StrId};
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait SaveLocationRepository {
+    /// 登録順で返す
+    async fn list_by_work_id(&mut self, work_id: StrId<Work>) -> Result<Vec<SaveLocation>>;
+    /// 同じ作品に同じ書き方の場所があれば、それの ID を返す
+    async fn insert(&mut self, location: &NewSaveLocation) -> Result<Id<SaveLocation>>;
+    async fn delete(&mut self, id: Id<SaveLocation>) -> Result<()>;
+    /// 見つけ直す前に、自動で見つけた場所だけを消す
+    async fn delete_detected_by_work_id(&mut self, work_id: StrId<Work>) -> Result<()>;
+}
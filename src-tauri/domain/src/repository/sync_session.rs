@@ -0,0 +1,23 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+
+use crate::sync_session::{NewSyncSession, SyncSession, SyncSessionChunk};
+use crate::StrId;
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait SyncSessionRepository {
+    async fn get(&mut self, id: &StrId<SyncSession>) -> Result<Option<SyncSession>>;
+    async fn insert(&mut self, session: &NewSyncSession) -> Result<()>;
+    /// chunk_index の昇順で返す
+    async fn list_chunks(&mut self, id: &StrId<SyncSession>) -> Result<Vec<SyncSessionChunk>>;
+    /// 同じ chunk_index が記録済みなら何もしない。セッションの updated_at も進める
+    async fn insert_chunk(
+        &mut self,
+        id: &StrId<SyncSession>,
+        chunk: &SyncSessionChunk,
+    ) -> Result<()>;
+    async fn mark_committed(&mut self, id: &StrId<SyncSession>) -> Result<()>;
+    /// updated_at が `before` より古いセッションをチャンクごと消す。消した件数を返す
+    async fn delete_stale(&mut self, before: DateTime<Local>) -> Result<u64>;
+}
@@ -0,0 +1,12 @@
+use anyhow::Result;
+
+use crate::wine::WineConfig;
+use crate::{works::Work, StrId};
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait WineConfigRepository {
+    async fn find_by_work_id(&mut self, work_id: StrId<Work>) -> Result<Option<WineConfig>>;
+    async fn upsert(&mut self, config: &WineConfig) -> Result<()>;
+    async fn delete_by_work_id(&mut self, work_id: StrId<Work>) -> Result<()>;
+}
@@ -0,0 +1,18 @@
+use anyhow::Result;
+
+use crate::work_duplicate::{DuplicateSource, WorkMergeResult};
+use crate::{works::Work, StrId};
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait WorkDuplicateRepository {
+    /// すべての作品について、重複の判定に使う情報を返す
+    async fn list_duplicate_sources(&mut self) -> Result<Vec<DuplicateSource>>;
+    /// `duplicate` に紐づくものをすべて `survivor` へ移し、`duplicate` を消す。
+    /// 1 対 1 のものは `survivor` に既にあればそちらを残す
+    async fn merge(
+        &mut self,
+        survivor: StrId<Work>,
+        duplicate: StrId<Work>,
+    ) -> Result<WorkMergeResult>;
+}
@@ -0,0 +1,20 @@
+use anyhow::Result;
+
+use crate::work_override::{WorkOverrideField, WorkOverrides};
+use crate::{works::Work, StrId};
+
+/// 利用者が直した作品の情報。作品の登録や同期では読み書きしない
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait WorkOverrideRepository {
+    /// 直した項目がなければ空の `WorkOverrides`
+    async fn get_by_work_id(&mut self, work_id: StrId<Work>) -> Result<WorkOverrides>;
+    /// `value` は `WorkOverrideField::normalize_value` を通したもの
+    async fn set(
+        &mut self,
+        work_id: StrId<Work>,
+        field: WorkOverrideField,
+        value: &str,
+    ) -> Result<()>;
+    async fn clear(&mut self, work_id: StrId<Work>, field: WorkOverrideField) -> Result<()>;
+}
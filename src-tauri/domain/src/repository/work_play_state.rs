@@ -0,0 +1,23 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+
+use crate::play_status::{PlayStatus, PlayStatusChange, WorkPlayState};
+use crate::{works::Work, Id, StrId};
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait WorkPlayStateRepository {
+    async fn find_by_work_id(&mut self, work_id: StrId<Work>) -> Result<Option<WorkPlayState>>;
+    async fn upsert(&mut self, state: &WorkPlayState) -> Result<()>;
+    async fn insert_status_change(
+        &mut self,
+        work_id: StrId<Work>,
+        status: PlayStatus,
+        changed_at: DateTime<Local>,
+    ) -> Result<Id<PlayStatusChange>>;
+    /// 新しい順で返す
+    async fn list_status_changes_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+    ) -> Result<Vec<PlayStatusChange>>;
+}
@@ -0,0 +1,21 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+
+use crate::play_status::{NewWorkRoute, WorkRoute};
+use crate::{works::Work, Id, StrId};
+
+#[trait_variant::make(Send)]
+#[mockall::automock]
+pub trait WorkRouteRepository {
+    /// 追加した順で返す
+    async fn list_by_work_id(&mut self, work_id: StrId<Work>) -> Result<Vec<WorkRoute>>;
+    /// 同じ作品に同じ名前のルートがあれば、それの ID を返す
+    async fn insert(&mut self, route: &NewWorkRoute) -> Result<Id<WorkRoute>>;
+    /// None で攻略済みを外す
+    async fn update_completed_at(
+        &mut self,
+        id: Id<WorkRoute>,
+        completed_at: Option<DateTime<Local>>,
+    ) -> Result<()>;
+    async fn delete(&mut self, id: Id<WorkRoute>) -> Result<()>;
+}
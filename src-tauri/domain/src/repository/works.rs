@@ -1,5 +1,11 @@
 use crate::{
-    works::{DlsiteWork, DmmWork, NewDlsiteWork, NewDmmWork, NewWork, Work, WorkDetails},
+    game_engine::GameEngine,
+    thumbnail::WorkThumbnailVariant,
+    work_query::{WorkFacets, WorkListFilter, WorkPage, WorkQuery},
+    works::{
+        DlsiteWork, DmmWork, MissingStoreWork, NewDlsiteWork, NewDmmWork, NewWork, Work,
+        WorkDetails,
+    },
     Id, StrId,
 };
 use anyhow::Result;
@@ -11,6 +17,9 @@ pub trait WorkRepository {
     async fn upsert(&mut self, new_work: &NewWork) -> Result<StrId<Work>>;
     async fn find_by_title(&mut self, title: &str) -> Result<Option<Work>>;
     async fn list_all_details(&mut self) -> Result<Vec<WorkDetails>>;
+    /// 絞り込みと並び替えをかけ、`query.after` の次から1ページ分を返す
+    async fn query_details(&mut self, query: &WorkQuery) -> Result<WorkPage>;
+    async fn count_facets(&mut self, filter: &WorkListFilter) -> Result<WorkFacets>;
     async fn find_details_by_work_id(
         &mut self,
         work_id: StrId<Work>,
@@ -32,6 +41,17 @@ pub trait WorkRepository {
         width: i32,
         height: i32,
     ) -> Result<()>;
+    /// サムネイルのバリアントをまだ記録していない作品。
+    /// 支配色や知覚ハッシュは画像によっては取れないので条件にしない
+    async fn list_work_ids_missing_thumbnail_variants(&mut self) -> Result<Vec<StrId<Work>>>;
+    /// 作品のバリアントを置き換え、支配色と知覚ハッシュを記録する
+    async fn replace_work_thumbnail_variants(
+        &mut self,
+        work_id: StrId<Work>,
+        variants: Vec<WorkThumbnailVariant>,
+        placeholder_color: Option<String>,
+        perceptual_hash: Option<u64>,
+    ) -> Result<()>;
     async fn update_last_play_at_by_work_id(
         &mut self,
         work_id: StrId<Work>,
@@ -43,6 +63,12 @@ pub trait WorkRepository {
         install_at: DateTime<Local>,
         original_path: String,
     ) -> Result<()>;
+    /// インストール情報にエンジンを記録する。インストール情報がなければ何もしない
+    async fn update_install_engine_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+        engine: Option<GameEngine>,
+    ) -> Result<()>;
 }
 
 #[trait_variant::make(Send)]
@@ -61,6 +87,16 @@ pub trait DmmWorkRepository {
         keys: &[(String, String, String)],
     ) -> Result<Vec<DmmWork>>;
     async fn find_by_work_id(&mut self, work_id: StrId<Work>) -> Result<Option<DmmWork>>;
+    async fn count(&mut self) -> Result<i64>;
+    /// 渡した store_id のうち、既に紐付けがあるもの
+    async fn find_existing_store_ids(&mut self, store_ids: &[String]) -> Result<Vec<String>>;
+    /// 同期で見えた紐付けの last_seen_at を進め、missing_since を外す
+    async fn mark_seen(&mut self, store_ids: &[String], seen_at: DateTime<Local>) -> Result<()>;
+    /// `since` 以降の同期で見えていない紐付けに missing_since を付け、その store_id を返す
+    async fn flag_missing_not_seen_since(&mut self, since: DateTime<Local>) -> Result<Vec<String>>;
+    async fn list_missing(&mut self) -> Result<Vec<MissingStoreWork>>;
+    /// missing_since が付いたままの紐付けだけを消す。作品そのものは残す
+    async fn delete_missing(&mut self, store_ids: &[String]) -> Result<u64>;
 }
 
 #[trait_variant::make(Send)]
@@ -73,4 +109,14 @@ pub trait DlsiteWorkRepository {
         category: &str,
     ) -> Result<Option<DlsiteWork>>;
     async fn find_by_store_id(&mut self, store_id: &str) -> Result<Option<DlsiteWork>>;
+    async fn count(&mut self) -> Result<i64>;
+    /// 渡した store_id のうち、既に紐付けがあるもの
+    async fn find_existing_store_ids(&mut self, store_ids: &[String]) -> Result<Vec<String>>;
+    /// 同期で見えた紐付けの last_seen_at を進め、missing_since を外す
+    async fn mark_seen(&mut self, store_ids: &[String], seen_at: DateTime<Local>) -> Result<()>;
+    /// `since` 以降の同期で見えていない紐付けに missing_since を付け、その store_id を返す
+    async fn flag_missing_not_seen_since(&mut self, since: DateTime<Local>) -> Result<Vec<String>>;
+    async fn list_missing(&mut self) -> Result<Vec<MissingStoreWork>>;
+    /// missing_since が付いたままの紐付けだけを消す。作品そのものは残す
+    async fn delete_missing(&mut self, store_ids: &[String]) -> Result<u64>;
 }
@@ -0,0 +1,387 @@
+//! 作品ごとのセーブデータの場所と、そのバックアップ
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::{game_engine::GameEngine, shortcut::expand_env_vars_with, works::Work, Id, StrId};
+
+/// 場所の書き方のうち、インストール先（起動 exe のあるフォルダ）に置き換える部分。
+/// 他に `%APPDATA%` のような環境変数も使える
+pub const INSTALL_DIR_PLACEHOLDER: &str = "{install}";
+
+/// バックアップの zip に入れる、場所の一覧のファイル名
+pub const SNAPSHOT_MANIFEST_NAME: &str = "manifest.json";
+
+/// エンジンを問わず、インストール先にあればセーブデータとみなすフォルダ
+const COMMON_SAVE_PATHS: [&str; 3] = ["{install}/savedata", "{install}/save", "{install}/userdata"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SaveLocationSource {
+    /// エンジンの慣習やルールファイルから見つけた
+    Detected,
+    /// 利用者が指定した
+    Manual,
+}
+
+impl SaveLocationSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SaveLocationSource::Detected => "detected",
+            SaveLocationSource::Manual => "manual",
+        }
+    }
+}
+
+impl std::str::FromStr for SaveLocationSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "detected" => Ok(SaveLocationSource::Detected),
+            "manual" => Ok(SaveLocationSource::Manual),
+            _ => anyhow::bail!("unknown save location source: {s}"),
+        }
+    }
+}
+
+/// 作品のセーブデータがあるフォルダ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveLocation {
+    pub id: Id<SaveLocation>,
+    pub work_id: StrId<Work>,
+    /// `{install}` や環境変数を含んだまま保存し、使うときに展開する（PC を移っても使えるように）
+    pub path: String,
+    pub source: SaveLocationSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewSaveLocation {
+    pub work_id: StrId<Work>,
+    pub path: String,
+    pub source: SaveLocationSource,
+}
+
+/// ルールファイル（`save-rules.json`）の1件。エンジンか起動 exe の名前が合えば `paths` を探す
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveRule {
+    #[serde(default)]
+    pub engine: Option<GameEngine>,
+    /// 大文字小文字は区別しない
+    #[serde(default)]
+    pub exe_name: Option<String>,
+    pub paths: Vec<String>,
+}
+
+impl SaveRule {
+    fn matches(&self, engine: Option<GameEngine>, exe_name: &str) -> bool {
+        let engine_matches = self.engine.is_some() && self.engine == engine;
+        let exe_matches = self
+            .exe_name
+            .as_deref()
+            .is_some_and(|name| name.eq_ignore_ascii_case(exe_name));
+        engine_matches || exe_matches
+    }
+}
+
+pub fn parse_save_rules(json: &str) -> anyhow::Result<Vec<SaveRule>> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// エンジンごとの、インストール先のセーブデータの置き場所
+pub fn engine_save_paths(engine: GameEngine) -> &'static [&'static str] {
+    match engine {
+        GameEngine::Kirikiri
+        | GameEngine::SiglusEngine
+        | GameEngine::Yuris
+        | GameEngine::NScripter => &["{install}/savedata"],
+        GameEngine::Bgi => &["{install}/userdata"],
+        GameEngine::CatSystem2 | GameEngine::Artemis | GameEngine::Qlie => &["{install}/save"],
+        GameEngine::RenPy => &["{install}/game/saves"],
+        GameEngine::RpgMakerMv => &["{install}/www/save"],
+        GameEngine::RpgMakerMz => &["{install}/save"],
+        // 会社名やゲーム固有の名前を含む場所に置かれるので、ルールファイルか手動で指定する
+        GameEngine::Rgss | GameEngine::Unity | GameEngine::TyranoScript | GameEngine::LiveMaker => {
+            &[]
+        }
+    }
+}
+
+/// ルールファイル、エンジンの慣習、共通の場所の順に探し、実際にあるフォルダの書き方を返す
+pub fn detect_save_paths(
+    install_dir: &Path,
+    engine: Option<GameEngine>,
+    exe_name: &str,
+    rules: &[SaveRule],
+) -> Vec<String> {
+    let rule_paths = rules
+        .iter()
+        .filter(|rule| rule.matches(engine, exe_name))
+        .flat_map(|rule| rule.paths.iter().map(|path| path.as_str()));
+    let engine_paths = engine
+        .map(engine_save_paths)
+        .unwrap_or_default()
+        .iter()
+        .copied();
+
+    let mut detected: Vec<String> = Vec::new();
+    for template in rule_paths.chain(engine_paths).chain(COMMON_SAVE_PATHS) {
+        let Some(path) = expand_save_path(template, install_dir) else {
+            continue;
+        };
+        if !path.is_dir() {
+            continue;
+        }
+        // 書き方が違っても同じフォルダなら1つにまとめる
+        let already = detected
+            .iter()
+            .any(|t| expand_save_path(t, install_dir).as_deref() == Some(path.as_path()));
+        if !already {
+            detected.push(template.to_string());
+        }
+    }
+    detected
+}
+
+/// 場所の書き方を実際のパスにする。知らない環境変数が残れば None
+pub fn expand_save_path(template: &str, install_dir: &Path) -> Option<PathBuf> {
+    expand_save_path_with(template, install_dir, |key| std::env::var(key).ok())
+}
+
+pub fn expand_save_path_with(
+    template: &str,
+    install_dir: &Path,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Option<PathBuf> {
+    if validate_save_location(template).is_err() {
+        return None;
+    }
+    let expanded = expand_env_vars_with(template, lookup);
+    if expanded.contains('%') {
+        return None;
+    }
+    match expanded.strip_prefix(INSTALL_DIR_PLACEHOLDER) {
+        Some(rest) => Some(resolve_case_insensitive(install_dir, rest)),
+        None if expanded.contains(INSTALL_DIR_PLACEHOLDER) => None,
+        None => Some(PathBuf::from(
+            expanded.replace(['/', '\\'], std::path::MAIN_SEPARATOR_STR),
+        )),
+    }
+}
+
+/// 復元ではこの場所にファイルを書き戻すので、インストール先や環境変数そのもの、ドライブのルート、
+/// 相対パスのような広すぎる・曖昧な場所は受け付けない
+pub fn validate_save_location(template: &str) -> anyhow::Result<()> {
+    let components = template
+        .split(['/', '\\'])
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<_>>();
+    if components.iter().any(|c| *c == "." || *c == "..") {
+        anyhow::bail!("save location must not contain . or ..: {}", template);
+    }
+    let Some(first) = components.first() else {
+        anyhow::bail!("save location is too broad: {}", template);
+    };
+    let is_env_var = first.len() > 2
+        && first.starts_with('%')
+        && first.ends_with('%')
+        && !first[1..first.len() - 1].contains('%');
+    let is_drive = first.len() == 2
+        && first.ends_with(':')
+        && first.starts_with(|c: char| c.is_ascii_alphabetic());
+    let is_rooted = template.starts_with(['/', '\\']);
+    if !(*first == INSTALL_DIR_PLACEHOLDER || is_env_var || is_drive || is_rooted) {
+        anyhow::bail!(
+            "save location must start with {}, an environment variable or an absolute path: {}",
+            INSTALL_DIR_PLACEHOLDER,
+            template
+        );
+    }
+    if components[1..]
+        .iter()
+        .any(|c| c.contains(INSTALL_DIR_PLACEHOLDER))
+    {
+        anyhow::bail!(
+            "{} must be at the start of save location: {}",
+            INSTALL_DIR_PLACEHOLDER,
+            template
+        );
+    }
+    // `{install}` や `%APPDATA%`、`C:` そのものではなく、その下のフォルダを指す
+    if !is_rooted && components.len() < 2 {
+        anyhow::bail!("save location is too broad: {}", template);
+    }
+    Ok(())
+}
+
+/// Windows のゲームは大文字小文字を気にせず書いているので、他の OS でも同じように辿る
+fn resolve_case_insensitive(base: &Path, relative: &str) -> PathBuf {
+    let mut path = base.to_path_buf();
+    for component in relative.split(['/', '\\']).filter(|c| !c.is_empty()) {
+        let exact = path.join(component);
+        if exact.exists() {
+            path = exact;
+            continue;
+        }
+        let found = std::fs::read_dir(&path).ok().and_then(|entries| {
+            entries.filter_map(|entry| entry.ok()).find(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case(component)
+            })
+        });
+        path = match found {
+            Some(entry) => entry.path(),
+            None => exact,
+        };
+    }
+    path
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotReason {
+    Manual,
+    /// 復元で上書きする前に自動で取った
+    BeforeRestore,
+}
+
+impl SnapshotReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotReason::Manual => "manual",
+            SnapshotReason::BeforeRestore => "before_restore",
+        }
+    }
+}
+
+/// バックアップの zip の `manifest.json`。`locations[i]` の中身は zip の `{i}/` 以下に入る
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    pub work_id: String,
+    pub created_at: DateTime<Local>,
+    pub reason: SnapshotReason,
+    pub locations: Vec<String>,
+}
+
+/// 保存済みのバックアップ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaveSnapshot {
+    /// 作品のバックアップフォルダの中のファイル名。復元するときの指定に使う
+    pub file_name: String,
+    pub path: String,
+    pub size: u64,
+    pub manifest: SnapshotManifest,
+}
+
+/// 時刻順に並ぶように、取った時刻と理由から名前を付ける
+pub fn snapshot_file_name(created_at: DateTime<Local>, reason: SnapshotReason) -> String {
+    format!(
+        "{}-{}.zip",
+        created_at.format("%Y%m%d-%H%M%S%3f"),
+        reason.as_str()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("fixtures")
+            .join("save_data")
+            .join(name)
+    }
+
+    #[test]
+    fn detect_save_paths_フィクスチャのフォルダ構成から見つける() {
+        let rules = parse_save_rules(
+            r#"[{ "exeName": "Custom.exe", "paths": ["{install}/MySave", "{install}/missing"] }]"#,
+        )
+        .unwrap();
+        let cases = [
+            (
+                "kirikiri",
+                Some(GameEngine::Kirikiri),
+                "game.exe",
+                vec!["{install}/savedata"],
+            ),
+            (
+                "rpg_maker_mv",
+                Some(GameEngine::RpgMakerMv),
+                "game.exe",
+                vec!["{install}/www/save"],
+            ),
+            (
+                "renpy",
+                Some(GameEngine::RenPy),
+                "sakura.exe",
+                vec!["{install}/game/saves"],
+            ),
+            (
+                "custom",
+                None,
+                "custom.exe",
+                vec!["{install}/MySave", "{install}/save"],
+            ),
+            ("no_save", Some(GameEngine::Kirikiri), "game.exe", vec![]),
+        ];
+
+        for (name, engine, exe_name, expected) in cases {
+            let detected = detect_save_paths(&fixture(name), engine, exe_name, &rules);
+            assert_eq!(detected, expected, "case: {name}");
+        }
+    }
+
+    #[test]
+    fn expand_save_path_with_インストール先と環境変数を置き換える() {
+        let lookup = |key: &str| (key == "APPDATA").then(|| "/home/u/appdata".to_string());
+        let install_dir = fixture("kirikiri");
+
+        assert_eq!(
+            expand_save_path_with("{install}/SAVEDATA", &install_dir, lookup),
+            Some(install_dir.join("SaveData"))
+        );
+        assert_eq!(
+            expand_save_path_with("%APPDATA%/brand/game", &install_dir, lookup),
+            Some(PathBuf::from("/home/u/appdata/brand/game"))
+        );
+        assert_eq!(
+            expand_save_path_with("%UNKNOWN%/game", &install_dir, lookup),
+            None
+        );
+    }
+
+    #[test]
+    fn validate_save_location_広すぎる場所や外へ出る場所を拒否する() {
+        for ok in [
+            "{install}/savedata",
+            "%APPDATA%/brand/game",
+            "%USERPROFILE%\\Documents",
+            "C:\\Saves\\game",
+            "/home/u/saves",
+        ] {
+            assert!(validate_save_location(ok).is_ok(), "case: {ok}");
+        }
+        for ng in [
+            "{install}",
+            "{install}/",
+            "%USERPROFILE%",
+            "C:\\",
+            "/",
+            "savedata",
+            "{install}/../..",
+            "{install}/./save",
+            "%APPDATA%/../Local",
+            "%APPDATA%/{install}/save",
+            "100%/save",
+        ] {
+            assert!(validate_save_location(ng).is_err(), "case: {ng}");
+        }
+    }
+}
@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
 use crate::Id;
 
 #[derive(Debug, Clone, Copy)]
@@ -13,6 +17,8 @@ pub enum ImagePreprocess {
     None = 0,
     ResizeAndCropSquare256 = 1,
     ResizeForWidth400 = 2,
+    /// 幅 400 の PNG に加えて、複数幅の WebP バリアントを同じディレクトリに書き出す
+    ThumbnailVariants = 3,
 }
 
 #[derive(Debug, Clone)]
@@ -23,4 +29,101 @@ pub struct ImageSaveQueueRow {
     pub dst_path: String,
     pub preprocess: ImagePreprocess,
     pub last_error: Option<String>,
+    /// これまでに失敗した試行の回数
+    pub attempt_count: i32,
+    /// 次に試行してよい時刻（None なら即時）
+    pub next_attempt_at: Option<DateTime<Local>>,
+    /// リトライを打ち切った時刻（デッドレター）
+    pub failed_at: Option<DateTime<Local>>,
+}
+
+/// 失敗の種類。再試行するかどうかの判断に使う
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageQueueFailureKind {
+    /// 5xx やタイムアウトなど、時間をおけば成功しうる失敗
+    Transient,
+    /// 4xx やフォールバックなど、再試行しても結果が変わらない失敗
+    Permanent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// 指定時間後に再試行する
+    RetryAfter(Duration),
+    /// 再試行を打ち切ってデッドレターにする
+    GiveUp,
+}
+
+/// 画像保存キューの再試行方針（指数バックオフ）
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// 失敗として数える試行回数の上限
+    pub max_attempts: i32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(30),
+            max_delay: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// `attempt_count` は今回の失敗を含めた試行回数
+    pub fn decide(&self, attempt_count: i32, kind: ImageQueueFailureKind) -> RetryDecision {
+        match kind {
+            ImageQueueFailureKind::Permanent => RetryDecision::GiveUp,
+            ImageQueueFailureKind::Transient if attempt_count >= self.max_attempts => {
+                RetryDecision::GiveUp
+            }
+            ImageQueueFailureKind::Transient => {
+                RetryDecision::RetryAfter(self.backoff(attempt_count))
+            }
+        }
+    }
+
+    /// base_delay * 2^(attempt_count - 1) を max_delay で頭打ちにした待ち時間
+    pub fn backoff(&self, attempt_count: i32) -> Duration {
+        let exp = (attempt_count.max(1) - 1).min(31) as u32;
+        self.base_delay
+            .saturating_mul(1u32 << exp)
+            .min(self.max_delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_grows_exponentially_and_is_capped() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_secs(30));
+        assert_eq!(policy.backoff(2), Duration::from_secs(60));
+        assert_eq!(policy.backoff(3), Duration::from_secs(120));
+        assert_eq!(policy.backoff(10), Duration::from_secs(60 * 60));
+        assert_eq!(policy.backoff(i32::MAX), Duration::from_secs(60 * 60));
+    }
+
+    #[test]
+    fn test_decide() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.decide(1, ImageQueueFailureKind::Transient),
+            RetryDecision::RetryAfter(Duration::from_secs(30))
+        );
+        assert_eq!(
+            policy.decide(policy.max_attempts, ImageQueueFailureKind::Transient),
+            RetryDecision::GiveUp
+        );
+        assert_eq!(
+            policy.decide(1, ImageQueueFailureKind::Permanent),
+            RetryDecision::GiveUp
+        );
+    }
 }
@@ -1,6 +1,8 @@
 use derive_new::new;
 use std::path::{Path, PathBuf};
 
+use crate::game_matcher::MatchExplanation;
+
 // データモデル（段階型）
 #[derive(new, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct WorkCandidate {
@@ -22,6 +24,9 @@ pub struct ResolvedWork {
     pub title: String,
     pub egs_id: i32,
     pub distance: f32,
+    /// マッチングの根拠（GameMatcher::explain_candidates の結果）
+    #[new(default)]
+    pub explanation: Option<MatchExplanation>,
 }
 
 #[derive(new, Clone, Debug, PartialEq)]
@@ -59,6 +64,8 @@ pub trait FileSystem {
         exclude: Option<std::sync::Arc<crate::explored_cache::ExploredCache>>,
     ) -> anyhow::Result<Box<dyn Iterator<Item = WorkCandidate> + Send>>;
     fn stat(&self, path: &Path) -> anyhow::Result<std::fs::Metadata>;
+    /// `root` 以下のファイルを `max_depth` 階層まで列挙する（フォルダは含めない）
+    fn list_files(&self, root: &Path, max_depth: usize) -> anyhow::Result<Vec<PathBuf>>;
 }
 
 // GameRecognizer は廃止
@@ -70,7 +77,8 @@ pub trait MetadataExtractor {
 }
 
 pub enum WorkCandidateOrResolvedWork {
-    Candidate(WorkCandidate),
+    /// 同定できなかった候補。マッチングまで進んだ場合はその根拠を持つ
+    Candidate(WorkCandidate, Option<MatchExplanation>),
     Resolved(ResolvedWork),
 }
 
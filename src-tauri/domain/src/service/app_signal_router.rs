@@ -41,4 +41,65 @@ pub struct AppSignal {
 pub trait AppSignalRouter {
     /// シグナルをデスクトップアプリへ中継する。
     async fn dispatch(&self, signal: AppSignal) -> anyhow::Result<()>;
+
+    /// 問い合わせを送って応答を待つ。アプリが起動していなければ `None` を返すので、送り手は自分で処理する。
+    async fn request(&self, request: AppRequest) -> anyhow::Result<Option<AppResponse>>;
+}
+
+/// 応答を求めてデスクトップアプリへ送る問い合わせ。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AppRequest {
+    /// 画像キューの処理状況を問い合わせる。
+    ImageQueueStatus,
+    /// 画像キューを空になるまで処理させる。アプリは受け付けた時点で応答する。
+    DrainImageQueue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AppResponse {
+    ImageQueueStatus {
+        running: bool,
+        pending: i64,
+    },
+    DrainImageQueue {
+        /// アプリが処理を引き受けたか。false なら送り手が自分で処理する
+        accepted: bool,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// 接続上を流れる問い合わせ。`request_id` で応答と対応付ける。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppRequestEnvelope {
+    pub request_id: String,
+    pub source: AppSignalSource,
+    pub request: AppRequest,
+    pub issued_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppResponseEnvelope {
+    pub request_id: String,
+    pub response: AppResponse,
+}
+
+/// 接続上を流れるメッセージ。従来の `AppSignal` はそのままの形で受け付ける。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AppSignalMessage {
+    Request(AppRequestEnvelope),
+    Signal(AppSignal),
+}
+
+/// デスクトップアプリ側で問い合わせに答える。
+#[trait_variant::make(Send + Sync)]
+#[mockall::automock]
+pub trait AppRequestHandler {
+    async fn handle(&self, request: AppRequest) -> anyhow::Result<AppResponse>;
 }
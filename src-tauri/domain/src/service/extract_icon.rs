@@ -0,0 +1,7 @@
+/// exe からアイコンを取り出す。OS ごとの取り出し方は infrastructure に置く
+#[mockall::automock]
+pub trait ExtractIconRunner: Send + Sync {
+    /// 指定 exe から PNG を抽出
+    /// 戻り値: 成功したら true（dst に出力済み）、失敗なら false
+    fn extract_icon(&self, width: u32, exe_path: &str, dst_path: &str) -> anyhow::Result<bool>;
+}
@@ -1,4 +1,5 @@
 pub mod app_signal_router;
+pub mod extract_icon;
 pub mod image_queue_drain;
 pub mod image_queue_event;
 pub mod save_path_resolver;
@@ -1,6 +1,10 @@
 use std::path::PathBuf;
 
-use crate::{works::Work, StrId};
+use crate::{
+    thumbnail::{thumbnail_variant_file_name, ThumbnailFormat},
+    works::Work,
+    StrId,
+};
 
 #[trait_variant::make(Send + Sync)]
 #[mockall::automock]
@@ -33,6 +37,15 @@ pub trait SavePathResolver {
     fn play_histories_dir(&self) -> String {
         self.join_and_ensure("play-histories")
     }
+    fn http_cache_dir(&self) -> String {
+        self.join_and_ensure("http-cache")
+    }
+    fn matcher_index_path(&self) -> String {
+        PathBuf::from(self.join_and_ensure("matcher"))
+            .join("index.bin")
+            .to_string_lossy()
+            .to_string()
+    }
     fn db_file_path(&self) -> String {
         PathBuf::from(self.root_dir())
             .join("launcherg_sqlite.db3")
@@ -52,6 +65,23 @@ pub trait SavePathResolver {
             .to_string_lossy()
             .to_string()
     }
+    fn thumbnail_variant_path(&self, id: &str, width: u32, format: ThumbnailFormat) -> String {
+        PathBuf::from(self.thumbnails_dir())
+            .join(thumbnail_variant_file_name(id, width, format))
+            .to_string_lossy()
+            .to_string()
+    }
+    /// 作品ごとのセーブデータのバックアップ（zip）を置くフォルダ
+    fn save_backups_dir(&self, work_id: &str) -> String {
+        self.join_and_ensure_with_base(&self.join_and_ensure("save-backups"), work_id)
+    }
+    /// セーブデータの場所を探すルールファイル。なければエンジンの慣習だけで探す
+    fn save_rules_path(&self) -> String {
+        PathBuf::from(self.root_dir())
+            .join("save-rules.json")
+            .to_string_lossy()
+            .to_string()
+    }
     fn play_history_jsonl_path(&self, work_id: StrId<Work>) -> String {
         PathBuf::from(self.play_histories_dir())
             .join(format!("{}.jsonl", work_id.value))
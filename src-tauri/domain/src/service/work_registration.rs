@@ -88,7 +88,8 @@ pub struct WorkRegistrationResult {
 #[trait_variant::make(Send)]
 #[mockall::automock]
 pub trait WorkRegistrationService {
-    /// Work を登録（バッチ対応、N+1回避）
+    /// Work を登録（バッチ対応、N+1回避）。
+    /// 利用者が直した項目（`WorkOverrideRepository`）は読み書きしない
     async fn register(
         &self,
         requests: Vec<WorkRegistrationRequest>,
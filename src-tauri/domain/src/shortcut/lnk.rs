@@ -0,0 +1,360 @@
+//! MS-SHLLINK 形式の .lnk を読む
+use super::{decode_ansi, expand_env_vars, join_relative};
+use crate::binary_reader::Reader;
+
+const HEADER_SIZE: usize = 0x4C;
+const LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x0000_0001;
+const HAS_LINK_INFO: u32 = 0x0000_0002;
+const HAS_NAME: u32 = 0x0000_0004;
+const HAS_RELATIVE_PATH: u32 = 0x0000_0008;
+const HAS_WORKING_DIR: u32 = 0x0000_0010;
+const HAS_ARGUMENTS: u32 = 0x0000_0020;
+const HAS_ICON_LOCATION: u32 = 0x0000_0040;
+const IS_UNICODE: u32 = 0x0000_0080;
+const FORCE_NO_LINK_INFO: u32 = 0x0000_0100;
+const HAS_EXP_STRING: u32 = 0x0000_0200;
+const HAS_EXP_ICON: u32 = 0x0000_4000;
+
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x1;
+const COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX: u32 = 0x2;
+
+const ENVIRONMENT_VARIABLE_DATA_BLOCK: u32 = 0xA000_0001;
+const ICON_ENVIRONMENT_DATA_BLOCK: u32 = 0xA000_0007;
+const FILE_ENTRY_EXTENSION: u32 = 0xBEEF_0004;
+
+/// .lnk の中身。文字列は書かれたままで、環境変数は展開していない
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShellLinkFile {
+    pub icon_index: i32,
+    /// LinkTargetIDList を辿って組み立てたパス
+    pub id_list_path: Option<String>,
+    pub local_base_path: Option<String>,
+    pub network_share: Option<String>,
+    pub common_path_suffix: Option<String>,
+    pub name: Option<String>,
+    pub relative_path: Option<String>,
+    pub working_dir: Option<String>,
+    pub arguments: Option<String>,
+    pub icon_location: Option<String>,
+    /// EnvironmentVariableDataBlock の `%VAR%` を含むリンク先
+    pub env_target: Option<String>,
+    /// IconEnvironmentDataBlock の `%VAR%` を含むアイコン
+    pub env_icon: Option<String>,
+}
+
+impl ShellLinkFile {
+    pub fn open(file_path: &str) -> anyhow::Result<Self> {
+        let bytes = std::fs::read(file_path)?;
+        Self::parse(&bytes).map_err(|e| anyhow::anyhow!("failed to parse {}: {}", file_path, e))
+    }
+
+    pub fn parse(bytes: &[u8]) -> anyhow::Result<Self> {
+        let r = Reader(bytes);
+        if r.u32(0)? as usize != HEADER_SIZE || r.slice(4, 16)? != LINK_CLSID {
+            anyhow::bail!("not a shell link");
+        }
+        let flags = r.u32(0x14)?;
+        let mut link = ShellLinkFile {
+            icon_index: r.u32(0x38)? as i32,
+            ..Default::default()
+        };
+
+        let mut offset = HEADER_SIZE;
+        if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+            let size = r.u16(offset)? as usize;
+            let id_list = Reader(r.slice(offset + 2, size)?);
+            link.id_list_path = parse_id_list(&id_list);
+            offset += 2 + size;
+        }
+        if flags & HAS_LINK_INFO != 0 {
+            let size = r.u32(offset)? as usize;
+            if flags & FORCE_NO_LINK_INFO == 0 {
+                link.read_link_info(&Reader(r.slice(offset, size)?))?;
+            }
+            offset += size;
+        }
+
+        let is_unicode = flags & IS_UNICODE != 0;
+        for (flag, field) in [
+            (HAS_NAME, &mut link.name),
+            (HAS_RELATIVE_PATH, &mut link.relative_path),
+            (HAS_WORKING_DIR, &mut link.working_dir),
+            (HAS_ARGUMENTS, &mut link.arguments),
+            (HAS_ICON_LOCATION, &mut link.icon_location),
+        ] {
+            if flags & flag == 0 {
+                continue;
+            }
+            let count = r.u16(offset)? as usize;
+            offset += 2;
+            let value = if is_unicode {
+                let s = r.utf16(offset, count)?;
+                offset += count * 2;
+                s
+            } else {
+                let s = decode_ansi(r.slice(offset, count)?);
+                offset += count;
+                s
+            };
+            *field = Some(value);
+        }
+
+        // ExtraData は BlockSize が 4 未満のところで終わる
+        while let Ok(size) = r.u32(offset) {
+            let size = size as usize;
+            if size < 8 {
+                break;
+            }
+            let block = Reader(r.slice(offset, size)?);
+            match block.u32(4)? {
+                ENVIRONMENT_VARIABLE_DATA_BLOCK if flags & HAS_EXP_STRING != 0 => {
+                    link.env_target = read_env_block(&block)?;
+                }
+                ENVIRONMENT_VARIABLE_DATA_BLOCK => {}
+                ICON_ENVIRONMENT_DATA_BLOCK if flags & HAS_EXP_ICON != 0 => {
+                    link.env_icon = read_env_block(&block)?;
+                }
+                _ => {}
+            }
+            offset += size;
+        }
+
+        Ok(link)
+    }
+
+    fn read_link_info(&mut self, r: &Reader) -> anyhow::Result<()> {
+        let header_size = r.u32(4)?;
+        let flags = r.u32(8)?;
+        let has_unicode = header_size >= 0x24;
+
+        let suffix = if has_unicode && r.u32(0x20)? != 0 {
+            r.utf16z(r.u32(0x20)? as usize)?
+        } else {
+            ansiz(r, r.u32(0x18)? as usize)?
+        };
+        self.common_path_suffix = Some(suffix).filter(|s| !s.is_empty());
+
+        if flags & VOLUME_ID_AND_LOCAL_BASE_PATH != 0 {
+            let local = if has_unicode && r.u32(0x1C)? != 0 {
+                r.utf16z(r.u32(0x1C)? as usize)?
+            } else {
+                ansiz(r, r.u32(0x10)? as usize)?
+            };
+            self.local_base_path = Some(local).filter(|s| !s.is_empty());
+        }
+        if flags & COMMON_NETWORK_RELATIVE_LINK_AND_PATH_SUFFIX != 0 {
+            let base = r.u32(0x14)? as usize;
+            let net_name_offset = r.u32(base + 8)? as usize;
+            let net_name = if net_name_offset > 0x14 {
+                r.utf16z(base + r.u32(base + 0x14)? as usize)?
+            } else {
+                ansiz(r, base + net_name_offset)?
+            };
+            self.network_share = Some(net_name).filter(|s| !s.is_empty());
+        }
+        Ok(())
+    }
+
+    /// IShellLinkW::GetPath 相当のリンク先。環境変数は展開する
+    pub fn target_path(&self) -> Option<String> {
+        if let Some(env_target) = &self.env_target {
+            return Some(expand_env_vars(env_target));
+        }
+        let suffix = self.common_path_suffix.as_deref().unwrap_or_default();
+        if let Some(local) = &self.local_base_path {
+            return Some(format!("{local}{suffix}"));
+        }
+        if let Some(share) = &self.network_share {
+            return Some(match suffix {
+                "" => share.clone(),
+                suffix => format!("{}\\{}", share.trim_end_matches('\\'), suffix),
+            });
+        }
+        self.id_list_path.clone()
+    }
+
+    /// リンク先が絶対パスで残っていないときに、.lnk の場所から相対パスを辿る
+    pub fn relative_target_path(&self, lnk_path: &str) -> Option<String> {
+        let relative = self.relative_path.as_deref()?;
+        Some(join_relative(lnk_path, relative))
+    }
+
+    /// IShellLinkW::GetIconLocation 相当のアイコンのパス。環境変数は展開する
+    pub fn icon_path(&self) -> Option<String> {
+        self.env_icon
+            .as_deref()
+            .or(self.icon_location.as_deref())
+            .filter(|s| !s.is_empty())
+            .map(expand_env_vars)
+    }
+}
+
+/// EnvironmentVariableDataBlock と IconEnvironmentDataBlock は同じ並び。Unicode 側を優先する
+fn read_env_block(block: &Reader) -> anyhow::Result<Option<String>> {
+    let unicode = block.utf16z(8 + 260)?;
+    let value = if unicode.is_empty() {
+        ansiz(block, 8)?
+    } else {
+        unicode
+    };
+    Ok(Some(value).filter(|s| !s.is_empty()))
+}
+
+/// ドライブとファイルエントリだけからなる IDList をパスにする。それ以外の項目があれば諦める
+fn parse_id_list(r: &Reader) -> Option<String> {
+    let mut path: Option<String> = None;
+    let mut offset = 0;
+    loop {
+        let size = r.u16(offset).ok()? as usize;
+        if size == 0 {
+            break;
+        }
+        let item = Reader(r.slice(offset, size).ok()?);
+        let item_type = item.u8(2).ok()?;
+        match item_type & 0x70 {
+            // マイコンピューターなどのルートフォルダ
+            0x10 => {}
+            0x20 => {
+                let drive = ansiz(&item, 3).ok()?;
+                path = Some(drive.trim_end_matches('\\').to_string());
+            }
+            0x30 => {
+                let name = file_entry_name(&item, item_type)?;
+                let parent = path.as_mut()?;
+                parent.push('\\');
+                parent.push_str(&name);
+            }
+            _ => return None,
+        }
+        offset += size;
+    }
+    // ドライブ直下を指すときは末尾の区切りを残す
+    path.map(|p| {
+        if p.ends_with(':') {
+            format!("{p}\\")
+        } else {
+            p
+        }
+    })
+}
+
+/// 8.3 形式の名前より、拡張ブロック 0xBEEF0004 にある長い名前を優先する
+fn file_entry_name(item: &Reader, item_type: u8) -> Option<String> {
+    if let Some(long_name) = file_entry_long_name(item) {
+        return Some(long_name);
+    }
+    if item_type & 0x04 != 0 {
+        item.utf16z(14).ok()
+    } else {
+        ansiz(item, 14).ok()
+    }
+}
+
+fn file_entry_long_name(item: &Reader) -> Option<String> {
+    let len = item.0.len();
+    let ext_offset = item.u16(len.checked_sub(2)?).ok()? as usize;
+    if ext_offset == 0 {
+        return None;
+    }
+    let ext = Reader(item.0.get(ext_offset..)?);
+    if ext.u32(4).ok()? != FILE_ENTRY_EXTENSION {
+        return None;
+    }
+    let version = ext.u16(2).ok()?;
+    let mut name_offset = 18;
+    if version >= 7 {
+        name_offset += 18;
+    }
+    if version >= 3 {
+        name_offset += 2;
+    }
+    if version >= 9 {
+        name_offset += 4;
+    }
+    if version >= 8 {
+        name_offset += 4;
+    }
+    ext.utf16z(name_offset).ok().filter(|s| !s.is_empty())
+}
+
+/// NUL で終わる ANSI の文字列
+fn ansiz(r: &Reader, offset: usize) -> anyhow::Result<String> {
+    let rest = r.rest(offset);
+    let len = rest
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or_else(|| anyhow::anyhow!("unterminated string at {offset:#x}"))?;
+    Ok(decode_ansi(&rest[..len]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCAL_GAME: &[u8] = include_bytes!("../../fixtures/shortcut/local_game.lnk");
+    const ENV_TARGET: &[u8] = include_bytes!("../../fixtures/shortcut/env_target.lnk");
+    const ID_LIST_ONLY: &[u8] = include_bytes!("../../fixtures/shortcut/id_list_only.lnk");
+
+    #[test]
+    fn parse_ローカルのexeへのショートカットを読める() {
+        let link = ShellLinkFile::parse(LOCAL_GAME).unwrap();
+
+        assert_eq!(
+            link.target_path().as_deref(),
+            Some("C:\\Games\\ゲーム\\game.exe")
+        );
+        assert_eq!(
+            link.id_list_path.as_deref(),
+            Some("C:\\Games\\ゲーム\\game.exe")
+        );
+        assert_eq!(link.relative_path.as_deref(), Some(".\\ゲーム\\game.exe"));
+        assert_eq!(link.working_dir.as_deref(), Some("C:\\Games\\ゲーム"));
+        assert_eq!(link.arguments.as_deref(), Some("-window \"save dir\""));
+        assert_eq!(
+            link.icon_path().as_deref(),
+            Some("C:\\Games\\ゲーム\\icon.ico")
+        );
+        assert_eq!(link.icon_index, 0);
+    }
+
+    #[test]
+    fn parse_環境変数のブロックがあればそちらをリンク先にする() {
+        let link = ShellLinkFile::parse(ENV_TARGET).unwrap();
+
+        assert_eq!(
+            link.env_target.as_deref(),
+            Some("%LAUNCHERG_LNK_FIXTURE%\\app\\app.exe")
+        );
+        assert_eq!(
+            link.env_icon.as_deref(),
+            Some("%LAUNCHERG_LNK_FIXTURE%\\app\\app.exe")
+        );
+        assert_eq!(link.icon_index, 2);
+        assert_eq!(link.local_base_path, None);
+        assert_eq!(
+            link.target_path().as_deref(),
+            Some("%LAUNCHERG_LNK_FIXTURE%\\app\\app.exe")
+        );
+    }
+
+    #[test]
+    fn parse_idlistだけでも長い名前でパスを組み立てる() {
+        let link = ShellLinkFile::parse(ID_LIST_ONLY).unwrap();
+
+        assert_eq!(
+            link.target_path().as_deref(),
+            Some("D:\\Program Files (x86)\\Visual Novel\\start.exe")
+        );
+        assert_eq!(link.icon_path(), None);
+    }
+
+    #[test]
+    fn parse_壊れたファイルはエラーになる() {
+        assert!(ShellLinkFile::parse(b"not a link").is_err());
+        assert!(ShellLinkFile::parse(&LOCAL_GAME[..LOCAL_GAME.len() / 2]).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! .lnk と .url を OS の API を使わずに読む
+pub mod lnk;
+pub mod url;
+
+use std::collections::HashMap;
+
+use crate::file::LnkMetadata;
+
+/// 指定した .lnk / .url の各ファイルパスに対してメタデータを読む。`ShellLink::get_lnk_metadatas` の中身
+pub fn read_metadatas(file_paths: Vec<String>) -> anyhow::Result<HashMap<String, LnkMetadata>> {
+    let mut metadatas = HashMap::new();
+    for file_path in file_paths.into_iter() {
+        let metadata = read_metadata(&file_path)?;
+        metadatas.insert(file_path, metadata);
+    }
+    Ok(metadatas)
+}
+
+pub fn read_metadata(file_path: &str) -> anyhow::Result<LnkMetadata> {
+    let lower = file_path.to_lowercase();
+    if lower.ends_with("lnk") {
+        let link = lnk::ShellLinkFile::open(file_path)?;
+        let path = link
+            .target_path()
+            .or_else(|| link.relative_target_path(file_path))
+            .ok_or_else(|| anyhow::anyhow!("{} has no link target", file_path))?;
+        Ok(LnkMetadata {
+            path,
+            icon: link.icon_path().unwrap_or_default(),
+            arguments: link.arguments.filter(|s| !s.is_empty()),
+            working_dir: link
+                .working_dir
+                .filter(|s| !s.is_empty())
+                .map(|s| expand_env_vars(&s)),
+        })
+    } else if lower.ends_with("url") {
+        let shortcut = url::InternetShortcut::open(file_path)?;
+        Ok(LnkMetadata {
+            path: file_path.to_string(),
+            icon: shortcut.icon_file.unwrap_or_default(),
+            ..Default::default()
+        })
+    } else {
+        Err(anyhow::anyhow!("{} is not end lnk|url", file_path))
+    }
+}
+
+/// `%VAR%` を環境変数で置き換える。見つからない変数はそのまま残す
+pub fn expand_env_vars(s: &str) -> String {
+    expand_env_vars_with(s, |key| std::env::var(key).ok())
+}
+
+pub fn expand_env_vars_with(s: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find('%') {
+        let Some(len) = rest[start + 1..].find('%') else {
+            break;
+        };
+        let key = &rest[start + 1..start + 1 + len];
+        result.push_str(&rest[..start]);
+        match lookup(key).filter(|_| !key.is_empty()) {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(&rest[start..start + len + 2]),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// ANSI 文字列はシステムのコードページで書かれる。UTF-8 として読めなければ日本語環境の CP932 とみなす
+fn decode_ansi(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => encoding_rs::SHIFT_JIS.decode(bytes).0.into_owned(),
+    }
+}
+
+/// .lnk のあるフォルダから見た相対パスを絶対パスにする
+fn join_relative(base_file: &str, relative: &str) -> String {
+    // Windows のパスを他の OS で扱うこともあるので Path を使わずに区切る
+    let separator = if base_file.contains('\\') { '\\' } else { '/' };
+    let mut joined = match base_file.rfind(['\\', '/']) {
+        Some(i) => base_file[..i].to_string(),
+        None => String::new(),
+    };
+    for part in relative.split(['\\', '/']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if let Some(i) = joined.rfind(['\\', '/']) {
+                    joined.truncate(i);
+                }
+            }
+            part => {
+                if !joined.is_empty() {
+                    joined.push(separator);
+                }
+                joined.push_str(part);
+            }
+        }
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_env_vars_with_見つからない変数は残す() {
+        let lookup = |key: &str| (key == "ProgramFiles").then(|| "C:\\Program Files".to_string());
+        assert_eq!(
+            expand_env_vars_with("%ProgramFiles%\\app\\%UNKNOWN%\\a.exe", lookup),
+            "C:\\Program Files\\app\\%UNKNOWN%\\a.exe"
+        );
+        assert_eq!(expand_env_vars_with("100% ok", lookup), "100% ok");
+    }
+
+    #[test]
+    fn join_relative_親フォルダを辿れる() {
+        assert_eq!(
+            join_relative("C:\\Games\\links\\game.lnk", "..\\ゲーム\\game.exe"),
+            "C:\\Games\\ゲーム\\game.exe"
+        );
+        assert_eq!(
+            join_relative("/home/u/links/game.lnk", ".\\bin\\game.exe"),
+            "/home/u/links/bin/game.exe"
+        );
+    }
+}
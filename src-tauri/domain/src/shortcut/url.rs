@@ -0,0 +1,93 @@
+//! インターネットショートカット（.url）を読む
+use super::decode_ansi;
+
+const SECTION: &str = "InternetShortcut";
+
+/// .url の `[InternetShortcut]` セクションの中身
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InternetShortcut {
+    pub url: Option<String>,
+    pub icon_file: Option<String>,
+    pub icon_index: Option<i32>,
+}
+
+impl InternetShortcut {
+    pub fn open(file_path: &str) -> anyhow::Result<Self> {
+        let bytes = std::fs::read(file_path)?;
+        Ok(Self::parse(&decode_ini(&bytes)))
+    }
+
+    /// キーとセクション名は大文字小文字を区別しない。他のセクションのキーは読まない
+    pub fn parse(contents: &str) -> Self {
+        let mut shortcut = InternetShortcut::default();
+        let mut in_section = false;
+        for line in contents.lines() {
+            let line = line.trim();
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                in_section = section.trim().eq_ignore_ascii_case(SECTION);
+                continue;
+            }
+            if !in_section {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            match key.trim().to_ascii_lowercase().as_str() {
+                "url" => shortcut.url = Some(value.to_string()),
+                "iconfile" => shortcut.icon_file = Some(value.to_string()),
+                "iconindex" => shortcut.icon_index = value.parse().ok(),
+                _ => {}
+            }
+        }
+        shortcut
+    }
+}
+
+/// BOM があればそれに従い、なければ .lnk の ANSI 文字列と同じように読む
+fn decode_ini(bytes: &[u8]) -> String {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8_lossy(rest).to_string();
+    }
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        return encoding_rs::UTF_16LE.decode(bytes).0.into_owned();
+    }
+    decode_ansi(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEAM_GAME: &[u8] = include_bytes!("../../fixtures/shortcut/steam_game.url");
+
+    #[test]
+    fn parse_urlとアイコンを読める() {
+        let shortcut = InternetShortcut::parse(&decode_ini(STEAM_GAME));
+
+        assert_eq!(
+            shortcut,
+            InternetShortcut {
+                url: Some("steam://rungameid/1234560".to_string()),
+                icon_file: Some(
+                    "C:\\Program Files (x86)\\Steam\\steam\\games\\ゲーム.ico".to_string()
+                ),
+                icon_index: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_他のセクションのキーは読まない() {
+        let shortcut = InternetShortcut::parse(
+            "[Other]\r\nURL=https://example.com/other\r\n[internetshortcut]\r\nurl = https://example.com/\r\n",
+        );
+
+        assert_eq!(shortcut.url.as_deref(), Some("https://example.com/"));
+        assert_eq!(shortcut.icon_file, None);
+    }
+}
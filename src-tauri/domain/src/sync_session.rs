@@ -0,0 +1,183 @@
+use chrono::{DateTime, Local};
+
+use crate::StrId;
+
+/// 分割同期の対象ストア
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStore {
+    Dmm,
+    Dlsite,
+}
+
+impl SyncStore {
+    /// `SyncCompletedLog` などに書く名前
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncStore::Dmm => "dmm",
+            SyncStore::Dlsite => "dlsite",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "dmm" => Some(SyncStore::Dmm),
+            "dlsite" => Some(SyncStore::Dlsite),
+            _ => None,
+        }
+    }
+}
+
+/// 拡張機能から複数のチャンクに分けて受け取る同期。
+/// ホストが再起動されても続きから受け取れるよう、受け取ったチャンクは DB に残す
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncSession {
+    /// 拡張機能が採番する ID。再開時は同じ ID で BeginSyncSession を送り直す
+    pub id: StrId<SyncSession>,
+    pub store: SyncStore,
+    pub extension_id: String,
+    pub total_chunks: u32,
+    pub committed_at: Option<DateTime<Local>>,
+    /// ライブラリ全体の同期では、これ以降に見えなかった紐付けを欠落とみなす
+    pub created_at: DateTime<Local>,
+    pub updated_at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewSyncSession {
+    pub id: StrId<SyncSession>,
+    pub store: SyncStore,
+    pub extension_id: String,
+    pub total_chunks: u32,
+}
+
+/// 反映済みのチャンク。同じチャンクが再送されたときはこの結果を返す
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncSessionChunk {
+    pub chunk_index: u32,
+    pub game_count: u32,
+    pub success_count: u32,
+    pub new_count: u32,
+    /// 同期前から紐付いていた件数
+    pub unchanged_count: u32,
+    pub error_count: u32,
+    pub errors: Vec<String>,
+}
+
+/// セッションと反映済みチャンクの組
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncSessionProgress {
+    pub session: SyncSession,
+    /// chunk_index の昇順
+    pub chunks: Vec<SyncSessionChunk>,
+}
+
+impl SyncSession {
+    /// 1 セッションのチャンク数の上限
+    pub const MAX_CHUNKS: u32 = 10_000;
+    /// 最後の更新からこれだけ経ったセッションは再開できないものとして捨てる
+    pub const RETENTION_HOURS: i64 = 24;
+
+    pub fn is_committed(&self) -> bool {
+        self.committed_at.is_some()
+    }
+}
+
+impl SyncSessionProgress {
+    pub fn received_chunks(&self) -> Vec<u32> {
+        self.chunks.iter().map(|c| c.chunk_index).collect()
+    }
+
+    /// まだ受け取っていない chunk_index
+    pub fn missing_chunks(&self) -> Vec<u32> {
+        let received = self.received_chunks();
+        (0..self.session.total_chunks)
+            .filter(|i| received.binary_search(i).is_err())
+            .collect()
+    }
+
+    pub fn success_count(&self) -> u32 {
+        self.chunks.iter().map(|c| c.success_count).sum()
+    }
+
+    pub fn new_count(&self) -> u32 {
+        self.chunks.iter().map(|c| c.new_count).sum()
+    }
+
+    pub fn unchanged_count(&self) -> u32 {
+        self.chunks.iter().map(|c| c.unchanged_count).sum()
+    }
+
+    pub fn error_count(&self) -> u32 {
+        self.chunks.iter().map(|c| c.error_count).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SyncSessionError {
+    #[error("sync session not found: {0}")]
+    NotFound(String),
+    #[error("sync session {id} was started with different parameters: {detail}")]
+    Mismatch { id: String, detail: String },
+    #[error("sync session {0} is already committed")]
+    AlreadyCommitted(String),
+    #[error("chunk index {index} is out of range (total_chunks={total_chunks})")]
+    ChunkOutOfRange { index: u32, total_chunks: u32 },
+    #[error("total_chunks must be between 1 and {max}: {value}")]
+    InvalidTotalChunks { value: u32, max: u32 },
+    #[error("sync session {id} is missing chunks: {missing:?}")]
+    Incomplete { id: String, missing: Vec<u32> },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(total_chunks: u32, received: &[u32]) -> SyncSessionProgress {
+        SyncSessionProgress {
+            session: SyncSession {
+                id: StrId::new("session".to_string()),
+                store: SyncStore::Dmm,
+                extension_id: "ext".to_string(),
+                total_chunks,
+                committed_at: None,
+                created_at: Local::now(),
+                updated_at: Local::now(),
+            },
+            chunks: received
+                .iter()
+                .map(|&chunk_index| SyncSessionChunk {
+                    chunk_index,
+                    game_count: 10,
+                    success_count: 9,
+                    new_count: 2,
+                    unchanged_count: 7,
+                    error_count: 1,
+                    errors: vec![],
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn 未受信のチャンクだけを返す() {
+        assert_eq!(progress(4, &[0, 2]).missing_chunks(), vec![1, 3]);
+        assert!(progress(2, &[0, 1]).missing_chunks().is_empty());
+    }
+
+    #[test]
+    fn 件数はチャンクの合計() {
+        let p = progress(3, &[0, 1, 2]);
+        assert_eq!(p.success_count(), 27);
+        assert_eq!(p.new_count(), 6);
+        assert_eq!(p.unchanged_count(), 21);
+        assert_eq!(p.error_count(), 3);
+    }
+
+    #[test]
+    fn ストア名は往復できる() {
+        for store in [SyncStore::Dmm, SyncStore::Dlsite] {
+            assert_eq!(SyncStore::parse(store.as_str()), Some(store));
+        }
+        assert_eq!(SyncStore::parse("steam"), None);
+    }
+}
@@ -1,8 +1,177 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
 use crate::{works::Work, StrId};
 
+/// 一覧・詳細で使い分けるサムネイルの幅（px）
+pub const THUMBNAIL_VARIANT_WIDTHS: [u32; 3] = [200, 400, 800];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThumbnailFormat {
+    Png,
+    Webp,
+}
+
+impl ThumbnailFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::Png => "png",
+            ThumbnailFormat::Webp => "webp",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "png" => Some(ThumbnailFormat::Png),
+            "webp" => Some(ThumbnailFormat::Webp),
+            _ => None,
+        }
+    }
+}
+
+/// 作品ごとに生成済みのサムネイル 1 枚分
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkThumbnailVariant {
+    pub width: i32,
+    pub height: i32,
+    pub format: ThumbnailFormat,
+    pub path: String,
+}
+
+/// `{id}@{width}w.{ext}` 形式のバリアントのファイル名
+pub fn thumbnail_variant_file_name(id: &str, width: u32, format: ThumbnailFormat) -> String {
+    format!("{}@{}w.{}", id, width, format.as_str())
+}
+
+/// 元画像の幅に対して生成するバリアントを (ファイル名に使う幅, 実際の幅) で返す。
+/// 拡大はせず、元画像が最小の幅より小さければ最小の枠に元の幅のまま 1 つだけ作る
+pub fn thumbnail_variant_widths(src_width: u32) -> Vec<(u32, u32)> {
+    if src_width == 0 {
+        return Vec::new();
+    }
+    THUMBNAIL_VARIANT_WIDTHS
+        .iter()
+        .enumerate()
+        .filter(|(i, w)| *i == 0 || **w <= src_width)
+        .map(|(_, w)| (*w, (*w).min(src_width)))
+        .collect()
+}
+
+/// RGBA の画素列から支配的な色を `#rrggbb` で返す。
+/// 各チャンネルを 4bit に量子化して最頻の区画を選び、その区画に属する画素の平均を取る。
+/// 半透明未満の画素は無視し、有効な画素がなければ None
+pub fn dominant_color_hex(rgba: &[u8]) -> Option<String> {
+    let mut buckets: HashMap<u16, (u32, [u64; 3])> = HashMap::new();
+    for px in rgba.chunks_exact(4) {
+        if px[3] < 128 {
+            continue;
+        }
+        let key = ((px[0] as u16 >> 4) << 8) | ((px[1] as u16 >> 4) << 4) | (px[2] as u16 >> 4);
+        let entry = buckets.entry(key).or_insert((0, [0; 3]));
+        entry.0 += 1;
+        for (sum, v) in entry.1.iter_mut().zip(px) {
+            *sum += *v as u64;
+        }
+    }
+    // 同数の区画があっても結果が揺れないよう key でも比較する
+    let (_, (count, sum)) = buckets
+        .into_iter()
+        .max_by(|(ka, (ca, _)), (kb, (cb, _))| ca.cmp(cb).then(kb.cmp(ka)))?;
+    let avg = |c: usize| (sum[c] / count as u64) as u8;
+    Some(format!("#{:02x}{:02x}{:02x}", avg(0), avg(1), avg(2)))
+}
+
+/// 知覚ハッシュ（dHash）を求めるときに縮小する寸法
+pub const PERCEPTUAL_HASH_WIDTH: u32 = 9;
+pub const PERCEPTUAL_HASH_HEIGHT: u32 = 8;
+
+/// `PERCEPTUAL_HASH_WIDTH` x `PERCEPTUAL_HASH_HEIGHT` に縮小したグレースケールの画素列から dHash を求める。
+/// 各行で隣り合う画素を比べ、左の方が明るければ 1 を立てる。寸法が合わなければ None
+pub fn difference_hash(gray: &[u8]) -> Option<u64> {
+    let (width, height) = (
+        PERCEPTUAL_HASH_WIDTH as usize,
+        PERCEPTUAL_HASH_HEIGHT as usize,
+    );
+    if gray.len() != width * height {
+        return None;
+    }
+    let mut hash = 0u64;
+    for row in gray.chunks_exact(width) {
+        for pair in row.windows(2) {
+            hash = (hash << 1) | u64::from(pair[0] > pair[1]);
+        }
+    }
+    Some(hash)
+}
+
+/// 2 つの知覚ハッシュで異なるビットの数。小さいほど見た目が近い
+pub fn perceptual_hash_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
 #[trait_variant::make(Send)]
 #[mockall::automock]
 pub trait ThumbnailService {
     async fn save_thumbnail(&self, id: &StrId<Work>, url: &str) -> anyhow::Result<()>;
     async fn get_thumbnail_size(&self, id: &StrId<Work>) -> anyhow::Result<Option<(u32, u32)>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_variant_widths() {
+        assert_eq!(
+            thumbnail_variant_widths(1200),
+            vec![(200, 200), (400, 400), (800, 800)]
+        );
+        assert_eq!(thumbnail_variant_widths(500), vec![(200, 200), (400, 400)]);
+        assert_eq!(thumbnail_variant_widths(120), vec![(200, 120)]);
+        assert!(thumbnail_variant_widths(0).is_empty());
+    }
+
+    #[test]
+    fn test_dominant_color_hex_picks_most_frequent_bucket() {
+        let mut rgba = Vec::new();
+        for _ in 0..3 {
+            rgba.extend_from_slice(&[200, 10, 10, 255]);
+        }
+        rgba.extend_from_slice(&[0, 0, 250, 255]);
+        // 透明な画素は数えない
+        for _ in 0..10 {
+            rgba.extend_from_slice(&[0, 255, 0, 0]);
+        }
+        assert_eq!(dominant_color_hex(&rgba).as_deref(), Some("#c80a0a"));
+    }
+
+    #[test]
+    fn test_difference_hash_compares_neighbors() {
+        // 左から右へ暗くなる行は全ビットが立ち、明るくなる行は立たない
+        let darker: Vec<u8> = (0..9).rev().map(|v| v * 10).collect();
+        let brighter: Vec<u8> = (0..9).map(|v| v * 10).collect();
+        let mut gray = Vec::new();
+        for i in 0..8 {
+            gray.extend_from_slice(if i == 0 { &darker } else { &brighter });
+        }
+        let hash = difference_hash(&gray).unwrap();
+        assert_eq!(hash, 0xff << 56);
+        assert_eq!(perceptual_hash_distance(hash, 0), 8);
+        assert_eq!(difference_hash(&gray[1..]), None);
+    }
+
+    #[test]
+    fn test_dominant_color_hex_empty() {
+        assert_eq!(dominant_color_hex(&[]), None);
+        assert_eq!(dominant_color_hex(&[1, 2, 3, 0]), None);
+    }
+
+    #[test]
+    fn test_thumbnail_format_roundtrip() {
+        for f in [ThumbnailFormat::Png, ThumbnailFormat::Webp] {
+            assert_eq!(ThumbnailFormat::parse(f.as_str()), Some(f));
+        }
+        assert_eq!(ThumbnailFormat::parse("avif"), None);
+    }
+}
@@ -10,8 +10,10 @@ pub trait ShellLink {
     ///   - .lnk の場合: ショートカットのリンク先（例: 実体の .exe 絶対パス）
     ///   - .url の場合: .url ファイル自体のパス（URL の実体ではなくファイルパス）
     /// - `icon`:
-    ///   - .lnk の場合: IShellLinkW::GetIconLocation により取得されたアイコンファイルパス
+    ///   - .lnk の場合: アイコンの場所として書かれたファイルパス（未設定時は空文字）
     ///   - .url の場合: INI の `IconFile` に記載されたアイコンファイルパス（未設定時は空文字）
+    ///
+    /// どの OS でも `crate::shortcut::read_metadatas` でファイルを直接読めばよい
     fn get_lnk_metadatas(
         &self,
         lnk_file_paths: Vec<String>,
@@ -21,6 +23,11 @@ pub trait ShellLink {
         lnk_path: &'a str,
         is_run_as_admin: bool,
     ) -> anyhow::Result<Option<u32>>;
+    /// 起動プロファイルを当てたコマンドを実行する。戻り値は `execute_lnk` と同じ
+    fn execute_command(
+        &self,
+        command: &crate::launch_profile::LaunchCommand,
+    ) -> anyhow::Result<Option<u32>>;
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{launch_profile::LaunchEnvVar, works::Work, StrId};
+
+/// Windows 以外で実行ファイルを起動するランナー
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WineRunner {
+    #[default]
+    Wine,
+    Proton,
+    Umu,
+}
+
+impl WineRunner {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WineRunner::Wine => "wine",
+            WineRunner::Proton => "proton",
+            WineRunner::Umu => "umu",
+        }
+    }
+}
+
+impl std::str::FromStr for WineRunner {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wine" => Ok(WineRunner::Wine),
+            "proton" => Ok(WineRunner::Proton),
+            "umu" => Ok(WineRunner::Umu),
+            _ => anyhow::bail!("unknown wine runner: {s}"),
+        }
+    }
+}
+
+/// 作品ごとの Wine の設定。設定がない作品は既定の prefix の `wine` で起動する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WineConfig {
+    pub work_id: StrId<Work>,
+    pub runner: WineRunner,
+    /// 未指定なら `wine` / `umu-run` を PATH から探す。Proton は `proton` スクリプトのパスが要る
+    pub runner_path: Option<String>,
+    /// Wine と umu では WINEPREFIX、Proton では STEAM_COMPAT_DATA_PATH になる
+    pub prefix: Option<String>,
+    pub env: Vec<LaunchEnvVar>,
+}
+
+impl WineConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.runner == WineRunner::Proton {
+            if self.runner_path.is_none() {
+                anyhow::bail!("proton runner requires the path to the proton script");
+            }
+            if self.prefix.is_none() {
+                anyhow::bail!("proton runner requires a compat data path");
+            }
+        }
+        for var in &self.env {
+            if var.key.is_empty() || var.key.contains('=') {
+                anyhow::bail!("invalid environment variable name: {:?}", var.key);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_protonはスクリプトとprefixが要る() {
+        let config = WineConfig {
+            work_id: StrId::new("w".into()),
+            runner: WineRunner::Proton,
+            runner_path: Some("/opt/proton/proton".into()),
+            prefix: Some("/home/u/compat/w".into()),
+            env: vec![],
+        };
+        assert!(config.validate().is_ok());
+        assert!(WineConfig {
+            runner_path: None,
+            ..config.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(WineConfig {
+            prefix: None,
+            ..config.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(WineConfig {
+            runner: WineRunner::Umu,
+            runner_path: None,
+            prefix: None,
+            ..config
+        }
+        .validate()
+        .is_ok());
+    }
+}
@@ -0,0 +1,267 @@
+//! 同じゲームが別々の作品として登録されたものを見つける
+use std::collections::{BTreeMap, HashMap};
+
+use crate::all_game_cache::AllGameCacheOne;
+use crate::distance::get_comparable_distance_bounded;
+use crate::game_matcher::config::remove_unnecessary_words;
+use crate::game_matcher::{normalize, GameMatcher, MatcherIndex};
+use crate::thumbnail::perceptual_hash_distance;
+use crate::{works::Work, StrId};
+
+/// タイトルの類似度がこれを超えれば重複の候補にする
+pub const DUPLICATE_TITLE_SIMILARITY: f32 = 0.9;
+/// 知覚ハッシュの差がこのビット数以下なら同じ画像とみなす
+pub const DUPLICATE_THUMBNAIL_DISTANCE: u32 = 4;
+
+/// 重複の判定に使う作品ごとの情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateSource {
+    pub work_id: StrId<Work>,
+    pub title: String,
+    pub erogamescape_id: Option<i32>,
+    pub thumbnail_hash: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DuplicateReason {
+    /// 批評空間の ID が同じ。紐づいていない作品はタイトルから推定した ID で比べる
+    SameErogamescapeId(i32),
+    SimilarTitle {
+        score: f32,
+    },
+    SimilarThumbnail {
+        distance: u32,
+    },
+}
+
+/// 重複の疑いがある 2 作品。`work_ids` は ID の昇順
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCandidate {
+    pub work_ids: (StrId<Work>, StrId<Work>),
+    pub reasons: Vec<DuplicateReason>,
+}
+
+/// 統合した結果。サムネイルを移したときは呼び出し側で画像ファイルも移す
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkMergeResult {
+    pub thumbnail_moved: bool,
+}
+
+/// 作品を 2 つずつ比べ、重複の疑いがある組を理由付きで返す。
+/// `egs_matcher` は批評空間に紐づいていない作品の ID を推定するのに使う
+pub fn find_duplicate_candidates(
+    sources: &[DuplicateSource],
+    egs_matcher: &dyn GameMatcher,
+) -> Vec<DuplicateCandidate> {
+    let mut pairs: BTreeMap<(usize, usize), Vec<DuplicateReason>> = BTreeMap::new();
+    let mut push = |a: usize, b: usize, reason: DuplicateReason| {
+        let key = (a.min(b), a.max(b));
+        pairs.entry(key).or_default().push(reason);
+    };
+
+    let titles: Vec<String> = sources
+        .iter()
+        .map(|s| remove_unnecessary_words(&s.title).trim().to_string())
+        .collect();
+
+    // 批評空間の ID
+    let mut by_egs: HashMap<i32, Vec<usize>> = HashMap::new();
+    for (i, source) in sources.iter().enumerate() {
+        let egs_id = source.erogamescape_id.or_else(|| {
+            egs_matcher
+                .find_candidates(std::slice::from_ref(&titles[i]))
+                .into_iter()
+                .next()
+                .filter(|(_, score)| *score > DUPLICATE_TITLE_SIMILARITY)
+                .map(|(game, _)| game.id)
+        });
+        if let Some(egs_id) = egs_id {
+            by_egs.entry(egs_id).or_default().push(i);
+        }
+    }
+    for (egs_id, indexes) in by_egs.iter() {
+        for (n, a) in indexes.iter().enumerate() {
+            for b in indexes.iter().skip(n + 1) {
+                push(*a, *b, DuplicateReason::SameErogamescapeId(*egs_id));
+            }
+        }
+    }
+
+    // タイトル。正規化して同じになる作品はまとめ、残りはマッチャーの索引と距離で比べる
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    let mut group_of: HashMap<String, usize> = HashMap::new();
+    for (i, title) in titles.iter().enumerate() {
+        let normalized = normalize(title);
+        if normalized.is_empty() {
+            continue;
+        }
+        let g = *group_of.entry(normalized.clone()).or_insert_with(|| {
+            groups.push((normalized, Vec::new()));
+            groups.len() - 1
+        });
+        groups[g].1.push(i);
+    }
+    for (_, members) in groups.iter() {
+        for (n, a) in members.iter().enumerate() {
+            for b in members.iter().skip(n + 1) {
+                push(*a, *b, DuplicateReason::SimilarTitle { score: 1.0 });
+            }
+        }
+    }
+    let index = MatcherIndex::build(
+        &groups
+            .iter()
+            .enumerate()
+            .map(|(g, (title, _))| AllGameCacheOne::new(g as i32, title.clone()))
+            .collect::<Vec<_>>(),
+    );
+    for (g, (title, members)) in groups.iter().enumerate() {
+        for h in index
+            .ngram
+            .filter_candidates(title, DUPLICATE_TITLE_SIMILARITY, &[])
+        {
+            // 同じ組は両方向から見つかるので片方だけ数える
+            let h = h as usize;
+            if h <= g {
+                continue;
+            }
+            let Some(score) =
+                get_comparable_distance_bounded(title, &groups[h].0, DUPLICATE_TITLE_SIMILARITY)
+            else {
+                continue;
+            };
+            for a in members.iter() {
+                for b in groups[h].1.iter() {
+                    push(*a, *b, DuplicateReason::SimilarTitle { score });
+                }
+            }
+        }
+    }
+
+    // サムネイル
+    let hashes: Vec<(usize, u64)> = sources
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| s.thumbnail_hash.map(|h| (i, h)))
+        .collect();
+    for (n, (a, hash_a)) in hashes.iter().enumerate() {
+        for (b, hash_b) in hashes.iter().skip(n + 1) {
+            let distance = perceptual_hash_distance(*hash_a, *hash_b);
+            if distance <= DUPLICATE_THUMBNAIL_DISTANCE {
+                push(*a, *b, DuplicateReason::SimilarThumbnail { distance });
+            }
+        }
+    }
+
+    pairs
+        .into_iter()
+        .map(|((a, b), reasons)| {
+            let (a, b) = (&sources[a].work_id, &sources[b].work_id);
+            let work_ids = if a.value <= b.value {
+                (a.clone(), b.clone())
+            } else {
+                (b.clone(), a.clone())
+            };
+            DuplicateCandidate { work_ids, reasons }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_matcher::MockGameMatcher;
+
+    fn source(id: &str, title: &str) -> DuplicateSource {
+        DuplicateSource {
+            work_id: StrId::new(id.to_string()),
+            title: title.to_string(),
+            erogamescape_id: None,
+            thumbnail_hash: None,
+        }
+    }
+
+    fn no_egs_matches() -> MockGameMatcher {
+        let mut matcher = MockGameMatcher::new();
+        matcher.expect_find_candidates().returning(|_| Vec::new());
+        matcher
+    }
+
+    #[test]
+    fn find_duplicate_candidates_紐づいていない作品は推定した批評空間の作品で比べる() {
+        let mut matcher = MockGameMatcher::new();
+        matcher.expect_find_candidates().returning(|queries| {
+            if queries[0] == "DMMで買った方" {
+                vec![(AllGameCacheOne::new(100, "ゲーム".into()), 0.95)]
+            } else {
+                Vec::new()
+            }
+        });
+        let sources = vec![
+            DuplicateSource {
+                erogamescape_id: Some(100),
+                ..source("b", "批評空間から登録")
+            },
+            source("a", "DMMで買った方"),
+            source("c", "無関係"),
+        ];
+
+        let candidates = find_duplicate_candidates(&sources, &matcher);
+
+        assert_eq!(
+            candidates,
+            vec![DuplicateCandidate {
+                work_ids: (StrId::new("a".into()), StrId::new("b".into())),
+                reasons: vec![DuplicateReason::SameErogamescapeId(100)],
+            }]
+        );
+    }
+
+    #[test]
+    fn find_duplicate_candidates_正規化したタイトルと画像の近さで見つける() {
+        let sources = vec![
+            source("a", "ＡＢＣ　ストーリー"),
+            DuplicateSource {
+                thumbnail_hash: Some(0b1111),
+                ..source("b", "abc　ストーリー DL版")
+            },
+            DuplicateSource {
+                thumbnail_hash: Some(0b0111),
+                ..source("c", "まったく別のタイトル")
+            },
+            DuplicateSource {
+                thumbnail_hash: Some(u64::MAX),
+                ..source("d", "もうひとつ別のタイトル")
+            },
+            source("e", "魔法少女の夏休み物語"),
+            source("f", "魔法少女の夏休み物語!"),
+        ];
+
+        let candidates = find_duplicate_candidates(&sources, &no_egs_matches());
+
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(
+            candidates[0].work_ids,
+            (StrId::new("a".into()), StrId::new("b".into()))
+        );
+        assert!(matches!(
+            candidates[0].reasons[..],
+            [DuplicateReason::SimilarTitle { .. }]
+        ));
+        assert_eq!(
+            candidates[1],
+            DuplicateCandidate {
+                work_ids: (StrId::new("b".into()), StrId::new("c".into())),
+                reasons: vec![DuplicateReason::SimilarThumbnail { distance: 1 }],
+            }
+        );
+        assert_eq!(
+            candidates[2].work_ids,
+            (StrId::new("e".into()), StrId::new("f".into()))
+        );
+        assert!(matches!(
+            candidates[2].reasons[..],
+            [DuplicateReason::SimilarTitle { score }] if score < 1.0
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+//! 利用者が直した作品の情報。同期や取り込みで得た情報より優先する
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::works::WorkDetails;
+
+/// 発売日の書式。批評空間の sellday と同じ
+pub const OVERRIDE_SELLDAY_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkOverrideField {
+    Title,
+    /// 並べ替えに使う読み
+    TitleRuby,
+    Brandname,
+    Sellday,
+}
+
+impl WorkOverrideField {
+    /// 前後の空白を除き、発売日は `OVERRIDE_SELLDAY_FORMAT` にそろえる。空にはできない
+    pub fn normalize_value(&self, value: &str) -> anyhow::Result<String> {
+        let value = value.trim();
+        if value.is_empty() {
+            anyhow::bail!("override value must not be empty");
+        }
+        match self {
+            WorkOverrideField::Sellday => {
+                let date = NaiveDate::parse_from_str(value, OVERRIDE_SELLDAY_FORMAT)
+                    .or_else(|_| NaiveDate::parse_from_str(value, "%Y/%m/%d"))
+                    .map_err(|_| anyhow::anyhow!("invalid sellday: {value}"))?;
+                Ok(date.format(OVERRIDE_SELLDAY_FORMAT).to_string())
+            }
+            _ => Ok(value.to_string()),
+        }
+    }
+}
+
+/// None の項目は元の情報を使う
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkOverrides {
+    pub title: Option<String>,
+    pub title_ruby: Option<String>,
+    pub brandname: Option<String>,
+    pub sellday: Option<NaiveDate>,
+}
+
+impl WorkOverrides {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// 作品名と批評空間の情報を上書きし、`details.overrides` にも残す。
+    /// 批評空間に紐づいていない作品の読み・ブランド・発売日は `details.overrides` からだけ読める
+    pub fn apply(self, details: &mut WorkDetails) {
+        if let Some(title) = &self.title {
+            details.work.title = title.clone();
+        }
+        if let Some(info) = details.erogamescape_information.as_mut() {
+            if let Some(ruby) = &self.title_ruby {
+                info.gamename_ruby = ruby.clone();
+            }
+            if let Some(brandname) = &self.brandname {
+                info.brandname = brandname.clone();
+            }
+            if let Some(sellday) = &self.sellday {
+                info.sellday = sellday.format(OVERRIDE_SELLDAY_FORMAT).to_string();
+            }
+        }
+        details.overrides = self;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::works::Work;
+    use crate::StrId;
+
+    #[test]
+    fn normalize_value_発売日の書式をそろえる() {
+        let field = WorkOverrideField::Sellday;
+        assert_eq!(field.normalize_value(" 2024/1/5 ").unwrap(), "2024-01-05");
+        assert!(field.normalize_value("2024-13-01").is_err());
+        assert!(WorkOverrideField::Title.normalize_value("  ").is_err());
+        assert_eq!(
+            WorkOverrideField::Brandname
+                .normalize_value(" ブランド ")
+                .unwrap(),
+            "ブランド"
+        );
+    }
+
+    #[test]
+    fn apply_直した項目だけ置き換える() {
+        let mut details = WorkDetails::new(
+            Work::new(StrId::new("w".into()), "元の名前".into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let overrides = WorkOverrides {
+            title: Some("直した名前".into()),
+            brandname: Some("ブランド".into()),
+            ..Default::default()
+        };
+
+        overrides.clone().apply(&mut details);
+
+        assert_eq!(details.work.title, "直した名前");
+        assert_eq!(details.overrides, overrides);
+    }
+}
@@ -0,0 +1,142 @@
+//! 作品一覧の絞り込み・並び替え・ページ送りと、絞り込み用サイドバーの件数
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{play_status::PlayStatus, works::DmmPackKey, works::WorkDetails};
+
+/// 1ページに返す作品数の上限
+pub const MAX_WORK_PAGE_SIZE: u32 = 500;
+
+/// どのストア由来の作品か
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkStore {
+    Dmm,
+    Dlsite,
+    /// どのストアにも紐づかず、批評空間の情報だけで登録した作品
+    ErogamescapeOnly,
+}
+
+/// 作品一覧の絞り込み。空の項目では絞り込まない
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkListFilter {
+    /// いずれかのストアに当てはまれば残す
+    pub stores: Vec<WorkStore>,
+    pub liked: Option<bool>,
+    pub installed: Option<bool>,
+    /// 批評空間の情報がない作品は抜きゲーでないとみなす
+    pub nukige: Option<bool>,
+    pub brands: Vec<String>,
+    /// 両端を含む。発売日の分からない作品は範囲を指定すると出さない
+    pub sellday_from: Option<NaiveDate>,
+    pub sellday_to: Option<NaiveDate>,
+    pub parent_pack: Option<DmmPackKey>,
+    /// 記録のない作品は未プレイとして扱う
+    pub play_statuses: Vec<PlayStatus>,
+    /// 点数で絞り込むときは、点数を付けていない作品は出さない
+    pub min_score: Option<i32>,
+    pub max_score: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkSortKey {
+    /// 読みがなければタイトル
+    #[default]
+    TitleRuby,
+    Sellday,
+    LastPlayAt,
+    RegisteredAt,
+    InstallAt,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// 値のない作品は向きによらず最後に並ぶ。同じ値の作品は作品 ID で並べる
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkSort {
+    pub key: WorkSortKey,
+    pub direction: SortDirection,
+}
+
+/// 前のページの最後の作品。次のページはこの作品の直後から始まる
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkCursor {
+    pub sort_value: String,
+    pub work_id: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkQuery {
+    pub filter: WorkListFilter,
+    pub sort: WorkSort,
+    pub after: Option<WorkCursor>,
+    /// 省くと残りをすべて返す
+    pub limit: Option<u32>,
+}
+
+impl WorkQuery {
+    pub fn page_size(&self) -> Option<u32> {
+        self.limit.map(|limit| limit.clamp(1, MAX_WORK_PAGE_SIZE))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorkPage {
+    pub items: Vec<WorkDetails>,
+    /// 続きがなければ None
+    pub next_cursor: Option<WorkCursor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FacetCount<T> {
+    pub value: T,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoolFacetCount {
+    pub yes: i64,
+    pub no: i64,
+}
+
+/// 絞り込み用サイドバーの件数。
+/// 各項目の件数は、その項目自身の絞り込みを外し、ほかの絞り込みはかけたまま数える
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkFacets {
+    /// すべての絞り込みをかけた件数
+    pub total: i64,
+    pub stores: Vec<FacetCount<WorkStore>>,
+    pub liked: BoolFacetCount,
+    pub installed: BoolFacetCount,
+    pub nukige: BoolFacetCount,
+    pub play_statuses: Vec<FacetCount<PlayStatus>>,
+    /// 件数の多い順
+    pub brands: Vec<FacetCount<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_size_件数を上限までに収める() {
+        let query = |limit| WorkQuery {
+            limit,
+            ..Default::default()
+        };
+        assert_eq!(query(None).page_size(), None);
+        assert_eq!(query(Some(0)).page_size(), Some(1));
+        assert_eq!(query(Some(50)).page_size(), Some(50));
+        assert_eq!(
+            query(Some(MAX_WORK_PAGE_SIZE + 1)).page_size(),
+            Some(MAX_WORK_PAGE_SIZE)
+        );
+    }
+}
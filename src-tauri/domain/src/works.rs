@@ -2,6 +2,10 @@ use derive_new::new;
 use serde::{Deserialize, Serialize};
 
 use crate::erogamescape::ErogamescapeInformation;
+use crate::game_engine::GameEngine;
+use crate::play_status::WorkPlayState;
+use crate::thumbnail::WorkThumbnailVariant;
+use crate::work_override::WorkOverrides;
 use crate::{Id, StrId};
 use chrono::{DateTime, Local};
 
@@ -39,6 +43,16 @@ pub struct DlsiteWork {
     pub category: String,
 }
 
+/// ライブラリ全体の同期で見つからなかったストアの紐付け（返金・配信終了・アカウント変更など）
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MissingStoreWork {
+    pub work_id: StrId<Work>,
+    pub title: String,
+    pub store_id: String,
+    pub category: String,
+    pub missing_since: DateTime<Local>,
+}
+
 #[derive(new, Clone, Debug, Serialize, Deserialize)]
 pub struct NewDmmWork {
     pub store_id: String,
@@ -73,12 +87,26 @@ pub struct WorkDetails {
     pub install_at: Option<DateTime<Local>>,
     #[new(default)]
     pub original_path: Option<String>,
+    /// インストール時にファイル構成から見分けたエンジン
+    #[new(default)]
+    pub engine: Option<GameEngine>,
     #[new(default)]
     pub last_play_at: Option<DateTime<Local>>,
+    /// 記録がなければ未プレイ
+    #[new(default)]
+    pub play_state: Option<WorkPlayState>,
     #[new(default)]
     pub registered_at: Option<DateTime<Local>>,
     #[new(default)]
     pub thumbnail_size: Option<WorkThumbnailSize>,
+    #[new(default)]
+    pub thumbnail_variants: Vec<WorkThumbnailVariant>,
+    /// サムネイル読み込み前に表示する支配色（#rrggbb）
+    #[new(default)]
+    pub thumbnail_placeholder_color: Option<String>,
+    /// 利用者が直した項目。作品名などには反映済み
+    #[new(default)]
+    pub overrides: WorkOverrides,
 }
 
 #[derive(new, Clone, Debug, Serialize, Deserialize)]
@@ -83,7 +83,7 @@ impl AppSignalEndpoint {
         let mut base = std::env::temp_dir();
         base.push(Self::DIR_NAME);
         let path = base.join(Self::FILE_NAME);
-        let name = path.to_fs_name::<GenericFilePath>()?.into_owned();
+        let name = path.clone().to_fs_name::<GenericFilePath>()?.into_owned();
         Ok((name, path))
     }
 }
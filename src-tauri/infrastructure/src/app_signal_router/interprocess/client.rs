@@ -1,11 +1,24 @@
+use std::time::Duration;
+
 use anyhow::Context;
-use domain::service::app_signal_router::{AppSignal, AppSignalRouter};
+use chrono::Utc;
+use domain::service::app_signal_router::{
+    AppRequest, AppRequestEnvelope, AppResponse, AppResponseEnvelope, AppSignal, AppSignalRouter,
+    AppSignalSource,
+};
 use interprocess::local_socket::{tokio::Stream, traits::tokio::Stream as _};
+use uuid::Uuid;
 
+use super::frame::{read_frame, write_frame};
 use crate::app_signal_router::endpoint::AppSignalEndpoint;
 
-/// `interprocess` を利用したアプリシグナル送信クライアント（実装は後続タスクで拡張）。
-pub struct InterprocessAppSignalRouter;
+/// 問い合わせの応答を待つ既定の時間
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `interprocess` を利用したアプリシグナル送信クライアント。
+pub struct InterprocessAppSignalRouter {
+    request_timeout: Duration,
+}
 
 impl AppSignalRouter for InterprocessAppSignalRouter {
     async fn dispatch(&self, signal: AppSignal) -> anyhow::Result<()> {
@@ -16,27 +29,72 @@ impl AppSignalRouter for InterprocessAppSignalRouter {
 
         send_signal(&mut stream, signal).await
     }
+
+    async fn request(&self, request: AppRequest) -> anyhow::Result<Option<AppResponse>> {
+        let name = AppSignalEndpoint::connect_name()?;
+        let mut stream = match Stream::connect(name).await {
+            Ok(stream) => stream,
+            Err(err) => {
+                // アプリが起動していない。送り手が自分で処理する
+                log::debug!("app signal endpoint is not available: {err}");
+                return Ok(None);
+            }
+        };
+
+        let envelope = AppRequestEnvelope {
+            request_id: Uuid::new_v4().to_string(),
+            source: AppSignalSource::NativeMessagingHost,
+            request,
+            issued_at: Utc::now(),
+        };
+        let response = tokio::time::timeout(self.request_timeout, exchange(&mut stream, &envelope))
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "timed out waiting for app response after {:?}",
+                    self.request_timeout
+                )
+            })??;
+        Ok(Some(response))
+    }
 }
 
 async fn send_signal(stream: &mut Stream, signal: AppSignal) -> anyhow::Result<()> {
-    let payload = serde_json::to_vec(&signal)?;
-    let len = (payload.len() as u32).to_le_bytes();
-    use tokio::io::AsyncWriteExt;
-    stream.write_all(&len).await?;
-    stream.write_all(&payload).await?;
-    stream.flush().await?;
-    Ok(())
+    write_frame(stream, &signal).await
+}
+
+async fn exchange(
+    stream: &mut Stream,
+    envelope: &AppRequestEnvelope,
+) -> anyhow::Result<AppResponse> {
+    write_frame(stream, envelope).await?;
+    let response: AppResponseEnvelope = read_frame(stream, "app response").await?;
+    if response.request_id != envelope.request_id {
+        anyhow::bail!(
+            "app response for another request: expected {}, got {}",
+            envelope.request_id,
+            response.request_id
+        );
+    }
+    Ok(response.response)
 }
 
 impl InterprocessAppSignalRouter {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
     }
 }
 
 impl Default for InterprocessAppSignalRouter {
     fn default() -> Self {
-        Self
+        Self {
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
     }
 }
 
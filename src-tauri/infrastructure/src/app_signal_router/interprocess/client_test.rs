@@ -7,7 +7,7 @@ mod tests {
     #[cfg(not(windows))]
     use domain::pubsub::PubSubEvent;
     use domain::service::app_signal_router::{
-        AppSignal, AppSignalEvent, AppSignalRouter, AppSignalSource,
+        AppRequest, AppSignal, AppSignalEvent, AppSignalRouter, AppSignalSource,
     };
     use interprocess::local_socket::{
         tokio::Stream,
@@ -19,11 +19,13 @@ mod tests {
     #[cfg(not(windows))]
     use crate::app_signal_router::{
         interprocess::listener::spawn_listener,
-        test_support::{RecordingPubSub, TempDirEnvGuard},
+        test_support::{RecordingPubSub, RecordingRequestHandler, TempDirEnvGuard},
         APP_SIGNAL_EVENT, APP_SIGNAL_REFETCH_WORKS_EVENT, APP_SIGNAL_REFETCH_WORK_EVENT,
         APP_SIGNAL_SHOW_ERROR_MESSAGE_EVENT, APP_SIGNAL_SHOW_MESSAGE_EVENT,
     };
     #[cfg(not(windows))]
+    use domain::service::app_signal_router::AppResponse;
+    #[cfg(not(windows))]
     use std::sync::Arc;
     #[cfg(not(windows))]
     use tokio::time::{sleep, Duration};
@@ -122,7 +124,10 @@ mod tests {
         let _lock = test_lock();
         let env_guard = TempDirEnvGuard::new()?;
         let pubsub = Arc::new(RecordingPubSub::new());
-        spawn_listener(Arc::clone(&pubsub))?;
+        spawn_listener(
+            Arc::clone(&pubsub),
+            Arc::new(RecordingRequestHandler::default()),
+        )?;
 
         sleep(Duration::from_millis(50)).await;
 
@@ -155,7 +160,10 @@ mod tests {
         let _lock = test_lock();
         let env_guard = TempDirEnvGuard::new()?;
         let pubsub = Arc::new(RecordingPubSub::new());
-        spawn_listener(Arc::clone(&pubsub))?;
+        spawn_listener(
+            Arc::clone(&pubsub),
+            Arc::new(RecordingRequestHandler::default()),
+        )?;
 
         sleep(Duration::from_millis(50)).await;
 
@@ -189,7 +197,10 @@ mod tests {
         let _lock = test_lock();
         let env_guard = TempDirEnvGuard::new()?;
         let pubsub = Arc::new(RecordingPubSub::new());
-        spawn_listener(Arc::clone(&pubsub))?;
+        spawn_listener(
+            Arc::clone(&pubsub),
+            Arc::new(RecordingRequestHandler::default()),
+        )?;
 
         sleep(Duration::from_millis(50)).await;
 
@@ -223,7 +234,10 @@ mod tests {
         let _lock = test_lock();
         let env_guard = TempDirEnvGuard::new()?;
         let pubsub = Arc::new(RecordingPubSub::new());
-        spawn_listener(Arc::clone(&pubsub))?;
+        spawn_listener(
+            Arc::clone(&pubsub),
+            Arc::new(RecordingRequestHandler::default()),
+        )?;
 
         sleep(Duration::from_millis(50)).await;
 
@@ -250,4 +264,66 @@ mod tests {
         drop(env_guard);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn request_アプリ未起動ならnoneを返す() -> Result<()> {
+        let _lock = test_lock();
+        #[cfg(not(windows))]
+        let _env = TempDirEnvGuard::new()?;
+
+        let router = InterprocessAppSignalRouter::new();
+        let response = router.request(AppRequest::ImageQueueStatus).await?;
+        assert!(response.is_none());
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn request_listener統合_応答を受け取る() -> Result<()> {
+        let _lock = test_lock();
+        let _env = TempDirEnvGuard::new()?;
+        let pubsub = Arc::new(RecordingPubSub::new());
+        let handler = Arc::new(RecordingRequestHandler::new(AppResponse::DrainImageQueue {
+            accepted: true,
+        }));
+        spawn_listener(Arc::clone(&pubsub), Arc::clone(&handler))?;
+
+        sleep(Duration::from_millis(50)).await;
+
+        let router = InterprocessAppSignalRouter::new();
+        let response = router.request(AppRequest::DrainImageQueue).await?;
+
+        assert_eq!(
+            response,
+            Some(AppResponse::DrainImageQueue { accepted: true })
+        );
+        assert_eq!(handler.requests(), vec![AppRequest::DrainImageQueue]);
+        assert!(pubsub.events().is_empty());
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn request_応答が遅ければタイムアウトする() -> Result<()> {
+        let _lock = test_lock();
+        let _env = TempDirEnvGuard::new()?;
+        let handler = Arc::new(RecordingRequestHandler::delayed(
+            AppResponse::DrainImageQueue { accepted: true },
+            Duration::from_secs(2),
+        ));
+        spawn_listener(Arc::new(RecordingPubSub::new()), handler)?;
+
+        sleep(Duration::from_millis(50)).await;
+
+        let router =
+            InterprocessAppSignalRouter::new().with_request_timeout(Duration::from_millis(100));
+        let err = router
+            .request(AppRequest::DrainImageQueue)
+            .await
+            .expect_err("expected timeout");
+        assert!(err
+            .to_string()
+            .contains("timed out waiting for app response"));
+        Ok(())
+    }
 }
@@ -0,0 +1,31 @@
+use anyhow::{Context, Result};
+use interprocess::local_socket::tokio::Stream;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// 長さ（u32 LE）と JSON 本体を 1 フレームとして書き込む。
+pub(crate) async fn write_frame<T: Serialize>(stream: &mut Stream, value: &T) -> Result<()> {
+    let payload = serde_json::to_vec(value)?;
+    let len = (payload.len() as u32).to_le_bytes();
+    stream.write_all(&len).await?;
+    stream.write_all(&payload).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+/// `write_frame` で書かれた 1 フレームを読む。`what` はエラーの文脈に使う。
+pub(crate) async fn read_frame<T: DeserializeOwned>(stream: &mut Stream, what: &str) -> Result<T> {
+    let mut len_buf = [0u8; 4];
+    stream
+        .read_exact(&mut len_buf)
+        .await
+        .with_context(|| "failed to read message length")?;
+    let expected = u32::from_le_bytes(len_buf) as usize;
+    let mut payload = vec![0u8; expected];
+    stream
+        .read_exact(&mut payload)
+        .await
+        .with_context(|| format!("failed to read {what} payload"))?;
+
+    serde_json::from_slice(&payload).with_context(|| format!("failed to deserialize {what}"))
+}
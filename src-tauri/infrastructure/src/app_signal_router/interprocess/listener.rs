@@ -3,29 +3,34 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use domain::{
     pubsub::{PubSubEvent, PubSubService},
-    service::app_signal_router::{AppSignal, AppSignalEvent},
+    service::app_signal_router::{
+        AppRequestEnvelope, AppRequestHandler, AppResponse, AppResponseEnvelope, AppSignal,
+        AppSignalEvent, AppSignalMessage,
+    },
 };
 use interprocess::local_socket::{
     tokio::{Listener as TokioListener, Stream},
     traits::tokio::Listener as _,
     ListenerOptions,
 };
-use tokio::{fs, io::AsyncReadExt};
+use tokio::fs;
 
+use super::frame::{read_frame, write_frame};
 use crate::app_signal_router::{
     endpoint::AppSignalEndpoint, APP_SIGNAL_EVENT, APP_SIGNAL_REFETCH_WORKS_EVENT,
     APP_SIGNAL_REFETCH_WORK_EVENT, APP_SIGNAL_SHOW_ERROR_MESSAGE_EVENT,
     APP_SIGNAL_SHOW_MESSAGE_EVENT,
 };
 
-pub fn spawn_listener<P>(pubsub: Arc<P>) -> Result<()>
+pub fn spawn_listener<P, H>(pubsub: Arc<P>, handler: Arc<H>) -> Result<()>
 where
     P: PubSubService + 'static,
+    H: AppRequestHandler + 'static,
 {
     let config = AppSignalEndpoint::prepare_listener()?;
 
     tauri::async_runtime::spawn(async move {
-        if let Err(err) = run_listener(config.options, config.cleanup_path, pubsub).await {
+        if let Err(err) = run_listener(config.options, config.cleanup_path, pubsub, handler).await {
             log::error!("app signal listener stopped: {err}");
         }
     });
@@ -33,13 +38,15 @@ where
     Ok(())
 }
 
-async fn run_listener<P>(
+async fn run_listener<P, H>(
     options: ListenerOptions<'static>,
     cleanup_path: Option<std::path::PathBuf>,
     pubsub: Arc<P>,
+    handler: Arc<H>,
 ) -> Result<()>
 where
     P: PubSubService + 'static,
+    H: AppRequestHandler + 'static,
 {
     let listener: TokioListener = options
         .create_tokio()
@@ -50,8 +57,9 @@ where
         match listener.accept().await {
             Ok(stream) => {
                 let pubsub = Arc::clone(&pubsub);
+                let handler = Arc::clone(&handler);
                 tauri::async_runtime::spawn(async move {
-                    if let Err(err) = handle_stream(stream, pubsub).await {
+                    if let Err(err) = handle_stream(stream, pubsub, handler).await {
                         log::warn!("app signal handling failed: {err}");
                     }
                 });
@@ -77,12 +85,52 @@ where
     result
 }
 
-async fn handle_stream<P>(mut stream: Stream, pubsub: Arc<P>) -> Result<()>
+async fn handle_stream<P, H>(mut stream: Stream, pubsub: Arc<P>, handler: Arc<H>) -> Result<()>
 where
     P: PubSubService + 'static,
+    H: AppRequestHandler + 'static,
 {
-    let signal = read_signal(&mut stream).await?;
+    match read_message(&mut stream).await? {
+        AppSignalMessage::Signal(signal) => emit_signal(signal, pubsub.as_ref()),
+        AppSignalMessage::Request(envelope) => {
+            answer_request(&mut stream, envelope, handler.as_ref()).await
+        }
+    }
+}
+
+/// 問い合わせを処理して同じ接続へ応答を書き戻す。処理の失敗も応答として返す
+async fn answer_request<H>(
+    stream: &mut Stream,
+    envelope: AppRequestEnvelope,
+    handler: &H,
+) -> Result<()>
+where
+    H: AppRequestHandler + 'static,
+{
+    let response = match handler.handle(envelope.request.clone()).await {
+        Ok(response) => response,
+        Err(err) => {
+            log::warn!("app request {:?} failed: {err:#}", envelope.request);
+            AppResponse::Error {
+                message: format!("{err:#}"),
+            }
+        }
+    };
+    write_frame(
+        stream,
+        &AppResponseEnvelope {
+            request_id: envelope.request_id,
+            response,
+        },
+    )
+    .await
+    .with_context(|| "failed to write app response")
+}
 
+fn emit_signal<P>(signal: AppSignal, pubsub: &P) -> Result<()>
+where
+    P: PubSubService + 'static,
+{
     pubsub
         .notify(PubSubEvent::AppSignal(signal.clone().into()))
         .with_context(|| format!("failed to emit {APP_SIGNAL_EVENT}"))?;
@@ -116,22 +164,8 @@ where
     Ok(())
 }
 
-async fn read_signal(stream: &mut Stream) -> Result<AppSignal> {
-    let mut len_buf = [0u8; 4];
-    stream
-        .read_exact(&mut len_buf)
-        .await
-        .with_context(|| "failed to read message length")?;
-    let expected = u32::from_le_bytes(len_buf) as usize;
-    let mut payload = vec![0u8; expected];
-    stream
-        .read_exact(&mut payload)
-        .await
-        .with_context(|| "failed to read app signal payload")?;
-
-    let signal: AppSignal =
-        serde_json::from_slice(&payload).with_context(|| "failed to deserialize app signal")?;
-    Ok(signal)
+async fn read_message(stream: &mut Stream) -> Result<AppSignalMessage> {
+    read_frame(stream, "app signal").await
 }
 
 #[cfg(test)]
@@ -1,7 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use super::super::{handle_stream, read_signal};
-    use crate::app_signal_router::test_support::{test_lock, RecordingPubSub, TestEndpoint};
+    use super::super::{handle_stream, read_message};
+    use crate::app_signal_router::test_support::{
+        test_lock, RecordingPubSub, RecordingRequestHandler, TestEndpoint,
+    };
     use crate::app_signal_router::{
         APP_SIGNAL_EVENT, APP_SIGNAL_REFETCH_WORKS_EVENT, APP_SIGNAL_REFETCH_WORK_EVENT,
         APP_SIGNAL_SHOW_ERROR_MESSAGE_EVENT, APP_SIGNAL_SHOW_MESSAGE_EVENT,
@@ -9,14 +11,17 @@ mod tests {
     use anyhow::{Error, Result};
     use chrono::Utc;
     use domain::pubsub::PubSubEvent;
-    use domain::service::app_signal_router::{AppSignal, AppSignalEvent, AppSignalSource};
+    use domain::service::app_signal_router::{
+        AppRequest, AppRequestEnvelope, AppResponse, AppResponseEnvelope, AppSignal,
+        AppSignalEvent, AppSignalMessage, AppSignalSource,
+    };
     use interprocess::local_socket::{
         tokio::Stream,
         traits::tokio::{Listener as _, Stream as _},
     };
     use std::future::Future;
     use std::sync::Arc;
-    use tokio::io::AsyncWriteExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
     fn build_signal(message: &str) -> AppSignal {
         AppSignal {
@@ -56,10 +61,10 @@ mod tests {
         }
     }
 
-    async fn run_pair<F, Fut>(endpoint: &TestEndpoint, writer: F) -> Result<(Stream, Fut)>
+    async fn run_pair<F, Fut, T>(endpoint: &TestEndpoint, writer: F) -> Result<(Stream, Fut)>
     where
         F: FnOnce(Stream) -> Fut,
-        Fut: Future<Output = Result<()>>,
+        Fut: Future<Output = Result<T>>,
     {
         let listener = endpoint.listener_options().create_tokio()?;
         let name = endpoint.clone_name();
@@ -74,7 +79,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn read_signal_正常入力を復元する() -> Result<()> {
+    async fn read_message_正常入力を復元する() -> Result<()> {
         let _lock = test_lock();
         let endpoint = TestEndpoint::new()?;
         let signal = build_signal("同期要求");
@@ -92,13 +97,13 @@ mod tests {
 
         writer_handle.await?;
 
-        let received = read_signal(&mut server_stream).await?;
-        assert_eq!(received, signal);
+        let received = read_message(&mut server_stream).await?;
+        assert_eq!(received, AppSignalMessage::Signal(signal));
         Ok(())
     }
 
     #[tokio::test]
-    async fn read_signal_長さ不足でエラーになる() -> Result<()> {
+    async fn read_message_長さ不足でエラーになる() -> Result<()> {
         let _lock = test_lock();
         let endpoint = TestEndpoint::new()?;
         let payload = b"{\"dummy\":true}".to_vec();
@@ -115,7 +120,7 @@ mod tests {
 
         writer_handle.await?;
 
-        let err = read_signal(&mut server_stream)
+        let err = read_message(&mut server_stream)
             .await
             .expect_err("expected failure");
         assert!(err
@@ -144,7 +149,12 @@ mod tests {
         writer_handle.await?;
 
         let pubsub = Arc::new(RecordingPubSub::new());
-        handle_stream(server_stream, Arc::clone(&pubsub)).await?;
+        handle_stream(
+            server_stream,
+            Arc::clone(&pubsub),
+            Arc::new(RecordingRequestHandler::default()),
+        )
+        .await?;
 
         let events = pubsub.events();
         assert_eq!(events.len(), 2);
@@ -184,7 +194,12 @@ mod tests {
         writer_handle.await?;
 
         let pubsub = Arc::new(RecordingPubSub::new());
-        handle_stream(server_stream, Arc::clone(&pubsub)).await?;
+        handle_stream(
+            server_stream,
+            Arc::clone(&pubsub),
+            Arc::new(RecordingRequestHandler::default()),
+        )
+        .await?;
 
         let events = pubsub.events();
         assert_eq!(events.len(), 2);
@@ -224,7 +239,12 @@ mod tests {
         writer_handle.await?;
 
         let pubsub = Arc::new(RecordingPubSub::new());
-        handle_stream(server_stream, Arc::clone(&pubsub)).await?;
+        handle_stream(
+            server_stream,
+            Arc::clone(&pubsub),
+            Arc::new(RecordingRequestHandler::default()),
+        )
+        .await?;
 
         let events = pubsub.events();
         assert_eq!(events.len(), 2);
@@ -264,7 +284,12 @@ mod tests {
         writer_handle.await?;
 
         let pubsub = Arc::new(RecordingPubSub::new());
-        handle_stream(server_stream, Arc::clone(&pubsub)).await?;
+        handle_stream(
+            server_stream,
+            Arc::clone(&pubsub),
+            Arc::new(RecordingRequestHandler::default()),
+        )
+        .await?;
 
         let events = pubsub.events();
         assert_eq!(events.len(), 2);
@@ -304,10 +329,107 @@ mod tests {
         writer_handle.await?;
 
         let pubsub = Arc::new(RecordingPubSub::failing(APP_SIGNAL_EVENT));
-        let err = handle_stream(server_stream, pubsub)
-            .await
-            .expect_err("expected failure");
+        let err = handle_stream(
+            server_stream,
+            pubsub,
+            Arc::new(RecordingRequestHandler::default()),
+        )
+        .await
+        .expect_err("expected failure");
         assert!(err.to_string().contains("failed to emit appSignal"));
         Ok(())
     }
+
+    fn build_request(request: AppRequest) -> AppRequestEnvelope {
+        AppRequestEnvelope {
+            request_id: "req-1".to_string(),
+            source: AppSignalSource::NativeMessagingHost,
+            request,
+            issued_at: Utc::now(),
+        }
+    }
+
+    async fn read_response(stream: &mut Stream) -> Result<AppResponseEnvelope> {
+        let mut len_buf = [0u8; 4];
+        stream.read_exact(&mut len_buf).await?;
+        let mut payload = vec![0u8; u32::from_le_bytes(len_buf) as usize];
+        stream.read_exact(&mut payload).await?;
+        Ok(serde_json::from_slice(&payload)?)
+    }
+
+    #[tokio::test]
+    async fn handle_stream_request_問い合わせに応答を書き戻す() -> Result<()> {
+        let _lock = test_lock();
+        let endpoint = TestEndpoint::new()?;
+        let envelope = build_request(AppRequest::ImageQueueStatus);
+        let payload = serde_json::to_vec(&envelope)?;
+        let len = (payload.len() as u32).to_le_bytes();
+
+        let (server_stream, client) = run_pair(&endpoint, move |mut client_stream| async move {
+            client_stream.write_all(&len).await?;
+            client_stream.write_all(&payload).await?;
+            client_stream.flush().await?;
+            read_response(&mut client_stream).await
+        })
+        .await?;
+
+        let pubsub = Arc::new(RecordingPubSub::new());
+        let handler = Arc::new(RecordingRequestHandler::new(
+            AppResponse::ImageQueueStatus {
+                running: true,
+                pending: 3,
+            },
+        ));
+        let (handled, response) = tokio::join!(
+            handle_stream(server_stream, Arc::clone(&pubsub), Arc::clone(&handler)),
+            client
+        );
+        handled?;
+        let response = response?;
+
+        assert_eq!(response.request_id, "req-1");
+        assert_eq!(
+            response.response,
+            AppResponse::ImageQueueStatus {
+                running: true,
+                pending: 3,
+            }
+        );
+        assert_eq!(handler.requests(), vec![AppRequest::ImageQueueStatus]);
+        assert!(pubsub.events().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn handle_stream_request_処理失敗をエラー応答で返す() -> Result<()> {
+        let _lock = test_lock();
+        let endpoint = TestEndpoint::new()?;
+        let envelope = build_request(AppRequest::DrainImageQueue);
+        let payload = serde_json::to_vec(&envelope)?;
+        let len = (payload.len() as u32).to_le_bytes();
+
+        let (server_stream, client) = run_pair(&endpoint, move |mut client_stream| async move {
+            client_stream.write_all(&len).await?;
+            client_stream.write_all(&payload).await?;
+            client_stream.flush().await?;
+            read_response(&mut client_stream).await
+        })
+        .await?;
+
+        let (handled, response) = tokio::join!(
+            handle_stream(
+                server_stream,
+                Arc::new(RecordingPubSub::new()),
+                Arc::new(RecordingRequestHandler::failing()),
+            ),
+            client
+        );
+        handled?;
+
+        match response?.response {
+            AppResponse::Error { message } => assert!(message.contains("forced failure")),
+            other => panic!("unexpected response: {other:?}"),
+        }
+        Ok(())
+    }
 }
@@ -1,2 +1,3 @@
 pub mod client;
+mod frame;
 pub mod listener;
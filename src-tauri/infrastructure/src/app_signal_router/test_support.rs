@@ -16,6 +16,7 @@ use interprocess::local_socket::{GenericFilePath, ToFsName};
 use interprocess::local_socket::{GenericNamespaced, ToNsName};
 
 use domain::pubsub::{PubSubEvent, PubSubService};
+use domain::service::app_signal_router::{AppRequest, AppRequestHandler, AppResponse};
 use tokio::sync::Notify;
 
 /// グローバルに共有するテスト用ロック。
@@ -87,6 +88,7 @@ impl TestEndpoint {
         std::fs::create_dir_all(&base_dir)?;
         let socket_path = base_dir.join("app-signal.sock");
         let name = socket_path
+            .clone()
             .to_fs_name::<GenericFilePath>()
             .context("failed to convert socket path to local socket name")?
             .into_owned();
@@ -223,3 +225,63 @@ impl PubSubService for RecordingPubSub {
         Ok(())
     }
 }
+
+/// 受け取った問い合わせを記録し、決まった応答を返すテスト用実装。
+pub(crate) struct RecordingRequestHandler {
+    requests: Mutex<Vec<AppRequest>>,
+    response: Option<AppResponse>,
+    delay: Option<std::time::Duration>,
+}
+
+impl RecordingRequestHandler {
+    pub(crate) fn new(response: AppResponse) -> Self {
+        Self {
+            requests: Mutex::new(Vec::new()),
+            response: Some(response),
+            delay: None,
+        }
+    }
+
+    /// 常に失敗する。
+    pub(crate) fn failing() -> Self {
+        Self {
+            requests: Mutex::new(Vec::new()),
+            response: None,
+            delay: None,
+        }
+    }
+
+    /// `delay` だけ待ってから応答する。
+    pub(crate) fn delayed(response: AppResponse, delay: std::time::Duration) -> Self {
+        Self {
+            requests: Mutex::new(Vec::new()),
+            response: Some(response),
+            delay: Some(delay),
+        }
+    }
+
+    pub(crate) fn requests(&self) -> Vec<AppRequest> {
+        self.requests.lock().expect("poisoned requests").clone()
+    }
+}
+
+impl Default for RecordingRequestHandler {
+    fn default() -> Self {
+        Self::new(AppResponse::DrainImageQueue { accepted: true })
+    }
+}
+
+impl AppRequestHandler for RecordingRequestHandler {
+    async fn handle(&self, request: AppRequest) -> Result<AppResponse> {
+        self.requests
+            .lock()
+            .expect("poisoned requests")
+            .push(request.clone());
+        if let Some(delay) = self.delay {
+            tokio::time::sleep(delay).await;
+        }
+        self.response
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("forced failure for {request:?}"))
+    }
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+/// 条件付きリクエスト用に保存しておくレスポンスのメタ情報
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub content_type: Option<String>,
+}
+
+/// URL ごとに本文とメタ情報を `{key}.body` / `{key}.json` として保存する。
+/// 本文の合計が `max_bytes` を超えたら、最後に使ってから長いものから消す
+#[derive(Clone)]
+pub struct HttpCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl HttpCache {
+    pub fn new(dir: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes,
+        }
+    }
+
+    /// 本文が残っているエントリだけを返す
+    pub fn lookup(&self, url: &str) -> Option<(CacheEntry, PathBuf)> {
+        let (meta_path, body_path) = self.paths(url);
+        let meta = fs::read(&meta_path).ok()?;
+        let entry: CacheEntry = serde_json::from_slice(&meta).ok()?;
+        // キーの衝突は別 URL として扱う
+        if entry.url != url || !body_path.exists() {
+            return None;
+        }
+        // 最後に使った時刻として更新日時を使う
+        if let Err(e) = fs::File::options()
+            .append(true)
+            .open(&body_path)
+            .and_then(|f| f.set_modified(SystemTime::now()))
+        {
+            log::warn!("failed to touch http cache {}: {}", body_path.display(), e);
+        }
+        Some((entry, body_path))
+    }
+
+    pub fn store(&self, entry: &CacheEntry, body_src: &Path) -> anyhow::Result<()> {
+        let size = fs::metadata(body_src)?.len();
+        if size > self.max_bytes {
+            self.remove(&entry.url);
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+        let (meta_path, body_path) = self.paths(&entry.url);
+        let tmp = body_path.with_extension("body.tmp");
+        fs::copy(body_src, &tmp)?;
+        fs::rename(&tmp, &body_path)?;
+        fs::write(&meta_path, serde_json::to_vec(entry)?)?;
+        self.evict()
+    }
+
+    /// 上限に収まるまで古いものから消す
+    fn evict(&self) -> anyhow::Result<()> {
+        let mut bodies = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("body") {
+                continue;
+            }
+            let meta = fs::metadata(&path)?;
+            bodies.push((meta.modified()?, meta.len(), path));
+        }
+        let mut total: u64 = bodies.iter().map(|(_, len, _)| len).sum();
+        if total <= self.max_bytes {
+            return Ok(());
+        }
+        bodies.sort_by_key(|(modified, _, _)| *modified);
+        for (_, len, path) in bodies {
+            if total <= self.max_bytes {
+                break;
+            }
+            let _ = fs::remove_file(path.with_extension("json"));
+            fs::remove_file(&path)?;
+            total -= len;
+        }
+        Ok(())
+    }
+
+    pub fn remove(&self, url: &str) {
+        let (meta_path, body_path) = self.paths(url);
+        let _ = fs::remove_file(meta_path);
+        let _ = fs::remove_file(body_path);
+    }
+
+    fn paths(&self, url: &str) -> (PathBuf, PathBuf) {
+        let key = cache_key(url);
+        (
+            self.dir.join(format!("{}.json", key)),
+            self.dir.join(format!("{}.body", key)),
+        )
+    }
+}
+
+/// URL から決まるファイル名（FNV-1a 64bit）
+fn cache_key(url: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in url.as_bytes() {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Context as _;
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use tokio::fs;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::sync::Semaphore;
+
+use super::cache::{CacheEntry, HttpCache};
+
+/// 形式判定に使う先頭バイト数
+const SNIFF_LEN: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct DownloaderConfig {
+    /// 同一ホストへの同時リクエスト数の上限
+    pub max_per_host: usize,
+    pub connect_timeout: Duration,
+    /// 本文の受信まで含めたリクエスト全体のタイムアウト
+    pub request_timeout: Duration,
+    /// キャッシュに残す本文の合計サイズの上限
+    pub cache_max_bytes: u64,
+}
+
+impl Default for DownloaderConfig {
+    fn default() -> Self {
+        Self {
+            max_per_host: 4,
+            connect_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(60),
+            cache_max_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadOutcome {
+    /// 先頭バイト（判定できなければ Content-Type）から求めた実際の画像形式
+    pub format: Option<image::ImageFormat>,
+    pub content_type: Option<String>,
+    /// 304 Not Modified によりキャッシュから書き出した
+    pub from_cache: bool,
+    pub bytes: u64,
+}
+
+/// 画像取得用の HTTP クライアント。アプリ全体で 1 つを `Arc` で共有し、
+/// ホストごとの同時接続数制限とキャッシュを呼び出し元の間で共有する。本文はファイルへ逐次書き出す。
+/// キャッシュディレクトリを設定すると ETag / Last-Modified による条件付きリクエストを行う。
+pub struct HttpDownloader {
+    client: reqwest::Client,
+    config: DownloaderConfig,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+    cache: Option<HttpCache>,
+}
+
+impl HttpDownloader {
+    pub fn new(config: DownloaderConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.request_timeout)
+            .build()
+            .unwrap_or_else(|e| {
+                log::warn!("failed to build http client, fallback to default: {}", e);
+                reqwest::Client::new()
+            });
+        Self {
+            client,
+            config,
+            hosts: Mutex::new(HashMap::new()),
+            cache: None,
+        }
+    }
+
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache = Some(HttpCache::new(dir, self.config.cache_max_bytes));
+        self
+    }
+
+    /// `url` を `dst` へ保存する。HTTP エラーは `reqwest::Error` をエラーチェーンに含めて返す
+    pub async fn download(&self, url: &str, dst: &str) -> anyhow::Result<DownloadOutcome> {
+        let parsed = reqwest::Url::parse(url).with_context(|| format!("invalid url: {}", url))?;
+        let _permit = self.host_semaphore(&parsed).acquire_owned().await?;
+        let cached = {
+            let url = url.to_string();
+            self.with_cache(move |c| c.lookup(&url)).await?.flatten()
+        };
+
+        let mut req = self.client.get(parsed);
+        if let Some((entry, _)) = &cached {
+            if let Some(etag) = &entry.etag {
+                req = req.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                req = req.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let res = req.send().await.context("download failed")?;
+
+        if res.status() == StatusCode::NOT_MODIFIED {
+            let (entry, body) =
+                cached.ok_or_else(|| anyhow::anyhow!("unexpected 304 without cache: {}", url))?;
+            let bytes = fs::copy(&body, dst)
+                .await
+                .context("copy cached body failed")?;
+            let head = read_head(Path::new(dst)).await?;
+            return Ok(DownloadOutcome {
+                format: sniff_image_format(&head, entry.content_type.as_deref()),
+                content_type: entry.content_type,
+                from_cache: true,
+                bytes,
+            });
+        }
+
+        let mut res = res.error_for_status().context("download failed")?;
+        let header = |res: &reqwest::Response, name| {
+            res.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string())
+        };
+        let content_type = header(&res, CONTENT_TYPE);
+        let etag = header(&res, ETAG);
+        let last_modified = header(&res, LAST_MODIFIED);
+
+        // 途中で失敗しても壊れたファイルが dst に残らないよう一時ファイルへ書いてから置き換える
+        let part = PathBuf::from(format!("{}.part", dst));
+        let (bytes, head) = match write_body(&mut res, &part).await {
+            Ok(v) => v,
+            Err(e) => {
+                let _ = fs::remove_file(&part).await;
+                return Err(e);
+            }
+        };
+        fs::rename(&part, dst)
+            .await
+            .context("rename downloaded file failed")?;
+
+        if etag.is_some() || last_modified.is_some() {
+            let entry = CacheEntry {
+                url: url.to_string(),
+                etag,
+                last_modified,
+                content_type: content_type.clone(),
+            };
+            let body = PathBuf::from(dst);
+            if let Some(Err(e)) = self.with_cache(move |c| c.store(&entry, &body)).await? {
+                log::warn!("failed to store http cache url={} err={:#}", url, e);
+            }
+        } else if cached.is_some() {
+            let url = url.to_string();
+            self.with_cache(move |c| c.remove(&url)).await?;
+        }
+
+        Ok(DownloadOutcome {
+            format: sniff_image_format(&head, content_type.as_deref()),
+            content_type,
+            from_cache: false,
+            bytes,
+        })
+    }
+
+    /// キャッシュの読み書きは同期 I/O なので spawn_blocking で行う。キャッシュが無ければ `None`
+    async fn with_cache<T, F>(&self, f: F) -> anyhow::Result<Option<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce(&HttpCache) -> T + Send + 'static,
+    {
+        let Some(cache) = self.cache.clone() else {
+            return Ok(None);
+        };
+        Ok(Some(tokio::task::spawn_blocking(move || f(&cache)).await?))
+    }
+
+    fn host_semaphore(&self, url: &reqwest::Url) -> Arc<Semaphore> {
+        let host = format!(
+            "{}:{}",
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or_default()
+        );
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        hosts
+            .entry(host)
+            .or_insert_with(|| Arc::new(Semaphore::new(self.config.max_per_host.max(1))))
+            .clone()
+    }
+}
+
+/// 先頭バイトのマジックナンバーから画像形式を判定し、判定できなければ Content-Type を使う
+pub fn sniff_image_format(head: &[u8], content_type: Option<&str>) -> Option<image::ImageFormat> {
+    image::guess_format(head).ok().or_else(|| {
+        let mime = content_type?.split(';').next()?.trim();
+        image::ImageFormat::from_mime_type(mime)
+    })
+}
+
+async fn write_body(res: &mut reqwest::Response, path: &Path) -> anyhow::Result<(u64, Vec<u8>)> {
+    let mut file = fs::File::create(path).await.context("create file failed")?;
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    let mut bytes = 0u64;
+    while let Some(chunk) = res.chunk().await.context("read body failed")? {
+        if head.len() < SNIFF_LEN {
+            let n = (SNIFF_LEN - head.len()).min(chunk.len());
+            head.extend_from_slice(&chunk[..n]);
+        }
+        file.write_all(&chunk).await.context("write file failed")?;
+        bytes += chunk.len() as u64;
+    }
+    file.flush().await.context("write file failed")?;
+    Ok((bytes, head))
+}
+
+async fn read_head(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    fs::File::open(path)
+        .await?
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut head)
+        .await?;
+    Ok(head)
+}
@@ -0,0 +1,319 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tempfile::TempDir;
+use wiremock::matchers::{header_exists, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use super::{sniff_image_format, DownloaderConfig, HttpDownloader};
+
+fn png_bytes() -> Vec<u8> {
+    let mut buf = std::io::Cursor::new(Vec::new());
+    image::RgbaImage::new(2, 2)
+        .write_to(&mut buf, image::ImageOutputFormat::Png)
+        .unwrap();
+    buf.into_inner()
+}
+
+fn dst(dir: &TempDir, name: &str) -> String {
+    dir.path().join(name).to_string_lossy().to_string()
+}
+
+#[test]
+fn sniff_image_format_先頭バイトを優先しcontent_typeで補う() {
+    struct Case {
+        name: &'static str,
+        head: Vec<u8>,
+        content_type: Option<&'static str>,
+        expected: Option<image::ImageFormat>,
+    }
+    let cases = vec![
+        Case {
+            name: "png の中身が jpeg として配信されても png",
+            head: png_bytes(),
+            content_type: Some("image/jpeg"),
+            expected: Some(image::ImageFormat::Png),
+        },
+        Case {
+            name: "webp",
+            head: b"RIFF\x00\x00\x00\x00WEBPVP8 ".to_vec(),
+            content_type: None,
+            expected: Some(image::ImageFormat::WebP),
+        },
+        Case {
+            name: "判定できなければ content-type（パラメータ付き）",
+            head: b"????".to_vec(),
+            content_type: Some("image/gif; charset=binary"),
+            expected: Some(image::ImageFormat::Gif),
+        },
+        Case {
+            name: "どちらも不明",
+            head: b"????".to_vec(),
+            content_type: Some("application/octet-stream"),
+            expected: None,
+        },
+    ];
+    for c in cases {
+        assert_eq!(
+            sniff_image_format(&c.head, c.content_type),
+            c.expected,
+            "{}",
+            c.name
+        );
+    }
+}
+
+#[tokio::test]
+async fn download_本文をファイルへ書き出し形式を判定する() {
+    let server = MockServer::start().await;
+    let body = png_bytes();
+    Mock::given(method("GET"))
+        .and(path("/a.jpg"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-type", "image/jpeg")
+                .set_body_bytes(body.clone()),
+        )
+        .mount(&server)
+        .await;
+    let tmp = TempDir::new().unwrap();
+    let out = dst(&tmp, "a.bin");
+
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
+    let outcome = downloader
+        .download(&format!("{}/a.jpg", server.uri()), &out)
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&out).unwrap(), body);
+    assert_eq!(outcome.format, Some(image::ImageFormat::Png));
+    assert_eq!(outcome.content_type.as_deref(), Some("image/jpeg"));
+    assert_eq!(outcome.bytes, body.len() as u64);
+    assert!(!outcome.from_cache);
+}
+
+#[tokio::test]
+async fn download_http_エラーは_reqwest_のステータスを保持しファイルを残さない() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    let tmp = TempDir::new().unwrap();
+    let out = dst(&tmp, "missing.png");
+
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
+    let err = downloader
+        .download(&format!("{}/missing.png", server.uri()), &out)
+        .await
+        .unwrap_err();
+
+    let status = err
+        .chain()
+        .find_map(|c| c.downcast_ref::<reqwest::Error>())
+        .and_then(|e| e.status());
+    assert_eq!(status, Some(reqwest::StatusCode::NOT_FOUND));
+    assert!(!Path::new(&out).exists());
+    assert!(!Path::new(&format!("{}.part", out)).exists());
+}
+
+#[tokio::test]
+async fn download_条件付きリクエストで未変更ならキャッシュから書き出す() {
+    struct Case {
+        name: &'static str,
+        validator_header: &'static str,
+        validator_value: &'static str,
+        conditional_header: &'static str,
+    }
+    let cases = vec![
+        Case {
+            name: "ETag",
+            validator_header: "etag",
+            validator_value: "\"v1\"",
+            conditional_header: "if-none-match",
+        },
+        Case {
+            name: "Last-Modified",
+            validator_header: "last-modified",
+            validator_value: "Wed, 21 Oct 2015 07:28:00 GMT",
+            conditional_header: "if-modified-since",
+        },
+    ];
+
+    for c in cases {
+        let server = MockServer::start().await;
+        let body = png_bytes();
+        // 条件付きリクエストには 304 を返す（先にマウントしたものが優先される）
+        Mock::given(method("GET"))
+            .and(path("/img"))
+            .and(header_exists(c.conditional_header))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/img"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(c.validator_header, c.validator_value)
+                    .set_body_bytes(body.clone()),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache_dir = TempDir::new().unwrap();
+        let tmp = TempDir::new().unwrap();
+        let downloader =
+            HttpDownloader::new(DownloaderConfig::default()).with_cache_dir(cache_dir.path());
+        let url = format!("{}/img", server.uri());
+
+        let first = downloader.download(&url, &dst(&tmp, "1")).await.unwrap();
+        let second = downloader.download(&url, &dst(&tmp, "2")).await.unwrap();
+
+        assert!(!first.from_cache, "{}", c.name);
+        assert!(second.from_cache, "{}", c.name);
+        assert_eq!(second.format, Some(image::ImageFormat::Png), "{}", c.name);
+        assert_eq!(std::fs::read(dst(&tmp, "2")).unwrap(), body, "{}", c.name);
+        server.verify().await;
+    }
+}
+
+#[tokio::test]
+async fn download_検証子がなければキャッシュしない() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(png_bytes()))
+        .expect(2)
+        .mount(&server)
+        .await;
+    let cache_dir = TempDir::new().unwrap();
+    let tmp = TempDir::new().unwrap();
+    let downloader =
+        HttpDownloader::new(DownloaderConfig::default()).with_cache_dir(cache_dir.path());
+    let url = format!("{}/img", server.uri());
+
+    assert!(
+        !downloader
+            .download(&url, &dst(&tmp, "1"))
+            .await
+            .unwrap()
+            .from_cache
+    );
+    assert!(
+        !downloader
+            .download(&url, &dst(&tmp, "2"))
+            .await
+            .unwrap()
+            .from_cache
+    );
+    assert_eq!(std::fs::read_dir(cache_dir.path()).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn download_キャッシュが上限を超えたら最後に使ってから長いものを消す() {
+    let server = MockServer::start().await;
+    let body = png_bytes();
+    Mock::given(method("GET"))
+        .and(header_exists("if-none-match"))
+        .respond_with(ResponseTemplate::new(304))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"v1\"")
+                .set_body_bytes(body.clone()),
+        )
+        .mount(&server)
+        .await;
+    let cache_dir = TempDir::new().unwrap();
+    let tmp = TempDir::new().unwrap();
+    let downloader = HttpDownloader::new(DownloaderConfig {
+        cache_max_bytes: body.len() as u64 * 2,
+        ..Default::default()
+    })
+    .with_cache_dir(cache_dir.path());
+    let url = |name: &str| format!("{}/{}", server.uri(), name);
+
+    // a, b を入れたあと a を使い、c を入れると b が消える
+    let mut from_cache = Vec::new();
+    for name in ["a", "b", "a", "c", "a", "c", "b"] {
+        let outcome = downloader
+            .download(&url(name), &dst(&tmp, name))
+            .await
+            .unwrap();
+        from_cache.push(outcome.from_cache);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(
+        from_cache,
+        vec![false, false, true, false, true, true, false]
+    );
+}
+
+#[tokio::test]
+async fn download_同一ホストの同時リクエスト数を制限する() {
+    let server = MockServer::start().await;
+    let delay = Duration::from_millis(200);
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_bytes(png_bytes())
+                .set_delay(delay),
+        )
+        .mount(&server)
+        .await;
+    let tmp = TempDir::new().unwrap();
+    let downloader = Arc::new(HttpDownloader::new(DownloaderConfig {
+        max_per_host: 1,
+        ..Default::default()
+    }));
+
+    let started = Instant::now();
+    let tasks: Vec<_> = (0..3)
+        .map(|i| {
+            let downloader = Arc::clone(&downloader);
+            let url = format!("{}/img{}", server.uri(), i);
+            let out = dst(&tmp, &format!("{}", i));
+            tokio::spawn(async move { downloader.download(&url, &out).await })
+        })
+        .collect();
+    for t in tasks {
+        t.await.unwrap().unwrap();
+    }
+
+    // 1 本ずつしか流れないので少なくとも 3 回分の遅延がかかる
+    assert!(started.elapsed() >= delay * 3, "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn download_タイムアウトでエラーになる() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_bytes(png_bytes())
+                .set_delay(Duration::from_secs(2)),
+        )
+        .mount(&server)
+        .await;
+    let tmp = TempDir::new().unwrap();
+    let downloader = HttpDownloader::new(DownloaderConfig {
+        request_timeout: Duration::from_millis(100),
+        ..Default::default()
+    });
+
+    let err = downloader
+        .download(&format!("{}/slow", server.uri()), &dst(&tmp, "slow"))
+        .await
+        .unwrap_err();
+
+    let is_timeout = err
+        .chain()
+        .find_map(|c| c.downcast_ref::<reqwest::Error>())
+        .is_some_and(|e| e.is_timeout());
+    assert!(is_timeout, "{:#}", err);
+}
@@ -0,0 +1,6 @@
+mod cache;
+mod http;
+#[cfg(test)]
+mod http_test;
+
+pub use http::{sniff_image_format, DownloadOutcome, DownloaderConfig, HttpDownloader};
@@ -14,6 +14,7 @@ fn rwd(path: &str, title: &str, egs_id: i32, distance: f32) -> ResolvedWork {
         title: title.to_string(),
         egs_id,
         distance,
+        explanation: None,
     }
 }
 
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use domain::game_matcher::{extract_file_info, GameMatcher};
+use domain::game_matcher::{extract_file_info, GameMatcher, MatchQuery, QuerySource};
 use domain::scan::{MetadataExtractor, ResolvedWork, WorkCandidate, WorkCandidateOrResolvedWork};
 
 pub struct HeuristicMetadataExtractor {
@@ -20,29 +20,27 @@ impl MetadataExtractor for HeuristicMetadataExtractor {
         let file_info = match extract_file_info(&c.path) {
             Ok(info) => info,
             Err(_) => {
-                return Ok(WorkCandidateOrResolvedWork::Candidate(c));
+                return Ok(WorkCandidateOrResolvedWork::Candidate(c, None));
             }
         };
         if file_info.skip_filename {
-            return Ok(WorkCandidateOrResolvedWork::Candidate(c));
+            return Ok(WorkCandidateOrResolvedWork::Candidate(c, None));
         }
 
-        let queries: Vec<String> = vec![file_info.parent_dir, file_info.filename];
-        let candidates = self.matcher.find_candidates(&queries);
-        let erogame_scape_game = candidates.into_iter().next();
-
-        match erogame_scape_game {
-            Some((one, distance)) => {
-                return Ok(WorkCandidateOrResolvedWork::Resolved(ResolvedWork::new(
-                    c,
-                    one.gamename,
-                    one.id,
-                    distance,
-                )));
-            }
-            None => {
-                return Ok(WorkCandidateOrResolvedWork::Candidate(c));
+        // 採否と根拠を同じ評価から得るため、explain_candidates の1回だけで判定する
+        let queries = vec![
+            MatchQuery::new(QuerySource::ParentDir, file_info.parent_dir),
+            MatchQuery::new(QuerySource::Filename, file_info.filename),
+        ];
+        let explanation = self.matcher.explain_candidates(&queries);
+        match explanation.best() {
+            Some(best) => {
+                let mut resolved =
+                    ResolvedWork::new(c, best.game.gamename.clone(), best.game.id, best.score);
+                resolved.explanation = Some(explanation);
+                Ok(WorkCandidateOrResolvedWork::Resolved(resolved))
             }
+            None => Ok(WorkCandidateOrResolvedWork::Candidate(c, Some(explanation))),
         }
     }
 }
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use domain::all_game_cache::AllGameCacheOne;
-use domain::game_matcher::MockGameMatcher;
+use domain::game_matcher::{
+    CandidateExplanation, MatchExplanation, MatchQuery, MatchRule, MockGameMatcher, PruneReason,
+    PrunedCandidate, QuerySource,
+};
 use domain::scan::{CandidateKind, MetadataExtractor, WorkCandidate, WorkCandidateOrResolvedWork};
 
 use super::HeuristicMetadataExtractor;
@@ -10,18 +13,39 @@ fn wc<P: Into<std::path::PathBuf>>(p: P) -> WorkCandidate {
     WorkCandidate::new(p.into(), CandidateKind::Exe)
 }
 
+fn pieces_queries() -> Vec<MatchQuery> {
+    vec![
+        MatchQuery::new(QuerySource::ParentDir, "pieces".to_string()),
+        MatchQuery::new(QuerySource::Filename, "pieces".to_string()),
+    ]
+}
+
+fn explained(id: i32, gamename: &str, score: f32) -> CandidateExplanation {
+    CandidateExplanation {
+        game: AllGameCacheOne::new(id, gamename.to_string()),
+        score,
+        rule: MatchRule::Fuzzy,
+        query: MatchQuery::new(QuerySource::ParentDir, "pieces".to_string()),
+        normalized_query: "pieces".to_string(),
+        normalized_gamename: gamename.to_string(),
+        ngram_overlap: Some(5),
+        distance: Some(1),
+    }
+}
+
 #[test]
 fn 正常系_候補ヒット_最初の候補を採用する() {
+    let explanation = MatchExplanation {
+        candidates: vec![explained(27123, "pieces/渡り鳥のソムニウム", 0.95)],
+        pruned: vec![],
+    };
+    let expected = explanation.clone();
     let mut mock = MockGameMatcher::new();
-    mock.expect_find_candidates()
-        .withf(|qs| qs == &vec!["pieces".to_string(), "pieces".to_string()])
+    mock.expect_find_candidates().never();
+    mock.expect_explain_candidates()
+        .withf(|qs| qs == pieces_queries())
         .times(1)
-        .returning_st(|_| {
-            vec![(
-                AllGameCacheOne::new(27123, "pieces/渡り鳥のソムニウム".to_string()),
-                0.95,
-            )]
-        });
+        .returning_st(move |_| explanation.clone());
 
     let extractor = HeuristicMetadataExtractor::new(Arc::new(mock));
     let c = wc("W:\\others\\software\\Whirlpool\\pieces\\pieces.exe");
@@ -33,26 +57,41 @@ fn 正常系_候補ヒット_最初の候補を採用する() {
             assert_eq!(r.egs_id, 27123);
             assert_eq!(r.candidate, c);
             assert!((r.distance - 0.95).abs() < 1e-6);
+            assert_eq!(r.explanation, Some(expected));
         }
         _ => panic!("expected Resolved"),
     }
 }
 
 #[test]
-fn 正常系_候補なし_そのままcandidateを返す() {
+fn 正常系_候補なし_根拠付きでcandidateを返す() {
+    let explanation = MatchExplanation {
+        candidates: vec![],
+        pruned: vec![PrunedCandidate {
+            game: AllGameCacheOne::new(1, "pieces2".to_string()),
+            query: MatchQuery::new(QuerySource::ParentDir, "pieces".to_string()),
+            reason: PruneReason::BelowThreshold {
+                overlap: 5,
+                distance: 2,
+                score: 0.7,
+            },
+        }],
+    };
+    let expected = explanation.clone();
     let mut mock = MockGameMatcher::new();
-    mock.expect_find_candidates()
-        .withf(|qs| qs == &vec!["pieces".to_string(), "pieces".to_string()])
+    mock.expect_explain_candidates()
+        .withf(|qs| qs == pieces_queries())
         .times(1)
-        .returning_st(|_| vec![]);
+        .returning_st(move |_| explanation.clone());
 
     let extractor = HeuristicMetadataExtractor::new(Arc::new(mock));
     let c = wc("W:\\others\\software\\Whirlpool\\pieces\\pieces.exe");
 
     let res = extractor.enrich(c.clone()).unwrap();
     match res {
-        WorkCandidateOrResolvedWork::Candidate(cc) => {
+        WorkCandidateOrResolvedWork::Candidate(cc, explanation) => {
             assert_eq!(cc, c);
+            assert_eq!(explanation, Some(expected));
         }
         _ => panic!("expected Candidate"),
     }
@@ -61,14 +100,17 @@ fn 正常系_候補なし_そのままcandidateを返す() {
 #[test]
 fn 抽出エラー時_マッチャは呼ばれずcandidateを返す() {
     let mut mock = MockGameMatcher::new();
-    mock.expect_find_candidates().never();
+    mock.expect_explain_candidates().never();
 
     let extractor = HeuristicMetadataExtractor::new(Arc::new(mock));
     let c = wc("C:\\test\\install.exe");
 
     let res = extractor.enrich(c.clone()).unwrap();
     match res {
-        WorkCandidateOrResolvedWork::Candidate(cc) => assert_eq!(cc, c),
+        WorkCandidateOrResolvedWork::Candidate(cc, explanation) => {
+            assert_eq!(cc, c);
+            assert_eq!(explanation, None);
+        }
         _ => panic!("expected Candidate"),
     }
 }
@@ -76,14 +118,17 @@ fn 抽出エラー時_マッチャは呼ばれずcandidateを返す() {
 #[test]
 fn スキップ対象ファイル名時_マッチャは呼ばれずcandidateを返す() {
     let mut mock = MockGameMatcher::new();
-    mock.expect_find_candidates().never();
+    mock.expect_explain_candidates().never();
 
     let extractor = HeuristicMetadataExtractor::new(Arc::new(mock));
     let c = wc("C:\\Program Files\\Game\\start.exe");
 
     let res = extractor.enrich(c.clone()).unwrap();
     match res {
-        WorkCandidateOrResolvedWork::Candidate(cc) => assert_eq!(cc, c),
+        WorkCandidateOrResolvedWork::Candidate(cc, explanation) => {
+            assert_eq!(cc, c);
+            assert_eq!(explanation, None);
+        }
         _ => panic!("expected Candidate"),
     }
 }
@@ -91,14 +136,11 @@ fn スキップ対象ファイル名時_マッチャは呼ばれずcandidateを
 #[test]
 fn 複数候補時_先頭候補を採用する() {
     let mut mock = MockGameMatcher::new();
-    mock.expect_find_candidates()
-        .withf(|qs| qs == &vec!["pieces".to_string(), "pieces".to_string()])
+    mock.expect_explain_candidates()
         .times(1)
-        .returning_st(|_| {
-            vec![
-                (AllGameCacheOne::new(1, "A".to_string()), 0.8),
-                (AllGameCacheOne::new(2, "B".to_string()), 0.7),
-            ]
+        .returning_st(|_| MatchExplanation {
+            candidates: vec![explained(1, "A", 0.8), explained(2, "B", 0.7)],
+            pruned: vec![],
         });
 
     let extractor = HeuristicMetadataExtractor::new(Arc::new(mock));
@@ -0,0 +1,27 @@
+/// ICO に含まれる一番大きい画像を PNG で保存する
+pub fn save_ico_to_png_sync(file_path: &str, save_png_path: &str) -> anyhow::Result<()> {
+    // Read an ICO file from disk:
+    let file = std::fs::File::open(file_path)?;
+    let icon_dir = ico::IconDir::read(file)?;
+
+    let largest_entry = icon_dir.entries().iter().fold(
+        None,
+        |largest: Option<&ico::IconDirEntry>, v| match largest {
+            Some(largest) if largest.width() >= v.width() => Some(largest),
+            _ => Some(v),
+        },
+    );
+
+    if let Some(entry) = largest_entry {
+        // Decode the first entry into an image:
+        let image = entry.decode()?;
+        // You can get raw RGBA pixel data to pass to another image library:
+        let rgba = image.rgba_data();
+        assert_eq!(rgba.len(), (4 * image.width() * image.height()) as usize);
+        // Alternatively, you can save the image as a PNG file:
+        let file = std::fs::File::create(save_png_path)?;
+        Ok(image.write_png(file)?)
+    } else {
+        Err(anyhow::anyhow!("icon_dir.entries() is empty"))
+    }
+}
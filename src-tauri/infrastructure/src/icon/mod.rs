@@ -1,14 +1,17 @@
+pub mod ico;
+pub mod pe;
+pub mod save;
 #[cfg(test)]
 mod tests;
 
 use std::{fs, path::Path};
 
 use std::sync::Arc;
-use tauri::AppHandle;
 
+use crate::downloader::HttpDownloader;
+use crate::icon::save::save_icon_to_png;
 use crate::thumbnail as thumb;
 use anyhow::Context as _;
-use domain::file::save_icon_to_png as domain_save_icon_to_png;
 use domain::service::save_path_resolver::{DirsSavePathResolver, SavePathResolver};
 use domain::{icon::IconService, works::Work, StrId};
 use fast_image_resize as fr;
@@ -79,31 +82,31 @@ pub fn process_square_icon(src: &str, dst: &str, target_short_px: u32) -> anyhow
 }
 
 enum Backend {
-    Tauri {
-        handle: Arc<AppHandle>,
-        resolver: Arc<dyn SavePathResolver>,
-    },
+    Tauri { resolver: Arc<dyn SavePathResolver> },
     Host { resolver: Arc<dyn SavePathResolver> },
 }
 
 pub struct IconServiceImpl {
     backend: Backend,
+    downloader: Arc<HttpDownloader>,
 }
 
 impl IconServiceImpl {
-    pub fn new_from_app_handle(
-        handle: Arc<AppHandle>,
+    pub fn new_from_resolver(
         resolver: Arc<dyn SavePathResolver>,
+        downloader: Arc<HttpDownloader>,
     ) -> Self {
         Self {
-            backend: Backend::Tauri { handle, resolver },
+            backend: Backend::Tauri { resolver },
+            downloader,
         }
     }
-    pub fn new_from_root_path(_root_dir: String) -> Self {
+    pub fn new_from_root_path(_root_dir: String, downloader: Arc<HttpDownloader>) -> Self {
         Self {
             backend: Backend::Host {
                 resolver: Arc::new(DirsSavePathResolver::default()),
             },
+            downloader,
         }
     }
 
@@ -128,9 +131,8 @@ impl IconServiceImpl {
 impl IconService for IconServiceImpl {
     async fn save_icon_from_path(&self, id: &StrId<Work>, source_path: &str) -> anyhow::Result<()> {
         match &self.backend {
-            Backend::Tauri { handle, resolver } => {
-                let _ = domain_save_icon_to_png(handle, resolver.as_ref(), source_path, id)?
-                    .await??;
+            Backend::Tauri { resolver } => {
+                save_icon_to_png(resolver.as_ref(), source_path, id)?.await??;
                 Ok(())
             }
             Backend::Host { resolver } => {
@@ -177,7 +179,7 @@ impl IconService for IconServiceImpl {
                     })
                     .unwrap_or_else(|| "icon".to_string());
                 let orig = dir.join(format!("{}-{}", id.value, filename));
-                thumb::download_to_file(url, &orig.to_string_lossy()).await?;
+                thumb::download_to_file(&self.downloader, url, &orig.to_string_lossy()).await?;
                 match process_square_icon(
                     &orig.to_string_lossy(),
                     &save_path,
@@ -206,7 +208,7 @@ impl IconService for IconServiceImpl {
                 let orig = std::env::temp_dir()
                     .join("launcherg-images")
                     .join(format!("{}-{}", id.value, filename));
-                thumb::download_to_file(url, &orig.to_string_lossy()).await?;
+                thumb::download_to_file(&self.downloader, url, &orig.to_string_lossy()).await?;
                 match process_square_icon(
                     &orig.to_string_lossy(),
                     &save_path,
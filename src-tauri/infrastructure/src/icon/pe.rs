@@ -0,0 +1,296 @@
+//! exe の PE リソースからアイコンを取り出す
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+
+use domain::binary_reader::Reader;
+
+const RT_ICON: u32 = 3;
+const RT_GROUP_ICON: u32 = 14;
+const RESOURCE_DIRECTORY_INDEX: usize = 2;
+
+/// RT_GROUP_ICON に並ぶ1つの画像
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupIconEntry {
+    pub width: u32,
+    pub height: u32,
+    pub color_count: u8,
+    pub planes: u16,
+    pub bit_count: u16,
+    pub id: u16,
+}
+
+/// exe の最初のアイコングループから `width` に一番合う画像を PNG で保存する。アイコンがなければ false
+pub fn save_exe_icon_to_png_sync(
+    exe_path: &str,
+    width: u32,
+    save_png_path: &str,
+) -> anyhow::Result<bool> {
+    let mut file = BufReader::new(std::fs::File::open(exe_path)?);
+    let Some(image) = read_best_icon(&mut file, width)? else {
+        return Ok(false);
+    };
+    let file = std::fs::File::create(save_png_path)?;
+    image.write_png(file)?;
+    Ok(true)
+}
+
+/// exe 全体は読まず、ヘッダーとリソースのセクションだけを読む
+pub fn read_best_icon<R: Read + Seek>(
+    reader: &mut R,
+    width: u32,
+) -> anyhow::Result<Option<ico::IconImage>> {
+    let Some(resources) = Resources::read(reader)? else {
+        return Ok(None);
+    };
+    let Some(group) = resources.first_data(RT_GROUP_ICON)? else {
+        return Ok(None);
+    };
+    let entries = parse_group_icon(group)?;
+    let Some(entry) = pick_entry(&entries, width) else {
+        return Ok(None);
+    };
+    let Some(data) = resources.data(RT_ICON, entry.id as u32)? else {
+        anyhow::bail!("RT_ICON {} is missing", entry.id);
+    };
+    // RT_ICON の中身は ICO の画像部分と同じなので、1枚だけの ICO にして読む
+    let icon_dir = ico::IconDir::read(Cursor::new(single_icon_file(entry, data)))?;
+    let image = icon_dir
+        .entries()
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("icon entry is empty"))?
+        .decode()?;
+    Ok(Some(image))
+}
+
+/// `width` 以上で一番小さい画像、なければ一番大きい画像を選ぶ。同じ大きさなら色の多い方
+pub fn pick_entry(entries: &[GroupIconEntry], width: u32) -> Option<GroupIconEntry> {
+    let best_of = |candidates: Vec<&GroupIconEntry>, smallest: bool| {
+        candidates.into_iter().copied().max_by(|a, b| {
+            let size = if smallest {
+                b.width.cmp(&a.width)
+            } else {
+                a.width.cmp(&b.width)
+            };
+            size.then(a.bit_count.cmp(&b.bit_count))
+        })
+    };
+    let larger: Vec<_> = entries.iter().filter(|e| e.width >= width).collect();
+    if larger.is_empty() {
+        best_of(entries.iter().collect(), false)
+    } else {
+        best_of(larger, true)
+    }
+}
+
+fn parse_group_icon(data: &[u8]) -> anyhow::Result<Vec<GroupIconEntry>> {
+    let r = Reader(data);
+    let count = r.u16(4)? as usize;
+    (0..count)
+        .map(|i| {
+            let offset = 6 + i * 14;
+            // 256px は 0 で書かれる
+            let size = |b: u8| if b == 0 { 256 } else { b as u32 };
+            Ok(GroupIconEntry {
+                width: size(r.u8(offset)?),
+                height: size(r.u8(offset + 1)?),
+                color_count: r.u8(offset + 2)?,
+                planes: r.u16(offset + 4)?,
+                bit_count: r.u16(offset + 6)?,
+                id: r.u16(offset + 12)?,
+            })
+        })
+        .collect()
+}
+
+fn single_icon_file(entry: GroupIconEntry, data: &[u8]) -> Vec<u8> {
+    let size = |v: u32| if v >= 256 { 0 } else { v as u8 };
+    let mut file = Vec::with_capacity(22 + data.len());
+    file.extend_from_slice(&[0, 0, 1, 0, 1, 0]);
+    file.extend_from_slice(&[size(entry.width), size(entry.height), entry.color_count, 0]);
+    file.extend_from_slice(&entry.planes.to_le_bytes());
+    file.extend_from_slice(&entry.bit_count.to_le_bytes());
+    file.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    file.extend_from_slice(&22u32.to_le_bytes());
+    file.extend_from_slice(data);
+    file
+}
+
+struct Section {
+    virtual_address: u32,
+    virtual_size: u32,
+    raw_size: u32,
+    raw_offset: u32,
+}
+
+impl Section {
+    fn contains(&self, rva: u32) -> bool {
+        let size = self.virtual_size.max(self.raw_size);
+        rva.checked_sub(self.virtual_address)
+            .is_some_and(|offset| offset < size)
+    }
+}
+
+/// .rsrc のリソースディレクトリ（種類 → ID → 言語 の3段）。ファイル全体ではなく、リソースディレクトリのあるセクションだけを持つ
+struct Resources {
+    section: Vec<u8>,
+    virtual_address: u32,
+    /// リソースディレクトリのセクション内の位置
+    root: usize,
+}
+
+impl Resources {
+    /// ヘッダーとセクション表を読み、リソースディレクトリのあるセクションだけを読み込む。リソースがなければ None
+    fn read<R: Read + Seek>(reader: &mut R) -> anyhow::Result<Option<Self>> {
+        let file_len = reader.seek(SeekFrom::End(0))?;
+        let dos = read_at(reader, 0, 0x40)?;
+        let dos = Reader(&dos);
+        if dos.slice(0, 2)? != b"MZ" {
+            anyhow::bail!("not a PE file");
+        }
+        let pe = dos.u32(0x3C)? as u64;
+        let coff = read_at(reader, pe, 24)?;
+        let coff = Reader(&coff);
+        if coff.slice(0, 4)? != b"PE\0\0" {
+            anyhow::bail!("not a PE file");
+        }
+        let section_count = coff.u16(6)? as usize;
+        let optional_header_size = coff.u16(20)? as usize;
+        let optional_header = read_at(reader, pe + 24, optional_header_size)?;
+        let optional_header = Reader(&optional_header);
+        let (rva_count_offset, directories) = match optional_header.u16(0)? {
+            0x10B => (92, 96),
+            0x20B => (108, 112),
+            magic => anyhow::bail!("unknown optional header magic: {magic:#x}"),
+        };
+        let rva_count = optional_header.u32(rva_count_offset)? as usize;
+        if rva_count <= RESOURCE_DIRECTORY_INDEX {
+            return Ok(None);
+        }
+        let root_rva = optional_header.u32(directories + RESOURCE_DIRECTORY_INDEX * 8)?;
+        if root_rva == 0 {
+            return Ok(None);
+        }
+
+        let table = read_at(
+            reader,
+            pe + 24 + optional_header_size as u64,
+            section_count * 40,
+        )?;
+        let table = Reader(&table);
+        let sections = (0..section_count)
+            .map(|i| {
+                let offset = i * 40;
+                Ok(Section {
+                    virtual_size: table.u32(offset + 8)?,
+                    virtual_address: table.u32(offset + 12)?,
+                    raw_size: table.u32(offset + 16)?,
+                    raw_offset: table.u32(offset + 20)?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let section = sections
+            .into_iter()
+            .find(|s| s.contains(root_rva))
+            .ok_or_else(|| anyhow::anyhow!("rva {root_rva:#x} is outside of sections"))?;
+        // 壊れたヘッダーで大きな領域を確保しないよう、ファイルに収まるかを先に確かめる
+        if section.raw_offset as u64 + section.raw_size as u64 > file_len {
+            anyhow::bail!("resource section exceeds the file");
+        }
+        let data = read_at(reader, section.raw_offset as u64, section.raw_size as usize)?;
+
+        let mut resources = Resources {
+            section: data,
+            virtual_address: section.virtual_address,
+            root: 0,
+        };
+        resources.root = resources.rva_to_offset(root_rva)?;
+        Ok(Some(resources))
+    }
+
+    fn file(&self) -> Reader<'_> {
+        Reader(&self.section)
+    }
+
+    /// リソースのデータは同じセクションにある前提で、セクション内の位置に直す
+    fn rva_to_offset(&self, rva: u32) -> anyhow::Result<usize> {
+        rva.checked_sub(self.virtual_address)
+            .map(|offset| offset as usize)
+            .filter(|offset| *offset < self.section.len())
+            .ok_or_else(|| anyhow::anyhow!("rva {rva:#x} is outside of the resource section"))
+    }
+
+    /// ディレクトリの (ID, 子の位置) を並び順に返す。名前付きの項目は ID を持たないので u32::MAX にする
+    fn entries(&self, directory: usize) -> anyhow::Result<Vec<(u32, u32)>> {
+        let file = self.file();
+        let named = file.u16(directory + 12)? as usize;
+        let ids = file.u16(directory + 14)? as usize;
+        (0..named + ids)
+            .map(|i| {
+                let offset = directory + 16 + i * 8;
+                let name = file.u32(offset)?;
+                let id = if name & 0x8000_0000 != 0 {
+                    u32::MAX
+                } else {
+                    name
+                };
+                Ok((id, file.u32(offset + 4)?))
+            })
+            .collect()
+    }
+
+    fn child(&self, child: u32) -> usize {
+        self.root + (child & 0x7FFF_FFFF) as usize
+    }
+
+    /// 種類の下にある (ID, 言語ディレクトリ) を返す
+    fn names(&self, kind: u32) -> anyhow::Result<Vec<(u32, usize)>> {
+        let Some((_, child)) = self
+            .entries(self.root)?
+            .into_iter()
+            .find(|(id, _)| *id == kind)
+        else {
+            return Ok(vec![]);
+        };
+        Ok(self
+            .entries(self.child(child))?
+            .into_iter()
+            .map(|(id, child)| (id, self.child(child)))
+            .collect())
+    }
+
+    /// 言語ディレクトリの最初の言語のデータを返す
+    fn language_data(&self, directory: usize) -> anyhow::Result<&[u8]> {
+        let (_, child) = self
+            .entries(directory)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("resource has no language"))?;
+        let data_entry = self.child(child);
+        let rva = self.file().u32(data_entry)?;
+        let size = self.file().u32(data_entry + 4)? as usize;
+        self.file().slice(self.rva_to_offset(rva)?, size)
+    }
+
+    /// Windows の ExtractIcon と同じく、並び順で最初のものを使う
+    fn first_data(&self, kind: u32) -> anyhow::Result<Option<&[u8]>> {
+        match self.names(kind)?.first() {
+            Some((_, directory)) => Ok(Some(self.language_data(*directory)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn data(&self, kind: u32, id: u32) -> anyhow::Result<Option<&[u8]>> {
+        match self.names(kind)?.into_iter().find(|(i, _)| *i == id) {
+            Some((_, directory)) => Ok(Some(self.language_data(directory)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+fn read_at<R: Read + Seek>(reader: &mut R, offset: u64, len: usize) -> anyhow::Result<Vec<u8>> {
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut buf = vec![0; len];
+    reader
+        .read_exact(&mut buf)
+        .map_err(|_| anyhow::anyhow!("unexpected end of data at {offset:#x}"))?;
+    Ok(buf)
+}
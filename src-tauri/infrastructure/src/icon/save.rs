@@ -0,0 +1,79 @@
+//! アプリ本体で作品のアイコンを PNG にして保存する
+use std::io::Write;
+
+use domain::service::save_path_resolver::SavePathResolver;
+use domain::{works::Work, StrId};
+use tauri::async_runtime::JoinHandle;
+
+use super::ico::save_ico_to_png_sync;
+use super::pe::save_exe_icon_to_png_sync;
+
+/// アイコンは 256px の正方形で表示するので、それ以上の大きさの画像があればそれを使う
+const EXE_ICON_WIDTH: u32 = 256;
+
+pub fn save_icon_to_png(
+    resolver: &dyn SavePathResolver,
+    file_path: &str,
+    work_id: &StrId<Work>,
+) -> anyhow::Result<JoinHandle<anyhow::Result<()>>> {
+    let save_png_path = resolver.icon_png_path(&work_id.value);
+
+    let is_exe = file_path.to_lowercase().ends_with("exe");
+    let is_ico = file_path.to_lowercase().ends_with("ico");
+
+    if is_ico {
+        return save_ico_to_png(file_path, &save_png_path);
+    }
+    if is_exe {
+        return save_exe_file_png(file_path, &save_png_path);
+    }
+    save_default_icon(&save_png_path)
+}
+
+pub fn save_default_icon(save_png_path: &str) -> anyhow::Result<JoinHandle<anyhow::Result<()>>> {
+    let save_p = save_png_path.to_string();
+    let handle = tauri::async_runtime::spawn_blocking(move || write_default_icon(&save_p));
+
+    Ok(handle)
+}
+
+fn write_default_icon(save_png_path: &str) -> anyhow::Result<()> {
+    let default_icon = include_bytes!("../../../icons/notfound.png");
+    let mut file = std::fs::File::create(save_png_path)?;
+    file.write_all(default_icon)?;
+    Ok(())
+}
+
+pub fn save_ico_to_png(
+    file_path: &str,
+    save_png_path: &str,
+) -> anyhow::Result<JoinHandle<anyhow::Result<()>>> {
+    assert!(file_path.to_lowercase().ends_with("ico"));
+
+    let p = file_path.to_string();
+    let save_p = save_png_path.to_string();
+    let handle =
+        tauri::async_runtime::spawn_blocking(move || match save_ico_to_png_sync(&p, &save_p) {
+            Err(_) => write_default_icon(&save_p),
+            _ => Ok(()),
+        });
+
+    Ok(handle)
+}
+
+pub fn save_exe_file_png(
+    file_path: &str,
+    save_png_path: &str,
+) -> anyhow::Result<JoinHandle<anyhow::Result<()>>> {
+    let p = file_path.to_string();
+    let save_p = save_png_path.to_string();
+    // exe の読み込みと PNG の書き出しは同期 I/O なので、非同期ランタイムのスレッドを塞がない
+    let handle = tauri::async_runtime::spawn_blocking(move || {
+        match save_exe_icon_to_png_sync(&p, EXE_ICON_WIDTH, &save_p) {
+            Ok(true) => Ok(()),
+            _ => write_default_icon(&save_p),
+        }
+    });
+
+    Ok(handle)
+}
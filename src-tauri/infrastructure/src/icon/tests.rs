@@ -1,6 +1,6 @@
 use crate::icon;
 use image::{io::Reader as ImageReader, ColorType, ImageEncoder};
-use std::io::Write as _;
+use std::io::{Read as _, Write as _};
 
 #[test]
 #[ignore]
@@ -114,3 +114,92 @@ fn 既知入力から同一画像が生成される() {
 
     let _ = std::fs::remove_file(dst_path);
 }
+
+fn pe_fixture(name: &str) -> std::fs::File {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("icon")
+        .join(name);
+    std::fs::File::open(path).unwrap()
+}
+
+#[test]
+fn peのアイコンは要求サイズ以上で一番小さいものを選ぶ() {
+    let entry = |width: u32, bit_count: u16, id: u16| icon::pe::GroupIconEntry {
+        width,
+        height: width,
+        color_count: 0,
+        planes: 1,
+        bit_count,
+        id,
+    };
+    let entries = vec![
+        entry(16, 32, 1),
+        entry(48, 8, 2),
+        entry(48, 32, 3),
+        entry(256, 32, 4),
+    ];
+
+    assert_eq!(icon::pe::pick_entry(&entries, 20).map(|e| e.id), Some(3));
+    assert_eq!(icon::pe::pick_entry(&entries, 256).map(|e| e.id), Some(4));
+    assert_eq!(icon::pe::pick_entry(&entries, 512).map(|e| e.id), Some(4));
+    assert_eq!(icon::pe::pick_entry(&[], 256), None);
+}
+
+#[test]
+fn peのpng圧縮された256pxのアイコンを読める() {
+    let image = icon::pe::read_best_icon(&mut pe_fixture("test_icons.exe"), 256)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!((image.width(), image.height()), (256, 256));
+    assert_eq!(&image.rgba_data()[..4], &[255, 0, 0, 255]);
+}
+
+#[test]
+fn peのbmpのアイコンを読める() {
+    let image = icon::pe::read_best_icon(&mut pe_fixture("test_icons.exe"), 48)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!((image.width(), image.height()), (48, 48));
+    assert_eq!(&image.rgba_data()[..4], &[0, 0, 255, 255]);
+}
+
+#[test]
+fn peにアイコンがなければnoneを返す() {
+    assert!(
+        icon::pe::read_best_icon(&mut pe_fixture("test_no_icon.exe"), 256)
+            .unwrap()
+            .is_none()
+    );
+    assert!(icon::pe::read_best_icon(&mut std::io::Cursor::new(b"MZ not a pe"), 256).is_err());
+}
+
+#[test]
+fn peのリソースの位置が壊れていてもエラーにする() {
+    let mut bytes = Vec::new();
+    pe_fixture("test_icons.exe")
+        .read_to_end(&mut bytes)
+        .unwrap();
+    let u16_at = |b: &[u8], o: usize| u16::from_le_bytes([b[o], b[o + 1]]) as usize;
+    let pe = u32::from_le_bytes(bytes[0x3C..0x40].try_into().unwrap()) as usize;
+    let directories = match u16_at(&bytes, pe + 24) {
+        0x10B => 96,
+        _ => 112,
+    };
+    let resource_rva = pe + 24 + directories + 2 * 8;
+    let first_section_raw_size = pe + 24 + u16_at(&bytes, pe + 20) + 16;
+
+    let mut broken_rva = bytes.clone();
+    broken_rva[resource_rva..resource_rva + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let mut broken_size = bytes.clone();
+    for i in 0..u16_at(&bytes, pe + 6) {
+        let offset = first_section_raw_size + i * 40;
+        broken_size[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    }
+
+    for broken in [broken_rva, broken_size] {
+        assert!(icon::pe::read_best_icon(&mut std::io::Cursor::new(broken), 256).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use domain::service::extract_icon::ExtractIconRunner;
+
+use crate::icon::pe::save_exe_icon_to_png_sync;
+
+/// exe の PE リソースからアイコンをプロセス内で取り出す
+#[derive(Default)]
+pub struct ExtractIconRunnerImpl;
+
+impl ExtractIconRunnerImpl {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ExtractIconRunner for ExtractIconRunnerImpl {
+    fn extract_icon(&self, width: u32, exe_path: &str, dst_path: &str) -> anyhow::Result<bool> {
+        if !std::path::Path::new(exe_path).exists() {
+            return Ok(false);
+        }
+        save_exe_icon_to_png_sync(exe_path, width, dst_path)
+    }
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::Path;
+
+use super::extract_icon::ExtractIconRunnerImpl;
+use domain::service::extract_icon::ExtractIconRunner;
+
+fn asset_exe_path(name: &str) -> std::path::PathBuf {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    std::path::Path::new(&manifest_dir)
+        .join("src")
+        .join("icon")
+        .join(name)
+}
+
+#[test]
+fn extract_icon_exeが存在しないとfalseを返す() {
+    let runner = ExtractIconRunnerImpl::new();
+    let tmp = tempfile::tempdir().unwrap();
+    let dst = tmp.path().join("out.png");
+    let res = runner
+        .extract_icon(256, "C:/not-exists/app.exe", &dst.to_string_lossy())
+        .unwrap();
+    assert!(!res);
+    assert!(!Path::new(&dst).exists());
+}
+
+#[test]
+fn extract_icon_成功でpngを書き出す() {
+    let runner = ExtractIconRunnerImpl::new();
+    let exe = asset_exe_path("test_icons.exe");
+    let tmp = tempfile::tempdir().unwrap();
+    let dst = tmp.path().join("out.png");
+    let ok = runner
+        .extract_icon(256, &exe.to_string_lossy(), &dst.to_string_lossy())
+        .unwrap();
+    assert!(ok);
+    let img = image::open(&dst).unwrap();
+    assert_eq!((img.width(), img.height()), (256, 256));
+    let _ = fs::remove_file(&dst);
+}
+
+#[test]
+fn extract_icon_アイコンのないexeはfalseを返す() {
+    let runner = ExtractIconRunnerImpl::new();
+    let exe = asset_exe_path("test_no_icon.exe");
+    let tmp = tempfile::tempdir().unwrap();
+    let dst = tmp.path().join("out.png");
+    let ok = runner
+        .extract_icon(256, &exe.to_string_lossy(), &dst.to_string_lossy())
+        .unwrap();
+    assert!(!ok);
+    assert!(!Path::new(&dst).exists());
+}
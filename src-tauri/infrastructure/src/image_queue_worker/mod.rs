@@ -1,8 +1,9 @@
 pub mod handler;
 pub mod preprocess;
 pub mod resolver;
+pub mod retry;
 pub mod runner;
-pub mod sidecar;
+pub mod extract_icon;
 pub mod types;
 mod worker;
 
@@ -15,8 +16,11 @@ mod tests;
 #[cfg(test)]
 mod types_test;
 
+#[cfg(test)]
+mod retry_test;
+
 #[cfg(test)]
 mod preprocess_test;
 
 #[cfg(test)]
-mod sidecar_test;
+mod extract_icon_test;
@@ -1,19 +1,25 @@
 use domain::save_image_queue::ImagePreprocess;
 
+use crate::thumbnail::GeneratedThumbnail;
+
+/// 前処理を行う。サムネイルのバリアントを作ったときは、記録するためにその結果を返す
 pub fn run_preprocess(
     src_path: &str,
     dst_path: &str,
     preprocess: ImagePreprocess,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Option<GeneratedThumbnail>> {
     match preprocess {
         ImagePreprocess::ResizeAndCropSquare256 => {
-            crate::icon::process_square_icon(src_path, dst_path, 256)
+            crate::icon::process_square_icon(src_path, dst_path, 256).map(|_| None)
         }
         ImagePreprocess::ResizeForWidth400 => {
-            crate::thumbnail::resize_image(src_path, dst_path, 400)
+            crate::thumbnail::resize_image(src_path, dst_path, 400).map(|_| None)
+        }
+        ImagePreprocess::ThumbnailVariants => {
+            crate::thumbnail::write_thumbnail_variants(src_path, dst_path).map(Some)
         }
         ImagePreprocess::None => std::fs::copy(src_path, dst_path)
-            .map(|_| ())
+            .map(|_| None)
             .map_err(|e| anyhow::anyhow!(e)),
     }
 }
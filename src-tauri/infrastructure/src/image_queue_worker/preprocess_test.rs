@@ -2,6 +2,7 @@ use std::path::Path;
 
 use super::preprocess::run_preprocess;
 use domain::save_image_queue::ImagePreprocess;
+use domain::thumbnail::ThumbnailFormat;
 
 fn write_small_png(path: &str, w: u32, h: u32) {
     let img = image::RgbaImage::from_pixel(w, h, image::Rgba([0u8, 0u8, 0u8, 255u8]));
@@ -58,3 +59,51 @@ fn copy_as_is_内容がコピーされる() {
     assert!(Path::new(&dst).exists());
     assert_eq!(std::fs::read(&src).unwrap(), std::fs::read(&dst).unwrap());
 }
+
+#[test]
+fn thumbnail_variants_png_と拡大しない幅の_webp_を書き出す() {
+    let tmp = tempfile::tempdir().unwrap();
+    let src = tmp.path().join("src.png");
+    let dst = tmp.path().join("work-1.png");
+    write_small_png(&src.to_string_lossy(), 600, 300);
+    let generated = run_preprocess(
+        &src.to_string_lossy(),
+        &dst.to_string_lossy(),
+        ImagePreprocess::ThumbnailVariants,
+    )
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(image::image_dimensions(&dst).unwrap(), (400, 200));
+    for (name, size) in [
+        ("work-1@200w.webp", (200, 100)),
+        ("work-1@400w.webp", (400, 200)),
+    ] {
+        let p = tmp.path().join(name);
+        assert_eq!(
+            image::io::Reader::open(&p).unwrap().format(),
+            Some(image::ImageFormat::WebP),
+            "{}",
+            name
+        );
+        assert_eq!(image::image_dimensions(&p).unwrap(), size, "{}", name);
+    }
+    // 元画像より大きい幅は作らない
+    assert!(!tmp.path().join("work-1@800w.webp").exists());
+    // 書き出したものは記録できるよう結果として返す
+    let sizes: Vec<_> = generated
+        .variants
+        .iter()
+        .map(|v| (v.width, v.height, v.format))
+        .collect();
+    assert_eq!(
+        sizes,
+        vec![
+            (400, 200, ThumbnailFormat::Png),
+            (200, 100, ThumbnailFormat::Webp),
+            (400, 200, ThumbnailFormat::Webp),
+        ]
+    );
+    assert_eq!(generated.placeholder_color.as_deref(), Some("#000000"));
+    assert!(generated.perceptual_hash.is_some());
+}
@@ -1,11 +1,15 @@
-use crate::image_queue_worker::sidecar::{ExtractIconRunner, ExtractIconRunnerImpl};
+use crate::image_queue_worker::extract_icon::ExtractIconRunnerImpl;
 use crate::image_queue_worker::types::{Cleanup, LocalSource, SourceDecision};
+use domain::service::extract_icon::ExtractIconRunner;
 use domain::service::save_path_resolver::SavePathResolver;
 
+/// キューで 256px の正方形に揃えるので、それ以上の大きさの画像があればそれを使う
+const ICON_WIDTH: u32 = 256;
+
 pub fn resolve(resolver: &dyn SavePathResolver, exe_path: &str) -> anyhow::Result<SourceDecision> {
     let runner = ExtractIconRunnerImpl::new();
     let dst_tmp = resolver.tmp_unique_path_with_ext("png");
-    match runner.extract_icon(48, exe_path, &dst_tmp) {
+    match runner.extract_icon(ICON_WIDTH, exe_path, &dst_tmp) {
         Ok(true) => Ok(SourceDecision::Use(LocalSource::new(
             dst_tmp,
             Cleanup::None,
@@ -14,12 +18,12 @@ pub fn resolve(resolver: &dyn SavePathResolver, exe_path: &str) -> anyhow::Resul
             let reason = if !std::path::Path::new(exe_path).exists() {
                 "exe not found".to_string()
             } else {
-                "extract-icon sidecar failed or not available".to_string()
+                "exe has no icon resource".to_string()
             };
             Ok(SourceDecision::FallbackDefaultAndSkip { reason })
         }
         Err(e) => Ok(SourceDecision::FallbackDefaultAndSkip {
-            reason: format!("extract-icon error: {}", e),
+            reason: format!("extract icon error: {}", e),
         }),
     }
 }
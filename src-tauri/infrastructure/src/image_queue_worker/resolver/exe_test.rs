@@ -8,7 +8,7 @@ use crate::image_queue_worker::types::SourceDecision;
 
 #[test]
 fn exe_存在しない場合は_skip() {
-    let resolver = DirsSavePathResolver::default();
+    let resolver = DirsSavePathResolver;
     let res = resolve(&resolver, "C:/not-exists/app.exe").unwrap();
     match res {
         SourceDecision::FallbackDefaultAndSkip { .. } => {}
@@ -17,29 +17,15 @@ fn exe_存在しない場合は_skip() {
 }
 
 #[test]
-fn exe_sidecar成功時_pngの一時パスが返る_クリーンアップ() {
-    // Arrange: 配置先は current_exe と同ディレクトリ
-    let sidecar_dst = {
-        let exe_dir = std::env::current_exe()
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-            .expect("resolve current exe dir");
-        let dst = exe_dir.join("extract-icon.exe");
-        const BYTES: &[u8] =
-            include_bytes!("../../../../bin/extract-icon-x86_64-pc-windows-msvc.exe");
-        fs::write(&dst, BYTES).expect("write sidecar");
-        dst
-    };
-
+fn exe_アイコンがあれば_pngの一時パスが返る_クリーンアップ() {
     // 実行対象のexe（リポジトリのアセット）
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
     let exe = std::path::Path::new(&manifest_dir)
         .join("src")
-        .join("image_queue_worker")
-        .join("assets")
-        .join("dohnadohna.exe");
+        .join("icon")
+        .join("test_icons.exe");
 
-    let resolver = DirsSavePathResolver::default();
+    let resolver = DirsSavePathResolver;
 
     // Act
     let res = resolve(&resolver, &exe.to_string_lossy()).unwrap();
@@ -52,5 +38,20 @@ fn exe_sidecar成功時_pngの一時パスが返る_クリーンアップ() {
         }
         _ => panic!("expected Use(local)"),
     }
-    let _ = fs::remove_file(&sidecar_dst);
+}
+
+#[test]
+fn exe_アイコンがなければ_skip() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let exe = std::path::Path::new(&manifest_dir)
+        .join("src")
+        .join("icon")
+        .join("test_no_icon.exe");
+
+    let resolver = DirsSavePathResolver;
+    let res = resolve(&resolver, &exe.to_string_lossy()).unwrap();
+    match res {
+        SourceDecision::FallbackDefaultAndSkip { .. } => {}
+        _ => panic!("expected skip"),
+    }
 }
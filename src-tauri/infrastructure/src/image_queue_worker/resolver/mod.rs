@@ -2,6 +2,7 @@ use domain::save_image_queue::ImageSrcType;
 use domain::service::save_path_resolver::SavePathResolver;
 use domain::windows::WindowsExt;
 
+use crate::downloader::HttpDownloader;
 use crate::image_queue_worker::types::{Cleanup, LocalSource, SourceDecision};
 
 pub mod exe;
@@ -19,13 +20,14 @@ mod url_test;
 
 pub async fn resolve_source<W: WindowsExt>(
     windows: &W,
+    downloader: &HttpDownloader,
     resolver: &dyn SavePathResolver,
     src: &str,
     src_type: ImageSrcType,
 ) -> anyhow::Result<SourceDecision> {
     Ok(match src_type {
         ImageSrcType::Url => {
-            let tmp = url::resolve_to_tmp(resolver, src).await?;
+            let tmp = url::resolve_to_tmp(downloader, resolver, src).await?;
             let cleanup_path = tmp.clone();
             SourceDecision::Use(LocalSource::new(
                 tmp,
@@ -43,6 +45,7 @@ pub async fn resolve_source<W: WindowsExt>(
 /// 事前取得したショートカットメタデータを活用して解決するバリアント
 pub async fn resolve_source_with_shortcut_metas<W: WindowsExt>(
     windows: &W,
+    downloader: &HttpDownloader,
     resolver: &dyn SavePathResolver,
     src: &str,
     src_type: ImageSrcType,
@@ -50,7 +53,7 @@ pub async fn resolve_source_with_shortcut_metas<W: WindowsExt>(
 ) -> anyhow::Result<SourceDecision> {
     Ok(match src_type {
         ImageSrcType::Url => {
-            let tmp = url::resolve_to_tmp(resolver, src).await?;
+            let tmp = url::resolve_to_tmp(downloader, resolver, src).await?;
             let cleanup_path = tmp.clone();
             SourceDecision::Use(LocalSource::new(
                 tmp,
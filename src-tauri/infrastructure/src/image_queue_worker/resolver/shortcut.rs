@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use domain::file::save_ico_to_png_sync;
+use crate::icon::ico::save_ico_to_png_sync;
 use domain::service::save_path_resolver::SavePathResolver;
 use domain::windows::shell_link::ShellLink as _;
 use domain::windows::WindowsExt;
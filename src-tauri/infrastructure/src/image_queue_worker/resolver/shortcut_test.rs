@@ -50,6 +50,7 @@ fn shortcut_メタにicoなら一時png() {
             LnkMetadata {
                 path: "C:/app/app.exe".into(),
                 icon: ico_path.clone(),
+                ..Default::default()
             },
         );
         Ok(map)
@@ -76,6 +77,7 @@ fn shortcut_メタpngならそのまま() {
             LnkMetadata {
                 path: "C:/app/app.exe".into(),
                 icon: "C:/images/icon.png".into(),
+                ..Default::default()
             },
         );
         Ok(map)
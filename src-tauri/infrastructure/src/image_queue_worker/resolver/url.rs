@@ -1,14 +1,33 @@
 use domain::service::save_path_resolver::SavePathResolver;
 
+use crate::downloader::HttpDownloader;
+
+/// URL を一時ファイルへ取得する。
+/// 画像デコーダは拡張子で形式を判定するため、拡張子は実際の内容から決める（判定できなければ URL のもの）
 pub async fn resolve_to_tmp(
+    downloader: &HttpDownloader,
     resolver: &dyn SavePathResolver,
     src_url: &str,
 ) -> anyhow::Result<String> {
-    let ext = std::path::Path::new(src_url)
-        .extension()
-        .and_then(|e| e.to_str())
+    let downloading = resolver.tmp_unique_path();
+    let outcome = match downloader.download(src_url, &downloading).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            let _ = std::fs::remove_file(&downloading);
+            return Err(e);
+        }
+    };
+    let ext = outcome
+        .format
+        .and_then(|f| f.extensions_str().first().copied())
+        .or_else(|| url_extension(src_url))
         .unwrap_or("bin");
-    let tmp = resolver.tmp_unique_path_with_ext(ext);
-    crate::thumbnail::download_to_file(src_url, &tmp).await?;
+    let tmp = format!("{}.{}", downloading, ext);
+    std::fs::rename(&downloading, &tmp)?;
     Ok(tmp)
 }
+
+fn url_extension(src_url: &str) -> Option<&str> {
+    let path = src_url.split(['?', '#']).next()?;
+    std::path::Path::new(path).extension()?.to_str()
+}
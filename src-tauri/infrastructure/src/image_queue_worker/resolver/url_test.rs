@@ -3,6 +3,7 @@ use std::path::Path;
 use domain::service::save_path_resolver::DirsSavePathResolver;
 
 use super::url::resolve_to_tmp;
+use crate::downloader::{DownloaderConfig, HttpDownloader};
 
 #[tokio::test]
 async fn http_成功で一時ファイルが作成される() {
@@ -12,16 +13,41 @@ async fn http_成功で一時ファイルが作成される() {
         .respond_with(wiremock::ResponseTemplate::new(200).set_body_bytes(b"x"))
         .mount(&server)
         .await;
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
     let resolver = DirsSavePathResolver::default();
     let url = format!("{}/a.png", server.uri());
-    let p = resolve_to_tmp(&resolver, &url).await.unwrap();
+    let p = resolve_to_tmp(&downloader, &resolver, &url).await.unwrap();
     assert!(Path::new(&p).exists());
 }
 
 #[tokio::test]
 async fn http_エラーで失敗する() {
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
     let resolver = DirsSavePathResolver::default();
     let url = "http://127.0.0.1:9/not-exist";
-    let res = resolve_to_tmp(&resolver, url).await;
+    let res = resolve_to_tmp(&downloader, &resolver, url).await;
     assert!(res.is_err());
 }
+
+#[tokio::test]
+async fn http_拡張子は内容から判定する() {
+    let png = {
+        let mut buf = std::io::Cursor::new(Vec::new());
+        image::RgbaImage::new(1, 1)
+            .write_to(&mut buf, image::ImageOutputFormat::Png)
+            .unwrap();
+        buf.into_inner()
+    };
+    let server = wiremock::MockServer::start().await;
+    wiremock::Mock::given(wiremock::matchers::method("GET"))
+        .and(wiremock::matchers::path("/image"))
+        .respond_with(wiremock::ResponseTemplate::new(200).set_body_bytes(png))
+        .mount(&server)
+        .await;
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
+    let resolver = DirsSavePathResolver::default();
+    let url = format!("{}/image?size=large", server.uri());
+    let p = resolve_to_tmp(&downloader, &resolver, &url).await.unwrap();
+    assert!(p.ends_with(".png"), "{}", p);
+    let _ = std::fs::remove_file(p);
+}
@@ -0,0 +1,34 @@
+use domain::save_image_queue::ImageQueueFailureKind;
+
+/// 再試行しても結果が変わらない失敗（フォールバックなど）
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct PermanentFailure(pub String);
+
+/// エラーチェーンから失敗の種類を判定する。
+/// HTTP 4xx（408/429 を除く）とフォールバックは恒久的、5xx・タイムアウト・接続失敗は一時的とみなす。
+/// 判定できないもの（ローカル I/O など）は一時的として扱い、回数上限に任せる。
+pub fn classify_failure(err: &anyhow::Error) -> ImageQueueFailureKind {
+    for cause in err.chain() {
+        if cause.downcast_ref::<PermanentFailure>().is_some() {
+            return ImageQueueFailureKind::Permanent;
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return match e.status() {
+                Some(status) => classify_status(status),
+                None => ImageQueueFailureKind::Transient,
+            };
+        }
+    }
+    ImageQueueFailureKind::Transient
+}
+
+fn classify_status(status: reqwest::StatusCode) -> ImageQueueFailureKind {
+    match status {
+        reqwest::StatusCode::REQUEST_TIMEOUT | reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            ImageQueueFailureKind::Transient
+        }
+        s if s.is_client_error() => ImageQueueFailureKind::Permanent,
+        _ => ImageQueueFailureKind::Transient,
+    }
+}
@@ -0,0 +1,88 @@
+use domain::save_image_queue::ImageQueueFailureKind;
+
+use super::retry::{classify_failure, PermanentFailure};
+use crate::downloader::{DownloaderConfig, HttpDownloader};
+
+async fn download_with_status(status: u16) -> anyhow::Error {
+    let server = wiremock::MockServer::start().await;
+    wiremock::Mock::given(wiremock::matchers::method("GET"))
+        .respond_with(wiremock::ResponseTemplate::new(status))
+        .mount(&server)
+        .await;
+    let tmp = tempfile::tempdir().unwrap();
+    let dst = tmp.path().join("out.png");
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
+    crate::thumbnail::download_to_file(
+        &downloader,
+        &format!("{}/img.png", server.uri()),
+        &dst.to_string_lossy(),
+    )
+    .await
+    .unwrap_err()
+}
+
+#[tokio::test]
+async fn classify_failure_http_ステータスで判定する() {
+    struct Case {
+        status: u16,
+        expected: ImageQueueFailureKind,
+    }
+    let cases = vec![
+        Case {
+            status: 404,
+            expected: ImageQueueFailureKind::Permanent,
+        },
+        Case {
+            status: 403,
+            expected: ImageQueueFailureKind::Permanent,
+        },
+        Case {
+            status: 429,
+            expected: ImageQueueFailureKind::Transient,
+        },
+        Case {
+            status: 408,
+            expected: ImageQueueFailureKind::Transient,
+        },
+        Case {
+            status: 500,
+            expected: ImageQueueFailureKind::Transient,
+        },
+        Case {
+            status: 503,
+            expected: ImageQueueFailureKind::Transient,
+        },
+    ];
+
+    for c in cases {
+        let err = download_with_status(c.status).await;
+        assert_eq!(classify_failure(&err), c.expected, "status={}", c.status);
+    }
+}
+
+#[tokio::test]
+async fn classify_failure_接続失敗は一時的() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dst = tmp.path().join("out.png");
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
+    let err = crate::thumbnail::download_to_file(
+        &downloader,
+        "http://127.0.0.1:9/does-not-exist",
+        &dst.to_string_lossy(),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(classify_failure(&err), ImageQueueFailureKind::Transient);
+}
+
+#[test]
+fn classify_failure_フォールバックとその他() {
+    let permanent = anyhow::Error::new(PermanentFailure("fallback".to_string())).context("wrapped");
+    assert_eq!(
+        classify_failure(&permanent),
+        ImageQueueFailureKind::Permanent
+    );
+
+    let other = anyhow::anyhow!("decode image failed");
+    assert_eq!(classify_failure(&other), ImageQueueFailureKind::Transient);
+}
@@ -6,6 +6,7 @@ use domain::service::save_path_resolver::SavePathResolver;
 use domain::windows::WindowsExt;
 
 use super::ImageQueueWorker;
+use crate::downloader::HttpDownloader;
 use domain::service::image_queue_event::ImageQueueWorkerEventHandler;
 
 pub struct ImageQueueRunnerImpl<M, R, W>
@@ -14,8 +15,19 @@ where
     R: RepositoriesExt + Send + Sync + 'static,
     W: WindowsExt + Send + Sync + 'static,
 {
-    worker: Arc<ImageQueueWorker<M, R, W>>,
+    state: Arc<RunnerState<M, R, W>>,
+}
+
+struct RunnerState<M, R, W>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+    W: WindowsExt + Send + Sync + 'static,
+{
+    worker: ImageQueueWorker<M, R, W>,
     is_running: std::sync::atomic::AtomicBool,
+    /// バックオフ待ちの項目のために予約した次回の drain
+    retry_timer: std::sync::Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
 }
 
 impl<M, R, W> ImageQueueRunnerImpl<M, R, W>
@@ -24,37 +36,63 @@ where
     R: RepositoriesExt + Send + Sync + 'static,
     W: WindowsExt + Send + Sync + 'static,
 {
-    pub fn new(manager: Arc<M>, resolver: Arc<dyn SavePathResolver>, windows: Arc<W>) -> Self {
-        let worker = Arc::new(ImageQueueWorker::new(manager, resolver, windows));
-        Self {
-            worker,
-            is_running: std::sync::atomic::AtomicBool::new(false),
-        }
+    pub fn new(
+        manager: Arc<M>,
+        resolver: Arc<dyn SavePathResolver>,
+        windows: Arc<W>,
+        downloader: Arc<HttpDownloader>,
+    ) -> Self {
+        Self::from_worker(ImageQueueWorker::new(
+            manager, resolver, windows, downloader,
+        ))
     }
 
     pub fn new_with_event_handler(
         manager: Arc<M>,
         resolver: Arc<dyn SavePathResolver>,
         windows: Arc<W>,
+        downloader: Arc<HttpDownloader>,
         handler: Arc<dyn ImageQueueWorkerEventHandler + Send + Sync>,
     ) -> Self {
-        let worker = Arc::new(ImageQueueWorker::new_with_event_handler(
-            manager, resolver, windows, handler,
-        ));
+        Self::from_worker(ImageQueueWorker::new_with_event_handler(
+            manager, resolver, windows, downloader, handler,
+        ))
+    }
+
+    pub(crate) fn from_worker(worker: ImageQueueWorker<M, R, W>) -> Self {
         Self {
-            worker,
-            is_running: std::sync::atomic::AtomicBool::new(false),
+            state: Arc::new(RunnerState {
+                worker,
+                is_running: std::sync::atomic::AtomicBool::new(false),
+                retry_timer: std::sync::Mutex::new(None),
+            }),
         }
     }
+
+    /// drain の途中かどうか
+    pub fn is_running(&self) -> bool {
+        self.state
+            .is_running
+            .load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    /// バックオフ待ちの項目のために次回の drain が予約されているかどうか
+    pub fn has_scheduled_retry(&self) -> bool {
+        self.state
+            .retry_timer
+            .lock()
+            .map(|timer| timer.is_some())
+            .unwrap_or(false)
+    }
 }
 
-impl<M, R, W> ImageQueueDrainService for ImageQueueRunnerImpl<M, R, W>
+impl<M, R, W> RunnerState<M, R, W>
 where
     M: RepositoryManager<R> + Send + Sync + 'static,
     R: RepositoriesExt + Send + Sync + 'static,
     W: WindowsExt + Send + Sync + 'static,
 {
-    async fn drain_until_empty(&self) -> anyhow::Result<()> {
+    async fn drain(self: &Arc<Self>) -> anyhow::Result<()> {
         // Non-blocking single-flight guard
         if self
             .is_running
@@ -69,8 +107,48 @@ where
             return Ok(());
         }
         let result = self.worker.drain_until_empty().await;
+        let next_retry = self.worker.next_retry_delay().await;
         self.is_running
             .store(false, std::sync::atomic::Ordering::Release);
+        match next_retry {
+            Ok(delay) => self.schedule_retry(delay),
+            Err(e) => log::warn!("failed to look up the next image queue retry: {:#}", e),
+        }
         result
     }
+
+    /// 最も早い next_attempt_at に合わせて drain を予約し直す。待ちがなければ予約を取り消す
+    fn schedule_retry(self: &Arc<Self>, delay: Option<std::time::Duration>) {
+        let timer = delay.map(|delay| {
+            let state = Arc::clone(self);
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(delay).await;
+                // 自分自身の予約を外してから drain する（drain の最後で予約し直される）
+                if let Ok(mut timer) = state.retry_timer.lock() {
+                    timer.take();
+                }
+                if let Err(e) = state.drain().await {
+                    log::warn!("scheduled image queue drain failed: {:#}", e);
+                }
+            })
+        });
+        let Ok(mut current) = self.retry_timer.lock() else {
+            return;
+        };
+        if let Some(previous) = std::mem::replace(&mut *current, timer) {
+            previous.abort();
+        }
+    }
+}
+
+impl<M, R, W> ImageQueueDrainService for ImageQueueRunnerImpl<M, R, W>
+where
+    M: RepositoryManager<R> + Send + Sync + 'static,
+    R: RepositoriesExt + Send + Sync + 'static,
+    W: WindowsExt + Send + Sync + 'static,
+{
+    /// バックオフ待ちの項目が残っていれば、最も早い next_attempt_at に再度 drain する
+    async fn drain_until_empty(&self) -> anyhow::Result<()> {
+        self.state.drain().await
+    }
 }
@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
 use tempfile::TempDir;
@@ -14,10 +13,16 @@ use domain::file::LnkMetadata;
 use domain::pubsub::{PubSubEvent, PubSubService};
 use domain::save_image_queue::ImageSrcType;
 use domain::service::save_path_resolver::{DirsSavePathResolver, SavePathResolver};
+use domain::thumbnail::ThumbnailFormat;
 use domain::windows::shell_link::MockShellLink;
 use domain::windows::{process::MockProcessWindows, WindowsExt};
 
 use super::{resolver, types::SourceDecision};
+use crate::downloader::{DownloaderConfig, HttpDownloader};
+
+fn downloader() -> Arc<HttpDownloader> {
+    Arc::new(HttpDownloader::new(DownloaderConfig::default()))
+}
 
 #[derive(Clone)]
 struct TestResolver {
@@ -68,6 +73,7 @@ async fn handle_http_download_のテスト() {
     let server = wiremock::MockServer::start().await;
     let tmp = TempDir::new().unwrap();
     let resolver = TestResolver::new(tmp.path().to_string_lossy().to_string());
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
 
     #[derive(Clone)]
     enum Kind {
@@ -114,7 +120,7 @@ async fn handle_http_download_のテスト() {
                     .await;
 
                 let url = format!("{}{}", &server.uri(), path);
-                let p = resolver::url::resolve_to_tmp(&resolver, &url)
+                let p = resolver::url::resolve_to_tmp(&downloader, &resolver, &url)
                     .await
                     .unwrap();
                 assert!(Path::new(&p).exists(), "{}", c.name);
@@ -122,7 +128,7 @@ async fn handle_http_download_のテスト() {
                 assert_eq!(read, body, "{}", c.name);
             }
             Kind::ErrorInvalidUrl { url } => {
-                let res = resolver::url::resolve_to_tmp(&resolver, url).await;
+                let res = resolver::url::resolve_to_tmp(&downloader, &resolver, url).await;
                 assert!(res.is_err(), "{}", c.name);
             }
         }
@@ -226,6 +232,7 @@ fn handle_shortcut_のテスト() {
                         LnkMetadata {
                             path: meta_path.to_string(),
                             icon: meta_icon.to_string(),
+                            ..Default::default()
                         },
                     );
                     Ok(map)
@@ -240,6 +247,7 @@ fn handle_shortcut_のテスト() {
                         LnkMetadata {
                             path: meta_path.to_string(),
                             icon: "".to_string(),
+                            ..Default::default()
                         },
                     );
                     Ok(map)
@@ -257,6 +265,7 @@ fn handle_shortcut_のテスト() {
                         LnkMetadata {
                             path: meta_path.to_string(),
                             icon: meta_icon.to_string(),
+                            ..Default::default()
                         },
                     );
                     Ok(map)
@@ -332,6 +341,7 @@ async fn resolve_local_src_path_のテスト() {
 
     let tmp = TempDir::new().unwrap();
     let resolver = TestResolver::new(tmp.path().to_string_lossy().to_string());
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
 
     #[derive(Clone, Debug)]
     enum Arrange {
@@ -374,7 +384,7 @@ async fn resolve_local_src_path_のテスト() {
             expect: Expect::Skip,
         },
         Case {
-            name: "Exe は sidecar の挙動により Path または Skip",
+            name: "Exe が存在しなければ Skip",
             arrange: Arrange::ExeNotExists {
                 exe: "C:/not-exists/app.exe",
             },
@@ -402,6 +412,7 @@ async fn resolve_local_src_path_のテスト() {
                         LnkMetadata {
                             path: "C:/Program Files/App/app.exe".to_string(),
                             icon: "C:/images/icon.png".to_string(),
+                            ..Default::default()
                         },
                     );
                     Ok(map)
@@ -423,14 +434,17 @@ async fn resolve_local_src_path_のテスト() {
         // Act
         let res = match c.arrange.clone() {
             Arrange::UrlOk => {
-                resolver::resolve_source(&win, &resolver, &url_ok, ImageSrcType::Url).await
+                resolver::resolve_source(&win, &downloader, &resolver, &url_ok, ImageSrcType::Url)
+                    .await
             }
             Arrange::Path { src } => {
-                resolver::resolve_source(&win, &resolver, src, ImageSrcType::Path).await
+                resolver::resolve_source(&win, &downloader, &resolver, src, ImageSrcType::Path)
+                    .await
             }
             Arrange::ShortcutPng => {
                 resolver::resolve_source(
                     &win,
+                    &downloader,
                     &resolver,
                     "C:/links/app.lnk",
                     ImageSrcType::Shortcut,
@@ -438,10 +452,10 @@ async fn resolve_local_src_path_のテスト() {
                 .await
             }
             Arrange::ExeNotExists { exe } => {
-                resolver::resolve_source(&win, &resolver, exe, ImageSrcType::Exe).await
+                resolver::resolve_source(&win, &downloader, &resolver, exe, ImageSrcType::Exe).await
             }
             Arrange::UrlBad { url } => {
-                resolver::resolve_source(&win, &resolver, url, ImageSrcType::Url).await
+                resolver::resolve_source(&win, &downloader, &resolver, url, ImageSrcType::Url).await
             }
         };
 
@@ -461,17 +475,16 @@ async fn resolve_local_src_path_のテスト() {
 }
 
 #[test]
-#[ignore]
-fn handle_exe_成功なら_pathを返す_要sidecar設置() {
-    // このテストは実行ファイルと同ディレクトリに extract-icon.exe が必要
-    // 適切なスタブを配置できる環境でのみ実行してください
-    let dst_dir = TempDir::new().unwrap();
-    let dst = dst_dir.path().join("exe_success.png");
-    let exe = "C:/Windows/System32/notepad.exe"; // 例: 適当なexe
-    let resolver_impl = TestResolver::new("/tmp".to_string());
-    let res = resolver::exe::resolve(&resolver_impl, exe).unwrap();
+fn handle_exe_アイコンがあれば_pathを返す() {
+    let tmp = TempDir::new().unwrap();
+    let exe = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("icon")
+        .join("test_icons.exe");
+    let resolver_impl = TestResolver::new(tmp.path().to_string_lossy().to_string());
+    let res = resolver::exe::resolve(&resolver_impl, &exe.to_string_lossy()).unwrap();
     match res {
-        SourceDecision::Use(local) => assert_eq!(local.path(), dst.to_string_lossy()),
+        SourceDecision::Use(local) => assert!(Path::new(local.path()).exists()),
         _ => panic!("expected Path"),
     }
 }
@@ -481,25 +494,12 @@ fn write_small_png(path: &str, w: u32, h: u32) {
     img.save(path).unwrap();
 }
 
+#[cfg(windows)]
 #[tokio::test]
 async fn resolve_local_src_pathに実際のファイルを渡してアイコンが一時的にローカルへ保存される() {
     // Arrange
     use crate::windowsimpl::windows::Windows as RealWindows;
-
-    // extract-icon.exe のセットアップ
-    let extract_icon_exe = {
-        let exe_dir = std::env::current_exe()
-            .ok()
-            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-            .ok_or_else(|| anyhow::anyhow!("failed to resolve current exe directory"))
-            .unwrap();
-        let dst = exe_dir.join("extract-icon.exe");
-        const BYTES: &[u8] = include_bytes!("../../../bin/extract-icon-x86_64-pc-windows-msvc.exe");
-        if !dst.exists() {
-            let _ = fs::write(&dst, BYTES);
-        }
-        dst
-    };
+    let downloader = HttpDownloader::new(DownloaderConfig::default());
 
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
     let assets_dir = std::path::Path::new(&manifest_dir)
@@ -542,7 +542,9 @@ async fn resolve_local_src_pathに実際のファイルを渡してアイコン
 
     for c in cases {
         // Act
-        let result = resolver::resolve_source(&windows, &*resolver, &c.src_path, c.src_type).await;
+        let result =
+            resolver::resolve_source(&windows, &downloader, &*resolver, &c.src_path, c.src_type)
+                .await;
 
         // Assert
         match result {
@@ -557,9 +559,6 @@ async fn resolve_local_src_pathに実際のファイルを渡してアイコン
             }
         }
     }
-
-    // cleanup
-    let _ = std::fs::remove_file(&extract_icon_exe);
 }
 
 #[tokio::test]
@@ -582,6 +581,9 @@ async fn drain_until_empty_成功コピー_only_mark_finished() {
         dst_path: dst.to_string_lossy().to_string(),
         preprocess: ImagePreprocess::None,
         last_error: None,
+        attempt_count: 0,
+        next_attempt_at: None,
+        failed_at: None,
     };
 
     // list_unfinished_oldest: 1回目は1件、2回目は空
@@ -589,9 +591,8 @@ async fn drain_until_empty_成功コピー_only_mark_finished() {
     {
         let c = counter.clone();
         let mut iq = repos.image_queue.lock().await;
-        iq.expect_list().returning(move |unfinished, _| {
+        iq.expect_list_due().returning(move |_| {
             let mut n = c.lock().unwrap();
-            assert!(unfinished);
             let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
             *n += 1;
             std::pin::Pin::from(Box::new(async move { Ok(ret) }))
@@ -606,14 +607,84 @@ async fn drain_until_empty_成功コピー_only_mark_finished() {
     mock.expect_get_lnk_metadatas()
         .returning(|_| Ok(HashMap::new()));
     let windows = Arc::new(TestWindows::new(mock));
-    let worker = crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows);
+    let worker =
+        crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows, downloader());
     worker.drain_until_empty().await.unwrap();
 
     assert!(Path::new(&dst).exists());
 }
 
 #[tokio::test]
-async fn drain_until_empty_処理失敗_mark_failedとエラーログ() {
+async fn drain_until_empty_サムネイルはpngがあっても作り直してバリアントを記録する() {
+    let repos = TestRepositories::default();
+    let manager = Arc::new(TestRepositoryManager::new(repos.clone()));
+    let tmp = TempDir::new().unwrap();
+    let resolver = Arc::new(TestResolver::new(tmp.path().to_string_lossy().to_string()));
+
+    // 以前のバージョンで保存した PNG から作り直す
+    let dst = tmp.path().join("work-1.png");
+    write_small_png(&dst.to_string_lossy(), 600, 300);
+    let row = ImageSaveQueueRow {
+        id: Id::new(1),
+        src: dst.to_string_lossy().to_string(),
+        src_type: ImageSrcType::Path,
+        dst_path: dst.to_string_lossy().to_string(),
+        preprocess: ImagePreprocess::ThumbnailVariants,
+        last_error: None,
+        attempt_count: 0,
+        next_attempt_at: None,
+        failed_at: None,
+    };
+
+    let counter = Arc::new(Mutex::new(0));
+    {
+        let c = counter.clone();
+        let mut iq = repos.image_queue.lock().await;
+        iq.expect_list_due().returning(move |_| {
+            let mut n = c.lock().unwrap();
+            let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
+            *n += 1;
+            std::pin::Pin::from(Box::new(async move { Ok(ret) }))
+        });
+        iq.expect_mark_finished()
+            .times(1)
+            .returning(|_| std::pin::Pin::from(Box::new(async { Ok(()) })));
+        iq.expect_mark_retry().times(0);
+        iq.expect_mark_failed().times(0);
+    }
+    repos
+        .work
+        .lock()
+        .await
+        .expect_replace_work_thumbnail_variants()
+        .withf(|work_id, variants, color, hash| {
+            let sizes: Vec<_> = variants.iter().map(|v| (v.width, v.format)).collect();
+            work_id.value == "work-1"
+                && sizes
+                    == vec![
+                        (400, ThumbnailFormat::Png),
+                        (200, ThumbnailFormat::Webp),
+                        (400, ThumbnailFormat::Webp),
+                    ]
+                && color.as_deref() == Some("#000000")
+                && hash.is_some()
+        })
+        .times(1)
+        .returning(|_, _, _, _| std::pin::Pin::from(Box::new(async { Ok(()) })));
+
+    let mut mock = MockShellLink::new();
+    mock.expect_get_lnk_metadatas()
+        .returning(|_| Ok(HashMap::new()));
+    let windows = Arc::new(TestWindows::new(mock));
+    let worker =
+        crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows, downloader());
+    worker.drain_until_empty().await.unwrap();
+
+    assert!(tmp.path().join("work-1@200w.webp").exists());
+}
+
+#[tokio::test]
+async fn drain_until_empty_処理失敗_バックオフ付きで_mark_retry() {
     let repos = TestRepositories::default();
     let manager = Arc::new(TestRepositoryManager::new(repos.clone()));
     let tmp = TempDir::new().unwrap();
@@ -628,20 +699,118 @@ async fn drain_until_empty_処理失敗_mark_failedとエラーログ() {
         dst_path: dst.to_string_lossy().to_string(),
         preprocess: ImagePreprocess::None,
         last_error: None,
+        attempt_count: 0,
+        next_attempt_at: None,
+        failed_at: None,
     };
 
     let counter = Arc::new(Mutex::new(0));
     {
         let c = counter.clone();
         let mut iq = repos.image_queue.lock().await;
-        iq.expect_list().returning(move |unfinished, _| {
+        iq.expect_list_due().returning(move |_| {
             let mut n = c.lock().unwrap();
-            assert!(unfinished);
             let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
             *n += 1;
             std::pin::Pin::from(Box::new(async move { Ok(ret) }))
         });
         iq.expect_mark_finished().times(0);
+        iq.expect_mark_failed().times(0);
+        iq.expect_mark_retry().times(1).returning(|_, _, delay| {
+            assert_eq!(delay, std::time::Duration::from_secs(30));
+            std::pin::Pin::from(Box::new(async { Ok(()) }))
+        });
+    }
+
+    let mut mock = MockShellLink::new();
+    mock.expect_get_lnk_metadatas()
+        .returning(|_| Ok(HashMap::new()));
+    let windows = Arc::new(TestWindows::new(mock));
+    let worker =
+        crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows, downloader());
+    worker.drain_until_empty().await.unwrap();
+}
+
+#[tokio::test]
+async fn runner_バックオフ待ちがあれば最も早い再試行時刻に_drain_し直す() {
+    use domain::service::image_queue_drain::ImageQueueDrainService as _;
+
+    let repos = TestRepositories::default();
+    let manager = Arc::new(TestRepositoryManager::new(repos.clone()));
+    let tmp = TempDir::new().unwrap();
+    let resolver = Arc::new(TestResolver::new(tmp.path().to_string_lossy().to_string()));
+
+    let list_due_calls = Arc::new(Mutex::new(0));
+    {
+        let calls = list_due_calls.clone();
+        let lookups = Arc::new(Mutex::new(0));
+        let mut iq = repos.image_queue.lock().await;
+        iq.expect_list_due().returning(move |_| {
+            *calls.lock().unwrap() += 1;
+            std::pin::Pin::from(Box::new(async { Ok(vec![]) }))
+        });
+        // 1 回目の drain の後だけバックオフ待ちが残っている
+        iq.expect_next_attempt_at().returning(move || {
+            let mut n = lookups.lock().unwrap();
+            let ret = (*n == 0).then(|| chrono::Local::now() + chrono::Duration::milliseconds(100));
+            *n += 1;
+            std::pin::Pin::from(Box::new(async move { Ok(ret) }))
+        });
+    }
+
+    let windows = Arc::new(TestWindows::new(MockShellLink::new()));
+    let runner = crate::image_queue_worker::ImageQueueRunnerImpl::new(
+        manager,
+        resolver,
+        windows,
+        downloader(),
+    );
+    runner.drain_until_empty().await.unwrap();
+    assert_eq!(*list_due_calls.lock().unwrap(), 1);
+    assert!(runner.has_scheduled_retry());
+
+    for _ in 0..50 {
+        if *list_due_calls.lock().unwrap() == 2 && !runner.has_scheduled_retry() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(*list_due_calls.lock().unwrap(), 2);
+    // 待ちがなくなれば予約しない
+    assert!(!runner.has_scheduled_retry());
+}
+
+#[tokio::test]
+async fn drain_until_empty_試行回数の上限に達したら_mark_failed() {
+    let repos = TestRepositories::default();
+    let manager = Arc::new(TestRepositoryManager::new(repos.clone()));
+    let tmp = TempDir::new().unwrap();
+    let resolver = Arc::new(TestResolver::new(tmp.path().to_string_lossy().to_string()));
+
+    let dst = tmp.path().join("dst.png");
+    let row = ImageSaveQueueRow {
+        id: Id::new(5),
+        src: "C:/not-exists/input.png".to_string(),
+        src_type: ImageSrcType::Path,
+        dst_path: dst.to_string_lossy().to_string(),
+        preprocess: ImagePreprocess::None,
+        last_error: Some("previous".to_string()),
+        attempt_count: 2,
+        next_attempt_at: None,
+        failed_at: None,
+    };
+
+    let counter = Arc::new(Mutex::new(0));
+    {
+        let c = counter.clone();
+        let mut iq = repos.image_queue.lock().await;
+        iq.expect_list_due().returning(move |_| {
+            let mut n = c.lock().unwrap();
+            let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
+            *n += 1;
+            std::pin::Pin::from(Box::new(async move { Ok(ret) }))
+        });
+        iq.expect_mark_retry().times(0);
         iq.expect_mark_failed()
             .times(1)
             .returning(|_, _| std::pin::Pin::from(Box::new(async { Ok(()) })));
@@ -651,10 +820,69 @@ async fn drain_until_empty_処理失敗_mark_failedとエラーログ() {
     mock.expect_get_lnk_metadatas()
         .returning(|_| Ok(HashMap::new()));
     let windows = Arc::new(TestWindows::new(mock));
-    let worker = crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows);
+    let policy = domain::save_image_queue::RetryPolicy {
+        max_attempts: 3,
+        ..Default::default()
+    };
+    let worker =
+        crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows, downloader())
+            .with_retry_policy(policy);
     worker.drain_until_empty().await.unwrap();
 }
 
+#[tokio::test]
+async fn drain_until_empty_http_4xx_は再試行せず_mark_failed() {
+    let repos = TestRepositories::default();
+    let manager = Arc::new(TestRepositoryManager::new(repos.clone()));
+    let tmp = TempDir::new().unwrap();
+    let resolver = Arc::new(TestResolver::new(tmp.path().to_string_lossy().to_string()));
+
+    let server = wiremock::MockServer::start().await;
+    wiremock::Mock::given(wiremock::matchers::method("GET"))
+        .and(wiremock::matchers::path("/gone.png"))
+        .respond_with(wiremock::ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let dst = tmp.path().join("dst_gone.png");
+    let row = ImageSaveQueueRow {
+        id: Id::new(6),
+        src: format!("{}/gone.png", server.uri()),
+        src_type: ImageSrcType::Url,
+        dst_path: dst.to_string_lossy().to_string(),
+        preprocess: ImagePreprocess::None,
+        last_error: None,
+        attempt_count: 0,
+        next_attempt_at: None,
+        failed_at: None,
+    };
+
+    let counter = Arc::new(Mutex::new(0));
+    {
+        let c = counter.clone();
+        let mut iq = repos.image_queue.lock().await;
+        iq.expect_list_due().returning(move |_| {
+            let mut n = c.lock().unwrap();
+            let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
+            *n += 1;
+            std::pin::Pin::from(Box::new(async move { Ok(ret) }))
+        });
+        iq.expect_mark_retry().times(0);
+        iq.expect_mark_failed()
+            .times(1)
+            .returning(|_, _| std::pin::Pin::from(Box::new(async { Ok(()) })));
+    }
+
+    let mut mock = MockShellLink::new();
+    mock.expect_get_lnk_metadatas()
+        .returning(|_| Ok(HashMap::new()));
+    let windows = Arc::new(TestWindows::new(mock));
+    let worker =
+        crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows, downloader());
+    worker.drain_until_empty().await.unwrap();
+    assert!(!Path::new(&dst).exists());
+}
+
 #[tokio::test]
 async fn drain_until_empty_skip経路_デフォルト書き出しと_mark_failed() {
     let repos = TestRepositories::default();
@@ -670,15 +898,17 @@ async fn drain_until_empty_skip経路_デフォルト書き出しと_mark_failed
         dst_path: dst.to_string_lossy().to_string(),
         preprocess: ImagePreprocess::ResizeAndCropSquare256,
         last_error: None,
+        attempt_count: 0,
+        next_attempt_at: None,
+        failed_at: None,
     };
 
     let counter = Arc::new(Mutex::new(0));
     {
         let c = counter.clone();
         let mut iq = repos.image_queue.lock().await;
-        iq.expect_list().returning(move |unfinished, _| {
+        iq.expect_list_due().returning(move |_| {
             let mut n = c.lock().unwrap();
-            assert!(unfinished);
             let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
             *n += 1;
             std::pin::Pin::from(Box::new(async move { Ok(ret) }))
@@ -694,7 +924,8 @@ async fn drain_until_empty_skip経路_デフォルト書き出しと_mark_failed
     mock.expect_get_lnk_metadatas()
         .returning(|_| Ok(HashMap::new()));
     let windows = Arc::new(TestWindows::new(mock));
-    let worker = crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows);
+    let worker =
+        crate::image_queue_worker::ImageQueueWorker::new(manager, resolver, windows, downloader());
     worker.drain_until_empty().await.unwrap();
     assert!(Path::new(&dst).exists());
 }
@@ -723,15 +954,17 @@ async fn drain_until_empty_url経由_一時ファイル削除される() {
         dst_path: dst.to_string_lossy().to_string(),
         preprocess: ImagePreprocess::None,
         last_error: None,
+        attempt_count: 0,
+        next_attempt_at: None,
+        failed_at: None,
     };
 
     let counter = Arc::new(Mutex::new(0));
     {
         let c = counter.clone();
         let mut iq = repos.image_queue.lock().await;
-        iq.expect_list().returning(move |unfinished, _| {
+        iq.expect_list_due().returning(move |_| {
             let mut n = c.lock().unwrap();
-            assert!(unfinished);
             let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
             *n += 1;
             std::pin::Pin::from(Box::new(async move { Ok(ret) }))
@@ -745,8 +978,12 @@ async fn drain_until_empty_url経由_一時ファイル削除される() {
     mock.expect_get_lnk_metadatas()
         .returning(|_| Ok(HashMap::new()));
     let windows = Arc::new(TestWindows::new(mock));
-    let worker =
-        crate::image_queue_worker::ImageQueueWorker::new(manager, resolver.clone(), windows);
+    let worker = crate::image_queue_worker::ImageQueueWorker::new(
+        manager,
+        resolver.clone(),
+        windows,
+        downloader(),
+    );
     worker.drain_until_empty().await.unwrap();
 
     // 一時ファイルは削除されているはず（RAII）: 具体パスは取得困難なので dst の存在のみを確認
@@ -786,6 +1023,9 @@ async fn drain_until_empty_イベントハンドラ_pubsub通知が発火する(
         dst_path: dst.to_string_lossy().to_string(),
         preprocess: ImagePreprocess::None,
         last_error: None,
+        attempt_count: 0,
+        next_attempt_at: None,
+        failed_at: None,
     };
 
     // list/mark expectations
@@ -793,19 +1033,16 @@ async fn drain_until_empty_イベントハンドラ_pubsub通知が発火する(
     {
         let c = counter.clone();
         let mut iq = repos.image_queue.lock().await;
-        iq.expect_list().returning(move |unfinished, _| {
+        iq.expect_list_due().returning(move |_| {
             let mut n = c.lock().unwrap();
-            assert!(unfinished);
             let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
             *n += 1;
             std::pin::Pin::from(Box::new(async move { Ok(ret) }))
         });
-        iq.expect_count()
-            .times(1)
-            .returning(|unfinished| {
-                assert!(unfinished);
-                std::pin::Pin::from(Box::new(async { Ok(1) }))
-            });
+        iq.expect_count().times(1).returning(|unfinished| {
+            assert!(unfinished);
+            std::pin::Pin::from(Box::new(async { Ok(1) }))
+        });
         iq.expect_mark_finished()
             .times(1)
             .returning(|_| std::pin::Pin::from(Box::new(async { Ok(()) })));
@@ -823,7 +1060,11 @@ async fn drain_until_empty_イベントハンドラ_pubsub通知が発火する(
         pubsub.clone(),
     ));
     let worker = crate::image_queue_worker::ImageQueueWorker::new_with_event_handler(
-        manager, resolver, windows, handler,
+        manager,
+        resolver,
+        windows,
+        downloader(),
+        handler,
     );
 
     // Act
@@ -868,15 +1109,17 @@ async fn drain_until_empty_イベントハンドラ_host_log_が書き込まれ
         dst_path: dst.to_string_lossy().to_string(),
         preprocess: ImagePreprocess::None,
         last_error: None,
+        attempt_count: 0,
+        next_attempt_at: None,
+        failed_at: None,
     };
 
     let counter = std::sync::Arc::new(std::sync::Mutex::new(0));
     {
         let c = counter.clone();
         let mut iq = repos.image_queue.lock().await;
-        iq.expect_list().returning(move |unfinished, _| {
+        iq.expect_list_due().returning(move |_| {
             let mut n = c.lock().unwrap();
-            assert!(unfinished);
             let ret = if *n == 0 { vec![row.clone()] } else { vec![] };
             *n += 1;
             std::pin::Pin::from(Box::new(async move { Ok(ret) }))
@@ -902,7 +1145,11 @@ async fn drain_until_empty_イベントハンドラ_host_log_が書き込まれ
 
     let handler = std::sync::Arc::new(ImageQueueHostLogHandler::new(manager.clone()));
     let worker = crate::image_queue_worker::ImageQueueWorker::new_with_event_handler(
-        manager, resolver, windows, handler,
+        manager,
+        resolver,
+        windows,
+        downloader(),
+        handler,
     );
 
     // Act & Assert
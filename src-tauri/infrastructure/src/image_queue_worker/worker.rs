@@ -7,17 +7,23 @@ use std::collections::{HashMap, HashSet};
 
 use domain::repository::manager::RepositoryManager;
 use domain::repository::save_image_queue::ImageSaveQueueRepository;
+use domain::repository::works::WorkRepository as _;
 use domain::repository::RepositoriesExt;
-use domain::save_image_queue::ImageSrcType;
+use domain::save_image_queue::{
+    ImagePreprocess, ImageSaveQueueRow, ImageSrcType, RetryDecision, RetryPolicy,
+};
 use domain::service::image_queue_event::ImageQueueWorkerEventHandler;
 use domain::service::save_path_resolver::SavePathResolver;
 use domain::windows::shell_link::ShellLink as _;
 use domain::windows::WindowsExt;
 
+use crate::downloader::HttpDownloader;
 use crate::icon::IconServiceImpl;
+use crate::thumbnail::{thumbnail_work_id, GeneratedThumbnail};
 
 use super::preprocess::run_preprocess;
 use super::resolver::resolve_source_with_shortcut_metas;
+use super::retry::{classify_failure, PermanentFailure};
 use super::types::SourceDecision;
 
 pub struct ImageQueueWorker<M, R, W>
@@ -30,6 +36,8 @@ where
     resolver: Arc<dyn SavePathResolver>,
     windows: Arc<W>,
     event_handler: Option<Arc<dyn ImageQueueWorkerEventHandler + Send + Sync>>,
+    retry_policy: RetryPolicy,
+    downloader: Arc<HttpDownloader>,
     _marker: PhantomData<R>,
 }
 
@@ -39,12 +47,21 @@ where
     R: RepositoriesExt + Send + Sync + 'static,
     W: WindowsExt + Send + Sync + 'static,
 {
-    pub fn new(manager: Arc<M>, resolver: Arc<dyn SavePathResolver>, windows: Arc<W>) -> Self {
+    /// URL ソースは `downloader` で取得する。アプリ全体で同じものを渡し、
+    /// ホストごとの同時接続数制限と HTTP キャッシュを共有する
+    pub fn new(
+        manager: Arc<M>,
+        resolver: Arc<dyn SavePathResolver>,
+        windows: Arc<W>,
+        downloader: Arc<HttpDownloader>,
+    ) -> Self {
         Self {
             manager,
+            downloader,
             resolver,
             windows,
             event_handler: None,
+            retry_policy: RetryPolicy::default(),
             _marker: PhantomData,
         }
     }
@@ -53,17 +70,76 @@ where
         manager: Arc<M>,
         resolver: Arc<dyn SavePathResolver>,
         windows: Arc<W>,
+        downloader: Arc<HttpDownloader>,
         event_handler: Arc<dyn ImageQueueWorkerEventHandler + Send + Sync>,
     ) -> Self {
         Self {
             manager,
+            downloader,
             resolver,
             windows,
             event_handler: Some(event_handler),
+            retry_policy: RetryPolicy::default(),
             _marker: PhantomData,
         }
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// 出力先が既にあれば処理済みとみなす。
+    /// ただし前回失敗した項目は既定アイコンで埋まっている可能性があるため上書きする
+    fn is_already_saved(item: &ImageSaveQueueRow) -> bool {
+        // サムネイルのバリアントは作った結果をここで記録するので、PNG があっても作り直す
+        item.last_error.is_none()
+            && !matches!(item.preprocess, ImagePreprocess::ThumbnailVariants)
+            && Path::new(&item.dst_path).exists()
+    }
+
+    /// 書き出したサムネイルのバリアントと支配色・知覚ハッシュを作品に記録する
+    async fn record_thumbnail(
+        manager: &M,
+        dst_path: &str,
+        generated: GeneratedThumbnail,
+    ) -> anyhow::Result<()> {
+        let work_id = thumbnail_work_id(dst_path)
+            .ok_or_else(|| anyhow::anyhow!("invalid thumbnail path: {}", dst_path))?;
+        manager
+            .run(|repos| {
+                Box::pin(async move {
+                    repos
+                        .work()
+                        .replace_work_thumbnail_variants(
+                            work_id,
+                            generated.variants,
+                            generated.placeholder_color,
+                            generated.perceptual_hash,
+                        )
+                        .await
+                })
+            })
+            .await
+    }
+
+    /// バックオフ待ちの項目が次に処理できるようになるまでの時間。待ちがなければ None
+    pub async fn next_retry_delay(&self) -> anyhow::Result<Option<std::time::Duration>> {
+        let next_attempt_at = self
+            .manager
+            .run(|repos| {
+                Box::pin(async move {
+                    let mut iq = repos.image_queue();
+                    iq.next_attempt_at().await
+                })
+            })
+            .await?;
+        Ok(next_attempt_at.map(|at| (at - chrono::Local::now()).to_std().unwrap_or_default()))
+    }
+
+    /// 今すぐ処理できる項目がなくなるまで処理する。
+    /// バックオフ待ちの項目は next_attempt_at を過ぎた後の呼び出しで処理される
+    /// （ImageQueueRunnerImpl が最も早い next_attempt_at に合わせて呼び直す）
     pub async fn drain_until_empty(&self) -> anyhow::Result<()> {
         if let Some(handler) = &self.event_handler {
             let h = Arc::clone(handler);
@@ -75,7 +151,7 @@ where
                 .run(|repos| {
                     Box::pin(async move {
                         let mut iq = repos.image_queue();
-                        iq.list(true, 50).await
+                        iq.list_due(50).await
                     })
                 })
                 .await?;
@@ -87,8 +163,7 @@ where
                 let set: HashSet<String> = items
                     .iter()
                     .filter(|it| {
-                        matches!(it.src_type, ImageSrcType::Shortcut)
-                            && !Path::new(&it.dst_path).exists()
+                        matches!(it.src_type, ImageSrcType::Shortcut) && !Self::is_already_saved(it)
                     })
                     .map(|it| it.src.clone())
                     .collect();
@@ -115,21 +190,24 @@ where
                     let manager = Arc::clone(&self.manager);
                     let resolver = Arc::clone(&self.resolver);
                     let windows = Arc::clone(&self.windows);
+                    let downloader = Arc::clone(&self.downloader);
                     let shortcut_metas = Arc::clone(&shortcut_metas);
                     let event_handler = self.event_handler.as_ref().map(Arc::clone);
+                    let retry_policy = self.retry_policy;
 
                     async move {
                         let result: anyhow::Result<()> = async {
                             if let Some(h) = &event_handler {
                                 let _ = h.on_item_started(&item).await;
                             }
-                            if Path::new(&item.dst_path).exists() {
+                            if Self::is_already_saved(&item) {
                                 return Ok(());
                             }
 
                             // 決定（ショートカットは事前メタを使用して追加の COM 呼び出しを避ける）
                             let decision = resolve_source_with_shortcut_metas(
                                 &*windows,
+                                &downloader,
                                 &*resolver,
                                 &item.src,
                                 item.src_type,
@@ -149,16 +227,20 @@ where
                                         "fallback: {}; wrote default icon. src={} src_type={:?}",
                                         reason, item.src, item.src_type
                                     );
-                                    return Err(anyhow::anyhow!(msg));
+                                    return Err(PermanentFailure(msg).into());
                                 }
                                 SourceDecision::Use(local) => {
                                     let src_path = local.path().to_string();
                                     let dst_path = item.dst_path.clone();
                                     let preprocess = item.preprocess;
-                                    tokio::task::spawn_blocking(move || {
+                                    let generated = tokio::task::spawn_blocking(move || {
                                         run_preprocess(&src_path, &dst_path, preprocess)
                                     })
                                     .await??;
+                                    if let Some(generated) = generated {
+                                        Self::record_thumbnail(&manager, &item.dst_path, generated)
+                                            .await?;
+                                    }
                                 }
                             }
 
@@ -186,18 +268,38 @@ where
                                 let failed_id = item.id.clone();
                                 let failed_id_value = failed_id.value;
                                 let msg = format!("failed id={} err={:#}", failed_id_value, e);
+                                let attempt = item.attempt_count + 1;
+                                let decision = retry_policy.decide(attempt, classify_failure(&e));
                                 let _ = manager
                                     .run(|repos| {
                                         let msg = msg.clone();
                                         Box::pin(async move {
                                             let mut iq = repos.image_queue();
-                                            let _ = iq.mark_failed(failed_id, &msg).await;
+                                            let _ = match decision {
+                                                RetryDecision::RetryAfter(delay) => {
+                                                    iq.mark_retry(failed_id, &msg, delay).await
+                                                }
+                                                RetryDecision::GiveUp => {
+                                                    iq.mark_failed(failed_id, &msg).await
+                                                }
+                                            };
                                             Ok::<(), anyhow::Error>(())
                                         })
                                     })
                                     .await;
                                 if let Some(h) = &event_handler {
-                                    let _ = h.on_item_failed(&item, &msg).await;
+                                    let event_msg = match decision {
+                                        RetryDecision::RetryAfter(delay) => format!(
+                                            "{} (attempt={} retry_in={}s)",
+                                            msg,
+                                            attempt,
+                                            delay.as_secs()
+                                        ),
+                                        RetryDecision::GiveUp => {
+                                            format!("{} (attempt={} gave up)", msg, attempt)
+                                        }
+                                    };
+                                    let _ = h.on_item_failed(&item, &event_msg).await;
                                 }
                             }
                         }
@@ -1,14 +1,20 @@
 pub mod app_signal_router;
+pub mod downloader;
 pub mod heuristic_duplicate_resolver;
 pub mod heuristic_metadata_extractor;
 pub mod icon;
 pub mod image_queue_worker;
 pub mod local_file_system;
+pub mod matcher_index_store;
 pub mod native_messaging;
+pub mod platform;
 pub mod pubsubimpl;
 pub mod save_path_resolver;
 pub mod sqliterepository;
 pub mod thumbnail;
+#[cfg(windows)]
 pub mod windowsimpl;
+#[cfg(not(windows))]
+pub mod wineimpl;
 pub mod work_linker;
 pub mod work_registration;
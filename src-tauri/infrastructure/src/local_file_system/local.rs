@@ -64,4 +64,14 @@ impl FileSystem for LocalFileSystem {
     fn stat(&self, path: &Path) -> anyhow::Result<std::fs::Metadata> {
         Ok(fs::metadata(path)?)
     }
+
+    fn list_files(&self, root: &Path, max_depth: usize) -> anyhow::Result<Vec<PathBuf>> {
+        Ok(walkdir::WalkDir::new(root)
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect())
+    }
 }
@@ -251,3 +251,24 @@ fn stat_テーブルテスト() {
         drop(tmp);
     }
 }
+
+#[test]
+fn list_files_指定した深さまでのファイルだけを返す() {
+    // Arrange
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path();
+    create_file(&root.join("game.exe"));
+    create_file(&root.join("data/scene.pck"));
+    create_file(&root.join("data/deep/nested/too_deep.txt"));
+    create_dir(&root.join("empty"));
+    let fs_impl = LocalFileSystem::default();
+
+    // Act
+    let files: HashSet<PathBuf> = fs_impl.list_files(root, 2).unwrap().into_iter().collect();
+
+    // Assert
+    let expected: HashSet<PathBuf> = [root.join("game.exe"), root.join("data/scene.pck")]
+        .into_iter()
+        .collect();
+    assert_eq!(files, expected);
+}
@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::PathBuf;
+
+use domain::game_matcher::{MatcherIndex, MatcherIndexStore, MATCHER_INDEX_VERSION};
+
+/// 構築済みの Matcher インデックスを 1 ファイルに保存する
+pub struct FileMatcherIndexStore {
+    path: PathBuf,
+}
+
+impl FileMatcherIndexStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl MatcherIndexStore for FileMatcherIndexStore {
+    fn load(&self, content_hash: u64) -> anyhow::Result<Option<MatcherIndex>> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        // 本体をデコードする前にヘッダだけで鮮度を判定する
+        let (version, hash) = MatcherIndex::peek_header(&bytes)?;
+        if version != MATCHER_INDEX_VERSION || hash != content_hash {
+            log::info!(
+                "matcher index is stale (version: {}, hash: {:016x}), rebuilding",
+                version,
+                hash
+            );
+            return Ok(None);
+        }
+        Ok(Some(MatcherIndex::decode(&bytes)?))
+    }
+
+    fn save(&self, index: &MatcherIndex) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // 書き込み途中のファイルを読まないよう、一時ファイルに書いてから置き換える
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, index.encode())?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
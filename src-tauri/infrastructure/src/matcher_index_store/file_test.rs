@@ -0,0 +1,55 @@
+use domain::all_game_cache::AllGameCacheOne;
+use domain::game_matcher::{content_hash, MatcherIndex, MatcherIndexStore};
+
+use super::FileMatcherIndexStore;
+
+fn cache() -> Vec<AllGameCacheOne> {
+    vec![
+        AllGameCacheOne::new(1, "さくらのうた".to_string()),
+        AllGameCacheOne::new(2, "Another Game".to_string()),
+    ]
+}
+
+#[test]
+fn 保存したインデックスを同じハッシュで読み込める() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileMatcherIndexStore::new(dir.path().join("matcher").join("index.bin"));
+    let index = MatcherIndex::build(&cache());
+
+    store.save(&index).unwrap();
+    let loaded = store.load(content_hash(&cache())).unwrap().unwrap();
+
+    assert_eq!(loaded.content_hash, index.content_hash);
+    assert_eq!(loaded.normalized, index.normalized);
+    assert_eq!(loaded.ngram.postings, index.ngram.postings);
+}
+
+#[test]
+fn ハッシュが異なればnoneを返す() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileMatcherIndexStore::new(dir.path().join("index.bin"));
+    store.save(&MatcherIndex::build(&cache())).unwrap();
+
+    let mut changed = cache();
+    changed.push(AllGameCacheOne::new(3, "新作".to_string()));
+
+    assert!(store.load(content_hash(&changed)).unwrap().is_none());
+}
+
+#[test]
+fn ファイルがなければnoneを返す() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileMatcherIndexStore::new(dir.path().join("missing.bin"));
+
+    assert!(store.load(content_hash(&cache())).unwrap().is_none());
+}
+
+#[test]
+fn 壊れたファイルはエラーになる() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("index.bin");
+    std::fs::write(&path, b"LG").unwrap();
+    let store = FileMatcherIndexStore::new(&path);
+
+    assert!(store.load(content_hash(&cache())).is_err());
+}
@@ -0,0 +1,5 @@
+mod file;
+#[cfg(test)]
+mod file_test;
+
+pub use file::FileMatcherIndexStore;
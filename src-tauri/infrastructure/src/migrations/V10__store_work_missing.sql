@@ -0,0 +1,15 @@
+-- ライブラリ全体の同期で見えなくなったストアの紐付けを記録する
+ALTER TABLE dmm_works ADD COLUMN last_seen_at DATETIME;
+ALTER TABLE dmm_works ADD COLUMN missing_since DATETIME;
+ALTER TABLE dlsite_works ADD COLUMN last_seen_at DATETIME;
+ALTER TABLE dlsite_works ADD COLUMN missing_since DATETIME;
+
+CREATE INDEX IF NOT EXISTS idx_dmm_works_missing_since
+ON dmm_works(missing_since)
+WHERE missing_since IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_dlsite_works_missing_since
+ON dlsite_works(missing_since)
+WHERE missing_since IS NOT NULL;
+
+-- 分割同期のチャンクにも既存の紐付けの件数を残す
+ALTER TABLE sync_session_chunks ADD COLUMN unchanged_count INTEGER NOT NULL DEFAULT 0;
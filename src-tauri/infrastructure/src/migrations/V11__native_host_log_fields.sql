@@ -0,0 +1,9 @@
+-- ホストログに絞り込みや集計に使う列を足す
+ALTER TABLE native_messaging_host_logs ADD COLUMN request_id TEXT;
+ALTER TABLE native_messaging_host_logs ADD COLUMN duration_ms INTEGER;
+ALTER TABLE native_messaging_host_logs ADD COLUMN item_count INTEGER;
+ALTER TABLE native_messaging_host_logs ADD COLUMN error_count INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_native_host_logs_request_id
+ON native_messaging_host_logs(request_id)
+WHERE request_id IS NOT NULL;
@@ -0,0 +1,10 @@
+-- リモート共有へ最後に送った作品の内容。差分同期で変わった作品と消えた作品だけを送るために使う
+-- 手元から消えた作品の墓標を送るため、works への外部キーは張らない
+CREATE TABLE IF NOT EXISTS remote_share_work_states (
+    device_id TEXT NOT NULL,
+    work_id TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    dirty INTEGER NOT NULL DEFAULT 0,
+    synced_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(device_id, work_id)
+);
@@ -0,0 +1,25 @@
+-- 共有ページからの起動要求の扱い。disabled / allowlist / ask_first
+ALTER TABLE app_settings ADD COLUMN remote_launch_policy TEXT NOT NULL DEFAULT 'ask_first';
+ALTER TABLE app_settings ADD COLUMN remote_launch_confirm_timeout_seconds INTEGER NOT NULL DEFAULT 30;
+
+-- allowlist のときに起動してよい作品
+CREATE TABLE IF NOT EXISTS remote_launch_allowed_works (
+    work_id TEXT PRIMARY KEY REFERENCES works(id) ON DELETE CASCADE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- 起動要求とその結果の記録。作品が消えても残すので works への外部キーは張らない
+CREATE TABLE IF NOT EXISTS remote_launch_audit_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    work_id TEXT NOT NULL,
+    work_title TEXT,
+    policy TEXT NOT NULL,
+    decision TEXT NOT NULL,
+    status TEXT NOT NULL,
+    error_message TEXT,
+    requested_at DATETIME NOT NULL,
+    finished_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_remote_launch_audit_logs_requested_at
+ON remote_launch_audit_logs(requested_at);
@@ -0,0 +1,22 @@
+-- 作品ごとの起動設定。env_json は [{"key": "...", "value": "..."}] の配列
+CREATE TABLE IF NOT EXISTS work_launch_profiles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    work_id TEXT NOT NULL REFERENCES works(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    arguments TEXT,
+    working_dir TEXT,
+    env_json TEXT NOT NULL DEFAULT '[]',
+    run_as_admin INTEGER NOT NULL DEFAULT 0,
+    wrapper_path TEXT,
+    wrapper_arguments TEXT,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_launch_profiles_work_id
+ON work_launch_profiles(work_id);
+
+-- 既定のプロファイルは作品ごとに高々1つ
+CREATE UNIQUE INDEX IF NOT EXISTS idx_work_launch_profiles_default
+ON work_launch_profiles(work_id) WHERE is_default = 1;
@@ -0,0 +1,10 @@
+-- Windows 以外で起動するときの作品ごとの Wine の設定。env_json は起動設定と同じ形式
+CREATE TABLE IF NOT EXISTS work_wine_configs (
+    work_id TEXT PRIMARY KEY REFERENCES works(id) ON DELETE CASCADE,
+    runner TEXT NOT NULL DEFAULT 'wine',
+    runner_path TEXT,
+    prefix TEXT,
+    env_json TEXT NOT NULL DEFAULT '[]',
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,2 @@
+-- インストール時にファイル構成から見分けたゲームエンジン（GameEngine::as_str）
+ALTER TABLE work_installs ADD COLUMN engine TEXT;
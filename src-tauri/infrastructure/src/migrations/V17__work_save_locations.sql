@@ -0,0 +1,11 @@
+-- 作品ごとのセーブデータの場所。path は {install} や %VAR% を含んだまま保存する
+CREATE TABLE IF NOT EXISTS work_save_locations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    work_id TEXT NOT NULL REFERENCES works(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    source TEXT NOT NULL DEFAULT 'manual',
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(work_id, path)
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_save_locations_work_id ON work_save_locations(work_id);
@@ -0,0 +1,34 @@
+-- 作品ごとの遊んだ状況。行がなければ未プレイ
+CREATE TABLE IF NOT EXISTS work_play_states (
+    work_id TEXT PRIMARY KEY NOT NULL REFERENCES works(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'unplayed',
+    score INTEGER,
+    started_on DATE,
+    finished_on DATE,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_play_states_status ON work_play_states(status);
+
+-- ステータスを変えた履歴
+CREATE TABLE IF NOT EXISTS work_play_status_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    work_id TEXT NOT NULL REFERENCES works(id) ON DELETE CASCADE,
+    status TEXT NOT NULL,
+    changed_at DATETIME NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_play_status_history_work_id ON work_play_status_history(work_id, changed_at);
+
+-- ヒロインやルートごとの攻略のチェック
+CREATE TABLE IF NOT EXISTS work_routes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    work_id TEXT NOT NULL REFERENCES works(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    completed_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(work_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_routes_work_id ON work_routes(work_id);
@@ -0,0 +1,9 @@
+-- 作品一覧の絞り込みと並び替えに使う列
+CREATE INDEX IF NOT EXISTS idx_erogamescape_information_gamename_ruby ON erogamescape_information(gamename_ruby);
+CREATE INDEX IF NOT EXISTS idx_erogamescape_information_sellday ON erogamescape_information(sellday);
+CREATE INDEX IF NOT EXISTS idx_erogamescape_information_brandname ON erogamescape_information(brandname);
+CREATE INDEX IF NOT EXISTS idx_erogamescape_information_is_nukige ON erogamescape_information(is_nukige);
+CREATE INDEX IF NOT EXISTS idx_works_created_at ON works(created_at);
+CREATE INDEX IF NOT EXISTS idx_work_plays_last_play_at ON work_plays(last_play_at);
+CREATE INDEX IF NOT EXISTS idx_work_installs_install_at ON work_installs(install_at);
+CREATE INDEX IF NOT EXISTS idx_work_play_states_score ON work_play_states(score);
@@ -0,0 +1,2 @@
+-- 重複作品の判定に使うサムネイルの知覚ハッシュ（dHash を 16 桁の 16 進数で）
+ALTER TABLE work_thumbnails ADD COLUMN perceptual_hash TEXT;
@@ -0,0 +1,11 @@
+-- 利用者が直した作品の情報。NULL の項目は同期や取り込みで得た情報を使う
+-- 作品の登録や同期では書き換えない
+CREATE TABLE IF NOT EXISTS work_overrides (
+    work_id TEXT PRIMARY KEY NOT NULL REFERENCES works(id) ON DELETE CASCADE,
+    title TEXT,
+    gamename_ruby TEXT,
+    brandname TEXT,
+    sellday TEXT, -- YYYY-MM-DD
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
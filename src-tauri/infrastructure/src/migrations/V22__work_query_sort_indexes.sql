@@ -0,0 +1,13 @@
+-- 並び替えの値と作品 ID の組で索引を張り、ページごとに索引をたどって読めるようにする。
+-- 直した値と組み合わせて比べる列や、外部結合した表の列の索引は使われないので消す
+DROP INDEX IF EXISTS idx_erogamescape_information_gamename_ruby;
+DROP INDEX IF EXISTS idx_erogamescape_information_sellday;
+DROP INDEX IF EXISTS idx_erogamescape_information_brandname;
+DROP INDEX IF EXISTS idx_erogamescape_information_is_nukige;
+DROP INDEX IF EXISTS idx_work_play_states_score;
+DROP INDEX IF EXISTS idx_works_created_at;
+DROP INDEX IF EXISTS idx_work_plays_last_play_at;
+DROP INDEX IF EXISTS idx_work_installs_install_at;
+CREATE INDEX IF NOT EXISTS idx_works_created_at_id ON works(created_at, id);
+CREATE INDEX IF NOT EXISTS idx_work_plays_last_play_at_work_id ON work_plays(last_play_at, work_id);
+CREATE INDEX IF NOT EXISTS idx_work_installs_install_at_work_id ON work_installs(install_at, work_id);
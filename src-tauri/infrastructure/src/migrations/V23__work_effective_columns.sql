@@ -0,0 +1,29 @@
+-- 直した値を批評空間の値より優先した、絞り込みと並び替えに使う値。
+-- 結合した表をまたぐ式には索引が張れないので works に持ち、作品の登録と直した値・批評空間の情報を書くたびに更新する
+ALTER TABLE works ADD COLUMN effective_title_ruby TEXT NOT NULL DEFAULT '';
+ALTER TABLE works ADD COLUMN effective_brandname TEXT;
+ALTER TABLE works ADD COLUMN effective_sellday TEXT; -- YYYY-MM-DD。空文字は NULL にする
+ALTER TABLE works ADD COLUMN effective_is_nukige INTEGER NOT NULL DEFAULT 0;
+
+UPDATE works SET
+    effective_title_ruby = v.title_ruby,
+    effective_brandname = v.brandname,
+    effective_sellday = v.sellday,
+    effective_is_nukige = v.is_nukige
+FROM (
+    SELECT w.id AS work_id,
+        COALESCE(NULLIF(wo.gamename_ruby, ''), NULLIF(ei.gamename_ruby, ''), wo.title, w.title) AS title_ruby,
+        COALESCE(wo.brandname, ei.brandname) AS brandname,
+        NULLIF(COALESCE(wo.sellday, ei.sellday), '') AS sellday,
+        COALESCE(ei.is_nukige, 0) AS is_nukige
+    FROM works w
+    LEFT JOIN work_erogamescape_map wem ON wem.work_id = w.id
+    LEFT JOIN erogamescape_information ei ON ei.id = wem.erogamescape_id
+    LEFT JOIN work_overrides wo ON wo.work_id = w.id
+) AS v
+WHERE works.id = v.work_id;
+
+CREATE INDEX IF NOT EXISTS idx_works_effective_title_ruby_id ON works(effective_title_ruby, id);
+CREATE INDEX IF NOT EXISTS idx_works_effective_sellday_id ON works(effective_sellday, id);
+CREATE INDEX IF NOT EXISTS idx_works_effective_brandname ON works(effective_brandname);
+CREATE INDEX IF NOT EXISTS idx_works_effective_is_nukige ON works(effective_is_nukige);
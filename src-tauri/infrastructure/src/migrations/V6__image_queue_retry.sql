@@ -0,0 +1,12 @@
+ALTER TABLE save_image_queue ADD COLUMN attempt_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE save_image_queue ADD COLUMN next_attempt_at DATETIME;
+ALTER TABLE save_image_queue ADD COLUMN failed_at DATETIME; -- リトライ打ち切り（デッドレター）
+
+-- これまでの失敗行（finished_at と last_error が両方ある）はデッドレター扱いにする
+UPDATE save_image_queue
+SET failed_at = finished_at, attempt_count = 1
+WHERE finished_at IS NOT NULL AND last_error IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_save_image_queue_failed
+ON save_image_queue(failed_at)
+WHERE failed_at IS NOT NULL;
@@ -0,0 +1,17 @@
+-- 作品ごとに生成済みのサムネイル（幅・形式ごと）
+CREATE TABLE IF NOT EXISTS work_thumbnail_variants (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    work_id TEXT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    format TEXT NOT NULL, -- 'png' | 'webp'
+    path TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(work_id, width, format),
+    FOREIGN KEY(work_id) REFERENCES works(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_thumbnail_variants_work_id ON work_thumbnail_variants(work_id);
+
+-- 画像の読み込み前に表示する支配色（#rrggbb）
+ALTER TABLE work_thumbnails ADD COLUMN placeholder_color TEXT;
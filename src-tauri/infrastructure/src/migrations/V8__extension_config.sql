@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS extension_config (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    auto_sync INTEGER NOT NULL,
+    -- JSON 配列
+    allowed_domains TEXT NOT NULL,
+    sync_interval_minutes INTEGER NOT NULL,
+    debug_mode INTEGER NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
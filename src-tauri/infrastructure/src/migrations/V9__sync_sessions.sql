@@ -0,0 +1,26 @@
+-- 拡張機能からの分割同期。ホストが再起動しても続きから受け取れるよう受信済みチャンクを残す
+CREATE TABLE IF NOT EXISTS sync_sessions (
+    id TEXT PRIMARY KEY,
+    store TEXT NOT NULL, -- "dmm" | "dlsite"
+    extension_id TEXT NOT NULL,
+    total_chunks INTEGER NOT NULL,
+    committed_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS sync_session_chunks (
+    session_id TEXT NOT NULL,
+    chunk_index INTEGER NOT NULL,
+    game_count INTEGER NOT NULL,
+    success_count INTEGER NOT NULL,
+    new_count INTEGER NOT NULL,
+    error_count INTEGER NOT NULL,
+    -- JSON 配列
+    errors TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(session_id, chunk_index),
+    FOREIGN KEY(session_id) REFERENCES sync_sessions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_sessions_updated_at ON sync_sessions(updated_at);
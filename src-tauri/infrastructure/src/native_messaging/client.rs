@@ -7,7 +7,12 @@ use std::{
 };
 use tokio::time::timeout;
 
-use domain::extension::{ExtensionConfig, NativeMessagingHostClient, SyncStatus};
+use domain::extension::{
+    ExtensionConfig, ExtensionConnectionStatus, NativeMessagingHostClient, SyncStatus,
+};
+
+/// 同梱の Native Messaging Host と話すプロトコルバージョン
+const NATIVE_PROTOCOL_VERSION: u32 = 2;
 
 pub struct NativeMessagingHostClientImpl {
     native_host_path: PathBuf,
@@ -84,54 +89,63 @@ impl NativeMessagingHostClientImpl {
         let value: serde_json::Value = serde_json::from_slice(&stdout[4..4 + response_length])?;
         Ok(value)
     }
+}
 
-    /// SyncStatusをドメインモデルに変換（Buf JSON）
-    fn convert_sync_status(&self, v: &serde_json::Value) -> SyncStatus {
-        let last_sync = None;
-        SyncStatus {
-            last_sync,
-            total_synced: v.get("totalSynced").and_then(|x| x.as_u64()).unwrap_or(0) as u32,
-            connected_extensions: v
-                .get("connectedExtensions")
-                .and_then(|x| x.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|e| e.as_str().map(|s| s.to_string()))
-                        .collect()
-                })
-                .unwrap_or_default(),
-            is_running: v
-                .get("isRunning")
-                .and_then(|x| x.as_bool())
-                .unwrap_or(false),
-            connection_status: v
-                .get("connectionStatus")
-                .and_then(|x| x.as_i64())
-                .unwrap_or_default() as i32,
-            error_message: v
-                .get("errorMessage")
-                .and_then(|x| x.as_str())
-                .unwrap_or("")
-                .to_string(),
-        }
+/// StatusResult の値（SyncStatusTs）をドメインモデルに変換
+fn convert_sync_status(v: &serde_json::Value) -> SyncStatus {
+    let last_sync = v
+        .get("last_sync")
+        .filter(|x| !x.is_null())
+        .map(|ts| pbjson_types::Timestamp {
+            seconds: ts.get("seconds").and_then(|x| x.as_i64()).unwrap_or(0),
+            nanos: ts.get("nanos").and_then(|x| x.as_i64()).unwrap_or(0) as i32,
+        });
+    let connection_status = match v.get("connection_status").and_then(|x| x.as_str()) {
+        Some("connected") => ExtensionConnectionStatus::Connected,
+        Some(_) => ExtensionConnectionStatus::UnknownError,
+        None => ExtensionConnectionStatus::Unspecified,
+    };
+    SyncStatus {
+        last_sync,
+        total_synced: v.get("total_synced").and_then(|x| x.as_u64()).unwrap_or(0) as u32,
+        connected_extensions: v
+            .get("extension_ids")
+            .and_then(|x| x.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|e| e.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        is_running: v
+            .get("is_running")
+            .and_then(|x| x.as_bool())
+            .unwrap_or(false),
+        connection_status: connection_status as i32,
+        error_message: v
+            .get("error_message")
+            .and_then(|x| x.as_str())
+            .unwrap_or("")
+            .to_string(),
     }
+}
 
-    /// ドメインモデルを Buf JSON の設定に変換
-    fn convert_extension_config_json(&self, config: &ExtensionConfig) -> serde_json::Value {
-        json!({
-            "autoSync": config.auto_sync,
-            "allowedDomains": config.allowed_domains,
-            "syncIntervalMinutes": config.sync_interval_minutes,
-            "debugMode": config.debug_mode,
-        })
-    }
+/// ドメインモデルを SetConfig の値（ExtensionConfigTs）に変換
+fn convert_extension_config_json(config: &ExtensionConfig) -> serde_json::Value {
+    json!({
+        "auto_sync": config.auto_sync,
+        "allowed_domains": config.allowed_domains,
+        "sync_interval_minutes": config.sync_interval_minutes,
+        "debug_mode": config.debug_mode,
+    })
 }
 
 impl NativeMessagingHostClient for NativeMessagingHostClientImpl {
     async fn health_check(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
         let payload = json!({
-            "requestId": "health-check",
-            "message": { "case": "healthCheck", "value": {} },
+            "protocol_version": NATIVE_PROTOCOL_VERSION,
+            "request_id": "health-check",
+            "message": { "case": "HealthCheck", "value": {} },
         });
 
         match timeout(
@@ -153,8 +167,9 @@ impl NativeMessagingHostClient for NativeMessagingHostClientImpl {
         &self,
     ) -> Result<SyncStatus, Box<dyn std::error::Error + Send + Sync>> {
         let payload = json!({
-            "requestId": "get-status",
-            "message": { "case": "getStatus", "value": {} },
+            "protocol_version": NATIVE_PROTOCOL_VERSION,
+            "request_id": "get-status",
+            "message": { "case": "GetStatus", "value": {} },
         });
 
         match timeout(
@@ -171,10 +186,10 @@ impl NativeMessagingHostClient for NativeMessagingHostClientImpl {
                 {
                     let status = response
                         .get("response")
+                        .filter(|r| r.get("case").and_then(|c| c.as_str()) == Some("StatusResult"))
                         .and_then(|r| r.get("value"))
-                        .and_then(|v| v.get("status"));
-                    let status = status.unwrap_or(&serde_json::Value::Null);
-                    Ok(self.convert_sync_status(status))
+                        .ok_or("Unexpected status response")?;
+                    Ok(convert_sync_status(status))
                 } else {
                     let err = response.get("error").and_then(|x| x.as_str()).unwrap_or("");
                     Err(format!("Failed to get status: {}", err).into())
@@ -189,10 +204,11 @@ impl NativeMessagingHostClient for NativeMessagingHostClientImpl {
         &self,
         config: &ExtensionConfig,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        let cfg = self.convert_extension_config_json(config);
+        let cfg = convert_extension_config_json(config);
         let payload = json!({
-            "requestId": "set-config",
-            "message": { "case": "setConfig", "value": cfg },
+            "protocol_version": NATIVE_PROTOCOL_VERSION,
+            "request_id": "set-config",
+            "message": { "case": "SetConfig", "value": cfg },
         });
 
         match timeout(
@@ -224,3 +240,68 @@ impl NativeMessagingHostClient for NativeMessagingHostClientImpl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_sync_status_ホストの応答を変換する() {
+        let status = convert_sync_status(&json!({
+            "last_sync": { "seconds": 1700000000, "nanos": 5 },
+            "total_synced": 12,
+            "connected_extensions": 2,
+            "extension_ids": ["ext-a", "ext-b"],
+            "is_running": true,
+            "connection_status": "connected",
+            "error_message": "",
+        }));
+        assert_eq!(
+            status.last_sync,
+            Some(pbjson_types::Timestamp {
+                seconds: 1700000000,
+                nanos: 5
+            })
+        );
+        assert_eq!(status.total_synced, 12);
+        assert_eq!(status.connected_extensions, vec!["ext-a", "ext-b"]);
+        assert!(status.is_running);
+        assert_eq!(
+            status.connection_status,
+            ExtensionConnectionStatus::Connected as i32
+        );
+    }
+
+    #[test]
+    fn convert_sync_status_同期履歴なし() {
+        let status = convert_sync_status(&json!({
+            "last_sync": null,
+            "total_synced": 0,
+            "connected_extensions": 0,
+            "is_running": true,
+            "connection_status": "connected",
+            "error_message": "",
+        }));
+        assert!(status.last_sync.is_none());
+        assert!(status.connected_extensions.is_empty());
+    }
+
+    #[test]
+    fn convert_extension_config_json_snake_caseで送る() {
+        let value = convert_extension_config_json(&ExtensionConfig {
+            auto_sync: true,
+            allowed_domains: vec!["www.dlsite.com".into()],
+            sync_interval_minutes: 30,
+            debug_mode: false,
+        });
+        assert_eq!(
+            value,
+            json!({
+                "auto_sync": true,
+                "allowed_domains": ["www.dlsite.com"],
+                "sync_interval_minutes": 30,
+                "debug_mode": false,
+            })
+        );
+    }
+}
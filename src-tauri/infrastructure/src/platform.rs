@@ -0,0 +1,5 @@
+//! 起動やスクリーンショットのバックエンドはビルド先で選ぶ
+#[cfg(windows)]
+pub use crate::windowsimpl::windows::Windows as Platform;
+#[cfg(not(windows))]
+pub use crate::wineimpl::wine::Wine as Platform;
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use domain::pubsub::PubSubEvent;
+
+/// フロントエンドへ送るイベント名とペイロードに分ける
+pub(crate) fn into_emit_args(event: PubSubEvent) -> Result<(&'static str, serde_json::Value)> {
+    fn args<T: Serialize>(
+        name: &'static str,
+        payload: T,
+    ) -> Result<(&'static str, serde_json::Value)> {
+        Ok((name, serde_json::to_value(payload)?))
+    }
+    match event {
+        PubSubEvent::Progress(payload) => args("progress", payload),
+        PubSubEvent::ProgressLive(payload) => args("progresslive", payload),
+        PubSubEvent::ScanCandidateDiscovered(payload) => args("scanCandidateDiscovered", payload),
+        PubSubEvent::ScanExploreFinished(payload) => args("scanExploreFinished", payload),
+        PubSubEvent::ScanProgress(payload) => args("scanProgress", payload),
+        PubSubEvent::ScanLog(payload) => args("scanLog", payload),
+        PubSubEvent::ScanSummary(payload) => args("scanSummary", payload),
+        PubSubEvent::ScanPhaseTiming(payload) => args("scanPhaseTiming", payload),
+        PubSubEvent::ScanEnrichResult(payload) => args("scanEnrichResult", payload),
+        PubSubEvent::ScanDedup(payload) => args("scanDedup", payload),
+        PubSubEvent::ExtensionConnectionStatus(payload) => {
+            args("extension-connection-status", payload)
+        }
+        PubSubEvent::ImageQueueWorkerStarted(payload) => args("imageQueueWorkerStarted", payload),
+        PubSubEvent::ImageQueueWorkerFinished(payload) => args("imageQueueWorkerFinished", payload),
+        PubSubEvent::ImageQueueItemStarted(payload) => args("imageQueueItemStarted", payload),
+        PubSubEvent::ImageQueueItemSucceeded(payload) => args("imageQueueItemSucceeded", payload),
+        PubSubEvent::ImageQueueItemFailed(payload) => args("imageQueueItemFailed", payload),
+        PubSubEvent::AppSignal(payload) => args("appSignal", payload),
+        PubSubEvent::AppSignalShowMessage(payload) => args("appSignal:showMessage", payload),
+        PubSubEvent::AppSignalShowErrorMessage(payload) => {
+            args("appSignal:showErrorMessage", payload)
+        }
+        PubSubEvent::AppSignalRefetchWork(payload) => args("appSignal:refetchWork", payload),
+        PubSubEvent::AppSignalRefetchWorks(payload) => args("appSignal:refetchWorks", payload),
+        PubSubEvent::RemoteLaunchConfirmationRequested(payload) => {
+            args("remoteLaunchConfirmationRequested", payload)
+        }
+        PubSubEvent::RemoteLaunchConfirmationClosed(payload) => {
+            args("remoteLaunchConfirmationClosed", payload)
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use chrono::Utc;
+use domain::pubsub::*;
+
+use super::event::into_emit_args;
+
+fn signal() -> AppSignalPayload {
+    AppSignalPayload {
+        source: AppSignalSourcePayload::NativeMessagingHost,
+        event: AppSignalEventPayload::RefetchWorks,
+        issued_at: Utc::now(),
+    }
+}
+
+fn all_events() -> Vec<PubSubEvent> {
+    let item = ImageQueueItemPayload::new("id".into(), "src".into(), 0, "dst".into());
+    vec![
+        PubSubEvent::Progress(ProgressPayload::new("m".into())),
+        PubSubEvent::ProgressLive(ProgressLivePayload::new(Some(1))),
+        PubSubEvent::ScanCandidateDiscovered(ScanCandidateDiscoveredPayload::new(1, "p".into())),
+        PubSubEvent::ScanExploreFinished(ScanExploreFinishedPayload::new(1)),
+        PubSubEvent::ScanProgress(ScanProgressPayload::new("phase".into(), 1, 2, 0, None)),
+        PubSubEvent::ScanLog(ScanLogPayload::new("info".into(), "m".into())),
+        PubSubEvent::ScanSummary(ScanSummaryPayload::new(1, 2, 3, 4, 5, 6)),
+        PubSubEvent::ScanPhaseTiming(ScanPhaseTimingPayload::new("phase".into(), 1)),
+        PubSubEvent::ScanEnrichResult(EnrichResultPayload::new(
+            "resolved".into(),
+            "p".into(),
+            None,
+            None,
+            None,
+        )),
+        PubSubEvent::ScanDedup(DedupResultPayload::new(1)),
+        PubSubEvent::ExtensionConnectionStatus(ExtensionConnectionPayload {
+            connection_status: "connected".into(),
+            is_running: true,
+            error_message: None,
+            timestamp: Utc::now(),
+        }),
+        PubSubEvent::ImageQueueWorkerStarted(ImageQueueWorkerStatusPayload::new(
+            "started".into(),
+            None,
+        )),
+        PubSubEvent::ImageQueueWorkerFinished(ImageQueueWorkerStatusPayload::new(
+            "finished".into(),
+            None,
+        )),
+        PubSubEvent::ImageQueueItemStarted(item.clone()),
+        PubSubEvent::ImageQueueItemSucceeded(item),
+        PubSubEvent::ImageQueueItemFailed(ImageQueueItemErrorPayload::new("id".into(), "m".into())),
+        PubSubEvent::AppSignal(signal()),
+        PubSubEvent::AppSignalShowMessage(signal()),
+        PubSubEvent::AppSignalShowErrorMessage(signal()),
+        PubSubEvent::AppSignalRefetchWork(signal()),
+        PubSubEvent::AppSignalRefetchWorks(signal()),
+        PubSubEvent::RemoteLaunchConfirmationRequested(RemoteLaunchConfirmationPayload::new(
+            "req".into(),
+            "work".into(),
+            None,
+            30,
+        )),
+        PubSubEvent::RemoteLaunchConfirmationClosed(RemoteLaunchConfirmationClosedPayload::new(
+            "req".into(),
+            "approved".into(),
+        )),
+    ]
+}
+
+/// 列挙子を足したらここでコンパイルが通らなくなるので、`all_events` にも足す
+fn variant_index(event: &PubSubEvent) -> usize {
+    match event {
+        PubSubEvent::Progress(_) => 0,
+        PubSubEvent::ProgressLive(_) => 1,
+        PubSubEvent::ScanCandidateDiscovered(_) => 2,
+        PubSubEvent::ScanExploreFinished(_) => 3,
+        PubSubEvent::ScanProgress(_) => 4,
+        PubSubEvent::ScanLog(_) => 5,
+        PubSubEvent::ScanSummary(_) => 6,
+        PubSubEvent::ScanPhaseTiming(_) => 7,
+        PubSubEvent::ScanEnrichResult(_) => 8,
+        PubSubEvent::ScanDedup(_) => 9,
+        PubSubEvent::ExtensionConnectionStatus(_) => 10,
+        PubSubEvent::ImageQueueWorkerStarted(_) => 11,
+        PubSubEvent::ImageQueueWorkerFinished(_) => 12,
+        PubSubEvent::ImageQueueItemStarted(_) => 13,
+        PubSubEvent::ImageQueueItemSucceeded(_) => 14,
+        PubSubEvent::ImageQueueItemFailed(_) => 15,
+        PubSubEvent::AppSignal(_) => 16,
+        PubSubEvent::AppSignalShowMessage(_) => 17,
+        PubSubEvent::AppSignalShowErrorMessage(_) => 18,
+        PubSubEvent::AppSignalRefetchWork(_) => 19,
+        PubSubEvent::AppSignalRefetchWorks(_) => 20,
+        PubSubEvent::RemoteLaunchConfirmationRequested(_) => 21,
+        PubSubEvent::RemoteLaunchConfirmationClosed(_) => 22,
+    }
+}
+
+#[test]
+fn into_emit_args_すべてのイベントをシリアライズ時と同じ名前で送る() {
+    let events = all_events();
+    let mut indexes: Vec<usize> = events.iter().map(variant_index).collect();
+    indexes.sort();
+    indexes.dedup();
+    assert_eq!(indexes, (0..=22).collect::<Vec<_>>());
+
+    for event in events {
+        let serialized = serde_json::to_value(&event).unwrap();
+        let expected_name = event.event_name();
+
+        let (name, payload) = into_emit_args(event).unwrap();
+
+        assert_eq!(name, expected_name);
+        assert_eq!(serialized["type"], name);
+        assert_eq!(serialized["payload"], payload);
+    }
+}
@@ -1 +1,4 @@
+mod event;
+#[cfg(test)]
+mod event_test;
 pub mod pubsub;
@@ -2,6 +2,7 @@ use domain::erogamescape::NewErogamescapeInformation;
 use domain::repository::erogamescape::ErogamescapeRepository;
 
 use crate::sqliterepository::sqliterepository::RepositoryImpl;
+use crate::sqliterepository::work_query::{refresh_effective_columns, EffectiveScope};
 
 impl ErogamescapeRepository for RepositoryImpl<domain::erogamescape::ErogamescapeInformation> {
     async fn upsert_information(
@@ -31,8 +32,10 @@ impl ErogamescapeRepository for RepositoryImpl<domain::erogamescape::Erogamescap
                     .bind(is_nukige)
                     .bind(req.brandname)
                     .bind(req.brandname_ruby)
-                    .execute(conn)
+                    .execute(&mut **conn)
                     .await?;
+                    refresh_effective_columns(conn, EffectiveScope::Erogamescape(req.erogamescape_id))
+                        .await?;
                     Ok::<(), anyhow::Error>(())
                 })
             })
@@ -0,0 +1,71 @@
+use crate::sqliterepository::{
+    models::extension_config::ExtensionConfigTable, sqliterepository::RepositoryImpl,
+};
+use domain::{extension::ExtensionConfig, repository::extension_config::ExtensionConfigRepository};
+
+impl ExtensionConfigRepository for RepositoryImpl<ExtensionConfig> {
+    async fn get(&mut self) -> anyhow::Result<Option<ExtensionConfig>> {
+        let row: Option<ExtensionConfigTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let row = sqlx::query_as(
+                        r#"SELECT
+                            id,
+                            auto_sync,
+                            allowed_domains,
+                            sync_interval_minutes,
+                            debug_mode,
+                            created_at,
+                            updated_at
+                        FROM extension_config
+                        WHERE id = 1
+                        LIMIT 1"#,
+                    )
+                    .fetch_optional(conn)
+                    .await?;
+                    Ok(row)
+                })
+            })
+            .await?;
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    async fn upsert(&mut self, config: &ExtensionConfig) -> anyhow::Result<()> {
+        let auto_sync = config.auto_sync as i64;
+        let allowed_domains = serde_json::to_string(&config.allowed_domains)?;
+        let sync_interval_minutes = config.sync_interval_minutes as i64;
+        let debug_mode = config.debug_mode as i64;
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO extension_config (
+                            id,
+                            auto_sync,
+                            allowed_domains,
+                            sync_interval_minutes,
+                            debug_mode
+                        )
+                        VALUES (1, ?, ?, ?, ?)
+                        ON CONFLICT(id) DO UPDATE SET
+                            auto_sync = excluded.auto_sync,
+                            allowed_domains = excluded.allowed_domains,
+                            sync_interval_minutes = excluded.sync_interval_minutes,
+                            debug_mode = excluded.debug_mode,
+                            updated_at = CURRENT_TIMESTAMP
+                        "#,
+                    )
+                    .bind(auto_sync)
+                    .bind(allowed_domains)
+                    .bind(sync_interval_minutes)
+                    .bind(debug_mode)
+                    .execute(conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+}
@@ -0,0 +1,201 @@
+use crate::sqliterepository::{
+    models::launch_profile::LaunchProfileTable, sqliterepository::RepositoryImpl,
+};
+use domain::{
+    launch_profile::{LaunchProfile, NewLaunchProfile},
+    repository::launch_profile::LaunchProfileRepository,
+    works::Work,
+    Id, StrId,
+};
+
+const SELECT_COLUMNS: &str = r#"SELECT
+    id,
+    work_id,
+    name,
+    arguments,
+    working_dir,
+    env_json,
+    run_as_admin,
+    wrapper_path,
+    wrapper_arguments,
+    is_default
+FROM work_launch_profiles"#;
+
+impl LaunchProfileRepository for RepositoryImpl<LaunchProfile> {
+    async fn list_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+    ) -> anyhow::Result<Vec<LaunchProfile>> {
+        let rows: Vec<LaunchProfileTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = sqlx::query_as(&format!(
+                        "{SELECT_COLUMNS} WHERE work_id = ? ORDER BY is_default DESC, id ASC"
+                    ))
+                    .bind(work_id.value)
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn find_by_id(&mut self, id: Id<LaunchProfile>) -> anyhow::Result<Option<LaunchProfile>> {
+        let row: Option<LaunchProfileTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let row = sqlx::query_as(&format!("{SELECT_COLUMNS} WHERE id = ? LIMIT 1"))
+                        .bind(id.value as i64)
+                        .fetch_optional(conn)
+                        .await?;
+                    Ok(row)
+                })
+            })
+            .await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_default_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+    ) -> anyhow::Result<Option<LaunchProfile>> {
+        let row: Option<LaunchProfileTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let row = sqlx::query_as(&format!(
+                        "{SELECT_COLUMNS} WHERE work_id = ? AND is_default = 1 LIMIT 1"
+                    ))
+                    .bind(work_id.value)
+                    .fetch_optional(conn)
+                    .await?;
+                    Ok(row)
+                })
+            })
+            .await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn insert(&mut self, profile: &NewLaunchProfile) -> anyhow::Result<Id<LaunchProfile>> {
+        let profile = profile.clone();
+        let env_json = serde_json::to_string(&profile.env)?;
+        let id: i64 = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    if profile.is_default {
+                        clear_default(conn, &profile.work_id.value, None).await?;
+                    }
+                    let (id,): (i64,) = sqlx::query_as(
+                        r#"INSERT INTO work_launch_profiles (
+                            work_id,
+                            name,
+                            arguments,
+                            working_dir,
+                            env_json,
+                            run_as_admin,
+                            wrapper_path,
+                            wrapper_arguments,
+                            is_default
+                        )
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                        RETURNING id"#,
+                    )
+                    .bind(profile.work_id.value)
+                    .bind(profile.name)
+                    .bind(profile.arguments)
+                    .bind(profile.working_dir)
+                    .bind(env_json)
+                    .bind(profile.run_as_admin as i64)
+                    .bind(profile.wrapper.as_ref().map(|w| w.path.clone()))
+                    .bind(profile.wrapper.and_then(|w| w.arguments))
+                    .bind(profile.is_default as i64)
+                    .fetch_one(&mut *conn)
+                    .await?;
+                    Ok::<i64, anyhow::Error>(id)
+                })
+            })
+            .await?;
+        Ok(Id::new(id as i32))
+    }
+
+    async fn update(
+        &mut self,
+        id: Id<LaunchProfile>,
+        profile: &NewLaunchProfile,
+    ) -> anyhow::Result<()> {
+        let profile = profile.clone();
+        let env_json = serde_json::to_string(&profile.env)?;
+        let id = id.value as i64;
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    if profile.is_default {
+                        clear_default(conn, &profile.work_id.value, Some(id)).await?;
+                    }
+                    sqlx::query(
+                        r#"UPDATE work_launch_profiles SET
+                            name = ?,
+                            arguments = ?,
+                            working_dir = ?,
+                            env_json = ?,
+                            run_as_admin = ?,
+                            wrapper_path = ?,
+                            wrapper_arguments = ?,
+                            is_default = ?,
+                            updated_at = CURRENT_TIMESTAMP
+                        WHERE id = ?"#,
+                    )
+                    .bind(profile.name)
+                    .bind(profile.arguments)
+                    .bind(profile.working_dir)
+                    .bind(env_json)
+                    .bind(profile.run_as_admin as i64)
+                    .bind(profile.wrapper.as_ref().map(|w| w.path.clone()))
+                    .bind(profile.wrapper.and_then(|w| w.arguments))
+                    .bind(profile.is_default as i64)
+                    .bind(id)
+                    .execute(&mut *conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn delete(&mut self, id: Id<LaunchProfile>) -> anyhow::Result<()> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query("DELETE FROM work_launch_profiles WHERE id = ?")
+                        .bind(id.value as i64)
+                        .execute(conn)
+                        .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+}
+
+/// 既定は作品ごとに1つなので、新しい既定を書き込む前に外しておく
+async fn clear_default(
+    conn: &mut sqlx::pool::PoolConnection<sqlx::Sqlite>,
+    work_id: &str,
+    except_id: Option<i64>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"UPDATE work_launch_profiles
+        SET is_default = 0, updated_at = CURRENT_TIMESTAMP
+        WHERE work_id = ? AND is_default = 1 AND id IS NOT ?"#,
+    )
+    .bind(work_id)
+    .bind(except_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
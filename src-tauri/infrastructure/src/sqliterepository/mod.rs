@@ -3,12 +3,24 @@ pub mod app_settings;
 pub mod driver;
 pub mod erogamescape;
 pub mod explored_cache;
+pub mod extension_config;
+pub mod launch_profile;
 pub mod models;
 pub mod native_host_log;
+pub mod remote_launch;
+pub mod remote_share_state;
 pub mod save_image_queue;
+pub mod save_location;
 pub mod sqliterepository;
+pub mod sync_session;
+pub mod wine_config;
 pub mod work_download_path;
+pub mod work_duplicate;
+pub mod work_override;
 pub mod work_parent_packs;
+pub mod work_play_state;
+pub mod work_query;
+pub mod work_route;
 pub mod works;
 
 #[cfg(test)]
@@ -0,0 +1,25 @@
+use domain::extension::ExtensionConfig;
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct ExtensionConfigTable {
+    pub id: i64,
+    pub auto_sync: i64,
+    pub allowed_domains: String,
+    pub sync_interval_minutes: i64,
+    pub debug_mode: i64,
+    pub created_at: Option<sqlx::types::chrono::NaiveDateTime>,
+    pub updated_at: Option<sqlx::types::chrono::NaiveDateTime>,
+}
+
+impl TryFrom<ExtensionConfigTable> for ExtensionConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(st: ExtensionConfigTable) -> Result<Self, Self::Error> {
+        Ok(ExtensionConfig {
+            auto_sync: st.auto_sync != 0,
+            allowed_domains: serde_json::from_str(&st.allowed_domains)?,
+            sync_interval_minutes: u32::try_from(st.sync_interval_minutes)?,
+            debug_mode: st.debug_mode != 0,
+        })
+    }
+}
@@ -0,0 +1,37 @@
+use domain::launch_profile::{LaunchEnvVar, LaunchProfile, LaunchWrapper};
+use domain::{Id, StrId};
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct LaunchProfileTable {
+    pub id: i64,
+    pub work_id: String,
+    pub name: String,
+    pub arguments: Option<String>,
+    pub working_dir: Option<String>,
+    pub env_json: String,
+    pub run_as_admin: i64,
+    pub wrapper_path: Option<String>,
+    pub wrapper_arguments: Option<String>,
+    pub is_default: i64,
+}
+
+impl From<LaunchProfileTable> for LaunchProfile {
+    fn from(st: LaunchProfileTable) -> Self {
+        // 読めない env_json は上書きなしとして扱う
+        let env: Vec<LaunchEnvVar> = serde_json::from_str(&st.env_json).unwrap_or_default();
+        LaunchProfile {
+            id: Id::new(st.id as i32),
+            work_id: StrId::new(st.work_id),
+            name: st.name,
+            arguments: st.arguments,
+            working_dir: st.working_dir,
+            env,
+            run_as_admin: st.run_as_admin != 0,
+            wrapper: st.wrapper_path.map(|path| LaunchWrapper {
+                path,
+                arguments: st.wrapper_arguments,
+            }),
+            is_default: st.is_default != 0,
+        }
+    }
+}
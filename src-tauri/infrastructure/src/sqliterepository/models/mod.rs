@@ -1,6 +1,16 @@
 pub mod all_game_cache;
 pub mod app_settings;
+pub mod extension_config;
+pub mod launch_profile;
 pub mod native_host_log;
+pub mod remote_launch;
+pub mod remote_share_state;
 pub mod save_image_queue;
+pub mod save_location;
+pub mod sync_session;
+pub mod wine_config;
 pub mod work_parent_packs;
+pub mod work_override;
+pub mod work_play_state;
+pub mod work_route;
 pub mod works;
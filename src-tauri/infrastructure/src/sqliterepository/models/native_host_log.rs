@@ -7,5 +7,9 @@ pub struct NativeHostLogTable {
     pub level: i64,
     pub r#type: i64,
     pub message: String,
+    pub request_id: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub item_count: Option<i64>,
+    pub error_count: Option<i64>,
     pub created_at: NaiveDateTime,
 }
@@ -0,0 +1,44 @@
+use chrono::TimeZone as _;
+use sqlx::types::chrono::NaiveDateTime;
+
+use domain::remote_launch::{
+    RemoteLaunchAudit, RemoteLaunchDecision, RemoteLaunchPolicy, RemoteLaunchStatus,
+};
+use domain::Id;
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct RemoteLaunchSettingsTable {
+    pub remote_launch_policy: String,
+    pub remote_launch_confirm_timeout_seconds: i64,
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct RemoteLaunchAuditTable {
+    pub id: i64,
+    pub work_id: String,
+    pub work_title: Option<String>,
+    pub policy: String,
+    pub decision: String,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub requested_at: NaiveDateTime,
+    pub finished_at: NaiveDateTime,
+}
+
+impl From<RemoteLaunchAuditTable> for RemoteLaunchAudit {
+    fn from(st: RemoteLaunchAuditTable) -> Self {
+        RemoteLaunchAudit {
+            id: Id::new(st.id as i32),
+            work_id: st.work_id,
+            work_title: st.work_title,
+            policy: RemoteLaunchPolicy::parse(&st.policy).unwrap_or_default(),
+            // 読めない値は起動しなかった側に倒す
+            decision: RemoteLaunchDecision::parse(&st.decision)
+                .unwrap_or(RemoteLaunchDecision::Disabled),
+            status: RemoteLaunchStatus::parse(&st.status).unwrap_or(RemoteLaunchStatus::Error),
+            error_message: st.error_message,
+            requested_at: chrono::Local.from_utc_datetime(&st.requested_at),
+            finished_at: chrono::Local.from_utc_datetime(&st.finished_at),
+        }
+    }
+}
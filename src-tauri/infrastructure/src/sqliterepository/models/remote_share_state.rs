@@ -0,0 +1,23 @@
+use chrono::TimeZone as _;
+use sqlx::types::chrono::NaiveDateTime;
+
+use domain::remote_share::RemoteShareWorkState;
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct RemoteShareWorkStateTable {
+    pub work_id: String,
+    pub content_hash: String,
+    pub dirty: i64,
+    pub synced_at: NaiveDateTime,
+}
+
+impl From<RemoteShareWorkStateTable> for RemoteShareWorkState {
+    fn from(st: RemoteShareWorkStateTable) -> Self {
+        RemoteShareWorkState {
+            work_id: st.work_id,
+            content_hash: st.content_hash,
+            dirty: st.dirty != 0,
+            synced_at: chrono::Local.from_utc_datetime(&st.synced_at),
+        }
+    }
+}
@@ -1,5 +1,10 @@
+use chrono::TimeZone as _;
+use sqlx::types::chrono::NaiveDateTime;
 use sqlx::FromRow;
 
+use domain::save_image_queue::{ImagePreprocess, ImageSaveQueueRow, ImageSrcType};
+use domain::Id;
+
 #[derive(FromRow, Debug, Clone)]
 pub struct SaveImageQueueTable {
     pub id: i64,
@@ -8,4 +13,36 @@ pub struct SaveImageQueueTable {
     pub dst_path: String,
     pub preprocess: i64,
     pub last_error: Option<String>,
+    pub attempt_count: i64,
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub failed_at: Option<NaiveDateTime>,
+}
+
+impl From<SaveImageQueueTable> for ImageSaveQueueRow {
+    fn from(t: SaveImageQueueTable) -> Self {
+        ImageSaveQueueRow {
+            id: Id::new(t.id as i32),
+            src: t.src,
+            src_type: match t.src_type {
+                1 => ImageSrcType::Url,
+                2 => ImageSrcType::Path,
+                3 => ImageSrcType::Exe,
+                4 => ImageSrcType::Shortcut,
+                _ => ImageSrcType::Path,
+            },
+            dst_path: t.dst_path,
+            preprocess: match t.preprocess {
+                0 => ImagePreprocess::None,
+                1 => ImagePreprocess::ResizeAndCropSquare256,
+                3 => ImagePreprocess::ThumbnailVariants,
+                _ => ImagePreprocess::ResizeForWidth400,
+            },
+            last_error: t.last_error,
+            attempt_count: t.attempt_count as i32,
+            next_attempt_at: t
+                .next_attempt_at
+                .map(|v| chrono::Local.from_utc_datetime(&v)),
+            failed_at: t.failed_at.map(|v| chrono::Local.from_utc_datetime(&v)),
+        }
+    }
 }
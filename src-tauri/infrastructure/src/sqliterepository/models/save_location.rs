@@ -0,0 +1,22 @@
+use domain::save_data::{SaveLocation, SaveLocationSource};
+use domain::{Id, StrId};
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct SaveLocationTable {
+    pub id: i64,
+    pub work_id: String,
+    pub path: String,
+    pub source: String,
+}
+
+impl From<SaveLocationTable> for SaveLocation {
+    fn from(st: SaveLocationTable) -> Self {
+        SaveLocation {
+            id: Id::new(st.id as i32),
+            work_id: StrId::new(st.work_id),
+            path: st.path,
+            // 読めない値は消されないように手動として扱う
+            source: st.source.parse().unwrap_or(SaveLocationSource::Manual),
+        }
+    }
+}
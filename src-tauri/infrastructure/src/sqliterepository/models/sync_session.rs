@@ -0,0 +1,60 @@
+use chrono::TimeZone as _;
+use sqlx::types::chrono::NaiveDateTime;
+
+use domain::sync_session::{SyncSession, SyncSessionChunk, SyncStore};
+use domain::StrId;
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct SyncSessionTable {
+    pub id: String,
+    pub store: String,
+    pub extension_id: String,
+    pub total_chunks: i64,
+    pub committed_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<SyncSessionTable> for SyncSession {
+    type Error = anyhow::Error;
+
+    fn try_from(st: SyncSessionTable) -> Result<Self, Self::Error> {
+        Ok(SyncSession {
+            id: StrId::new(st.id),
+            store: SyncStore::parse(&st.store)
+                .ok_or_else(|| anyhow::anyhow!("unknown sync store: {}", st.store))?,
+            extension_id: st.extension_id,
+            total_chunks: u32::try_from(st.total_chunks)?,
+            committed_at: st.committed_at.map(|v| chrono::Local.from_utc_datetime(&v)),
+            created_at: chrono::Local.from_utc_datetime(&st.created_at),
+            updated_at: chrono::Local.from_utc_datetime(&st.updated_at),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct SyncSessionChunkTable {
+    pub chunk_index: i64,
+    pub game_count: i64,
+    pub success_count: i64,
+    pub new_count: i64,
+    pub unchanged_count: i64,
+    pub error_count: i64,
+    pub errors: String,
+}
+
+impl TryFrom<SyncSessionChunkTable> for SyncSessionChunk {
+    type Error = anyhow::Error;
+
+    fn try_from(st: SyncSessionChunkTable) -> Result<Self, Self::Error> {
+        Ok(SyncSessionChunk {
+            chunk_index: u32::try_from(st.chunk_index)?,
+            game_count: u32::try_from(st.game_count)?,
+            success_count: u32::try_from(st.success_count)?,
+            new_count: u32::try_from(st.new_count)?,
+            unchanged_count: u32::try_from(st.unchanged_count)?,
+            error_count: u32::try_from(st.error_count)?,
+            errors: serde_json::from_str(&st.errors)?,
+        })
+    }
+}
@@ -0,0 +1,26 @@
+use domain::launch_profile::LaunchEnvVar;
+use domain::wine::WineConfig;
+use domain::StrId;
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct WineConfigTable {
+    pub work_id: String,
+    pub runner: String,
+    pub runner_path: Option<String>,
+    pub prefix: Option<String>,
+    pub env_json: String,
+}
+
+impl From<WineConfigTable> for WineConfig {
+    fn from(st: WineConfigTable) -> Self {
+        // 読めない値は既定の wine と上書きなしとして扱う
+        let env: Vec<LaunchEnvVar> = serde_json::from_str(&st.env_json).unwrap_or_default();
+        WineConfig {
+            work_id: StrId::new(st.work_id),
+            runner: st.runner.parse().unwrap_or_default(),
+            runner_path: st.runner_path,
+            prefix: st.prefix,
+            env,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::NaiveDate;
+use domain::work_override::{WorkOverrides, OVERRIDE_SELLDAY_FORMAT};
+
+#[derive(sqlx::FromRow, Clone, Default)]
+pub struct WorkOverrideTable {
+    pub title: Option<String>,
+    pub gamename_ruby: Option<String>,
+    pub brandname: Option<String>,
+    pub sellday: Option<String>,
+}
+
+impl From<WorkOverrideTable> for WorkOverrides {
+    fn from(st: WorkOverrideTable) -> Self {
+        WorkOverrides {
+            title: st.title,
+            title_ruby: st.gamename_ruby,
+            brandname: st.brandname,
+            sellday: st
+                .sellday
+                .and_then(|v| NaiveDate::parse_from_str(&v, OVERRIDE_SELLDAY_FORMAT).ok()),
+        }
+    }
+}
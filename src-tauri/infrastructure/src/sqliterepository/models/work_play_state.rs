@@ -0,0 +1,45 @@
+use domain::play_status::{PlayStatus, PlayStatusChange, WorkPlayState};
+use domain::{Id, StrId};
+use sqlx::types::chrono::{NaiveDate, NaiveDateTime};
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct WorkPlayStateTable {
+    pub work_id: String,
+    pub status: String,
+    pub score: Option<i64>,
+    pub started_on: Option<NaiveDate>,
+    pub finished_on: Option<NaiveDate>,
+}
+
+impl From<WorkPlayStateTable> for WorkPlayState {
+    fn from(st: WorkPlayStateTable) -> Self {
+        WorkPlayState {
+            work_id: StrId::new(st.work_id),
+            status: st.status.parse().unwrap_or(PlayStatus::Unplayed),
+            score: st.score.map(|v| v as i32),
+            started_on: st.started_on,
+            finished_on: st.finished_on,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct PlayStatusChangeTable {
+    pub id: i64,
+    pub work_id: String,
+    pub status: String,
+    pub changed_at: NaiveDateTime,
+}
+
+impl TryFrom<PlayStatusChangeTable> for PlayStatusChange {
+    type Error = anyhow::Error;
+
+    fn try_from(st: PlayStatusChangeTable) -> Result<Self, Self::Error> {
+        Ok(PlayStatusChange {
+            id: Id::new(st.id as i32),
+            work_id: StrId::new(st.work_id),
+            status: st.status.parse()?,
+            changed_at: st.changed_at.and_utc().with_timezone(&chrono::Local),
+        })
+    }
+}
@@ -0,0 +1,24 @@
+use domain::play_status::WorkRoute;
+use domain::{Id, StrId};
+use sqlx::types::chrono::NaiveDateTime;
+
+#[derive(sqlx::FromRow, Clone)]
+pub struct WorkRouteTable {
+    pub id: i64,
+    pub work_id: String,
+    pub name: String,
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+impl From<WorkRouteTable> for WorkRoute {
+    fn from(st: WorkRouteTable) -> Self {
+        WorkRoute {
+            id: Id::new(st.id as i32),
+            work_id: StrId::new(st.work_id),
+            name: st.name,
+            completed_at: st
+                .completed_at
+                .map(|v| v.and_utc().with_timezone(&chrono::Local)),
+        }
+    }
+}
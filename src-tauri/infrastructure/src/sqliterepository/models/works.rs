@@ -21,6 +21,27 @@ pub struct WorkTable {
     pub title: String,
 }
 
+#[derive(sqlx::FromRow, Clone)]
+pub struct WorkThumbnailVariantTable {
+    pub work_id: String,
+    pub width: i64,
+    pub height: i64,
+    pub format: String,
+    pub path: String,
+}
+
+impl WorkThumbnailVariantTable {
+    /// 未知の形式（将来追加されたものなど）は None
+    pub fn into_domain(self) -> Option<domain::thumbnail::WorkThumbnailVariant> {
+        Some(domain::thumbnail::WorkThumbnailVariant {
+            width: self.width as i32,
+            height: self.height as i32,
+            format: domain::thumbnail::ThumbnailFormat::parse(&self.format)?,
+            path: self.path,
+        })
+    }
+}
+
 #[derive(sqlx::FromRow, Clone)]
 pub struct WorkDetailsRow {
     pub work_id: String,
@@ -48,6 +69,7 @@ pub struct WorkDetailsRow {
     pub egs_info_updated_at: Option<sqlx::types::chrono::NaiveDateTime>,
     pub cet_width: Option<i64>,
     pub cet_height: Option<i64>,
+    pub cet_placeholder_color: Option<String>,
     pub dlsite_id: Option<i64>,
     pub dlsite_store_id: Option<String>,
     pub dlsite_category: Option<String>,
@@ -55,11 +77,20 @@ pub struct WorkDetailsRow {
     pub latest_path_download_path: Option<String>,
     pub install_install_at: Option<sqlx::types::chrono::NaiveDateTime>,
     pub install_original_path: Option<String>,
+    pub install_engine: Option<String>,
     pub play_last_play_at: Option<sqlx::types::chrono::NaiveDateTime>,
+    pub play_state_status: Option<String>,
+    pub play_state_score: Option<i64>,
+    pub play_state_started_on: Option<sqlx::types::chrono::NaiveDate>,
+    pub play_state_finished_on: Option<sqlx::types::chrono::NaiveDate>,
     pub like_id: Option<i64>,
     pub like_like_at: Option<sqlx::types::chrono::NaiveDateTime>,
     pub like_created_at: Option<sqlx::types::chrono::NaiveDateTime>,
     pub like_updated_at: Option<sqlx::types::chrono::NaiveDateTime>,
+    pub override_title: Option<String>,
+    pub override_gamename_ruby: Option<String>,
+    pub override_brandname: Option<String>,
+    pub override_sellday: Option<String>,
 }
 
 impl From<crate::sqliterepository::models::works::WorkDetailsRow> for domain::works::WorkDetails {
@@ -84,13 +115,31 @@ impl From<crate::sqliterepository::models::works::WorkDetailsRow> for domain::wo
                 .install_install_at
                 .map(|v| v.and_utc().with_timezone(&chrono::Local)),
             original_path: r.install_original_path.clone(),
+            engine: r
+                .install_engine
+                .as_deref()
+                .and_then(|v| v.parse::<domain::game_engine::GameEngine>().ok()),
             last_play_at: r
                 .play_last_play_at
                 .map(|v| v.and_utc().with_timezone(&chrono::Local)),
+            play_state: r.play_state_status.as_deref().map(|status| {
+                domain::play_status::WorkPlayState {
+                    work_id: StrId::new(r.work_id.clone()),
+                    status: status
+                        .parse()
+                        .unwrap_or(domain::play_status::PlayStatus::Unplayed),
+                    score: r.play_state_score.map(|v| v as i32),
+                    started_on: r.play_state_started_on,
+                    finished_on: r.play_state_finished_on,
+                }
+            }),
             registered_at: r
                 .ce_created_at
                 .map(|v| v.and_utc().with_timezone(&chrono::Local)),
             thumbnail_size: None,
+            thumbnail_variants: Vec::new(),
+            thumbnail_placeholder_color: r.cet_placeholder_color.clone(),
+            overrides: Default::default(),
         };
 
         if let Some(dmm_id) = r.dmm_id {
@@ -105,13 +154,13 @@ impl From<crate::sqliterepository::models::works::WorkDetailsRow> for domain::wo
                     r.parent_dmm_category.clone(),
                     r.parent_dmm_subcategory.clone(),
                 ) {
-                    (Some(store_id), Some(category), Some(subcategory)) => Some(
-                        domain::works::DmmPackKey {
+                    (Some(store_id), Some(category), Some(subcategory)) => {
+                        Some(domain::works::DmmPackKey {
                             store_id,
                             category,
                             subcategory,
-                        },
-                    ),
+                        })
+                    }
                     _ => None,
                 },
             });
@@ -186,6 +235,17 @@ impl From<crate::sqliterepository::models::works::WorkDetailsRow> for domain::wo
             details.thumbnail_size = Some(WorkThumbnailSize::new(w as i32, h as i32))
         }
 
+        // 利用者が直した項目は同期や取り込みで得た情報より優先する
+        let overrides: domain::work_override::WorkOverrides =
+            crate::sqliterepository::models::work_override::WorkOverrideTable {
+                title: r.override_title,
+                gamename_ruby: r.override_gamename_ruby,
+                brandname: r.override_brandname,
+                sellday: r.override_sellday,
+            }
+            .into();
+        overrides.apply(&mut details);
+
         details
     }
 }
@@ -256,6 +316,27 @@ impl TryFrom<crate::sqliterepository::models::works::WorkLikeRow> for domain::wo
     }
 }
 
+#[derive(sqlx::FromRow, Clone)]
+pub struct MissingStoreWorkRow {
+    pub work_id: String,
+    pub title: String,
+    pub store_id: String,
+    pub category: String,
+    pub missing_since: sqlx::types::chrono::NaiveDateTime,
+}
+
+impl From<MissingStoreWorkRow> for domain::works::MissingStoreWork {
+    fn from(v: MissingStoreWorkRow) -> Self {
+        domain::works::MissingStoreWork {
+            work_id: domain::StrId::new(v.work_id),
+            title: v.title,
+            store_id: v.store_id,
+            category: v.category,
+            missing_since: v.missing_since.and_utc().with_timezone(&chrono::Local),
+        }
+    }
+}
+
 #[derive(sqlx::FromRow, Clone)]
 pub struct WorkLnkRow {
     pub id: i64,
@@ -1,9 +1,36 @@
 use crate::sqliterepository::models::native_host_log::NativeHostLogTable;
-use crate::sqliterepository::sqliterepository::RepositoryImpl;
-use chrono::TimeZone;
-use domain::native_host_log::{HostLogLevel, HostLogType, NativeHostLogRow};
+use crate::sqliterepository::sqliterepository::{to_sqlite_utc, RepositoryImpl};
+use chrono::{DateTime, Local, TimeZone};
+use domain::native_host_log::{
+    truncate_message, HostLogFilter, HostLogLevel, HostLogType, NativeHostLogRow, NewHostLog,
+    MAX_MESSAGE_BYTES,
+};
 use domain::repository::native_host_log::NativeHostLogRepository;
 use domain::Id;
+use sqlx::{QueryBuilder, Sqlite};
+
+fn push_filter(qb: &mut QueryBuilder<'_, Sqlite>, filter: &HostLogFilter) {
+    qb.push(" WHERE 1 = 1");
+    if let Some(level) = filter.level {
+        qb.push(" AND level = ").push_bind(level as i64);
+    }
+    if let Some(typ) = filter.r#type {
+        qb.push(" AND type = ").push_bind(typ as i64);
+    }
+    if let Some(request_id) = &filter.request_id {
+        qb.push(" AND request_id = ").push_bind(request_id.clone());
+    }
+    // created_at は秒までなので、datetime() で since を同じ書式（秒に切り捨て）にしてから比べる
+    if let Some(since) = filter.since {
+        qb.push(" AND created_at >= datetime(")
+            .push_bind(to_sqlite_utc(since))
+            .push(")");
+    }
+    if let Some(until) = filter.until {
+        qb.push(" AND created_at < ")
+            .push_bind(to_sqlite_utc(until));
+    }
+}
 
 impl NativeHostLogRepository for RepositoryImpl<domain::native_host_log::NativeHostLogRow> {
     async fn insert_log(
@@ -12,110 +39,132 @@ impl NativeHostLogRepository for RepositoryImpl<domain::native_host_log::NativeH
         typ: HostLogType,
         message: &str,
     ) -> anyhow::Result<()> {
-        let message = message.to_string();
-        self.executor.with_conn(|conn| {
-            Box::pin(async move {
-                sqlx::query("INSERT INTO native_messaging_host_logs (level, type, message) VALUES (?, ?, ?)")
-                    .bind(level as i64)
-                    .bind(typ as i64)
+        self.insert(&NewHostLog::new(level, typ, message)).await
+    }
+
+    async fn insert(&mut self, log: &NewHostLog) -> anyhow::Result<()> {
+        let log = log.clone();
+        let message = truncate_message(&log.message, MAX_MESSAGE_BYTES);
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"INSERT INTO native_messaging_host_logs
+                        (level, type, message, request_id, duration_ms, item_count, error_count)
+                        VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+                    )
+                    .bind(log.level as i64)
+                    .bind(log.r#type as i64)
                     .bind(message)
+                    .bind(log.request_id)
+                    .bind(log.duration_ms)
+                    .bind(log.item_count)
+                    .bind(log.error_count)
                     .execute(conn)
                     .await?;
-                Ok::<(), anyhow::Error>(())
+                    Ok::<(), anyhow::Error>(())
+                })
             })
-        }).await?;
-        Ok(())
+            .await
     }
 
     async fn list_logs(
         &mut self,
         limit: i64,
         offset: i64,
-        level: Option<HostLogLevel>,
-        typ: Option<HostLogType>,
+        filter: &HostLogFilter,
     ) -> anyhow::Result<Vec<NativeHostLogRow>> {
-        let rows: Vec<NativeHostLogTable> = self.executor.with_conn(|conn| {
-            Box::pin(async move {
-                let mut sql = String::from("SELECT id, level, type, message, created_at FROM native_messaging_host_logs");
-                let mut where_added = false;
-                if level.is_some() { sql.push_str(" WHERE level = ?"); where_added = true; }
-                if typ.is_some() { sql.push_str(if where_added { " AND type = ?" } else { " WHERE type = ?" }); }
-                sql.push_str(" ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?");
-                let mut q = sqlx::query_as::<_, NativeHostLogTable>(&sql);
-                if let Some(level) = level { q = q.bind(level as i64); }
-                if let Some(typ) = typ { q = q.bind(typ as i64); }
-                q = q.bind(limit).bind(offset);
-                let rows = q.fetch_all(conn).await?;
-                Ok(rows)
+        let filter = filter.clone();
+        let rows: Vec<NativeHostLogTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let mut qb = QueryBuilder::new(
+                        r#"SELECT id, level, type, message, request_id, duration_ms, item_count, error_count, created_at
+                        FROM native_messaging_host_logs"#,
+                    );
+                    push_filter(&mut qb, &filter);
+                    qb.push(" ORDER BY created_at DESC, id DESC LIMIT ")
+                        .push_bind(limit)
+                        .push(" OFFSET ")
+                        .push_bind(offset);
+                    let rows = qb.build_query_as().fetch_all(conn).await?;
+                    Ok(rows)
+                })
             })
-        }).await?;
+            .await?;
         Ok(rows
             .into_iter()
             .map(|t| NativeHostLogRow {
                 id: Id::new(t.id as i32),
-                level: match t.level {
-                    0 => HostLogLevel::Debug,
-                    1 => HostLogLevel::Info,
-                    2 => HostLogLevel::Warn,
-                    3 => HostLogLevel::Error,
-                    _ => HostLogLevel::Info,
-                },
-                r#type: match t.r#type {
-                    0 => HostLogType::Unknown,
-                    1 => HostLogType::ReceiveDmmSyncGamesRequest,
-                    2 => HostLogType::ReceiveDlsiteSyncGamesRequest,
-                    10 => HostLogType::ImageQueueWorkerStarted,
-                    11 => HostLogType::ImageQueueWorkerFinished,
-                    20 => HostLogType::ImageQueueItemStarted,
-                    21 => HostLogType::ImageQueueItemSucceeded,
-                    22 => HostLogType::ImageQueueItemFailed,
-                    30 => HostLogType::ReceiveRequest,
-                    31 => HostLogType::Response,
-                    32 => HostLogType::EndProcessImageQueue,
-                    33 => HostLogType::AppSignalDispatchFailed,
-                    _ => HostLogType::Unknown,
-                },
+                level: HostLogLevel::from_i64(t.level).unwrap_or(HostLogLevel::Info),
+                r#type: HostLogType::from_i64(t.r#type).unwrap_or(HostLogType::Unknown),
                 message: t.message,
+                request_id: t.request_id,
+                duration_ms: t.duration_ms,
+                item_count: t.item_count,
+                error_count: t.error_count,
                 created_at: chrono::Local.from_utc_datetime(&t.created_at),
             })
             .collect())
     }
 
-    async fn count_logs(
-        &mut self,
-        level: Option<HostLogLevel>,
-        typ: Option<HostLogType>,
-    ) -> anyhow::Result<i64> {
+    async fn count_logs(&mut self, filter: &HostLogFilter) -> anyhow::Result<i64> {
+        let filter = filter.clone();
         let (cnt,): (i64,) = self
             .executor
             .with_conn(|conn| {
                 Box::pin(async move {
-                    let mut sql =
-                        String::from("SELECT COUNT(*) as cnt FROM native_messaging_host_logs");
-                    let mut where_added = false;
-                    if level.is_some() {
-                        sql.push_str(" WHERE level = ?");
-                        where_added = true;
-                    }
-                    if typ.is_some() {
-                        sql.push_str(if where_added {
-                            " AND type = ?"
-                        } else {
-                            " WHERE type = ?"
-                        });
-                    }
-                    let mut q = sqlx::query_as::<_, (i64,)>(&sql);
-                    if let Some(level) = level {
-                        q = q.bind(level as i64);
-                    }
-                    if let Some(typ) = typ {
-                        q = q.bind(typ as i64);
-                    }
-                    let row = q.fetch_one(conn).await?;
+                    let mut qb =
+                        QueryBuilder::new("SELECT COUNT(*) as cnt FROM native_messaging_host_logs");
+                    push_filter(&mut qb, &filter);
+                    let row = qb.build_query_as().fetch_one(conn).await?;
                     Ok::<(i64,), anyhow::Error>(row)
                 })
             })
             .await?;
         Ok(cnt)
     }
+
+    async fn delete_older_than(&mut self, before: DateTime<Local>) -> anyhow::Result<u64> {
+        let before = to_sqlite_utc(before);
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let result = sqlx::query(
+                        "DELETE FROM native_messaging_host_logs WHERE created_at < ? AND type <> ?",
+                    )
+                    .bind(before)
+                    .bind(HostLogType::SyncGamesCompleted as i64)
+                    .execute(conn)
+                    .await?;
+                    Ok(result.rows_affected())
+                })
+            })
+            .await
+    }
+
+    async fn delete_exceeding(&mut self, keep: i64) -> anyhow::Result<u64> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    // id は追加順なので、keep 件目より古い id をまとめて消す（同期完了ログは数えず残す）
+                    let result = sqlx::query(
+                        r#"DELETE FROM native_messaging_host_logs
+                        WHERE type <> ?1 AND id <= (
+                            SELECT id FROM native_messaging_host_logs
+                            WHERE type <> ?1
+                            ORDER BY id DESC
+                            LIMIT 1 OFFSET ?2
+                        )"#,
+                    )
+                    .bind(HostLogType::SyncGamesCompleted as i64)
+                    .bind(keep.max(0))
+                    .execute(conn)
+                    .await?;
+                    Ok(result.rows_affected())
+                })
+            })
+            .await
+    }
 }
@@ -0,0 +1,164 @@
+use crate::sqliterepository::{
+    models::remote_launch::{RemoteLaunchAuditTable, RemoteLaunchSettingsTable},
+    sqliterepository::RepositoryImpl,
+};
+use domain::{
+    remote_launch::{
+        NewRemoteLaunchAudit, RemoteLaunchAudit, RemoteLaunchPolicy, RemoteLaunchSettings,
+    },
+    repository::remote_launch::RemoteLaunchRepository,
+};
+
+impl RemoteLaunchRepository for RepositoryImpl<RemoteLaunchSettings> {
+    async fn get_settings(&mut self) -> anyhow::Result<RemoteLaunchSettings> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let row: Option<RemoteLaunchSettingsTable> = sqlx::query_as(
+                        r#"SELECT
+                            remote_launch_policy,
+                            remote_launch_confirm_timeout_seconds
+                        FROM app_settings
+                        WHERE id = 1
+                        LIMIT 1"#,
+                    )
+                    .fetch_optional(&mut *conn)
+                    .await?;
+                    let allowed_work_ids: Vec<(String,)> = sqlx::query_as(
+                        "SELECT work_id FROM remote_launch_allowed_works ORDER BY work_id",
+                    )
+                    .fetch_all(&mut *conn)
+                    .await?;
+
+                    let mut settings = RemoteLaunchSettings::default();
+                    if let Some(row) = row {
+                        settings.policy = RemoteLaunchPolicy::parse(&row.remote_launch_policy)
+                            .unwrap_or_default();
+                        settings.confirm_timeout_seconds =
+                            row.remote_launch_confirm_timeout_seconds.max(0) as u32;
+                    }
+                    settings.allowed_work_ids =
+                        allowed_work_ids.into_iter().map(|(id,)| id).collect();
+                    Ok::<RemoteLaunchSettings, anyhow::Error>(settings)
+                })
+            })
+            .await
+    }
+
+    async fn set_settings(&mut self, settings: &RemoteLaunchSettings) -> anyhow::Result<()> {
+        let settings = settings.clone();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO app_settings (
+                            id,
+                            remote_launch_policy,
+                            remote_launch_confirm_timeout_seconds
+                        )
+                        VALUES (1, ?, ?)
+                        ON CONFLICT(id) DO UPDATE SET
+                            remote_launch_policy = excluded.remote_launch_policy,
+                            remote_launch_confirm_timeout_seconds = excluded.remote_launch_confirm_timeout_seconds,
+                            updated_at = CURRENT_TIMESTAMP
+                        "#,
+                    )
+                    .bind(settings.policy.as_str())
+                    .bind(settings.confirm_timeout_seconds as i64)
+                    .execute(&mut *conn)
+                    .await?;
+                    sqlx::query("DELETE FROM remote_launch_allowed_works")
+                        .execute(&mut *conn)
+                        .await?;
+                    for work_id in settings.allowed_work_ids {
+                        insert_allowed_work(conn, work_id).await?;
+                    }
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn add_allowed_work(&mut self, work_id: &str) -> anyhow::Result<()> {
+        let work_id = work_id.to_string();
+        self.executor
+            .with_conn(|conn| Box::pin(async move { insert_allowed_work(conn, work_id).await }))
+            .await
+    }
+
+    async fn insert_audit(&mut self, audit: &NewRemoteLaunchAudit) -> anyhow::Result<()> {
+        let audit = audit.clone();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"INSERT INTO remote_launch_audit_logs
+                        (work_id, work_title, policy, decision, status, error_message, requested_at)
+                        VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+                    )
+                    .bind(audit.work_id)
+                    .bind(audit.work_title)
+                    .bind(audit.policy.as_str())
+                    .bind(audit.decision.as_str())
+                    .bind(audit.status.as_str())
+                    .bind(audit.error_message)
+                    .bind(audit.requested_at.naive_utc())
+                    .execute(conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn list_audits(
+        &mut self,
+        limit: i64,
+        offset: i64,
+    ) -> anyhow::Result<Vec<RemoteLaunchAudit>> {
+        let rows: Vec<RemoteLaunchAuditTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = sqlx::query_as(
+                        r#"SELECT
+                            id,
+                            work_id,
+                            work_title,
+                            policy,
+                            decision,
+                            status,
+                            error_message,
+                            requested_at,
+                            finished_at
+                        FROM remote_launch_audit_logs
+                        ORDER BY requested_at DESC, id DESC
+                        LIMIT ? OFFSET ?"#,
+                    )
+                    .bind(limit)
+                    .bind(offset)
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+/// 存在しない作品は外部キーで弾かれる前に読み飛ばす
+async fn insert_allowed_work(
+    conn: &mut sqlx::pool::PoolConnection<sqlx::Sqlite>,
+    work_id: String,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"INSERT OR IGNORE INTO remote_launch_allowed_works (work_id)
+        SELECT id FROM works WHERE id = ?"#,
+    )
+    .bind(work_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use sqlx::QueryBuilder;
+
+use crate::sqliterepository::{
+    models::remote_share_state::RemoteShareWorkStateTable, sqliterepository::RepositoryImpl,
+};
+use domain::{
+    remote_share::{RemoteShareWorkState, SyncedRemoteShareWork},
+    repository::remote_share_state::RemoteShareStateRepository,
+};
+
+/// 1 文あたりのバインド数を SQLite の上限に収めるための件数
+const BATCH_SIZE: usize = 200;
+
+impl RemoteShareStateRepository for RepositoryImpl<RemoteShareWorkState> {
+    async fn list(&mut self, device_id: &str) -> anyhow::Result<Vec<RemoteShareWorkState>> {
+        let device_id = device_id.to_string();
+        let rows: Vec<RemoteShareWorkStateTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = sqlx::query_as(
+                        r#"SELECT
+                            work_id,
+                            content_hash,
+                            dirty,
+                            synced_at
+                        FROM remote_share_work_states
+                        WHERE device_id = ?
+                        ORDER BY work_id"#,
+                    )
+                    .bind(device_id)
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn upsert_synced(
+        &mut self,
+        device_id: &str,
+        works: &[SyncedRemoteShareWork],
+    ) -> anyhow::Result<()> {
+        for batch in works.chunks(BATCH_SIZE) {
+            let device_id = device_id.to_string();
+            let batch = batch.to_vec();
+            self.executor
+                .with_conn(|conn| {
+                    Box::pin(async move {
+                        let mut qb = QueryBuilder::new(
+                            "INSERT INTO remote_share_work_states (device_id, work_id, content_hash, dirty) ",
+                        );
+                        qb.push_values(batch, |mut b, work| {
+                            b.push_bind(device_id.clone())
+                                .push_bind(work.work_id)
+                                .push_bind(work.content_hash)
+                                .push_bind(work.dirty as i64);
+                        });
+                        qb.push(
+                            r#" ON CONFLICT(device_id, work_id) DO UPDATE SET
+                                content_hash = excluded.content_hash,
+                                dirty = excluded.dirty,
+                                synced_at = CURRENT_TIMESTAMP"#,
+                        );
+                        qb.build().execute(conn).await?;
+                        Ok::<(), anyhow::Error>(())
+                    })
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn delete(&mut self, device_id: &str, work_ids: &[String]) -> anyhow::Result<u64> {
+        let mut deleted = 0;
+        for batch in work_ids.chunks(BATCH_SIZE) {
+            let device_id = device_id.to_string();
+            let batch = batch.to_vec();
+            deleted += self
+                .executor
+                .with_conn(|conn| {
+                    Box::pin(async move {
+                        let mut qb = QueryBuilder::new(
+                            "DELETE FROM remote_share_work_states WHERE device_id = ",
+                        );
+                        qb.push_bind(device_id);
+                        qb.push(" AND work_id IN (");
+                        let mut separated = qb.separated(", ");
+                        for work_id in batch.iter() {
+                            separated.push_bind(work_id);
+                        }
+                        qb.push(")");
+                        let result = qb.build().execute(conn).await?;
+                        Ok(result.rows_affected())
+                    })
+                })
+                .await?;
+        }
+        Ok(deleted)
+    }
+
+    async fn mark_all_dirty(&mut self, device_id: &str) -> anyhow::Result<u64> {
+        let device_id = device_id.to_string();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let result = sqlx::query(
+                        "UPDATE remote_share_work_states SET dirty = 1 WHERE device_id = ? AND dirty = 0",
+                    )
+                    .bind(device_id)
+                    .execute(conn)
+                    .await?;
+                    Ok(result.rows_affected())
+                })
+            })
+            .await
+    }
+
+    async fn delete_other_devices(&mut self, device_id: &str) -> anyhow::Result<u64> {
+        let device_id = device_id.to_string();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let result =
+                        sqlx::query("DELETE FROM remote_share_work_states WHERE device_id <> ?")
+                            .bind(device_id)
+                            .execute(conn)
+                            .await?;
+                    Ok(result.rows_affected())
+                })
+            })
+            .await
+    }
+}
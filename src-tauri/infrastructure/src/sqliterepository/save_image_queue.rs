@@ -1,3 +1,8 @@
+use std::time::Duration;
+
+use chrono::TimeZone as _;
+use sqlx::types::chrono::NaiveDateTime;
+
 use crate::sqliterepository::models::save_image_queue::SaveImageQueueTable;
 use crate::sqliterepository::sqliterepository::RepositoryImpl;
 use domain::{
@@ -6,6 +11,15 @@ use domain::{
     Id,
 };
 
+fn preprocess_value(preprocess: ImagePreprocess) -> i64 {
+    match preprocess {
+        ImagePreprocess::None => 0,
+        ImagePreprocess::ResizeAndCropSquare256 => 1,
+        ImagePreprocess::ResizeForWidth400 => 2,
+        ImagePreprocess::ThumbnailVariants => 3,
+    }
+}
+
 impl ImageSaveQueueRepository for RepositoryImpl<domain::save_image_queue::ImageSaveQueueRow> {
     async fn enqueue(
         &mut self,
@@ -22,7 +36,7 @@ impl ImageSaveQueueRepository for RepositoryImpl<domain::save_image_queue::Image
                     .bind(src)
                     .bind(match src_type { ImageSrcType::Url => 1_i64, ImageSrcType::Path => 2_i64, ImageSrcType::Exe => 3_i64, ImageSrcType::Shortcut => 4_i64 })
                     .bind(dst_path)
-                    .bind(match preprocess { ImagePreprocess::None => 0_i64, ImagePreprocess::ResizeAndCropSquare256 => 1_i64, ImagePreprocess::ResizeForWidth400 => 2_i64 })
+                    .bind(preprocess_value(preprocess))
                     .fetch_one(conn)
                     .await?;
                 Ok::<(i64,), anyhow::Error>(rec)
@@ -41,42 +55,59 @@ impl ImageSaveQueueRepository for RepositoryImpl<domain::save_image_queue::Image
         } else {
             ("finished_at IS NOT NULL", "finished_at DESC")
         };
-        let query = format!(
-            "SELECT id, src, src_type, dst_path, preprocess, last_error FROM save_image_queue WHERE {} ORDER BY {} LIMIT ?",
-            condition, order
-        );
-        let rows: Vec<SaveImageQueueTable> = self
+        self.select_rows(condition, order, limit).await
+    }
+
+    async fn list_due(&mut self, limit: i64) -> anyhow::Result<Vec<ImageSaveQueueRow>> {
+        self.select_rows(
+            "finished_at IS NULL AND (next_attempt_at IS NULL OR next_attempt_at <= CURRENT_TIMESTAMP)",
+            "created_at ASC",
+            limit,
+        )
+        .await
+    }
+
+    async fn next_attempt_at(&mut self) -> anyhow::Result<Option<chrono::DateTime<chrono::Local>>> {
+        let (at,): (Option<NaiveDateTime>,) = self
             .executor
             .with_conn(|conn| {
                 Box::pin(async move {
-                    let rows: Vec<SaveImageQueueTable> =
-                        sqlx::query_as(&query).bind(limit).fetch_all(conn).await?;
-                    Ok(rows)
+                    let row: (Option<NaiveDateTime>,) = sqlx::query_as(
+                        "SELECT MIN(next_attempt_at) FROM save_image_queue WHERE finished_at IS NULL AND next_attempt_at IS NOT NULL",
+                    )
+                    .fetch_one(conn)
+                    .await?;
+                    Ok::<(Option<NaiveDateTime>,), anyhow::Error>(row)
                 })
             })
             .await?;
-        let mapped = rows
-            .into_iter()
-            .map(|t| ImageSaveQueueRow {
-                id: Id::new(t.id as i32),
-                src: t.src,
-                src_type: match t.src_type {
-                    1 => ImageSrcType::Url,
-                    2 => ImageSrcType::Path,
-                    3 => ImageSrcType::Exe,
-                    4 => ImageSrcType::Shortcut,
-                    _ => ImageSrcType::Path,
-                },
-                dst_path: t.dst_path,
-                preprocess: match t.preprocess {
-                    0 => ImagePreprocess::None,
-                    1 => ImagePreprocess::ResizeAndCropSquare256,
-                    _ => ImagePreprocess::ResizeForWidth400,
-                },
-                last_error: t.last_error,
+        Ok(at.map(|v| chrono::Local.from_utc_datetime(&v)))
+    }
+
+    async fn list_failed(&mut self, limit: i64) -> anyhow::Result<Vec<ImageSaveQueueRow>> {
+        self.select_rows("failed_at IS NOT NULL", "failed_at DESC", limit)
+            .await
+    }
+
+    async fn list_dst_paths_not_succeeded(
+        &mut self,
+        preprocess: ImagePreprocess,
+    ) -> anyhow::Result<Vec<String>> {
+        let rows: Vec<(String,)> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        "SELECT DISTINCT dst_path FROM save_image_queue WHERE preprocess = ? AND (finished_at IS NULL OR failed_at IS NOT NULL)",
+                    )
+                    .bind(preprocess_value(preprocess))
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
             })
-            .collect();
-        Ok(mapped)
+            .await?;
+        Ok(rows.into_iter().map(|(path,)| path).collect())
     }
 
     async fn count(&mut self, unfinished: bool) -> anyhow::Result<i64> {
@@ -101,7 +132,29 @@ impl ImageSaveQueueRepository for RepositoryImpl<domain::save_image_queue::Image
     async fn mark_finished(&mut self, id: Id<ImageSaveQueueRow>) -> anyhow::Result<()> {
         self.executor.with_conn(|conn| {
             Box::pin(async move {
-                sqlx::query("UPDATE save_image_queue SET finished_at = CURRENT_TIMESTAMP, last_error = NULL WHERE id = ?")
+                sqlx::query("UPDATE save_image_queue SET finished_at = CURRENT_TIMESTAMP, last_error = NULL, next_attempt_at = NULL, failed_at = NULL WHERE id = ?")
+                    .bind(id.value)
+                    .execute(conn)
+                    .await?;
+                Ok::<(), anyhow::Error>(())
+            })
+        }).await?;
+        Ok(())
+    }
+
+    async fn mark_retry(
+        &mut self,
+        id: Id<ImageSaveQueueRow>,
+        error: &str,
+        delay: Duration,
+    ) -> anyhow::Result<()> {
+        let error = error.to_string();
+        let delay = format!("+{} seconds", delay.as_secs());
+        self.executor.with_conn(|conn| {
+            Box::pin(async move {
+                sqlx::query("UPDATE save_image_queue SET attempt_count = attempt_count + 1, last_error = ?, next_attempt_at = datetime('now', ?) WHERE id = ?")
+                    .bind(error)
+                    .bind(delay)
                     .bind(id.value)
                     .execute(conn)
                     .await?;
@@ -115,7 +168,7 @@ impl ImageSaveQueueRepository for RepositoryImpl<domain::save_image_queue::Image
         let error = error.to_string();
         self.executor.with_conn(|conn| {
             Box::pin(async move {
-                sqlx::query("UPDATE save_image_queue SET finished_at = CURRENT_TIMESTAMP, last_error = ? WHERE id = ?")
+                sqlx::query("UPDATE save_image_queue SET attempt_count = attempt_count + 1, finished_at = CURRENT_TIMESTAMP, failed_at = CURRENT_TIMESTAMP, next_attempt_at = NULL, last_error = ? WHERE id = ?")
                     .bind(error)
                     .bind(id.value)
                     .execute(conn)
@@ -125,4 +178,71 @@ impl ImageSaveQueueRepository for RepositoryImpl<domain::save_image_queue::Image
         }).await?;
         Ok(())
     }
+
+    async fn requeue_failed(&mut self, ids: Vec<Id<ImageSaveQueueRow>>) -> anyhow::Result<u64> {
+        // last_error は残す（worker が既定アイコンで埋まった出力先を上書きする目印になる）
+        self.update_failed(
+            "UPDATE save_image_queue SET attempt_count = 0, next_attempt_at = NULL, failed_at = NULL, finished_at = NULL",
+            ids,
+        )
+        .await
+    }
+
+    async fn discard_failed(&mut self, ids: Vec<Id<ImageSaveQueueRow>>) -> anyhow::Result<u64> {
+        self.update_failed("DELETE FROM save_image_queue", ids)
+            .await
+    }
+}
+
+impl RepositoryImpl<ImageSaveQueueRow> {
+    async fn select_rows(
+        &self,
+        condition: &str,
+        order: &str,
+        limit: i64,
+    ) -> anyhow::Result<Vec<ImageSaveQueueRow>> {
+        let query = format!(
+            "SELECT id, src, src_type, dst_path, preprocess, last_error, attempt_count, next_attempt_at, failed_at FROM save_image_queue WHERE {} ORDER BY {} LIMIT ?",
+            condition, order
+        );
+        let rows: Vec<SaveImageQueueTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows: Vec<SaveImageQueueTable> =
+                        sqlx::query_as(&query).bind(limit).fetch_all(conn).await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// デッドレターの行だけを対象に `statement` を実行する
+    async fn update_failed(
+        &self,
+        statement: &'static str,
+        ids: Vec<Id<ImageSaveQueueRow>>,
+    ) -> anyhow::Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let mut builder = sqlx::QueryBuilder::new(statement);
+                    builder.push(" WHERE failed_at IS NOT NULL AND id IN (");
+                    {
+                        let mut separated = builder.separated(", ");
+                        for id in ids.iter() {
+                            separated.push_bind(id.value);
+                        }
+                    }
+                    builder.push(")");
+                    let result = builder.build().execute(conn).await?;
+                    Ok::<u64, anyhow::Error>(result.rows_affected())
+                })
+            })
+            .await
+    }
 }
@@ -0,0 +1,84 @@
+use crate::sqliterepository::{
+    models::save_location::SaveLocationTable, sqliterepository::RepositoryImpl,
+};
+use domain::{
+    repository::save_location::SaveLocationRepository,
+    save_data::{NewSaveLocation, SaveLocation, SaveLocationSource},
+    works::Work,
+    Id, StrId,
+};
+
+impl SaveLocationRepository for RepositoryImpl<SaveLocation> {
+    async fn list_by_work_id(&mut self, work_id: StrId<Work>) -> anyhow::Result<Vec<SaveLocation>> {
+        let rows: Vec<SaveLocationTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = sqlx::query_as(
+                        r#"SELECT id, work_id, path, source
+                        FROM work_save_locations WHERE work_id = ? ORDER BY id ASC"#,
+                    )
+                    .bind(work_id.value)
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn insert(&mut self, location: &NewSaveLocation) -> anyhow::Result<Id<SaveLocation>> {
+        let location = location.clone();
+        let id: i64 = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    // 既にあれば source は変えずに ID だけ返す
+                    let (id,): (i64,) = sqlx::query_as(
+                        r#"INSERT INTO work_save_locations (work_id, path, source)
+                        VALUES (?, ?, ?)
+                        ON CONFLICT(work_id, path) DO UPDATE SET path = excluded.path
+                        RETURNING id"#,
+                    )
+                    .bind(location.work_id.value)
+                    .bind(location.path)
+                    .bind(location.source.as_str())
+                    .fetch_one(conn)
+                    .await?;
+                    Ok(id)
+                })
+            })
+            .await?;
+        Ok(Id::new(id as i32))
+    }
+
+    async fn delete(&mut self, id: Id<SaveLocation>) -> anyhow::Result<()> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query("DELETE FROM work_save_locations WHERE id = ?")
+                        .bind(id.value as i64)
+                        .execute(conn)
+                        .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn delete_detected_by_work_id(&mut self, work_id: StrId<Work>) -> anyhow::Result<()> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query("DELETE FROM work_save_locations WHERE work_id = ? AND source = ?")
+                        .bind(work_id.value)
+                        .bind(SaveLocationSource::Detected.as_str())
+                        .execute(conn)
+                        .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+}
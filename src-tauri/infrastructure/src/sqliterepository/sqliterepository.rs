@@ -1,3 +1,4 @@
+use chrono::{DateTime, Local};
 use domain::repository::{manager::RepositoryManager, RepositoriesExt};
 use futures::future::BoxFuture;
 use futures::FutureExt;
@@ -34,6 +35,13 @@ impl RepositoryExecutor {
     }
 }
 
+/// 日時を CURRENT_TIMESTAMP と同じ UTC の書式にする。同じ秒の前後も比べられるよう秒未満まで残す。
+/// 秒までの CURRENT_TIMESTAMP の値と文字列で比べると、同じ秒ではこちらが常に大きくなる。
+/// 秒単位で比べたいときは SQL 側で datetime() を通す
+pub(crate) fn to_sqlite_utc(at: DateTime<Local>) -> String {
+    at.naive_utc().format("%Y-%m-%d %H:%M:%S%.6f").to_string()
+}
+
 #[derive(Clone, derive_new::new)]
 pub struct RepositoryImpl<T> {
     pub executor: Arc<RepositoryExecutor>,
@@ -49,6 +57,7 @@ pub struct SqliteRepositories {
     all_game_cache: RepositoryImpl<domain::all_game_cache::AllGameCache>,
     app_settings: RepositoryImpl<domain::repository::app_settings::AppStorageSettings>,
     explored_cache: RepositoryImpl<domain::explored_cache::ExploredCache>,
+    extension_config: RepositoryImpl<domain::extension::ExtensionConfig>,
     image_queue: RepositoryImpl<domain::save_image_queue::ImageSaveQueueRow>,
     host_log: RepositoryImpl<domain::native_host_log::NativeHostLogRow>,
     work_parent_packs: RepositoryImpl<domain::work_parent_pack::WorkParentPack>,
@@ -57,6 +66,16 @@ pub struct SqliteRepositories {
     work_like: RepositoryImpl<domain::works::WorkLike>,
     work_link_pending_exe: RepositoryImpl<domain::work_link_pending_exe::WorkLinkPendingExe>,
     erogamescape: RepositoryImpl<domain::erogamescape::ErogamescapeInformation>,
+    sync_session: RepositoryImpl<domain::sync_session::SyncSession>,
+    remote_share_state: RepositoryImpl<domain::remote_share::RemoteShareWorkState>,
+    remote_launch: RepositoryImpl<domain::remote_launch::RemoteLaunchSettings>,
+    launch_profile: RepositoryImpl<domain::launch_profile::LaunchProfile>,
+    wine_config: RepositoryImpl<domain::wine::WineConfig>,
+    save_location: RepositoryImpl<domain::save_data::SaveLocation>,
+    work_play_state: RepositoryImpl<domain::play_status::WorkPlayState>,
+    work_route: RepositoryImpl<domain::play_status::WorkRoute>,
+    work_duplicate: RepositoryImpl<domain::work_duplicate::DuplicateCandidate>,
+    work_override: RepositoryImpl<domain::work_override::WorkOverrides>,
 }
 
 impl RepositoriesExt for SqliteRepositories {
@@ -66,6 +85,7 @@ impl RepositoriesExt for SqliteRepositories {
     type AllGameCacheRepo = RepositoryImpl<domain::all_game_cache::AllGameCache>;
     type AppSettingsRepo = RepositoryImpl<domain::repository::app_settings::AppStorageSettings>;
     type ExploredCacheRepo = RepositoryImpl<domain::explored_cache::ExploredCache>;
+    type ExtensionConfigRepo = RepositoryImpl<domain::extension::ExtensionConfig>;
     type ImageQueueRepo = RepositoryImpl<domain::save_image_queue::ImageSaveQueueRow>;
     type HostLogRepo = RepositoryImpl<domain::native_host_log::NativeHostLogRow>;
     type WorkParentPacksRepo = RepositoryImpl<domain::work_parent_pack::WorkParentPack>;
@@ -74,6 +94,16 @@ impl RepositoriesExt for SqliteRepositories {
     type WorkLnkRepo = RepositoryImpl<domain::repository::work_lnk::WorkLnk>;
     type WorkLikeRepo = RepositoryImpl<domain::works::WorkLike>;
     type WorkLinkPendingExeRepo = RepositoryImpl<domain::work_link_pending_exe::WorkLinkPendingExe>;
+    type SyncSessionRepo = RepositoryImpl<domain::sync_session::SyncSession>;
+    type RemoteShareStateRepo = RepositoryImpl<domain::remote_share::RemoteShareWorkState>;
+    type RemoteLaunchRepo = RepositoryImpl<domain::remote_launch::RemoteLaunchSettings>;
+    type LaunchProfileRepo = RepositoryImpl<domain::launch_profile::LaunchProfile>;
+    type WineConfigRepo = RepositoryImpl<domain::wine::WineConfig>;
+    type SaveLocationRepo = RepositoryImpl<domain::save_data::SaveLocation>;
+    type WorkPlayStateRepo = RepositoryImpl<domain::play_status::WorkPlayState>;
+    type WorkRouteRepo = RepositoryImpl<domain::play_status::WorkRoute>;
+    type WorkDuplicateRepo = RepositoryImpl<domain::work_duplicate::DuplicateCandidate>;
+    type WorkOverrideRepo = RepositoryImpl<domain::work_override::WorkOverrides>;
 
     fn work(&self) -> Self::WorkRepo {
         self.work.clone()
@@ -93,6 +123,9 @@ impl RepositoriesExt for SqliteRepositories {
     fn explored_cache(&self) -> Self::ExploredCacheRepo {
         self.explored_cache.clone()
     }
+    fn extension_config(&self) -> Self::ExtensionConfigRepo {
+        self.extension_config.clone()
+    }
     fn image_queue(&self) -> Self::ImageQueueRepo {
         self.image_queue.clone()
     }
@@ -117,6 +150,36 @@ impl RepositoriesExt for SqliteRepositories {
     fn work_link_pending_exe(&self) -> Self::WorkLinkPendingExeRepo {
         self.work_link_pending_exe.clone()
     }
+    fn sync_session(&self) -> Self::SyncSessionRepo {
+        self.sync_session.clone()
+    }
+    fn remote_share_state(&self) -> Self::RemoteShareStateRepo {
+        self.remote_share_state.clone()
+    }
+    fn remote_launch(&self) -> Self::RemoteLaunchRepo {
+        self.remote_launch.clone()
+    }
+    fn launch_profile(&self) -> Self::LaunchProfileRepo {
+        self.launch_profile.clone()
+    }
+    fn wine_config(&self) -> Self::WineConfigRepo {
+        self.wine_config.clone()
+    }
+    fn save_location(&self) -> Self::SaveLocationRepo {
+        self.save_location.clone()
+    }
+    fn work_play_state(&self) -> Self::WorkPlayStateRepo {
+        self.work_play_state.clone()
+    }
+    fn work_route(&self) -> Self::WorkRouteRepo {
+        self.work_route.clone()
+    }
+    fn work_duplicate(&self) -> Self::WorkDuplicateRepo {
+        self.work_duplicate.clone()
+    }
+    fn work_override(&self) -> Self::WorkOverrideRepo {
+        self.work_override.clone()
+    }
 }
 
 impl SqliteRepositories {
@@ -142,6 +205,7 @@ impl SqliteRepositories {
             all_game_cache: RepositoryImpl::new(executor.clone()),
             app_settings: RepositoryImpl::new(executor.clone()),
             explored_cache: RepositoryImpl::new(executor.clone()),
+            extension_config: RepositoryImpl::new(executor.clone()),
             image_queue: RepositoryImpl::new(executor.clone()),
             host_log: RepositoryImpl::new(executor.clone()),
             work_parent_packs: RepositoryImpl::new(executor.clone()),
@@ -150,6 +214,16 @@ impl SqliteRepositories {
             work_like: RepositoryImpl::new(executor.clone()),
             work_link_pending_exe: RepositoryImpl::new(executor.clone()),
             erogamescape: RepositoryImpl::new(executor.clone()),
+            sync_session: RepositoryImpl::new(executor.clone()),
+            remote_share_state: RepositoryImpl::new(executor.clone()),
+            remote_launch: RepositoryImpl::new(executor.clone()),
+            launch_profile: RepositoryImpl::new(executor.clone()),
+            wine_config: RepositoryImpl::new(executor.clone()),
+            save_location: RepositoryImpl::new(executor.clone()),
+            work_play_state: RepositoryImpl::new(executor.clone()),
+            work_route: RepositoryImpl::new(executor.clone()),
+            work_duplicate: RepositoryImpl::new(executor.clone()),
+            work_override: RepositoryImpl::new(executor.clone()),
         }
     }
 }
@@ -0,0 +1,180 @@
+use chrono::{DateTime, Local};
+
+use crate::sqliterepository::{
+    models::sync_session::{SyncSessionChunkTable, SyncSessionTable},
+    sqliterepository::RepositoryImpl,
+};
+use domain::{
+    repository::sync_session::SyncSessionRepository,
+    sync_session::{NewSyncSession, SyncSession, SyncSessionChunk},
+    StrId,
+};
+
+impl SyncSessionRepository for RepositoryImpl<SyncSession> {
+    async fn get(&mut self, id: &StrId<SyncSession>) -> anyhow::Result<Option<SyncSession>> {
+        let id = id.value.clone();
+        let row: Option<SyncSessionTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let row = sqlx::query_as(
+                        r#"SELECT
+                            id,
+                            store,
+                            extension_id,
+                            total_chunks,
+                            committed_at,
+                            created_at,
+                            updated_at
+                        FROM sync_sessions
+                        WHERE id = ?"#,
+                    )
+                    .bind(id)
+                    .fetch_optional(conn)
+                    .await?;
+                    Ok(row)
+                })
+            })
+            .await?;
+        row.map(|r| r.try_into()).transpose()
+    }
+
+    async fn insert(&mut self, session: &NewSyncSession) -> anyhow::Result<()> {
+        let id = session.id.value.clone();
+        let store = session.store.as_str();
+        let extension_id = session.extension_id.clone();
+        let total_chunks = session.total_chunks as i64;
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        "INSERT INTO sync_sessions (id, store, extension_id, total_chunks) VALUES (?, ?, ?, ?)",
+                    )
+                    .bind(id)
+                    .bind(store)
+                    .bind(extension_id)
+                    .bind(total_chunks)
+                    .execute(conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn list_chunks(
+        &mut self,
+        id: &StrId<SyncSession>,
+    ) -> anyhow::Result<Vec<SyncSessionChunk>> {
+        let id = id.value.clone();
+        let rows: Vec<SyncSessionChunkTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = sqlx::query_as(
+                        r#"SELECT
+                            chunk_index,
+                            game_count,
+                            success_count,
+                            new_count,
+                            unchanged_count,
+                            error_count,
+                            errors
+                        FROM sync_session_chunks
+                        WHERE session_id = ?
+                        ORDER BY chunk_index ASC"#,
+                    )
+                    .bind(id)
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        rows.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    async fn insert_chunk(
+        &mut self,
+        id: &StrId<SyncSession>,
+        chunk: &SyncSessionChunk,
+    ) -> anyhow::Result<()> {
+        let id = id.value.clone();
+        let chunk = chunk.clone();
+        let errors = serde_json::to_string(&chunk.errors)?;
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO sync_session_chunks (
+                            session_id,
+                            chunk_index,
+                            game_count,
+                            success_count,
+                            new_count,
+                            unchanged_count,
+                            error_count,
+                            errors
+                        )
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                        ON CONFLICT(session_id, chunk_index) DO NOTHING
+                        "#,
+                    )
+                    .bind(&id)
+                    .bind(chunk.chunk_index as i64)
+                    .bind(chunk.game_count as i64)
+                    .bind(chunk.success_count as i64)
+                    .bind(chunk.new_count as i64)
+                    .bind(chunk.unchanged_count as i64)
+                    .bind(chunk.error_count as i64)
+                    .bind(errors)
+                    .execute(&mut *conn)
+                    .await?;
+                    sqlx::query(
+                        "UPDATE sync_sessions SET updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+                    )
+                    .bind(&id)
+                    .execute(&mut *conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn mark_committed(&mut self, id: &StrId<SyncSession>) -> anyhow::Result<()> {
+        let id = id.value.clone();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"UPDATE sync_sessions
+                        SET committed_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+                        WHERE id = ? AND committed_at IS NULL"#,
+                    )
+                    .bind(id)
+                    .execute(conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn delete_stale(&mut self, before: DateTime<Local>) -> anyhow::Result<u64> {
+        // updated_at は CURRENT_TIMESTAMP（UTC）で入っているので同じ書式で比べる
+        let before = before.naive_utc().format("%Y-%m-%d %H:%M:%S").to_string();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let result = sqlx::query("DELETE FROM sync_sessions WHERE updated_at < ?")
+                        .bind(before)
+                        .execute(conn)
+                        .await?;
+                    Ok(result.rows_affected())
+                })
+            })
+            .await
+    }
+}
@@ -0,0 +1,34 @@
+use domain::extension::ExtensionConfig;
+use domain::repository::extension_config::ExtensionConfigRepository;
+use domain::repository::RepositoriesExt;
+
+use super::TestDatabase;
+
+#[tokio::test]
+async fn extension_config_repository_未保存ならnoneで保存後は上書きされる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    assert_eq!(repo.extension_config().get().await.unwrap(), None);
+
+    let first = ExtensionConfig {
+        auto_sync: true,
+        allowed_domains: vec!["dlsite.com".into(), "games.dmm.co.jp".into()],
+        sync_interval_minutes: 30,
+        debug_mode: false,
+    };
+    repo.extension_config().upsert(&first).await.unwrap();
+    assert_eq!(
+        repo.extension_config().get().await.unwrap(),
+        Some(first.clone())
+    );
+
+    let second = ExtensionConfig {
+        auto_sync: false,
+        allowed_domains: vec![],
+        sync_interval_minutes: 5,
+        debug_mode: true,
+    };
+    repo.extension_config().upsert(&second).await.unwrap();
+    assert_eq!(repo.extension_config().get().await.unwrap(), Some(second));
+}
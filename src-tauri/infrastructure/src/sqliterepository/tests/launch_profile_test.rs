@@ -0,0 +1,165 @@
+use domain::launch_profile::{LaunchEnvVar, LaunchWrapper, NewLaunchProfile};
+use domain::repository::{
+    launch_profile::LaunchProfileRepository, works::WorkRepository, RepositoriesExt,
+};
+use domain::works::NewWork;
+use domain::{works::Work, StrId};
+
+use super::TestDatabase;
+
+fn new_profile(work_id: &StrId<Work>, name: &str, is_default: bool) -> NewLaunchProfile {
+    NewLaunchProfile {
+        work_id: work_id.clone(),
+        name: name.into(),
+        arguments: None,
+        working_dir: None,
+        env: vec![],
+        run_as_admin: false,
+        wrapper: None,
+        is_default,
+    }
+}
+
+#[tokio::test]
+async fn launch_profile_repository_保存した内容を読み戻せる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+
+    let id = repo
+        .launch_profile()
+        .insert(&NewLaunchProfile {
+            arguments: Some("-window".into()),
+            working_dir: Some("C:/games/w".into()),
+            env: vec![LaunchEnvVar {
+                key: "LANG".into(),
+                value: "ja_JP".into(),
+            }],
+            wrapper: Some(LaunchWrapper {
+                path: "C:/tools/LEProc.exe".into(),
+                arguments: Some("-run {target}".into()),
+            }),
+            ..new_profile(&work_id, "LE", true)
+        })
+        .await
+        .unwrap();
+
+    let profile = repo.launch_profile().find_by_id(id).await.unwrap().unwrap();
+    assert_eq!(profile.name, "LE");
+    assert_eq!(profile.arguments.as_deref(), Some("-window"));
+    assert_eq!(profile.working_dir.as_deref(), Some("C:/games/w"));
+    assert_eq!(profile.env.len(), 1);
+    assert_eq!(profile.env[0].key, "LANG");
+    assert_eq!(
+        profile.wrapper.as_ref().map(|w| w.path.as_str()),
+        Some("C:/tools/LEProc.exe")
+    );
+    assert!(profile.is_default);
+}
+
+#[tokio::test]
+async fn launch_profile_repository_既定は作品ごとに1つ() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let w1 = repo
+        .work()
+        .upsert(&NewWork { title: "W1".into() })
+        .await
+        .unwrap();
+    let w2 = repo
+        .work()
+        .upsert(&NewWork { title: "W2".into() })
+        .await
+        .unwrap();
+
+    let first = repo
+        .launch_profile()
+        .insert(&new_profile(&w1, "first", true))
+        .await
+        .unwrap();
+    let other_work = repo
+        .launch_profile()
+        .insert(&new_profile(&w2, "other", true))
+        .await
+        .unwrap();
+    let second = repo
+        .launch_profile()
+        .insert(&new_profile(&w1, "second", true))
+        .await
+        .unwrap();
+
+    let default = repo
+        .launch_profile()
+        .find_default_by_work_id(w1.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(default.id, second);
+    // 既定が先頭
+    let list = repo
+        .launch_profile()
+        .list_by_work_id(w1.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        list.iter().map(|p| p.id.value).collect::<Vec<_>>(),
+        vec![second.value, first.value]
+    );
+    // 他の作品の既定には触らない
+    let other_default = repo
+        .launch_profile()
+        .find_default_by_work_id(w2.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(other_default.id, other_work);
+
+    // 更新で既定を戻す
+    repo.launch_profile()
+        .update(first.clone(), &new_profile(&w1, "first", true))
+        .await
+        .unwrap();
+    let default = repo
+        .launch_profile()
+        .find_default_by_work_id(w1.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(default.id, first);
+
+    repo.launch_profile().delete(first).await.unwrap();
+    assert!(repo
+        .launch_profile()
+        .find_default_by_work_id(w1)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn launch_profile_repository_作品を消すと消える() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+    let id = repo
+        .launch_profile()
+        .insert(&new_profile(&work_id, "p", false))
+        .await
+        .unwrap();
+
+    repo.work().delete(work_id).await.unwrap();
+    assert!(repo
+        .launch_profile()
+        .find_by_id(id)
+        .await
+        .unwrap()
+        .is_none());
+}
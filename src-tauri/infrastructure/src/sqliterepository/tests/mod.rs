@@ -16,10 +16,21 @@ mod embedded {
 mod all_game_cache_test;
 mod app_settings_test;
 mod explored_cache_test;
+mod extension_config_test;
+mod launch_profile_test;
 mod native_host_log_test;
+mod remote_launch_test;
+mod remote_share_state_test;
 mod save_image_queue_test;
+mod save_location_test;
+mod sync_session_test;
+mod wine_config_test;
+mod work_duplicate_test;
 mod work_lnk_test;
+mod work_override_test;
 mod work_parent_packs_test;
+mod work_play_state_test;
+mod work_query_test;
 mod works;
 mod works_extra_test;
 
@@ -1,5 +1,8 @@
 use super::TestDatabase;
-use domain::native_host_log::{HostLogLevel, HostLogType};
+use chrono::{Duration, Local};
+use domain::native_host_log::{
+    HostLogFilter, HostLogLevel, HostLogType, NewHostLog, MAX_MESSAGE_BYTES,
+};
 use domain::repository::{native_host_log::NativeHostLogRepository, RepositoriesExt};
 
 #[tokio::test]
@@ -18,7 +21,7 @@ async fn native_host_log_normal_flows() {
     // list
     {
         let mut r = repo.host_log();
-        let rows = r.list_logs(10, 0, None, None).await.unwrap();
+        let rows = r.list_logs(10, 0, &HostLogFilter::default()).await.unwrap();
         assert_eq!(rows.len(), 1);
         assert_eq!(rows[0].message, "hello");
     }
@@ -26,7 +29,153 @@ async fn native_host_log_normal_flows() {
     // count
     {
         let mut r = repo.host_log();
-        let cnt = r.count_logs(None, None).await.unwrap();
+        let cnt = r.count_logs(&HostLogFilter::default()).await.unwrap();
         assert_eq!(cnt, 1);
     }
 }
+
+#[tokio::test]
+async fn native_host_log_構造化した列で絞り込める() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    repo.host_log()
+        .insert(
+            &NewHostLog::new(HostLogLevel::Info, HostLogType::Response, "response")
+                .request_id("req-1")
+                .duration(std::time::Duration::from_millis(1500))
+                .item_count(3)
+                .error_count(1),
+        )
+        .await
+        .unwrap();
+    repo.host_log()
+        .insert(
+            &NewHostLog::new(HostLogLevel::Info, HostLogType::ReceiveRequest, "request")
+                .request_id("req-2"),
+        )
+        .await
+        .unwrap();
+
+    let filter = HostLogFilter {
+        request_id: Some("req-1".into()),
+        ..Default::default()
+    };
+    let rows = repo.host_log().list_logs(10, 0, &filter).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].request_id.as_deref(), Some("req-1"));
+    assert_eq!(rows[0].duration_ms, Some(1500));
+    assert_eq!(rows[0].item_count, Some(3));
+    assert_eq!(rows[0].error_count, Some(1));
+    assert_eq!(repo.host_log().count_logs(&filter).await.unwrap(), 1);
+
+    // 時間の範囲
+    let future = HostLogFilter {
+        since: Some(Local::now() + Duration::hours(1)),
+        ..Default::default()
+    };
+    assert_eq!(repo.host_log().count_logs(&future).await.unwrap(), 0);
+    let window = HostLogFilter {
+        since: Some(Local::now() - Duration::hours(1)),
+        until: Some(Local::now() + Duration::hours(1)),
+        ..Default::default()
+    };
+    assert_eq!(repo.host_log().count_logs(&window).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn native_host_log_同じ秒に書いたログも_since_で絞り込める() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    repo.host_log()
+        .insert_log(HostLogLevel::Info, HostLogType::Unknown, "same second")
+        .await
+        .unwrap();
+    let created_at = repo
+        .host_log()
+        .list_logs(10, 0, &HostLogFilter::default())
+        .await
+        .unwrap()[0]
+        .created_at;
+
+    // created_at ちょうどと、同じ秒の途中の時刻のどちらでも含まれる
+    for since in [created_at, created_at + Duration::milliseconds(500)] {
+        let filter = HostLogFilter {
+            since: Some(since),
+            ..Default::default()
+        };
+        assert_eq!(repo.host_log().count_logs(&filter).await.unwrap(), 1);
+    }
+    let next_second = HostLogFilter {
+        since: Some(created_at + Duration::seconds(1)),
+        ..Default::default()
+    };
+    assert_eq!(repo.host_log().count_logs(&next_second).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn native_host_log_大きなメッセージは切り詰める() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    let message = "x".repeat(MAX_MESSAGE_BYTES * 4);
+    repo.host_log()
+        .insert_log(HostLogLevel::Info, HostLogType::ReceiveRequest, &message)
+        .await
+        .unwrap();
+
+    let rows = repo
+        .host_log()
+        .list_logs(1, 0, &HostLogFilter::default())
+        .await
+        .unwrap();
+    assert!(rows[0].message.len() < MAX_MESSAGE_BYTES + 64);
+    assert!(rows[0].message.ends_with("(truncated 24576 bytes)"));
+}
+
+#[tokio::test]
+async fn native_host_log_保持期間と件数を超えたログを消す() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    sqlx::query(
+        "INSERT INTO native_messaging_host_logs (level, type, message, created_at) VALUES (1, 0, 'old', datetime('now', '-40 days'))",
+    )
+    .execute(&test_db.pool)
+    .await
+    .unwrap();
+    // 同期完了ログは古くても件数を超えても残す
+    sqlx::query(
+        "INSERT INTO native_messaging_host_logs (level, type, message, created_at) VALUES (1, 34, 'synced', datetime('now', '-40 days'))",
+    )
+    .execute(&test_db.pool)
+    .await
+    .unwrap();
+    for i in 0..5 {
+        repo.host_log()
+            .insert_log(HostLogLevel::Info, HostLogType::Unknown, &format!("m{i}"))
+            .await
+            .unwrap();
+    }
+
+    let deleted = repo
+        .host_log()
+        .delete_older_than(Local::now() - Duration::days(30))
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+
+    let deleted = repo.host_log().delete_exceeding(2).await.unwrap();
+    assert_eq!(deleted, 3);
+    let rows = repo
+        .host_log()
+        .list_logs(10, 0, &HostLogFilter::default())
+        .await
+        .unwrap();
+    let messages: Vec<_> = rows.iter().map(|r| r.message.as_str()).collect();
+    assert_eq!(messages, vec!["m4", "m3", "synced"]);
+
+    // 件数が上限以下なら何も消さない
+    assert_eq!(repo.host_log().delete_exceeding(10).await.unwrap(), 0);
+}
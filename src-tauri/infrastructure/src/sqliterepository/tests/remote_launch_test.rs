@@ -0,0 +1,127 @@
+use chrono::{Duration, Local};
+use domain::remote_launch::{
+    NewRemoteLaunchAudit, RemoteLaunchDecision, RemoteLaunchPolicy, RemoteLaunchSettings,
+    RemoteLaunchStatus,
+};
+use domain::repository::{
+    remote_launch::RemoteLaunchRepository, works::WorkRepository, RepositoriesExt,
+};
+use domain::works::NewWork;
+
+use super::TestDatabase;
+
+#[tokio::test]
+async fn remote_launch_repository_未保存なら既定値() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    let settings = repo.remote_launch().get_settings().await.unwrap();
+    assert_eq!(settings, RemoteLaunchSettings::default());
+}
+
+#[tokio::test]
+async fn remote_launch_repository_設定と許可リストを置き換える() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let w1 = repo
+        .work()
+        .upsert(&NewWork { title: "W1".into() })
+        .await
+        .unwrap();
+    let w2 = repo
+        .work()
+        .upsert(&NewWork { title: "W2".into() })
+        .await
+        .unwrap();
+
+    repo.remote_launch()
+        .set_settings(&RemoteLaunchSettings {
+            policy: RemoteLaunchPolicy::Allowlist,
+            allowed_work_ids: vec![w1.value.clone(), "missing".into()],
+            confirm_timeout_seconds: 60,
+        })
+        .await
+        .unwrap();
+    repo.remote_launch()
+        .add_allowed_work(&w2.value)
+        .await
+        .unwrap();
+    repo.remote_launch()
+        .add_allowed_work(&w2.value)
+        .await
+        .unwrap();
+
+    let settings = repo.remote_launch().get_settings().await.unwrap();
+    assert_eq!(settings.policy, RemoteLaunchPolicy::Allowlist);
+    assert_eq!(settings.confirm_timeout_seconds, 60);
+    let mut expected = vec![w1.value.clone(), w2.value.clone()];
+    expected.sort();
+    assert_eq!(settings.allowed_work_ids, expected);
+
+    // 作品を消すと許可リストからも外れる
+    repo.work().delete(w1).await.unwrap();
+    let settings = repo.remote_launch().get_settings().await.unwrap();
+    assert_eq!(settings.allowed_work_ids, vec![w2.value]);
+}
+
+#[tokio::test]
+async fn remote_launch_repository_監査ログを新しい順に返す() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let now = Local::now();
+
+    for (work_id, decision, status, minutes_ago) in [
+        (
+            "w1",
+            RemoteLaunchDecision::Approved,
+            RemoteLaunchStatus::Queued,
+            2,
+        ),
+        (
+            "w2",
+            RemoteLaunchDecision::TimedOut,
+            RemoteLaunchStatus::Denied,
+            1,
+        ),
+    ] {
+        repo.remote_launch()
+            .insert_audit(&NewRemoteLaunchAudit {
+                work_id: work_id.into(),
+                work_title: Some(format!("title-{work_id}")),
+                policy: RemoteLaunchPolicy::AskFirst,
+                decision,
+                status,
+                error_message: None,
+                requested_at: now - Duration::minutes(minutes_ago),
+            })
+            .await
+            .unwrap();
+    }
+
+    let audits = repo.remote_launch().list_audits(10, 0).await.unwrap();
+    let summary = audits
+        .iter()
+        .map(|a| (a.work_id.as_str(), a.decision, a.status))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "w2",
+                RemoteLaunchDecision::TimedOut,
+                RemoteLaunchStatus::Denied
+            ),
+            (
+                "w1",
+                RemoteLaunchDecision::Approved,
+                RemoteLaunchStatus::Queued
+            ),
+        ]
+    );
+    assert_eq!(audits[0].work_title.as_deref(), Some("title-w2"));
+    assert_eq!(audits[0].policy, RemoteLaunchPolicy::AskFirst);
+
+    let page = repo.remote_launch().list_audits(1, 1).await.unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].work_id, "w1");
+}
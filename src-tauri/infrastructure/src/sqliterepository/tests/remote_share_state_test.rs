@@ -0,0 +1,134 @@
+use domain::remote_share::SyncedRemoteShareWork;
+use domain::repository::remote_share_state::RemoteShareStateRepository;
+use domain::repository::RepositoriesExt;
+
+use super::TestDatabase;
+
+fn synced(work_id: &str, content_hash: &str) -> SyncedRemoteShareWork {
+    SyncedRemoteShareWork {
+        work_id: work_id.into(),
+        content_hash: content_hash.into(),
+        dirty: false,
+    }
+}
+
+#[tokio::test]
+async fn remote_share_state_repository_同期した内容を上書きして消せる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    repo.remote_share_state()
+        .upsert_synced("device-1", &[synced("w2", "h2"), synced("w1", "h1")])
+        .await
+        .unwrap();
+    repo.remote_share_state()
+        .upsert_synced(
+            "device-1",
+            &[SyncedRemoteShareWork {
+                dirty: true,
+                ..synced("w2", "h2-new")
+            }],
+        )
+        .await
+        .unwrap();
+
+    let states = repo.remote_share_state().list("device-1").await.unwrap();
+    let summary = states
+        .iter()
+        .map(|s| (s.work_id.as_str(), s.content_hash.as_str(), s.dirty))
+        .collect::<Vec<_>>();
+    assert_eq!(summary, vec![("w1", "h1", false), ("w2", "h2-new", true)]);
+
+    let deleted = repo
+        .remote_share_state()
+        .delete("device-1", &["w2".into(), "missing".into()])
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+    let states = repo.remote_share_state().list("device-1").await.unwrap();
+    assert_eq!(states.len(), 1);
+    assert_eq!(states[0].work_id, "w1");
+}
+
+#[tokio::test]
+async fn remote_share_state_repository_dirtyを立てて他の端末の記録を消す() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    repo.remote_share_state()
+        .upsert_synced("device-1", &[synced("w1", "h1"), synced("w2", "h2")])
+        .await
+        .unwrap();
+    repo.remote_share_state()
+        .upsert_synced("device-old", &[synced("w1", "h1")])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        repo.remote_share_state()
+            .mark_all_dirty("device-1")
+            .await
+            .unwrap(),
+        2
+    );
+    assert!(repo
+        .remote_share_state()
+        .list("device-1")
+        .await
+        .unwrap()
+        .iter()
+        .all(|s| s.dirty));
+
+    assert_eq!(
+        repo.remote_share_state()
+            .delete_other_devices("device-1")
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(repo
+        .remote_share_state()
+        .list("device-old")
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        repo.remote_share_state()
+            .list("device-1")
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn remote_share_state_repository_件数が多くても分けて書き込む() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    let works = (0..450)
+        .map(|i| synced(&format!("w{i:03}"), "h"))
+        .collect::<Vec<_>>();
+    repo.remote_share_state()
+        .upsert_synced("device-1", &works)
+        .await
+        .unwrap();
+    assert_eq!(
+        repo.remote_share_state()
+            .list("device-1")
+            .await
+            .unwrap()
+            .len(),
+        450
+    );
+
+    let work_ids = works.iter().map(|w| w.work_id.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        repo.remote_share_state()
+            .delete("device-1", &work_ids)
+            .await
+            .unwrap(),
+        450
+    );
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use super::TestDatabase;
 use domain::repository::{save_image_queue::ImageSaveQueueRepository, RepositoriesExt};
 use domain::save_image_queue::{ImagePreprocess, ImageSrcType};
@@ -52,3 +54,124 @@ async fn save_image_queue_normal_flows() {
         assert_eq!(r.count(false).await.unwrap(), 1);
     }
 }
+
+#[tokio::test]
+async fn save_image_queue_リトライとデッドレター() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let mut r = repo.image_queue();
+
+    let retry_now = r
+        .enqueue(
+            "http://a",
+            ImageSrcType::Url,
+            "dst-a",
+            ImagePreprocess::None,
+        )
+        .await
+        .unwrap();
+    let retry_later = r
+        .enqueue(
+            "http://b",
+            ImageSrcType::Url,
+            "dst-b",
+            ImagePreprocess::None,
+        )
+        .await
+        .unwrap();
+    let dead = r
+        .enqueue(
+            "http://c",
+            ImageSrcType::Url,
+            "dst-c",
+            ImagePreprocess::None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(r.next_attempt_at().await.unwrap(), None);
+
+    // mark_retry: 待ち時間 0 なら即時、先の時刻なら list_due に出ない
+    r.mark_retry(retry_now.clone(), "503", Duration::ZERO)
+        .await
+        .unwrap();
+    r.mark_retry(retry_later.clone(), "timeout", Duration::from_secs(3600))
+        .await
+        .unwrap();
+    r.mark_failed(dead.clone(), "404").await.unwrap();
+
+    let due = r.list_due(10).await.unwrap();
+    assert_eq!(
+        due.iter().map(|v| v.id.value).collect::<Vec<_>>(),
+        vec![retry_now.value]
+    );
+    assert_eq!(due[0].attempt_count, 1);
+    assert_eq!(due[0].last_error.as_deref(), Some("503"));
+    assert!(due[0].next_attempt_at.is_some());
+
+    // バックオフ待ちも未完了には含まれる
+    assert_eq!(r.count(true).await.unwrap(), 2);
+
+    let failed = r.list_failed(10).await.unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].id.value, dead.value);
+    assert_eq!(failed[0].attempt_count, 1);
+    assert!(failed[0].failed_at.is_some());
+
+    // デッドレター以外は requeue/discard の対象外
+    assert_eq!(
+        r.requeue_failed(vec![retry_now.clone(), dead.clone()])
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(r.list_failed(10).await.unwrap().is_empty());
+    let due = r.list_due(10).await.unwrap();
+    let requeued = due.iter().find(|v| v.id.value == dead.value).unwrap();
+    assert_eq!(requeued.attempt_count, 0);
+    assert!(requeued.failed_at.is_none());
+    assert_eq!(requeued.last_error.as_deref(), Some("404"));
+
+    r.mark_failed(dead.clone(), "404").await.unwrap();
+    assert_eq!(r.discard_failed(vec![retry_later, dead]).await.unwrap(), 1);
+    assert!(r.list_failed(10).await.unwrap().is_empty());
+    assert_eq!(r.count(true).await.unwrap(), 2);
+    assert_eq!(r.count(false).await.unwrap(), 0);
+
+    // 次の再試行時刻は未完了のうち最も早いもの
+    let next = r.next_attempt_at().await.unwrap().unwrap();
+    assert!(next <= chrono::Local::now());
+    r.mark_finished(retry_now).await.unwrap();
+    let next = r.next_attempt_at().await.unwrap().unwrap();
+    assert!(next > chrono::Local::now() + chrono::Duration::minutes(59));
+}
+
+#[tokio::test]
+async fn save_image_queue_成功していない項目の出力先を返す() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let mut r = repo.image_queue();
+
+    let mut ids = vec![];
+    for dst in ["pending", "failed", "finished", "other"] {
+        let preprocess = if dst == "other" {
+            ImagePreprocess::ResizeForWidth400
+        } else {
+            ImagePreprocess::ThumbnailVariants
+        };
+        ids.push(
+            r.enqueue(dst, ImageSrcType::Path, dst, preprocess)
+                .await
+                .unwrap(),
+        );
+    }
+    r.mark_failed(ids[1].clone(), "broken png").await.unwrap();
+    r.mark_finished(ids[2].clone()).await.unwrap();
+
+    let mut paths = r
+        .list_dst_paths_not_succeeded(ImagePreprocess::ThumbnailVariants)
+        .await
+        .unwrap();
+    paths.sort();
+    assert_eq!(paths, vec!["failed".to_string(), "pending".to_string()]);
+}
@@ -0,0 +1,105 @@
+use domain::repository::{
+    save_location::SaveLocationRepository, works::WorkRepository, RepositoriesExt,
+};
+use domain::save_data::{NewSaveLocation, SaveLocationSource};
+use domain::works::NewWork;
+use domain::StrId;
+
+use super::TestDatabase;
+
+fn new_location(
+    work_id: &StrId<domain::works::Work>,
+    path: &str,
+    source: SaveLocationSource,
+) -> NewSaveLocation {
+    NewSaveLocation {
+        work_id: work_id.clone(),
+        path: path.into(),
+        source,
+    }
+}
+
+#[tokio::test]
+async fn save_location_repository_同じ場所は一度だけ登録される() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+
+    let first = repo
+        .save_location()
+        .insert(&new_location(
+            &work_id,
+            "{install}/savedata",
+            SaveLocationSource::Manual,
+        ))
+        .await
+        .unwrap();
+    let second = repo
+        .save_location()
+        .insert(&new_location(
+            &work_id,
+            "{install}/savedata",
+            SaveLocationSource::Detected,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(first, second);
+    let locations = repo.save_location().list_by_work_id(work_id).await.unwrap();
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].source, SaveLocationSource::Manual);
+}
+
+#[tokio::test]
+async fn save_location_repository_見つけ直すときは手動の場所を残す() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+    repo.save_location()
+        .insert(&new_location(
+            &work_id,
+            "{install}/savedata",
+            SaveLocationSource::Detected,
+        ))
+        .await
+        .unwrap();
+    let manual = repo
+        .save_location()
+        .insert(&new_location(
+            &work_id,
+            "%APPDATA%/brand/game",
+            SaveLocationSource::Manual,
+        ))
+        .await
+        .unwrap();
+
+    repo.save_location()
+        .delete_detected_by_work_id(work_id.clone())
+        .await
+        .unwrap();
+
+    let locations = repo
+        .save_location()
+        .list_by_work_id(work_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].id, manual);
+    assert_eq!(locations[0].path, "%APPDATA%/brand/game");
+
+    repo.save_location().delete(manual).await.unwrap();
+    assert!(repo
+        .save_location()
+        .list_by_work_id(work_id)
+        .await
+        .unwrap()
+        .is_empty());
+}
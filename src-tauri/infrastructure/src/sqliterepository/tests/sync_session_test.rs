@@ -0,0 +1,104 @@
+use chrono::{Duration, Local};
+use domain::repository::sync_session::SyncSessionRepository;
+use domain::repository::RepositoriesExt;
+use domain::sync_session::{NewSyncSession, SyncSessionChunk, SyncStore};
+use domain::StrId;
+
+use super::TestDatabase;
+
+fn chunk(chunk_index: u32, success_count: u32) -> SyncSessionChunk {
+    SyncSessionChunk {
+        chunk_index,
+        game_count: success_count,
+        success_count,
+        new_count: 1,
+        unchanged_count: success_count - 1,
+        error_count: 0,
+        errors: vec![],
+    }
+}
+
+#[tokio::test]
+async fn sync_session_repository_チャンクを記録して再送は無視する() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let id = StrId::new("session-1".to_string());
+
+    assert_eq!(repo.sync_session().get(&id).await.unwrap(), None);
+    repo.sync_session()
+        .insert(&NewSyncSession {
+            id: id.clone(),
+            store: SyncStore::Dlsite,
+            extension_id: "ext".into(),
+            total_chunks: 3,
+        })
+        .await
+        .unwrap();
+
+    repo.sync_session()
+        .insert_chunk(&id, &chunk(2, 5))
+        .await
+        .unwrap();
+    repo.sync_session()
+        .insert_chunk(&id, &chunk(0, 7))
+        .await
+        .unwrap();
+    // 再送された同じチャンクは最初の結果を残す
+    repo.sync_session()
+        .insert_chunk(&id, &chunk(0, 99))
+        .await
+        .unwrap();
+
+    let chunks = repo.sync_session().list_chunks(&id).await.unwrap();
+    assert_eq!(chunks, vec![chunk(0, 7), chunk(2, 5)]);
+
+    let session = repo.sync_session().get(&id).await.unwrap().unwrap();
+    assert_eq!(session.store, SyncStore::Dlsite);
+    assert_eq!(session.total_chunks, 3);
+    assert!(!session.is_committed());
+
+    repo.sync_session().mark_committed(&id).await.unwrap();
+    let session = repo.sync_session().get(&id).await.unwrap().unwrap();
+    assert!(session.is_committed());
+}
+
+#[tokio::test]
+async fn sync_session_repository_古いセッションはチャンクごと消える() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let id = StrId::new("session-old".to_string());
+    repo.sync_session()
+        .insert(&NewSyncSession {
+            id: id.clone(),
+            store: SyncStore::Dmm,
+            extension_id: "ext".into(),
+            total_chunks: 1,
+        })
+        .await
+        .unwrap();
+    repo.sync_session()
+        .insert_chunk(&id, &chunk(0, 1))
+        .await
+        .unwrap();
+
+    let deleted = repo
+        .sync_session()
+        .delete_stale(Local::now() - Duration::hours(1))
+        .await
+        .unwrap();
+    assert_eq!(deleted, 0);
+
+    let deleted = repo
+        .sync_session()
+        .delete_stale(Local::now() + Duration::hours(1))
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+    assert_eq!(repo.sync_session().get(&id).await.unwrap(), None);
+    assert!(repo
+        .sync_session()
+        .list_chunks(&id)
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,99 @@
+use domain::launch_profile::LaunchEnvVar;
+use domain::repository::{
+    wine_config::WineConfigRepository, works::WorkRepository, RepositoriesExt,
+};
+use domain::wine::{WineConfig, WineRunner};
+use domain::works::NewWork;
+
+use super::TestDatabase;
+
+#[tokio::test]
+async fn wine_config_repository_作品ごとに上書き保存できる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+    assert!(repo
+        .wine_config()
+        .find_by_work_id(work_id.clone())
+        .await
+        .unwrap()
+        .is_none());
+
+    let config = WineConfig {
+        work_id: work_id.clone(),
+        runner: WineRunner::Proton,
+        runner_path: Some("/opt/proton/proton".into()),
+        prefix: Some("/home/u/prefixes/w".into()),
+        env: vec![LaunchEnvVar {
+            key: "LANG".into(),
+            value: "ja_JP.UTF-8".into(),
+        }],
+    };
+    repo.wine_config().upsert(&config).await.unwrap();
+    let saved = repo
+        .wine_config()
+        .find_by_work_id(work_id.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(saved, config);
+
+    let updated = WineConfig {
+        runner: WineRunner::Umu,
+        runner_path: None,
+        env: vec![],
+        ..config
+    };
+    repo.wine_config().upsert(&updated).await.unwrap();
+    let saved = repo
+        .wine_config()
+        .find_by_work_id(work_id.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(saved, updated);
+
+    repo.wine_config()
+        .delete_by_work_id(work_id.clone())
+        .await
+        .unwrap();
+    assert!(repo
+        .wine_config()
+        .find_by_work_id(work_id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn wine_config_repository_作品を消すと消える() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+    repo.wine_config()
+        .upsert(&WineConfig {
+            work_id: work_id.clone(),
+            runner: WineRunner::Wine,
+            runner_path: None,
+            prefix: None,
+            env: vec![],
+        })
+        .await
+        .unwrap();
+
+    repo.work().delete(work_id.clone()).await.unwrap();
+    assert!(repo
+        .wine_config()
+        .find_by_work_id(work_id)
+        .await
+        .unwrap()
+        .is_none());
+}
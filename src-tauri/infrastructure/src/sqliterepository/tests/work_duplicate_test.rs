@@ -0,0 +1,140 @@
+use chrono::{Duration, Local};
+use domain::repository::{
+    work_duplicate::WorkDuplicateRepository,
+    work_like::WorkLikeRepository,
+    work_lnk::{NewWorkLnk, WorkLnkRepository},
+    works::{DmmWorkRepository, WorkRepository},
+    RepositoriesExt,
+};
+use domain::thumbnail::{ThumbnailFormat, WorkThumbnailVariant};
+use domain::works::{NewDmmWork, NewWork};
+
+use super::TestDatabase;
+
+#[tokio::test]
+async fn work_duplicate_統合すると紐づくものが生き残る側へ移る() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let survivor = repo
+        .work()
+        .upsert(&NewWork {
+            title: "survivor".into(),
+        })
+        .await
+        .unwrap();
+    let duplicate = repo
+        .work()
+        .upsert(&NewWork {
+            title: "duplicate".into(),
+        })
+        .await
+        .unwrap();
+    repo.dmm_work()
+        .upsert(&NewDmmWork {
+            store_id: "d_1".into(),
+            category: "mono".into(),
+            subcategory: "pcgame".into(),
+            work_id: duplicate.clone(),
+        })
+        .await
+        .unwrap();
+    repo.work_lnk()
+        .insert(&NewWorkLnk {
+            work_id: duplicate.clone(),
+            lnk_path: "dup.lnk".into(),
+        })
+        .await
+        .unwrap();
+    repo.work_like()
+        .update_like_at_by_work_id(duplicate.clone(), Some(Local::now()))
+        .await
+        .unwrap();
+    let now = Local::now();
+    repo.work()
+        .update_last_play_at_by_work_id(survivor.clone(), now - Duration::days(3))
+        .await
+        .unwrap();
+    repo.work()
+        .update_last_play_at_by_work_id(duplicate.clone(), now)
+        .await
+        .unwrap();
+    repo.work()
+        .upsert_work_thumbnail_size(duplicate.clone(), 400, 300)
+        .await
+        .unwrap();
+    repo.work()
+        .replace_work_thumbnail_variants(
+            duplicate.clone(),
+            vec![WorkThumbnailVariant {
+                width: 200,
+                height: 150,
+                format: ThumbnailFormat::Webp,
+                path: format!("thumbnails/{}@200w.webp", duplicate.value),
+            }],
+            Some("#112233".into()),
+            Some(0xabcd),
+        )
+        .await
+        .unwrap();
+
+    let result = repo
+        .work_duplicate()
+        .merge(survivor.clone(), duplicate.clone())
+        .await
+        .unwrap();
+
+    assert!(result.thumbnail_moved);
+    let details = repo
+        .work()
+        .find_details_by_work_id(survivor.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(details.dmm.unwrap().store_id, "d_1");
+    assert!(details.like.is_some());
+    assert_eq!(details.last_play_at.unwrap().timestamp(), now.timestamp());
+    assert_eq!(
+        details.thumbnail_variants[0].path,
+        format!("thumbnails/{}@200w.webp", survivor.value)
+    );
+    let lnks = repo
+        .work_lnk()
+        .list_by_work_id(survivor.clone())
+        .await
+        .unwrap();
+    assert_eq!(lnks.len(), 1);
+    assert!(repo
+        .work()
+        .find_details_by_work_id(duplicate.clone())
+        .await
+        .unwrap()
+        .is_none());
+
+    let sources = repo
+        .work_duplicate()
+        .list_duplicate_sources()
+        .await
+        .unwrap();
+    assert_eq!(sources.len(), 1);
+    assert_eq!(sources[0].thumbnail_hash, Some(0xabcd));
+}
+
+#[tokio::test]
+async fn work_duplicate_存在しない作品とは統合しない() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let survivor = repo
+        .work()
+        .upsert(&NewWork {
+            title: "survivor".into(),
+        })
+        .await
+        .unwrap();
+
+    let result = repo
+        .work_duplicate()
+        .merge(survivor, domain::StrId::new("missing".into()))
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,184 @@
+use chrono::NaiveDate;
+use domain::erogamescape::NewErogamescapeInformation;
+use domain::repository::{
+    erogamescape::ErogamescapeRepository, work_override::WorkOverrideRepository,
+    works::WorkRepository, RepositoriesExt,
+};
+use domain::work_override::{WorkOverrideField, WorkOverrides};
+use domain::work_query::{FacetCount, WorkListFilter, WorkQuery};
+use domain::works::{NewWork, Work};
+use domain::StrId;
+
+use super::TestDatabase;
+use crate::sqliterepository::sqliterepository::SqliteRepositories;
+
+fn egs_information(
+    erogamescape_id: i32,
+    ruby: &str,
+    brandname: &str,
+) -> NewErogamescapeInformation {
+    NewErogamescapeInformation {
+        erogamescape_id,
+        gamename_ruby: ruby.into(),
+        sellday: "2024-01-01".into(),
+        is_nukige: false,
+        brandname: brandname.into(),
+        brandname_ruby: brandname.into(),
+    }
+}
+
+async fn add_work(
+    repo: &SqliteRepositories,
+    title: &str,
+    erogamescape_id: i32,
+    ruby: &str,
+    brandname: &str,
+) -> StrId<Work> {
+    let work_id = repo
+        .work()
+        .upsert(&NewWork {
+            title: title.into(),
+        })
+        .await
+        .unwrap();
+    repo.erogamescape()
+        .upsert_information(&egs_information(erogamescape_id, ruby, brandname))
+        .await
+        .unwrap();
+    repo.work()
+        .upsert_erogamescape_map(work_id.clone(), erogamescape_id)
+        .await
+        .unwrap();
+    work_id
+}
+
+#[tokio::test]
+async fn work_override_直した項目は取り込み直しても元の情報より優先する() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = add_work(&repo, "元の名前", 1, "もとのなまえ", "元ブランド").await;
+    let mut overrides = repo.work_override();
+    overrides
+        .set(work_id.clone(), WorkOverrideField::Title, "直した名前")
+        .await
+        .unwrap();
+    overrides
+        .set(
+            work_id.clone(),
+            WorkOverrideField::Brandname,
+            "直したブランド",
+        )
+        .await
+        .unwrap();
+    overrides
+        .set(work_id.clone(), WorkOverrideField::Sellday, "2020-02-02")
+        .await
+        .unwrap();
+    // 同期で批評空間の情報が書き換わっても直した項目はそのまま
+    repo.erogamescape()
+        .upsert_information(&egs_information(1, "もとのなまえ", "同期したブランド"))
+        .await
+        .unwrap();
+
+    let details = repo
+        .work()
+        .find_details_by_work_id(work_id.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(details.work.title, "直した名前");
+    let info = details.erogamescape_information.unwrap();
+    assert_eq!(info.brandname, "直したブランド");
+    assert_eq!(info.sellday, "2020-02-02");
+    assert_eq!(info.gamename_ruby, "もとのなまえ");
+    assert_eq!(
+        details.overrides.sellday,
+        NaiveDate::from_ymd_opt(2020, 2, 2)
+    );
+
+    overrides
+        .clear(work_id.clone(), WorkOverrideField::Title)
+        .await
+        .unwrap();
+    let details = repo
+        .work()
+        .find_details_by_work_id(work_id.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(details.work.title, "元の名前");
+
+    overrides
+        .clear(work_id.clone(), WorkOverrideField::Brandname)
+        .await
+        .unwrap();
+    overrides
+        .clear(work_id.clone(), WorkOverrideField::Sellday)
+        .await
+        .unwrap();
+    assert_eq!(
+        overrides.get_by_work_id(work_id).await.unwrap(),
+        WorkOverrides::default()
+    );
+}
+
+#[tokio::test]
+async fn work_override_絞り込みと並び替えにも直した値を使う() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    add_work(&repo, "A", 1, "ああ", "B1").await;
+    let b = add_work(&repo, "B", 2, "いい", "B1").await;
+    add_work(&repo, "C", 3, "うう", "B2").await;
+    let mut overrides = repo.work_override();
+    overrides
+        .set(b.clone(), WorkOverrideField::TitleRuby, "ええ")
+        .await
+        .unwrap();
+    overrides
+        .set(b.clone(), WorkOverrideField::Brandname, "B2")
+        .await
+        .unwrap();
+
+    let titles = |filter: WorkListFilter| {
+        let repo = &repo;
+        async move {
+            let query = WorkQuery {
+                filter,
+                ..Default::default()
+            };
+            let page = repo.work().query_details(&query).await.unwrap();
+            page.items
+                .into_iter()
+                .map(|d| d.work.title)
+                .collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(titles(WorkListFilter::default()).await, vec!["A", "C", "B"]);
+    assert_eq!(
+        titles(WorkListFilter {
+            brands: vec!["B2".into()],
+            ..Default::default()
+        })
+        .await,
+        vec!["C", "B"]
+    );
+
+    let facets = repo
+        .work()
+        .count_facets(&WorkListFilter::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        facets.brands,
+        vec![
+            FacetCount {
+                value: "B2".to_string(),
+                count: 2
+            },
+            FacetCount {
+                value: "B1".to_string(),
+                count: 1
+            },
+        ]
+    );
+}
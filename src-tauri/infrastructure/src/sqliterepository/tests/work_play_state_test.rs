@@ -0,0 +1,124 @@
+use chrono::{Duration, Local, NaiveDate};
+use domain::play_status::{NewWorkRoute, PlayStatus, WorkPlayState};
+use domain::repository::{
+    work_play_state::WorkPlayStateRepository, work_route::WorkRouteRepository,
+    works::WorkRepository, RepositoriesExt,
+};
+use domain::works::NewWork;
+
+use super::TestDatabase;
+
+#[tokio::test]
+async fn work_play_state_repository_保存した状況が作品の詳細に載る() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+    let details = repo
+        .work()
+        .find_details_by_work_id(work_id.clone())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(details.play_state, None);
+
+    let mut state = WorkPlayState {
+        work_id: work_id.clone(),
+        status: PlayStatus::Playing,
+        score: None,
+        started_on: NaiveDate::from_ymd_opt(2024, 4, 1),
+        finished_on: None,
+    };
+    repo.work_play_state().upsert(&state).await.unwrap();
+    state.status = PlayStatus::Finished;
+    state.score = Some(85);
+    state.finished_on = NaiveDate::from_ymd_opt(2024, 5, 1);
+    repo.work_play_state().upsert(&state).await.unwrap();
+
+    let found = repo
+        .work_play_state()
+        .find_by_work_id(work_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(found, Some(state.clone()));
+    let all = repo.work().list_all_details().await.unwrap();
+    assert_eq!(all[0].play_state, Some(state));
+}
+
+#[tokio::test]
+async fn work_play_state_repository_ステータスの履歴を新しい順に返す() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+    let now = Local::now();
+
+    repo.work_play_state()
+        .insert_status_change(
+            work_id.clone(),
+            PlayStatus::Playing,
+            now - Duration::days(1),
+        )
+        .await
+        .unwrap();
+    repo.work_play_state()
+        .insert_status_change(work_id.clone(), PlayStatus::Finished, now)
+        .await
+        .unwrap();
+
+    let changes = repo
+        .work_play_state()
+        .list_status_changes_by_work_id(work_id)
+        .await
+        .unwrap();
+    let statuses: Vec<_> = changes.iter().map(|c| c.status).collect();
+    assert_eq!(statuses, vec![PlayStatus::Finished, PlayStatus::Playing]);
+}
+
+#[tokio::test]
+async fn work_route_repository_攻略済みを付け外しできる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let work_id = repo
+        .work()
+        .upsert(&NewWork { title: "W".into() })
+        .await
+        .unwrap();
+    let new_route = |name: &str| NewWorkRoute {
+        work_id: work_id.clone(),
+        name: name.into(),
+    };
+
+    let first = repo.work_route().insert(&new_route("A")).await.unwrap();
+    let second = repo.work_route().insert(&new_route("B")).await.unwrap();
+    let again = repo.work_route().insert(&new_route("A")).await.unwrap();
+    assert_eq!(first.value, again.value);
+
+    repo.work_route()
+        .update_completed_at(first.clone(), Some(Local::now()))
+        .await
+        .unwrap();
+    repo.work_route().delete(second).await.unwrap();
+
+    let routes = repo
+        .work_route()
+        .list_by_work_id(work_id.clone())
+        .await
+        .unwrap();
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].name, "A");
+    assert!(routes[0].completed_at.is_some());
+
+    repo.work_route()
+        .update_completed_at(first, None)
+        .await
+        .unwrap();
+    let routes = repo.work_route().list_by_work_id(work_id).await.unwrap();
+    assert!(routes[0].completed_at.is_none());
+}
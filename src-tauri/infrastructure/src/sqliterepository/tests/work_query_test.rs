@@ -0,0 +1,400 @@
+use chrono::{Duration, Local};
+use domain::erogamescape::NewErogamescapeInformation;
+use domain::play_status::{PlayStatus, WorkPlayState};
+use domain::repository::{
+    erogamescape::ErogamescapeRepository,
+    work_like::WorkLikeRepository,
+    work_override::WorkOverrideRepository,
+    work_play_state::WorkPlayStateRepository,
+    works::{DlsiteWorkRepository, DmmWorkRepository, WorkRepository},
+    RepositoriesExt,
+};
+use domain::work_override::WorkOverrideField;
+use domain::work_query::{
+    BoolFacetCount, FacetCount, SortDirection, WorkCursor, WorkListFilter, WorkQuery, WorkSort,
+    WorkSortKey, WorkStore,
+};
+use domain::works::{NewDlsiteWork, NewDmmWork, NewWork, Work};
+use domain::StrId;
+
+use super::TestDatabase;
+use crate::sqliterepository::sqliterepository::SqliteRepositories;
+use crate::sqliterepository::work_query::page_scans;
+
+async fn add_work(
+    repo: &SqliteRepositories,
+    title: &str,
+    egs: Option<(i32, &str, &str, bool)>,
+) -> StrId<Work> {
+    let work_id = repo
+        .work()
+        .upsert(&NewWork {
+            title: title.into(),
+        })
+        .await
+        .unwrap();
+    if let Some((erogamescape_id, ruby, brandname, is_nukige)) = egs {
+        repo.erogamescape()
+            .upsert_information(&NewErogamescapeInformation {
+                erogamescape_id,
+                gamename_ruby: ruby.into(),
+                sellday: "2024-01-01".into(),
+                is_nukige,
+                brandname: brandname.into(),
+                brandname_ruby: brandname.into(),
+            })
+            .await
+            .unwrap();
+        repo.work()
+            .upsert_erogamescape_map(work_id.clone(), erogamescape_id)
+            .await
+            .unwrap();
+    }
+    work_id
+}
+
+async fn collect_pages(repo: &SqliteRepositories, sort: WorkSort) -> Vec<String> {
+    let mut query = WorkQuery {
+        sort,
+        limit: Some(2),
+        ..Default::default()
+    };
+    let mut titles = Vec::new();
+    loop {
+        let page = repo.work().query_details(&query).await.unwrap();
+        assert!(page.items.len() <= 2);
+        titles.extend(page.items.into_iter().map(|d| d.work.title));
+        match page.next_cursor {
+            Some(cursor) => query.after = Some(cursor),
+            None => return titles,
+        }
+    }
+}
+
+#[tokio::test]
+async fn work_query_読みの順にページを送る() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    add_work(&repo, "C", Some((1, "うう", "B1", false))).await;
+    add_work(&repo, "A", Some((2, "ああ", "B1", false))).await;
+    add_work(&repo, "いい", None).await;
+    add_work(&repo, "E", Some((3, "おお", "B2", false))).await;
+    add_work(&repo, "D", Some((4, "ええ", "B2", false))).await;
+
+    let asc = collect_pages(&repo, WorkSort::default()).await;
+    assert_eq!(asc, vec!["A", "いい", "C", "D", "E"]);
+
+    let desc = collect_pages(
+        &repo,
+        WorkSort {
+            key: WorkSortKey::TitleRuby,
+            direction: SortDirection::Desc,
+        },
+    )
+    .await;
+    assert_eq!(desc, vec!["E", "D", "C", "いい", "A"]);
+}
+
+#[tokio::test]
+async fn work_query_直した読みと発売日で並べる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let a = add_work(&repo, "A", Some((1, "ああ", "B1", false))).await;
+    add_work(&repo, "B", Some((2, "いい", "B1", false))).await;
+    let c = add_work(&repo, "C", None).await;
+    repo.work_override()
+        .set(a.clone(), WorkOverrideField::TitleRuby, "うう")
+        .await
+        .unwrap();
+    repo.work_override()
+        .set(a.clone(), WorkOverrideField::Sellday, "2023-06-01")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        collect_pages(&repo, WorkSort::default()).await,
+        vec!["C", "B", "A"]
+    );
+    // 発売日のない作品は向きによらず最後に並ぶ
+    for (direction, expected) in [
+        (SortDirection::Asc, vec!["A", "B", "C"]),
+        (SortDirection::Desc, vec!["B", "A", "C"]),
+    ] {
+        let sort = WorkSort {
+            key: WorkSortKey::Sellday,
+            direction,
+        };
+        assert_eq!(collect_pages(&repo, sort).await, expected, "{direction:?}");
+    }
+
+    repo.work_override()
+        .clear(a, WorkOverrideField::TitleRuby)
+        .await
+        .unwrap();
+    repo.work_override()
+        .set(c, WorkOverrideField::Sellday, "2025-01-01")
+        .await
+        .unwrap();
+    assert_eq!(
+        collect_pages(&repo, WorkSort::default()).await,
+        vec!["C", "A", "B"]
+    );
+    let sort = WorkSort {
+        key: WorkSortKey::Sellday,
+        direction: SortDirection::Desc,
+    };
+    assert_eq!(collect_pages(&repo, sort).await, vec!["C", "B", "A"]);
+}
+
+#[tokio::test]
+async fn work_query_値のない作品は向きによらず最後に並ぶ() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let now = Local::now();
+    for (title, days_ago) in [("old", Some(3)), ("never", None), ("new", Some(1))] {
+        let work_id = add_work(&repo, title, None).await;
+        if let Some(days_ago) = days_ago {
+            repo.work()
+                .update_last_play_at_by_work_id(work_id, now - Duration::days(days_ago))
+                .await
+                .unwrap();
+        }
+    }
+
+    let sort = |direction| WorkSort {
+        key: WorkSortKey::LastPlayAt,
+        direction,
+    };
+    assert_eq!(
+        collect_pages(&repo, sort(SortDirection::Desc)).await,
+        vec!["new", "old", "never"]
+    );
+    assert_eq!(
+        collect_pages(&repo, sort(SortDirection::Asc)).await,
+        vec!["old", "new", "never"]
+    );
+}
+
+#[tokio::test]
+async fn work_query_値のない作品の途中からもページを送れる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let played = add_work(&repo, "played", None).await;
+    repo.work()
+        .update_last_play_at_by_work_id(played, Local::now())
+        .await
+        .unwrap();
+    for title in ["a", "b", "c", "d"] {
+        add_work(&repo, title, None).await;
+    }
+
+    for direction in [SortDirection::Asc, SortDirection::Desc] {
+        let mut titles = collect_pages(
+            &repo,
+            WorkSort {
+                key: WorkSortKey::LastPlayAt,
+                direction,
+            },
+        )
+        .await;
+        assert_eq!(titles[0], "played");
+        titles.sort();
+        assert_eq!(titles, vec!["a", "b", "c", "d", "played"]);
+    }
+}
+
+#[tokio::test]
+async fn work_query_絞り込みと項目ごとの件数() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    let dmm = add_work(&repo, "dmm", Some((1, "a", "B1", true))).await;
+    repo.dmm_work()
+        .upsert(&NewDmmWork {
+            store_id: "d_1".into(),
+            category: "mono".into(),
+            subcategory: "pcgame".into(),
+            work_id: dmm.clone(),
+        })
+        .await
+        .unwrap();
+    let dlsite = add_work(&repo, "dlsite", Some((2, "b", "B2", false))).await;
+    repo.dlsite_work()
+        .upsert(&NewDlsiteWork {
+            store_id: "RJ1".into(),
+            category: "pro".into(),
+            work_id: dlsite.clone(),
+        })
+        .await
+        .unwrap();
+    let egs = add_work(&repo, "egs", Some((3, "c", "B1", false))).await;
+    repo.work_like()
+        .update_like_at_by_work_id(egs.clone(), Some(Local::now()))
+        .await
+        .unwrap();
+    repo.work_play_state()
+        .upsert(&WorkPlayState {
+            work_id: egs.clone(),
+            status: PlayStatus::Finished,
+            score: Some(80),
+            started_on: None,
+            finished_on: None,
+        })
+        .await
+        .unwrap();
+
+    let titles = |filter: WorkListFilter| {
+        let repo = &repo;
+        async move {
+            let query = WorkQuery {
+                filter,
+                ..Default::default()
+            };
+            let page = repo.work().query_details(&query).await.unwrap();
+            page.items
+                .into_iter()
+                .map(|d| d.work.title)
+                .collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(
+        titles(WorkListFilter {
+            stores: vec![WorkStore::Dmm, WorkStore::ErogamescapeOnly],
+            ..Default::default()
+        })
+        .await,
+        vec!["dmm", "egs"]
+    );
+    assert_eq!(
+        titles(WorkListFilter {
+            brands: vec!["B1".into()],
+            nukige: Some(false),
+            ..Default::default()
+        })
+        .await,
+        vec!["egs"]
+    );
+    // 記録のない作品は未プレイとして扱い、点数で絞り込むと点数のない作品は出さない
+    assert_eq!(
+        titles(WorkListFilter {
+            play_statuses: vec![PlayStatus::Unplayed],
+            ..Default::default()
+        })
+        .await,
+        vec!["dmm", "dlsite"]
+    );
+    assert_eq!(
+        titles(WorkListFilter {
+            min_score: Some(70),
+            ..Default::default()
+        })
+        .await,
+        vec!["egs"]
+    );
+
+    let facets = repo
+        .work()
+        .count_facets(&WorkListFilter {
+            brands: vec!["B1".into()],
+            liked: Some(true),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(facets.total, 1);
+    // 自分の絞り込みは外して数える
+    assert_eq!(facets.liked, BoolFacetCount { yes: 1, no: 1 });
+    assert_eq!(
+        facets.brands,
+        vec![FacetCount {
+            value: "B1".to_string(),
+            count: 1
+        }]
+    );
+    assert_eq!(
+        facets.stores,
+        vec![
+            FacetCount {
+                value: WorkStore::Dmm,
+                count: 0
+            },
+            FacetCount {
+                value: WorkStore::Dlsite,
+                count: 0
+            },
+            FacetCount {
+                value: WorkStore::ErogamescapeOnly,
+                count: 1
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn work_query_索引の順に読み並べ替えない() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let cursor = WorkCursor {
+        sort_value: "2024-01-01".into(),
+        work_id: "w".into(),
+    };
+    let cases = [
+        (
+            WorkSortKey::TitleRuby,
+            vec!["idx_works_effective_title_ruby_id"],
+        ),
+        (
+            WorkSortKey::Sellday,
+            vec![
+                "idx_works_effective_sellday_id",
+                "idx_works_effective_sellday_id",
+            ],
+        ),
+        (WorkSortKey::RegisteredAt, vec!["idx_works_created_at_id"]),
+        (
+            WorkSortKey::LastPlayAt,
+            vec![
+                "idx_work_plays_last_play_at_work_id",
+                "sqlite_autoindex_works_1",
+            ],
+        ),
+        (
+            WorkSortKey::InstallAt,
+            vec![
+                "idx_work_installs_install_at_work_id",
+                "sqlite_autoindex_works_1",
+            ],
+        ),
+    ];
+
+    for (key, indexes) in cases {
+        for direction in [SortDirection::Asc, SortDirection::Desc] {
+            let query = WorkQuery {
+                filter: WorkListFilter {
+                    liked: Some(true),
+                    ..Default::default()
+                },
+                sort: WorkSort { key, direction },
+                after: Some(cursor.clone()),
+                limit: Some(20),
+            };
+            let scans = page_scans(&query);
+            assert_eq!(scans.len(), indexes.len(), "{key:?}");
+            for (scan, index) in scans.iter().zip(indexes.iter()) {
+                let sql = scan.build(&query.filter, direction, Some(21)).into_sql();
+                let plan: Vec<(i64, i64, i64, String)> =
+                    sqlx::query_as(&format!("EXPLAIN QUERY PLAN {sql}"))
+                        .fetch_all(&test_db.pool)
+                        .await
+                        .unwrap();
+                let details: Vec<&str> = plan.iter().map(|(_, _, _, d)| d.as_str()).collect();
+                assert!(
+                    details[0].contains(index),
+                    "{key:?} {direction:?}: {details:?}"
+                );
+                assert!(
+                    details.iter().all(|d| !d.contains("TEMP B-TREE")),
+                    "{key:?} {direction:?}: {details:?}"
+                );
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use super::super::TestDatabase;
+use chrono::{Duration, Local};
 use domain::repository::{
     works::{DlsiteWorkRepository, WorkRepository},
     RepositoriesExt,
@@ -71,3 +72,73 @@ async fn dlsite_works_upsert_and_find_by_store_key() {
     assert!(updated_by_store_id.is_some());
     assert_eq!(updated_by_store_id.unwrap().category, "doujin");
 }
+
+#[tokio::test]
+async fn dlsite_works_ライブラリ同期で見つからなかった紐付けに印を付けて消せる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    for (store_id, title) in [("RJ001", "Kept"), ("RJ002", "Refunded")] {
+        let work_id = repo
+            .work()
+            .upsert(&NewWork {
+                title: title.into(),
+            })
+            .await
+            .unwrap();
+        repo.dlsite_work()
+            .upsert(&NewDlsiteWork {
+                store_id: store_id.into(),
+                category: "maniax".into(),
+                work_id,
+            })
+            .await
+            .unwrap();
+    }
+
+    let existing = repo
+        .dlsite_work()
+        .find_existing_store_ids(&["RJ001".into(), "RJ999".into()])
+        .await
+        .unwrap();
+    assert_eq!(existing, vec!["RJ001".to_string()]);
+
+    let started_at = Local::now() - Duration::seconds(5);
+    repo.dlsite_work()
+        .mark_seen(&["RJ001".into()], Local::now())
+        .await
+        .unwrap();
+    let flagged = repo
+        .dlsite_work()
+        .flag_missing_not_seen_since(started_at)
+        .await
+        .unwrap();
+    assert_eq!(flagged, vec!["RJ002".to_string()]);
+
+    let missing = repo.dlsite_work().list_missing().await.unwrap();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].store_id, "RJ002");
+    assert_eq!(missing[0].title, "Refunded");
+    assert_eq!(missing[0].category, "maniax");
+
+    // 印の付いていない紐付けは消さない
+    let deleted = repo
+        .dlsite_work()
+        .delete_missing(&["RJ001".into(), "RJ002".into()])
+        .await
+        .unwrap();
+    assert_eq!(deleted, 1);
+    assert!(repo
+        .dlsite_work()
+        .find_by_store_id("RJ001")
+        .await
+        .unwrap()
+        .is_some());
+    assert!(repo
+        .dlsite_work()
+        .find_by_store_id("RJ002")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repo.dlsite_work().list_missing().await.unwrap().is_empty());
+}
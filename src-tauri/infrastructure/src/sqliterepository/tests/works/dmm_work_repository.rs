@@ -1,4 +1,5 @@
 use super::super::TestDatabase;
+use chrono::Local;
 use domain::repository::{
     works::{DmmWorkRepository, WorkRepository},
     RepositoriesExt,
@@ -268,3 +269,74 @@ async fn dmm_find_by_store_keys_空_単一_複数() {
     mixed_store_ids.sort();
     assert_eq!(mixed_store_ids, vec!["SID-K1", "SID-K2"]);
 }
+
+#[tokio::test]
+async fn dmm_works_count_登録件数を返す() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+    assert_eq!(repo.dmm_work().count().await.unwrap(), 0);
+
+    for sid in ["SID-C1", "SID-C2"] {
+        let work_id = repo
+            .work()
+            .upsert(&NewWork { title: sid.into() })
+            .await
+            .unwrap();
+        repo.dmm_work()
+            .upsert(&NewDmmWork {
+                store_id: sid.into(),
+                category: "software".into(),
+                subcategory: "game".into(),
+                work_id,
+            })
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(repo.dmm_work().count().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn dmm_works_再び見つかった紐付けは印が外れる() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    let work_id = repo
+        .work()
+        .upsert(&NewWork {
+            title: "Title M".into(),
+        })
+        .await
+        .unwrap();
+    repo.dmm_work()
+        .upsert(&NewDmmWork {
+            store_id: "SID-M".into(),
+            category: "software".into(),
+            subcategory: "game".into(),
+            work_id,
+        })
+        .await
+        .unwrap();
+
+    let flagged = repo
+        .dmm_work()
+        .flag_missing_not_seen_since(Local::now())
+        .await
+        .unwrap();
+    assert_eq!(flagged, vec!["SID-M".to_string()]);
+    assert_eq!(repo.dmm_work().list_missing().await.unwrap().len(), 1);
+
+    repo.dmm_work()
+        .mark_seen(&["SID-M".into()], Local::now())
+        .await
+        .unwrap();
+    assert!(repo.dmm_work().list_missing().await.unwrap().is_empty());
+
+    // 同じ秒のうちに始まった次の同期でも、それより前に見えた紐付けは欠落として扱う
+    let flagged = repo
+        .dmm_work()
+        .flag_missing_not_seen_since(Local::now())
+        .await
+        .unwrap();
+    assert_eq!(flagged, vec!["SID-M".to_string()]);
+}
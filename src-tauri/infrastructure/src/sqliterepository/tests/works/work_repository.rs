@@ -9,6 +9,7 @@ use domain::repository::{
     works::{DlsiteWorkRepository, DmmWorkRepository, WorkRepository},
     RepositoriesExt,
 };
+use domain::thumbnail::{ThumbnailFormat, WorkThumbnailVariant};
 use domain::works::{NewDlsiteWork, NewDmmWork, NewWork, NewWorkLike};
 
 #[tokio::test]
@@ -543,6 +544,109 @@ async fn work_upsert_work_thumbnail_size_挿入と更新() {
     assert!(!missing.iter().any(|id| id.value == work_id.value));
 }
 
+#[tokio::test]
+async fn work_replace_work_thumbnail_variants_置き換えと詳細への反映() {
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    let (work_id, other_id) = {
+        let mut r = repo.work();
+        let a = r
+            .upsert(&NewWork {
+                title: "バリアント".into(),
+            })
+            .await
+            .unwrap();
+        let b = r
+            .upsert(&NewWork {
+                title: "未生成".into(),
+            })
+            .await
+            .unwrap();
+        (a, b)
+    };
+    let variant = |width: i32, format: ThumbnailFormat| WorkThumbnailVariant {
+        width,
+        height: width / 2,
+        format,
+        path: format!("/thumbs/{}.{}", width, format.as_str()),
+    };
+
+    {
+        let mut r = repo.work();
+        // サイズだけ記録済みならまだ欠損扱い
+        r.upsert_work_thumbnail_size(work_id.clone(), 400, 200)
+            .await
+            .unwrap();
+        let missing = r.list_work_ids_missing_thumbnail_variants().await.unwrap();
+        assert!(missing.contains(&work_id));
+        assert!(missing.contains(&other_id));
+
+        r.replace_work_thumbnail_variants(
+            work_id.clone(),
+            vec![
+                variant(800, ThumbnailFormat::Webp),
+                variant(400, ThumbnailFormat::Png),
+                variant(200, ThumbnailFormat::Webp),
+            ],
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        // 支配色や知覚ハッシュが取れない（透明な）画像でも、バリアントがあれば済み
+        let missing = r.list_work_ids_missing_thumbnail_variants().await.unwrap();
+        assert!(!missing.contains(&work_id));
+        // 置き換えなので古いバリアントは残らない
+        r.replace_work_thumbnail_variants(
+            work_id.clone(),
+            vec![
+                variant(400, ThumbnailFormat::Png),
+                variant(200, ThumbnailFormat::Webp),
+                variant(400, ThumbnailFormat::Webp),
+            ],
+            Some("#445566".into()),
+            Some(u64::MAX),
+        )
+        .await
+        .unwrap();
+
+        let missing = r.list_work_ids_missing_thumbnail_variants().await.unwrap();
+        assert!(!missing.contains(&work_id));
+        assert!(missing.contains(&other_id));
+    }
+
+    let expected = vec![
+        variant(200, ThumbnailFormat::Webp),
+        variant(400, ThumbnailFormat::Png),
+        variant(400, ThumbnailFormat::Webp),
+    ];
+    let details = {
+        let mut r = repo.work();
+        r.find_details_by_work_id(work_id.clone())
+            .await
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(details.thumbnail_variants, expected);
+    assert_eq!(
+        details.thumbnail_placeholder_color.as_deref(),
+        Some("#445566")
+    );
+    // 既存のサイズは上書きしない
+    assert_eq!(details.thumbnail_size.map(|s| s.width), Some(400));
+
+    let all = {
+        let mut r = repo.work();
+        r.list_all_details().await.unwrap()
+    };
+    let found = all.iter().find(|d| d.work.id == work_id).unwrap();
+    assert_eq!(found.thumbnail_variants, expected);
+    let other = all.iter().find(|d| d.work.id == other_id).unwrap();
+    assert!(other.thumbnail_variants.is_empty());
+    assert!(other.thumbnail_placeholder_color.is_none());
+}
+
 #[tokio::test]
 async fn work_repository_delete_should_remove_work_and_cascade() {
     let test_db = TestDatabase::new().await.unwrap();
@@ -914,9 +1018,11 @@ async fn test_update_install_by_work_id_upsert() {
     // Work を作成
     let work_id = {
         let mut r = repo.work();
-        r.upsert(&domain::works::NewWork::new("インストール日時更新テスト".into()))
-            .await
-            .unwrap()
+        r.upsert(&domain::works::NewWork::new(
+            "インストール日時更新テスト".into(),
+        ))
+        .await
+        .unwrap()
     };
 
     // 初回 INSERT: install_at と original_path を設定
@@ -928,9 +1034,13 @@ async fn test_update_install_by_work_id_upsert() {
 
     {
         let mut r = repo.work();
-        r.update_install_by_work_id(work_id.clone(), first_install_at_dt, first_original_path.clone())
-            .await
-            .unwrap();
+        r.update_install_by_work_id(
+            work_id.clone(),
+            first_install_at_dt,
+            first_original_path.clone(),
+        )
+        .await
+        .unwrap();
     }
 
     // 初回 INSERT が正しく保存されたことを確認
@@ -950,13 +1060,12 @@ async fn test_update_install_by_work_id_upsert() {
     );
 
     // original_path をデータベースから直接確認
-    let row: Option<(Option<String>,)> = sqlx::query_as(
-        "SELECT original_path FROM work_installs WHERE work_id = ?",
-    )
-    .bind(&work_id.value)
-    .fetch_optional(&test_db.pool)
-    .await
-    .unwrap();
+    let row: Option<(Option<String>,)> =
+        sqlx::query_as("SELECT original_path FROM work_installs WHERE work_id = ?")
+            .bind(&work_id.value)
+            .fetch_optional(&test_db.pool)
+            .await
+            .unwrap();
     assert_eq!(
         row.map(|r| r.0).flatten(),
         Some(first_original_path),
@@ -972,9 +1081,13 @@ async fn test_update_install_by_work_id_upsert() {
 
     {
         let mut r = repo.work();
-        r.update_install_by_work_id(work_id.clone(), second_install_at_dt, second_original_path.clone())
-            .await
-            .unwrap();
+        r.update_install_by_work_id(
+            work_id.clone(),
+            second_install_at_dt,
+            second_original_path.clone(),
+        )
+        .await
+        .unwrap();
     }
 
     // UPDATE が正しく反映されたことを確認
@@ -999,16 +1112,75 @@ async fn test_update_install_by_work_id_upsert() {
     );
 
     // original_path の更新を確認
-    let row_after: Option<(Option<String>,)> = sqlx::query_as(
-        "SELECT original_path FROM work_installs WHERE work_id = ?",
-    )
-    .bind(&work_id.value)
-    .fetch_optional(&test_db.pool)
-    .await
-    .unwrap();
+    let row_after: Option<(Option<String>,)> =
+        sqlx::query_as("SELECT original_path FROM work_installs WHERE work_id = ?")
+            .bind(&work_id.value)
+            .fetch_optional(&test_db.pool)
+            .await
+            .unwrap();
     assert_eq!(
         row_after.map(|r| r.0).flatten(),
         Some(second_original_path),
         "更新後の original_path が正しく保存されていること"
     );
 }
+
+#[tokio::test]
+async fn test_update_install_engine_by_work_id_詳細から読める() {
+    use domain::game_engine::GameEngine;
+
+    let test_db = TestDatabase::new().await.unwrap();
+    let repo = test_db.sqlite_repository();
+
+    let work_id = {
+        let mut r = repo.work();
+        r.upsert(&domain::works::NewWork::new("エンジン記録テスト".into()))
+            .await
+            .unwrap()
+    };
+
+    // インストール情報がなければ何も記録しない
+    {
+        let mut r = repo.work();
+        r.update_install_engine_by_work_id(work_id.clone(), Some(GameEngine::Kirikiri))
+            .await
+            .unwrap();
+    }
+    let details = {
+        let mut r = repo.work();
+        r.find_details_by_work_id(work_id.clone())
+            .await
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(details.engine, None);
+
+    {
+        let mut r = repo.work();
+        r.update_install_by_work_id(work_id.clone(), Local::now(), "C:\\test\\game.exe".into())
+            .await
+            .unwrap();
+        r.update_install_engine_by_work_id(work_id.clone(), Some(GameEngine::SiglusEngine))
+            .await
+            .unwrap();
+    }
+
+    let details = {
+        let mut r = repo.work();
+        r.find_details_by_work_id(work_id.clone())
+            .await
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(details.engine, Some(GameEngine::SiglusEngine));
+    let all = {
+        let mut r = repo.work();
+        r.list_all_details().await.unwrap()
+    };
+    assert_eq!(
+        all.iter()
+            .find(|d| d.work.id == work_id)
+            .and_then(|d| d.engine),
+        Some(GameEngine::SiglusEngine)
+    );
+}
@@ -0,0 +1,71 @@
+use crate::sqliterepository::{
+    models::wine_config::WineConfigTable, sqliterepository::RepositoryImpl,
+};
+use domain::{repository::wine_config::WineConfigRepository, wine::WineConfig, works::Work, StrId};
+
+impl WineConfigRepository for RepositoryImpl<WineConfig> {
+    async fn find_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+    ) -> anyhow::Result<Option<WineConfig>> {
+        let row: Option<WineConfigTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let row = sqlx::query_as(
+                        r#"SELECT work_id, runner, runner_path, prefix, env_json
+                        FROM work_wine_configs WHERE work_id = ? LIMIT 1"#,
+                    )
+                    .bind(work_id.value)
+                    .fetch_optional(conn)
+                    .await?;
+                    Ok(row)
+                })
+            })
+            .await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn upsert(&mut self, config: &WineConfig) -> anyhow::Result<()> {
+        let config = config.clone();
+        let env_json = serde_json::to_string(&config.env)?;
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"INSERT INTO work_wine_configs (work_id, runner, runner_path, prefix, env_json)
+                        VALUES (?, ?, ?, ?, ?)
+                        ON CONFLICT(work_id) DO UPDATE SET
+                            runner = excluded.runner,
+                            runner_path = excluded.runner_path,
+                            prefix = excluded.prefix,
+                            env_json = excluded.env_json,
+                            updated_at = CURRENT_TIMESTAMP"#,
+                    )
+                    .bind(config.work_id.value)
+                    .bind(config.runner.as_str())
+                    .bind(config.runner_path)
+                    .bind(config.prefix)
+                    .bind(env_json)
+                    .execute(conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn delete_by_work_id(&mut self, work_id: StrId<Work>) -> anyhow::Result<()> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query("DELETE FROM work_wine_configs WHERE work_id = ?")
+                        .bind(work_id.value)
+                        .execute(conn)
+                        .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+}
@@ -0,0 +1,175 @@
+use domain::{
+    repository::work_duplicate::WorkDuplicateRepository,
+    work_duplicate::{DuplicateCandidate, DuplicateSource, WorkMergeResult},
+    works::Work,
+    StrId,
+};
+
+use crate::sqliterepository::{
+    sqliterepository::RepositoryImpl,
+    work_query::{refresh_effective_columns, EffectiveScope},
+    works::decode_perceptual_hash,
+};
+
+/// 作品を付け替えるだけでよいテーブル
+const MOVABLE_TABLES: [&str; 6] = [
+    "dmm_works",
+    "dlsite_works",
+    "work_lnks",
+    "work_link_pending_exe",
+    "work_download_paths",
+    "work_play_status_history",
+];
+
+/// 作品ごとに高々 1 行（またはキーが重なりうる）テーブル。生き残る側に既にある行は上書きしない
+const SURVIVOR_FIRST_TABLES: [&str; 10] = [
+    "work_erogamescape_map",
+    "work_parent_packs",
+    "work_likes",
+    "work_installs",
+    "work_play_states",
+    "work_wine_configs",
+    "work_routes",
+    "work_save_locations",
+    "remote_launch_allowed_works",
+    "work_overrides",
+];
+
+impl WorkDuplicateRepository for RepositoryImpl<DuplicateCandidate> {
+    async fn list_duplicate_sources(&mut self) -> anyhow::Result<Vec<DuplicateSource>> {
+        let rows: Vec<(String, String, Option<i64>, Option<String>)> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = sqlx::query_as(
+                        r#"SELECT w.id, COALESCE(wo.title, w.title), wem.erogamescape_id, wt.perceptual_hash
+                        FROM works w
+                        LEFT JOIN work_erogamescape_map wem ON wem.work_id = w.id
+                        LEFT JOIN work_thumbnails wt ON wt.work_id = w.id
+                        LEFT JOIN work_overrides wo ON wo.work_id = w.id
+                        ORDER BY w.id ASC"#,
+                    )
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(id, title, erogamescape_id, hash)| DuplicateSource {
+                work_id: StrId::new(id),
+                title,
+                erogamescape_id: erogamescape_id.map(|v| v as i32),
+                thumbnail_hash: hash.as_deref().and_then(decode_perceptual_hash),
+            })
+            .collect())
+    }
+
+    async fn merge(
+        &mut self,
+        survivor: StrId<Work>,
+        duplicate: StrId<Work>,
+    ) -> anyhow::Result<WorkMergeResult> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let (found,): (i64,) =
+                        sqlx::query_as("SELECT COUNT(*) FROM works WHERE id IN (?, ?)")
+                            .bind(&survivor.value)
+                            .bind(&duplicate.value)
+                            .fetch_one(&mut **conn)
+                            .await?;
+                    if found != 2 {
+                        anyhow::bail!("work not found: {} or {}", survivor.value, duplicate.value);
+                    }
+
+                    for table in MOVABLE_TABLES {
+                        sqlx::query(&format!("UPDATE {table} SET work_id = ? WHERE work_id = ?"))
+                            .bind(&survivor.value)
+                            .bind(&duplicate.value)
+                            .execute(&mut **conn)
+                            .await?;
+                    }
+                    // 移せなかった行は作品を消すときに一緒に消える
+                    for table in SURVIVOR_FIRST_TABLES {
+                        sqlx::query(&format!(
+                            "UPDATE OR IGNORE {table} SET work_id = ? WHERE work_id = ?"
+                        ))
+                        .bind(&survivor.value)
+                        .bind(&duplicate.value)
+                        .execute(&mut **conn)
+                        .await?;
+                    }
+
+                    // 最後に遊んだ日時は新しい方
+                    sqlx::query(
+                        r#"INSERT INTO work_plays (work_id, last_play_at)
+                        SELECT ?, last_play_at FROM work_plays WHERE work_id = ?
+                        ON CONFLICT(work_id) DO UPDATE SET
+                            last_play_at = MAX(work_plays.last_play_at, excluded.last_play_at),
+                            updated_at = CURRENT_TIMESTAMP"#,
+                    )
+                    .bind(&survivor.value)
+                    .bind(&duplicate.value)
+                    .execute(&mut **conn)
+                    .await?;
+
+                    // 既定の起動設定は生き残る側を優先する
+                    sqlx::query(
+                        r#"UPDATE work_launch_profiles SET is_default = 0
+                        WHERE work_id = ? AND EXISTS (
+                            SELECT 1 FROM work_launch_profiles
+                            WHERE work_id = ? AND is_default = 1
+                        )"#,
+                    )
+                    .bind(&duplicate.value)
+                    .bind(&survivor.value)
+                    .execute(&mut **conn)
+                    .await?;
+                    sqlx::query("UPDATE work_launch_profiles SET work_id = ? WHERE work_id = ?")
+                        .bind(&survivor.value)
+                        .bind(&duplicate.value)
+                        .execute(&mut **conn)
+                        .await?;
+
+                    // サムネイルは生き残る側に無いときだけ移す。ファイル名も作品 ID なのでパスも書き換える
+                    let (survivor_has_thumbnail,): (bool,) = sqlx::query_as(
+                        "SELECT EXISTS (SELECT 1 FROM work_thumbnails WHERE work_id = ?)",
+                    )
+                    .bind(&survivor.value)
+                    .fetch_one(&mut **conn)
+                    .await?;
+                    let mut thumbnail_moved = false;
+                    if !survivor_has_thumbnail {
+                        thumbnail_moved =
+                            sqlx::query("UPDATE work_thumbnails SET work_id = ? WHERE work_id = ?")
+                                .bind(&survivor.value)
+                                .bind(&duplicate.value)
+                                .execute(&mut **conn)
+                                .await?
+                                .rows_affected()
+                                > 0;
+                        sqlx::query(
+                            r#"UPDATE OR REPLACE work_thumbnail_variants
+                            SET work_id = ?1, path = REPLACE(path, ?2, ?1)
+                            WHERE work_id = ?2"#,
+                        )
+                        .bind(&survivor.value)
+                        .bind(&duplicate.value)
+                        .execute(&mut **conn)
+                        .await?;
+                    }
+
+                    sqlx::query("DELETE FROM works WHERE id = ?")
+                        .bind(&duplicate.value)
+                        .execute(&mut **conn)
+                        .await?;
+                    // 批評空間の対応や直した値を引き継いだかもしれない
+                    refresh_effective_columns(conn, EffectiveScope::Work(&survivor.value)).await?;
+                    Ok(WorkMergeResult { thumbnail_moved })
+                })
+            })
+            .await
+    }
+}
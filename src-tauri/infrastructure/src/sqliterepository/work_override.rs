@@ -0,0 +1,104 @@
+use domain::{
+    repository::work_override::WorkOverrideRepository,
+    work_override::{WorkOverrideField, WorkOverrides},
+    works::Work,
+    StrId,
+};
+
+use crate::sqliterepository::{
+    models::work_override::WorkOverrideTable,
+    sqliterepository::RepositoryImpl,
+    work_query::{refresh_effective_columns, EffectiveScope},
+};
+
+fn column(field: WorkOverrideField) -> &'static str {
+    match field {
+        WorkOverrideField::Title => "title",
+        WorkOverrideField::TitleRuby => "gamename_ruby",
+        WorkOverrideField::Brandname => "brandname",
+        WorkOverrideField::Sellday => "sellday",
+    }
+}
+
+impl WorkOverrideRepository for RepositoryImpl<WorkOverrides> {
+    async fn get_by_work_id(&mut self, work_id: StrId<Work>) -> anyhow::Result<WorkOverrides> {
+        let row: Option<WorkOverrideTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let row = sqlx::query_as(
+                        r#"SELECT title, gamename_ruby, brandname, sellday
+                        FROM work_overrides WHERE work_id = ?"#,
+                    )
+                    .bind(work_id.value)
+                    .fetch_optional(conn)
+                    .await?;
+                    Ok(row)
+                })
+            })
+            .await?;
+        Ok(row.map(Into::into).unwrap_or_default())
+    }
+
+    async fn set(
+        &mut self,
+        work_id: StrId<Work>,
+        field: WorkOverrideField,
+        value: &str,
+    ) -> anyhow::Result<()> {
+        let value = value.to_string();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let column = column(field);
+                    sqlx::query(&format!(
+                        r#"INSERT INTO work_overrides (work_id, {column}) VALUES (?, ?)
+                        ON CONFLICT(work_id) DO UPDATE SET
+                            {column} = excluded.{column},
+                            updated_at = CURRENT_TIMESTAMP"#
+                    ))
+                    .bind(&work_id.value)
+                    .bind(value)
+                    .execute(&mut **conn)
+                    .await?;
+                    refresh_effective_columns(conn, EffectiveScope::Work(&work_id.value)).await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn clear(
+        &mut self,
+        work_id: StrId<Work>,
+        field: WorkOverrideField,
+    ) -> anyhow::Result<()> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let column = column(field);
+                    sqlx::query(&format!(
+                        r#"UPDATE work_overrides
+                        SET {column} = NULL, updated_at = CURRENT_TIMESTAMP
+                        WHERE work_id = ?"#
+                    ))
+                    .bind(&work_id.value)
+                    .execute(&mut **conn)
+                    .await?;
+                    // 直した項目が残っていなければ行ごと消す
+                    sqlx::query(
+                        r#"DELETE FROM work_overrides
+                        WHERE work_id = ?
+                          AND title IS NULL AND gamename_ruby IS NULL
+                          AND brandname IS NULL AND sellday IS NULL"#,
+                    )
+                    .bind(&work_id.value)
+                    .execute(&mut **conn)
+                    .await?;
+                    refresh_effective_columns(conn, EffectiveScope::Work(&work_id.value)).await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+}
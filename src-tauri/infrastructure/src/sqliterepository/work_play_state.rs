@@ -0,0 +1,119 @@
+use chrono::{DateTime, Local};
+use domain::{
+    play_status::{PlayStatus, PlayStatusChange, WorkPlayState},
+    repository::work_play_state::WorkPlayStateRepository,
+    works::Work,
+    Id, StrId,
+};
+
+use crate::sqliterepository::{
+    models::work_play_state::{PlayStatusChangeTable, WorkPlayStateTable},
+    sqliterepository::RepositoryImpl,
+};
+
+impl WorkPlayStateRepository for RepositoryImpl<WorkPlayState> {
+    async fn find_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+    ) -> anyhow::Result<Option<WorkPlayState>> {
+        let row: Option<WorkPlayStateTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let row = sqlx::query_as(
+                        r#"SELECT work_id, status, score, started_on, finished_on
+                        FROM work_play_states WHERE work_id = ?"#,
+                    )
+                    .bind(work_id.value)
+                    .fetch_optional(conn)
+                    .await?;
+                    Ok(row)
+                })
+            })
+            .await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn upsert(&mut self, state: &WorkPlayState) -> anyhow::Result<()> {
+        let state = state.clone();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"INSERT INTO work_play_states (work_id, status, score, started_on, finished_on)
+                        VALUES (?, ?, ?, ?, ?)
+                        ON CONFLICT(work_id) DO UPDATE SET
+                            status = excluded.status,
+                            score = excluded.score,
+                            started_on = excluded.started_on,
+                            finished_on = excluded.finished_on,
+                            updated_at = CURRENT_TIMESTAMP"#,
+                    )
+                    .bind(state.work_id.value)
+                    .bind(state.status.as_str())
+                    .bind(state.score.map(|v| v as i64))
+                    .bind(state.started_on)
+                    .bind(state.finished_on)
+                    .execute(conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn insert_status_change(
+        &mut self,
+        work_id: StrId<Work>,
+        status: PlayStatus,
+        changed_at: DateTime<Local>,
+    ) -> anyhow::Result<Id<PlayStatusChange>> {
+        let id: i64 = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let (id,): (i64,) = sqlx::query_as(
+                        r#"INSERT INTO work_play_status_history (work_id, status, changed_at)
+                        VALUES (?, ?, ?)
+                        RETURNING id"#,
+                    )
+                    .bind(work_id.value)
+                    .bind(status.as_str())
+                    .bind(changed_at.naive_utc())
+                    .fetch_one(conn)
+                    .await?;
+                    Ok(id)
+                })
+            })
+            .await?;
+        Ok(Id::new(id as i32))
+    }
+
+    async fn list_status_changes_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+    ) -> anyhow::Result<Vec<PlayStatusChange>> {
+        let rows: Vec<PlayStatusChangeTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = sqlx::query_as(
+                        r#"SELECT id, work_id, status, changed_at
+                        FROM work_play_status_history
+                        WHERE work_id = ?
+                        ORDER BY changed_at DESC, id DESC"#,
+                    )
+                    .bind(work_id.value)
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        // 読めない行は飛ばす
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| row.try_into().ok())
+            .collect())
+    }
+}
@@ -0,0 +1,530 @@
+use std::collections::HashMap;
+
+use domain::{
+    play_status::PlayStatus,
+    thumbnail::WorkThumbnailVariant,
+    work_query::{
+        BoolFacetCount, FacetCount, SortDirection, WorkCursor, WorkFacets, WorkListFilter,
+        WorkPage, WorkQuery, WorkSortKey, WorkStore,
+    },
+    works::{Work, WorkDetails},
+};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection};
+
+use crate::sqliterepository::{
+    models::works::{WorkDetailsRow, WorkThumbnailVariantTable},
+    sqliterepository::RepositoryImpl,
+    works::{merge_details_rows, WORK_DETAILS_SELECT},
+};
+
+/// 詳細を読むときに1回の IN 句へ並べる作品 ID の数
+const DETAILS_CHUNK_SIZE: usize = 500;
+
+/// 絞り込みと並び替えに使う1対1の表。複数行になるストアの表は EXISTS で見る。
+/// 批評空間の情報や直した値は `works` の `effective_*` 列で見るので結合しない
+const FILTER_JOINS: [(&str, &str); 5] = [
+    ("wi", "LEFT JOIN work_installs AS wi ON wi.work_id = w.id"),
+    ("wp", "LEFT JOIN work_plays AS wp ON wp.work_id = w.id"),
+    (
+        "ps",
+        "LEFT JOIN work_play_states AS ps ON ps.work_id = w.id",
+    ),
+    (
+        "wpp",
+        "LEFT JOIN work_parent_packs wpp ON wpp.work_id = w.id",
+    ),
+    ("wl", "LEFT JOIN work_likes wl ON wl.work_id = w.id"),
+];
+
+/// `FILTER_JOINS` をつないだ FROM 句。`driving` を渡すとその表を先頭にして `works` と内部結合し、
+/// その表の索引の順に読めるようにする
+fn filter_from(driving: Option<SortTable>) -> String {
+    let mut from = match driving {
+        Some(SortTable { table, alias }) => {
+            format!("FROM {table} AS {alias}\nJOIN works w ON w.id = {alias}.work_id")
+        }
+        None => "FROM works w".to_string(),
+    };
+    for (alias, join) in FILTER_JOINS {
+        if driving.is_some_and(|t| t.alias == alias) {
+            continue;
+        }
+        from.push('\n');
+        from.push_str(join);
+    }
+    from.push_str("\nWHERE 1 = 1\n");
+    from
+}
+
+const HAS_DMM: &str = "EXISTS (SELECT 1 FROM dmm_works sdw WHERE sdw.work_id = w.id)";
+const HAS_DLSITE: &str = "EXISTS (SELECT 1 FROM dlsite_works slw WHERE slw.work_id = w.id)";
+const IS_EROGAMESCAPE_ONLY: &str = "(NOT EXISTS (SELECT 1 FROM dmm_works sdw WHERE sdw.work_id = w.id) AND NOT EXISTS (SELECT 1 FROM dlsite_works slw WHERE slw.work_id = w.id))";
+const PLAY_STATUS: &str = "COALESCE(ps.status, 'unplayed')";
+const BRANDNAME: &str = "w.effective_brandname";
+const SELLDAY: &str = "w.effective_sellday";
+
+/// `works` の `effective_*` 列を計算し直す作品
+pub(crate) enum EffectiveScope<'a> {
+    Work(&'a str),
+    Erogamescape(i32),
+}
+
+/// 利用者が直した値を批評空間の値より優先した読み・ブランド・発売日と抜きゲーかどうかを
+/// `works` の列へ書き戻す。作品の登録と、直した値や批評空間の情報を書いたときに呼ぶ
+pub(crate) async fn refresh_effective_columns(
+    conn: &mut SqliteConnection,
+    scope: EffectiveScope<'_>,
+) -> anyhow::Result<()> {
+    let mut qb = QueryBuilder::<Sqlite>::new(
+        r#"UPDATE works SET
+            effective_title_ruby = v.title_ruby,
+            effective_brandname = v.brandname,
+            effective_sellday = v.sellday,
+            effective_is_nukige = v.is_nukige
+        FROM (
+            SELECT w.id AS work_id,
+                COALESCE(NULLIF(wo.gamename_ruby, ''), NULLIF(ei.gamename_ruby, ''), wo.title, w.title) AS title_ruby,
+                COALESCE(wo.brandname, ei.brandname) AS brandname,
+                NULLIF(COALESCE(wo.sellday, ei.sellday), '') AS sellday,
+                COALESCE(ei.is_nukige, 0) AS is_nukige
+            FROM works w
+            LEFT JOIN work_erogamescape_map wem ON wem.work_id = w.id
+            LEFT JOIN erogamescape_information ei ON ei.id = wem.erogamescape_id
+            LEFT JOIN work_overrides wo ON wo.work_id = w.id
+            WHERE "#,
+    );
+    match scope {
+        EffectiveScope::Work(work_id) => qb.push("w.id = ").push_bind(work_id.to_string()),
+        EffectiveScope::Erogamescape(id) => qb.push("wem.erogamescape_id = ").push_bind(id),
+    };
+    qb.push(") AS v WHERE works.id = v.work_id");
+    qb.build().execute(conn).await?;
+    Ok(())
+}
+
+/// 件数を数えるときに絞り込みを外す項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FacetDimension {
+    Store,
+    Liked,
+    Installed,
+    Nukige,
+    Brand,
+    PlayStatus,
+}
+
+fn store_condition(store: WorkStore) -> &'static str {
+    match store {
+        WorkStore::Dmm => HAS_DMM,
+        WorkStore::Dlsite => HAS_DLSITE,
+        WorkStore::ErogamescapeOnly => IS_EROGAMESCAPE_ONLY,
+    }
+}
+
+fn push_presence(qb: &mut QueryBuilder<'_, Sqlite>, column: &str, present: bool) {
+    qb.push(format!(
+        " AND {column} IS {}NULL",
+        if present { "NOT " } else { "" }
+    ));
+}
+
+/// `filter_from` の後ろに絞り込みの条件を足す。`skip` の項目では絞り込まない
+fn push_filter(
+    qb: &mut QueryBuilder<'_, Sqlite>,
+    filter: &WorkListFilter,
+    skip: Option<FacetDimension>,
+) {
+    let keep = |dimension| skip != Some(dimension);
+    if keep(FacetDimension::Store) && !filter.stores.is_empty() {
+        let conditions: Vec<&str> = filter.stores.iter().map(|s| store_condition(*s)).collect();
+        qb.push(format!(" AND ({})", conditions.join(" OR ")));
+    }
+    if let Some(liked) = filter.liked.filter(|_| keep(FacetDimension::Liked)) {
+        push_presence(qb, "wl.work_id", liked);
+    }
+    if let Some(installed) = filter.installed.filter(|_| keep(FacetDimension::Installed)) {
+        push_presence(qb, "wi.work_id", installed);
+    }
+    if let Some(nukige) = filter.nukige.filter(|_| keep(FacetDimension::Nukige)) {
+        qb.push(" AND w.effective_is_nukige = ");
+        qb.push_bind(i32::from(nukige));
+    }
+    if keep(FacetDimension::Brand) && !filter.brands.is_empty() {
+        qb.push(format!(" AND {BRANDNAME} IN ("));
+        let mut separated = qb.separated(", ");
+        for brand in filter.brands.iter() {
+            separated.push_bind(brand.clone());
+        }
+        qb.push(")");
+    }
+    if let Some(from) = filter.sellday_from {
+        qb.push(format!(" AND {SELLDAY} >= "));
+        qb.push_bind(from.format("%Y-%m-%d").to_string());
+    }
+    if let Some(to) = filter.sellday_to {
+        qb.push(format!(" AND {SELLDAY} <= "));
+        qb.push_bind(to.format("%Y-%m-%d").to_string());
+    }
+    if let Some(pack) = filter.parent_pack.as_ref() {
+        qb.push(" AND wpp.parent_pack_store_id = ");
+        qb.push_bind(pack.store_id.clone());
+        qb.push(" AND wpp.parent_pack_category = ");
+        qb.push_bind(pack.category.clone());
+        qb.push(" AND wpp.parent_pack_subcategory = ");
+        qb.push_bind(pack.subcategory.clone());
+    }
+    if keep(FacetDimension::PlayStatus) && !filter.play_statuses.is_empty() {
+        qb.push(format!(" AND {PLAY_STATUS} IN ("));
+        let mut separated = qb.separated(", ");
+        for status in filter.play_statuses.iter() {
+            separated.push_bind(status.as_str());
+        }
+        qb.push(")");
+    }
+    if let Some(min) = filter.min_score {
+        qb.push(" AND ps.score >= ");
+        qb.push_bind(min);
+    }
+    if let Some(max) = filter.max_score {
+        qb.push(" AND ps.score <= ");
+        qb.push_bind(max);
+    }
+}
+
+/// 並び替えの値を持つ1対1の表
+#[derive(Debug, Clone, Copy)]
+struct SortTable {
+    table: &'static str,
+    alias: &'static str,
+}
+
+/// 作品一覧の並び替え方。`(値, 作品 ID)` の索引の順に読み、値のない作品は続けて `works` の主キーの順に読む
+struct SortPlan {
+    /// 値を持つ表。表に行がない作品は値がないものとする。None なら `works` の列
+    table: Option<SortTable>,
+    value: &'static str,
+    id: &'static str,
+    /// `works` の列が NULL になりうる
+    nullable: bool,
+}
+
+impl SortPlan {
+    /// 値のない作品の条件
+    fn missing(&self) -> Option<String> {
+        match self.table {
+            Some(table) => Some(format!("{}.work_id IS NULL", table.alias)),
+            None if self.nullable => Some(format!("{} IS NULL", self.value)),
+            None => None,
+        }
+    }
+}
+
+fn sort_plan(key: WorkSortKey) -> SortPlan {
+    match key {
+        WorkSortKey::TitleRuby => SortPlan {
+            table: None,
+            value: "w.effective_title_ruby",
+            id: "w.id",
+            nullable: false,
+        },
+        WorkSortKey::Sellday => SortPlan {
+            table: None,
+            value: SELLDAY,
+            id: "w.id",
+            nullable: true,
+        },
+        WorkSortKey::RegisteredAt => SortPlan {
+            table: None,
+            value: "w.created_at",
+            id: "w.id",
+            nullable: false,
+        },
+        WorkSortKey::LastPlayAt => SortPlan {
+            table: Some(SortTable {
+                table: "work_plays",
+                alias: "wp",
+            }),
+            value: "wp.last_play_at",
+            id: "wp.work_id",
+            nullable: false,
+        },
+        WorkSortKey::InstallAt => SortPlan {
+            table: Some(SortTable {
+                table: "work_installs",
+                alias: "wi",
+            }),
+            value: "wi.install_at",
+            id: "wi.work_id",
+            nullable: false,
+        },
+    }
+}
+
+/// 1ページ分の作品 ID を読む SELECT の1回分
+pub(crate) struct PageScan {
+    from: String,
+    /// 絞り込みに足す条件
+    condition: Option<String>,
+    /// 並び替えの値。None のときは値のない作品を ID の順に読み、カーソルの値は空文字にする
+    value: Option<String>,
+    id: &'static str,
+    after: Option<WorkCursor>,
+}
+
+impl PageScan {
+    pub(crate) fn build<'a>(
+        &self,
+        filter: &WorkListFilter,
+        direction: SortDirection,
+        limit: Option<usize>,
+    ) -> QueryBuilder<'a, Sqlite> {
+        let (order, compare) = match direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+        let id = self.id;
+        let mut qb = QueryBuilder::new(format!(
+            "SELECT {id} AS id, CAST({} AS TEXT) AS sort_value {}",
+            self.value.as_deref().unwrap_or("''"),
+            self.from
+        ));
+        push_filter(&mut qb, filter, None);
+        if let Some(condition) = self.condition.as_ref() {
+            qb.push(format!(" AND {condition}"));
+        }
+        match (self.after.clone(), self.value.as_ref()) {
+            (Some(after), Some(value)) => {
+                qb.push(format!(" AND ({value}, {id}) {compare} ("));
+                qb.push_bind(after.sort_value);
+                qb.push(", ");
+                qb.push_bind(after.work_id);
+                qb.push(")");
+            }
+            (Some(after), None) => {
+                qb.push(format!(" AND {id} {compare} "));
+                qb.push_bind(after.work_id);
+            }
+            (None, _) => {}
+        }
+        match self.value.as_ref() {
+            Some(value) => qb.push(format!(" ORDER BY {value} {order}, {id} {order}")),
+            None => qb.push(format!(" ORDER BY {id} {order}")),
+        };
+        if let Some(limit) = limit {
+            qb.push(" LIMIT ");
+            qb.push_bind(limit as i64);
+        }
+        qb
+    }
+}
+
+/// 並び順どおりに読む SELECT を順に返す。前のものを読み切ったら次を読む
+pub(crate) fn page_scans(query: &WorkQuery) -> Vec<PageScan> {
+    let plan = sort_plan(query.sort.key);
+    let missing = plan.missing();
+    let after = query.after.clone();
+    // 値のない作品の途中から続けるときは、値のある作品を読み終えている
+    let in_missing = missing.is_some() && after.as_ref().is_some_and(|a| a.sort_value.is_empty());
+    // 表に行がない作品は内部結合で外れるので、`works` の列が NULL になりうるときだけ条件を足す
+    let present = match (plan.table, missing.as_ref()) {
+        (None, Some(_)) => Some(format!("{} IS NOT NULL", plan.value)),
+        _ => None,
+    };
+    let mut scans = Vec::new();
+    if !in_missing {
+        scans.push(PageScan {
+            from: filter_from(plan.table),
+            condition: present,
+            value: Some(plan.value.to_string()),
+            id: plan.id,
+            after: after.clone(),
+        });
+    }
+    if let Some(missing) = missing {
+        scans.push(PageScan {
+            from: filter_from(None),
+            condition: Some(missing),
+            value: None,
+            id: "w.id",
+            after: after.filter(|_| in_missing),
+        });
+    }
+    scans
+}
+
+impl RepositoryImpl<Work> {
+    pub(super) async fn query_work_details(
+        &mut self,
+        query: &WorkQuery,
+    ) -> anyhow::Result<WorkPage> {
+        let query = query.clone();
+        let page_size = query.page_size();
+        let mut rows: Vec<(String, String)> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let mut rows: Vec<(String, String)> = Vec::new();
+                    for scan in page_scans(&query) {
+                        // 1件多く読んで続きがあるかを見る
+                        let limit = page_size.map(|p| p as usize + 1 - rows.len());
+                        if limit == Some(0) {
+                            break;
+                        }
+                        let mut qb = scan.build(&query.filter, query.sort.direction, limit);
+                        let scanned: Vec<(String, String)> =
+                            qb.build_query_as().fetch_all(&mut **conn).await?;
+                        rows.extend(scanned);
+                    }
+                    Ok(rows)
+                })
+            })
+            .await?;
+
+        let next_cursor = match page_size {
+            Some(page_size) if rows.len() > page_size as usize => {
+                rows.truncate(page_size as usize);
+                rows.last().map(|(work_id, sort_value)| WorkCursor {
+                    sort_value: sort_value.clone(),
+                    work_id: work_id.clone(),
+                })
+            }
+            _ => None,
+        };
+        let ids: Vec<String> = rows.into_iter().map(|(id, _)| id).collect();
+        let mut details = self.list_details_by_ids(&ids).await?;
+        let items = ids.iter().filter_map(|id| details.remove(id)).collect();
+        Ok(WorkPage { items, next_cursor })
+    }
+
+    async fn list_details_by_ids(
+        &mut self,
+        ids: &[String],
+    ) -> anyhow::Result<HashMap<String, WorkDetails>> {
+        let mut details = HashMap::new();
+        for chunk in ids.chunks(DETAILS_CHUNK_SIZE) {
+            let chunk = chunk.to_vec();
+            let (rows, variants) = self
+                .executor
+                .with_conn(|conn| {
+                    Box::pin(async move {
+                        let mut qb = QueryBuilder::new(format!("{WORK_DETAILS_SELECT} WHERE w.id IN ("));
+                        let mut separated = qb.separated(", ");
+                        for id in chunk.iter() {
+                            separated.push_bind(id.clone());
+                        }
+                        qb.push(")");
+                        let rows: Vec<WorkDetailsRow> =
+                            qb.build_query_as().fetch_all(&mut **conn).await?;
+
+                        let mut qb = QueryBuilder::new(
+                            "SELECT work_id, width, height, format, path FROM work_thumbnail_variants WHERE work_id IN (",
+                        );
+                        let mut separated = qb.separated(", ");
+                        for id in chunk.iter() {
+                            separated.push_bind(id.clone());
+                        }
+                        qb.push(") ORDER BY work_id ASC, width ASC, format ASC");
+                        let variants: Vec<WorkThumbnailVariantTable> =
+                            qb.build_query_as().fetch_all(&mut **conn).await?;
+                        Ok((rows, variants))
+                    })
+                })
+                .await?;
+
+            let mut merged = merge_details_rows(rows);
+            for v in variants.into_iter() {
+                let work_id = v.work_id.clone();
+                let variant: Option<WorkThumbnailVariant> = v.into_domain();
+                if let (Some(entry), Some(variant)) = (merged.get_mut(&work_id), variant) {
+                    entry.thumbnail_variants.push(variant);
+                }
+            }
+            details.extend(merged);
+        }
+        Ok(details)
+    }
+
+    pub(super) async fn count_work_facets(
+        &mut self,
+        filter: &WorkListFilter,
+    ) -> anyhow::Result<WorkFacets> {
+        let filter = filter.clone();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let from = filter_from(None);
+                    let mut qb = QueryBuilder::new(format!("SELECT COUNT(*) {from}"));
+                    push_filter(&mut qb, &filter, None);
+                    let (total,): (i64,) = qb.build_query_as().fetch_one(&mut **conn).await?;
+
+                    let mut qb = QueryBuilder::new(format!(
+                        "SELECT COALESCE(SUM({HAS_DMM}), 0), COALESCE(SUM({HAS_DLSITE}), 0), COALESCE(SUM({IS_EROGAMESCAPE_ONLY}), 0) {from}"
+                    ));
+                    push_filter(&mut qb, &filter, Some(FacetDimension::Store));
+                    let (dmm, dlsite, erogamescape_only): (i64, i64, i64) =
+                        qb.build_query_as().fetch_one(&mut **conn).await?;
+                    let stores = vec![
+                        FacetCount { value: WorkStore::Dmm, count: dmm },
+                        FacetCount { value: WorkStore::Dlsite, count: dlsite },
+                        FacetCount {
+                            value: WorkStore::ErogamescapeOnly,
+                            count: erogamescape_only,
+                        },
+                    ];
+
+                    let mut bools = Vec::new();
+                    for (dimension, condition) in [
+                        (FacetDimension::Liked, "wl.work_id IS NOT NULL"),
+                        (FacetDimension::Installed, "wi.work_id IS NOT NULL"),
+                        (FacetDimension::Nukige, "w.effective_is_nukige = 1"),
+                    ] {
+                        let mut qb = QueryBuilder::new(format!(
+                            "SELECT COUNT(*), COALESCE(SUM({condition}), 0) {from}"
+                        ));
+                        push_filter(&mut qb, &filter, Some(dimension));
+                        let (count, yes): (i64, i64) =
+                            qb.build_query_as().fetch_one(&mut **conn).await?;
+                        bools.push(BoolFacetCount { yes, no: count - yes });
+                    }
+
+                    let mut qb = QueryBuilder::new(format!(
+                        "SELECT {PLAY_STATUS} AS status, COUNT(*) {from}"
+                    ));
+                    push_filter(&mut qb, &filter, Some(FacetDimension::PlayStatus));
+                    qb.push(" GROUP BY status ORDER BY status ASC");
+                    let rows: Vec<(String, i64)> = qb.build_query_as().fetch_all(&mut **conn).await?;
+                    let play_statuses = rows
+                        .into_iter()
+                        .filter_map(|(status, count)| {
+                            status
+                                .parse::<PlayStatus>()
+                                .ok()
+                                .map(|value| FacetCount { value, count })
+                        })
+                        .collect();
+
+                    let mut qb = QueryBuilder::new(format!(
+                        "SELECT {BRANDNAME} AS brand, COUNT(*) {from} AND COALESCE({BRANDNAME}, '') <> ''"
+                    ));
+                    push_filter(&mut qb, &filter, Some(FacetDimension::Brand));
+                    qb.push(" GROUP BY brand ORDER BY COUNT(*) DESC, brand ASC");
+                    let rows: Vec<(String, i64)> = qb.build_query_as().fetch_all(&mut **conn).await?;
+                    let brands = rows
+                        .into_iter()
+                        .map(|(value, count)| FacetCount { value, count })
+                        .collect();
+
+                    Ok(WorkFacets {
+                        total,
+                        stores,
+                        liked: bools[0],
+                        installed: bools[1],
+                        nukige: bools[2],
+                        play_statuses,
+                        brands,
+                    })
+                })
+            })
+            .await
+    }
+}
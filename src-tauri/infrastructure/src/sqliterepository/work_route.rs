@@ -0,0 +1,89 @@
+use chrono::{DateTime, Local};
+use domain::{
+    play_status::{NewWorkRoute, WorkRoute},
+    repository::work_route::WorkRouteRepository,
+    works::Work,
+    Id, StrId,
+};
+
+use crate::sqliterepository::{
+    models::work_route::WorkRouteTable, sqliterepository::RepositoryImpl,
+};
+
+impl WorkRouteRepository for RepositoryImpl<WorkRoute> {
+    async fn list_by_work_id(&mut self, work_id: StrId<Work>) -> anyhow::Result<Vec<WorkRoute>> {
+        let rows: Vec<WorkRouteTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = sqlx::query_as(
+                        r#"SELECT id, work_id, name, completed_at
+                        FROM work_routes WHERE work_id = ? ORDER BY id ASC"#,
+                    )
+                    .bind(work_id.value)
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn insert(&mut self, route: &NewWorkRoute) -> anyhow::Result<Id<WorkRoute>> {
+        let route = route.clone();
+        let id: i64 = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    // 既にあれば攻略済みかどうかは変えずに ID だけ返す
+                    let (id,): (i64,) = sqlx::query_as(
+                        r#"INSERT INTO work_routes (work_id, name)
+                        VALUES (?, ?)
+                        ON CONFLICT(work_id, name) DO UPDATE SET name = excluded.name
+                        RETURNING id"#,
+                    )
+                    .bind(route.work_id.value)
+                    .bind(route.name)
+                    .fetch_one(conn)
+                    .await?;
+                    Ok(id)
+                })
+            })
+            .await?;
+        Ok(Id::new(id as i32))
+    }
+
+    async fn update_completed_at(
+        &mut self,
+        id: Id<WorkRoute>,
+        completed_at: Option<DateTime<Local>>,
+    ) -> anyhow::Result<()> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query("UPDATE work_routes SET completed_at = ? WHERE id = ?")
+                        .bind(completed_at.map(|v| v.naive_utc()))
+                        .bind(id.value as i64)
+                        .execute(conn)
+                        .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
+    async fn delete(&mut self, id: Id<WorkRoute>) -> anyhow::Result<()> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query("DELETE FROM work_routes WHERE id = ?")
+                        .bind(id.value as i64)
+                        .execute(conn)
+                        .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+}
@@ -1,22 +1,158 @@
 use std::collections::BTreeMap;
 
+use chrono::{DateTime, Local};
 use domain::repository::work_lnk::{NewWorkLnk, WorkLnk as DomainWorkLnk, WorkLnkRepository};
 use domain::work_link_pending_exe::WorkLinkPendingExeRepository;
 use domain::{
+    game_engine::GameEngine,
     repository::works::{DlsiteWorkRepository, DmmWorkRepository, WorkRepository},
+    thumbnail::WorkThumbnailVariant,
+    work_query::{WorkFacets, WorkListFilter, WorkPage, WorkQuery},
     works::{
-        DlsiteWork, DmmWork, NewDlsiteWork, NewDmmWork, NewWork, NewWorkLike, Work, WorkDetails,
-        WorkLike,
+        DlsiteWork, DmmWork, MissingStoreWork, NewDlsiteWork, NewDmmWork, NewWork, NewWorkLike,
+        Work, WorkDetails, WorkLike,
     },
     Id, StrId,
 };
-use sqlx::query_as;
+use sqlx::{query_as, QueryBuilder};
 
 use crate::sqliterepository::{
-    models::works::{WorkDetailsRow, WorkLinkPendingExeRow, WorkLnkRow, WorkTable},
-    sqliterepository::RepositoryImpl,
+    models::works::{
+        MissingStoreWorkRow, WorkDetailsRow, WorkLinkPendingExeRow, WorkLnkRow, WorkTable,
+        WorkThumbnailVariantTable,
+    },
+    sqliterepository::{to_sqlite_utc, RepositoryImpl},
+    work_query::{refresh_effective_columns, EffectiveScope},
 };
 
+/// 作品詳細の SELECT 句と FROM 句。複数行になる dmm_works と dlsite_works は呼び出し側でまとめる
+pub(super) const WORK_DETAILS_SELECT: &str = r#"
+SELECT
+    w.id   as work_id,
+    w.title as work_title,
+    w.created_at as ce_created_at,
+    dw.id   as dmm_id,
+    dw.store_id as dmm_store_id,
+    dw.category as dmm_category,
+    dw.subcategory as dmm_subcategory,
+    wpp.parent_pack_store_id as parent_dmm_store_id,
+    wpp.parent_pack_category as parent_dmm_category,
+    wpp.parent_pack_subcategory as parent_dmm_subcategory,
+    NULL as ce_id,
+    wem.id as egs_id,
+    wem.erogamescape_id as egs_erogamescape_id,
+    wem.created_at as egs_created_at,
+    wem.updated_at as egs_updated_at,
+    ei.gamename_ruby as egs_info_gamename_ruby,
+    ei.brandname as egs_info_brandname,
+    ei.brandname_ruby as egs_info_brandname_ruby,
+    ei.sellday as egs_info_sellday,
+    ei.is_nukige as egs_info_is_nukige,
+    ei.created_at as egs_info_created_at,
+    ei.updated_at as egs_info_updated_at,
+    wt.thumbnail_width as cet_width,
+    wt.thumbnail_height as cet_height,
+    wt.placeholder_color as cet_placeholder_color,
+    wi.install_at as install_install_at,
+    wi.original_path as install_original_path,
+    wi.engine as install_engine,
+    wp.last_play_at as play_last_play_at,
+    ps.status as play_state_status,
+    ps.score as play_state_score,
+    ps.started_on as play_state_started_on,
+    ps.finished_on as play_state_finished_on,
+    lw.id   as dlsite_id,
+    lw.store_id as dlsite_store_id,
+    lw.category as dlsite_category,
+    (SELECT id FROM work_download_paths wdp WHERE wdp.work_id = w.id ORDER BY id DESC LIMIT 1) as latest_path_id,
+    (SELECT download_path FROM work_download_paths wdp WHERE wdp.work_id = w.id ORDER BY id DESC LIMIT 1) as latest_path_download_path,
+    wl.id as like_id,
+    wl.like_at as like_like_at,
+    wl.created_at as like_created_at,
+    wl.updated_at as like_updated_at,
+    wo.title as override_title,
+    wo.gamename_ruby as override_gamename_ruby,
+    wo.brandname as override_brandname,
+    wo.sellday as override_sellday
+FROM works w
+LEFT JOIN dmm_works dw ON dw.work_id = w.id
+LEFT JOIN work_erogamescape_map wem ON wem.work_id = w.id
+LEFT JOIN erogamescape_information ei ON ei.id = wem.erogamescape_id
+LEFT JOIN work_thumbnails AS wt ON wt.work_id = w.id
+LEFT JOIN work_installs AS wi ON wi.work_id = w.id
+LEFT JOIN work_plays AS wp ON wp.work_id = w.id
+LEFT JOIN work_play_states AS ps ON ps.work_id = w.id
+LEFT JOIN work_parent_packs wpp ON wpp.work_id = w.id
+LEFT JOIN dlsite_works lw ON lw.work_id = w.id
+LEFT JOIN work_likes wl ON wl.work_id = w.id
+LEFT JOIN work_overrides wo ON wo.work_id = w.id
+"#;
+
+/// 作品ごとにまとめる。複数のストア情報があれば最初の行を使う
+pub(super) fn merge_details_rows(rows: Vec<WorkDetailsRow>) -> BTreeMap<String, WorkDetails> {
+    let mut map: BTreeMap<String, WorkDetails> = BTreeMap::new();
+    for r in rows.into_iter() {
+        let details: WorkDetails = r.into();
+        let key = details.work.id.value.clone();
+        match map.get_mut(&key) {
+            Some(entry) => {
+                if entry.dmm.is_none() {
+                    entry.dmm = details.dmm;
+                }
+                if entry.dlsite.is_none() {
+                    entry.dlsite = details.dlsite;
+                }
+                if entry.erogamescape_information.is_none() {
+                    entry.erogamescape_information = details.erogamescape_information;
+                }
+                if entry.latest_download_path.is_none() {
+                    entry.latest_download_path = details.latest_download_path;
+                }
+            }
+            None => {
+                map.insert(key, details);
+            }
+        }
+    }
+    map
+}
+
+impl RepositoryImpl<Work> {
+    /// work_id を指定しなければ全作品分を (work_id, バリアント) で幅の昇順に返す
+    async fn list_thumbnail_variants(
+        &mut self,
+        work_id: Option<String>,
+    ) -> anyhow::Result<Vec<(String, WorkThumbnailVariant)>> {
+        let rows: Vec<WorkThumbnailVariantTable> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows: Vec<WorkThumbnailVariantTable> = query_as(
+                        r#"
+                        SELECT work_id, width, height, format, path
+                        FROM work_thumbnail_variants
+                        WHERE ? IS NULL OR work_id = ?
+                        ORDER BY work_id ASC, width ASC, format ASC
+                        "#,
+                    )
+                    .bind(work_id.clone())
+                    .bind(work_id)
+                    .fetch_all(conn)
+                    .await?;
+                    Ok::<_, anyhow::Error>(rows)
+                })
+            })
+            .await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|r| {
+                let work_id = r.work_id.clone();
+                r.into_domain().map(|v| (work_id, v))
+            })
+            .collect())
+    }
+}
+
 impl WorkRepository for RepositoryImpl<Work> {
     async fn upsert(&mut self, new_work: &NewWork) -> anyhow::Result<StrId<Work>> {
         let title = new_work.title.clone();
@@ -32,8 +168,9 @@ impl WorkRepository for RepositoryImpl<Work> {
                     )
                     .bind(work_id)
                     .bind(title)
-                    .fetch_one(conn)
+                    .fetch_one(&mut **conn)
                     .await?;
+                    refresh_effective_columns(conn, EffectiveScope::Work(&id)).await?;
                     Ok::<String, anyhow::Error>(id)
                 })
             })
@@ -62,184 +199,67 @@ impl WorkRepository for RepositoryImpl<Work> {
     }
 
     async fn list_all_details(&mut self) -> anyhow::Result<Vec<WorkDetails>> {
-        let rows = self.executor.with_conn(|conn| {
-            Box::pin(async move {
-                let rows: Vec<WorkDetailsRow> = query_as(
-                    r#"
-                    SELECT 
-                        w.id   as work_id,
-                        w.title as work_title,
-                        NULL as ce_created_at,
-                        dw.id   as dmm_id,
-                        dw.store_id as dmm_store_id,
-                        dw.category as dmm_category,
-                        dw.subcategory as dmm_subcategory,
-                        wpp.parent_pack_store_id as parent_dmm_store_id,
-                        wpp.parent_pack_category as parent_dmm_category,
-                        wpp.parent_pack_subcategory as parent_dmm_subcategory,
-                        NULL as ce_id,
-                        wem.id as egs_id,
-                        wem.erogamescape_id as egs_erogamescape_id,
-                        wem.created_at as egs_created_at,
-                        wem.updated_at as egs_updated_at,
-                        ei.gamename_ruby as egs_info_gamename_ruby,
-                        ei.brandname as egs_info_brandname,
-                        ei.brandname_ruby as egs_info_brandname_ruby,
-                        ei.sellday as egs_info_sellday,
-                        ei.is_nukige as egs_info_is_nukige,
-                        ei.created_at as egs_info_created_at,
-                        ei.updated_at as egs_info_updated_at,
-                        wt.thumbnail_width as cet_width,
-                        wt.thumbnail_height as cet_height,
-                        wi.install_at as install_install_at,
-                        wi.original_path as install_original_path,
-                        wp.last_play_at as play_last_play_at,
-                        lw.id   as dlsite_id,
-                        lw.store_id as dlsite_store_id,
-                        lw.category as dlsite_category,
-                        (SELECT id FROM work_download_paths wdp WHERE wdp.work_id = w.id ORDER BY id DESC LIMIT 1) as latest_path_id,
-                        (SELECT download_path FROM work_download_paths wdp WHERE wdp.work_id = w.id ORDER BY id DESC LIMIT 1) as latest_path_download_path,
-                        wl.id as like_id,
-                        wl.like_at as like_like_at,
-                        wl.created_at as like_created_at,
-                        wl.updated_at as like_updated_at
-                    FROM works w
-                    LEFT JOIN dmm_works dw ON dw.work_id = w.id
-                    LEFT JOIN work_erogamescape_map wem ON wem.work_id = w.id
-                    LEFT JOIN erogamescape_information ei ON ei.id = wem.erogamescape_id
-                    LEFT JOIN work_thumbnails AS wt ON wt.work_id = w.id
-                    LEFT JOIN work_installs AS wi ON wi.work_id = w.id
-                    LEFT JOIN work_plays AS wp ON wp.work_id = w.id
-                    LEFT JOIN work_parent_packs wpp ON wpp.work_id = w.id
-                    LEFT JOIN dlsite_works lw ON lw.work_id = w.id
-                    LEFT JOIN work_likes wl ON wl.work_id = w.id
-                    ORDER BY w.id ASC
-                    "#,
-                )
-                .fetch_all(conn)
-                .await?;
-                Ok(rows)
+        let rows = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows: Vec<WorkDetailsRow> =
+                        query_as(&format!("{WORK_DETAILS_SELECT} ORDER BY w.id ASC"))
+                            .fetch_all(conn)
+                            .await?;
+                    Ok(rows)
+                })
             })
-        }).await?;
+            .await?;
 
-        let mut map: BTreeMap<String, WorkDetails> = BTreeMap::new();
-        for r in rows.into_iter() {
-            let details: WorkDetails = r.into();
-            let key = details.work.id.value.clone();
-            match map.get_mut(&key) {
-                Some(entry) => {
-                    if entry.dmm.is_none() {
-                        entry.dmm = details.dmm;
-                    }
-                    if entry.dlsite.is_none() {
-                        entry.dlsite = details.dlsite;
-                    }
-                    if entry.latest_download_path.is_none() {
-                        entry.latest_download_path = details.latest_download_path;
-                    }
-                }
-                None => {
-                    map.insert(key, details);
-                }
+        let mut map = merge_details_rows(rows);
+        let variants = self.list_thumbnail_variants(None).await?;
+        for (work_id, v) in variants.into_iter() {
+            if let Some(entry) = map.get_mut(&work_id) {
+                entry.thumbnail_variants.push(v);
             }
         }
 
         Ok(map.into_values().collect())
     }
 
+    async fn query_details(&mut self, query: &WorkQuery) -> anyhow::Result<WorkPage> {
+        self.query_work_details(query).await
+    }
+
+    async fn count_facets(&mut self, filter: &WorkListFilter) -> anyhow::Result<WorkFacets> {
+        self.count_work_facets(filter).await
+    }
+
     async fn find_details_by_work_id(
         &mut self,
         work_id: StrId<Work>,
     ) -> anyhow::Result<Option<WorkDetails>> {
         let idv = work_id.value.clone();
-        let rows = self.executor.with_conn(|conn| {
-            Box::pin(async move {
-                let rows: Vec<WorkDetailsRow> = query_as(
-                    r#"
-                    SELECT 
-                        w.id   as work_id,
-                        w.title as work_title,
-                        NULL as ce_created_at,
-                        dw.id   as dmm_id,
-                        dw.store_id as dmm_store_id,
-                        dw.category as dmm_category,
-                        dw.subcategory as dmm_subcategory,
-                        wpp.parent_pack_store_id as parent_dmm_store_id,
-                        wpp.parent_pack_category as parent_dmm_category,
-                        wpp.parent_pack_subcategory as parent_dmm_subcategory,
-                        NULL as ce_id,
-                        wem.id as egs_id,
-                        wem.erogamescape_id as egs_erogamescape_id,
-                        wem.created_at as egs_created_at,
-                        wem.updated_at as egs_updated_at,
-                        ei.gamename_ruby as egs_info_gamename_ruby,
-                        ei.brandname as egs_info_brandname,
-                        ei.brandname_ruby as egs_info_brandname_ruby,
-                        ei.sellday as egs_info_sellday,
-                        ei.is_nukige as egs_info_is_nukige,
-                        ei.created_at as egs_info_created_at,
-                        ei.updated_at as egs_info_updated_at,
-                        wt.thumbnail_width as cet_width,
-                        wt.thumbnail_height as cet_height,
-                        wi.install_at as install_install_at,
-                        wi.original_path as install_original_path,
-                        wp.last_play_at as play_last_play_at,
-                        lw.id   as dlsite_id,
-                        lw.store_id as dlsite_store_id,
-                        lw.category as dlsite_category,
-                        (SELECT id FROM work_download_paths wdp WHERE wdp.work_id = w.id ORDER BY id DESC LIMIT 1) as latest_path_id,
-                        (SELECT download_path FROM work_download_paths wdp WHERE wdp.work_id = w.id ORDER BY id DESC LIMIT 1) as latest_path_download_path,
-                        wl.id as like_id,
-                        wl.like_at as like_like_at,
-                        wl.created_at as like_created_at,
-                        wl.updated_at as like_updated_at
-                    FROM works w
-                    LEFT JOIN dmm_works dw ON dw.work_id = w.id
-                    LEFT JOIN work_erogamescape_map wem ON wem.work_id = w.id
-                    LEFT JOIN erogamescape_information ei ON ei.id = wem.erogamescape_id
-                    LEFT JOIN work_thumbnails AS wt ON wt.work_id = w.id
-                    LEFT JOIN work_parent_packs wpp ON wpp.work_id = w.id
-                    LEFT JOIN dlsite_works lw ON lw.work_id = w.id
-                    LEFT JOIN work_installs AS wi ON wi.work_id = w.id
-                    LEFT JOIN work_plays AS wp ON wp.work_id = w.id
-                    LEFT JOIN work_likes wl ON wl.work_id = w.id
-                    WHERE w.id = ?
-                    ORDER BY w.id ASC
-                    "#,
-                )
-                .bind(idv)
-                .fetch_all(conn)
-                .await?;
-                Ok(rows)
+        let rows = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows: Vec<WorkDetailsRow> =
+                        query_as(&format!("{WORK_DETAILS_SELECT} WHERE w.id = ?"))
+                            .bind(idv)
+                            .fetch_all(conn)
+                            .await?;
+                    Ok(rows)
+                })
             })
-        }).await?;
+            .await?;
 
-        let mut map: BTreeMap<String, WorkDetails> = BTreeMap::new();
-        for r in rows.into_iter() {
-            let details: WorkDetails = r.into();
-            let key = details.work.id.value.clone();
-            match map.get_mut(&key) {
-                Some(entry) => {
-                    if entry.dmm.is_none() {
-                        entry.dmm = details.dmm;
-                    }
-                    if entry.dlsite.is_none() {
-                        entry.dlsite = details.dlsite;
-                    }
-                    if entry.erogamescape_information.is_none() {
-                        entry.erogamescape_information = details.erogamescape_information;
-                    }
-                    if entry.latest_download_path.is_none() {
-                        entry.latest_download_path = details.latest_download_path;
-                    }
-                }
-                None => {
-                    map.insert(key, details);
-                }
-            }
+        let mut details = merge_details_rows(rows).into_values().next();
+        if let Some(details) = details.as_mut() {
+            details.thumbnail_variants = self
+                .list_thumbnail_variants(Some(work_id.value.clone()))
+                .await?
+                .into_iter()
+                .map(|(_, v)| v)
+                .collect();
         }
-
-        Ok(map.into_values().next())
+        Ok(details)
     }
     // 廃止: CE ID での検索は非対応
 
@@ -295,10 +315,11 @@ impl WorkRepository for RepositoryImpl<Work> {
                             updated_at = CURRENT_TIMESTAMP
                         "#,
                     )
-                    .bind(wid)
+                    .bind(&wid)
                     .bind(egs_id)
-                    .execute(&mut *conn)
+                    .execute(&mut **conn)
                     .await?;
+                    refresh_effective_columns(conn, EffectiveScope::Work(&wid)).await?;
                     Ok::<(), anyhow::Error>(())
                 })
             })
@@ -379,6 +400,82 @@ impl WorkRepository for RepositoryImpl<Work> {
         Ok(())
     }
 
+    async fn list_work_ids_missing_thumbnail_variants(
+        &mut self,
+    ) -> anyhow::Result<Vec<StrId<Work>>> {
+        let rows: Vec<(String,)> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows: Vec<(String,)> = sqlx::query_as(
+                        r#"
+                        SELECT w.id
+                        FROM works w
+                        WHERE NOT EXISTS (
+                            SELECT 1 FROM work_thumbnail_variants v WHERE v.work_id = w.id
+                        )
+                        "#,
+                    )
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows.into_iter().map(|(id,)| StrId::new(id)).collect())
+    }
+
+    async fn replace_work_thumbnail_variants(
+        &mut self,
+        work_id: StrId<Work>,
+        variants: Vec<WorkThumbnailVariant>,
+        placeholder_color: Option<String>,
+        perceptual_hash: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let wid = work_id.value.clone();
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(r#"DELETE FROM work_thumbnail_variants WHERE work_id = ?"#)
+                        .bind(wid.clone())
+                        .execute(&mut *conn)
+                        .await?;
+                    for v in variants.iter() {
+                        sqlx::query(
+                            r#"
+                            INSERT INTO work_thumbnail_variants (work_id, width, height, format, path)
+                            VALUES (?, ?, ?, ?, ?)
+                            "#,
+                        )
+                        .bind(wid.clone())
+                        .bind(v.width as i64)
+                        .bind(v.height as i64)
+                        .bind(v.format.as_str())
+                        .bind(v.path.clone())
+                        .execute(&mut *conn)
+                        .await?;
+                    }
+                    sqlx::query(
+                        r#"
+                        INSERT INTO work_thumbnails (work_id, placeholder_color, perceptual_hash)
+                        VALUES (?, ?, ?)
+                        ON CONFLICT(work_id) DO UPDATE SET
+                            placeholder_color = excluded.placeholder_color,
+                            perceptual_hash = excluded.perceptual_hash,
+                            updated_at = CURRENT_TIMESTAMP
+                        "#,
+                    )
+                    .bind(wid)
+                    .bind(placeholder_color)
+                    .bind(perceptual_hash.map(encode_perceptual_hash))
+                    .execute(&mut *conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await
+    }
+
     async fn update_last_play_at_by_work_id(
         &mut self,
         work_id: StrId<Work>,
@@ -441,6 +538,192 @@ impl WorkRepository for RepositoryImpl<Work> {
             .await?;
         Ok(())
     }
+
+    async fn update_install_engine_by_work_id(
+        &mut self,
+        work_id: StrId<Work>,
+        engine: Option<GameEngine>,
+    ) -> anyhow::Result<()> {
+        let wid = work_id.value.clone();
+        let engine = engine.map(|e| e.as_str().to_string());
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        r#"
+                        UPDATE work_installs
+                        SET engine = ?, updated_at = CURRENT_TIMESTAMP
+                        WHERE work_id = ?
+                        "#,
+                    )
+                    .bind(engine)
+                    .bind(wid)
+                    .execute(conn)
+                    .await?;
+                    Ok::<(), anyhow::Error>(())
+                })
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+/// IN 句に並べる store_id の数の上限
+const STORE_ID_BATCH_SIZE: usize = 500;
+
+/// 知覚ハッシュは INTEGER（i64）に収まらないので 16 桁の 16 進数で持つ
+pub(super) fn encode_perceptual_hash(hash: u64) -> String {
+    format!("{hash:016x}")
+}
+
+pub(super) fn decode_perceptual_hash(s: &str) -> Option<u64> {
+    u64::from_str_radix(s, 16).ok()
+}
+
+/// dmm_works / dlsite_works に共通する、ライブラリ同期での欠落検知
+impl<T> RepositoryImpl<T> {
+    async fn store_find_existing_store_ids(
+        &mut self,
+        table: &'static str,
+        store_ids: &[String],
+    ) -> anyhow::Result<Vec<String>> {
+        let mut existing = Vec::new();
+        for batch in store_ids.chunks(STORE_ID_BATCH_SIZE) {
+            let batch = batch.to_vec();
+            let rows: Vec<(String,)> = self
+                .executor
+                .with_conn(|conn| {
+                    Box::pin(async move {
+                        let mut qb = QueryBuilder::new(format!(
+                            "SELECT store_id FROM {table} WHERE store_id IN ("
+                        ));
+                        let mut separated = qb.separated(", ");
+                        for store_id in batch.iter() {
+                            separated.push_bind(store_id);
+                        }
+                        qb.push(")");
+                        let rows = qb.build_query_as().fetch_all(conn).await?;
+                        Ok(rows)
+                    })
+                })
+                .await?;
+            existing.extend(rows.into_iter().map(|(store_id,)| store_id));
+        }
+        Ok(existing)
+    }
+
+    async fn store_mark_seen(
+        &mut self,
+        table: &'static str,
+        store_ids: &[String],
+        seen_at: DateTime<Local>,
+    ) -> anyhow::Result<()> {
+        let seen_at = to_sqlite_utc(seen_at);
+        for batch in store_ids.chunks(STORE_ID_BATCH_SIZE) {
+            let batch = batch.to_vec();
+            let seen_at = seen_at.clone();
+            self.executor
+                .with_conn(|conn| {
+                    Box::pin(async move {
+                        let mut qb =
+                            QueryBuilder::new(format!("UPDATE {table} SET last_seen_at = "));
+                        qb.push_bind(seen_at);
+                        qb.push(", missing_since = NULL WHERE store_id IN (");
+                        let mut separated = qb.separated(", ");
+                        for store_id in batch.iter() {
+                            separated.push_bind(store_id);
+                        }
+                        qb.push(")");
+                        qb.build().execute(conn).await?;
+                        Ok::<(), anyhow::Error>(())
+                    })
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn store_flag_missing_not_seen_since(
+        &mut self,
+        table: &'static str,
+        since: DateTime<Local>,
+    ) -> anyhow::Result<Vec<String>> {
+        let since = to_sqlite_utc(since);
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows: Vec<(String,)> = query_as(&format!(
+                        "SELECT store_id FROM {table} WHERE last_seen_at IS NULL OR last_seen_at < ? ORDER BY store_id"
+                    ))
+                    .bind(&since)
+                    .fetch_all(&mut *conn)
+                    .await?;
+                    sqlx::query(&format!(
+                        r#"UPDATE {table}
+                        SET missing_since = COALESCE(missing_since, CURRENT_TIMESTAMP)
+                        WHERE last_seen_at IS NULL OR last_seen_at < ?"#
+                    ))
+                    .bind(&since)
+                    .execute(&mut *conn)
+                    .await?;
+                    Ok(rows.into_iter().map(|(store_id,)| store_id).collect())
+                })
+            })
+            .await
+    }
+
+    async fn store_list_missing(
+        &mut self,
+        table: &'static str,
+    ) -> anyhow::Result<Vec<MissingStoreWork>> {
+        let rows: Vec<MissingStoreWorkRow> = self
+            .executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let rows = query_as(&format!(
+                        r#"SELECT s.work_id, w.title, s.store_id, s.category, s.missing_since
+                        FROM {table} s
+                        INNER JOIN works w ON w.id = s.work_id
+                        WHERE s.missing_since IS NOT NULL
+                        ORDER BY s.missing_since DESC, s.store_id ASC"#
+                    ))
+                    .fetch_all(conn)
+                    .await?;
+                    Ok(rows)
+                })
+            })
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn store_delete_missing(
+        &mut self,
+        table: &'static str,
+        store_ids: &[String],
+    ) -> anyhow::Result<u64> {
+        let mut deleted = 0;
+        for batch in store_ids.chunks(STORE_ID_BATCH_SIZE) {
+            let batch = batch.to_vec();
+            deleted += self
+                .executor
+                .with_conn(|conn| {
+                    Box::pin(async move {
+                        let mut qb = QueryBuilder::new(format!(
+                            "DELETE FROM {table} WHERE missing_since IS NOT NULL AND store_id IN ("
+                        ));
+                        let mut separated = qb.separated(", ");
+                        for store_id in batch.iter() {
+                            separated.push_bind(store_id);
+                        }
+                        qb.push(")");
+                        let result = qb.build().execute(conn).await?;
+                        Ok(result.rows_affected())
+                    })
+                })
+                .await?;
+        }
+        Ok(deleted)
+    }
 }
 
 impl DmmWorkRepository for RepositoryImpl<domain::works::DmmWork> {
@@ -603,6 +886,51 @@ INNER JOIN keys k
             .await?;
         Ok(row.map(|t| t.try_into()).transpose()?)
     }
+
+    async fn count(&mut self) -> anyhow::Result<i64> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let (count,): (i64,) = query_as("SELECT COUNT(*) FROM dmm_works")
+                        .fetch_one(conn)
+                        .await?;
+                    Ok(count)
+                })
+            })
+            .await
+    }
+
+    async fn find_existing_store_ids(
+        &mut self,
+        store_ids: &[String],
+    ) -> anyhow::Result<Vec<String>> {
+        self.store_find_existing_store_ids("dmm_works", store_ids)
+            .await
+    }
+
+    async fn mark_seen(
+        &mut self,
+        store_ids: &[String],
+        seen_at: DateTime<Local>,
+    ) -> anyhow::Result<()> {
+        self.store_mark_seen("dmm_works", store_ids, seen_at).await
+    }
+
+    async fn flag_missing_not_seen_since(
+        &mut self,
+        since: DateTime<Local>,
+    ) -> anyhow::Result<Vec<String>> {
+        self.store_flag_missing_not_seen_since("dmm_works", since)
+            .await
+    }
+
+    async fn list_missing(&mut self) -> anyhow::Result<Vec<MissingStoreWork>> {
+        self.store_list_missing("dmm_works").await
+    }
+
+    async fn delete_missing(&mut self, store_ids: &[String]) -> anyhow::Result<u64> {
+        self.store_delete_missing("dmm_works", store_ids).await
+    }
 }
 
 impl DlsiteWorkRepository for RepositoryImpl<domain::works::DlsiteWork> {
@@ -687,6 +1015,52 @@ impl DlsiteWorkRepository for RepositoryImpl<domain::works::DlsiteWork> {
             .await?;
         Ok(row.map(|t| t.try_into()).transpose()?)
     }
+
+    async fn count(&mut self) -> anyhow::Result<i64> {
+        self.executor
+            .with_conn(|conn| {
+                Box::pin(async move {
+                    let (count,): (i64,) = query_as("SELECT COUNT(*) FROM dlsite_works")
+                        .fetch_one(conn)
+                        .await?;
+                    Ok(count)
+                })
+            })
+            .await
+    }
+
+    async fn find_existing_store_ids(
+        &mut self,
+        store_ids: &[String],
+    ) -> anyhow::Result<Vec<String>> {
+        self.store_find_existing_store_ids("dlsite_works", store_ids)
+            .await
+    }
+
+    async fn mark_seen(
+        &mut self,
+        store_ids: &[String],
+        seen_at: DateTime<Local>,
+    ) -> anyhow::Result<()> {
+        self.store_mark_seen("dlsite_works", store_ids, seen_at)
+            .await
+    }
+
+    async fn flag_missing_not_seen_since(
+        &mut self,
+        since: DateTime<Local>,
+    ) -> anyhow::Result<Vec<String>> {
+        self.store_flag_missing_not_seen_since("dlsite_works", since)
+            .await
+    }
+
+    async fn list_missing(&mut self) -> anyhow::Result<Vec<MissingStoreWork>> {
+        self.store_list_missing("dlsite_works").await
+    }
+
+    async fn delete_missing(&mut self, store_ids: &[String]) -> anyhow::Result<u64> {
+        self.store_delete_missing("dlsite_works", store_ids).await
+    }
 }
 
 impl WorkLnkRepository for RepositoryImpl<domain::repository::work_lnk::WorkLnk> {
@@ -1,10 +1,10 @@
+use domain::launch_profile::LaunchCommand;
 use domain::windows::shell_link::{CreateShortcutRequest, ShellLink};
 use windows::{
     core::{ComInterface, PCWSTR},
     Win32::{
-        Storage::FileSystem::WIN32_FIND_DATAW,
+        System::Com::IPersistFile,
         System::Com::{CoCreateInstance, CoInitialize, CoUninitialize, CLSCTX_INPROC_SERVER},
-        System::Com::{IPersistFile, STGM_READ},
         System::Threading::{GetExitCodeProcess, WaitForSingleObject, INFINITE},
         UI::Shell::{
             IShellLinkW, ShellExecuteExW, ShellLink as ShellLinkCom, SEE_MASK_NOCLOSEPROCESS,
@@ -82,48 +82,7 @@ impl ShellLink for ShellLinkImpl {
         &self,
         lnk_file_paths: Vec<String>,
     ) -> anyhow::Result<std::collections::HashMap<String, domain::file::LnkMetadata>> {
-        let mut metadatas: std::collections::HashMap<String, domain::file::LnkMetadata> =
-            std::collections::HashMap::new();
-        unsafe {
-            CoInitialize(None)?;
-            let mut target_path_vec: Vec<u16> = vec![0; 261];
-            let target_path_slice =
-                std::slice::from_raw_parts_mut(target_path_vec.as_mut_ptr(), target_path_vec.len());
-            for file_path in lnk_file_paths.into_iter() {
-                if file_path.to_lowercase().ends_with("lnk") {
-                    let shell_link: IShellLinkW =
-                        CoCreateInstance(&ShellLinkCom, None, CLSCTX_INPROC_SERVER)?;
-                    let persist_file: IPersistFile = ComInterface::cast(&shell_link)?;
-                    persist_file.Load(
-                        PCWSTR::from_raw(file_path.as_str().to_wide_null_terminated().as_ptr()),
-                        STGM_READ,
-                    )?;
-                    shell_link.GetPath(target_path_slice, &mut WIN32_FIND_DATAW::default(), 0)?;
-                    let path = PCWSTR::from_raw(target_path_vec.as_mut_ptr())
-                        .to_string()?
-                        .clone();
-                    shell_link.GetIconLocation(target_path_slice, &mut 0)?;
-                    let icon = PCWSTR::from_raw(target_path_vec.as_mut_ptr())
-                        .to_string()?
-                        .clone();
-                    metadatas.insert(file_path, domain::file::LnkMetadata { path, icon });
-                } else if file_path.to_lowercase().ends_with("url") {
-                    let icon_file: Option<String> =
-                        domain::file::get_url_file_icon_path(&file_path)?;
-                    metadatas.insert(
-                        file_path.clone(),
-                        domain::file::LnkMetadata {
-                            path: file_path,
-                            icon: icon_file.unwrap_or_default(),
-                        },
-                    );
-                } else {
-                    return Err(anyhow::anyhow!("{} is not end lnk|url", file_path));
-                }
-            }
-            CoUninitialize();
-        }
-        Ok(metadatas)
+        domain::shortcut::read_metadatas(lnk_file_paths)
     }
 
     fn execute_lnk<'a>(
@@ -159,4 +118,73 @@ impl ShellLink for ShellLinkImpl {
             Ok(Some(exit_code))
         }
     }
+
+    fn execute_command(&self, command: &LaunchCommand) -> anyhow::Result<Option<u32>> {
+        if command.run_as_admin {
+            // 昇格は ShellExecuteExW の runas でしかできない。環境変数はプロファイルの保存時に弾いている
+            if !command.env.is_empty() {
+                anyhow::bail!("environment overrides cannot be used with run as admin");
+            }
+            return unsafe { execute_elevated(command) };
+        }
+
+        let mut process = std::process::Command::new(&command.program);
+        if let Some(parameters) = &command.parameters {
+            // 引数は Windows のコマンドラインとして書かれているので分割せずに渡す
+            std::os::windows::process::CommandExt::raw_arg(&mut process, parameters);
+        }
+        if let Some(working_dir) = &command.working_dir {
+            process.current_dir(working_dir);
+        }
+        for var in &command.env {
+            process.env(&var.key, &var.value);
+        }
+        let status = process
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("failed to start {}: {e}", command.program))?
+            .wait()?;
+        Ok(status.code().map(|code| code as u32))
+    }
+}
+
+unsafe fn execute_elevated(command: &LaunchCommand) -> anyhow::Result<Option<u32>> {
+    CoInitialize(None)?;
+    let verb_w = (&"runas").to_wide_null_terminated();
+    let file_w = (&command.program.as_str()).to_wide_null_terminated();
+    let parameters_w = command
+        .parameters
+        .as_deref()
+        .map(|parameters| (&parameters).to_wide_null_terminated());
+    let directory_w = command
+        .working_dir
+        .as_deref()
+        .map(|dir| (&dir).to_wide_null_terminated());
+
+    let mut sei = SHELLEXECUTEINFOW::default();
+    sei.cbSize = std::mem::size_of::<SHELLEXECUTEINFOW>() as u32;
+    sei.fMask = SEE_MASK_NOCLOSEPROCESS;
+    sei.lpVerb = PCWSTR::from_raw(verb_w.as_ptr());
+    sei.lpFile = PCWSTR::from_raw(file_w.as_ptr());
+    if let Some(parameters_w) = &parameters_w {
+        sei.lpParameters = PCWSTR::from_raw(parameters_w.as_ptr());
+    }
+    if let Some(directory_w) = &directory_w {
+        sei.lpDirectory = PCWSTR::from_raw(directory_w.as_ptr());
+    }
+    sei.nShow = SW_SHOWNORMAL.0 as i32;
+
+    ShellExecuteExW(&mut sei as *mut SHELLEXECUTEINFOW)
+        .map_err(|_| anyhow::anyhow!("ShellExecuteExW failed"))?;
+
+    let h_process = sei.hProcess;
+    if h_process.0 == 0 {
+        CoUninitialize();
+        return Ok(None);
+    }
+    let _ = WaitForSingleObject(h_process, INFINITE);
+    let mut exit_code: u32 = 0;
+    GetExitCodeProcess(h_process, &mut exit_code as *mut u32)
+        .map_err(|_| anyhow::anyhow!("GetExitCodeProcess failed"))?;
+    CoUninitialize();
+    Ok(Some(exit_code))
 }
@@ -0,0 +1,6 @@
+pub mod process;
+pub mod runner;
+pub mod shell_link;
+#[cfg(test)]
+mod tests;
+pub mod wine;
@@ -0,0 +1,19 @@
+use super::wine::WineImpl;
+use domain::{process::Process, windows::process::ProcessWindows};
+
+// Wine のウィンドウはホストのウィンドウマネージャが持つので、ここからは取れない
+impl ProcessWindows for WineImpl<Process> {
+    fn save_screenshot_by_process_id(
+        &self,
+        _process_id: u32,
+        _filepath: &str,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("screenshot is not supported on this platform")
+    }
+    fn save_top_window_screenshot(&self, _filepath: &str) -> anyhow::Result<()> {
+        anyhow::bail!("screenshot is not supported on this platform")
+    }
+    fn get_top_window_name(&self) -> anyhow::Result<String> {
+        anyhow::bail!("cannot get top window on this platform")
+    }
+}
@@ -0,0 +1,116 @@
+use std::process::Command;
+
+use domain::launch_profile::LaunchCommand;
+use domain::wine::{WineConfig, WineRunner};
+
+const DEFAULT_WINE: &str = "wine";
+const DEFAULT_UMU: &str = "umu-run";
+/// umu は GAMEID で protonfixes を引く。作品を特定できないので既定のものを使う
+const DEFAULT_UMU_GAME_ID: &str = "umu-default";
+
+/// 起動コマンドを作品の Wine の設定に従ってランナー越しのコマンドにする
+pub fn build_command(command: &LaunchCommand) -> anyhow::Result<Command> {
+    let config = command.wine.clone().unwrap_or_else(|| WineConfig {
+        work_id: domain::StrId::new(String::new()),
+        runner: WineRunner::Wine,
+        runner_path: None,
+        prefix: None,
+        env: vec![],
+    });
+    config.validate()?;
+
+    let runner_path = config.runner_path.as_deref();
+    let mut process = match config.runner {
+        WineRunner::Wine => Command::new(runner_path.unwrap_or(DEFAULT_WINE)),
+        WineRunner::Umu => Command::new(runner_path.unwrap_or(DEFAULT_UMU)),
+        WineRunner::Proton => {
+            let mut process = Command::new(runner_path.unwrap_or_default());
+            process.arg("run");
+            process
+        }
+    };
+    // .exe 以外（.url や .bat）は Wine の start に開き方を任せる
+    if !command.program.to_lowercase().ends_with(".exe") {
+        process.args(["start", "/unix"]);
+    }
+    process.arg(&command.program);
+    if let Some(parameters) = &command.parameters {
+        process.args(split_parameters(parameters));
+    }
+    if let Some(working_dir) = &command.working_dir {
+        process.current_dir(working_dir);
+    }
+
+    if let Some(prefix) = &config.prefix {
+        match config.runner {
+            WineRunner::Wine | WineRunner::Umu => {
+                process.env("WINEPREFIX", prefix);
+            }
+            WineRunner::Proton => {
+                process.env("STEAM_COMPAT_DATA_PATH", prefix);
+            }
+        }
+    }
+    match config.runner {
+        WineRunner::Wine => {}
+        WineRunner::Umu => {
+            process.env("GAMEID", DEFAULT_UMU_GAME_ID);
+        }
+        WineRunner::Proton => {
+            // Steam の外から呼ぶときも proton スクリプトはこの変数を読む
+            if std::env::var_os("STEAM_COMPAT_CLIENT_INSTALL_PATH").is_none() {
+                process.env("STEAM_COMPAT_CLIENT_INSTALL_PATH", steam_install_path());
+            }
+        }
+    }
+    // 作品の設定より起動プロファイルの上書きを優先する
+    for var in config.env.iter().chain(command.env.iter()) {
+        process.env(&var.key, &var.value);
+    }
+    if command.run_as_admin {
+        log::debug!("run as admin is ignored under wine: {}", command.program);
+    }
+    Ok(process)
+}
+
+fn steam_install_path() -> String {
+    std::env::var("HOME")
+        .map(|home| format!("{home}/.steam/steam"))
+        .unwrap_or_default()
+}
+
+/// Windows のコマンドラインとして書かれた引数を分割する。`"` で囲むと空白を含められる
+pub fn split_parameters(parameters: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_arg = false;
+    let mut chars = parameters.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+                has_arg = true;
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                has_arg = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+    if has_arg {
+        args.push(current);
+    }
+    args
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use domain::launch_profile::LaunchCommand;
+use domain::windows::shell_link::{CreateShortcutRequest, ShellLink};
+
+use super::runner::build_command;
+
+/// .lnk の代わりにリンク先の実行ファイルへのシンボリックリンクを置く
+#[derive(Default)]
+pub struct ShellLinkImpl;
+
+impl ShellLinkImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl ShellLink for ShellLinkImpl {
+    fn create_bulk(&self, items: Vec<CreateShortcutRequest>) -> anyhow::Result<()> {
+        for item in items.iter() {
+            if item.arguments.is_some() {
+                log::warn!(
+                    "shortcut arguments are not kept on this platform: {}",
+                    item.dest_lnk_path
+                );
+            }
+            let dest = Path::new(&item.dest_lnk_path);
+            if dest.symlink_metadata().is_ok() {
+                std::fs::remove_file(dest)?;
+            }
+            std::os::unix::fs::symlink(&item.target_path, dest)?;
+        }
+        Ok(())
+    }
+
+    fn get_lnk_metadatas(
+        &self,
+        lnk_file_paths: Vec<String>,
+    ) -> anyhow::Result<std::collections::HashMap<String, domain::file::LnkMetadata>> {
+        let mut metadatas = std::collections::HashMap::new();
+        for file_path in lnk_file_paths.into_iter() {
+            let metadata = read_link_metadata(&file_path)?;
+            metadatas.insert(file_path, metadata);
+        }
+        Ok(metadatas)
+    }
+
+    fn execute_lnk(&self, lnk_path: &str, is_run_as_admin: bool) -> anyhow::Result<Option<u32>> {
+        // .lnk に書かれた引数と作業フォルダも引き継ぐ
+        let command = if lnk_path.to_lowercase().ends_with("url") {
+            LaunchCommand::direct(lnk_path)
+        } else {
+            LaunchCommand::from_link(&read_link_metadata(lnk_path)?)
+        };
+        let command = LaunchCommand {
+            run_as_admin: is_run_as_admin,
+            ..command
+        };
+        self.execute_command(&command)
+    }
+
+    /// Windows と違い終了を待たず、起動したランナーの PID を返す
+    fn execute_command(&self, command: &LaunchCommand) -> anyhow::Result<Option<u32>> {
+        let mut child = build_command(command)?
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("failed to start {}: {e}", command.program))?;
+        let pid = child.id();
+        // ゾンビにならないよう終了は別スレッドで拾う
+        std::thread::spawn(move || {
+            if let Err(e) = child.wait() {
+                log::warn!("failed to wait wine process {pid}: {e}");
+            }
+        });
+        Ok(Some(pid))
+    }
+}
+
+fn is_symlink(file_path: &str) -> bool {
+    Path::new(file_path)
+        .symlink_metadata()
+        .is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// シンボリックリンクと実行ファイルはリンク先をアイコンにも使い、それ以外は .lnk / .url として読む
+fn read_link_metadata(file_path: &str) -> anyhow::Result<domain::file::LnkMetadata> {
+    if is_symlink(file_path) || file_path.to_lowercase().ends_with(".exe") {
+        let path = resolve_target(file_path)?;
+        return Ok(domain::file::LnkMetadata {
+            icon: path.clone(),
+            path,
+            ..Default::default()
+        });
+    }
+    domain::shortcut::read_metadata(file_path)
+}
+
+/// シンボリックリンクならリンク先を、実行ファイルならそれ自身を返す
+fn resolve_target(file_path: &str) -> anyhow::Result<String> {
+    if is_symlink(file_path) {
+        return Ok(std::fs::read_link(file_path)?.to_string_lossy().to_string());
+    }
+    Ok(file_path.to_string())
+}
@@ -0,0 +1,2 @@
+mod runner;
+mod shell_link;
@@ -0,0 +1,134 @@
+use domain::launch_profile::{LaunchCommand, LaunchEnvVar};
+use domain::wine::{WineConfig, WineRunner};
+use domain::StrId;
+
+use crate::wineimpl::runner::{build_command, split_parameters};
+
+fn config(runner: WineRunner) -> WineConfig {
+    WineConfig {
+        work_id: StrId::new("w".into()),
+        runner,
+        runner_path: None,
+        prefix: Some("/home/u/prefixes/w".into()),
+        env: vec![],
+    }
+}
+
+fn args(process: &std::process::Command) -> Vec<String> {
+    process
+        .get_args()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect()
+}
+
+fn env(process: &std::process::Command, key: &str) -> Option<String> {
+    process
+        .get_envs()
+        .find(|(k, _)| *k == key)
+        .and_then(|(_, v)| v.map(|v| v.to_string_lossy().to_string()))
+}
+
+#[test]
+fn 設定がなければ既定のprefixのwineで起動する() {
+    let process = build_command(&LaunchCommand::direct("/games/w/game.exe")).unwrap();
+    assert_eq!(process.get_program(), "wine");
+    assert_eq!(args(&process), vec!["/games/w/game.exe"]);
+    assert_eq!(
+        process
+            .get_current_dir()
+            .map(|d| d.to_string_lossy().to_string()),
+        Some("/games/w".into())
+    );
+    assert_eq!(env(&process, "WINEPREFIX"), None);
+}
+
+#[test]
+fn wineはprefixと引数を渡す() {
+    let command = LaunchCommand {
+        parameters: Some("-window \"save dir\"".into()),
+        env: vec![LaunchEnvVar {
+            key: "LANG".into(),
+            value: "ja_JP.UTF-8".into(),
+        }],
+        wine: Some(WineConfig {
+            runner_path: Some("/opt/wine-ge/bin/wine".into()),
+            env: vec![LaunchEnvVar {
+                key: "LANG".into(),
+                value: "C".into(),
+            }],
+            ..config(WineRunner::Wine)
+        }),
+        ..LaunchCommand::direct("/games/w/game.exe")
+    };
+    let process = build_command(&command).unwrap();
+    assert_eq!(process.get_program(), "/opt/wine-ge/bin/wine");
+    assert_eq!(
+        args(&process),
+        vec!["/games/w/game.exe", "-window", "save dir"]
+    );
+    assert_eq!(
+        env(&process, "WINEPREFIX").as_deref(),
+        Some("/home/u/prefixes/w")
+    );
+    // 起動プロファイルの上書きが勝つ
+    assert_eq!(env(&process, "LANG").as_deref(), Some("ja_JP.UTF-8"));
+}
+
+#[test]
+fn protonはrunで起動してcompat_data_pathを渡す() {
+    let command = LaunchCommand {
+        wine: Some(WineConfig {
+            runner_path: Some("/opt/proton/proton".into()),
+            ..config(WineRunner::Proton)
+        }),
+        ..LaunchCommand::direct("/games/w/game.exe")
+    };
+    let process = build_command(&command).unwrap();
+    assert_eq!(process.get_program(), "/opt/proton/proton");
+    assert_eq!(args(&process), vec!["run", "/games/w/game.exe"]);
+    assert_eq!(
+        env(&process, "STEAM_COMPAT_DATA_PATH").as_deref(),
+        Some("/home/u/prefixes/w")
+    );
+    assert_eq!(env(&process, "WINEPREFIX"), None);
+}
+
+#[test]
+fn protonはスクリプトのパスがなければ起動しない() {
+    let command = LaunchCommand {
+        wine: Some(config(WineRunner::Proton)),
+        ..LaunchCommand::direct("/games/w/game.exe")
+    };
+    assert!(build_command(&command).is_err());
+}
+
+#[test]
+fn umuはumu_runで起動する() {
+    let command = LaunchCommand {
+        wine: Some(config(WineRunner::Umu)),
+        ..LaunchCommand::direct("/games/w/game.exe")
+    };
+    let process = build_command(&command).unwrap();
+    assert_eq!(process.get_program(), "umu-run");
+    assert_eq!(args(&process), vec!["/games/w/game.exe"]);
+    assert_eq!(
+        env(&process, "WINEPREFIX").as_deref(),
+        Some("/home/u/prefixes/w")
+    );
+    assert_eq!(env(&process, "GAMEID").as_deref(), Some("umu-default"));
+}
+
+#[test]
+fn 実行ファイル以外はstartで開く() {
+    let process = build_command(&LaunchCommand::direct("/games/w/play.url")).unwrap();
+    assert_eq!(args(&process), vec!["start", "/unix", "/games/w/play.url"]);
+}
+
+#[test]
+fn split_parametersは引用符の中の空白で分けない() {
+    assert_eq!(
+        split_parameters(r#"-run "C:/games/a b/game.exe" -w  "" x\"y"#),
+        vec!["-run", "C:/games/a b/game.exe", "-w", "", "x\"y"]
+    );
+    assert!(split_parameters("  ").is_empty());
+}
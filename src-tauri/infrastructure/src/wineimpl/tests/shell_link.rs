@@ -0,0 +1,113 @@
+use std::io::Write as _;
+use std::os::unix::fs::PermissionsExt as _;
+use std::time::Duration;
+
+use domain::launch_profile::{LaunchCommand, LaunchEnvVar};
+use domain::windows::shell_link::{CreateShortcutRequest, ShellLink};
+use domain::wine::{WineConfig, WineRunner};
+use domain::StrId;
+
+use crate::wineimpl::shell_link::ShellLinkImpl;
+
+#[test]
+fn リンクはリンク先の実行ファイルに解決される() {
+    let dir = tempfile::tempdir().unwrap();
+    let exe = dir.path().join("game.exe");
+    std::fs::write(&exe, b"").unwrap();
+    let lnk = dir.path().join("w.lnk").display().to_string();
+
+    let shell_link = ShellLinkImpl::new();
+    let request = CreateShortcutRequest {
+        target_path: exe.display().to_string(),
+        dest_lnk_path: lnk.clone(),
+        working_dir: None,
+        arguments: None,
+        icon_path: None,
+    };
+    shell_link.create_bulk(vec![request.clone()]).unwrap();
+    // 作り直しても失敗しない
+    shell_link.create_bulk(vec![request]).unwrap();
+
+    let metadatas = shell_link
+        .get_lnk_metadatas(vec![lnk.clone(), exe.display().to_string()])
+        .unwrap();
+    assert_eq!(metadatas[&lnk].path, exe.display().to_string());
+    assert_eq!(
+        metadatas[&exe.display().to_string()].path,
+        exe.display().to_string()
+    );
+}
+
+#[test]
+fn windowsで作られたlnkはファイルを読んでリンク先を返す() {
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../domain/fixtures/shortcut/local_game.lnk");
+    let lnk = dir.path().join("game.lnk");
+    std::fs::copy(fixture, &lnk).unwrap();
+    let lnk = lnk.display().to_string();
+
+    let metadatas = ShellLinkImpl::new()
+        .get_lnk_metadatas(vec![lnk.clone()])
+        .unwrap();
+    assert_eq!(metadatas[&lnk].path, "C:\\Games\\ゲーム\\game.exe");
+    assert_eq!(metadatas[&lnk].icon, "C:\\Games\\ゲーム\\icon.ico");
+    assert_eq!(
+        metadatas[&lnk].arguments.as_deref(),
+        Some("-window \"save dir\"")
+    );
+    assert_eq!(
+        metadatas[&lnk].working_dir.as_deref(),
+        Some("C:\\Games\\ゲーム")
+    );
+}
+
+#[test]
+fn ランナー越しに起動してpidを返す() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("out.txt");
+    let runner = dir.path().join("fake-wine");
+    {
+        let mut f = std::fs::File::create(&runner).unwrap();
+        writeln!(f, "#!/bin/sh").unwrap();
+        writeln!(
+            f,
+            "echo \"$WINEPREFIX $LANG $*\" > \"{}.tmp\"",
+            out.display()
+        )
+        .unwrap();
+        writeln!(f, "mv \"{0}.tmp\" \"{0}\"", out.display()).unwrap();
+    }
+    std::fs::set_permissions(&runner, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let exe = dir.path().join("game.exe");
+
+    let command = LaunchCommand {
+        parameters: Some("-window".into()),
+        wine: Some(WineConfig {
+            work_id: StrId::new("w".into()),
+            runner: WineRunner::Wine,
+            runner_path: Some(runner.display().to_string()),
+            prefix: Some("/prefix".into()),
+            env: vec![LaunchEnvVar {
+                key: "LANG".into(),
+                value: "ja_JP.UTF-8".into(),
+            }],
+        }),
+        ..LaunchCommand::direct(&exe.display().to_string())
+    };
+    let pid = ShellLinkImpl::new().execute_command(&command).unwrap();
+    assert!(pid.is_some_and(|pid| pid > 0));
+
+    let mut written = None;
+    for _ in 0..100 {
+        if let Ok(s) = std::fs::read_to_string(&out) {
+            written = Some(s);
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert_eq!(
+        written.unwrap().trim(),
+        format!("/prefix ja_JP.UTF-8 {} -window", exe.display())
+    );
+}
@@ -0,0 +1,47 @@
+use derive_new::new;
+use std::marker::PhantomData;
+
+use crate::wineimpl::shell_link::ShellLinkImpl;
+use domain::{process::Process, windows::WindowsExt};
+
+#[derive(new)]
+pub struct WineImpl<T> {
+    _marker: PhantomData<T>,
+}
+
+/// Windows 以外で `WindowsExt` を Wine 越しに満たす
+pub struct Wine {
+    process: WineImpl<Process>,
+    shell_link: ShellLinkImpl,
+}
+
+impl WindowsExt for Wine {
+    type ProcessWindows = WineImpl<Process>;
+    type ShellLink = ShellLinkImpl;
+
+    fn process(&self) -> &Self::ProcessWindows {
+        &self.process
+    }
+
+    fn shell_link(&self) -> &Self::ShellLink {
+        &self.shell_link
+    }
+}
+
+impl Wine {
+    pub fn new() -> Self {
+        let process = WineImpl::new();
+        let shell_link = ShellLinkImpl::new();
+
+        Self {
+            process,
+            shell_link,
+        }
+    }
+}
+
+impl Default for Wine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -376,10 +376,7 @@ where
                                     }
                                 }
                             } else {
-                                log::warn!(
-                                    "Failed to get metadata for path: {}",
-                                    src_path
-                                );
+                                log::warn!("Failed to get metadata for path: {}", src_path);
                             }
                         }
 
@@ -428,7 +425,17 @@ where
                                             &src_path,
                                             src_type,
                                             &thumb_dst,
-                                            ImagePreprocess::ResizeForWidth400,
+                                            ImagePreprocess::ThumbnailVariants,
+                                        )
+                                        .await?;
+                                    // 作り直すので記録済みのバリアントを捨てる。作り直したものはキューの処理で記録される
+                                    repos
+                                        .work()
+                                        .replace_work_thumbnail_variants(
+                                            work_id.clone(),
+                                            Vec::new(),
+                                            None,
+                                            None,
                                         )
                                         .await?;
                                 }
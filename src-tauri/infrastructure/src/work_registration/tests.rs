@@ -3,7 +3,7 @@ use tempfile::TempDir;
 
 use crate::sqliterepository::sqliterepository::{SqliteRepositories, SqliteRepositoryManager};
 use crate::sqliterepository::tests::TestDatabase;
-use crate::windowsimpl::windows::Windows;
+use crate::platform::Platform;
 use domain::repository::{
     all_game_cache::AllGameCacheRepository, save_image_queue::ImageSaveQueueRepository,
     work_parent_packs::WorkParentPacksRepository, works::WorkRepository, RepositoriesExt,
@@ -20,12 +20,8 @@ use super::WorkRegistrationServiceImpl;
 
 fn create_service(
     test_db: &TestDatabase,
-) -> WorkRegistrationServiceImpl<
-    SqliteRepositoryManager,
-    SqliteRepositories,
-    crate::windowsimpl::windows::Windows,
-> {
-    let windows = Arc::new(crate::windowsimpl::windows::Windows::new());
+) -> WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform> {
+    let windows = Arc::new(Platform::new());
     create_service_with_windows(test_db, windows)
 }
 
@@ -162,7 +158,7 @@ async fn register_画像戦略_only_if_missing_はファイルが存在しない
             test_db.pool.clone(),
         )),
     );
-    let windows = Arc::new(Windows::new());
+    let windows = Arc::new(Platform::new());
     let service = WorkRegistrationServiceImpl::new(manager, resolver, windows);
 
     let requests = vec![WorkRegistrationRequest {
@@ -217,7 +213,7 @@ async fn register_画像ソース_from_path_でパスから抽出される() {
             test_db.pool.clone(),
         )),
     );
-    let windows = Arc::new(Windows::new());
+    let windows = Arc::new(Platform::new());
     let service = WorkRegistrationServiceImpl::new(manager, resolver, windows);
 
     let requests = vec![WorkRegistrationRequest {
@@ -1,48 +1,63 @@
 mod models;
+mod protocol;
 
-use chrono::Utc;
+use chrono::{Local, Utc};
 use serde_json;
 use std::fs;
 use std::io::ErrorKind;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 use thiserror::Error;
-use tokio::io::{self as tokio_io, AsyncReadExt, AsyncWriteExt};
+use tokio::io::{self as tokio_io, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
-use domain::native_host_log::{HostLogLevel, HostLogType};
+use domain::extension::ExtensionConfig;
+use domain::native_host_log::{HostLogLevel, HostLogType, NewHostLog, SyncCompletedLog};
 use domain::repository::{
     manager::RepositoryManager, native_host_log::NativeHostLogRepository, RepositoriesExt,
 };
 use domain::service::app_signal_router::{
-    AppSignal, AppSignalEvent, AppSignalRouter, AppSignalSource,
+    AppRequest, AppResponse, AppSignal, AppSignalEvent, AppSignalRouter, AppSignalSource,
 };
 use domain::service::save_path_resolver::{DirsSavePathResolver, SavePathResolver};
+use domain::sync_session::{
+    NewSyncSession, SyncSessionChunk, SyncSessionError, SyncSessionProgress, SyncStore,
+};
+use domain::StrId;
 use infrastructure::{
     app_signal_router::interprocess::client::InterprocessAppSignalRouter,
+    downloader::{DownloaderConfig, HttpDownloader},
     image_queue_worker::ImageQueueWorker,
     local_file_system::LocalFileSystem,
+    platform::Platform,
     save_path_resolver::{DbSavePathResolver, StoragePathSettingsStore},
     sqliterepository::{
         driver::Db as RepoDb, sqliterepository::SqliteRepositories,
         sqliterepository::SqliteRepositoryManager,
     },
-    windowsimpl::windows::Windows,
     work_linker::WorkLinkerImpl,
     work_registration::WorkRegistrationServiceImpl,
 };
 use models::{
     common::{
-        HealthCheckRequestTs, HealthCheckResultTs, NativeMessageCase, NativeMessageTs,
+        HealthCheckRequestTs, HealthCheckResultTs, NativeErrorCodeTs, NativeMessageCase,
         NativeResponseCase, NativeResponseTs,
     },
     downloads::DownloadsCompletedRequestTs,
-    sync::{DlsiteSyncGamesRequestTs, DmmSyncGamesRequestTs, SyncBatchResultTs},
+    status::{ConfigUpdateResultTs, ExtensionConfigTs, SyncStatusTs, TimestampTs},
+    sync::{
+        DlsiteSyncChunkRequestTs, DlsiteSyncGamesRequestTs, DmmSyncChunkRequestTs,
+        DmmSyncGamesRequestTs, SyncBatchResultTs, SyncSessionBeginRequestTs,
+        SyncSessionCommitRequestTs, SyncSessionStatusTs, SyncStoreTs,
+    },
 };
 use usecase::app_settings::AppSettingsUseCase;
+use usecase::native_host_status::NativeHostStatusUseCase;
 use usecase::native_host_sync::downloads::DownloadsUseCase;
 use usecase::native_host_sync::{
-    DlsiteSyncGameParam, DmmSyncGameParam, EgsInfo, NativeHostSyncUseCase,
+    DlsiteSyncGameParam, DmmSyncGameParam, EgsInfo, NativeHostSyncUseCase, SyncGamesSummary,
 };
+use usecase::native_host_sync_session::NativeHostSyncSessionUseCase;
 use usecase::work_thumbnail::WorkThumbnailUseCase;
 
 struct AppCtx {
@@ -51,12 +66,14 @@ struct AppCtx {
     sync_usecase: NativeHostSyncUseCase<
         SqliteRepositoryManager,
         SqliteRepositories,
-        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     >,
+    status_usecase: NativeHostStatusUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    sync_session_usecase: NativeHostSyncSessionUseCase<SqliteRepositoryManager, SqliteRepositories>,
     resolver: Arc<dyn SavePathResolver>,
     storage_path_settings: Arc<StoragePathSettingsStore>,
     fs: Arc<LocalFileSystem>,
-    work_linker: Arc<WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>>,
+    work_linker: Arc<WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>>,
     app_signal_router: Arc<InterprocessAppSignalRouter>,
 }
 
@@ -109,11 +126,40 @@ async fn log_app_signal_dispatch_failure(
         .await;
 }
 
-async fn read_framed() -> HostResult<Option<Vec<u8>>> {
-    let mut stdin = tokio_io::stdin();
+async fn insert_host_log(ctx: &AppCtx, log: NewHostLog) {
+    let _ = ctx
+        .manager
+        .run(move |repos| Box::pin(async move { repos.host_log().insert(&log).await }))
+        .await;
+}
 
+/// リクエストに含まれる作品やダウンロードの件数
+fn request_item_count(message: &NativeMessageCase) -> Option<usize> {
+    match message {
+        NativeMessageCase::SyncDmmGames(req) => Some(req.games.len()),
+        NativeMessageCase::SyncDlsiteGames(req) => Some(req.games.len()),
+        NativeMessageCase::SyncDmmGamesChunk(req) => Some(req.games.len()),
+        NativeMessageCase::SyncDlsiteGamesChunk(req) => Some(req.games.len()),
+        NativeMessageCase::DownloadsCompleted(req) => Some(req.items.len()),
+        _ => None,
+    }
+}
+
+/// 応答に含まれるエラー件数。リクエスト自体が失敗したときは 1 件として数える
+fn response_error_count(response: &NativeResponseTs) -> Option<usize> {
+    if !response.success {
+        return Some(1);
+    }
+    match &response.response {
+        Some(NativeResponseCase::SyncGamesResult(result)) => Some(result.error_count as usize),
+        Some(NativeResponseCase::SyncSessionResult(status)) => Some(status.error_count as usize),
+        _ => None,
+    }
+}
+
+async fn read_framed<R: AsyncRead + Unpin>(reader: &mut R) -> HostResult<Option<Vec<u8>>> {
     let mut length_bytes = [0u8; 4];
-    if let Err(e) = stdin.read_exact(&mut length_bytes).await {
+    if let Err(e) = reader.read_exact(&mut length_bytes).await {
         if e.kind() == ErrorKind::UnexpectedEof {
             return Ok(None);
         }
@@ -126,7 +172,7 @@ async fn read_framed() -> HostResult<Option<Vec<u8>>> {
     }
 
     let mut message_bytes = vec![0u8; length];
-    stdin.read_exact(&mut message_bytes).await?;
+    reader.read_exact(&mut message_bytes).await?;
     Ok(Some(message_bytes))
 }
 
@@ -137,12 +183,10 @@ async fn main() {
         .init();
 
     let fixed_root = DirsSavePathResolver::default().root_dir();
-    // ensure sidecar (extract-icon.exe) is present next to host executable
-    let _ = ensure_extract_icon_sidecar();
     let db_path = DirsSavePathResolver::default().db_file_path();
     let repo_db = RepoDb::from_path(&db_path).await;
     let repo_manager = Arc::new(SqliteRepositoryManager::new(repo_db.pool_arc()));
-    let windows = Arc::new(Windows::new());
+    let windows = Arc::new(Platform::new());
     let app_settings_use_case = AppSettingsUseCase::new(repo_manager.clone());
     let initial_storage_settings = app_settings_use_case
         .get_storage_settings()
@@ -156,7 +200,7 @@ async fn main() {
         storage_path_settings.clone(),
     ));
     let work_registration_service: Arc<
-        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     > = Arc::new(WorkRegistrationServiceImpl::new(
         repo_manager.clone(),
         resolver.clone(),
@@ -164,6 +208,8 @@ async fn main() {
     ));
     let sync_usecase =
         NativeHostSyncUseCase::new(repo_manager.clone(), work_registration_service.clone());
+    let status_usecase = NativeHostStatusUseCase::new(repo_manager.clone());
+    let sync_session_usecase = NativeHostSyncSessionUseCase::new(repo_manager.clone());
     let fs = Arc::new(LocalFileSystem::default());
     let work_linker = Arc::new(WorkLinkerImpl::new(
         repo_manager.clone(),
@@ -174,6 +220,8 @@ async fn main() {
         manager: repo_manager,
         app_settings_use_case,
         sync_usecase,
+        status_usecase,
+        sync_session_usecase,
         resolver,
         storage_path_settings,
         fs,
@@ -183,8 +231,21 @@ async fn main() {
 
     log::info!("Native Messaging Host started");
 
+    let mut stdin = tokio_io::stdin();
+    let mut stdout = tokio_io::stdout();
+    serve(&ctx, &mut stdin, &mut stdout).await;
+
+    log::info!("Native Messaging Host stopped");
+}
+
+/// 入力が閉じるか読み書きに失敗するまで、フレーム単位でリクエストを処理する
+async fn serve<R, W>(ctx: &AppCtx, reader: &mut R, writer: &mut W)
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
     loop {
-        match handle_message(&ctx).await {
+        match handle_message(ctx, reader, writer).await {
             Ok(true) => continue,
             Ok(false) => break,
             Err(e) => {
@@ -193,37 +254,43 @@ async fn main() {
             }
         }
     }
-
-    log::info!("Native Messaging Host stopped");
 }
 
-async fn handle_message(ctx: &AppCtx) -> HostResult<bool> {
+async fn handle_message<R, W>(ctx: &AppCtx, reader: &mut R, writer: &mut W) -> HostResult<bool>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
     if let Err(err) = reload_storage_settings(ctx).await {
         log::warn!("failed to reload storage settings: {err}");
     }
 
-    let message_bytes = match read_framed().await {
+    let message_bytes = match read_framed(reader).await {
         Ok(Some(bytes)) => bytes,
         Ok(None) => return Ok(false),
         Err(HostError::TooLarge(length)) => {
-            let error_msg = HostError::TooLarge(length).to_string();
-            send_error_response("", error_msg).await?;
+            let err = protocol::ProtocolError {
+                code: NativeErrorCodeTs::MessageTooLarge,
+                message: HostError::TooLarge(length).to_string(),
+                request_id: String::new(),
+                protocol_version: None,
+            };
+            send_response_json(writer, &protocol::error_response(&err)).await?;
             return Ok(true);
         }
         Err(e) => return Err(e),
     };
 
-    let message = match parse_message(&message_bytes) {
-        Ok(v) => v,
+    let (protocol_version, message) = match protocol::decode_request(&message_bytes) {
+        Ok(decoded) => (decoded.protocol_version, decoded.message),
         Err(err) => {
-            let mut request_id = String::new();
-            if let Ok(v) = serde_json::from_slice::<serde_json::Value>(&message_bytes) {
-                if let Some(id) = v.get("request_id").and_then(|x| x.as_str()) {
-                    request_id = id.to_string();
-                }
-            }
-
-            send_error_response(&request_id, err.to_string()).await?;
+            log::warn!(
+                "rejected native message: {:?} {} (request_id={})",
+                err.code,
+                err.message,
+                err.request_id
+            );
+            send_response_json(writer, &protocol::error_response(&err)).await?;
             return Ok(true);
         }
     };
@@ -232,26 +299,17 @@ async fn handle_message(ctx: &AppCtx) -> HostResult<bool> {
         log_app_signal_dispatch_failure(&ctx, "notify_app_signal", err).await;
     }
 
-    let _ = ctx
-        .manager
-        .run(|repos| {
-            let log_message = format!(
-                "id: {}, request: {:?}",
-                &message.request_id, &message.message
-            );
-            Box::pin(async move {
-                repos
-                    .host_log()
-                    .insert_log(
-                        HostLogLevel::Info,
-                        HostLogType::ReceiveRequest,
-                        log_message.as_str(),
-                    )
-                    .await?;
-                Ok::<(), anyhow::Error>(())
-            })
-        })
-        .await;
+    let started = Instant::now();
+    let mut request_log = NewHostLog::new(
+        HostLogLevel::Info,
+        HostLogType::ReceiveRequest,
+        format!("request: {:?}", &message.message),
+    )
+    .request_id(&message.request_id);
+    if let Some(item_count) = request_item_count(&message.message) {
+        request_log = request_log.item_count(item_count);
+    }
+    insert_host_log(ctx, request_log).await;
 
     let response = match &message.message {
         NativeMessageCase::SyncDmmGames(req) => {
@@ -263,63 +321,53 @@ async fn handle_message(ctx: &AppCtx) -> HostResult<bool> {
         NativeMessageCase::DownloadsCompleted(req) => {
             handle_downloads_completed(ctx, req, &message.request_id).await
         }
-        NativeMessageCase::GetStatus(_) => NativeResponseTs {
-            success: false,
-            error: "GetStatus is not supported".to_string(),
-            request_id: message.request_id.clone(),
-            response: None,
-        },
-        NativeMessageCase::SetConfig(_) => NativeResponseTs {
-            success: false,
-            error: "SetConfig is not supported".to_string(),
-            request_id: message.request_id.clone(),
-            response: None,
-        },
-        NativeMessageCase::HealthCheck(_) => {
-            handle_health_check(&HealthCheckRequestTs {}, &message.request_id)
+        NativeMessageCase::GetStatus(_) => handle_get_status(ctx, &message.request_id).await,
+        NativeMessageCase::SetConfig(req) => handle_set_config(ctx, req, &message.request_id).await,
+        NativeMessageCase::HealthCheck(req) => handle_health_check(req, &message.request_id),
+        NativeMessageCase::BeginSyncSession(req) => {
+            handle_begin_sync_session(ctx, req, &message.request_id).await
+        }
+        NativeMessageCase::SyncDmmGamesChunk(req) => {
+            handle_sync_dmm_games_chunk(ctx, req, &message.request_id).await
+        }
+        NativeMessageCase::SyncDlsiteGamesChunk(req) => {
+            handle_sync_dlsite_games_chunk(ctx, req, &message.request_id).await
+        }
+        NativeMessageCase::CommitSyncSession(req) => {
+            handle_commit_sync_session(ctx, req, &message.request_id).await
         }
     };
+    let response = protocol::encode_response(response, protocol_version);
 
-    send_response_json(&response).await?;
+    send_response_json(writer, &response).await?;
 
-    let _ = ctx
-        .manager
-        .run(|repos| {
-            let log_message = format!("id: {}, response: {:?}", &message.request_id, &response);
-            Box::pin(async move {
-                repos
-                    .host_log()
-                    .insert_log(
-                        HostLogLevel::Info,
-                        HostLogType::Response,
-                        log_message.as_str(),
-                    )
-                    .await?;
-                Ok::<(), anyhow::Error>(())
-            })
-        })
-        .await;
-
-    let _ = ctx
-        .manager
-        .run(|repos| {
-            let log_message = format!(
-                "end process image queue. id: {}, message: {:?}",
-                &message.request_id, &message.message
-            );
-            Box::pin(async move {
-                repos
-                    .host_log()
-                    .insert_log(
-                        HostLogLevel::Info,
-                        HostLogType::EndProcessImageQueue,
-                        log_message.as_str(),
-                    )
-                    .await?;
-                Ok::<(), anyhow::Error>(())
-            })
-        })
-        .await;
+    let level = if response.success {
+        HostLogLevel::Info
+    } else {
+        HostLogLevel::Warn
+    };
+    let mut response_log = NewHostLog::new(
+        level,
+        HostLogType::Response,
+        format!("response: {:?}", &response),
+    )
+    .request_id(&message.request_id)
+    .duration(started.elapsed());
+    if let Some(error_count) = response_error_count(&response) {
+        response_log = response_log.error_count(error_count);
+    }
+    insert_host_log(ctx, response_log).await;
+
+    insert_host_log(
+        ctx,
+        NewHostLog::new(
+            HostLogLevel::Info,
+            HostLogType::EndProcessImageQueue,
+            "end process image queue",
+        )
+        .request_id(&message.request_id),
+    )
+    .await;
 
     Ok(true)
 }
@@ -338,7 +386,7 @@ async fn handle_downloads_completed(
         SqliteRepositoryManager,
         SqliteRepositories,
         LocalFileSystem,
-        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     > = DownloadsUseCase::new(
         ctx.manager.clone(),
         ctx.resolver.clone(),
@@ -420,6 +468,7 @@ async fn handle_downloads_completed(
             NativeResponseCase::HealthCheckResult(HealthCheckResultTs {
                 message: "OK".into(),
                 version: env!("CARGO_PKG_VERSION").into(),
+                ..Default::default()
             }),
         );
     }
@@ -456,6 +505,7 @@ async fn handle_downloads_completed(
             NativeResponseCase::HealthCheckResult(HealthCheckResultTs {
                 message: "OK".into(),
                 version: env!("CARGO_PKG_VERSION").into(),
+                ..Default::default()
             }),
         );
     }
@@ -474,6 +524,7 @@ async fn handle_downloads_completed(
         NativeResponseCase::HealthCheckResult(HealthCheckResultTs {
             message: "NOOP".into(),
             version: env!("CARGO_PKG_VERSION").into(),
+            ..Default::default()
         }),
     )
 }
@@ -488,6 +539,14 @@ async fn notify_app_signal(ctx: &AppCtx, message: &NativeMessageCase) -> anyhow:
             "DLsite との連携リクエストを受信しました（対象 {} 件）。処理が完了すると画面が自動で更新されます。",
             req.games.len()
         )),
+        NativeMessageCase::BeginSyncSession(req) => Some(format!(
+            "{} との連携リクエストを受信しました（{} 回に分けて受信します）。処理が完了すると画面が自動で更新されます。",
+            match req.store {
+                SyncStoreTs::Dmm => "DMM GAMES",
+                SyncStoreTs::Dlsite => "DLsite",
+            },
+            req.total_chunks
+        )),
         _ => None,
     };
 
@@ -544,16 +603,38 @@ async fn dispatch_refetch_works(router: &Arc<InterprocessAppSignalRouter>) -> an
 }
 
 async fn finalize_sync_and_notify(ctx: &AppCtx) -> anyhow::Result<()> {
+    // アプリが起動していれば画像キューはアプリに任せ、同じキューを並行して処理しない
+    match ctx
+        .app_signal_router
+        .request(AppRequest::DrainImageQueue)
+        .await
+    {
+        Ok(Some(AppResponse::DrainImageQueue { accepted: true })) => return Ok(()),
+        Ok(Some(response)) => {
+            log::info!("app did not take over image queue drain: {response:?}");
+        }
+        Ok(None) => {}
+        Err(err) => {
+            log_app_signal_dispatch_failure(ctx, "request_drain_image_queue", err).await;
+        }
+    }
+
     // Native Messaging Host 側では HostLog を使用
     let handler = std::sync::Arc::new(
         infrastructure::image_queue_worker::handler::ImageQueueHostLogHandler::new(
             ctx.manager.clone(),
         ),
     );
+    // ホストはアプリと別プロセスなので、ここで1つ作って drain 全体で共有する
+    let downloader = Arc::new(
+        HttpDownloader::new(DownloaderConfig::default())
+            .with_cache_dir(ctx.resolver.http_cache_dir()),
+    );
     let worker = ImageQueueWorker::new_with_event_handler(
         ctx.manager.clone(),
         ctx.resolver.clone(),
-        Arc::new(Windows::new()),
+        Arc::new(Platform::new()),
+        downloader,
         handler,
     );
     worker.drain_until_empty().await?;
@@ -619,20 +700,40 @@ async fn handle_sync_dmm_games(
             NativeResponseCase::SyncGamesResult(SyncBatchResultTs {
                 success_count: 0,
                 new_count: 0,
+                unchanged_count: 0,
                 error_count: 0,
                 errors: vec![],
                 synced_games: vec![],
+                missing_games: vec![],
             }),
         );
     }
+    let started_at = Local::now();
     match ctx.sync_usecase.sync_dmm_games(params).await {
         Ok(summary) => {
+            let missing_games = if request.full_library {
+                flag_missing_store_works(ctx, SyncStore::Dmm, started_at).await
+            } else {
+                vec![]
+            };
+            log_sync_completed(
+                ctx,
+                SyncCompletedLog {
+                    store: "dmm".to_string(),
+                    extension_id: request.extension_id.clone(),
+                    success_count: summary.success_count,
+                    new_count: summary.new_count,
+                },
+            )
+            .await;
             let result = SyncBatchResultTs {
                 success_count: summary.success_count,
                 new_count: summary.new_count,
+                unchanged_count: summary.unchanged_count,
                 error_count: 0,
                 errors: vec![],
                 synced_games: input_ids.clone(),
+                missing_games,
             };
             if let Err(err) = finalize_sync_and_notify(ctx).await {
                 let err_msg = anyhow_chain_to_string(&err);
@@ -660,9 +761,11 @@ async fn handle_sync_dmm_games(
             let result = SyncBatchResultTs {
                 success_count: 0,
                 new_count: 0,
+                unchanged_count: 0,
                 error_count: input_ids.len() as u32,
                 errors: vec![err_msg.clone()],
                 synced_games: input_ids,
+                missing_games: vec![],
             };
             if let Err(dispatch_err) =
                 dispatch_show_error_message(&ctx.app_signal_router, err_msg.clone()).await
@@ -695,20 +798,40 @@ async fn handle_sync_dlsite_games(
             NativeResponseCase::SyncGamesResult(SyncBatchResultTs {
                 success_count: 0,
                 new_count: 0,
+                unchanged_count: 0,
                 error_count: 0,
                 errors: vec![],
                 synced_games: vec![],
+                missing_games: vec![],
             }),
         );
     }
+    let started_at = Local::now();
     match ctx.sync_usecase.sync_dlsite_games(params).await {
         Ok(summary) => {
+            let missing_games = if request.full_library {
+                flag_missing_store_works(ctx, SyncStore::Dlsite, started_at).await
+            } else {
+                vec![]
+            };
+            log_sync_completed(
+                ctx,
+                SyncCompletedLog {
+                    store: "dlsite".to_string(),
+                    extension_id: request.extension_id.clone(),
+                    success_count: summary.success_count,
+                    new_count: summary.new_count,
+                },
+            )
+            .await;
             let result = SyncBatchResultTs {
                 success_count: summary.success_count,
                 new_count: summary.new_count,
+                unchanged_count: summary.unchanged_count,
                 error_count: 0,
                 errors: vec![],
                 synced_games: input_ids.clone(),
+                missing_games,
             };
             if let Err(err) = finalize_sync_and_notify(ctx).await {
                 let err_msg = anyhow_chain_to_string(&err);
@@ -736,9 +859,11 @@ async fn handle_sync_dlsite_games(
             let result = SyncBatchResultTs {
                 success_count: 0,
                 new_count: 0,
+                unchanged_count: 0,
                 error_count: input_ids.len() as u32,
                 errors: vec![err_msg.clone()],
                 synced_games: input_ids,
+                missing_games: vec![],
             };
             if let Err(dispatch_err) =
                 dispatch_show_error_message(&ctx.app_signal_router, err_msg.clone()).await
@@ -759,42 +884,409 @@ async fn handle_sync_dlsite_games(
     }
 }
 
-fn handle_health_check(_request: &HealthCheckRequestTs, request_id: &str) -> NativeResponseTs {
+async fn handle_begin_sync_session(
+    ctx: &AppCtx,
+    request: &SyncSessionBeginRequestTs,
+    request_id: &str,
+) -> NativeResponseTs {
+    let new_session = NewSyncSession {
+        id: StrId::new(request.session_id.clone()),
+        store: to_sync_store(request.store),
+        extension_id: request.extension_id.clone(),
+        total_chunks: request.total_chunks,
+    };
+    match ctx.sync_session_usecase.begin(new_session).await {
+        Ok(progress) => {
+            if !progress.chunks.is_empty() {
+                log::info!(
+                    "resume sync session {}: {}/{} chunk(s) already received",
+                    request.session_id,
+                    progress.chunks.len(),
+                    progress.session.total_chunks
+                );
+            }
+            ok(
+                request_id,
+                NativeResponseCase::SyncSessionResult(to_sync_session_status(&progress)),
+            )
+        }
+        Err(e) => sync_session_err(request_id, &e),
+    }
+}
+
+async fn handle_sync_dmm_games_chunk(
+    ctx: &AppCtx,
+    request: &DmmSyncChunkRequestTs,
+    request_id: &str,
+) -> NativeResponseTs {
+    let (input_ids, params) = to_dmm_params(&DmmSyncGamesRequestTs {
+        games: request.games.clone(),
+        extension_id: String::new(),
+        full_library: false,
+    });
+    handle_sync_chunk(
+        ctx,
+        SyncStore::Dmm,
+        &request.session_id,
+        request.chunk_index,
+        input_ids,
+        ctx.sync_usecase.sync_dmm_games(params),
+        request_id,
+    )
+    .await
+}
+
+async fn handle_sync_dlsite_games_chunk(
+    ctx: &AppCtx,
+    request: &DlsiteSyncChunkRequestTs,
+    request_id: &str,
+) -> NativeResponseTs {
+    let (input_ids, params) = to_dlsite_params(&DlsiteSyncGamesRequestTs {
+        games: request.games.clone(),
+        extension_id: String::new(),
+        full_library: false,
+    });
+    handle_sync_chunk(
+        ctx,
+        SyncStore::Dlsite,
+        &request.session_id,
+        request.chunk_index,
+        input_ids,
+        ctx.sync_usecase.sync_dlsite_games(params),
+        request_id,
+    )
+    .await
+}
+
+/// チャンクを 1 つ反映して記録する。画像キューの処理と画面の更新は commit までまとめて遅らせる。
+/// 反映済みのチャンクが再送されたときは反映し直さず、記録した結果を返す
+async fn handle_sync_chunk(
+    ctx: &AppCtx,
+    store: SyncStore,
+    session_id: &str,
+    chunk_index: u32,
+    input_ids: Vec<String>,
+    sync: impl std::future::Future<Output = anyhow::Result<SyncGamesSummary>>,
+    request_id: &str,
+) -> NativeResponseTs {
+    let id = StrId::new(session_id.to_string());
+    match ctx
+        .sync_session_usecase
+        .check_chunk(&id, store, chunk_index)
+        .await
+    {
+        Ok(Some(recorded)) => {
+            return ok(
+                request_id,
+                NativeResponseCase::SyncGamesResult(SyncBatchResultTs {
+                    success_count: recorded.success_count,
+                    new_count: recorded.new_count,
+                    unchanged_count: recorded.unchanged_count,
+                    error_count: recorded.error_count,
+                    errors: recorded.errors,
+                    synced_games: input_ids,
+                    missing_games: vec![],
+                }),
+            )
+        }
+        Ok(None) => {}
+        Err(e) => return sync_session_err(request_id, &e),
+    }
+
+    let summary = if input_ids.is_empty() {
+        SyncGamesSummary {
+            success_count: 0,
+            new_count: 0,
+            unchanged_count: 0,
+        }
+    } else {
+        match sync.await {
+            Ok(summary) => summary,
+            Err(e) => {
+                // 記録しないので、拡張機能は同じチャンクを送り直せる
+                let err_msg = anyhow_chain_to_string(&e);
+                log::error!(
+                    "failed to sync chunk {} of session {}: {}",
+                    chunk_index,
+                    session_id,
+                    err_msg
+                );
+                return fail_with_body(
+                    request_id,
+                    err_msg.clone(),
+                    NativeResponseCase::SyncGamesResult(SyncBatchResultTs {
+                        success_count: 0,
+                        new_count: 0,
+                        unchanged_count: 0,
+                        error_count: input_ids.len() as u32,
+                        errors: vec![err_msg],
+                        synced_games: input_ids,
+                        missing_games: vec![],
+                    }),
+                );
+            }
+        }
+    };
+
+    let chunk = SyncSessionChunk {
+        chunk_index,
+        game_count: input_ids.len() as u32,
+        success_count: summary.success_count,
+        new_count: summary.new_count,
+        unchanged_count: summary.unchanged_count,
+        error_count: 0,
+        errors: vec![],
+    };
+    if let Err(e) = ctx.sync_session_usecase.record_chunk(&id, chunk).await {
+        return err(request_id, anyhow_chain_to_string(&e));
+    }
+    ok(
+        request_id,
+        NativeResponseCase::SyncGamesResult(SyncBatchResultTs {
+            success_count: summary.success_count,
+            new_count: summary.new_count,
+            unchanged_count: summary.unchanged_count,
+            error_count: 0,
+            errors: vec![],
+            synced_games: input_ids,
+            missing_games: vec![],
+        }),
+    )
+}
+
+async fn handle_commit_sync_session(
+    ctx: &AppCtx,
+    request: &SyncSessionCommitRequestTs,
+    request_id: &str,
+) -> NativeResponseTs {
+    let id = StrId::new(request.session_id.clone());
+    let committed = match ctx.sync_session_usecase.commit(&id).await {
+        Ok(committed) => committed,
+        Err(e) => return sync_session_err(request_id, &e),
+    };
+    let mut status = to_sync_session_status(&committed.progress);
+    // 確定の再送でも同じ結果を返せるよう、確定済みなら毎回印を付け直す（既に付いた印の日時は変わらない）
+    if request.full_library && committed.progress.success_count() > 0 {
+        status.missing_games = flag_missing_store_works(
+            ctx,
+            committed.progress.session.store,
+            committed.progress.session.created_at,
+        )
+        .await;
+    }
+    if !committed.newly_committed {
+        return ok(request_id, NativeResponseCase::SyncSessionResult(status));
+    }
+
+    log_sync_completed(
+        ctx,
+        SyncCompletedLog {
+            store: committed.progress.session.store.as_str().to_string(),
+            extension_id: committed.progress.session.extension_id.clone(),
+            success_count: committed.progress.success_count(),
+            new_count: committed.progress.new_count(),
+        },
+    )
+    .await;
+    if let Err(e) = finalize_sync_and_notify(ctx).await {
+        let err_msg = anyhow_chain_to_string(&e);
+        log::error!(
+            "failed to finalize sync session {}: {}",
+            request.session_id,
+            err_msg
+        );
+        if let Err(dispatch_err) =
+            dispatch_show_error_message(&ctx.app_signal_router, err_msg.clone()).await
+        {
+            log_app_signal_dispatch_failure(
+                ctx,
+                format!("dispatch_show_error_message request_id={}", request_id),
+                dispatch_err,
+            )
+            .await;
+        }
+        return fail_with_body(
+            request_id,
+            err_msg,
+            NativeResponseCase::SyncSessionResult(status),
+        );
+    }
+    ok(request_id, NativeResponseCase::SyncSessionResult(status))
+}
+
+fn to_sync_store(store: SyncStoreTs) -> SyncStore {
+    match store {
+        SyncStoreTs::Dmm => SyncStore::Dmm,
+        SyncStoreTs::Dlsite => SyncStore::Dlsite,
+    }
+}
+
+fn to_sync_session_status(progress: &SyncSessionProgress) -> SyncSessionStatusTs {
+    SyncSessionStatusTs {
+        session_id: progress.session.id.value.clone(),
+        store: match progress.session.store {
+            SyncStore::Dmm => SyncStoreTs::Dmm,
+            SyncStore::Dlsite => SyncStoreTs::Dlsite,
+        },
+        total_chunks: progress.session.total_chunks,
+        received_chunks: progress.received_chunks(),
+        success_count: progress.success_count(),
+        new_count: progress.new_count(),
+        unchanged_count: progress.unchanged_count(),
+        error_count: progress.error_count(),
+        committed: progress.session.is_committed(),
+        missing_games: vec![],
+    }
+}
+
+/// ライブラリ全体の同期を終えたあとに、`since` 以降に見えなかった紐付けへ欠落の印を付ける。
+/// 印付けに失敗しても同期そのものは成功として返す
+async fn flag_missing_store_works(
+    ctx: &AppCtx,
+    store: SyncStore,
+    since: chrono::DateTime<Local>,
+) -> Vec<String> {
+    match ctx
+        .sync_usecase
+        .flag_missing_store_works(store, since)
+        .await
+    {
+        Ok(missing) => {
+            if !missing.is_empty() {
+                log::info!(
+                    "{} {} store work(s) were not found in the full library sync",
+                    missing.len(),
+                    store.as_str()
+                );
+            }
+            missing
+        }
+        Err(e) => {
+            log::error!(
+                "failed to flag missing {} store works: {}",
+                store.as_str(),
+                anyhow_chain_to_string(&e)
+            );
+            vec![]
+        }
+    }
+}
+
+/// セッションの状態による失敗は拡張機能が次の手を選べるようエラーコードを分ける
+fn sync_session_err(request_id: &str, e: &anyhow::Error) -> NativeResponseTs {
+    let mut response = err(request_id, anyhow_chain_to_string(e));
+    response.error_code = Some(match e.downcast_ref::<SyncSessionError>() {
+        Some(SyncSessionError::NotFound(_)) => NativeErrorCodeTs::SyncSessionNotFound,
+        Some(SyncSessionError::Mismatch { .. }) | Some(SyncSessionError::AlreadyCommitted(_)) => {
+            NativeErrorCodeTs::SyncSessionConflict
+        }
+        Some(SyncSessionError::Incomplete { .. }) => NativeErrorCodeTs::SyncSessionIncomplete,
+        Some(SyncSessionError::ChunkOutOfRange { .. })
+        | Some(SyncSessionError::InvalidTotalChunks { .. }) => NativeErrorCodeTs::InvalidMessage,
+        None => NativeErrorCodeTs::HandlerFailed,
+    });
+    response
+}
+
+async fn log_sync_completed(ctx: &AppCtx, log: SyncCompletedLog) {
+    let _ = ctx
+        .manager
+        .run(|repos| {
+            let log_message = log.to_message();
+            Box::pin(async move {
+                repos
+                    .host_log()
+                    .insert_log(
+                        HostLogLevel::Info,
+                        HostLogType::SyncGamesCompleted,
+                        log_message.as_str(),
+                    )
+                    .await?;
+                Ok::<(), anyhow::Error>(())
+            })
+        })
+        .await;
+}
+
+async fn handle_get_status(ctx: &AppCtx, request_id: &str) -> NativeResponseTs {
+    match ctx.status_usecase.get_sync_stats().await {
+        Ok(stats) => ok(
+            request_id,
+            NativeResponseCase::StatusResult(SyncStatusTs {
+                last_sync: stats.last_sync_at.map(|at| TimestampTs {
+                    seconds: at.timestamp() as i32,
+                    nanos: at.timestamp_subsec_nanos() as i32,
+                }),
+                total_synced: stats.total_synced,
+                connected_extensions: stats.extension_ids.len() as u32,
+                extension_ids: stats.extension_ids,
+                // 応答している時点でホストは動作中
+                is_running: true,
+                connection_status: "connected".to_string(),
+                error_message: String::new(),
+            }),
+        ),
+        Err(e) => err(request_id, anyhow_chain_to_string(&e)),
+    }
+}
+
+async fn handle_set_config(
+    ctx: &AppCtx,
+    request: &ExtensionConfigTs,
+    request_id: &str,
+) -> NativeResponseTs {
+    let config = ExtensionConfig {
+        auto_sync: request.auto_sync,
+        allowed_domains: request.allowed_domains.clone(),
+        sync_interval_minutes: request.sync_interval_minutes,
+        debug_mode: request.debug_mode,
+    };
+    match ctx.status_usecase.set_config(config).await {
+        Ok(saved) => ok(
+            request_id,
+            NativeResponseCase::ConfigResult(ConfigUpdateResultTs {
+                message: format!(
+                    "saved {} allowed domain(s), sync every {} minute(s)",
+                    saved.allowed_domains.len(),
+                    saved.sync_interval_minutes
+                ),
+            }),
+        ),
+        Err(e) => err(request_id, anyhow_chain_to_string(&e)),
+    }
+}
+
+fn handle_health_check(request: &HealthCheckRequestTs, request_id: &str) -> NativeResponseTs {
     let result = HealthCheckResultTs {
         message: "OK".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        supported_protocol_versions: protocol::supported_protocol_versions(),
+        capabilities: protocol::negotiate_capabilities(request),
     };
     ok(request_id, NativeResponseCase::HealthCheckResult(result))
 }
 
-fn parse_message(message_bytes: &[u8]) -> HostResult<NativeMessageTs> {
-    let message: NativeMessageTs = serde_json::from_slice(message_bytes)?;
-    Ok(message)
-}
-
-async fn send_response_json(response: &NativeResponseTs) -> HostResult<()> {
-    let mut stdout = tokio_io::stdout();
-
+async fn send_response_json<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    response: &NativeResponseTs,
+) -> HostResult<()> {
     let json_response = serde_json::to_string(&response)?;
     let json_bytes = json_response.as_bytes();
     let length = json_bytes.len() as u32;
-    stdout.write_all(&length.to_le_bytes()).await?;
-    stdout.write_all(json_bytes).await?;
-    stdout.flush().await?;
+    writer.write_all(&length.to_le_bytes()).await?;
+    writer.write_all(json_bytes).await?;
+    writer.flush().await?;
     Ok(())
 }
 
-async fn send_error_response(request_id: &str, message: String) -> HostResult<()> {
-    let response = err(request_id, message);
-    send_response_json(&response).await
-}
-
 fn ok<R: Into<NativeResponseCase>>(request_id: &str, body: R) -> NativeResponseTs {
     NativeResponseTs {
         success: true,
         error: String::new(),
         request_id: request_id.to_string(),
         response: Some(body.into()),
+        error_code: None,
+        protocol_version: None,
     }
 }
 
@@ -804,25 +1296,11 @@ fn err(request_id: &str, msg: impl Into<String>) -> NativeResponseTs {
         error: msg.into(),
         request_id: request_id.to_string(),
         response: None,
+        error_code: Some(NativeErrorCodeTs::HandlerFailed),
+        protocol_version: None,
     }
 }
 
-fn ensure_extract_icon_sidecar() -> anyhow::Result<PathBuf> {
-    // resolve destination: same directory as current executable
-    let exe_dir = std::env::current_exe()
-        .ok()
-        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
-        .ok_or_else(|| anyhow::anyhow!("failed to resolve current exe directory"))?;
-    let dst = exe_dir.join("extract-icon.exe");
-    if dst.exists() {
-        return Ok(dst);
-    }
-    // embedded bytes from repository's prebuilt binary
-    const BYTES: &[u8] = include_bytes!("../../bin/extract-icon-x86_64-pc-windows-msvc.exe");
-    fs::write(&dst, BYTES)?;
-    Ok(dst)
-}
-
 fn fail_with_body(
     request_id: &str,
     msg: impl Into<String>,
@@ -833,6 +1311,8 @@ fn fail_with_body(
         error: msg.into(),
         request_id: request_id.to_string(),
         response: Some(body),
+        error_code: Some(NativeErrorCodeTs::HandlerFailed),
+        protocol_version: None,
     }
 }
 
@@ -860,10 +1340,12 @@ fn to_dmm_params(request: &DmmSyncGamesRequestTs) -> (Vec<String>, Vec<DmmSyncGa
             gamename: g.title.clone(),
             image_url: g.image_url.clone(),
             egs: g.egs_info.as_ref().map(map_egs_ts),
-            parent_pack: g.parent_pack.as_ref().map(|parent| usecase::native_host_sync::DmmPackKey {
-                store_id: parent.store_id.clone(),
-                category: parent.category.clone(),
-                subcategory: parent.subcategory.clone(),
+            parent_pack: g.parent_pack.as_ref().map(|parent| {
+                usecase::native_host_sync::DmmPackKey {
+                    store_id: parent.store_id.clone(),
+                    category: parent.category.clone(),
+                    subcategory: parent.subcategory.clone(),
+                }
             }),
         })
         .collect();
@@ -897,6 +1379,7 @@ mod tests {
     use std::future::Future;
     use std::pin::Pin;
     use std::sync::Arc as StdArc;
+    use usecase::store_library::StoreLibraryUseCase;
 
     async fn setup_db() -> RepoDb {
         let rng = rand::rng();
@@ -913,17 +1396,17 @@ mod tests {
     async fn build_test_ctx(
         repo_manager: StdArc<SqliteRepositoryManager>,
         resolver: Arc<dyn SavePathResolver>,
-        _windows: Arc<Windows>,
+        _windows: Arc<Platform>,
         _work_registration_service: Arc<
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         >,
         sync_usecase: NativeHostSyncUseCase<
             SqliteRepositoryManager,
             SqliteRepositories,
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         >,
         fs: Arc<LocalFileSystem>,
-        work_linker: Arc<WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>>,
+        work_linker: Arc<WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>>,
     ) -> AppCtx {
         let app_settings_use_case = AppSettingsUseCase::new(repo_manager.clone());
         let storage_path_settings = Arc::new(StoragePathSettingsStore::new(
@@ -933,10 +1416,14 @@ mod tests {
                 .unwrap_or_default()
                 .into(),
         ));
+        let status_usecase = NativeHostStatusUseCase::new(repo_manager.clone());
+        let sync_session_usecase = NativeHostSyncSessionUseCase::new(repo_manager.clone());
         AppCtx {
             manager: repo_manager,
             app_settings_use_case,
             sync_usecase,
+            status_usecase,
+            sync_session_usecase,
             resolver,
             storage_path_settings,
             fs,
@@ -951,9 +1438,9 @@ mod tests {
         let db = setup_db().await;
         let repo_manager = StdArc::new(SqliteRepositoryManager::new(db.pool_arc()));
         let resolver = Arc::new(DirsSavePathResolver::default());
-        let windows = Arc::new(Windows::new());
+        let windows = Arc::new(Platform::new());
         let work_registration_service: Arc<
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = Arc::new(WorkRegistrationServiceImpl::new(
             repo_manager.clone(),
             resolver.clone(),
@@ -1312,9 +1799,9 @@ mod tests {
             let db = setup_db().await;
             let repo_manager = StdArc::new(SqliteRepositoryManager::new(db.pool_arc()));
             let resolver = Arc::new(DirsSavePathResolver::default());
-            let windows = Arc::new(Windows::new());
+            let windows = Arc::new(Platform::new());
             let work_registration_service: Arc<
-                WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+                WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
             > = Arc::new(WorkRegistrationServiceImpl::new(
                 repo_manager.clone(),
                 resolver.clone(),
@@ -1362,7 +1849,7 @@ mod tests {
 
     #[test]
     fn ヘルスチェック_okが返る() {
-        let resp = handle_health_check(&HealthCheckRequestTs {}, "req1");
+        let resp = handle_health_check(&HealthCheckRequestTs::default(), "req1");
         assert!(resp.success);
         match resp.response {
             Some(NativeResponseCase::HealthCheckResult(body)) => {
@@ -1373,28 +1860,449 @@ mod tests {
         }
     }
 
-    #[test]
-    fn ステータス未対応_エラー() {
-        let resp = NativeResponseTs {
-            success: false,
-            error: "GetStatus is not supported".into(),
-            request_id: "x".into(),
-            response: None,
-        };
-        assert!(!resp.success);
-        assert!(resp.error.contains("not supported"));
+    async fn build_default_test_ctx(db: &RepoDb) -> AppCtx {
+        let repo_manager = StdArc::new(SqliteRepositoryManager::new(db.pool_arc()));
+        let resolver = Arc::new(DirsSavePathResolver::default());
+        let windows = Arc::new(Platform::new());
+        let work_registration_service: Arc<
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
+        > = Arc::new(WorkRegistrationServiceImpl::new(
+            repo_manager.clone(),
+            resolver.clone(),
+            windows.clone(),
+        ));
+        let usecase =
+            NativeHostSyncUseCase::new(repo_manager.clone(), work_registration_service.clone());
+        let fs = Arc::new(LocalFileSystem::default());
+        let work_linker = Arc::new(WorkLinkerImpl::new(
+            repo_manager.clone(),
+            resolver.clone(),
+            windows.clone(),
+        ));
+        build_test_ctx(
+            repo_manager,
+            resolver,
+            windows,
+            work_registration_service,
+            usecase,
+            fs,
+            work_linker,
+        )
+        .await
     }
 
-    #[test]
-    fn セット未対応_エラー() {
-        let resp = NativeResponseTs {
-            success: false,
-            error: "SetConfig is not supported".into(),
-            request_id: "x".into(),
-            response: None,
-        };
-        assert!(!resp.success);
-        assert!(resp.error.contains("not supported"));
+    /// stdin に流すのと同じ長さ付きフレームでリクエストを送り、stdout 相当の応答を読み戻す
+    async fn exchange_framed_raw(
+        ctx: &AppCtx,
+        requests: &[serde_json::Value],
+    ) -> Vec<serde_json::Value> {
+        let mut input = Vec::new();
+        for request in requests {
+            let body = serde_json::to_vec(request).unwrap();
+            input.extend_from_slice(&(body.len() as u32).to_le_bytes());
+            input.extend_from_slice(&body);
+        }
+        let mut output: Vec<u8> = Vec::new();
+        serve(ctx, &mut input.as_slice(), &mut output).await;
+
+        let mut responses = Vec::new();
+        let mut rest = output.as_slice();
+        while !rest.is_empty() {
+            let length = u32::from_le_bytes(rest[..4].try_into().unwrap()) as usize;
+            responses.push(serde_json::from_slice(&rest[4..4 + length]).unwrap());
+            rest = &rest[4 + length..];
+        }
+        responses
+    }
+
+    async fn exchange_framed(
+        ctx: &AppCtx,
+        requests: &[serde_json::Value],
+    ) -> Vec<NativeResponseTs> {
+        exchange_framed_raw(ctx, requests)
+            .await
+            .into_iter()
+            .map(|v| serde_json::from_value(v).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn 統合_ステータス取得_同期履歴と作品数を返す() {
+        let db = setup_db().await;
+        let ctx = build_default_test_ctx(&db).await;
+
+        let responses = exchange_framed(
+            &ctx,
+            &[
+                serde_json::json!({
+                    "request_id": "s1",
+                    "message": { "case": "GetStatus", "value": {} },
+                }),
+                serde_json::json!({
+                    "request_id": "d1",
+                    "message": { "case": "SyncDmmGames", "value": {
+                        "extension_id": "ext-a",
+                        "games": [{
+                            "id": "SID-STATUS",
+                            "category": "game",
+                            "subcategory": "pc",
+                            "title": "Status Game",
+                            "image_url": "",
+                        }],
+                    } },
+                }),
+                serde_json::json!({
+                    "request_id": "s2",
+                    "message": { "case": "GetStatus", "value": {} },
+                }),
+            ],
+        )
+        .await;
+        assert_eq!(responses.len(), 3);
+
+        let before = &responses[0];
+        assert!(before.success);
+        assert_eq!(before.request_id, "s1");
+        match &before.response {
+            Some(NativeResponseCase::StatusResult(status)) => {
+                assert!(status.last_sync.is_none());
+                assert_eq!(status.total_synced, 0);
+                assert_eq!(status.connected_extensions, 0);
+                assert!(status.is_running);
+                assert_eq!(status.connection_status, "connected");
+            }
+            other => panic!("unexpected response: {other:?}"),
+        }
+
+        assert!(responses[1].success);
+
+        let after = &responses[2];
+        assert!(after.success);
+        match &after.response {
+            Some(NativeResponseCase::StatusResult(status)) => {
+                assert!(status.last_sync.is_some());
+                assert_eq!(status.total_synced, 1);
+                assert_eq!(status.connected_extensions, 1);
+                assert_eq!(status.extension_ids, vec!["ext-a".to_string()]);
+            }
+            other => panic!("unexpected response: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn 統合_設定保存_正規化して永続化する() {
+        let db = setup_db().await;
+        let ctx = build_default_test_ctx(&db).await;
+
+        let responses = exchange_framed(
+            &ctx,
+            &[
+                serde_json::json!({
+                    "request_id": "c1",
+                    "message": { "case": "SetConfig", "value": {
+                        "auto_sync": false,
+                        "allowed_domains": [" WWW.DLsite.com ", "www.dlsite.com", ""],
+                        "sync_interval_minutes": 15,
+                        "debug_mode": true,
+                    } },
+                }),
+                serde_json::json!({
+                    "request_id": "c2",
+                    "message": { "case": "SetConfig", "value": {
+                        "auto_sync": true,
+                        "allowed_domains": [],
+                        "sync_interval_minutes": 0,
+                        "debug_mode": false,
+                    } },
+                }),
+            ],
+        )
+        .await;
+        assert_eq!(responses.len(), 2);
+
+        assert!(responses[0].success, "{}", responses[0].error);
+        assert_eq!(responses[0].request_id, "c1");
+        assert!(matches!(
+            responses[0].response,
+            Some(NativeResponseCase::ConfigResult(_))
+        ));
+
+        // 不正な間隔は拒否され、直前の設定が残る
+        assert!(!responses[1].success);
+        assert_eq!(responses[1].request_id, "c2");
+
+        let saved = ctx.status_usecase.get_config().await.unwrap();
+        assert_eq!(
+            saved,
+            ExtensionConfig {
+                auto_sync: false,
+                allowed_domains: vec!["www.dlsite.com".to_string()],
+                sync_interval_minutes: 15,
+                debug_mode: true,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn 統合_未知のケース_エラーコードを返して処理を続ける() {
+        let db = setup_db().await;
+        let ctx = build_default_test_ctx(&db).await;
+
+        let responses = exchange_framed(
+            &ctx,
+            &[
+                serde_json::from_str(include_str!(
+                    "../fixtures/protocol/v2_unknown_case.request.json"
+                ))
+                .unwrap(),
+                serde_json::from_str(include_str!(
+                    "../fixtures/protocol/v3_get_status.request.json"
+                ))
+                .unwrap(),
+                serde_json::json!({
+                    "protocol_version": 2,
+                    "request_id": "hc",
+                    "message": { "case": "HealthCheck", "value": {} },
+                }),
+            ],
+        )
+        .await;
+        assert_eq!(responses.len(), 3);
+
+        assert!(!responses[0].success);
+        assert_eq!(responses[0].request_id, "rid-unknown");
+        assert_eq!(
+            responses[0].error_code,
+            Some(NativeErrorCodeTs::UnknownMessageCase)
+        );
+        assert_eq!(
+            responses[1].error_code,
+            Some(NativeErrorCodeTs::UnsupportedProtocolVersion)
+        );
+        assert_eq!(
+            responses[1].protocol_version,
+            Some(protocol::PROTOCOL_VERSION)
+        );
+
+        assert!(responses[2].success);
+        assert_eq!(responses[2].protocol_version, Some(2));
+        match &responses[2].response {
+            Some(NativeResponseCase::HealthCheckResult(body)) => {
+                assert_eq!(body.supported_protocol_versions, vec![1, 2]);
+                assert!(body.capabilities.iter().any(|c| c == "GetStatus"));
+            }
+            other => panic!("unexpected response: {other:?}"),
+        }
+    }
+
+    fn dmm_chunk(session_id: &str, chunk_index: u32, store_id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "protocol_version": 2,
+            "request_id": format!("c{chunk_index}"),
+            "message": { "case": "SyncDmmGamesChunk", "value": {
+                "session_id": session_id,
+                "chunk_index": chunk_index,
+                "games": [{
+                    "id": store_id,
+                    "category": "game",
+                    "subcategory": "pc",
+                    "title": format!("Chunk Game {chunk_index}"),
+                    "image_url": "",
+                }],
+            } },
+        })
+    }
+
+    fn sync_session_message(case: &str, value: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "protocol_version": 2,
+            "request_id": case,
+            "message": { "case": case, "value": value },
+        })
+    }
+
+    #[tokio::test]
+    async fn 統合_分割同期_ホスト再起動後も続きから受け取って確定する() {
+        let db = setup_db().await;
+        let begin = sync_session_message(
+            "BeginSyncSession",
+            serde_json::json!({
+                "session_id": "sess-1",
+                "store": "Dmm",
+                "extension_id": "ext-a",
+                "total_chunks": 2,
+            }),
+        );
+        let commit = sync_session_message(
+            "CommitSyncSession",
+            serde_json::json!({ "session_id": "sess-1" }),
+        );
+
+        // 1 つ目のホストはチャンク 1 だけ受け取って終了する
+        let ctx = build_default_test_ctx(&db).await;
+        let responses = exchange_framed_raw(
+            &ctx,
+            &[
+                begin.clone(),
+                dmm_chunk("sess-1", 1, "SID-CHUNK-1"),
+                commit.clone(),
+            ],
+        )
+        .await;
+        assert_eq!(
+            responses[0]["response"]["value"]["received_chunks"],
+            serde_json::json!([])
+        );
+        assert_eq!(responses[1]["success"], true);
+        assert_eq!(responses[1]["response"]["value"]["new_count"], 1);
+        assert_eq!(responses[2]["success"], false);
+        assert_eq!(responses[2]["error_code"], "SyncSessionIncomplete");
+
+        // 再起動したホストは受信済みのチャンクを返し、再送されたチャンクは反映し直さない
+        let ctx = build_default_test_ctx(&db).await;
+        let responses = exchange_framed_raw(
+            &ctx,
+            &[
+                begin,
+                dmm_chunk("sess-1", 1, "SID-CHUNK-1"),
+                dmm_chunk("sess-1", 0, "SID-CHUNK-0"),
+                commit.clone(),
+                commit,
+                dmm_chunk("sess-unknown", 0, "SID-CHUNK-X"),
+            ],
+        )
+        .await;
+        assert_eq!(
+            responses[0]["response"]["value"]["received_chunks"],
+            serde_json::json!([1])
+        );
+        assert_eq!(responses[1]["success"], true);
+        assert_eq!(responses[1]["response"]["value"]["new_count"], 1);
+        assert_eq!(responses[2]["success"], true);
+
+        let committed = &responses[3];
+        assert_eq!(committed["success"], true);
+        assert_eq!(committed["response"]["case"], "SyncSessionResult");
+        assert_eq!(committed["response"]["value"]["committed"], true);
+        assert_eq!(
+            committed["response"]["value"]["received_chunks"],
+            serde_json::json!([0, 1])
+        );
+        assert_eq!(committed["response"]["value"]["success_count"], 2);
+        assert_eq!(committed["response"]["value"]["new_count"], 2);
+        // 確定済みの commit の再送は同じ集計を返す
+        assert_eq!(responses[4]["response"], committed["response"]);
+
+        assert_eq!(responses[5]["success"], false);
+        assert_eq!(responses[5]["error_code"], "SyncSessionNotFound");
+
+        let status = ctx.status_usecase.get_sync_stats().await.unwrap();
+        assert_eq!(status.total_synced, 2);
+        assert_eq!(status.extension_ids, vec!["ext-a".to_string()]);
+    }
+
+    fn dmm_full_library(request_id: &str, store_ids: &[&str]) -> serde_json::Value {
+        let games = store_ids
+            .iter()
+            .map(|store_id| {
+                serde_json::json!({
+                    "id": store_id,
+                    "category": "game",
+                    "subcategory": "pc",
+                    "title": format!("Library Game {store_id}"),
+                    "image_url": "",
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({
+            "protocol_version": 2,
+            "request_id": request_id,
+            "message": { "case": "SyncDmmGames", "value": {
+                "games": games,
+                "extension_id": "ext-a",
+                "full_library": true,
+            } },
+        })
+    }
+
+    #[tokio::test]
+    async fn 統合_ライブラリ全体の同期_含まれなかった紐付けに印を付ける() {
+        let db = setup_db().await;
+        let ctx = build_default_test_ctx(&db).await;
+
+        let responses = exchange_framed_raw(
+            &ctx,
+            &[
+                dmm_full_library("first", &["SID-KEEP", "SID-REFUNDED"]),
+                dmm_full_library("second", &["SID-KEEP", "SID-NEW"]),
+            ],
+        )
+        .await;
+        assert_eq!(responses[0]["success"], true);
+        assert_eq!(responses[0]["response"]["value"]["new_count"], 2);
+        assert!(responses[0]["response"]["value"]
+            .get("missing_games")
+            .is_none());
+
+        let second = &responses[1]["response"]["value"];
+        assert_eq!(responses[1]["success"], true);
+        assert_eq!(second["new_count"], 1);
+        assert_eq!(second["unchanged_count"], 1);
+        assert_eq!(second["missing_games"], serde_json::json!(["SID-REFUNDED"]));
+
+        let store_library = StoreLibraryUseCase::new(ctx.manager.clone());
+        let missing = store_library.list_missing(SyncStore::Dmm).await.unwrap();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].store_id, "SID-REFUNDED");
+        assert_eq!(missing[0].title, "Library Game SID-REFUNDED");
+
+        // 再び見つかれば印は外れる
+        exchange_framed_raw(
+            &ctx,
+            &[dmm_full_library(
+                "third",
+                &["SID-KEEP", "SID-NEW", "SID-REFUNDED"],
+            )],
+        )
+        .await;
+        assert!(store_library
+            .list_missing(SyncStore::Dmm)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn 統合_v1のリクエスト_v1の形で応答する() {
+        let db = setup_db().await;
+        let ctx = build_default_test_ctx(&db).await;
+
+        let responses = exchange_framed_raw(
+            &ctx,
+            &[
+                serde_json::from_str(include_str!(
+                    "../fixtures/protocol/v1_health_check.request.json"
+                ))
+                .unwrap(),
+                serde_json::from_str(include_str!(
+                    "../fixtures/protocol/v1_get_status.request.json"
+                ))
+                .unwrap(),
+            ],
+        )
+        .await;
+        assert_eq!(responses.len(), 2);
+
+        let mut expected: serde_json::Value = serde_json::from_str(include_str!(
+            "../fixtures/protocol/v1_health_check.response.json"
+        ))
+        .unwrap();
+        expected["response"]["value"]["version"] = env!("CARGO_PKG_VERSION").into();
+        assert_eq!(responses[0], expected);
+
+        assert_eq!(responses[1]["success"], true);
+        assert_eq!(responses[1]["response"]["case"], "StatusResult");
+        assert!(responses[1].get("protocol_version").is_none());
     }
 
     #[test]
@@ -1422,6 +2330,7 @@ mod tests {
                 }),
             }],
             extension_id: "ext".into(),
+            full_library: false,
         };
         let (ids, params) = to_dmm_params(&req);
         assert_eq!(ids, vec!["SID1".to_string()]);
@@ -1451,6 +2360,7 @@ mod tests {
                 egs_info: None,
             }],
             extension_id: "ext".into(),
+            full_library: false,
         };
         let (ids, params) = to_dlsite_params(&req);
         assert_eq!(ids, vec!["RJ1".to_string()]);
@@ -1473,9 +2383,9 @@ mod tests {
         let db = RepoDb::from_path(&tmp_str).await;
         let repo_manager = StdArc::new(SqliteRepositoryManager::new(db.pool_arc()));
         let resolver = Arc::new(DirsSavePathResolver::default());
-        let windows = Arc::new(Windows::new());
+        let windows = Arc::new(Platform::new());
         let work_registration_service: Arc<
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = Arc::new(WorkRegistrationServiceImpl::new(
             repo_manager.clone(),
             resolver.clone(),
@@ -1502,6 +2412,7 @@ mod tests {
         let req = DmmSyncGamesRequestTs {
             games: vec![],
             extension_id: "ext".into(),
+            full_library: false,
         };
         let resp = handle_sync_dmm_games(&ctx, &req, "r1").await;
         assert!(resp.success);
@@ -1527,9 +2438,9 @@ mod tests {
         let db = RepoDb::from_path(&tmp_str).await;
         let repo_manager = StdArc::new(SqliteRepositoryManager::new(db.pool_arc()));
         let resolver = Arc::new(DirsSavePathResolver::default());
-        let windows = Arc::new(Windows::new());
+        let windows = Arc::new(Platform::new());
         let work_registration_service: Arc<
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = Arc::new(WorkRegistrationServiceImpl::new(
             repo_manager.clone(),
             resolver.clone(),
@@ -1556,6 +2467,7 @@ mod tests {
         let req = DlsiteSyncGamesRequestTs {
             games: vec![],
             extension_id: "ext".into(),
+            full_library: false,
         };
         let resp = handle_sync_dlsite_games(&ctx, &req, "r1").await;
         assert!(resp.success);
@@ -1581,9 +2493,9 @@ mod tests {
         let db = RepoDb::from_path(&tmp_str).await;
         let repo_manager = StdArc::new(SqliteRepositoryManager::new(db.pool_arc()));
         let resolver = Arc::new(DirsSavePathResolver::default());
-        let windows = Arc::new(Windows::new());
+        let windows = Arc::new(Platform::new());
         let work_registration_service: Arc<
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = Arc::new(WorkRegistrationServiceImpl::new(
             repo_manager.clone(),
             resolver.clone(),
@@ -1618,6 +2530,7 @@ mod tests {
                 parent_pack: None,
             }],
             extension_id: "ext".into(),
+            full_library: false,
         };
 
         let resp = handle_sync_dmm_games(&ctx, &req, "r1").await;
@@ -1630,5 +2543,4 @@ mod tests {
             panic!("unexpected");
         }
     }
-
 }
@@ -3,6 +3,9 @@ use typeshare::typeshare;
 #[typeshare]
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct NativeMessageTs {
+    /// 省略時はバージョン導入前の v1 として扱う
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<u32>,
     pub request_id: String,
     pub message: NativeMessageCase,
 }
@@ -17,6 +20,10 @@ pub enum NativeMessageCase {
     GetStatus(GetStatusRequestTs),
     SetConfig(super::status::ExtensionConfigTs),
     HealthCheck(HealthCheckRequestTs),
+    BeginSyncSession(super::sync::SyncSessionBeginRequestTs),
+    SyncDmmGamesChunk(super::sync::DmmSyncChunkRequestTs),
+    SyncDlsiteGamesChunk(super::sync::DlsiteSyncChunkRequestTs),
+    CommitSyncSession(super::sync::SyncSessionCommitRequestTs),
 }
 
 #[typeshare]
@@ -25,7 +32,11 @@ pub struct GetStatusRequestTs {}
 
 #[typeshare]
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
-pub struct HealthCheckRequestTs {}
+pub struct HealthCheckRequestTs {
+    /// 拡張機能が使いたいメッセージケース。空ならホストが対応する全ケースを返す
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<String>,
+}
 
 #[typeshare]
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
@@ -34,6 +45,35 @@ pub struct NativeResponseTs {
     pub error: String,
     pub request_id: String,
     pub response: Option<NativeResponseCase>,
+    /// 失敗時の分類。v1 の応答には含めない
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<NativeErrorCodeTs>,
+    /// 応答を組み立てたプロトコルバージョン。v1 の応答には含めない
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<u32>,
+}
+
+#[typeshare]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NativeErrorCodeTs {
+    /// JSON として読めない
+    InvalidJson,
+    /// 既知のケースだが内容がスキーマに合わない
+    InvalidMessage,
+    /// ホストが知らないメッセージケース
+    UnknownMessageCase,
+    /// ホストが対応していないプロトコルバージョン
+    UnsupportedProtocolVersion,
+    /// 1MB を超えるメッセージ
+    MessageTooLarge,
+    /// リクエストは受理したが処理に失敗した
+    HandlerFailed,
+    /// 同期セッションが見つからない（期限切れを含む）。BeginSyncSession からやり直す
+    SyncSessionNotFound,
+    /// 同期セッションの内容と合わない（ストアやチャンク数の違い、確定済みへの追加）
+    SyncSessionConflict,
+    /// 未受信のチャンクがあるため確定できない
+    SyncSessionIncomplete,
 }
 
 #[typeshare]
@@ -44,11 +84,18 @@ pub enum NativeResponseCase {
     StatusResult(super::status::SyncStatusTs),
     ConfigResult(super::status::ConfigUpdateResultTs),
     HealthCheckResult(HealthCheckResultTs),
+    SyncSessionResult(super::sync::SyncSessionStatusTs),
 }
 
 #[typeshare]
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
 pub struct HealthCheckResultTs {
     pub message: String,
     pub version: String,
+    /// ホストが受け付けるプロトコルバージョン
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supported_protocol_versions: Vec<u32>,
+    /// 要求されたうちホストが対応しているメッセージケース
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<String>,
 }
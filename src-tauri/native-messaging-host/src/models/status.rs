@@ -15,6 +15,9 @@ pub struct SyncStatusTs {
     pub last_sync: Option<TimestampTs>,
     pub total_synced: u32,
     pub connected_extensions: u32,
+    /// 最近同期してきた拡張機能の ID。connected_extensions はこの件数
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extension_ids: Vec<String>,
     pub is_running: bool,
     pub connection_status: String,
     pub error_message: String,
@@ -47,6 +47,9 @@ pub struct EgsInfoTs {
 pub struct DmmSyncGamesRequestTs {
     pub games: Vec<DmmGameTs>,
     pub extension_id: String,
+    /// ライブラリ全体を送ったとき true。含まれなかった紐付けに欠落の印を付ける
+    #[serde(default)]
+    pub full_library: bool,
 }
 
 #[typeshare]
@@ -54,6 +57,9 @@ pub struct DmmSyncGamesRequestTs {
 pub struct DlsiteSyncGamesRequestTs {
     pub games: Vec<DlsiteGameTs>,
     pub extension_id: String,
+    /// ライブラリ全体を送ったとき true。含まれなかった紐付けに欠落の印を付ける
+    #[serde(default)]
+    pub full_library: bool,
 }
 
 #[typeshare]
@@ -61,7 +67,78 @@ pub struct DlsiteSyncGamesRequestTs {
 pub struct SyncBatchResultTs {
     pub success_count: u32,
     pub new_count: u32,
+    /// 同期前から紐付いていた件数
+    #[serde(default)]
+    pub unchanged_count: u32,
     pub error_count: u32,
     pub errors: Vec<String>,
     pub synced_games: Vec<String>,
+    /// full_library のとき、今回の同期に含まれず欠落の印を付けた store_id
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_games: Vec<String>,
+}
+
+#[typeshare]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncStoreTs {
+    Dmm,
+    Dlsite,
+}
+
+/// 分割同期の開始。同じ session_id で送り直すと受信済みチャンクを返して再開する
+#[typeshare]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct SyncSessionBeginRequestTs {
+    /// 拡張機能が採番する ID
+    pub session_id: String,
+    pub store: SyncStoreTs,
+    pub extension_id: String,
+    pub total_chunks: u32,
+}
+
+#[typeshare]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct DmmSyncChunkRequestTs {
+    pub session_id: String,
+    /// 0 始まり
+    pub chunk_index: u32,
+    pub games: Vec<DmmGameTs>,
+}
+
+#[typeshare]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct DlsiteSyncChunkRequestTs {
+    pub session_id: String,
+    /// 0 始まり
+    pub chunk_index: u32,
+    pub games: Vec<DlsiteGameTs>,
+}
+
+#[typeshare]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct SyncSessionCommitRequestTs {
+    pub session_id: String,
+    /// 全チャンクでライブラリ全体になるとき true。セッション開始以降に見えなかった紐付けに欠落の印を付ける
+    #[serde(default)]
+    pub full_library: bool,
+}
+
+/// BeginSyncSession / CommitSyncSession の応答
+#[typeshare]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct SyncSessionStatusTs {
+    pub session_id: String,
+    pub store: SyncStoreTs,
+    pub total_chunks: u32,
+    /// 反映済みの chunk_index（昇順）
+    pub received_chunks: Vec<u32>,
+    pub success_count: u32,
+    pub new_count: u32,
+    #[serde(default)]
+    pub unchanged_count: u32,
+    pub error_count: u32,
+    pub committed: bool,
+    /// full_library で確定したとき、欠落の印を付けた store_id
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_games: Vec<String>,
 }
@@ -0,0 +1,295 @@
+//! Native Messaging のプロトコルバージョンと互換シム。
+//!
+//! v1 はバージョン導入前のメッセージで、`protocol_version` を持たず、エラーは文字列のみ。
+//! v2 からエンベロープに `protocol_version` が入り、失敗時は `error_code` を返す。
+//! ホストは一つ前のバージョン（v1）まで受け付け、v1 のリクエストには v1 の形で応答する。
+
+use crate::models::common::{
+    HealthCheckRequestTs, NativeErrorCodeTs, NativeMessageTs, NativeResponseCase, NativeResponseTs,
+};
+
+/// ホストが話す最新のプロトコルバージョン
+pub const PROTOCOL_VERSION: u32 = 2;
+/// 互換シムで受け付ける最古のバージョン。`protocol_version` を省略したメッセージはこれとみなす
+pub const LEGACY_PROTOCOL_VERSION: u32 = 1;
+
+/// ホストが処理できるメッセージケース
+pub const CAPABILITIES: &[&str] = &[
+    "SyncDmmGames",
+    "SyncDlsiteGames",
+    "DownloadsCompleted",
+    "GetStatus",
+    "SetConfig",
+    "HealthCheck",
+    "BeginSyncSession",
+    "SyncDmmGamesChunk",
+    "SyncDlsiteGamesChunk",
+    "CommitSyncSession",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolError {
+    pub code: NativeErrorCodeTs,
+    pub message: String,
+    /// 読み取れた範囲の request_id。読めなければ空
+    pub request_id: String,
+    /// 読み取れた範囲のバージョン。不明なら最新の形で応答する
+    pub protocol_version: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedRequest {
+    pub protocol_version: u32,
+    pub message: NativeMessageTs,
+}
+
+pub fn supported_protocol_versions() -> Vec<u32> {
+    (LEGACY_PROTOCOL_VERSION..=PROTOCOL_VERSION).collect()
+}
+
+/// フレームの中身をバージョンとケースを確かめてから型付きのメッセージにする
+pub fn decode_request(bytes: &[u8]) -> Result<DecodedRequest, ProtocolError> {
+    let value: serde_json::Value = serde_json::from_slice(bytes).map_err(|e| ProtocolError {
+        code: NativeErrorCodeTs::InvalidJson,
+        message: e.to_string(),
+        request_id: String::new(),
+        protocol_version: None,
+    })?;
+    let request_id = value
+        .get("request_id")
+        .and_then(|x| x.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let fail = |code, message: String, protocol_version| ProtocolError {
+        code,
+        message,
+        request_id: request_id.clone(),
+        protocol_version,
+    };
+
+    let protocol_version = match value.get("protocol_version") {
+        None | Some(serde_json::Value::Null) => LEGACY_PROTOCOL_VERSION,
+        Some(v) => match v.as_u64().and_then(|v| u32::try_from(v).ok()) {
+            Some(v) => v,
+            None => {
+                return Err(fail(
+                    NativeErrorCodeTs::InvalidMessage,
+                    format!("protocol_version must be a positive integer: {v}"),
+                    None,
+                ))
+            }
+        },
+    };
+    if !(LEGACY_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&protocol_version) {
+        return Err(fail(
+            NativeErrorCodeTs::UnsupportedProtocolVersion,
+            format!(
+                "protocol version {protocol_version} is not supported (host supports {LEGACY_PROTOCOL_VERSION}..={PROTOCOL_VERSION})"
+            ),
+            None,
+        ));
+    }
+
+    let case = value
+        .get("message")
+        .and_then(|m| m.get("case"))
+        .and_then(|c| c.as_str());
+    match case {
+        None => {
+            return Err(fail(
+                NativeErrorCodeTs::InvalidMessage,
+                "message.case is missing".to_string(),
+                Some(protocol_version),
+            ))
+        }
+        Some(case) if !CAPABILITIES.contains(&case) => {
+            return Err(fail(
+                NativeErrorCodeTs::UnknownMessageCase,
+                format!("unknown message case: {case}"),
+                Some(protocol_version),
+            ))
+        }
+        Some(_) => {}
+    }
+
+    let mut message: NativeMessageTs = serde_json::from_value(value).map_err(|e| {
+        fail(
+            NativeErrorCodeTs::InvalidMessage,
+            e.to_string(),
+            Some(protocol_version),
+        )
+    })?;
+    message.protocol_version = Some(protocol_version);
+    Ok(DecodedRequest {
+        protocol_version,
+        message,
+    })
+}
+
+/// リクエストのバージョンに合わせて応答を整える。v1 には v2 で増えた項目を載せない
+pub fn encode_response(mut response: NativeResponseTs, protocol_version: u32) -> NativeResponseTs {
+    if protocol_version == LEGACY_PROTOCOL_VERSION {
+        response.error_code = None;
+        response.protocol_version = None;
+        if let Some(NativeResponseCase::HealthCheckResult(result)) = response.response.as_mut() {
+            result.supported_protocol_versions.clear();
+            result.capabilities.clear();
+        }
+    } else {
+        response.protocol_version = Some(protocol_version);
+    }
+    response
+}
+
+pub fn error_response(err: &ProtocolError) -> NativeResponseTs {
+    encode_response(
+        NativeResponseTs {
+            success: false,
+            error: err.message.clone(),
+            request_id: err.request_id.clone(),
+            response: None,
+            error_code: Some(err.code),
+            protocol_version: None,
+        },
+        err.protocol_version.unwrap_or(PROTOCOL_VERSION),
+    )
+}
+
+/// 拡張機能が要求したケースのうちホストが対応しているもの。要求が空なら全ケース
+pub fn negotiate_capabilities(request: &HealthCheckRequestTs) -> Vec<String> {
+    CAPABILITIES
+        .iter()
+        .filter(|c| request.capabilities.is_empty() || request.capabilities.iter().any(|r| r == *c))
+        .map(|c| c.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::common::{HealthCheckResultTs, NativeMessageCase};
+
+    macro_rules! fixture {
+        ($name:literal) => {
+            include_str!(concat!("../fixtures/protocol/", $name))
+        };
+    }
+
+    fn json(s: &str) -> serde_json::Value {
+        serde_json::from_str(s).unwrap()
+    }
+
+    fn health_check_response(request_id: &str) -> NativeResponseTs {
+        NativeResponseTs {
+            success: true,
+            error: String::new(),
+            request_id: request_id.to_string(),
+            response: Some(NativeResponseCase::HealthCheckResult(HealthCheckResultTs {
+                message: "OK".into(),
+                version: "0.1.0".into(),
+                supported_protocol_versions: supported_protocol_versions(),
+                capabilities: vec!["GetStatus".into(), "HealthCheck".into()],
+            })),
+            error_code: None,
+            protocol_version: None,
+        }
+    }
+
+    #[test]
+    fn v1のリクエストはバージョン省略でも受け付ける() {
+        for fixture in [
+            fixture!("v1_health_check.request.json"),
+            fixture!("v1_get_status.request.json"),
+            fixture!("v1_set_config.request.json"),
+            fixture!("v1_sync_dmm_games.request.json"),
+        ] {
+            let decoded = decode_request(fixture.as_bytes()).unwrap();
+            assert_eq!(decoded.protocol_version, LEGACY_PROTOCOL_VERSION);
+            assert_eq!(
+                decoded.message.protocol_version,
+                Some(LEGACY_PROTOCOL_VERSION)
+            );
+        }
+    }
+
+    #[test]
+    fn v2のヘルスチェックは要求したケースを受け取れる() {
+        let decoded = decode_request(fixture!("v2_health_check.request.json").as_bytes()).unwrap();
+        assert_eq!(decoded.protocol_version, 2);
+        let NativeMessageCase::HealthCheck(request) = decoded.message.message else {
+            panic!("unexpected case");
+        };
+        assert_eq!(
+            negotiate_capabilities(&request),
+            vec!["GetStatus".to_string(), "HealthCheck".to_string()]
+        );
+        assert_eq!(
+            negotiate_capabilities(&HealthCheckRequestTs::default()).len(),
+            CAPABILITIES.len()
+        );
+    }
+
+    #[test]
+    fn v1への応答はv1の形に戻す() {
+        let response = encode_response(health_check_response("rid-v1"), LEGACY_PROTOCOL_VERSION);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json(fixture!("v1_health_check.response.json"))
+        );
+    }
+
+    #[test]
+    fn v2への応答はバージョンと対応状況を含める() {
+        let response = encode_response(health_check_response("rid-v2"), PROTOCOL_VERSION);
+        assert_eq!(
+            serde_json::to_value(&response).unwrap(),
+            json(fixture!("v2_health_check.response.json"))
+        );
+    }
+
+    #[test]
+    fn 新しすぎるバージョンはエラーコード付きで拒否する() {
+        let err = decode_request(fixture!("v3_get_status.request.json").as_bytes()).unwrap_err();
+        assert_eq!(err.code, NativeErrorCodeTs::UnsupportedProtocolVersion);
+        assert_eq!(err.request_id, "rid-v3");
+        assert_eq!(
+            serde_json::to_value(error_response(&err)).unwrap(),
+            json(fixture!("v3_get_status.response.json"))
+        );
+    }
+
+    #[test]
+    fn 未知のケースはエラーコード付きで拒否する() {
+        let err = decode_request(fixture!("v2_unknown_case.request.json").as_bytes()).unwrap_err();
+        assert_eq!(err.code, NativeErrorCodeTs::UnknownMessageCase);
+        assert_eq!(
+            serde_json::to_value(error_response(&err)).unwrap(),
+            json(fixture!("v2_unknown_case.response.json"))
+        );
+    }
+
+    #[test]
+    fn v1の未知のケースは文字列のエラーだけ返す() {
+        let err = decode_request(
+            br#"{"request_id":"rid-old","message":{"case":"ExportLibrary","value":{}}}"#,
+        )
+        .unwrap_err();
+        assert_eq!(err.code, NativeErrorCodeTs::UnknownMessageCase);
+        let response = serde_json::to_value(error_response(&err)).unwrap();
+        assert!(response.get("error_code").is_none());
+        assert!(response.get("protocol_version").is_none());
+        assert_eq!(response["error"], "unknown message case: ExportLibrary");
+    }
+
+    #[test]
+    fn スキーマ違反と壊れたjsonを区別する() {
+        let err =
+            decode_request(fixture!("v2_invalid_set_config.request.json").as_bytes()).unwrap_err();
+        assert_eq!(err.code, NativeErrorCodeTs::InvalidMessage);
+        assert_eq!(err.request_id, "rid-invalid");
+
+        let err = decode_request(b"{\"request_id\": ").unwrap_err();
+        assert_eq!(err.code, NativeErrorCodeTs::InvalidJson);
+        assert_eq!(err.request_id, "");
+    }
+}
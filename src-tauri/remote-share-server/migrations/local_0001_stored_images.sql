@@ -0,0 +1,8 @@
+-- R2 のオブジェクトメタデータの代わりに、ローカルに置いた画像の Content-Type を持つ
+CREATE TABLE IF NOT EXISTS stored_images (
+  image_key TEXT PRIMARY KEY,
+  content_type TEXT NOT NULL,
+  byte_size INTEGER NOT NULL,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
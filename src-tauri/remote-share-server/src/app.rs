@@ -0,0 +1,538 @@
+//! `server/src/app.ts` と同じ API
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::{
+    body::Bytes,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        DefaultBodyLimit, Path, Query, State,
+    },
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE, HOST, SET_COOKIE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
+    Json, Router,
+};
+use futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tower_http::services::{ServeDir, ServeFile};
+
+use crate::{
+    broker::{BrokerMessage, LaunchBroker},
+    config::ServerConfig,
+    cookies::{create_session_cookie, has_session},
+    crypto::sha256_hex,
+    db::{
+        encode_uri_component, is_sync_base_current, remote_share_dedupe_key_for_work,
+        remote_share_image_key_for_dedupe_key, snapshot_work_ids_to_remove, Db, DeviceRecord,
+        DeviceSnapshotRow,
+    },
+    errors::{ApiError, ApiResult},
+    schema::{
+        parse_body, DeviceRegisterInput, DeviceRegisterOutput, DeviceSessionInput,
+        DeviceWorksListItem, DeviceWorksListOutput, DeviceWorksSyncCommitInput,
+        DeviceWorksSyncCommitOutput, DeviceWorksSyncPrepareInput, DeviceWorksSyncPrepareOutput,
+        RemoteShareUploadTarget, RemoteShareWorkInput,
+    },
+    storage::ImageStorage,
+    uploads::{create_upload_url, verify_upload},
+};
+
+/// 同期の JSON とサムネイルの上限
+const MAX_BODY_BYTES: usize = 32 * 1024 * 1024;
+/// 切れた接続を見つけるための ping の間隔
+const BROKER_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone)]
+pub struct AppState {
+    pub db: Db,
+    pub storage: ImageStorage,
+    pub broker: LaunchBroker,
+    pub config: Arc<ServerConfig>,
+}
+
+pub fn router(state: AppState) -> Router {
+    let api = Router::new()
+        .route("/api/device/register", post(handle_register_device))
+        .route("/api/device/session", post(handle_create_session))
+        .route("/api/device/:device_id/works", get(handle_list_works))
+        .route(
+            "/api/device/:device_id/works/:work_id/launch",
+            post(handle_launch_work),
+        )
+        .route(
+            "/api/device/:device_id/launch-broker",
+            get(handle_launch_broker_connect),
+        )
+        .route(
+            "/api/device/:device_id/works/sync/prepare",
+            post(handle_prepare_sync_works),
+        )
+        .route(
+            "/api/device/:device_id/works/sync/commit",
+            post(handle_commit_sync_works),
+        )
+        .route(
+            "/api/device/:device_id/images/*image_key",
+            get(handle_image_request),
+        )
+        .route("/api/uploads/*image_key", put(handle_upload_image))
+        .route(
+            "/api/*rest",
+            axum::routing::any(|| async { ApiError::NotFound("Not found".to_string()) }),
+        )
+        .layer(DefaultBodyLimit::max(MAX_BODY_BYTES));
+
+    // 共有ページは SPA なので、ファイルがなければ index.html を返す
+    let api = match &state.config.ui_dir {
+        Some(ui_dir) => api.fallback_service(
+            ServeDir::new(ui_dir).not_found_service(ServeFile::new(ui_dir.join("index.html"))),
+        ),
+        None => api,
+    };
+    api.with_state(state)
+}
+
+async fn handle_register_device(State(state): State<AppState>, body: Bytes) -> ApiResult<Response> {
+    let input: DeviceRegisterInput = parse_body(&body)?;
+    let secret_hash = sha256_hex(&input.device_secret);
+
+    if let Some(existing) = state.db.find_device_by_secret_hash(&secret_hash).await? {
+        return Ok(Json(DeviceRegisterOutput {
+            device_id: existing.device_id,
+        })
+        .into_response());
+    }
+
+    let device_id = uuid::Uuid::new_v4().to_string();
+    state.db.insert_device(&device_id, &secret_hash).await?;
+    Ok(Json(DeviceRegisterOutput { device_id }).into_response())
+}
+
+async fn handle_create_session(State(state): State<AppState>, body: Bytes) -> ApiResult<Response> {
+    let input: DeviceSessionInput = parse_body(&body)?;
+    match state.db.find_device_by_id(&input.device_id).await? {
+        Some(device) if device.secret_hash == sha256_hex(&input.device_secret) => {}
+        _ => {
+            return Err(ApiError::Unauthorized(
+                "deviceSecret is invalid".to_string(),
+            ))
+        }
+    }
+
+    let cookie = create_session_cookie(
+        &state.config.session_secret,
+        &input.device_id,
+        state.config.session_ttl_seconds,
+        now_ms(),
+    );
+    Ok((StatusCode::NO_CONTENT, [(SET_COOKIE, cookie)]).into_response())
+}
+
+async fn handle_list_works(
+    State(state): State<AppState>,
+    Path(device_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Json<DeviceWorksListOutput>> {
+    let device = find_device(&state, &device_id).await?;
+    require_session(&state, &headers, &device_id)?;
+    let rows = state.db.list_device_snapshots(&device_id).await?;
+
+    Ok(Json(DeviceWorksListOutput {
+        works: rows
+            .into_iter()
+            .map(|row| to_public_work_item(&device_id, row))
+            .collect(),
+        device_id,
+        last_synced_at: device.last_synced_at,
+    }))
+}
+
+async fn handle_prepare_sync_works(
+    State(state): State<AppState>,
+    Path(device_id): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<Json<DeviceWorksSyncPrepareOutput>> {
+    let input: DeviceWorksSyncPrepareInput = parse_body(&body)?;
+    let device = find_device(&state, &device_id).await?;
+    require_device_secret(&device, &input.device_secret)?;
+
+    if !is_sync_base_current(
+        input.mode,
+        device.last_synced_at.as_deref(),
+        input.base_synced_at.as_deref(),
+    ) {
+        return Err(ApiError::ResyncRequired(
+            "baseSyncedAt does not match the last sync".to_string(),
+        ));
+    }
+
+    let dedupe_keys: Vec<String> = input
+        .works
+        .iter()
+        .filter(|work| work.thumbnail.is_some())
+        .map(remote_share_dedupe_key_for_work)
+        .collect();
+    let existing_images = state
+        .db
+        .list_remote_share_images_by_dedupe_keys(&dedupe_keys)
+        .await?;
+    let public_base_url = public_base_url(&state.config, &headers)?;
+    let expires_at = now_ms() / 1000 + state.config.upload_ttl_seconds;
+    let mut seen = HashSet::new();
+    let mut upload_targets = vec![];
+
+    for work in &input.works {
+        let Some(thumbnail) = &work.thumbnail else {
+            continue;
+        };
+        let dedupe_key = remote_share_dedupe_key_for_work(work);
+        if !seen.insert(dedupe_key.clone()) || existing_images.contains_key(&dedupe_key) {
+            continue;
+        }
+
+        let image_key = remote_share_image_key_for_dedupe_key(&dedupe_key);
+        upload_targets.push(RemoteShareUploadTarget {
+            work_id: work.work_id.clone(),
+            upload_url: create_upload_url(
+                &public_base_url,
+                &state.config.session_secret,
+                &image_key,
+                &thumbnail.content_type,
+                expires_at,
+            ),
+            content_type: thumbnail.content_type.clone(),
+            dedupe_key,
+            image_key,
+        });
+    }
+
+    Ok(Json(DeviceWorksSyncPrepareOutput {
+        device_id,
+        upload_targets,
+    }))
+}
+
+async fn handle_commit_sync_works(
+    State(state): State<AppState>,
+    Path(device_id): Path<String>,
+    body: Bytes,
+) -> ApiResult<Json<DeviceWorksSyncCommitOutput>> {
+    let input: DeviceWorksSyncCommitInput = parse_body(&body)?;
+    let device = find_device(&state, &device_id).await?;
+    require_device_secret(&device, &input.device_secret)?;
+
+    if !is_sync_base_current(
+        input.mode,
+        device.last_synced_at.as_deref(),
+        input.base_synced_at.as_deref(),
+    ) {
+        return Err(ApiError::ResyncRequired(
+            "baseSyncedAt does not match the last sync".to_string(),
+        ));
+    }
+
+    let mut image_keys = HashMap::new();
+    for uploaded_image in &input.uploaded_images {
+        image_keys.insert(
+            uploaded_image.dedupe_key.clone(),
+            uploaded_image.image_key.clone(),
+        );
+        state
+            .db
+            .insert_remote_share_image(&uploaded_image.dedupe_key, &uploaded_image.image_key)
+            .await?;
+    }
+
+    let missing_dedupe_keys: Vec<String> = input
+        .works
+        .iter()
+        .map(remote_share_dedupe_key_for_work)
+        .filter(|dedupe_key| !image_keys.contains_key(dedupe_key))
+        .collect();
+    image_keys.extend(
+        state
+            .db
+            .list_remote_share_images_by_dedupe_keys(&missing_dedupe_keys)
+            .await?,
+    );
+
+    let synced_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+    let existing_rows = state.db.list_device_snapshots(&device_id).await?;
+    let existing_map: HashMap<&str, &DeviceSnapshotRow> = existing_rows
+        .iter()
+        .map(|row| (row.work_id.as_str(), row))
+        .collect();
+    let works_to_upsert: Vec<&RemoteShareWorkInput> = input
+        .works
+        .iter()
+        .filter(|work| {
+            let image_key = image_keys.get(&remote_share_dedupe_key_for_work(work));
+            existing_map
+                .get(work.work_id.as_str())
+                .is_none_or(|existing| snapshot_changed(existing, work, image_key))
+        })
+        .collect();
+
+    let removed_work_ids = snapshot_work_ids_to_remove(
+        input.mode,
+        &existing_rows
+            .iter()
+            .map(|row| row.work_id.clone())
+            .collect::<Vec<_>>(),
+        &input.works,
+        &input.removed_work_ids,
+    );
+    let removed_count = state
+        .db
+        .delete_device_snapshots(&device_id, &removed_work_ids)
+        .await?;
+    state
+        .db
+        .upsert_device_snapshots(&device_id, &works_to_upsert, &image_keys, &synced_at)
+        .await?;
+
+    // 端末が持っている件数と合わなければ、差分の取りこぼしがあるので全件を送り直してもらう
+    let remaining_count = state.db.count_device_snapshots(&device_id).await?;
+    let resync_required = input
+        .total_count
+        .is_some_and(|total_count| total_count != remaining_count);
+
+    Ok(Json(DeviceWorksSyncCommitOutput {
+        device_id,
+        synced_count: input.works.len(),
+        removed_count,
+        last_synced_at: synced_at,
+        resync_required,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadQuery {
+    content_type: String,
+    expires: i64,
+    signature: String,
+}
+
+async fn handle_upload_image(
+    State(state): State<AppState>,
+    Path(image_key): Path<String>,
+    Query(query): Query<UploadQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> ApiResult<StatusCode> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if content_type != query.content_type
+        || !verify_upload(
+            &state.config.session_secret,
+            &image_key,
+            &query.content_type,
+            query.expires,
+            &query.signature,
+            now_ms() / 1000,
+        )
+    {
+        return Err(ApiError::Unauthorized(
+            "upload signature is invalid or expired".to_string(),
+        ));
+    }
+    if state.storage.path_for(&image_key).is_none() {
+        return Err(ApiError::BadRequest("invalid image key".to_string()));
+    }
+
+    state.storage.write(&image_key, &body).await?;
+    state
+        .db
+        .upsert_stored_image(&image_key, content_type, body.len() as i64)
+        .await?;
+    Ok(StatusCode::OK)
+}
+
+async fn handle_image_request(
+    State(state): State<AppState>,
+    Path((device_id, image_key)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    require_session(&state, &headers, &device_id)?;
+
+    let not_found = || (StatusCode::NOT_FOUND, "Not found").into_response();
+    let Some(image) = state.db.find_stored_image(&image_key).await? else {
+        return Ok(not_found());
+    };
+    let Some(bytes) = state.storage.read(&image_key).await? else {
+        return Ok(not_found());
+    };
+
+    Ok((
+        [
+            (CONTENT_TYPE, image.content_type),
+            (CACHE_CONTROL, "private, max-age=300".to_string()),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchBrokerQuery {
+    #[serde(default)]
+    device_secret: String,
+}
+
+async fn handle_launch_broker_connect(
+    State(state): State<AppState>,
+    Path(device_id): Path<String>,
+    Query(query): Query<LaunchBrokerQuery>,
+    ws: WebSocketUpgrade,
+) -> ApiResult<Response> {
+    let device = state.db.find_device_by_id(&device_id).await?;
+    match device {
+        Some(device) if device.secret_hash == sha256_hex(&query.device_secret) => {}
+        _ => {
+            return Err(ApiError::Unauthorized(
+                "deviceSecret is invalid".to_string(),
+            ))
+        }
+    }
+
+    Ok(ws.on_upgrade(move |socket| serve_desktop_socket(socket, state.broker, device_id)))
+}
+
+async fn serve_desktop_socket(socket: WebSocket, broker: LaunchBroker, device_id: String) {
+    let mut connection = broker.connect(&device_id);
+    let (mut sender, mut receiver) = socket.split();
+    let mut ping = tokio::time::interval(BROKER_PING_INTERVAL);
+    ping.tick().await;
+    log::info!("desktop connected to launch broker: {device_id}");
+
+    loop {
+        tokio::select! {
+            message = connection.recv() => {
+                let Some(message) = message else { break };
+                let Ok(text) = serde_json::to_string(&message) else { continue };
+                if sender.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = receiver.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    if let Ok(BrokerMessage::LaunchAck { work_id, status, decision }) =
+                        serde_json::from_str::<BrokerMessage>(&text)
+                    {
+                        log::info!(
+                            "launch ack from {device_id}: work={work_id}, status={status}, decision={}",
+                            decision.as_deref().unwrap_or("-")
+                        );
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            _ = ping.tick() => {
+                if sender.send(Message::Ping(vec![])).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    log::info!("desktop disconnected from launch broker: {device_id}");
+}
+
+async fn handle_launch_work(
+    State(state): State<AppState>,
+    Path((device_id, work_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> ApiResult<StatusCode> {
+    find_device(&state, &device_id).await?;
+    require_session(&state, &headers, &device_id)?;
+
+    state.broker.request_launch(&device_id, &work_id)?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn find_device(state: &AppState, device_id: &str) -> ApiResult<DeviceRecord> {
+    state
+        .db
+        .find_device_by_id(device_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound("device not found".to_string()))
+}
+
+fn require_device_secret(device: &DeviceRecord, device_secret: &str) -> ApiResult<()> {
+    if device.secret_hash != sha256_hex(device_secret) {
+        return Err(ApiError::Unauthorized(
+            "deviceSecret is invalid".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn require_session(state: &AppState, headers: &HeaderMap, device_id: &str) -> ApiResult<()> {
+    if !has_session(headers, &state.config.session_secret, device_id, now_ms()) {
+        return Err(ApiError::unauthorized());
+    }
+    Ok(())
+}
+
+fn public_base_url(config: &ServerConfig, headers: &HeaderMap) -> ApiResult<String> {
+    if let Some(url) = &config.public_base_url {
+        return Ok(url.clone());
+    }
+    let host = headers
+        .get(HOST)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| ApiError::BadRequest("Host header is required".to_string()))?;
+    Ok(format!("http://{host}"))
+}
+
+fn snapshot_changed(
+    existing: &DeviceSnapshotRow,
+    work: &RemoteShareWorkInput,
+    image_key: Option<&String>,
+) -> bool {
+    existing.title != work.title
+        || existing.erogamescape_id != work.erogamescape_id
+        || existing.official_url != work.official_url
+        || existing.erogamescape_url != work.erogamescape_url
+        || existing.seiya_url != work.seiya_url
+        || existing.image_key.as_ref() != image_key
+        || existing.thumbnail_width != work.thumbnail.as_ref().and_then(|t| t.width)
+        || existing.thumbnail_height != work.thumbnail.as_ref().and_then(|t| t.height)
+}
+
+fn to_public_work_item(device_id: &str, row: DeviceSnapshotRow) -> DeviceWorksListItem {
+    DeviceWorksListItem {
+        image_url: row.image_key.map(|image_key| {
+            format!(
+                "/api/device/{}/images/{}",
+                encode_uri_component(device_id),
+                encode_uri_component(&image_key)
+            )
+        }),
+        work_id: row.work_id,
+        title: row.title,
+        width: row.thumbnail_width,
+        height: row.thumbnail_height,
+        official_url: row.official_url,
+        erogamescape_url: row.erogamescape_url,
+        seiya_url: row.seiya_url,
+    }
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
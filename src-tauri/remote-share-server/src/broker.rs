@@ -0,0 +1,168 @@
+//! 作品の起動要求をデスクトップに中継する。Workers 版の `RemoteLaunchBroker` にあたる
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::errors::{ApiError, ApiResult};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum BrokerMessage {
+    #[serde(rename = "launch-work")]
+    LaunchWork {
+        #[serde(rename = "workId")]
+        work_id: String,
+    },
+    #[serde(rename = "launch-ack")]
+    LaunchAck {
+        #[serde(rename = "workId")]
+        work_id: String,
+        status: String,
+        /// デスクトップの起動ポリシーによる判断。古いデスクトップは送らない
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        decision: Option<String>,
+    },
+}
+
+type Senders = HashMap<String, HashMap<u64, mpsc::UnboundedSender<BrokerMessage>>>;
+
+/// 端末ごとに、接続中のデスクトップへの送信口を持つ
+#[derive(Clone, Default)]
+pub struct LaunchBroker {
+    senders: Arc<Mutex<Senders>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl LaunchBroker {
+    /// デスクトップの接続を登録する。戻り値を落とすと登録も外れる
+    pub fn connect(&self, device_id: &str) -> DesktopConnection {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.senders
+            .lock()
+            .expect("poisoned launch broker")
+            .entry(device_id.to_string())
+            .or_default()
+            .insert(id, tx);
+        DesktopConnection {
+            broker: self.clone(),
+            device_id: device_id.to_string(),
+            id,
+            rx,
+        }
+    }
+
+    /// 接続中のすべてのデスクトップに起動を頼む
+    pub fn request_launch(&self, device_id: &str, work_id: &str) -> ApiResult<()> {
+        let work_id = work_id.trim();
+        if work_id.is_empty() {
+            return Err(ApiError::BadRequest("workId is required".to_string()));
+        }
+
+        let senders = self.senders.lock().expect("poisoned launch broker");
+        let delivered = senders
+            .get(device_id)
+            .into_iter()
+            .flat_map(|senders| senders.values())
+            .filter(|tx| {
+                tx.send(BrokerMessage::LaunchWork {
+                    work_id: work_id.to_string(),
+                })
+                .is_ok()
+            })
+            .count();
+        if delivered == 0 {
+            return Err(ApiError::DesktopNotConnected);
+        }
+        Ok(())
+    }
+
+    pub fn connected_count(&self, device_id: &str) -> usize {
+        self.senders
+            .lock()
+            .expect("poisoned launch broker")
+            .get(device_id)
+            .map_or(0, HashMap::len)
+    }
+
+    fn disconnect(&self, device_id: &str, id: u64) {
+        let mut senders = self.senders.lock().expect("poisoned launch broker");
+        if let Some(device_senders) = senders.get_mut(device_id) {
+            device_senders.remove(&id);
+            if device_senders.is_empty() {
+                senders.remove(device_id);
+            }
+        }
+    }
+}
+
+pub struct DesktopConnection {
+    broker: LaunchBroker,
+    device_id: String,
+    id: u64,
+    rx: mpsc::UnboundedReceiver<BrokerMessage>,
+}
+
+impl DesktopConnection {
+    pub async fn recv(&mut self) -> Option<BrokerMessage> {
+        self.rx.recv().await
+    }
+}
+
+impl Drop for DesktopConnection {
+    fn drop(&mut self) {
+        self.broker.disconnect(&self.device_id, self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn request_launch_接続中のデスクトップすべてに送る() {
+        let broker = LaunchBroker::default();
+        let mut first = broker.connect("device-1");
+        let mut second = broker.connect("device-1");
+        let _other = broker.connect("device-2");
+
+        broker.request_launch("device-1", " work-1 ").unwrap();
+
+        let expected = BrokerMessage::LaunchWork {
+            work_id: "work-1".to_string(),
+        };
+        assert_eq!(first.recv().await, Some(expected));
+        assert_eq!(
+            second.recv().await,
+            Some(BrokerMessage::LaunchWork {
+                work_id: "work-1".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn request_launch_接続がなければdesktop_not_connected() {
+        let broker = LaunchBroker::default();
+        let connection = broker.connect("device-1");
+        assert_eq!(broker.connected_count("device-1"), 1);
+        drop(connection);
+
+        assert_eq!(broker.connected_count("device-1"), 0);
+        assert!(matches!(
+            broker.request_launch("device-1", "work-1"),
+            Err(ApiError::DesktopNotConnected)
+        ));
+        assert!(matches!(
+            broker.request_launch("device-1", " "),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+}
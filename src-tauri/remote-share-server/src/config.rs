@@ -0,0 +1,121 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use anyhow::Context;
+
+const DEFAULT_BIND_ADDR: &str = "0.0.0.0:8787";
+const DEFAULT_DATA_DIR: &str = "remote-share-data";
+const DEFAULT_SESSION_TTL_SECONDS: i64 = 60 * 60;
+const DEFAULT_UPLOAD_TTL_SECONDS: i64 = 15 * 60;
+const SESSION_SECRET_FILE_NAME: &str = "session-secret";
+
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    pub bind_addr: SocketAddr,
+    /// SQLite と画像を置くディレクトリ
+    pub data_dir: PathBuf,
+    /// アップロード先 URL に使う外向きの URL。未設定ならリクエストの Host から作る
+    pub public_base_url: Option<String>,
+    /// 閲覧セッションとアップロード URL の署名鍵
+    pub session_secret: String,
+    pub session_ttl_seconds: i64,
+    pub upload_ttl_seconds: i64,
+    /// 共有ページ（`server/ui` の build 結果）。未設定なら API だけを提供する
+    pub ui_dir: Option<PathBuf>,
+}
+
+impl ServerConfig {
+    /// 環境変数から読む。署名鍵が未設定なら `data_dir` に生成して使い回す
+    pub fn from_env() -> anyhow::Result<Self> {
+        let bind_addr = env_var("LAUNCHERG_REMOTE_SHARE_BIND")
+            .unwrap_or_else(|| DEFAULT_BIND_ADDR.to_string())
+            .parse()
+            .context("LAUNCHERG_REMOTE_SHARE_BIND must be an address like 0.0.0.0:8787")?;
+        let data_dir = PathBuf::from(
+            env_var("LAUNCHERG_REMOTE_SHARE_DATA_DIR").unwrap_or_else(|| DEFAULT_DATA_DIR.into()),
+        );
+        std::fs::create_dir_all(&data_dir)
+            .with_context(|| format!("failed to create data dir: {}", data_dir.display()))?;
+        let session_secret = match env_var("LAUNCHERG_REMOTE_SHARE_SESSION_SECRET") {
+            Some(secret) => secret,
+            None => load_or_create_session_secret(&data_dir)?,
+        };
+
+        Ok(Self {
+            bind_addr,
+            public_base_url: env_var("LAUNCHERG_REMOTE_SHARE_PUBLIC_URL")
+                .map(|url| url.trim_end_matches('/').to_string()),
+            session_secret,
+            session_ttl_seconds: parse_seconds(
+                "LAUNCHERG_REMOTE_SHARE_SESSION_TTL_SECONDS",
+                DEFAULT_SESSION_TTL_SECONDS,
+            )?,
+            upload_ttl_seconds: parse_seconds(
+                "LAUNCHERG_REMOTE_SHARE_UPLOAD_TTL_SECONDS",
+                DEFAULT_UPLOAD_TTL_SECONDS,
+            )?,
+            ui_dir: env_var("LAUNCHERG_REMOTE_SHARE_UI_DIR").map(PathBuf::from),
+            data_dir,
+        })
+    }
+
+    /// `data_dir` 以外を既定値にした設定
+    pub fn with_data_dir(data_dir: PathBuf, session_secret: String) -> Self {
+        Self {
+            bind_addr: DEFAULT_BIND_ADDR
+                .parse()
+                .expect("default bind addr is valid"),
+            data_dir,
+            public_base_url: None,
+            session_secret,
+            session_ttl_seconds: DEFAULT_SESSION_TTL_SECONDS,
+            upload_ttl_seconds: DEFAULT_UPLOAD_TTL_SECONDS,
+            ui_dir: None,
+        }
+    }
+
+    pub fn db_file_path(&self) -> PathBuf {
+        self.data_dir.join("remote-share.db")
+    }
+
+    pub fn images_dir(&self) -> PathBuf {
+        self.data_dir.join("images")
+    }
+}
+
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_seconds(key: &str, default: i64) -> anyhow::Result<i64> {
+    match env_var(key) {
+        Some(value) => value
+            .parse::<i64>()
+            .ok()
+            .filter(|seconds| *seconds > 0)
+            .with_context(|| format!("{key} must be a positive number of seconds")),
+        None => Ok(default),
+    }
+}
+
+fn load_or_create_session_secret(data_dir: &std::path::Path) -> anyhow::Result<String> {
+    let path = data_dir.join(SESSION_SECRET_FILE_NAME);
+    if let Ok(secret) = std::fs::read_to_string(&path) {
+        let secret = secret.trim().to_string();
+        if !secret.is_empty() {
+            return Ok(secret);
+        }
+    }
+
+    let secret = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    std::fs::write(&path, &secret)
+        .with_context(|| format!("failed to write session secret: {}", path.display()))?;
+    log::info!("generated session secret: {}", path.display());
+    Ok(secret)
+}
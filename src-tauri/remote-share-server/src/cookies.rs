@@ -0,0 +1,111 @@
+//! 閲覧セッションの Cookie。Workers 版と同じ形式なので、同じ secret なら相互に通る
+
+use axum::http::{header::COOKIE, HeaderMap};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::{constant_time_eq, hmac_sha256_hex};
+
+pub const SESSION_COOKIE_NAME: &str = "launcherg_remote_session";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionPayload {
+    device_id: String,
+    /// UNIX ミリ秒
+    expires_at: i64,
+}
+
+pub fn create_session_cookie(
+    session_secret: &str,
+    device_id: &str,
+    ttl_seconds: i64,
+    now_ms: i64,
+) -> String {
+    let payload = SessionPayload {
+        device_id: device_id.to_string(),
+        expires_at: now_ms + ttl_seconds * 1000,
+    };
+    let body = STANDARD.encode(serde_json::to_vec(&payload).expect("payload is serializable"));
+    let signature = hmac_sha256_hex(session_secret, &body);
+    format!(
+        "{SESSION_COOKIE_NAME}={body}.{signature}; HttpOnly; Path=/; SameSite=Lax; Max-Age={ttl_seconds}"
+    )
+}
+
+/// `device_id` の有効なセッションを持っているか
+pub fn has_session(
+    headers: &HeaderMap,
+    session_secret: &str,
+    device_id: &str,
+    now_ms: i64,
+) -> bool {
+    let Some(token) = find_cookie(headers, SESSION_COOKIE_NAME) else {
+        return false;
+    };
+    let Some((body, signature)) = token.split_once('.') else {
+        return false;
+    };
+    if body.is_empty() || !constant_time_eq(&hmac_sha256_hex(session_secret, body), signature) {
+        return false;
+    }
+
+    let Some(payload) = STANDARD
+        .decode(body)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<SessionPayload>(&bytes).ok())
+    else {
+        return false;
+    };
+    payload.device_id == device_id && payload.expires_at > now_ms
+}
+
+fn find_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|segment| segment.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn headers_from_set_cookie(set_cookie: &str) -> HeaderMap {
+        let token = set_cookie.split(';').next().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            COOKIE,
+            HeaderValue::from_str(&format!("other=1; {token}")).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn has_session_発行したcookieを受け付ける() {
+        let cookie = create_session_cookie("secret", "device-1", 60, 1_000);
+        let headers = headers_from_set_cookie(&cookie);
+
+        assert!(has_session(&headers, "secret", "device-1", 1_000));
+        assert!(!has_session(&headers, "secret", "device-2", 1_000));
+        assert!(!has_session(&headers, "other-secret", "device-1", 1_000));
+        assert!(!has_session(&headers, "secret", "device-1", 61_000));
+    }
+
+    #[test]
+    fn create_session_cookie_workers_と同じ本文になる() {
+        // btoa(JSON.stringify({ deviceId: 'device-1', expiresAt: 61000 }))
+        let cookie = create_session_cookie("secret", "device-1", 60, 1_000);
+
+        assert!(cookie.starts_with(
+            "launcherg_remote_session=eyJkZXZpY2VJZCI6ImRldmljZS0xIiwiZXhwaXJlc0F0Ijo2MTAwMH0=."
+        ));
+        assert!(cookie.ends_with("; HttpOnly; Path=/; SameSite=Lax; Max-Age=60"));
+    }
+}
@@ -0,0 +1,522 @@
+//! Workers 版の D1 と同じスキーマを SQLite に置く
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    str::FromStr,
+};
+
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+    QueryBuilder, Row, Sqlite, SqlitePool,
+};
+
+use crate::schema::{RemoteShareSyncMode, RemoteShareWorkInput};
+
+/// 適用するマイグレーション。D1 用のものはそのまま使い、ローカル版だけの表は後ろに足す
+const MIGRATIONS: &[(&str, &str)] = &[
+    (
+        "0001_initial.sql",
+        include_str!("../../../server/migrations/0001_initial.sql"),
+    ),
+    (
+        "0002_remote_share.sql",
+        include_str!("../../../server/migrations/0002_remote_share.sql"),
+    ),
+    (
+        "0003_remote_share_work_links.sql",
+        include_str!("../../../server/migrations/0003_remote_share_work_links.sql"),
+    ),
+    (
+        "local_0001_stored_images.sql",
+        include_str!("../migrations/local_0001_stored_images.sql"),
+    ),
+];
+
+/// 1 文あたりのバインド数を SQLite の上限に収めるための件数
+const BATCH_SIZE: usize = 99;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceRecord {
+    pub device_id: String,
+    pub secret_hash: String,
+    pub last_synced_at: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceSnapshotRow {
+    pub work_id: String,
+    pub title: String,
+    pub image_key: Option<String>,
+    pub thumbnail_width: Option<i64>,
+    pub thumbnail_height: Option<i64>,
+    pub erogamescape_id: Option<i64>,
+    pub official_url: Option<String>,
+    pub erogamescape_url: Option<String>,
+    pub seiya_url: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredImage {
+    pub content_type: String,
+    pub byte_size: i64,
+}
+
+#[derive(Clone)]
+pub struct Db {
+    pool: SqlitePool,
+}
+
+impl Db {
+    pub async fn open(db_file_path: &Path) -> anyhow::Result<Self> {
+        let options = SqliteConnectOptions::from_str(&format!(
+            "sqlite://{}?mode=rwc",
+            db_file_path.display()
+        ))?
+        .foreign_keys(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(std::time::Duration::from_millis(5000));
+        let pool = SqlitePoolOptions::new()
+            .max_connections(16)
+            .connect_with(options)
+            .await?;
+        let db = Self { pool };
+        db.migrate().await?;
+        log::info!("finish setup database. file: {:?}", db_file_path);
+        Ok(db)
+    }
+
+    async fn migrate(&self) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"CREATE TABLE IF NOT EXISTS migrations (
+                name TEXT PRIMARY KEY,
+                applied_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )"#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        let applied: HashSet<String> = sqlx::query("SELECT name FROM migrations")
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| row.get("name"))
+            .collect();
+
+        for (name, sql) in MIGRATIONS {
+            if applied.contains(*name) {
+                continue;
+            }
+            let mut tx = self.pool.begin().await?;
+            sqlx::query(sql).execute(&mut tx).await?;
+            sqlx::query("INSERT INTO migrations (name) VALUES (?)")
+                .bind(*name)
+                .execute(&mut tx)
+                .await?;
+            tx.commit().await?;
+            log::info!("applied migration {name}");
+        }
+        Ok(())
+    }
+
+    pub async fn find_device_by_secret_hash(
+        &self,
+        secret_hash: &str,
+    ) -> anyhow::Result<Option<DeviceRecord>> {
+        let row = sqlx::query(
+            r#"SELECT device_id, secret_hash, last_synced_at
+            FROM devices
+            WHERE secret_hash = ?
+            LIMIT 1"#,
+        )
+        .bind(secret_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|row| DeviceRecord {
+            device_id: row.get("device_id"),
+            secret_hash: row.get("secret_hash"),
+            last_synced_at: row.get("last_synced_at"),
+        }))
+    }
+
+    pub async fn find_device_by_id(&self, device_id: &str) -> anyhow::Result<Option<DeviceRecord>> {
+        let row = sqlx::query(
+            r#"SELECT device_id, secret_hash, last_synced_at
+            FROM devices
+            WHERE device_id = ?
+            LIMIT 1"#,
+        )
+        .bind(device_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|row| DeviceRecord {
+            device_id: row.get("device_id"),
+            secret_hash: row.get("secret_hash"),
+            last_synced_at: row.get("last_synced_at"),
+        }))
+    }
+
+    pub async fn insert_device(&self, device_id: &str, secret_hash: &str) -> anyhow::Result<()> {
+        sqlx::query("INSERT INTO devices (device_id, secret_hash) VALUES (?, ?)")
+            .bind(device_id)
+            .bind(secret_hash)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn list_device_snapshots(
+        &self,
+        device_id: &str,
+    ) -> anyhow::Result<Vec<DeviceSnapshotRow>> {
+        let rows = sqlx::query(
+            r#"SELECT
+                work_id,
+                title,
+                image_key,
+                thumbnail_width,
+                thumbnail_height,
+                erogamescape_id,
+                official_url,
+                erogamescape_url,
+                seiya_url
+            FROM device_work_snapshots
+            WHERE device_id = ?
+            ORDER BY title COLLATE NOCASE ASC"#,
+        )
+        .bind(device_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| DeviceSnapshotRow {
+                work_id: row.get("work_id"),
+                title: row.get("title"),
+                image_key: row.get("image_key"),
+                thumbnail_width: row.get("thumbnail_width"),
+                thumbnail_height: row.get("thumbnail_height"),
+                erogamescape_id: row.get("erogamescape_id"),
+                official_url: row.get("official_url"),
+                erogamescape_url: row.get("erogamescape_url"),
+                seiya_url: row.get("seiya_url"),
+            })
+            .collect())
+    }
+
+    pub async fn count_device_snapshots(&self, device_id: &str) -> anyhow::Result<i64> {
+        let count =
+            sqlx::query("SELECT COUNT(*) AS count FROM device_work_snapshots WHERE device_id = ?")
+                .bind(device_id)
+                .fetch_one(&self.pool)
+                .await?
+                .get("count");
+        Ok(count)
+    }
+
+    pub async fn delete_device_snapshots(
+        &self,
+        device_id: &str,
+        work_ids: &[String],
+    ) -> anyhow::Result<u64> {
+        let unique_ids: Vec<&String> = work_ids
+            .iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut deleted = 0;
+        for chunk in unique_ids.chunks(BATCH_SIZE) {
+            let mut qb =
+                QueryBuilder::<Sqlite>::new("DELETE FROM device_work_snapshots WHERE device_id = ");
+            qb.push_bind(device_id).push(" AND work_id IN (");
+            let mut separated = qb.separated(", ");
+            for work_id in chunk {
+                separated.push_bind(work_id.as_str());
+            }
+            separated.push_unseparated(")");
+            deleted += qb.build().execute(&self.pool).await?.rows_affected();
+        }
+        Ok(deleted)
+    }
+
+    /// 作品を書き込み、端末の最終同期日時を `synced_at` にする
+    pub async fn upsert_device_snapshots(
+        &self,
+        device_id: &str,
+        works: &[&RemoteShareWorkInput],
+        image_keys: &HashMap<String, String>,
+        synced_at: &str,
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+        for work in works {
+            let image_key = image_keys.get(&remote_share_dedupe_key_for_work(work));
+            sqlx::query(
+                r#"INSERT INTO device_work_snapshots (
+                    device_id,
+                    work_id,
+                    erogamescape_id,
+                    title,
+                    official_url,
+                    erogamescape_url,
+                    seiya_url,
+                    image_key,
+                    thumbnail_width,
+                    thumbnail_height,
+                    updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(device_id, work_id) DO UPDATE SET
+                    erogamescape_id = excluded.erogamescape_id,
+                    title = excluded.title,
+                    official_url = excluded.official_url,
+                    erogamescape_url = excluded.erogamescape_url,
+                    seiya_url = excluded.seiya_url,
+                    image_key = excluded.image_key,
+                    thumbnail_width = excluded.thumbnail_width,
+                    thumbnail_height = excluded.thumbnail_height,
+                    updated_at = excluded.updated_at"#,
+            )
+            .bind(device_id)
+            .bind(&work.work_id)
+            .bind(work.erogamescape_id)
+            .bind(&work.title)
+            .bind(&work.official_url)
+            .bind(&work.erogamescape_url)
+            .bind(&work.seiya_url)
+            .bind(image_key)
+            .bind(work.thumbnail.as_ref().and_then(|t| t.width))
+            .bind(work.thumbnail.as_ref().and_then(|t| t.height))
+            .bind(synced_at)
+            .execute(&mut tx)
+            .await?;
+        }
+        sqlx::query(
+            r#"UPDATE devices
+            SET last_synced_at = ?,
+                updated_at = ?
+            WHERE device_id = ?"#,
+        )
+        .bind(synced_at)
+        .bind(synced_at)
+        .bind(device_id)
+        .execute(&mut tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn list_remote_share_images_by_dedupe_keys(
+        &self,
+        dedupe_keys: &[String],
+    ) -> anyhow::Result<HashMap<String, String>> {
+        let unique_keys: Vec<&String> = dedupe_keys
+            .iter()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let mut results = HashMap::new();
+        for chunk in unique_keys.chunks(BATCH_SIZE) {
+            let mut qb = QueryBuilder::<Sqlite>::new(
+                "SELECT dedupe_key, image_key FROM remote_share_images WHERE dedupe_key IN (",
+            );
+            let mut separated = qb.separated(", ");
+            for dedupe_key in chunk {
+                separated.push_bind(dedupe_key.as_str());
+            }
+            separated.push_unseparated(")");
+            for row in qb.build().fetch_all(&self.pool).await? {
+                results.insert(row.get("dedupe_key"), row.get("image_key"));
+            }
+        }
+        Ok(results)
+    }
+
+    pub async fn insert_remote_share_image(
+        &self,
+        dedupe_key: &str,
+        image_key: &str,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            "INSERT OR IGNORE INTO remote_share_images (dedupe_key, image_key) VALUES (?, ?)",
+        )
+        .bind(dedupe_key)
+        .bind(image_key)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn upsert_stored_image(
+        &self,
+        image_key: &str,
+        content_type: &str,
+        byte_size: i64,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"INSERT INTO stored_images (image_key, content_type, byte_size)
+            VALUES (?, ?, ?)
+            ON CONFLICT(image_key) DO UPDATE SET
+                content_type = excluded.content_type,
+                byte_size = excluded.byte_size,
+                updated_at = CURRENT_TIMESTAMP"#,
+        )
+        .bind(image_key)
+        .bind(content_type)
+        .bind(byte_size)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_stored_image(&self, image_key: &str) -> anyhow::Result<Option<StoredImage>> {
+        let row =
+            sqlx::query("SELECT content_type, byte_size FROM stored_images WHERE image_key = ?")
+                .bind(image_key)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|row| StoredImage {
+            content_type: row.get("content_type"),
+            byte_size: row.get("byte_size"),
+        }))
+    }
+}
+
+/// 同期で消す作品。full では送られなかった作品、delta では墓標として送られた作品
+pub fn snapshot_work_ids_to_remove(
+    mode: RemoteShareSyncMode,
+    existing_work_ids: &[String],
+    works: &[RemoteShareWorkInput],
+    removed_work_ids: &[String],
+) -> Vec<String> {
+    let sent: HashSet<&str> = works.iter().map(|work| work.work_id.as_str()).collect();
+    match mode {
+        RemoteShareSyncMode::Full => existing_work_ids
+            .iter()
+            .filter(|work_id| !sent.contains(work_id.as_str()))
+            .cloned()
+            .collect(),
+        RemoteShareSyncMode::Delta => {
+            // 同じ同期で送り直された作品は消さない
+            let existing: HashSet<&str> = existing_work_ids.iter().map(String::as_str).collect();
+            removed_work_ids
+                .iter()
+                .filter(|work_id| {
+                    existing.contains(work_id.as_str()) && !sent.contains(work_id.as_str())
+                })
+                .cloned()
+                .collect()
+        }
+    }
+}
+
+/// delta は端末が前回受け取った lastSyncedAt を起点にしていなければ反映できない
+pub fn is_sync_base_current(
+    mode: RemoteShareSyncMode,
+    last_synced_at: Option<&str>,
+    base_synced_at: Option<&str>,
+) -> bool {
+    match mode {
+        RemoteShareSyncMode::Full => true,
+        RemoteShareSyncMode::Delta => last_synced_at.is_some() && base_synced_at == last_synced_at,
+    }
+}
+
+pub fn remote_share_dedupe_key_for_work(work: &RemoteShareWorkInput) -> String {
+    match work.erogamescape_id {
+        Some(erogamescape_id) => format!("egs:{erogamescape_id}"),
+        None => format!("work:{}", work.work_id),
+    }
+}
+
+pub fn remote_share_image_key_for_dedupe_key(dedupe_key: &str) -> String {
+    format!(
+        "remote-share/{}/thumbnail",
+        encode_uri_component(dedupe_key)
+    )
+}
+
+/// JavaScript の `encodeURIComponent` と同じ規則で符号化する
+pub fn encode_uri_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn work(work_id: &str) -> RemoteShareWorkInput {
+        RemoteShareWorkInput {
+            work_id: work_id.to_string(),
+            title: work_id.to_string(),
+            erogamescape_id: None,
+            official_url: None,
+            erogamescape_url: None,
+            seiya_url: None,
+            thumbnail: None,
+        }
+    }
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn snapshot_work_ids_to_remove_fullは送られなかった作品を消す() {
+        let removed = snapshot_work_ids_to_remove(
+            RemoteShareSyncMode::Full,
+            &ids(&["a", "b", "c"]),
+            &[work("a")],
+            &ids(&["a"]),
+        );
+        assert_eq!(removed, ids(&["b", "c"]));
+    }
+
+    #[test]
+    fn snapshot_work_ids_to_remove_deltaは墓標のうち残っている作品だけ消す() {
+        let removed = snapshot_work_ids_to_remove(
+            RemoteShareSyncMode::Delta,
+            &ids(&["a", "b", "c"]),
+            &[work("b")],
+            &ids(&["b", "c", "x"]),
+        );
+        assert_eq!(removed, ids(&["c"]));
+    }
+
+    #[test]
+    fn is_sync_base_current_deltaは起点が最終同期と一致するときだけ通す() {
+        let delta = RemoteShareSyncMode::Delta;
+        assert!(is_sync_base_current(delta, Some("t1"), Some("t1")));
+        assert!(!is_sync_base_current(delta, Some("t1"), Some("t0")));
+        assert!(!is_sync_base_current(delta, None, None));
+        assert!(is_sync_base_current(
+            RemoteShareSyncMode::Full,
+            None,
+            Some("t0")
+        ));
+    }
+
+    #[test]
+    fn remote_share_image_key_for_dedupe_key_workersと同じキーになる() {
+        assert_eq!(
+            remote_share_image_key_for_dedupe_key("egs:123"),
+            "remote-share/egs%3A123/thumbnail"
+        );
+        assert_eq!(encode_uri_component("a b/ÿ"), "a%20b%2F%C3%BF");
+    }
+}
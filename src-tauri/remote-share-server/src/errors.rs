@@ -0,0 +1,61 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+/// API のエラー。Workers 版が返すエラーコードに合わせる
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("{0}")]
+    Unauthorized(String),
+    #[error("{0}")]
+    NotFound(String),
+    /// delta の起点がサーバーの最終同期と合わない。端末は全件を送り直す
+    #[error("{0}")]
+    ResyncRequired(String),
+    #[error("Desktop is not connected")]
+    DesktopNotConnected,
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+impl ApiError {
+    pub fn unauthorized() -> Self {
+        Self::Unauthorized("Unauthorized".to_string())
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(err: sqlx::Error) -> Self {
+        Self::Internal(err.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, code) = match &self {
+            ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+            ApiError::Unauthorized(_) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED"),
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "NOT_FOUND"),
+            ApiError::ResyncRequired(_) => (StatusCode::CONFLICT, "resync_required"),
+            ApiError::DesktopNotConnected => {
+                return (StatusCode::CONFLICT, self.to_string()).into_response();
+            }
+            ApiError::Internal(err) => {
+                log::error!("remote share request failed: {err:?}");
+                (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_SERVER_ERROR")
+            }
+        };
+        let message = match &self {
+            ApiError::Internal(_) => "Internal Server Error".to_string(),
+            _ => self.to_string(),
+        };
+        (status, Json(json!({ "code": code, "message": message }))).into_response()
+    }
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
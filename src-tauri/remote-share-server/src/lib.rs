@@ -0,0 +1,48 @@
+//! Cloudflare Workers 版（`server/`）と同じ API を、SQLite とローカルのファイルで提供するサーバー
+
+pub mod app;
+pub mod broker;
+pub mod config;
+pub mod cookies;
+pub mod crypto;
+pub mod db;
+pub mod errors;
+pub mod schema;
+pub mod storage;
+pub mod uploads;
+
+#[cfg(test)]
+mod tests;
+
+use std::sync::Arc;
+
+use tokio::net::TcpListener;
+
+use crate::{
+    app::{router, AppState},
+    broker::LaunchBroker,
+    config::ServerConfig,
+    db::Db,
+    storage::ImageStorage,
+};
+
+pub async fn build_state(config: ServerConfig) -> anyhow::Result<AppState> {
+    let db = Db::open(&config.db_file_path()).await?;
+    Ok(AppState {
+        db,
+        storage: ImageStorage::new(config.images_dir()),
+        broker: LaunchBroker::default(),
+        config: Arc::new(config),
+    })
+}
+
+/// `listener` で待ち受ける。Ctrl+C で止まる
+pub async fn serve(listener: TcpListener, config: ServerConfig) -> anyhow::Result<()> {
+    let state = build_state(config).await?;
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use remote_share_server::config::ServerConfig;
+use tokio::net::TcpListener;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let config = ServerConfig::from_env()?;
+    let listener = TcpListener::bind(config.bind_addr).await?;
+    log::info!(
+        "remote share server listening on {} (data: {})",
+        listener.local_addr()?,
+        config.data_dir.display()
+    );
+    remote_share_server::serve(listener, config).await
+}
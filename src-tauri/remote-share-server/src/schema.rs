@@ -0,0 +1,265 @@
+//! リクエストとレスポンスの型。`server/src/shared/schema.ts` と同じ JSON になるようにする
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::errors::{ApiError, ApiResult};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RemoteShareSyncMode {
+    /// works が端末の全作品
+    #[default]
+    Full,
+    /// 前回の同期（baseSyncedAt）からの差分だけ
+    Delta,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceRegisterInput {
+    pub device_secret: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceRegisterOutput {
+    pub device_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceSessionInput {
+    pub device_id: String,
+    pub device_secret: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteShareThumbnailInput {
+    pub content_type: String,
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteShareWorkInput {
+    pub work_id: String,
+    pub title: String,
+    pub erogamescape_id: Option<i64>,
+    pub official_url: Option<String>,
+    pub erogamescape_url: Option<String>,
+    pub seiya_url: Option<String>,
+    pub thumbnail: Option<RemoteShareThumbnailInput>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceWorksSyncPrepareInput {
+    pub device_secret: String,
+    #[serde(default)]
+    pub mode: RemoteShareSyncMode,
+    #[serde(default)]
+    pub base_synced_at: Option<String>,
+    pub works: Vec<RemoteShareWorkInput>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteShareUploadedImage {
+    pub dedupe_key: String,
+    pub image_key: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceWorksSyncCommitInput {
+    pub device_secret: String,
+    #[serde(default)]
+    pub mode: RemoteShareSyncMode,
+    #[serde(default)]
+    pub base_synced_at: Option<String>,
+    pub works: Vec<RemoteShareWorkInput>,
+    /// 端末から消えた作品（墓標）。delta のときだけ使う
+    #[serde(default)]
+    pub removed_work_ids: Vec<String>,
+    /// 反映後に残るはずの作品数。合わなければ resyncRequired を返す
+    #[serde(default)]
+    pub total_count: Option<i64>,
+    pub uploaded_images: Vec<RemoteShareUploadedImage>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteShareUploadTarget {
+    pub work_id: String,
+    pub dedupe_key: String,
+    pub image_key: String,
+    pub upload_url: String,
+    pub content_type: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceWorksSyncPrepareOutput {
+    pub device_id: String,
+    pub upload_targets: Vec<RemoteShareUploadTarget>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceWorksSyncCommitOutput {
+    pub device_id: String,
+    pub synced_count: usize,
+    pub removed_count: u64,
+    pub last_synced_at: String,
+    pub resync_required: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceWorksListItem {
+    pub work_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub official_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub erogamescape_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seiya_url: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceWorksListOutput {
+    pub device_id: String,
+    pub last_synced_at: Option<String>,
+    pub works: Vec<DeviceWorksListItem>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchRequestInput {
+    #[serde(default)]
+    pub work_id: String,
+}
+
+/// 本文を JSON として読んで検証する。zod と同じく前後の空白は落とす
+pub fn parse_body<T: DeserializeOwned + Validate>(body: &[u8]) -> ApiResult<T> {
+    let mut value: T = serde_json::from_slice(body)
+        .map_err(|err| ApiError::BadRequest(format!("invalid request body: {err}")))?;
+    value.validate()?;
+    Ok(value)
+}
+
+pub trait Validate {
+    fn validate(&mut self) -> ApiResult<()>;
+}
+
+impl Validate for DeviceRegisterInput {
+    fn validate(&mut self) -> ApiResult<()> {
+        validate_device_secret(&mut self.device_secret)
+    }
+}
+
+impl Validate for DeviceSessionInput {
+    fn validate(&mut self) -> ApiResult<()> {
+        validate_device_id(&self.device_id)?;
+        validate_device_secret(&mut self.device_secret)
+    }
+}
+
+impl Validate for DeviceWorksSyncPrepareInput {
+    fn validate(&mut self) -> ApiResult<()> {
+        validate_device_secret(&mut self.device_secret)?;
+        self.works.iter_mut().try_for_each(validate_work)
+    }
+}
+
+impl Validate for DeviceWorksSyncCommitInput {
+    fn validate(&mut self) -> ApiResult<()> {
+        validate_device_secret(&mut self.device_secret)?;
+        self.works.iter_mut().try_for_each(validate_work)?;
+        for work_id in self.removed_work_ids.iter_mut() {
+            trim_non_empty(work_id, "removedWorkIds")?;
+        }
+        if matches!(self.total_count, Some(count) if count < 0) {
+            return Err(ApiError::BadRequest(
+                "totalCount must be nonnegative".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Validate for LaunchRequestInput {
+    fn validate(&mut self) -> ApiResult<()> {
+        trim_non_empty(&mut self.work_id, "workId")
+            .map_err(|_| ApiError::BadRequest("workId is required".to_string()))
+    }
+}
+
+fn validate_device_id(device_id: &str) -> ApiResult<()> {
+    uuid::Uuid::parse_str(device_id)
+        .map(|_| ())
+        .map_err(|_| ApiError::BadRequest("deviceId must be a uuid".to_string()))
+}
+
+fn validate_device_secret(device_secret: &mut String) -> ApiResult<()> {
+    *device_secret = device_secret.trim().to_string();
+    match device_secret.chars().count() {
+        0..=3 => Err(ApiError::BadRequest(
+            "deviceSecret must be at least 4 characters".to_string(),
+        )),
+        4..=256 => Ok(()),
+        _ => Err(ApiError::BadRequest("deviceSecret is too long".to_string())),
+    }
+}
+
+fn validate_work(work: &mut RemoteShareWorkInput) -> ApiResult<()> {
+    trim_non_empty(&mut work.work_id, "workId")?;
+    trim_non_empty(&mut work.title, "title")?;
+    if matches!(work.erogamescape_id, Some(id) if id <= 0) {
+        return Err(ApiError::BadRequest(
+            "erogamescapeId must be positive".to_string(),
+        ));
+    }
+    for (name, value) in [
+        ("officialUrl", &mut work.official_url),
+        ("erogamescapeUrl", &mut work.erogamescape_url),
+        ("seiyaUrl", &mut work.seiya_url),
+    ] {
+        if let Some(value) = value {
+            *value = value.trim().to_string();
+            if url::Url::parse(value).is_err() {
+                return Err(ApiError::BadRequest(format!("{name} must be a url")));
+            }
+        }
+    }
+    if let Some(thumbnail) = work.thumbnail.as_mut() {
+        trim_non_empty(&mut thumbnail.content_type, "thumbnail.contentType")?;
+        if [thumbnail.width, thumbnail.height]
+            .iter()
+            .any(|size| matches!(size, Some(size) if *size <= 0))
+        {
+            return Err(ApiError::BadRequest(
+                "thumbnail size must be positive".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn trim_non_empty(value: &mut String, name: &str) -> ApiResult<()> {
+    *value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(ApiError::BadRequest(format!("{name} is required")));
+    }
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! R2 の代わりに画像をローカルのディレクトリに置く
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+#[derive(Clone, Debug)]
+pub struct ImageStorage {
+    root: PathBuf,
+}
+
+impl ImageStorage {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// `image_key` の置き場所。`..` などでディレクトリの外を指すキーは `None`
+    pub fn path_for(&self, image_key: &str) -> Option<PathBuf> {
+        let mut path = self.root.clone();
+        for segment in image_key.split('/') {
+            if segment.is_empty()
+                || segment == "."
+                || segment == ".."
+                || segment.contains(['\\', ':', '\0'])
+            {
+                return None;
+            }
+            path.push(segment);
+        }
+        Some(path)
+    }
+
+    /// 一時ファイルに書いてから置き換えるので、読み込み中の画像が途中で壊れない
+    pub async fn write(&self, image_key: &str, bytes: &[u8]) -> anyhow::Result<()> {
+        let path = self
+            .path_for(image_key)
+            .with_context(|| format!("invalid image key: {image_key}"))?;
+        let dir = path.parent().unwrap_or(&self.root);
+        tokio::fs::create_dir_all(dir).await?;
+        let tmp_path = dir.join(format!(".{}.tmp", uuid::Uuid::new_v4().simple()));
+        tokio::fs::write(&tmp_path, bytes).await?;
+        if let Err(err) = tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    pub async fn read(&self, image_key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let Some(path) = self.path_for(image_key) else {
+            return Ok(None);
+        };
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_for_ディレクトリの外を指すキーは拒否する() {
+        let storage = ImageStorage::new(PathBuf::from("/data/images"));
+
+        assert_eq!(
+            storage.path_for("remote-share/egs%3A1/thumbnail"),
+            Some(PathBuf::from("/data/images/remote-share/egs%3A1/thumbnail"))
+        );
+        assert_eq!(storage.path_for("remote-share/../../etc/passwd"), None);
+        assert_eq!(storage.path_for("/etc/passwd"), None);
+        assert_eq!(storage.path_for("remote-share\\..\\x"), None);
+    }
+
+    #[tokio::test]
+    async fn write_書いた画像を読める() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ImageStorage::new(dir.path().to_path_buf());
+
+        storage
+            .write("remote-share/a/thumbnail", b"png")
+            .await
+            .unwrap();
+        storage
+            .write("remote-share/a/thumbnail", b"png2")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage.read("remote-share/a/thumbnail").await.unwrap(),
+            Some(b"png2".to_vec())
+        );
+        assert_eq!(
+            storage.read("remote-share/b/thumbnail").await.unwrap(),
+            None
+        );
+    }
+}
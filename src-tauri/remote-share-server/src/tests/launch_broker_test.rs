@@ -0,0 +1,125 @@
+//! デスクトップの `remote_launch.rs` と同じやり取りで起動要求を受け取る
+
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use reqwest::StatusCode;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use usecase::remote_share::RemoteShareUseCase;
+
+use super::{TestServer, DEVICE_SECRET};
+
+fn broker_url(server: &TestServer, device_id: &str, device_secret: &str) -> String {
+    let mut url = url::Url::parse(&server.base_url).unwrap();
+    url.set_scheme("ws").unwrap();
+    url.set_path(&format!("/api/device/{device_id}/launch-broker"));
+    url.query_pairs_mut()
+        .append_pair("deviceSecret", device_secret);
+    url.to_string()
+}
+
+async fn launch(server: &TestServer, device_id: &str, cookie: &str, work_id: &str) -> StatusCode {
+    reqwest::Client::new()
+        .post(format!(
+            "{}/api/device/{device_id}/works/{work_id}/launch",
+            server.base_url
+        ))
+        .header("cookie", cookie)
+        .send()
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn wait_for_desktop(server: &TestServer, device_id: &str) {
+    for _ in 0..100 {
+        if server.state.broker.connected_count(device_id) > 0 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("desktop did not connect");
+}
+
+#[tokio::test]
+async fn launch_work_接続中のデスクトップに届く() {
+    let server = TestServer::spawn().await;
+    let device_id = RemoteShareUseCase::new()
+        .register_device(&server.base_url, DEVICE_SECRET)
+        .await
+        .unwrap();
+    let cookie = server
+        .create_session(&device_id, DEVICE_SECRET)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        launch(&server, &device_id, &cookie, "work-1").await,
+        StatusCode::CONFLICT
+    );
+
+    let (mut stream, _) = connect_async(broker_url(&server, &device_id, DEVICE_SECRET))
+        .await
+        .unwrap();
+    wait_for_desktop(&server, &device_id).await;
+
+    assert_eq!(
+        launch(&server, &device_id, &cookie, "work-1").await,
+        StatusCode::ACCEPTED
+    );
+    let message = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    let Message::Text(text) = message else {
+        panic!("unexpected message: {message:?}");
+    };
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+        serde_json::json!({ "type": "launch-work", "workId": "work-1" })
+    );
+    stream
+        .send(Message::Text(
+            serde_json::json!({ "type": "launch-ack", "workId": "work-1", "status": "queued" })
+                .to_string(),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        launch(
+            &server,
+            &device_id,
+            "launcherg_remote_session=x.y",
+            "work-1"
+        )
+        .await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    stream.close(None).await.unwrap();
+    for _ in 0..100 {
+        if server.state.broker.connected_count(&device_id) == 0 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("desktop was not disconnected");
+}
+
+#[tokio::test]
+async fn launch_broker_secretが違えば接続できない() {
+    let server = TestServer::spawn().await;
+    let device_id = RemoteShareUseCase::new()
+        .register_device(&server.base_url, DEVICE_SECRET)
+        .await
+        .unwrap();
+
+    assert!(
+        connect_async(broker_url(&server, &device_id, "wrong-secret"))
+            .await
+            .is_err()
+    );
+    assert_eq!(server.state.broker.connected_count(&device_id), 0);
+}
@@ -0,0 +1,59 @@
+mod launch_broker_test;
+mod remote_share_test;
+
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+
+use crate::{
+    app::{router, AppState},
+    build_state,
+    config::ServerConfig,
+};
+
+pub(crate) const DEVICE_SECRET: &str = "device-secret";
+
+/// 空いているポートで起動したテスト用のサーバー
+pub(crate) struct TestServer {
+    pub base_url: String,
+    pub state: AppState,
+    _data_dir: TempDir,
+}
+
+impl TestServer {
+    pub(crate) async fn spawn() -> Self {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config =
+            ServerConfig::with_data_dir(data_dir.path().to_path_buf(), "session-secret".into());
+        let state = build_state(config).await.unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let app = router(state.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        Self {
+            base_url,
+            state,
+            _data_dir: data_dir,
+        }
+    }
+
+    /// 共有ページと同じ手順で閲覧セッションを作り、Cookie を返す
+    pub(crate) async fn create_session(
+        &self,
+        device_id: &str,
+        device_secret: &str,
+    ) -> Option<String> {
+        let response = reqwest::Client::new()
+            .post(format!("{}/api/device/session", self.base_url))
+            .json(&serde_json::json!({ "deviceId": device_id, "deviceSecret": device_secret }))
+            .send()
+            .await
+            .unwrap();
+        if response.status() != reqwest::StatusCode::NO_CONTENT {
+            return None;
+        }
+        let set_cookie = response.headers().get("set-cookie")?.to_str().ok()?;
+        set_cookie.split(';').next().map(str::to_string)
+    }
+}
@@ -0,0 +1,255 @@
+//! デスクトップの `RemoteShareUseCase` をそのまま使って同期する
+
+use domain::remote_share::RemoteShareSyncError;
+use reqwest::StatusCode;
+use usecase::remote_share::{
+    HashedRemoteShareWork, RemoteShareSyncMode, RemoteShareSyncPlan, RemoteShareThumbnailInput,
+    RemoteShareUploadedImage, RemoteShareUseCase, RemoteShareWorkInput,
+};
+
+use super::{TestServer, DEVICE_SECRET};
+use crate::schema::DeviceWorksListOutput;
+
+fn work(
+    work_id: &str,
+    erogamescape_id: Option<i32>,
+    with_thumbnail: bool,
+) -> HashedRemoteShareWork {
+    let work = RemoteShareWorkInput {
+        work_id: work_id.to_string(),
+        title: format!("Title {work_id}"),
+        erogamescape_id,
+        official_url: Some("https://example.com/".to_string()),
+        erogamescape_url: None,
+        seiya_url: None,
+        thumbnail: with_thumbnail.then(|| RemoteShareThumbnailInput {
+            content_type: "image/png".to_string(),
+            width: Some(320),
+            height: Some(240),
+        }),
+    };
+    HashedRemoteShareWork {
+        content_hash: work.content_hash(None).unwrap(),
+        work,
+    }
+}
+
+async fn list_works(server: &TestServer, device_id: &str, cookie: &str) -> DeviceWorksListOutput {
+    reqwest::Client::new()
+        .get(format!("{}/api/device/{device_id}/works", server.base_url))
+        .header("cookie", cookie)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn 登録から同期と閲覧までデスクトップのクライアントで通る() {
+    let server = TestServer::spawn().await;
+    let usecase = RemoteShareUseCase::new();
+    let thumbnail_dir = tempfile::tempdir().unwrap();
+    let thumbnail_path = thumbnail_dir.path().join("thumbnail.png");
+    std::fs::write(&thumbnail_path, b"fake png").unwrap();
+
+    let device_id = usecase
+        .register_device(&server.base_url, DEVICE_SECRET)
+        .await
+        .unwrap();
+    assert_eq!(
+        usecase
+            .register_device(&server.base_url, DEVICE_SECRET)
+            .await
+            .unwrap(),
+        device_id
+    );
+
+    let plan = RemoteShareSyncPlan::full(vec![
+        work("work-1", Some(10), true),
+        work("work-2", None, false),
+    ]);
+    let targets = usecase
+        .prepare_sync_works(&server.base_url, &device_id, DEVICE_SECRET, &plan)
+        .await
+        .unwrap();
+    assert_eq!(targets.len(), 1);
+    assert_eq!(targets[0].dedupe_key, "egs:10");
+
+    let mut uploaded_images = vec![];
+    for target in &targets {
+        usecase
+            .upload_thumbnail(&target.upload_url, &target.content_type, &thumbnail_path)
+            .await
+            .unwrap();
+        uploaded_images.push(RemoteShareUploadedImage {
+            dedupe_key: target.dedupe_key.clone(),
+            image_key: target.image_key.clone(),
+        });
+    }
+    let committed = usecase
+        .commit_sync_works(
+            &server.base_url,
+            &device_id,
+            DEVICE_SECRET,
+            &plan,
+            uploaded_images,
+        )
+        .await
+        .unwrap();
+    assert_eq!(committed.synced_count, 2);
+
+    // 同じ画像は次の同期で送らない
+    let targets = usecase
+        .prepare_sync_works(&server.base_url, &device_id, DEVICE_SECRET, &plan)
+        .await
+        .unwrap();
+    assert!(targets.is_empty());
+
+    assert!(server
+        .create_session(&device_id, "wrong-secret")
+        .await
+        .is_none());
+    let cookie = server
+        .create_session(&device_id, DEVICE_SECRET)
+        .await
+        .unwrap();
+    let listed = list_works(&server, &device_id, &cookie).await;
+    assert_eq!(
+        listed.last_synced_at.as_deref(),
+        Some(committed.last_synced_at.as_str())
+    );
+    assert_eq!(
+        listed
+            .works
+            .iter()
+            .map(|w| w.work_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["work-1", "work-2"]
+    );
+    assert_eq!(listed.works[0].width, Some(320));
+    assert_eq!(listed.works[1].image_url, None);
+
+    let image_url = format!(
+        "{}{}",
+        server.base_url,
+        listed.works[0].image_url.as_ref().unwrap()
+    );
+    let image = reqwest::Client::new()
+        .get(&image_url)
+        .header("cookie", &cookie)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(image.headers()["content-type"], "image/png");
+    assert_eq!(image.bytes().await.unwrap().as_ref(), b"fake png");
+    let anonymous = reqwest::get(&image_url).await.unwrap();
+    assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn delta_は墓標を消し_起点が古ければ送り直しを求める() {
+    let server = TestServer::spawn().await;
+    let usecase = RemoteShareUseCase::new();
+    let device_id = usecase
+        .register_device(&server.base_url, DEVICE_SECRET)
+        .await
+        .unwrap();
+    let full = RemoteShareSyncPlan::full(vec![
+        work("work-1", None, false),
+        work("work-2", None, false),
+    ]);
+    let committed = usecase
+        .commit_sync_works(&server.base_url, &device_id, DEVICE_SECRET, &full, vec![])
+        .await
+        .unwrap();
+
+    let delta = RemoteShareSyncPlan {
+        mode: RemoteShareSyncMode::Delta,
+        base_synced_at: Some(committed.last_synced_at.clone()),
+        works: vec![],
+        removed_work_ids: vec!["work-2".to_string()],
+        total_count: 1,
+    };
+    let committed = usecase
+        .commit_sync_works(&server.base_url, &device_id, DEVICE_SECRET, &delta, vec![])
+        .await
+        .unwrap();
+    assert_eq!(committed.removed_count, 1);
+
+    // 起点がもう古い
+    let err = usecase
+        .prepare_sync_works(&server.base_url, &device_id, DEVICE_SECRET, &delta)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<RemoteShareSyncError>(),
+        Some(RemoteShareSyncError::ResyncRequired(_))
+    ));
+
+    // 件数が合わない
+    let mismatched = RemoteShareSyncPlan {
+        base_synced_at: Some(committed.last_synced_at.clone()),
+        removed_work_ids: vec![],
+        total_count: 5,
+        ..delta
+    };
+    let err = usecase
+        .commit_sync_works(
+            &server.base_url,
+            &device_id,
+            DEVICE_SECRET,
+            &mismatched,
+            vec![],
+        )
+        .await
+        .unwrap_err();
+    assert!(err.downcast_ref::<RemoteShareSyncError>().is_some());
+}
+
+#[tokio::test]
+async fn upload_署名が合わなければ受け付けない() {
+    let server = TestServer::spawn().await;
+    let usecase = RemoteShareUseCase::new();
+    let device_id = usecase
+        .register_device(&server.base_url, DEVICE_SECRET)
+        .await
+        .unwrap();
+    let plan = RemoteShareSyncPlan::full(vec![work("work-1", None, true)]);
+    let targets = usecase
+        .prepare_sync_works(&server.base_url, &device_id, DEVICE_SECRET, &plan)
+        .await
+        .unwrap();
+
+    let client = reqwest::Client::new();
+    let tampered = targets[0].upload_url.replace("signature=", "signature=0");
+    let response = client
+        .put(&tampered)
+        .header("content-type", "image/png")
+        .body("x")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = client
+        .put(&targets[0].upload_url)
+        .header("content-type", "image/jpeg")
+        .body("x")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = client
+        .post(format!(
+            "{}/api/device/{device_id}/works/sync/prepare",
+            server.base_url
+        ))
+        .json(&serde_json::json!({ "deviceSecret": "wrong-secret", "works": [] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,115 @@
+//! R2 の presigned PUT の代わりに、このサーバー自身へ PUT させる署名付き URL
+
+use crate::{
+    crypto::{constant_time_eq, hmac_sha256_hex},
+    db::encode_uri_component,
+};
+
+pub fn create_upload_url(
+    public_base_url: &str,
+    secret: &str,
+    image_key: &str,
+    content_type: &str,
+    expires_at: i64,
+) -> String {
+    let signature = upload_signature(secret, image_key, content_type, expires_at);
+    format!(
+        "{}/api/uploads/{}?contentType={}&expires={}&signature={}",
+        public_base_url.trim_end_matches('/'),
+        encode_uri_component(image_key),
+        encode_uri_component(content_type),
+        expires_at,
+        signature,
+    )
+}
+
+/// 署名が正しく、期限（UNIX 秒）が切れていないか。Content-Type も署名に含める
+pub fn verify_upload(
+    secret: &str,
+    image_key: &str,
+    content_type: &str,
+    expires_at: i64,
+    signature: &str,
+    now: i64,
+) -> bool {
+    expires_at > now
+        && constant_time_eq(
+            &upload_signature(secret, image_key, content_type, expires_at),
+            signature,
+        )
+}
+
+fn upload_signature(secret: &str, image_key: &str, content_type: &str, expires_at: i64) -> String {
+    hmac_sha256_hex(
+        secret,
+        &format!("PUT\n{image_key}\n{content_type}\n{expires_at}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_upload_発行した署名だけを期限内に受け付ける() {
+        let url = create_upload_url(
+            "http://nas.local:8787/",
+            "secret",
+            "remote-share/egs%3A1/thumbnail",
+            "image/png",
+            100,
+        );
+        let parsed = url::Url::parse(&url).unwrap();
+        assert_eq!(
+            parsed.path(),
+            "/api/uploads/remote-share%2Fegs%253A1%2Fthumbnail"
+        );
+        let signature = parsed
+            .query_pairs()
+            .find(|(key, _)| key == "signature")
+            .map(|(_, value)| value.into_owned())
+            .unwrap();
+
+        let key = "remote-share/egs%3A1/thumbnail";
+        assert!(verify_upload(
+            "secret",
+            key,
+            "image/png",
+            100,
+            &signature,
+            99
+        ));
+        assert!(!verify_upload(
+            "secret",
+            key,
+            "image/png",
+            100,
+            &signature,
+            100
+        ));
+        assert!(!verify_upload(
+            "secret",
+            key,
+            "image/jpeg",
+            100,
+            &signature,
+            99
+        ));
+        assert!(!verify_upload(
+            "secret",
+            key,
+            "image/png",
+            101,
+            &signature,
+            99
+        ));
+        assert!(!verify_upload(
+            "other",
+            key,
+            "image/png",
+            100,
+            &signature,
+            99
+        ));
+    }
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use domain::pubsub::event::{
+    AppSignalEventPayload, AppSignalPayload, AppSignalSourcePayload, PubSubEvent,
+};
+use domain::pubsub::PubSubService;
+use domain::service::app_signal_router::{AppRequest, AppRequestHandler, AppResponse};
+use domain::service::image_queue_drain::ImageQueueDrainService;
+
+use crate::interface::module::{Modules, ModulesExt};
+
+/// ネイティブホストからの問い合わせに、起動中のアプリとして答える
+pub struct AppRequestHandlerImpl {
+    modules: Arc<Modules>,
+}
+
+impl AppRequestHandlerImpl {
+    pub fn new(modules: Arc<Modules>) -> Self {
+        Self { modules }
+    }
+}
+
+impl AppRequestHandler for AppRequestHandlerImpl {
+    async fn handle(&self, request: AppRequest) -> anyhow::Result<AppResponse> {
+        match request {
+            AppRequest::ImageQueueStatus => {
+                let pending = self
+                    .modules
+                    .image_queue_use_case()
+                    .count_unfinished()
+                    .await?;
+                Ok(AppResponse::ImageQueueStatus {
+                    running: self.modules.image_queue_runner().is_running(),
+                    pending,
+                })
+            }
+            AppRequest::DrainImageQueue => {
+                // ホストを待たせないよう、引き受けた時点で応答して処理はバックグラウンドで行う
+                let modules = Arc::clone(&self.modules);
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = drain_image_queue(modules).await {
+                        log::warn!("image queue drain requested by host failed: {:#}", e);
+                    }
+                });
+                Ok(AppResponse::DrainImageQueue { accepted: true })
+            }
+        }
+    }
+}
+
+/// ホストが自分で drain したときと同じく、サムネイルの寸法を補ってから一覧を再取得させる
+async fn drain_image_queue(modules: Arc<Modules>) -> anyhow::Result<()> {
+    ImageQueueDrainService::drain_until_empty(modules.image_queue_runner().as_ref()).await?;
+    modules
+        .work_thumbnail_use_case()
+        .backfill_thumbnail_sizes()
+        .await?;
+    let payload = AppSignalPayload {
+        source: AppSignalSourcePayload::NativeMessagingHost,
+        event: AppSignalEventPayload::RefetchWorks,
+        issued_at: Utc::now(),
+    };
+    modules
+        .pubsub()
+        .notify(PubSubEvent::AppSignalRefetchWorks(payload))
+}
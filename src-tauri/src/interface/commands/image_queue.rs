@@ -4,6 +4,8 @@ use tauri::State;
 use crate::interface::error::CommandError;
 use crate::interface::models::save_image_queue::ImageSaveQueueRowVm;
 use crate::interface::module::{Modules, ModulesExt};
+use domain::service::image_queue_drain::ImageQueueDrainService;
+use domain::Id;
 
 #[derive(serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -27,3 +29,44 @@ pub async fn get_image_save_queue(
         .await?;
     Ok(rows.into_iter().map(|r| r.into()).collect())
 }
+
+#[tauri::command]
+pub async fn get_image_save_queue_failed(
+    modules: State<'_, Arc<Modules>>,
+    limit: Option<i64>,
+) -> anyhow::Result<Vec<ImageSaveQueueRowVm>, CommandError> {
+    let rows = modules
+        .image_queue_use_case()
+        .list_failed(limit.unwrap_or(500))
+        .await?;
+    Ok(rows.into_iter().map(|r| r.into()).collect())
+}
+
+#[tauri::command]
+pub async fn requeue_image_save_queue_items(
+    modules: State<'_, Arc<Modules>>,
+    ids: Vec<i32>,
+) -> anyhow::Result<u64, CommandError> {
+    let ids = ids.into_iter().map(Id::new).collect();
+    let requeued = modules.image_queue_use_case().requeue_failed(ids).await?;
+    if requeued > 0 {
+        // 完了は待たずにバックグラウンドで処理させる
+        let modules = Arc::clone(modules.inner());
+        tauri::async_runtime::spawn(async move {
+            let runner = modules.image_queue_runner();
+            if let Err(e) = ImageQueueDrainService::drain_until_empty(runner.as_ref()).await {
+                log::warn!("image queue drain after requeue failed: {:#}", e);
+            }
+        });
+    }
+    Ok(requeued)
+}
+
+#[tauri::command]
+pub async fn discard_image_save_queue_items(
+    modules: State<'_, Arc<Modules>>,
+    ids: Vec<i32>,
+) -> anyhow::Result<u64, CommandError> {
+    let ids = ids.into_iter().map(Id::new).collect();
+    Ok(modules.image_queue_use_case().discard_failed(ids).await?)
+}
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::interface::error::CommandError;
+use crate::interface::models::launch_profile::{LaunchProfileInput, LaunchProfileVm};
+use crate::interface::module::{Modules, ModulesExt};
+
+/// 既定のプロファイルが先頭
+#[tauri::command]
+pub async fn list_launch_profiles(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<Vec<LaunchProfileVm>, CommandError> {
+    let profiles = modules.launch_profile_use_case().list(work_id).await?;
+    Ok(profiles.into_iter().map(|p| p.into()).collect())
+}
+
+/// `id` がなければ作る。保存したプロファイルの id を返す
+#[tauri::command]
+pub async fn save_launch_profile(
+    modules: State<'_, Arc<Modules>>,
+    id: Option<i32>,
+    profile: LaunchProfileInput,
+) -> anyhow::Result<i32, CommandError> {
+    let id = modules
+        .launch_profile_use_case()
+        .save(id, profile.into())
+        .await?;
+    Ok(id.value)
+}
+
+#[tauri::command]
+pub async fn delete_launch_profile(
+    modules: State<'_, Arc<Modules>>,
+    id: i32,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules.launch_profile_use_case().delete(id).await?)
+}
+
+#[tauri::command]
+pub async fn set_default_launch_profile(
+    modules: State<'_, Arc<Modules>>,
+    id: i32,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules.launch_profile_use_case().set_default(id).await?)
+}
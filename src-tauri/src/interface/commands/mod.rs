@@ -3,12 +3,20 @@ pub mod erogamescape;
 pub mod extension;
 pub mod image_queue;
 pub mod images;
+pub mod launch_profile;
 pub mod matcher;
 pub mod notification;
+pub mod play_status;
 pub mod native_host_logs;
+pub mod remote_launch;
 pub mod remote_share;
+pub mod save_backup;
 pub mod scan;
 pub mod storage_paths;
+pub mod store_library;
 pub mod utils;
+pub mod wine_config;
 pub mod work_details;
+pub mod work_duplicate;
+pub mod work_override;
 pub mod works;
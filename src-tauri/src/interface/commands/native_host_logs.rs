@@ -3,7 +3,8 @@ use tauri::State;
 
 use crate::interface::error::CommandError;
 use crate::interface::module::{Modules, ModulesExt};
-use domain::native_host_log::{HostLogLevel, HostLogType};
+use chrono::{DateTime, Local};
+use domain::native_host_log::{HostLogFilter, HostLogLevel, HostLogType};
 
 #[derive(serde::Deserialize)]
 pub struct GetHostLogsRequest {
@@ -11,6 +12,11 @@ pub struct GetHostLogsRequest {
     pub offset: Option<i32>,
     pub level: Option<i32>,
     pub typ: Option<i32>,
+    pub request_id: Option<String>,
+    /// RFC3339。この時刻以降のログに絞る
+    pub since: Option<String>,
+    /// RFC3339。この時刻より前のログに絞る
+    pub until: Option<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -19,6 +25,10 @@ pub struct HostLogDto {
     pub level: i32,
     pub typ: i32,
     pub message: String,
+    pub request_id: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub item_count: Option<i64>,
+    pub error_count: Option<i64>,
     pub created_at: String,
 }
 
@@ -28,6 +38,29 @@ pub struct HostLogsResponse {
     pub total: i64,
 }
 
+fn parse_time(value: Option<String>) -> anyhow::Result<Option<DateTime<Local>>> {
+    value
+        .filter(|v| !v.is_empty())
+        .map(|v| Ok(DateTime::parse_from_rfc3339(&v)?.with_timezone(&Local)))
+        .transpose()
+}
+
+fn to_filter(
+    level: Option<i32>,
+    typ: Option<i32>,
+    request_id: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+) -> anyhow::Result<HostLogFilter> {
+    Ok(HostLogFilter {
+        level: level.and_then(|v| HostLogLevel::from_i64(v as i64)),
+        r#type: typ.and_then(|v| HostLogType::from_i64(v as i64)),
+        request_id: request_id.filter(|v| !v.is_empty()),
+        since: parse_time(since)?,
+        until: parse_time(until)?,
+    })
+}
+
 #[tauri::command]
 pub async fn get_native_host_logs(
     modules: State<'_, Arc<Modules>>,
@@ -35,34 +68,19 @@ pub async fn get_native_host_logs(
 ) -> anyhow::Result<HostLogsResponse, CommandError> {
     let limit = request.limit.unwrap_or(50) as i64;
     let offset = request.offset.unwrap_or(0) as i64;
-    let level = match request.level {
-        Some(0) => Some(HostLogLevel::Debug),
-        Some(1) => Some(HostLogLevel::Info),
-        Some(2) => Some(HostLogLevel::Warn),
-        Some(3) => Some(HostLogLevel::Error),
-        _ => None,
-    };
-    let typ = match request.typ {
-        Some(0) => Some(HostLogType::Unknown),
-        Some(1) => Some(HostLogType::ReceiveDmmSyncGamesRequest),
-        Some(2) => Some(HostLogType::ReceiveDlsiteSyncGamesRequest),
-        Some(10) => Some(HostLogType::ImageQueueWorkerStarted),
-        Some(11) => Some(HostLogType::ImageQueueWorkerFinished),
-        Some(20) => Some(HostLogType::ImageQueueItemStarted),
-        Some(21) => Some(HostLogType::ImageQueueItemSucceeded),
-        Some(22) => Some(HostLogType::ImageQueueItemFailed),
-        Some(30) => Some(HostLogType::ReceiveRequest),
-        Some(31) => Some(HostLogType::Response),
-        Some(32) => Some(HostLogType::EndProcessImageQueue),
-        Some(33) => Some(HostLogType::AppSignalDispatchFailed),
-        _ => None,
-    };
+    let filter = to_filter(
+        request.level,
+        request.typ,
+        request.request_id,
+        request.since,
+        request.until,
+    )?;
 
     let items = modules
         .host_log_use_case()
-        .list_logs(limit, offset, level, typ)
+        .list_logs(limit, offset, filter.clone())
         .await?;
-    let total = modules.host_log_use_case().count_logs(level, typ).await?;
+    let total = modules.host_log_use_case().count_logs(filter).await?;
 
     Ok(HostLogsResponse {
         items: items
@@ -72,9 +90,45 @@ pub async fn get_native_host_logs(
                 level: row.level as i32,
                 typ: row.r#type as i32,
                 message: row.message,
+                request_id: row.request_id,
+                duration_ms: row.duration_ms,
+                item_count: row.item_count,
+                error_count: row.error_count,
                 created_at: row.created_at.to_rfc3339(),
             })
             .collect(),
         total,
     })
 }
+
+#[derive(serde::Deserialize)]
+pub struct ExportHostLogsRequest {
+    pub path: String,
+    pub level: Option<i32>,
+    pub typ: Option<i32>,
+    pub request_id: Option<String>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+/// 条件に合うログを JSONL で書き出し、書き出した件数を返す
+#[tauri::command]
+pub async fn export_native_host_logs(
+    modules: State<'_, Arc<Modules>>,
+    request: ExportHostLogsRequest,
+) -> anyhow::Result<u64, CommandError> {
+    let filter = to_filter(
+        request.level,
+        request.typ,
+        request.request_id,
+        request.since,
+        request.until,
+    )?;
+    let file = std::fs::File::create(&request.path)
+        .map_err(|e| anyhow::anyhow!("ログの書き出し先を作成できません: {}", e))?;
+    let exported = modules
+        .host_log_use_case()
+        .export_jsonl(filter, std::io::BufWriter::new(file))
+        .await?;
+    Ok(exported)
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::interface::error::CommandError;
+use crate::interface::models::play_status::{
+    PlayStateInput, PlayStatusChangeVm, WorkPlayStateVm, WorkRouteVm,
+};
+use crate::interface::module::{Modules, ModulesExt};
+
+/// 記録がなければ None（未プレイ）
+#[tauri::command]
+pub async fn get_work_play_state(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<Option<WorkPlayStateVm>, CommandError> {
+    let state = modules.play_status_use_case().get_state(work_id).await?;
+    Ok(state.map(Into::into))
+}
+
+/// ステータスが変わったときは履歴にも残す
+#[tauri::command]
+pub async fn save_work_play_state(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+    state: PlayStateInput,
+) -> anyhow::Result<WorkPlayStateVm, CommandError> {
+    let state = modules
+        .play_status_use_case()
+        .save_state(work_id, state.into())
+        .await?;
+    Ok(state.into())
+}
+
+/// 新しい順
+#[tauri::command]
+pub async fn list_work_play_status_changes(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<Vec<PlayStatusChangeVm>, CommandError> {
+    let changes = modules
+        .play_status_use_case()
+        .list_status_changes(work_id)
+        .await?;
+    Ok(changes.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn list_work_routes(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<Vec<WorkRouteVm>, CommandError> {
+    let routes = modules.play_status_use_case().list_routes(work_id).await?;
+    Ok(routes.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn add_work_route(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+    name: String,
+) -> anyhow::Result<Vec<WorkRouteVm>, CommandError> {
+    let routes = modules
+        .play_status_use_case()
+        .add_route(work_id, name)
+        .await?;
+    Ok(routes.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn set_work_route_completed(
+    modules: State<'_, Arc<Modules>>,
+    id: i32,
+    completed: bool,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules
+        .play_status_use_case()
+        .set_route_completed(id, completed)
+        .await?)
+}
+
+#[tauri::command]
+pub async fn delete_work_route(
+    modules: State<'_, Arc<Modules>>,
+    id: i32,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules.play_status_use_case().delete_route(id).await?)
+}
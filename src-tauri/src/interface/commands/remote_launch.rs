@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::interface::error::CommandError;
+use crate::interface::models::remote_launch::{RemoteLaunchAuditVm, RemoteLaunchSettingsVm};
+use crate::interface::module::{Modules, ModulesExt};
+
+#[tauri::command]
+pub async fn get_remote_launch_settings(
+    modules: State<'_, Arc<Modules>>,
+) -> anyhow::Result<RemoteLaunchSettingsVm, CommandError> {
+    Ok(modules
+        .remote_launch_use_case()
+        .get_settings()
+        .await?
+        .into())
+}
+
+#[tauri::command]
+pub async fn set_remote_launch_settings(
+    modules: State<'_, Arc<Modules>>,
+    settings: RemoteLaunchSettingsVm,
+) -> anyhow::Result<RemoteLaunchSettingsVm, CommandError> {
+    Ok(modules
+        .remote_launch_use_case()
+        .set_settings(settings.into())
+        .await?
+        .into())
+}
+
+/// 起動の確認に答える。時間切れなどで既に閉じていれば false
+#[tauri::command]
+pub async fn respond_remote_launch_confirmation(
+    modules: State<'_, Arc<Modules>>,
+    request_id: String,
+    approved: bool,
+    always_allow: bool,
+) -> anyhow::Result<bool, CommandError> {
+    Ok(modules
+        .remote_launch_use_case()
+        .respond_confirmation(&request_id, approved, always_allow)
+        .await?)
+}
+
+/// 起動要求の記録。新しい順
+#[tauri::command]
+pub async fn get_remote_launch_audits(
+    modules: State<'_, Arc<Modules>>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> anyhow::Result<Vec<RemoteLaunchAuditVm>, CommandError> {
+    let rows = modules
+        .remote_launch_use_case()
+        .list_audits(limit.unwrap_or(100), offset.unwrap_or(0))
+        .await?;
+    Ok(rows.into_iter().map(|r| r.into()).collect())
+}
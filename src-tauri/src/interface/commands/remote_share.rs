@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -10,12 +10,12 @@ use crate::interface::error::CommandError;
 use crate::interface::models::remote_share::RemoteShareSettingsVm;
 use crate::interface::models::work_details::WorkDetailsVm;
 use crate::interface::module::{Modules, ModulesExt};
-use futures::stream::{self, StreamExt, TryStreamExt};
+use domain::remote_share::RemoteShareSyncError;
+use futures::stream::{self, StreamExt};
 
 use usecase::remote_share::{
-    CommitSyncResponse,
-    RemoteShareUploadedImage,
-    RemoteShareWorkInput,
+    thumbnail_fingerprint, CommitSyncResponse, HashedRemoteShareWork, RemoteShareSyncPlan,
+    RemoteShareUploadedImage, RemoteShareUseCase, RemoteShareWorkInput,
 };
 
 #[tauri::command]
@@ -94,7 +94,7 @@ pub async fn sync_remote_share_works(
 
     let rows = modules.work_use_case().list_all_details().await?;
     let resolver = modules.save_path_resolver().clone();
-    let remote_share_use_case = crate::usecase::remote_share::RemoteShareUseCase::new();
+    let remote_share_use_case = RemoteShareUseCase::new();
 
     let candidates = rows
         .into_iter()
@@ -102,63 +102,59 @@ pub async fn sync_remote_share_works(
         .map(|work| to_remote_share_work(work))
         .collect::<anyhow::Result<Vec<_>>>()?;
 
-    let works = candidates.iter().map(|candidate| candidate.work.clone()).collect::<Vec<_>>();
     let thumbnail_paths: HashMap<String, PathBuf> = candidates
         .iter()
         .filter_map(|candidate| {
             candidate.thumbnail_path.as_ref().map(|path| {
-                (candidate.work.dedupe_key(), path.clone())
+                (candidate.work.work.dedupe_key(), path.clone())
             })
         })
         .fold(HashMap::new(), |mut acc, (dedupe_key, path)| {
             acc.entry(dedupe_key).or_insert(path);
             acc
         });
-
-    let upload_targets = remote_share_use_case
-        .prepare_sync_works(
-            &server_base_url,
-            &device_id,
-            &device_secret,
-            works.clone(),
-        )
-        .await?;
-
-    let upload_jobs = upload_targets
+    let works = candidates
         .into_iter()
-        .filter_map(|target| {
-            thumbnail_paths
-                .get(&target.dedupe_key)
-                .map(|path| (target, path.clone()))
-        })
+        .map(|candidate| candidate.work)
         .collect::<Vec<_>>();
 
-    let uploaded_images = stream::iter(upload_jobs)
-        .map(|(target, path)| {
-            let remote_share_use_case = remote_share_use_case.clone();
-            async move {
-                remote_share_use_case
-                    .upload_thumbnail(&target.upload_url, &target.content_type, &path)
-                    .await?;
-                Ok::<RemoteShareUploadedImage, anyhow::Error>(RemoteShareUploadedImage {
-                    dedupe_key: target.dedupe_key,
-                    image_key: target.image_key,
-                })
-            }
-        })
-        .buffer_unordered(4)
-        .try_collect::<Vec<_>>()
-        .await?;
-
-    let response: CommitSyncResponse = remote_share_use_case
-        .commit_sync_works(
-            &server_base_url,
+    let sync_use_case = modules.remote_share_sync_use_case();
+    let plan = sync_use_case
+        .plan(
             &device_id,
-            &device_secret,
-            works,
-            uploaded_images,
+            current.remote_share_last_synced_at.clone(),
+            works.clone(),
         )
         .await?;
+    let connection = RemoteShareConnection {
+        server_base_url: &server_base_url,
+        device_id: &device_id,
+        device_secret: &device_secret,
+    };
+    let response = match sync_plan(
+        &modules,
+        &remote_share_use_case,
+        &connection,
+        &plan,
+        &thumbnail_paths,
+    )
+    .await
+    {
+        // サーバーの内容が手元の記録と合わないので、全件を送り直して揃える
+        Err(err) if err.downcast_ref::<RemoteShareSyncError>().is_some() => {
+            log::warn!("remote share sync falls back to a full resync: {err}");
+            sync_use_case.mark_all_dirty(&device_id).await?;
+            sync_plan(
+                &modules,
+                &remote_share_use_case,
+                &connection,
+                &RemoteShareSyncPlan::full(works),
+                &thumbnail_paths,
+            )
+            .await?
+        }
+        result => result?,
+    };
 
     let saved = modules
         .app_settings_use_case()
@@ -192,8 +188,94 @@ pub async fn get_remote_share_url(
         .build_share_url(server_base_url, device_id)?)
 }
 
+struct RemoteShareConnection<'a> {
+    server_base_url: &'a str,
+    device_id: &'a str,
+    device_secret: &'a str,
+}
+
+/// `plan` をサーバーへ送り、反映できた内容を記録する。
+/// サムネイルを送れなかった作品は同期を止めずに、次の同期で送り直す
+async fn sync_plan(
+    modules: &Modules,
+    remote_share_use_case: &RemoteShareUseCase,
+    connection: &RemoteShareConnection<'_>,
+    plan: &RemoteShareSyncPlan,
+    thumbnail_paths: &HashMap<String, PathBuf>,
+) -> anyhow::Result<CommitSyncResponse> {
+    let upload_targets = remote_share_use_case
+        .prepare_sync_works(
+            connection.server_base_url,
+            connection.device_id,
+            connection.device_secret,
+            plan,
+        )
+        .await?;
+
+    let upload_jobs = upload_targets
+        .into_iter()
+        .filter_map(|target| {
+            thumbnail_paths
+                .get(&target.dedupe_key)
+                .map(|path| (target, path.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    let upload_results = stream::iter(upload_jobs)
+        .map(|(target, path)| {
+            let remote_share_use_case = remote_share_use_case.clone();
+            async move {
+                let result = remote_share_use_case
+                    .upload_thumbnail(&target.upload_url, &target.content_type, &path)
+                    .await;
+                (target, result)
+            }
+        })
+        .buffer_unordered(4)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut uploaded_images = Vec::new();
+    let mut failed_dedupe_keys = HashSet::new();
+    for (target, result) in upload_results {
+        match result {
+            Ok(()) => uploaded_images.push(RemoteShareUploadedImage {
+                dedupe_key: target.dedupe_key,
+                image_key: target.image_key,
+            }),
+            Err(err) => {
+                log::warn!("failed to upload thumbnail {}: {err}", target.dedupe_key);
+                failed_dedupe_keys.insert(target.dedupe_key);
+            }
+        }
+    }
+
+    let response = remote_share_use_case
+        .commit_sync_works(
+            connection.server_base_url,
+            connection.device_id,
+            connection.device_secret,
+            plan,
+            uploaded_images,
+        )
+        .await?;
+
+    let dirty_work_ids = plan
+        .works
+        .iter()
+        .filter(|w| failed_dedupe_keys.contains(&w.work.dedupe_key()))
+        .map(|w| w.work.work_id.clone())
+        .collect::<HashSet<_>>();
+    modules
+        .remote_share_sync_use_case()
+        .record_synced(connection.device_id, plan, &dirty_work_ids)
+        .await?;
+
+    Ok(response)
+}
+
 struct RemoteShareWorkCandidate {
-    work: RemoteShareWorkInput,
+    work: HashedRemoteShareWork,
     thumbnail_path: Option<PathBuf>,
 }
 
@@ -225,16 +307,20 @@ fn to_remote_share_work(work: WorkDetailsVm) -> anyhow::Result<RemoteShareWorkCa
         None => (None, None),
     };
 
+    let work = RemoteShareWorkInput {
+        work_id: work.id,
+        title: work.title,
+        erogamescape_id: work.erogamescape_id,
+        official_url,
+        erogamescape_url,
+        seiya_url: None,
+        thumbnail,
+    };
+    let fingerprint = thumbnail_path.as_deref().and_then(thumbnail_fingerprint);
+    let content_hash = work.content_hash(fingerprint.as_deref())?;
+
     Ok(RemoteShareWorkCandidate {
-        work: RemoteShareWorkInput {
-            work_id: work.id,
-            title: work.title,
-            erogamescape_id: work.erogamescape_id,
-            official_url,
-            erogamescape_url,
-            seiya_url: None,
-            thumbnail,
-        },
+        work: HashedRemoteShareWork { work, content_hash },
         thumbnail_path,
     })
 }
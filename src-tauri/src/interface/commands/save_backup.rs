@@ -0,0 +1,99 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::domain::save_data::SnapshotReason;
+use crate::interface::error::CommandError;
+use crate::interface::models::save_backup::{SaveLocationVm, SaveSnapshotVm};
+use crate::interface::module::{Modules, ModulesExt};
+
+#[tauri::command]
+pub async fn list_save_locations(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<Vec<SaveLocationVm>, CommandError> {
+    let locations = modules
+        .save_backup_use_case()
+        .list_locations(work_id)
+        .await?;
+    Ok(locations.into_iter().map(Into::into).collect())
+}
+
+/// 前回見つけた場所は消して探し直す。手動で追加した場所は残る
+#[tauri::command]
+pub async fn detect_save_locations(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<Vec<SaveLocationVm>, CommandError> {
+    let locations = modules
+        .save_backup_use_case()
+        .detect_locations(work_id)
+        .await?;
+    Ok(locations.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn add_save_location(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+    path: String,
+) -> anyhow::Result<Vec<SaveLocationVm>, CommandError> {
+    let locations = modules
+        .save_backup_use_case()
+        .add_location(work_id, path)
+        .await?;
+    Ok(locations.into_iter().map(Into::into).collect())
+}
+
+#[tauri::command]
+pub async fn delete_save_location(
+    modules: State<'_, Arc<Modules>>,
+    id: i32,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules.save_backup_use_case().delete_location(id).await?)
+}
+
+#[tauri::command]
+pub async fn create_save_snapshot(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<SaveSnapshotVm, CommandError> {
+    let snapshot = modules
+        .save_backup_use_case()
+        .create_snapshot(work_id, SnapshotReason::Manual)
+        .await?;
+    Ok(snapshot.into())
+}
+
+/// 新しい順
+#[tauri::command]
+pub async fn list_save_snapshots(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<Vec<SaveSnapshotVm>, CommandError> {
+    let snapshots = modules.save_backup_use_case().list_snapshots(work_id)?;
+    Ok(snapshots.into_iter().map(Into::into).collect())
+}
+
+/// 上書きする前の中身は `before_restore` のバックアップとして残す
+#[tauri::command]
+pub async fn restore_save_snapshot(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+    file_name: String,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules
+        .save_backup_use_case()
+        .restore_snapshot(work_id, file_name)
+        .await?)
+}
+
+#[tauri::command]
+pub async fn delete_save_snapshot(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+    file_name: String,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules
+        .save_backup_use_case()
+        .delete_snapshot(work_id, file_name)?)
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::interface::error::CommandError;
+use crate::interface::models::store_library::MissingStoreWorkVm;
+use crate::interface::module::{Modules, ModulesExt};
+use domain::sync_session::SyncStore;
+
+fn parse_store(store: &str) -> anyhow::Result<SyncStore> {
+    SyncStore::parse(store).ok_or_else(|| anyhow::anyhow!("unknown store: {}", store))
+}
+
+/// ライブラリ全体の同期で見つからなかったストアの紐付け。store は "dmm" | "dlsite"
+#[tauri::command]
+pub async fn get_missing_store_works(
+    modules: State<'_, Arc<Modules>>,
+    store: String,
+) -> anyhow::Result<Vec<MissingStoreWorkVm>, CommandError> {
+    let rows = modules
+        .store_library_use_case()
+        .list_missing(parse_store(&store)?)
+        .await?;
+    Ok(rows.into_iter().map(|r| r.into()).collect())
+}
+
+/// 欠落の印が付いた紐付けを消す。作品そのものは残る
+#[tauri::command]
+pub async fn prune_missing_store_works(
+    modules: State<'_, Arc<Modules>>,
+    store: String,
+    store_ids: Vec<String>,
+) -> anyhow::Result<u64, CommandError> {
+    Ok(modules
+        .store_library_use_case()
+        .prune_missing(parse_store(&store)?, store_ids)
+        .await?)
+}
@@ -34,7 +34,7 @@ pub fn open_folder(path: String) -> anyhow::Result<(), CommandError> {
 fn resolve_open_folder_path(path: &str) -> anyhow::Result<String> {
     let mut resolved_path = path.to_string();
     if path.to_lowercase().ends_with(".lnk") {
-        let windows = crate::infrastructure::windowsimpl::windows::Windows::new();
+        let windows = crate::infrastructure::platform::Platform::new();
         if let Ok(metadatas) = windows.shell_link().get_lnk_metadatas(vec![path.to_string()]) {
             if let Some(meta) = metadatas.get(path) {
                 resolved_path = meta.path.clone();
@@ -84,7 +84,7 @@ pub async fn get_exe_path_by_lnk(
     }
 
     let p = filepath.clone();
-    let windows = crate::infrastructure::windowsimpl::windows::Windows::new();
+    let windows = crate::infrastructure::platform::Platform::new();
     let metadatas = windows.shell_link().get_lnk_metadatas(vec![p.clone()])?;
     if let Some(meta) = metadatas.get(&p) {
         return Ok(meta.path.clone());
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::interface::error::CommandError;
+use crate::interface::models::wine_config::{WineConfigVm, WineSettingsVm};
+use crate::interface::module::{Modules, ModulesExt};
+
+#[tauri::command]
+pub async fn get_wine_settings(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<WineSettingsVm, CommandError> {
+    let config = modules.wine_config_use_case().get(work_id).await?;
+    Ok(WineSettingsVm {
+        available: cfg!(not(windows)),
+        config: config.map(Into::into),
+    })
+}
+
+#[tauri::command]
+pub async fn save_wine_config(
+    modules: State<'_, Arc<Modules>>,
+    config: WineConfigVm,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules.wine_config_use_case().save(config.into()).await?)
+}
+
+/// 消すと既定の prefix の `wine` で起動する
+#[tauri::command]
+pub async fn delete_wine_config(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<(), CommandError> {
+    Ok(modules.wine_config_use_case().delete(work_id).await?)
+}
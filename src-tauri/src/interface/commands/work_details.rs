@@ -3,13 +3,19 @@ use tauri::State;
 
 use crate::interface::error::CommandError;
 use crate::interface::models::work_details::WorkDetailsVm;
+use crate::interface::models::work_query::{
+    WorkFacetsVm, WorkListFilterVm, WorkPageVm, WorkQueryVm,
+};
 use crate::interface::module::{Modules, ModulesExt};
 
+/// `filter` を省くと全作品を返す
 #[tauri::command]
 pub async fn get_work_details_all(
     modules: State<'_, Arc<Modules>>,
+    filter: Option<WorkListFilterVm>,
 ) -> anyhow::Result<Vec<WorkDetailsVm>, CommandError> {
-    let rows = modules.work_use_case().list_all_details().await?;
+    let filter = filter.unwrap_or_default().into();
+    let rows = modules.work_use_case().list_details(&filter).await?;
     let resolver = modules.save_path_resolver().clone();
     Ok(rows
         .into_iter()
@@ -17,6 +23,31 @@ pub async fn get_work_details_all(
         .collect())
 }
 
+/// 絞り込みと並び替えをかけて1ページ分を返す
+#[tauri::command]
+pub async fn query_work_details(
+    modules: State<'_, Arc<Modules>>,
+    query: WorkQueryVm,
+) -> anyhow::Result<WorkPageVm, CommandError> {
+    let page = modules.work_use_case().query_details(&query.into()).await?;
+    let resolver = modules.save_path_resolver().clone();
+    Ok(WorkPageVm::from_work_page_with_resolver(
+        page,
+        resolver.as_ref(),
+    ))
+}
+
+/// 絞り込み用サイドバーの件数
+#[tauri::command]
+pub async fn get_work_facets(
+    modules: State<'_, Arc<Modules>>,
+    filter: Option<WorkListFilterVm>,
+) -> anyhow::Result<WorkFacetsVm, CommandError> {
+    let filter = filter.unwrap_or_default().into();
+    let facets = modules.work_use_case().count_facets(&filter).await?;
+    Ok(facets.into())
+}
+
 #[tauri::command]
 pub async fn get_work_details_by_work_id(
     modules: State<'_, Arc<Modules>>,
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::interface::error::CommandError;
+use crate::interface::models::work_duplicate::{DuplicateCandidateVm, WorkMergeResultVm};
+use crate::interface::module::{Modules, ModulesExt};
+
+/// 重複の疑いがある作品の組。どちらを残すかは呼び出し側で選ぶ
+#[tauri::command]
+pub async fn find_duplicate_works(
+    modules: State<'_, Arc<Modules>>,
+) -> anyhow::Result<Vec<DuplicateCandidateVm>, CommandError> {
+    let candidates = modules.work_duplicate_use_case().find_duplicates().await?;
+    Ok(candidates.into_iter().map(Into::into).collect())
+}
+
+/// `duplicate_work_id` の購入情報や起動リンク、プレイ記録などを `survivor_work_id` へ移し、
+/// `duplicate_work_id` を消す
+#[tauri::command]
+pub async fn merge_works(
+    modules: State<'_, Arc<Modules>>,
+    survivor_work_id: String,
+    duplicate_work_id: String,
+) -> anyhow::Result<WorkMergeResultVm, CommandError> {
+    let result = modules
+        .work_duplicate_use_case()
+        .merge(survivor_work_id, duplicate_work_id)
+        .await?;
+    Ok(result.into())
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+use tauri::State;
+
+use crate::domain::work_override::WorkOverrideField;
+use crate::interface::error::CommandError;
+use crate::interface::models::work_override::WorkOverridesVm;
+use crate::interface::module::{Modules, ModulesExt};
+
+#[tauri::command]
+pub async fn get_work_overrides(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+) -> anyhow::Result<WorkOverridesVm, CommandError> {
+    let overrides = modules.work_override_use_case().get(work_id).await?;
+    Ok(overrides.into())
+}
+
+/// 発売日は `YYYY-MM-DD`（`YYYY/MM/DD` も可）。空の値は受け付けない
+#[tauri::command]
+pub async fn set_work_override(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+    field: WorkOverrideField,
+    value: String,
+) -> anyhow::Result<WorkOverridesVm, CommandError> {
+    let overrides = modules
+        .work_override_use_case()
+        .set(work_id, field, value)
+        .await?;
+    Ok(overrides.into())
+}
+
+/// 元の情報に戻す
+#[tauri::command]
+pub async fn clear_work_override(
+    modules: State<'_, Arc<Modules>>,
+    work_id: String,
+    field: WorkOverrideField,
+) -> anyhow::Result<WorkOverridesVm, CommandError> {
+    let overrides = modules
+        .work_override_use_case()
+        .clear(work_id, field)
+        .await?;
+    Ok(overrides.into())
+}
@@ -76,15 +76,18 @@ pub async fn delete_work(
     Ok(modules.work_use_case().delete_work(work_id).await?)
 }
 
+/// `profile_id` を省くと既定の起動プロファイルで起動する。
+/// `is_run_as_admin` を指定すると、その権限を既定の起動プロファイルに覚える
 #[tauri::command]
 pub async fn launch_work(
     modules: State<'_, Arc<Modules>>,
-    is_run_as_admin: bool,
     work_lnk_id: i32,
+    profile_id: Option<i32>,
+    is_run_as_admin: Option<bool>,
 ) -> anyhow::Result<Option<u32>, CommandError> {
     Ok(modules
         .work_use_case()
-        .launch_work(is_run_as_admin, work_lnk_id)
+        .launch_work(work_lnk_id, profile_id, is_run_as_admin)
         .await?)
 }
 
@@ -0,0 +1,95 @@
+use crate::domain::launch_profile::{LaunchEnvVar, LaunchProfile, LaunchWrapper, NewLaunchProfile};
+use crate::domain::StrId;
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchEnvVarVm {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchWrapperVm {
+    pub path: String,
+    /// `{target}` と `{args}` を置き換える
+    pub arguments: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfileVm {
+    pub id: i32,
+    pub work_id: String,
+    pub name: String,
+    pub arguments: Option<String>,
+    pub working_dir: Option<String>,
+    pub env: Vec<LaunchEnvVarVm>,
+    pub run_as_admin: bool,
+    pub wrapper: Option<LaunchWrapperVm>,
+    pub is_default: bool,
+}
+
+impl From<LaunchProfile> for LaunchProfileVm {
+    fn from(v: LaunchProfile) -> Self {
+        Self {
+            id: v.id.value,
+            work_id: v.work_id.value,
+            name: v.name,
+            arguments: v.arguments,
+            working_dir: v.working_dir,
+            env: v
+                .env
+                .into_iter()
+                .map(|var| LaunchEnvVarVm {
+                    key: var.key,
+                    value: var.value,
+                })
+                .collect(),
+            run_as_admin: v.run_as_admin,
+            wrapper: v.wrapper.map(|w| LaunchWrapperVm {
+                path: w.path,
+                arguments: w.arguments,
+            }),
+            is_default: v.is_default,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfileInput {
+    pub work_id: String,
+    pub name: String,
+    pub arguments: Option<String>,
+    pub working_dir: Option<String>,
+    pub env: Vec<LaunchEnvVarVm>,
+    pub run_as_admin: bool,
+    pub wrapper: Option<LaunchWrapperVm>,
+    pub is_default: bool,
+}
+
+impl From<LaunchProfileInput> for NewLaunchProfile {
+    fn from(v: LaunchProfileInput) -> Self {
+        Self {
+            work_id: StrId::new(v.work_id),
+            name: v.name,
+            arguments: v.arguments,
+            working_dir: v.working_dir,
+            env: v
+                .env
+                .into_iter()
+                .map(|var| LaunchEnvVar {
+                    key: var.key,
+                    value: var.value,
+                })
+                .collect(),
+            run_as_admin: v.run_as_admin,
+            wrapper: v.wrapper.map(|w| LaunchWrapper {
+                path: w.path,
+                arguments: w.arguments,
+            }),
+            is_default: v.is_default,
+        }
+    }
+}
@@ -1,7 +1,16 @@
 pub mod all_game_cache;
+pub mod launch_profile;
 pub mod parent_dmm_pack;
+pub mod play_status;
+pub mod remote_launch;
 pub mod remote_share;
+pub mod save_backup;
 pub mod save_image_queue;
 pub mod storage_paths;
+pub mod store_library;
+pub mod wine_config;
 pub mod work_details;
+pub mod work_duplicate;
+pub mod work_override;
 pub mod work_path_input;
+pub mod work_query;
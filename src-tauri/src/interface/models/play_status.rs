@@ -0,0 +1,87 @@
+use chrono::NaiveDate;
+
+use crate::domain::play_status::{
+    PlayStateUpdate, PlayStatus, PlayStatusChange, WorkPlayState, WorkRoute,
+};
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkPlayStateVm {
+    pub status: PlayStatus,
+    pub score: Option<i32>,
+    /// `YYYY-MM-DD`
+    pub started_on: Option<NaiveDate>,
+    pub finished_on: Option<NaiveDate>,
+}
+
+impl From<WorkPlayState> for WorkPlayStateVm {
+    fn from(v: WorkPlayState) -> Self {
+        Self {
+            status: v.status,
+            score: v.score,
+            started_on: v.started_on,
+            finished_on: v.finished_on,
+        }
+    }
+}
+
+/// 日付を空にすると、ステータスに合わせて今日が入る
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayStateInput {
+    pub status: PlayStatus,
+    pub score: Option<i32>,
+    pub started_on: Option<NaiveDate>,
+    pub finished_on: Option<NaiveDate>,
+}
+
+impl From<PlayStateInput> for PlayStateUpdate {
+    fn from(v: PlayStateInput) -> Self {
+        Self {
+            status: v.status,
+            score: v.score,
+            started_on: v.started_on,
+            finished_on: v.finished_on,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayStatusChangeVm {
+    pub id: i32,
+    pub status: PlayStatus,
+    pub changed_at: String,
+}
+
+impl From<PlayStatusChange> for PlayStatusChangeVm {
+    fn from(v: PlayStatusChange) -> Self {
+        Self {
+            id: v.id.value,
+            status: v.status,
+            changed_at: v.changed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkRouteVm {
+    pub id: i32,
+    pub work_id: String,
+    pub name: String,
+    pub completed_at: Option<String>,
+}
+
+impl From<WorkRoute> for WorkRouteVm {
+    fn from(v: WorkRoute) -> Self {
+        Self {
+            id: v.id.value,
+            work_id: v.work_id.value,
+            name: v.name,
+            completed_at: v
+                .completed_at
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+        }
+    }
+}
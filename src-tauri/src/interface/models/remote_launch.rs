@@ -0,0 +1,60 @@
+use crate::domain::remote_launch::{RemoteLaunchAudit, RemoteLaunchPolicy, RemoteLaunchSettings};
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteLaunchSettingsVm {
+    /// "disabled" | "allowlist" | "ask_first"
+    pub policy: RemoteLaunchPolicy,
+    pub allowed_work_ids: Vec<String>,
+    pub confirm_timeout_seconds: u32,
+}
+
+impl From<RemoteLaunchSettings> for RemoteLaunchSettingsVm {
+    fn from(value: RemoteLaunchSettings) -> Self {
+        Self {
+            policy: value.policy,
+            allowed_work_ids: value.allowed_work_ids,
+            confirm_timeout_seconds: value.confirm_timeout_seconds,
+        }
+    }
+}
+
+impl From<RemoteLaunchSettingsVm> for RemoteLaunchSettings {
+    fn from(value: RemoteLaunchSettingsVm) -> Self {
+        Self {
+            policy: value.policy,
+            allowed_work_ids: value.allowed_work_ids,
+            confirm_timeout_seconds: value.confirm_timeout_seconds,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteLaunchAuditVm {
+    pub id: i32,
+    pub work_id: String,
+    pub work_title: Option<String>,
+    pub policy: RemoteLaunchPolicy,
+    pub decision: String,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub requested_at: String,
+    pub finished_at: String,
+}
+
+impl From<RemoteLaunchAudit> for RemoteLaunchAuditVm {
+    fn from(v: RemoteLaunchAudit) -> Self {
+        Self {
+            id: v.id.value,
+            work_id: v.work_id,
+            work_title: v.work_title,
+            policy: v.policy,
+            decision: v.decision.as_str().to_string(),
+            status: v.status.as_str().to_string(),
+            error_message: v.error_message,
+            requested_at: v.requested_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            finished_at: v.finished_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Local};
+
+use crate::domain::save_data::{SaveLocation, SaveLocationSource, SaveSnapshot, SnapshotReason};
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveLocationVm {
+    pub id: i32,
+    pub work_id: String,
+    /// `{install}` や `%APPDATA%` を含んだままの書き方
+    pub path: String,
+    pub source: SaveLocationSource,
+}
+
+impl From<SaveLocation> for SaveLocationVm {
+    fn from(v: SaveLocation) -> Self {
+        Self {
+            id: v.id.value,
+            work_id: v.work_id.value,
+            path: v.path,
+            source: v.source,
+        }
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveSnapshotVm {
+    pub file_name: String,
+    pub path: String,
+    pub size: u64,
+    pub created_at: DateTime<Local>,
+    pub reason: SnapshotReason,
+    pub locations: Vec<String>,
+}
+
+impl From<SaveSnapshot> for SaveSnapshotVm {
+    fn from(v: SaveSnapshot) -> Self {
+        Self {
+            file_name: v.file_name,
+            path: v.path,
+            size: v.size,
+            created_at: v.manifest.created_at,
+            reason: v.manifest.reason,
+            locations: v.manifest.locations,
+        }
+    }
+}
@@ -12,6 +12,9 @@ pub struct ImageSaveQueueRowVm {
     pub dst_path: String,
     pub preprocess: i32,
     pub last_error: Option<String>,
+    pub attempt_count: i32,
+    pub next_attempt_at: Option<String>,
+    pub failed_at: Option<String>,
 }
 
 impl From<DomainRow> for ImageSaveQueueRowVm {
@@ -30,8 +33,16 @@ impl From<DomainRow> for ImageSaveQueueRowVm {
                 DomainPreprocess::None => 0,
                 DomainPreprocess::ResizeAndCropSquare256 => 1,
                 DomainPreprocess::ResizeForWidth400 => 2,
+                DomainPreprocess::ThumbnailVariants => 3,
             },
             last_error: v.last_error,
+            attempt_count: v.attempt_count,
+            next_attempt_at: v
+                .next_attempt_at
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+            failed_at: v
+                .failed_at
+                .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
         }
     }
 }
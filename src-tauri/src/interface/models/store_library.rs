@@ -0,0 +1,23 @@
+use crate::domain::works::MissingStoreWork;
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingStoreWorkVm {
+    pub work_id: String,
+    pub title: String,
+    pub store_id: String,
+    pub category: String,
+    pub missing_since: String,
+}
+
+impl From<MissingStoreWork> for MissingStoreWorkVm {
+    fn from(v: MissingStoreWork) -> Self {
+        Self {
+            work_id: v.work_id.value,
+            title: v.title,
+            store_id: v.store_id,
+            category: v.category,
+            missing_since: v.missing_since.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::domain::launch_profile::LaunchEnvVar;
+use crate::domain::wine::{WineConfig, WineRunner};
+use crate::domain::StrId;
+use crate::interface::models::launch_profile::LaunchEnvVarVm;
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WineConfigVm {
+    pub work_id: String,
+    pub runner: WineRunner,
+    pub runner_path: Option<String>,
+    pub prefix: Option<String>,
+    pub env: Vec<LaunchEnvVarVm>,
+}
+
+impl From<WineConfig> for WineConfigVm {
+    fn from(v: WineConfig) -> Self {
+        Self {
+            work_id: v.work_id.value,
+            runner: v.runner,
+            runner_path: v.runner_path,
+            prefix: v.prefix,
+            env: v
+                .env
+                .into_iter()
+                .map(|var| LaunchEnvVarVm {
+                    key: var.key,
+                    value: var.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<WineConfigVm> for WineConfig {
+    fn from(v: WineConfigVm) -> Self {
+        Self {
+            work_id: StrId::new(v.work_id),
+            runner: v.runner,
+            runner_path: v.runner_path,
+            prefix: v.prefix,
+            env: v
+                .env
+                .into_iter()
+                .map(|var| LaunchEnvVar {
+                    key: var.key,
+                    value: var.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Windows 版では Wine を使わないので、画面で設定を出すかどうかも返す
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WineSettingsVm {
+    pub available: bool,
+    pub config: Option<WineConfigVm>,
+}
@@ -2,8 +2,10 @@ use std::path::Path;
 
 use domain::service::save_path_resolver::{DirsSavePathResolver, SavePathResolver};
 
-use crate::interface::models::parent_dmm_pack::DmmPackKeysVm;
 use crate::domain::works::WorkDetails;
+use crate::interface::models::parent_dmm_pack::DmmPackKeysVm;
+use crate::interface::models::play_status::WorkPlayStateVm;
+use crate::interface::models::work_override::WorkOverridesVm;
 
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -18,10 +20,16 @@ pub struct WorkDetailsVm {
     pub thumbnail: Option<ThumbnailVm>,
     pub latest_download_path: Option<LatestWorkDownloadPathVm>,
     pub original_path: Option<String>,
+    /// `GameEngine::as_str` の値
+    pub engine: Option<String>,
     pub like_at: Option<String>,
     pub install_at: Option<String>,
     pub last_play_at: Option<String>,
     pub registered_at: Option<String>,
+    /// 記録がなければ None（未プレイ）
+    pub play_state: Option<WorkPlayStateVm>,
+    /// 利用者が直した項目。`title` と `erogamescape_information` には反映済み
+    pub overrides: WorkOverridesVm,
 }
 
 #[derive(serde::Serialize)]
@@ -66,6 +74,18 @@ pub struct ThumbnailVm {
     pub path: String,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    /// 幅の昇順。PNG のフォールバックも含む
+    pub variants: Vec<ThumbnailVariantVm>,
+    pub placeholder_color: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailVariantVm {
+    pub path: String,
+    pub width: i32,
+    pub height: i32,
+    pub format: String,
 }
 
 #[derive(serde::Serialize)]
@@ -124,13 +144,22 @@ impl WorkDetailsVm {
                     is_nukige: i.is_nukige,
                 }
             }),
-            icon: icon_path.map(|p| IconVm {
-                path: p,
-            }),
+            icon: icon_path.map(|p| IconVm { path: p }),
             thumbnail: thumbnail_path.map(|p| ThumbnailVm {
                 path: p,
                 width: w.thumbnail_size.as_ref().map(|s| s.width),
                 height: w.thumbnail_size.as_ref().map(|s| s.height),
+                variants: w
+                    .thumbnail_variants
+                    .iter()
+                    .map(|v| ThumbnailVariantVm {
+                        path: v.path.clone(),
+                        width: v.width,
+                        height: v.height,
+                        format: v.format.as_str().to_string(),
+                    })
+                    .collect(),
+                placeholder_color: w.thumbnail_placeholder_color.clone(),
             }),
             latest_download_path: w.latest_download_path.map(|p| LatestWorkDownloadPathVm {
                 id: p.id.value,
@@ -138,6 +167,7 @@ impl WorkDetailsVm {
                 download_path: p.download_path,
             }),
             original_path: w.original_path.clone(),
+            engine: w.engine.map(|e| e.as_str().to_string()),
             like_at: w
                 .like
                 .as_ref()
@@ -154,6 +184,8 @@ impl WorkDetailsVm {
                 .registered_at
                 .as_ref()
                 .map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+            play_state: w.play_state.map(Into::into),
+            overrides: w.overrides.into(),
         }
     }
 }
@@ -0,0 +1,55 @@
+use serde::Serialize;
+
+use crate::domain::work_duplicate::{DuplicateCandidate, DuplicateReason, WorkMergeResult};
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DuplicateReasonVm {
+    /// 批評空間の ID が同じ
+    SameErogamescapeId { id: i32 },
+    /// 0〜1。正規化して同じタイトルなら 1
+    SimilarTitle { score: f32 },
+    /// 知覚ハッシュの異なるビット数
+    SimilarThumbnail { distance: u32 },
+}
+
+impl From<DuplicateReason> for DuplicateReasonVm {
+    fn from(v: DuplicateReason) -> Self {
+        match v {
+            DuplicateReason::SameErogamescapeId(id) => Self::SameErogamescapeId { id },
+            DuplicateReason::SimilarTitle { score } => Self::SimilarTitle { score },
+            DuplicateReason::SimilarThumbnail { distance } => Self::SimilarThumbnail { distance },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCandidateVm {
+    pub work_ids: [String; 2],
+    pub reasons: Vec<DuplicateReasonVm>,
+}
+
+impl From<DuplicateCandidate> for DuplicateCandidateVm {
+    fn from(v: DuplicateCandidate) -> Self {
+        let (a, b) = v.work_ids;
+        Self {
+            work_ids: [a.value, b.value],
+            reasons: v.reasons.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkMergeResultVm {
+    pub thumbnail_moved: bool,
+}
+
+impl From<WorkMergeResult> for WorkMergeResultVm {
+    fn from(v: WorkMergeResult) -> Self {
+        Self {
+            thumbnail_moved: v.thumbnail_moved,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use chrono::NaiveDate;
+
+use crate::domain::work_override::WorkOverrides;
+
+/// None の項目は同期や取り込みで得た情報を使う
+#[derive(Clone, Debug, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkOverridesVm {
+    pub title: Option<String>,
+    pub title_ruby: Option<String>,
+    pub brandname: Option<String>,
+    /// `YYYY-MM-DD`
+    pub sellday: Option<NaiveDate>,
+}
+
+impl From<WorkOverrides> for WorkOverridesVm {
+    fn from(v: WorkOverrides) -> Self {
+        Self {
+            title: v.title,
+            title_ruby: v.title_ruby,
+            brandname: v.brandname,
+            sellday: v.sellday,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use chrono::NaiveDate;
+use domain::service::save_path_resolver::SavePathResolver;
+
+use crate::domain::play_status::PlayStatus;
+use crate::domain::work_query::{
+    BoolFacetCount, FacetCount, SortDirection, WorkCursor, WorkFacets, WorkListFilter, WorkPage,
+    WorkQuery, WorkSort, WorkSortKey, WorkStore,
+};
+use crate::domain::works::DmmPackKey;
+use crate::interface::models::parent_dmm_pack::DmmPackKeysVm;
+use crate::interface::models::work_details::WorkDetailsVm;
+
+/// 作品一覧の絞り込み。空の項目では絞り込まない
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WorkListFilterVm {
+    pub stores: Vec<WorkStore>,
+    pub liked: Option<bool>,
+    pub installed: Option<bool>,
+    pub nukige: Option<bool>,
+    pub brands: Vec<String>,
+    /// `YYYY-MM-DD`。両端を含む
+    pub sellday_from: Option<NaiveDate>,
+    pub sellday_to: Option<NaiveDate>,
+    pub parent_pack: Option<DmmPackKeysVm>,
+    pub play_statuses: Vec<PlayStatus>,
+    pub min_score: Option<i32>,
+    pub max_score: Option<i32>,
+}
+
+impl From<WorkListFilterVm> for WorkListFilter {
+    fn from(v: WorkListFilterVm) -> Self {
+        Self {
+            stores: v.stores,
+            liked: v.liked,
+            installed: v.installed,
+            nukige: v.nukige,
+            brands: v.brands,
+            sellday_from: v.sellday_from,
+            sellday_to: v.sellday_to,
+            parent_pack: v
+                .parent_pack
+                .map(|p| DmmPackKey::new(p.store_id, p.category, p.subcategory)),
+            play_statuses: v.play_statuses,
+            min_score: v.min_score,
+            max_score: v.max_score,
+        }
+    }
+}
+
+/// 前のページの `nextCursor` をそのまま渡す
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkCursorVm {
+    pub sort_value: String,
+    pub work_id: String,
+}
+
+impl From<WorkCursor> for WorkCursorVm {
+    fn from(v: WorkCursor) -> Self {
+        Self {
+            sort_value: v.sort_value,
+            work_id: v.work_id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WorkQueryVm {
+    pub filter: WorkListFilterVm,
+    pub sort_key: WorkSortKey,
+    pub sort_direction: SortDirection,
+    pub after: Option<WorkCursorVm>,
+    /// 省くと残りをすべて返す
+    pub limit: Option<u32>,
+}
+
+impl From<WorkQueryVm> for WorkQuery {
+    fn from(v: WorkQueryVm) -> Self {
+        Self {
+            filter: v.filter.into(),
+            sort: WorkSort {
+                key: v.sort_key,
+                direction: v.sort_direction,
+            },
+            after: v.after.map(|c| WorkCursor {
+                sort_value: c.sort_value,
+                work_id: c.work_id,
+            }),
+            limit: v.limit,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkPageVm {
+    pub items: Vec<WorkDetailsVm>,
+    pub next_cursor: Option<WorkCursorVm>,
+}
+
+impl WorkPageVm {
+    pub fn from_work_page_with_resolver(page: WorkPage, resolver: &dyn SavePathResolver) -> Self {
+        Self {
+            items: page
+                .items
+                .into_iter()
+                .map(|w| WorkDetailsVm::from_work_details_with_resolver(w, resolver))
+                .collect(),
+            next_cursor: page.next_cursor.map(Into::into),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetCountVm<T> {
+    pub value: T,
+    pub count: i64,
+}
+
+impl<T> From<FacetCount<T>> for FacetCountVm<T> {
+    fn from(v: FacetCount<T>) -> Self {
+        Self {
+            value: v.value,
+            count: v.count,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoolFacetCountVm {
+    pub yes: i64,
+    pub no: i64,
+}
+
+impl From<BoolFacetCount> for BoolFacetCountVm {
+    fn from(v: BoolFacetCount) -> Self {
+        Self {
+            yes: v.yes,
+            no: v.no,
+        }
+    }
+}
+
+/// 各項目の件数は、その項目自身の絞り込みを外して数える
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkFacetsVm {
+    pub total: i64,
+    pub stores: Vec<FacetCountVm<WorkStore>>,
+    pub liked: BoolFacetCountVm,
+    pub installed: BoolFacetCountVm,
+    pub nukige: BoolFacetCountVm,
+    pub play_statuses: Vec<FacetCountVm<PlayStatus>>,
+    pub brands: Vec<FacetCountVm<String>>,
+}
+
+impl From<WorkFacets> for WorkFacetsVm {
+    fn from(v: WorkFacets) -> Self {
+        Self {
+            total: v.total,
+            stores: v.stores.into_iter().map(Into::into).collect(),
+            liked: v.liked.into(),
+            installed: v.installed.into(),
+            nukige: v.nukige.into(),
+            play_statuses: v.play_statuses.into_iter().map(Into::into).collect(),
+            brands: v.brands.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -5,19 +5,21 @@ use crate::{
     domain::windows::WindowsExt,
     domain::{pubsub::PubSubService, repository::RepositoriesExt},
     infrastructure::{
+        downloader::{DownloaderConfig, HttpDownloader},
         heuristic_duplicate_resolver::HeuristicDuplicateResolver,
         heuristic_metadata_extractor::HeuristicMetadataExtractor,
         image_queue_worker::handler::ImageQueuePubSubHandler,
         image_queue_worker::ImageQueueRunnerImpl,
         local_file_system::LocalFileSystem,
+        matcher_index_store::FileMatcherIndexStore,
         native_messaging::NativeMessagingHostClientFactoryImpl,
+        platform::Platform,
         pubsubimpl::pubsub::{PubSub, PubSubExt},
         save_path_resolver::{DbSavePathResolver, StoragePathSettingsStore},
         sqliterepository::{
             driver::Db,
             sqliterepository::{SqliteRepositories, SqliteRepositoryManager},
         },
-        windowsimpl::windows::Windows,
         work_linker::WorkLinkerImpl,
         work_registration::WorkRegistrationServiceImpl,
     },
@@ -25,11 +27,16 @@ use crate::{
         all_game_cache::AllGameCacheUseCase, app_settings::AppSettingsUseCase,
         erogamescape::ErogamescapeUseCase, extension_manager::ExtensionManagerUseCase,
         file::FileUseCase, host_log::HostLogUseCase, image_queue::ImageQueueUseCase,
-        process::ProcessUseCase, work::WorkUseCase, work_link_pending_exe::WorkLinkPendingExeUseCase,
-        work_pipeline::WorkPipelineUseCase, work_thumbnail::WorkThumbnailUseCase,
+        launch_profile::LaunchProfileUseCase, play_status::PlayStatusUseCase,
+        process::ProcessUseCase, remote_launch::RemoteLaunchUseCase,
+        remote_share_sync::RemoteShareSyncUseCase, save_backup::SaveBackupUseCase,
+        store_library::StoreLibraryUseCase, wine_config::WineConfigUseCase, work::WorkUseCase,
+        work_duplicate::WorkDuplicateUseCase, work_link_pending_exe::WorkLinkPendingExeUseCase,
+        work_override::WorkOverrideUseCase, work_pipeline::WorkPipelineUseCase,
+        work_thumbnail::WorkThumbnailUseCase,
     },
 };
-use domain::game_matcher::{GameMatcher, Matcher as GameMatcherImpl};
+use domain::game_matcher::{GameMatcher, Matcher as GameMatcherImpl, MatcherConfig};
 use domain::repository::all_game_cache::AllGameCacheRepository as _;
 use domain::repository::manager::RepositoryManager as _;
 use tauri::AppHandle;
@@ -39,13 +46,13 @@ pub struct Modules {
         ExtensionManagerUseCase<PubSub, NativeMessagingHostClientFactoryImpl>,
     file_use_case: FileUseCase,
     all_game_cache_use_case: AllGameCacheUseCase<SqliteRepositoryManager, SqliteRepositories>,
-    process_use_case: ProcessUseCase<Windows>,
+    process_use_case: ProcessUseCase<Platform>,
     host_log_use_case: HostLogUseCase<SqliteRepositoryManager, SqliteRepositories>,
     work_use_case: WorkUseCase<
         SqliteRepositoryManager,
         SqliteRepositories,
-        Windows,
-        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        Platform,
+        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     >,
     work_pipeline_use_case: WorkPipelineUseCase<
         SqliteRepositoryManager,
@@ -54,20 +61,30 @@ pub struct Modules {
         LocalFileSystem,
         HeuristicMetadataExtractor,
         HeuristicDuplicateResolver,
-        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
-        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
+        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     >,
     image_queue_use_case: ImageQueueUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    store_library_use_case: StoreLibraryUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    remote_share_sync_use_case: RemoteShareSyncUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    remote_launch_use_case:
+        RemoteLaunchUseCase<SqliteRepositoryManager, SqliteRepositories, PubSub>,
+    launch_profile_use_case: LaunchProfileUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    wine_config_use_case: WineConfigUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    save_backup_use_case: SaveBackupUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    play_status_use_case: PlayStatusUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    work_duplicate_use_case: WorkDuplicateUseCase<SqliteRepositoryManager, SqliteRepositories>,
+    work_override_use_case: WorkOverrideUseCase<SqliteRepositoryManager, SqliteRepositories>,
     erogamescape_use_case: ErogamescapeUseCase<SqliteRepositoryManager, SqliteRepositories>,
     work_link_pending_exe_use_case: WorkLinkPendingExeUseCase<
         SqliteRepositoryManager,
         SqliteRepositories,
-        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     >,
     pubsub: PubSub,
     game_matcher: std::sync::Arc<dyn GameMatcher + Send + Sync>,
     image_queue_runner:
-        std::sync::Arc<ImageQueueRunnerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>>,
+        std::sync::Arc<ImageQueueRunnerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>>,
     work_thumbnail_use_case: WorkThumbnailUseCase<SqliteRepositoryManager, SqliteRepositories>,
     save_path_resolver: Arc<dyn domain::service::save_path_resolver::SavePathResolver>,
     app_settings_use_case: AppSettingsUseCase<SqliteRepositoryManager, SqliteRepositories>,
@@ -92,8 +109,8 @@ pub trait ModulesExt {
     ) -> &WorkUseCase<
         SqliteRepositoryManager,
         SqliteRepositories,
-        Windows,
-        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        Platform,
+        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     >;
     fn work_pipeline_use_case(
         &self,
@@ -104,17 +121,44 @@ pub trait ModulesExt {
         LocalFileSystem,
         HeuristicMetadataExtractor,
         HeuristicDuplicateResolver,
-        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
-        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
+        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     >;
     fn pubsub(&self) -> &Self::PubSub;
     fn game_matcher(&self) -> &std::sync::Arc<dyn GameMatcher + Send + Sync>;
     fn image_queue_runner(
         &self,
-    ) -> &std::sync::Arc<ImageQueueRunnerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>>;
+    ) -> &std::sync::Arc<ImageQueueRunnerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>>;
     fn image_queue_use_case(
         &self,
     ) -> &ImageQueueUseCase<SqliteRepositoryManager, SqliteRepositories>;
+    fn store_library_use_case(
+        &self,
+    ) -> &StoreLibraryUseCase<SqliteRepositoryManager, SqliteRepositories>;
+    fn remote_share_sync_use_case(
+        &self,
+    ) -> &RemoteShareSyncUseCase<SqliteRepositoryManager, SqliteRepositories>;
+    fn remote_launch_use_case(
+        &self,
+    ) -> &RemoteLaunchUseCase<SqliteRepositoryManager, SqliteRepositories, Self::PubSub>;
+    fn launch_profile_use_case(
+        &self,
+    ) -> &LaunchProfileUseCase<SqliteRepositoryManager, SqliteRepositories>;
+    fn wine_config_use_case(
+        &self,
+    ) -> &WineConfigUseCase<SqliteRepositoryManager, SqliteRepositories>;
+    fn save_backup_use_case(
+        &self,
+    ) -> &SaveBackupUseCase<SqliteRepositoryManager, SqliteRepositories>;
+    fn play_status_use_case(
+        &self,
+    ) -> &PlayStatusUseCase<SqliteRepositoryManager, SqliteRepositories>;
+    fn work_duplicate_use_case(
+        &self,
+    ) -> &WorkDuplicateUseCase<SqliteRepositoryManager, SqliteRepositories>;
+    fn work_override_use_case(
+        &self,
+    ) -> &WorkOverrideUseCase<SqliteRepositoryManager, SqliteRepositories>;
     fn erogamescape_use_case(
         &self,
     ) -> &ErogamescapeUseCase<SqliteRepositoryManager, SqliteRepositories>;
@@ -123,7 +167,7 @@ pub trait ModulesExt {
     ) -> &WorkLinkPendingExeUseCase<
         SqliteRepositoryManager,
         SqliteRepositories,
-        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     >;
     fn work_thumbnail_use_case(
         &self,
@@ -137,7 +181,7 @@ pub trait ModulesExt {
 
 impl ModulesExt for Modules {
     type Repositories = SqliteRepositories;
-    type Windows = Windows;
+    type Windows = Platform;
     type PubSub = PubSub;
 
     fn extension_manager_use_case(
@@ -164,8 +208,8 @@ impl ModulesExt for Modules {
     ) -> &WorkUseCase<
         SqliteRepositoryManager,
         SqliteRepositories,
-        Windows,
-        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        Platform,
+        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     > {
         &self.work_use_case
     }
@@ -178,8 +222,8 @@ impl ModulesExt for Modules {
         LocalFileSystem,
         HeuristicMetadataExtractor,
         HeuristicDuplicateResolver,
-        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
-        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
+        WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     > {
         &self.work_pipeline_use_case
     }
@@ -191,7 +235,7 @@ impl ModulesExt for Modules {
     }
     fn image_queue_runner(
         &self,
-    ) -> &std::sync::Arc<ImageQueueRunnerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>>
+    ) -> &std::sync::Arc<ImageQueueRunnerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>>
     {
         &self.image_queue_runner
     }
@@ -200,6 +244,51 @@ impl ModulesExt for Modules {
     ) -> &ImageQueueUseCase<SqliteRepositoryManager, SqliteRepositories> {
         &self.image_queue_use_case
     }
+    fn store_library_use_case(
+        &self,
+    ) -> &StoreLibraryUseCase<SqliteRepositoryManager, SqliteRepositories> {
+        &self.store_library_use_case
+    }
+    fn remote_share_sync_use_case(
+        &self,
+    ) -> &RemoteShareSyncUseCase<SqliteRepositoryManager, SqliteRepositories> {
+        &self.remote_share_sync_use_case
+    }
+    fn remote_launch_use_case(
+        &self,
+    ) -> &RemoteLaunchUseCase<SqliteRepositoryManager, SqliteRepositories, Self::PubSub> {
+        &self.remote_launch_use_case
+    }
+    fn launch_profile_use_case(
+        &self,
+    ) -> &LaunchProfileUseCase<SqliteRepositoryManager, SqliteRepositories> {
+        &self.launch_profile_use_case
+    }
+    fn wine_config_use_case(
+        &self,
+    ) -> &WineConfigUseCase<SqliteRepositoryManager, SqliteRepositories> {
+        &self.wine_config_use_case
+    }
+    fn save_backup_use_case(
+        &self,
+    ) -> &SaveBackupUseCase<SqliteRepositoryManager, SqliteRepositories> {
+        &self.save_backup_use_case
+    }
+    fn play_status_use_case(
+        &self,
+    ) -> &PlayStatusUseCase<SqliteRepositoryManager, SqliteRepositories> {
+        &self.play_status_use_case
+    }
+    fn work_duplicate_use_case(
+        &self,
+    ) -> &WorkDuplicateUseCase<SqliteRepositoryManager, SqliteRepositories> {
+        &self.work_duplicate_use_case
+    }
+    fn work_override_use_case(
+        &self,
+    ) -> &WorkOverrideUseCase<SqliteRepositoryManager, SqliteRepositories> {
+        &self.work_override_use_case
+    }
     fn erogamescape_use_case(
         &self,
     ) -> &ErogamescapeUseCase<SqliteRepositoryManager, SqliteRepositories> {
@@ -210,7 +299,7 @@ impl ModulesExt for Modules {
     ) -> &WorkLinkPendingExeUseCase<
         SqliteRepositoryManager,
         SqliteRepositories,
-        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+        WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
     > {
         &self.work_link_pending_exe_use_case
     }
@@ -235,7 +324,7 @@ impl ModulesExt for Modules {
 impl Modules {
     pub async fn new(db: Db, handle: &AppHandle) -> Self {
         let repo_manager = Arc::new(SqliteRepositoryManager::new(db.pool_arc()));
-        let windows = Arc::new(Windows::new());
+        let windows = Arc::new(Platform::new());
         let pubsub = PubSub::new(Arc::new(handle.clone()));
         let fixed_root = DirsSavePathResolver::default().root_dir();
         let app_settings_use_case = AppSettingsUseCase::new(repo_manager.clone());
@@ -250,6 +339,12 @@ impl Modules {
             DbSavePathResolver::new(fixed_root, storage_path_settings.clone()),
         );
 
+        // 画像の取得はすべてこれを通し、ホストごとの同時接続数制限と HTTP キャッシュを共有する
+        let downloader = Arc::new(
+            HttpDownloader::new(DownloaderConfig::default())
+                .with_cache_dir(resolver.http_cache_dir()),
+        );
+
         let extension_manager_use_case = ExtensionManagerUseCase::new(
             pubsub.clone(),
             Arc::new(NativeMessagingHostClientFactoryImpl),
@@ -257,7 +352,7 @@ impl Modules {
 
         let file_use_case: FileUseCase = FileUseCase::new(resolver.clone());
 
-        let process_use_case: ProcessUseCase<Windows> = ProcessUseCase::new(windows.clone());
+        let process_use_case: ProcessUseCase<Platform> = ProcessUseCase::new(windows.clone());
 
         let host_log_use_case: HostLogUseCase<SqliteRepositoryManager, SqliteRepositories> =
             HostLogUseCase::new(repo_manager.clone());
@@ -266,7 +361,7 @@ impl Modules {
             SqliteRepositories,
         > = ErogamescapeUseCase::new(repo_manager.clone());
         let work_registration_service: Arc<
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = Arc::new(WorkRegistrationServiceImpl::new(
             repo_manager.clone(),
             resolver.clone(),
@@ -275,8 +370,8 @@ impl Modules {
         let work_use_case: WorkUseCase<
             SqliteRepositoryManager,
             SqliteRepositories,
-            Windows,
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            Platform,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = WorkUseCase::new(
             repo_manager.clone(),
             windows.clone(),
@@ -285,7 +380,7 @@ impl Modules {
         let work_link_pending_exe_use_case: WorkLinkPendingExeUseCase<
             SqliteRepositoryManager,
             SqliteRepositories,
-            WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = WorkLinkPendingExeUseCase::new(
             repo_manager.clone(),
             std::sync::Arc::new(WorkLinkerImpl::new(
@@ -296,18 +391,61 @@ impl Modules {
         );
         let image_queue_use_case: ImageQueueUseCase<SqliteRepositoryManager, SqliteRepositories> =
             ImageQueueUseCase::new(repo_manager.clone());
+        let store_library_use_case: StoreLibraryUseCase<
+            SqliteRepositoryManager,
+            SqliteRepositories,
+        > = StoreLibraryUseCase::new(repo_manager.clone());
+        let remote_share_sync_use_case: RemoteShareSyncUseCase<
+            SqliteRepositoryManager,
+            SqliteRepositories,
+        > = RemoteShareSyncUseCase::new(repo_manager.clone());
+        let remote_launch_use_case: RemoteLaunchUseCase<
+            SqliteRepositoryManager,
+            SqliteRepositories,
+            PubSub,
+        > = RemoteLaunchUseCase::new(repo_manager.clone(), pubsub.clone());
+        let launch_profile_use_case: LaunchProfileUseCase<
+            SqliteRepositoryManager,
+            SqliteRepositories,
+        > = LaunchProfileUseCase::new(repo_manager.clone());
+        let wine_config_use_case: WineConfigUseCase<SqliteRepositoryManager, SqliteRepositories> =
+            WineConfigUseCase::new(repo_manager.clone());
+        let save_backup_use_case: SaveBackupUseCase<SqliteRepositoryManager, SqliteRepositories> =
+            SaveBackupUseCase::new(repo_manager.clone(), resolver.clone());
+        let play_status_use_case: PlayStatusUseCase<SqliteRepositoryManager, SqliteRepositories> =
+            PlayStatusUseCase::new(repo_manager.clone());
+        let work_override_use_case: WorkOverrideUseCase<
+            SqliteRepositoryManager,
+            SqliteRepositories,
+        > = WorkOverrideUseCase::new(repo_manager.clone());
 
         // GameMatcher 構築
         let initial_cache = repo_manager
             .run(|repos| Box::pin(async move { repos.all_game_cache().get_all().await }))
             .await
             .unwrap_or_else(|_| vec![]);
-        let game_matcher = std::sync::Arc::new(GameMatcherImpl::with_default_config(initial_cache));
+        // インデックスは永続化したものを使い、キャッシュ内容が変わっていたときだけ再構築する
+        let matcher_index_store =
+            Arc::new(FileMatcherIndexStore::new(resolver.matcher_index_path()));
+        let game_matcher = std::sync::Arc::new(GameMatcherImpl::with_index_store(
+            initial_cache,
+            MatcherConfig::default(),
+            matcher_index_store,
+        ));
+        {
+            // 起動を待たせないよう読み込み/構築はバックグラウンドで行う
+            let game_matcher = game_matcher.clone();
+            tauri::async_runtime::spawn_blocking(move || game_matcher.warm_up());
+        }
         // AllGameCacheUseCase を生成（matcher を注入）
         let all_game_cache_use_case: AllGameCacheUseCase<
             SqliteRepositoryManager,
             SqliteRepositories,
         > = AllGameCacheUseCase::with_matcher(repo_manager.clone(), game_matcher.clone());
+        let work_duplicate_use_case: WorkDuplicateUseCase<
+            SqliteRepositoryManager,
+            SqliteRepositories,
+        > = WorkDuplicateUseCase::new(repo_manager.clone(), game_matcher.clone(), resolver.clone());
 
         // WorkPipelineUseCase 構築
         let fs = std::sync::Arc::new(LocalFileSystem::default());
@@ -320,8 +458,8 @@ impl Modules {
             LocalFileSystem,
             HeuristicMetadataExtractor,
             HeuristicDuplicateResolver,
-            WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
-            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            WorkLinkerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
+            WorkRegistrationServiceImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = WorkPipelineUseCase::new(
             repo_manager.clone(),
             pubsub.clone(),
@@ -338,15 +476,18 @@ impl Modules {
         );
 
         // ImageQueue のイベントハンドラ: Tauri 側は PubSub を利用
-        let pubsub_handler =
-            std::sync::Arc::new(ImageQueuePubSubHandler::new(repo_manager.clone(), pubsub.clone()));
+        let pubsub_handler = std::sync::Arc::new(ImageQueuePubSubHandler::new(
+            repo_manager.clone(),
+            pubsub.clone(),
+        ));
 
         let image_queue_runner: std::sync::Arc<
-            ImageQueueRunnerImpl<SqliteRepositoryManager, SqliteRepositories, Windows>,
+            ImageQueueRunnerImpl<SqliteRepositoryManager, SqliteRepositories, Platform>,
         > = std::sync::Arc::new(ImageQueueRunnerImpl::new_with_event_handler(
             repo_manager.clone(),
             resolver.clone(),
             windows.clone(),
+            downloader.clone(),
             pubsub_handler,
         ));
 
@@ -369,6 +510,15 @@ impl Modules {
             game_matcher,
             image_queue_runner,
             image_queue_use_case,
+            store_library_use_case,
+            remote_share_sync_use_case,
+            remote_launch_use_case,
+            launch_profile_use_case,
+            wine_config_use_case,
+            save_backup_use_case,
+            play_status_use_case,
+            work_duplicate_use_case,
+            work_override_use_case,
             work_thumbnail_use_case,
             save_path_resolver: resolver,
             app_settings_use_case,
@@ -1,6 +1,7 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod app_request;
 mod interface;
 mod remote_launch;
 pub mod domain {
@@ -16,6 +17,8 @@ pub mod usecase {
 use std::sync::Arc;
 
 use ::infrastructure::sqliterepository::driver::Db;
+use domain::native_host_log::HostLogRetention;
+use domain::service::image_queue_drain::ImageQueueDrainService;
 use domain::service::save_path_resolver::{DirsSavePathResolver, SavePathResolver};
 use interface::{
     commands,
@@ -80,11 +83,51 @@ fn main() {
             let modules = Arc::new(block_on(Modules::new(db, &app.handle())));
             app.manage(modules.clone());
             crate::remote_launch::spawn_remote_launch_client(modules.clone());
+            {
+                let modules = modules.clone();
+                tauri::async_runtime::spawn(async move {
+                    match modules
+                        .host_log_use_case()
+                        .apply_retention(HostLogRetention::default())
+                        .await
+                    {
+                        Ok(deleted) if deleted > 0 => {
+                            log::info!("pruned {deleted} native host logs")
+                        }
+                        Ok(_) => {}
+                        Err(err) => log::warn!("failed to prune native host logs: {err}"),
+                    }
+                });
+            }
+            {
+                // 以前のバージョンで保存したサムネイルにもバリアントを作る
+                let modules = modules.clone();
+                tauri::async_runtime::spawn(async move {
+                    match modules
+                        .work_thumbnail_use_case()
+                        .backfill_thumbnail_variants()
+                        .await
+                    {
+                        Ok(enqueued) if enqueued > 0 => {
+                            log::info!("enqueued {enqueued} thumbnail variant backfills");
+                            let runner = modules.image_queue_runner();
+                            if let Err(err) =
+                                ImageQueueDrainService::drain_until_empty(runner.as_ref()).await
+                            {
+                                log::warn!("failed to drain thumbnail variant backfills: {err}");
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(err) => log::warn!("failed to backfill thumbnail variants: {err}"),
+                    }
+                });
+            }
 
             if let Err(err) =
-                infrastructure::app_signal_router::interprocess::listener::spawn_listener(Arc::new(
-                    modules.pubsub().clone(),
-                ))
+                infrastructure::app_signal_router::interprocess::listener::spawn_listener(
+                    Arc::new(modules.pubsub().clone()),
+                    Arc::new(app_request::AppRequestHandlerImpl::new(modules.clone())),
+                )
             {
                 log::error!("failed to start app signal listener: {err}");
             }
@@ -130,6 +173,37 @@ fn main() {
             commands::remote_share::register_remote_share_device,
             commands::remote_share::sync_remote_share_works,
             commands::remote_share::get_remote_share_url,
+            commands::remote_launch::get_remote_launch_settings,
+            commands::remote_launch::set_remote_launch_settings,
+            commands::remote_launch::respond_remote_launch_confirmation,
+            commands::remote_launch::get_remote_launch_audits,
+            commands::launch_profile::list_launch_profiles,
+            commands::launch_profile::save_launch_profile,
+            commands::launch_profile::delete_launch_profile,
+            commands::launch_profile::set_default_launch_profile,
+            commands::wine_config::get_wine_settings,
+            commands::wine_config::save_wine_config,
+            commands::wine_config::delete_wine_config,
+            commands::save_backup::list_save_locations,
+            commands::save_backup::detect_save_locations,
+            commands::save_backup::add_save_location,
+            commands::save_backup::delete_save_location,
+            commands::save_backup::create_save_snapshot,
+            commands::save_backup::list_save_snapshots,
+            commands::save_backup::restore_save_snapshot,
+            commands::save_backup::delete_save_snapshot,
+            commands::play_status::get_work_play_state,
+            commands::play_status::save_work_play_state,
+            commands::play_status::list_work_play_status_changes,
+            commands::play_status::list_work_routes,
+            commands::play_status::add_work_route,
+            commands::play_status::set_work_route_completed,
+            commands::play_status::delete_work_route,
+            commands::work_duplicate::find_duplicate_works,
+            commands::work_duplicate::merge_works,
+            commands::work_override::get_work_overrides,
+            commands::work_override::set_work_override,
+            commands::work_override::clear_work_override,
             commands::utils::open_url,
             commands::matcher::get_game_candidates_by_name,
             commands::notification::show_os_notification,
@@ -143,7 +217,10 @@ fn main() {
             commands::extension::check_registry_keys,
             commands::extension::remove_registry_keys,
             commands::native_host_logs::get_native_host_logs,
+            commands::native_host_logs::export_native_host_logs,
             commands::work_details::get_work_details_all,
+            commands::work_details::query_work_details,
+            commands::work_details::get_work_facets,
             commands::work_details::get_work_details_by_work_id,
             commands::works::backfill_thumbnail_sizes,
             commands::works::list_work_lnks,
@@ -153,6 +230,11 @@ fn main() {
             commands::works::register_work_from_path,
             commands::works::process_pending_exe_links,
             commands::image_queue::get_image_save_queue,
+            commands::image_queue::get_image_save_queue_failed,
+            commands::image_queue::requeue_image_save_queue_items,
+            commands::image_queue::discard_image_save_queue_items,
+            commands::store_library::get_missing_store_works,
+            commands::store_library::prune_missing_store_works,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
+use chrono::Local;
+use domain::remote_launch::{NewRemoteLaunchAudit, RemoteLaunchStatus};
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
 use tokio::time::{sleep, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
@@ -21,12 +24,15 @@ enum BrokerMessage {
 
 #[derive(Debug, Serialize)]
 #[serde(tag = "type")]
-enum BrokerAckMessage<'a> {
+enum BrokerAckMessage {
     #[serde(rename = "launch-ack")]
     LaunchAck {
         #[serde(rename = "workId")]
-        work_id: &'a str,
-        status: &'a str,
+        work_id: String,
+        status: &'static str,
+        /// 起動ポリシーによる判断。判断できなかったときは送らない
+        #[serde(skip_serializing_if = "Option::is_none")]
+        decision: Option<&'static str>,
     },
 }
 
@@ -43,7 +49,10 @@ pub fn spawn_remote_launch_client(modules: Arc<Modules>) {
 }
 
 async fn run_once(modules: Arc<Modules>) -> anyhow::Result<()> {
-    let settings = modules.app_settings_use_case().get_remote_share_settings().await?;
+    let settings = modules
+        .app_settings_use_case()
+        .get_remote_share_settings()
+        .await?;
     let server_base_url = match settings.remote_share_server_base_url {
         Some(value) if !value.is_empty() => value,
         _ => return Ok(()),
@@ -58,67 +67,122 @@ async fn run_once(modules: Arc<Modules>) -> anyhow::Result<()> {
     };
 
     let broker_url = build_remote_launch_ws_url(&server_base_url, &device_id, &device_secret)?;
-    let (mut stream, _) = connect_async(broker_url).await?;
+    let (stream, _) = connect_async(broker_url).await?;
+    let (mut sink, mut stream) = stream.split();
     log::info!("remote launch broker connected");
 
-    while let Some(message) = stream.next().await {
-        let message = message?;
-        match message {
-            Message::Text(text) => {
-                if let Ok(payload) = serde_json::from_str::<BrokerMessage>(&text) {
-                    handle_broker_message(&modules, payload, &mut stream).await;
+    // 確認を待つあいだも ping に応えられるよう、要求ごとにタスクで処理して ack だけ受け取る
+    let (ack_tx, mut ack_rx) = mpsc::unbounded_channel::<BrokerAckMessage>();
+    loop {
+        tokio::select! {
+            message = stream.next() => {
+                let Some(message) = message else { break };
+                match message? {
+                    Message::Text(text) => {
+                        if let Ok(payload) = serde_json::from_str::<BrokerMessage>(&text) {
+                            let modules = modules.clone();
+                            let ack_tx = ack_tx.clone();
+                            tauri::async_runtime::spawn(async move {
+                                let ack = handle_broker_message(&modules, payload).await;
+                                let _ = ack_tx.send(ack);
+                            });
+                        }
+                    }
+                    Message::Close(_) => break,
+                    Message::Ping(value) => {
+                        sink.send(Message::Pong(value)).await?;
+                    }
+                    _ => {}
                 }
             }
-            Message::Close(_) => break,
-            Message::Ping(value) => {
-                stream.send(Message::Pong(value)).await?;
+            Some(ack) = ack_rx.recv() => {
+                if let Ok(text) = serde_json::to_string(&ack) {
+                    sink.send(Message::Text(text)).await?;
+                }
             }
-            _ => {}
         }
     }
 
     Ok(())
 }
 
-async fn handle_broker_message(
-    modules: &Arc<Modules>,
-    payload: BrokerMessage,
-    stream: &mut tokio_tungstenite::WebSocketStream<
-        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-    >,
-) {
+async fn handle_broker_message(modules: &Arc<Modules>, payload: BrokerMessage) -> BrokerAckMessage {
     match payload {
-        BrokerMessage::LaunchWork { work_id } => {
-            let status = match launch_first_work_link(modules, &work_id).await {
-                Ok(true) => "queued",
-                Ok(false) => "not-found",
-                Err(err) => {
-                    log::warn!("failed to launch work {work_id}: {err}");
-                    "error"
-                }
-            };
+        BrokerMessage::LaunchWork { work_id } => handle_launch_request(modules, work_id).await,
+    }
+}
 
-            let ack = BrokerAckMessage::LaunchAck {
-                work_id: &work_id,
-                status,
+/// 起動ポリシーに従って起動し、要求と結果を記録する
+async fn handle_launch_request(modules: &Arc<Modules>, work_id: String) -> BrokerAckMessage {
+    let requested_at = Local::now();
+    let use_case = modules.remote_launch_use_case();
+    let authorization = match use_case.authorize(&work_id).await {
+        Ok(authorization) => authorization,
+        Err(err) => {
+            // 判断できないときは起動しない
+            log::warn!("failed to authorize remote launch of {work_id}: {err}");
+            return BrokerAckMessage::LaunchAck {
+                work_id,
+                status: RemoteLaunchStatus::Error.as_str(),
+                decision: None,
             };
+        }
+    };
 
-            if let Ok(text) = serde_json::to_string(&ack) {
-                let _ = stream.send(Message::Text(text)).await;
+    let mut error_message = None;
+    let status = if !authorization.decision.is_permitted() {
+        log::info!(
+            "remote launch of {work_id} denied: {}",
+            authorization.decision.as_str()
+        );
+        RemoteLaunchStatus::Denied
+    } else {
+        match launch_first_work_link(modules, &work_id).await {
+            Ok(true) => RemoteLaunchStatus::Queued,
+            Ok(false) => RemoteLaunchStatus::NotFound,
+            Err(err) => {
+                log::warn!("failed to launch work {work_id}: {err}");
+                error_message = Some(err.to_string());
+                RemoteLaunchStatus::Error
             }
         }
+    };
+
+    if let Err(err) = use_case
+        .record_audit(NewRemoteLaunchAudit {
+            work_id: work_id.clone(),
+            work_title: authorization.work_title,
+            policy: authorization.policy,
+            decision: authorization.decision,
+            status,
+            error_message,
+            requested_at,
+        })
+        .await
+    {
+        log::warn!("failed to record remote launch audit: {err}");
+    }
+
+    BrokerAckMessage::LaunchAck {
+        work_id,
+        status: status.as_str(),
+        decision: Some(authorization.decision.as_str()),
     }
 }
 
 async fn launch_first_work_link(modules: &Arc<Modules>, work_id: &str) -> anyhow::Result<bool> {
-    let links = modules.work_use_case().list_work_lnks(work_id.to_string()).await?;
+    let links = modules
+        .work_use_case()
+        .list_work_lnks(work_id.to_string())
+        .await?;
     let Some((first_lnk_id, _)) = links.first() else {
         return Ok(false);
     };
 
+    // 作品の既定の起動プロファイルで起動する
     let _ = modules
         .work_use_case()
-        .launch_work(false, *first_lnk_id)
+        .launch_work(*first_lnk_id, None, None)
         .await?;
 
     Ok(true)
@@ -152,12 +216,31 @@ fn build_remote_launch_ws_url(
 
 #[cfg(test)]
 mod tests {
-    use super::build_remote_launch_ws_url;
+    use super::{build_remote_launch_ws_url, BrokerAckMessage};
+
+    #[test]
+    fn launch_ack_判断を載せる() {
+        let ack = BrokerAckMessage::LaunchAck {
+            work_id: "work-1".into(),
+            status: "denied",
+            decision: Some("timed-out"),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&ack).unwrap(),
+            serde_json::json!({
+                "type": "launch-ack",
+                "workId": "work-1",
+                "status": "denied",
+                "decision": "timed-out"
+            })
+        );
+    }
 
     #[test]
     fn build_remote_launch_ws_url_httpsをwssに変換する() {
-        let url = build_remote_launch_ws_url("https://example.com/", "device-id", "secret")
-            .unwrap();
+        let url =
+            build_remote_launch_ws_url("https://example.com/", "device-id", "secret").unwrap();
 
         assert_eq!(
             url,
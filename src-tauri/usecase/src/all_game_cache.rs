@@ -125,5 +125,11 @@ impl GameMatcher for NopMatcher {
     ) -> Vec<(domain::all_game_cache::AllGameCacheOne, f32)> {
         Vec::new()
     }
+    fn explain_candidates(
+        &self,
+        _queries: &[domain::game_matcher::MatchQuery],
+    ) -> domain::game_matcher::MatchExplanation {
+        domain::game_matcher::MatchExplanation::default()
+    }
     fn update_all_game_cache(&self, _new_cache: domain::all_game_cache::AllGameCache) {}
 }
@@ -1,12 +1,18 @@
+use std::io::Write;
 use std::sync::Arc;
 
+use chrono::{Duration, Local};
 use derive_new::new;
-use domain::native_host_log::{HostLogLevel, HostLogType, NativeHostLogRow};
+use domain::native_host_log::{HostLogFilter, HostLogRetention, NativeHostLogRow};
 use domain::repository::{
     manager::RepositoryManager, native_host_log::NativeHostLogRepository, RepositoriesExt,
 };
+use serde::Serialize;
 use std::marker::PhantomData;
 
+/// エクスポートで 1 回に読むログの件数
+const EXPORT_PAGE_SIZE: i64 = 1000;
+
 #[derive(new)]
 pub struct HostLogUseCase<M, R>
 where
@@ -17,6 +23,36 @@ where
     _marker: PhantomData<R>,
 }
 
+/// エクスポートする JSONL の 1 行
+#[derive(Serialize)]
+struct HostLogExportLine<'a> {
+    id: i32,
+    level: String,
+    r#type: String,
+    message: &'a str,
+    request_id: Option<&'a str>,
+    duration_ms: Option<i64>,
+    item_count: Option<i64>,
+    error_count: Option<i64>,
+    created_at: String,
+}
+
+impl<'a> From<&'a NativeHostLogRow> for HostLogExportLine<'a> {
+    fn from(row: &'a NativeHostLogRow) -> Self {
+        Self {
+            id: row.id.value,
+            level: format!("{:?}", row.level),
+            r#type: format!("{:?}", row.r#type),
+            message: &row.message,
+            request_id: row.request_id.as_deref(),
+            duration_ms: row.duration_ms,
+            item_count: row.item_count,
+            error_count: row.error_count,
+            created_at: row.created_at.to_rfc3339(),
+        }
+    }
+}
+
 impl<M, R> HostLogUseCase<M, R>
 where
     M: RepositoryManager<R>,
@@ -26,25 +62,67 @@ where
         &self,
         limit: i64,
         offset: i64,
-        level: Option<HostLogLevel>,
-        typ: Option<HostLogType>,
+        filter: HostLogFilter,
     ) -> anyhow::Result<Vec<NativeHostLogRow>> {
         self.manager
             .run(move |repos| {
-                Box::pin(async move { repos.host_log().list_logs(limit, offset, level, typ).await })
+                Box::pin(async move { repos.host_log().list_logs(limit, offset, &filter).await })
             })
             .await
     }
 
-    pub async fn count_logs(
-        &self,
-        level: Option<HostLogLevel>,
-        typ: Option<HostLogType>,
-    ) -> anyhow::Result<i64> {
+    pub async fn count_logs(&self, filter: HostLogFilter) -> anyhow::Result<i64> {
+        self.manager
+            .run(move |repos| Box::pin(async move { repos.host_log().count_logs(&filter).await }))
+            .await
+    }
+
+    /// 保持期間を過ぎたログと、件数の上限を超えた古いログを消す。消した件数を返す
+    pub async fn apply_retention(&self, retention: HostLogRetention) -> anyhow::Result<u64> {
         self.manager
             .run(move |repos| {
-                Box::pin(async move { repos.host_log().count_logs(level, typ).await })
+                Box::pin(async move {
+                    let before = Local::now() - Duration::days(retention.max_age_days);
+                    let expired = repos.host_log().delete_older_than(before).await?;
+                    let exceeded = repos
+                        .host_log()
+                        .delete_exceeding(retention.max_rows)
+                        .await?;
+                    Ok(expired + exceeded)
+                })
             })
             .await
     }
+
+    /// 条件に合うログを新しい順に 1 行 1 件の JSON で書き出す。書き出した件数を返す
+    pub async fn export_jsonl<W: Write>(
+        &self,
+        filter: HostLogFilter,
+        mut writer: W,
+    ) -> anyhow::Result<u64> {
+        let mut exported = 0u64;
+        let mut offset = 0i64;
+        // 書き出している間に増えたログで同じ行を二度書かないよう、書いた最小の id より古いものだけ書く
+        let mut min_written_id: Option<i32> = None;
+        loop {
+            let rows = self
+                .list_logs(EXPORT_PAGE_SIZE, offset, filter.clone())
+                .await?;
+            for row in rows.iter() {
+                if min_written_id.is_some_and(|min| row.id.value >= min) {
+                    continue;
+                }
+                serde_json::to_writer(&mut writer, &HostLogExportLine::from(row))?;
+                writer.write_all(b"\n")?;
+                min_written_id = Some(row.id.value);
+                exported += 1;
+            }
+            if (rows.len() as i64) < EXPORT_PAGE_SIZE {
+                break;
+            }
+            offset += EXPORT_PAGE_SIZE;
+        }
+        writer.flush()?;
+        Ok(exported)
+    }
 }
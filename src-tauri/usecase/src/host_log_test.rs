@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::sync::Arc;
+
+    use chrono::{Duration, Local};
+    use domain::native_host_log::{
+        HostLogFilter, HostLogLevel, HostLogRetention, HostLogType, NativeHostLogRow,
+    };
+    use domain::Id;
+
+    use crate::host_log::HostLogUseCase;
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+
+    fn log(id: i32) -> NativeHostLogRow {
+        NativeHostLogRow {
+            id: Id::new(id),
+            level: HostLogLevel::Info,
+            r#type: HostLogType::Response,
+            message: format!("message {id}"),
+            request_id: Some(format!("req-{id}")),
+            duration_ms: Some(12),
+            item_count: Some(3),
+            error_count: None,
+            created_at: Local::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn apply_retention_期限切れと上限超過を削除して合計を返す() {
+        let repos = TestRepositories::default();
+        repos
+            .host_log
+            .lock()
+            .await
+            .expect_delete_older_than()
+            .withf(|before| {
+                let expected = Local::now() - Duration::days(7);
+                (expected - *before).num_seconds().abs() < 5
+            })
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(4) }));
+        repos
+            .host_log
+            .lock()
+            .await
+            .expect_delete_exceeding()
+            .withf(|keep| *keep == 100)
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(2) }));
+
+        let usecase = HostLogUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let deleted = usecase
+            .apply_retention(HostLogRetention {
+                max_age_days: 7,
+                max_rows: 100,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, 6);
+    }
+
+    #[tokio::test]
+    async fn export_jsonl_条件に合うログを1行ずつ書き出す() {
+        let repos = TestRepositories::default();
+        repos
+            .host_log
+            .lock()
+            .await
+            .expect_list_logs()
+            .withf(|_, offset, filter| {
+                *offset == 0 && filter.request_id.as_deref() == Some("req-2")
+            })
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(vec![log(2)]) }));
+
+        let usecase = HostLogUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let mut buf = Vec::new();
+        let exported = usecase
+            .export_jsonl(
+                HostLogFilter {
+                    request_id: Some("req-2".into()),
+                    ..Default::default()
+                },
+                &mut buf,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(exported, 1);
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let value: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["id"], 2);
+        assert_eq!(value["level"], "Info");
+        assert_eq!(value["type"], "Response");
+        assert_eq!(value["request_id"], "req-2");
+        assert_eq!(value["item_count"], 3);
+        assert!(value["error_count"].is_null());
+    }
+
+    #[tokio::test]
+    async fn export_jsonl_ページの途中で増えたログを重複して書かない() {
+        let repos = TestRepositories::default();
+        repos
+            .host_log
+            .lock()
+            .await
+            .expect_list_logs()
+            .withf(|_, offset, _| *offset == 0)
+            .times(1)
+            .returning(|limit, _, _| {
+                Box::pin(async move { Ok((0..limit as i32).map(|i| log(2000 - i)).collect()) })
+            });
+        // 1 ページ目を読んだあとに 1 件増え、2 ページ目の先頭が 1 ページ目の末尾と重なる
+        repos
+            .host_log
+            .lock()
+            .await
+            .expect_list_logs()
+            .withf(|_, offset, _| *offset > 0)
+            .times(1)
+            .returning(|limit, _, _| {
+                Box::pin(async move { Ok((0..limit as i32).map(|i| log(1001 - i)).collect()) })
+            });
+        repos
+            .host_log
+            .lock()
+            .await
+            .expect_list_logs()
+            .returning(|_, _, _| Box::pin(async { Ok(vec![]) }));
+
+        let usecase = HostLogUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let mut buf = Vec::new();
+        let exported = usecase
+            .export_jsonl(HostLogFilter::default(), &mut buf)
+            .await
+            .unwrap();
+
+        // id 2..=2000 がちょうど一度ずつ書き出される
+        assert_eq!(exported, 1999);
+        assert_eq!(String::from_utf8(buf).unwrap().lines().count(), 1999);
+    }
+}
@@ -4,6 +4,8 @@ use derive_new::new;
 use domain::repository::{
     manager::RepositoryManager, save_image_queue::ImageSaveQueueRepository as _, RepositoriesExt,
 };
+use domain::save_image_queue::ImageSaveQueueRow;
+use domain::Id;
 use std::marker::PhantomData;
 
 #[derive(new)]
@@ -30,4 +32,29 @@ where
             .run(|repos| Box::pin(async move { repos.image_queue().list(unfinished, limit).await }))
             .await
     }
+
+    pub async fn count_unfinished(&self) -> anyhow::Result<i64> {
+        self.manager
+            .run(|repos| Box::pin(async move { repos.image_queue().count(true).await }))
+            .await
+    }
+
+    pub async fn list_failed(&self, limit: i64) -> anyhow::Result<Vec<ImageSaveQueueRow>> {
+        self.manager
+            .run(|repos| Box::pin(async move { repos.image_queue().list_failed(limit).await }))
+            .await
+    }
+
+    /// デッドレターを再投入する。処理自体は次の drain で行われる
+    pub async fn requeue_failed(&self, ids: Vec<Id<ImageSaveQueueRow>>) -> anyhow::Result<u64> {
+        self.manager
+            .run(|repos| Box::pin(async move { repos.image_queue().requeue_failed(ids).await }))
+            .await
+    }
+
+    pub async fn discard_failed(&self, ids: Vec<Id<ImageSaveQueueRow>>) -> anyhow::Result<u64> {
+        self.manager
+            .run(|repos| Box::pin(async move { repos.image_queue().discard_failed(ids).await }))
+            .await
+    }
 }
@@ -0,0 +1,223 @@
+//! 作品ごとの起動プロファイルの編集
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use derive_new::new;
+use domain::launch_profile::{LaunchEnvVar, LaunchProfile, NewLaunchProfile};
+use domain::repository::{
+    launch_profile::LaunchProfileRepository as _, manager::RepositoryManager, RepositoriesExt,
+};
+use domain::{works::Work, Id, StrId};
+
+#[derive(new)]
+pub struct LaunchProfileUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    #[new(default)]
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> LaunchProfileUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    pub async fn list(&self, work_id: String) -> anyhow::Result<Vec<LaunchProfile>> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move { repos.launch_profile().list_by_work_id(work_id).await })
+            })
+            .await
+    }
+
+    /// `id` がなければ作る。作品の最初のプロファイルは既定にする
+    pub async fn save(
+        &self,
+        id: Option<i32>,
+        profile: NewLaunchProfile,
+    ) -> anyhow::Result<Id<LaunchProfile>> {
+        let profile = normalize(profile);
+        profile.validate()?;
+
+        self.manager
+            .run_in_transaction(|repos| {
+                let mut profile = profile.clone();
+                Box::pin(async move {
+                    let existing = repos
+                        .launch_profile()
+                        .list_by_work_id(profile.work_id.clone())
+                        .await?;
+                    match id {
+                        Some(id) => {
+                            let current = existing
+                                .iter()
+                                .find(|p| p.id.value == id)
+                                .ok_or_else(|| anyhow::anyhow!("launch profile not found: {id}"))?;
+                            // 既定を外すと既定がなくなるので、外すのは他を既定にしたときだけ
+                            profile.is_default |= current.is_default;
+                            repos.launch_profile().update(Id::new(id), &profile).await?;
+                            Ok(Id::new(id))
+                        }
+                        None => {
+                            profile.is_default |= existing.is_empty();
+                            repos.launch_profile().insert(&profile).await
+                        }
+                    }
+                })
+            })
+            .await
+    }
+
+    /// 既定を消したときは残りの先頭を既定にする
+    pub async fn delete(&self, id: i32) -> anyhow::Result<()> {
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    let Some(profile) = repos.launch_profile().find_by_id(Id::new(id)).await?
+                    else {
+                        return Ok(());
+                    };
+                    repos.launch_profile().delete(profile.id.clone()).await?;
+                    if profile.is_default {
+                        let rest = repos
+                            .launch_profile()
+                            .list_by_work_id(profile.work_id.clone())
+                            .await?;
+                        if let Some(next) = rest.into_iter().next() {
+                            let next_id = next.id.clone();
+                            repos
+                                .launch_profile()
+                                .update(next_id, &into_new(next, true))
+                                .await?;
+                        }
+                    }
+                    Ok(())
+                })
+            })
+            .await
+    }
+
+    pub async fn set_default(&self, id: i32) -> anyhow::Result<()> {
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    let profile = repos
+                        .launch_profile()
+                        .find_by_id(Id::new(id))
+                        .await?
+                        .ok_or_else(|| anyhow::anyhow!("launch profile not found: {id}"))?;
+                    let profile_id = profile.id.clone();
+                    repos
+                        .launch_profile()
+                        .update(profile_id, &into_new(profile, true))
+                        .await
+                })
+            })
+            .await
+    }
+}
+
+/// 起動時に管理者権限を指定されたら、既定のプロファイルに覚えておく。
+/// プロファイルがなければ権限だけの既定のプロファイルを作る
+pub(crate) async fn remember_run_as_admin<R: RepositoriesExt>(
+    repos: &R,
+    work_id: StrId<Work>,
+    profile: Option<LaunchProfile>,
+    run_as_admin: bool,
+) -> anyhow::Result<LaunchProfile> {
+    match profile {
+        Some(profile) if profile.run_as_admin == run_as_admin => Ok(profile),
+        Some(profile) => {
+            let profile_id = profile.id.clone();
+            let is_default = profile.is_default;
+            let mut updated = into_new(profile, is_default);
+            updated.run_as_admin = run_as_admin;
+            updated.validate()?;
+            repos
+                .launch_profile()
+                .update(profile_id.clone(), &updated)
+                .await?;
+            Ok(from_new(profile_id, updated))
+        }
+        None => {
+            let new_profile = NewLaunchProfile {
+                work_id,
+                name: DEFAULT_PROFILE_NAME.to_string(),
+                arguments: None,
+                working_dir: None,
+                env: vec![],
+                run_as_admin,
+                wrapper: None,
+                is_default: true,
+            };
+            let id = repos.launch_profile().insert(&new_profile).await?;
+            Ok(from_new(id, new_profile))
+        }
+    }
+}
+
+const DEFAULT_PROFILE_NAME: &str = "既定";
+
+fn normalize(profile: NewLaunchProfile) -> NewLaunchProfile {
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    NewLaunchProfile {
+        work_id: profile.work_id,
+        name: profile.name.trim().to_string(),
+        arguments: non_empty(profile.arguments),
+        working_dir: non_empty(profile.working_dir),
+        env: profile
+            .env
+            .into_iter()
+            .map(|var| LaunchEnvVar {
+                key: var.key.trim().to_string(),
+                value: var.value,
+            })
+            .filter(|var| !var.key.is_empty())
+            .collect(),
+        run_as_admin: profile.run_as_admin,
+        wrapper: profile.wrapper.and_then(|wrapper| {
+            let path = wrapper.path.trim().to_string();
+            (!path.is_empty()).then(|| domain::launch_profile::LaunchWrapper {
+                path,
+                arguments: non_empty(wrapper.arguments),
+            })
+        }),
+        is_default: profile.is_default,
+    }
+}
+
+fn into_new(profile: LaunchProfile, is_default: bool) -> NewLaunchProfile {
+    NewLaunchProfile {
+        work_id: profile.work_id,
+        name: profile.name,
+        arguments: profile.arguments,
+        working_dir: profile.working_dir,
+        env: profile.env,
+        run_as_admin: profile.run_as_admin,
+        wrapper: profile.wrapper,
+        is_default,
+    }
+}
+
+fn from_new(id: Id<LaunchProfile>, profile: NewLaunchProfile) -> LaunchProfile {
+    LaunchProfile {
+        id,
+        work_id: profile.work_id,
+        name: profile.name,
+        arguments: profile.arguments,
+        working_dir: profile.working_dir,
+        env: profile.env,
+        run_as_admin: profile.run_as_admin,
+        wrapper: profile.wrapper,
+        is_default: profile.is_default,
+    }
+}
@@ -0,0 +1,390 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use domain::file::LnkMetadata;
+    use domain::launch_profile::{LaunchEnvVar, LaunchProfile, LaunchWrapper, NewLaunchProfile};
+    use domain::repository::work_lnk::WorkLnk;
+    use domain::service::work_registration::MockWorkRegistrationService;
+    use domain::windows::shell_link::MockShellLink;
+    use domain::wine::{WineConfig, WineRunner};
+    use domain::{Id, StrId};
+
+    use crate::launch_profile::LaunchProfileUseCase;
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+    use crate::windowsmock::MockWindowsExtMock;
+    use crate::work::WorkUseCase;
+
+    fn new_profile(name: &str) -> NewLaunchProfile {
+        NewLaunchProfile {
+            work_id: StrId::new("w".into()),
+            name: name.into(),
+            arguments: None,
+            working_dir: None,
+            env: vec![],
+            run_as_admin: false,
+            wrapper: None,
+            is_default: false,
+        }
+    }
+
+    fn profile(id: i32, new_profile: NewLaunchProfile) -> LaunchProfile {
+        LaunchProfile {
+            id: Id::new(id),
+            work_id: new_profile.work_id,
+            name: new_profile.name,
+            arguments: new_profile.arguments,
+            working_dir: new_profile.working_dir,
+            env: new_profile.env,
+            run_as_admin: new_profile.run_as_admin,
+            wrapper: new_profile.wrapper,
+            is_default: new_profile.is_default,
+        }
+    }
+
+    fn profile_usecase(
+        repos: &TestRepositories,
+    ) -> LaunchProfileUseCase<TestRepositoryManager, TestRepositories> {
+        LaunchProfileUseCase::new(Arc::new(TestRepositoryManager::new(repos.clone())))
+    }
+
+    fn work_usecase(
+        repos: &TestRepositories,
+        shell_link: MockShellLink,
+    ) -> WorkUseCase<
+        TestRepositoryManager,
+        TestRepositories,
+        MockWindowsExtMock,
+        MockWorkRegistrationService,
+    > {
+        let mut windows = MockWindowsExtMock::new();
+        windows.expect_shell_link().return_const(shell_link);
+        WorkUseCase::new(
+            Arc::new(TestRepositoryManager::new(repos.clone())),
+            Arc::new(windows),
+            Arc::new(MockWorkRegistrationService::new()),
+        )
+    }
+
+    async fn expect_lnk(repos: &TestRepositories, lnk_path: &str) {
+        let lnk_path = lnk_path.to_string();
+        repos
+            .work_lnk
+            .lock()
+            .await
+            .expect_find_by_id()
+            .returning(move |id| {
+                let lnk = WorkLnk {
+                    id,
+                    work_id: StrId::new("w".into()),
+                    lnk_path: lnk_path.clone(),
+                };
+                Box::pin(async move { Ok(Some(lnk)) })
+            });
+        repos
+            .work
+            .lock()
+            .await
+            .expect_update_last_play_at_by_work_id()
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+    }
+
+    async fn expect_wine(repos: &TestRepositories, config: Option<WineConfig>) {
+        repos
+            .wine_config
+            .lock()
+            .await
+            .expect_find_by_work_id()
+            .returning(move |_| {
+                let config = config.clone();
+                Box::pin(async move { Ok(config) })
+            });
+    }
+
+    #[tokio::test]
+    async fn save_作品の最初のプロファイルは既定にする() {
+        let repos = TestRepositories::default();
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_list_by_work_id()
+            .returning(|_| Box::pin(async { Ok(vec![]) }));
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_insert()
+            .withf(|p| {
+                p.is_default
+                    && p.name == "LE"
+                    && p.arguments.is_none()
+                    && p.env.len() == 1
+                    && p.env[0].key == "LANG"
+            })
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(Id::new(1)) }));
+
+        let id = profile_usecase(&repos)
+            .save(
+                None,
+                NewLaunchProfile {
+                    arguments: Some("  ".into()),
+                    env: vec![
+                        LaunchEnvVar {
+                            key: " LANG ".into(),
+                            value: "ja_JP".into(),
+                        },
+                        LaunchEnvVar {
+                            key: "".into(),
+                            value: "ignored".into(),
+                        },
+                    ],
+                    ..new_profile(" LE ")
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(id.value, 1);
+    }
+
+    #[tokio::test]
+    async fn save_管理者実行と環境変数の併用は保存しない() {
+        let repos = TestRepositories::default();
+        let result = profile_usecase(&repos)
+            .save(
+                None,
+                NewLaunchProfile {
+                    run_as_admin: true,
+                    env: vec![LaunchEnvVar {
+                        key: "LANG".into(),
+                        value: "ja_JP".into(),
+                    }],
+                    ..new_profile("admin")
+                },
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn delete_既定を消すと残りの先頭を既定にする() {
+        let repos = TestRepositories::default();
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_find_by_id()
+            .returning(|id| {
+                let p = profile(
+                    id.value,
+                    NewLaunchProfile {
+                        is_default: true,
+                        ..new_profile("default")
+                    },
+                );
+                Box::pin(async move { Ok(Some(p)) })
+            });
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_delete()
+            .withf(|id| id.value == 1)
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_list_by_work_id()
+            .returning(|_| Box::pin(async { Ok(vec![profile(2, new_profile("rest"))]) }));
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_update()
+            .withf(|id, p| id.value == 2 && p.is_default)
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+
+        profile_usecase(&repos).delete(1).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn launch_work_プロファイルがなければショートカットを開く() {
+        let repos = TestRepositories::default();
+        expect_lnk(&repos, "C:/links/w.lnk").await;
+        expect_wine(&repos, None).await;
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_find_default_by_work_id()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        let mut shell_link = MockShellLink::new();
+        shell_link
+            .expect_execute_lnk()
+            .withf(|path, admin| path == "C:/links/w.lnk" && !admin)
+            .times(1)
+            .returning(|_, _| Ok(Some(0)));
+
+        let pid = work_usecase(&repos, shell_link)
+            .launch_work(1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(pid, Some(0));
+    }
+
+    #[tokio::test]
+    async fn launch_work_既定のプロファイルのラッパーでショートカットの引数ごと起動する() {
+        let repos = TestRepositories::default();
+        expect_lnk(&repos, "C:/links/w.lnk").await;
+        expect_wine(&repos, None).await;
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_find_default_by_work_id()
+            .returning(|_| {
+                let p = profile(
+                    1,
+                    NewLaunchProfile {
+                        arguments: Some("-window".into()),
+                        wrapper: Some(LaunchWrapper {
+                            path: "C:/tools/LEProc.exe".into(),
+                            arguments: Some("-run {target} {args}".into()),
+                        }),
+                        is_default: true,
+                        ..new_profile("LE")
+                    },
+                );
+                Box::pin(async move { Ok(Some(p)) })
+            });
+        let mut shell_link = MockShellLink::new();
+        shell_link.expect_get_lnk_metadatas().returning(|paths| {
+            Ok(paths
+                .into_iter()
+                .map(|path| {
+                    (
+                        path,
+                        LnkMetadata {
+                            path: "C:/games/w/game.exe".into(),
+                            arguments: Some("-nolauncher".into()),
+                            working_dir: Some("C:/games/w/bin".into()),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>())
+        });
+        shell_link
+            .expect_execute_command()
+            .withf(|command| {
+                command.program == "C:/tools/LEProc.exe"
+                    && command.parameters.as_deref()
+                        == Some("-run \"C:/games/w/game.exe\" -nolauncher -window")
+                    && command.working_dir.as_deref() == Some("C:/games/w/bin")
+            })
+            .times(1)
+            .returning(|_| Ok(Some(0)));
+
+        work_usecase(&repos, shell_link)
+            .launch_work(1, None, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn launch_work_権限を指定するとプロファイルに覚える() {
+        let repos = TestRepositories::default();
+        expect_lnk(&repos, "C:/links/w.lnk").await;
+        expect_wine(&repos, None).await;
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_find_default_by_work_id()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_insert()
+            .withf(|p| p.is_default && p.run_as_admin)
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(Id::new(1)) }));
+        let mut shell_link = MockShellLink::new();
+        shell_link
+            .expect_execute_lnk()
+            .withf(|_, admin| *admin)
+            .times(1)
+            .returning(|_, _| Ok(Some(0)));
+
+        work_usecase(&repos, shell_link)
+            .launch_work(1, None, Some(true))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn launch_work_wineの設定があればランナー越しにリンク先を起動する() {
+        let repos = TestRepositories::default();
+        expect_lnk(&repos, "/links/w.lnk").await;
+        expect_wine(
+            &repos,
+            Some(WineConfig {
+                work_id: StrId::new("w".into()),
+                runner: WineRunner::Umu,
+                runner_path: None,
+                prefix: Some("/prefixes/w".into()),
+                env: vec![],
+            }),
+        )
+        .await;
+        repos
+            .launch_profile
+            .lock()
+            .await
+            .expect_find_default_by_work_id()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        let mut shell_link = MockShellLink::new();
+        shell_link.expect_get_lnk_metadatas().returning(|paths| {
+            Ok(paths
+                .into_iter()
+                .map(|path| {
+                    (
+                        path,
+                        LnkMetadata {
+                            path: "/games/w/game.exe".into(),
+                            arguments: Some("-nolauncher".into()),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>())
+        });
+        shell_link
+            .expect_execute_command()
+            .withf(|command| {
+                command.program == "/games/w/game.exe"
+                    && command.parameters.as_deref() == Some("-nolauncher")
+                    && command.working_dir.as_deref() == Some("/games/w")
+                    && command
+                        .wine
+                        .as_ref()
+                        .is_some_and(|wine| wine.runner == WineRunner::Umu)
+            })
+            .times(1)
+            .returning(|_| Ok(Some(4242)));
+
+        let pid = work_usecase(&repos, shell_link)
+            .launch_work(1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(pid, Some(4242));
+    }
+}
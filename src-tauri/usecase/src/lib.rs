@@ -9,21 +9,58 @@ mod extension_manager_test;
 pub mod file;
 pub mod game_identifier;
 pub mod host_log;
+#[cfg(test)]
+mod host_log_test;
 pub mod image_queue;
+pub mod launch_profile;
+#[cfg(test)]
+mod launch_profile_test;
+pub mod native_host_status;
+#[cfg(test)]
+mod native_host_status_test;
 pub mod native_host_sync;
+pub mod native_host_sync_session;
+#[cfg(test)]
+mod native_host_sync_session_test;
 #[cfg(test)]
 mod native_host_sync_test;
 #[cfg(test)]
 mod native_messaging_mock;
+pub mod play_status;
+#[cfg(test)]
+mod play_status_test;
 pub mod process;
+pub mod remote_launch;
+#[cfg(test)]
+mod remote_launch_test;
 pub mod remote_share;
+pub mod remote_share_sync;
+#[cfg(test)]
+mod remote_share_sync_test;
 #[cfg(test)]
 mod repositorymock;
+pub mod save_backup;
+#[cfg(test)]
+mod save_backup_test;
+pub mod store_library;
+#[cfg(test)]
+mod store_library_test;
 #[cfg(test)]
 mod windowsmock;
+pub mod wine_config;
+#[cfg(test)]
+mod wine_config_test;
 pub mod work;
+pub mod work_duplicate;
+#[cfg(test)]
+mod work_duplicate_test;
 pub mod work_link_pending_exe;
+pub mod work_override;
+#[cfg(test)]
+mod work_override_test;
 pub mod work_pipeline;
 #[cfg(test)]
 mod work_pipeline_test;
 pub mod work_thumbnail;
+#[cfg(test)]
+mod work_thumbnail_test;
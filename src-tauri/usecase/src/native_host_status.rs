@@ -0,0 +1,97 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use chrono::{Duration, Local};
+use derive_new::new;
+use domain::extension::{ExtensionConfig, NativeHostSyncStats};
+use domain::native_host_log::{HostLogFilter, HostLogType, SyncCompletedLog};
+use domain::repository::{
+    extension_config::ExtensionConfigRepository,
+    manager::RepositoryManager,
+    native_host_log::NativeHostLogRepository,
+    works::{DlsiteWorkRepository, DmmWorkRepository},
+    RepositoriesExt,
+};
+
+/// 接続中とみなす拡張機能を集計する期間
+const CONNECTED_EXTENSION_WINDOW_DAYS: i64 = 30;
+/// 集計で遡る同期完了ログの上限
+const SYNC_LOG_SCAN_LIMIT: i64 = 1000;
+
+#[derive(new)]
+pub struct NativeHostStatusUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> NativeHostStatusUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    /// 同期完了ログと DMM / DLsite 作品数から同期の統計を組み立てる
+    pub async fn get_sync_stats(&self) -> anyhow::Result<NativeHostSyncStats> {
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    let logs = repos
+                        .host_log()
+                        .list_logs(
+                            SYNC_LOG_SCAN_LIMIT,
+                            0,
+                            &HostLogFilter {
+                                r#type: Some(HostLogType::SyncGamesCompleted),
+                                ..Default::default()
+                            },
+                        )
+                        .await?;
+                    let dmm_count = repos.dmm_work().count().await?;
+                    let dlsite_count = repos.dlsite_work().count().await?;
+
+                    let since = Local::now() - Duration::days(CONNECTED_EXTENSION_WINDOW_DAYS);
+                    let mut extension_ids: Vec<String> = Vec::new();
+                    for log in logs.iter().filter(|log| log.created_at >= since) {
+                        let Some(parsed) = SyncCompletedLog::parse(&log.message) else {
+                            continue;
+                        };
+                        if !extension_ids.contains(&parsed.extension_id) {
+                            extension_ids.push(parsed.extension_id);
+                        }
+                    }
+
+                    Ok(NativeHostSyncStats {
+                        // list_logs は新しい順
+                        last_sync_at: logs.first().map(|log| log.created_at),
+                        total_synced: u32::try_from(dmm_count + dlsite_count).unwrap_or(u32::MAX),
+                        extension_ids,
+                    })
+                })
+            })
+            .await
+    }
+
+    /// 保存済みの拡張機能設定。未保存なら既定値
+    pub async fn get_config(&self) -> anyhow::Result<ExtensionConfig> {
+        let saved = self
+            .manager
+            .run(|repos| Box::pin(async move { repos.extension_config().get().await }))
+            .await?;
+        Ok(saved.unwrap_or_default())
+    }
+
+    /// 正規化して保存し、保存した値を返す
+    pub async fn set_config(&self, config: ExtensionConfig) -> anyhow::Result<ExtensionConfig> {
+        let normalized = config.normalized()?;
+        self.manager
+            .run(|repos| {
+                let normalized = normalized.clone();
+                Box::pin(async move { repos.extension_config().upsert(&normalized).await })
+            })
+            .await?;
+        Ok(normalized)
+    }
+}
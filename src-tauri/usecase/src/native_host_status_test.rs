@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::sync::Arc;
+
+    use chrono::{Duration, Local};
+    use domain::extension::{ExtensionConfig, NativeHostSyncStats};
+    use domain::native_host_log::{HostLogLevel, HostLogType, NativeHostLogRow, SyncCompletedLog};
+    use domain::Id;
+
+    use crate::native_host_status::NativeHostStatusUseCase;
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+
+    fn sync_log(extension_id: &str, days_ago: i64) -> NativeHostLogRow {
+        NativeHostLogRow {
+            id: Id::new(0),
+            level: HostLogLevel::Info,
+            r#type: HostLogType::SyncGamesCompleted,
+            message: SyncCompletedLog {
+                store: "dmm".into(),
+                extension_id: extension_id.into(),
+                success_count: 1,
+                new_count: 0,
+            }
+            .to_message(),
+            request_id: None,
+            duration_ms: None,
+            item_count: None,
+            error_count: None,
+            created_at: Local::now() - Duration::days(days_ago),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_sync_stats_ログと作品数から集計する() {
+        let repos = TestRepositories::default();
+        let logs = vec![
+            sync_log("ext-a", 0),
+            sync_log("ext-b", 1),
+            sync_log("ext-a", 2),
+            sync_log("ext-old", 60),
+        ];
+        let newest = logs[0].created_at;
+        repos
+            .host_log
+            .lock()
+            .await
+            .expect_list_logs()
+            .withf(|_, offset, filter| {
+                *offset == 0
+                    && filter.level.is_none()
+                    && matches!(filter.r#type, Some(HostLogType::SyncGamesCompleted))
+            })
+            .returning(move |_, _, _| {
+                let logs = logs.clone();
+                Box::pin(async move { Ok(logs) })
+            });
+        repos
+            .dmm_work
+            .lock()
+            .await
+            .expect_count()
+            .returning(|| Box::pin(async { Ok(3) }));
+        repos
+            .dlsite_work
+            .lock()
+            .await
+            .expect_count()
+            .returning(|| Box::pin(async { Ok(4) }));
+
+        let usecase = NativeHostStatusUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let stats = usecase.get_sync_stats().await.unwrap();
+
+        assert_eq!(stats.last_sync_at, Some(newest));
+        assert_eq!(stats.total_synced, 7);
+        assert_eq!(
+            stats.extension_ids,
+            vec!["ext-a".to_string(), "ext-b".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_sync_stats_同期履歴がなければlast_syncはnone() {
+        let repos = TestRepositories::default();
+        repos
+            .host_log
+            .lock()
+            .await
+            .expect_list_logs()
+            .returning(|_, _, _| Box::pin(async { Ok(vec![]) }));
+        repos
+            .dmm_work
+            .lock()
+            .await
+            .expect_count()
+            .returning(|| Box::pin(async { Ok(0) }));
+        repos
+            .dlsite_work
+            .lock()
+            .await
+            .expect_count()
+            .returning(|| Box::pin(async { Ok(0) }));
+
+        let usecase = NativeHostStatusUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        assert_eq!(
+            usecase.get_sync_stats().await.unwrap(),
+            NativeHostSyncStats::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn set_config_正規化した値を保存する() {
+        let repos = TestRepositories::default();
+        repos
+            .extension_config
+            .lock()
+            .await
+            .expect_upsert()
+            .withf(|config| config.allowed_domains == vec!["www.dlsite.com".to_string()])
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let usecase = NativeHostStatusUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let saved = usecase
+            .set_config(ExtensionConfig {
+                auto_sync: false,
+                allowed_domains: vec![" WWW.DLsite.com ".into(), "www.dlsite.com".into()],
+                sync_interval_minutes: 10,
+                debug_mode: true,
+            })
+            .await
+            .unwrap();
+        assert_eq!(saved.allowed_domains, vec!["www.dlsite.com".to_string()]);
+        assert_eq!(saved.sync_interval_minutes, 10);
+    }
+
+    #[tokio::test]
+    async fn set_config_不正な値は保存しない() {
+        let repos = TestRepositories::default();
+        repos.extension_config.lock().await.expect_upsert().times(0);
+
+        let usecase = NativeHostStatusUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let result = usecase
+            .set_config(ExtensionConfig {
+                sync_interval_minutes: 0,
+                ..Default::default()
+            })
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn get_config_未保存なら既定値() {
+        let repos = TestRepositories::default();
+        repos
+            .extension_config
+            .lock()
+            .await
+            .expect_get()
+            .returning(|| Box::pin(async { Ok(None) }));
+
+        let usecase = NativeHostStatusUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        assert_eq!(
+            usecase.get_config().await.unwrap(),
+            ExtensionConfig::default()
+        );
+    }
+}
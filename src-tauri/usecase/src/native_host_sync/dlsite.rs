@@ -2,7 +2,6 @@ use super::*;
 use domain::service::work_registration::{
     ImageApply, ImageSource, ImageStrategy, UniqueWorkKey, WorkInsert,
 };
-use std::collections::HashSet;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct DlsiteKey {
@@ -35,6 +34,18 @@ where
             }
         }
 
+        let store_ids = unique_games
+            .iter()
+            .map(|game| game.store_id.clone())
+            .collect::<Vec<_>>();
+        let existing = self
+            .find_existing_store_ids(SyncStore::Dlsite, store_ids.clone())
+            .await?;
+        let unchanged_count = unique_games
+            .iter()
+            .filter(|game| existing.contains(&game.store_id))
+            .count() as u32;
+
         let mut requests: Vec<domain::service::work_registration::WorkRegistrationRequest> =
             Vec::new();
         for game in unique_games.into_iter() {
@@ -99,9 +110,11 @@ where
 
         // WorkRegistrationService で一括登録
         let results = self.registrar.register(requests).await?;
+        self.mark_seen(SyncStore::Dlsite, store_ids).await?;
         Ok(SyncGamesSummary {
             success_count: results.len() as u32,
             new_count: results.iter().filter(|result| result.is_new_work).count() as u32,
+            unchanged_count,
         })
     }
 }
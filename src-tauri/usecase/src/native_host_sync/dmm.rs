@@ -2,7 +2,6 @@ use super::*;
 use domain::service::work_registration::{
     ImageApply, ImageSource, ImageStrategy, UniqueWorkKey, WorkInsert,
 };
-use std::collections::HashSet;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct DmmKey {
@@ -32,6 +31,7 @@ where
     /// - 親 pack は作品として登録せず、子作品の登録時に親 pack の DMM キーをそのまま保存する
     /// - `egs: Some` の場合、EGS に紐づく要素を用意・更新した上で DMM マッピングを upsert
     /// - `egs: None` の場合、空要素を採番し DMM マッピングのみ upsert
+    /// - 受け取った store_id は最終確認日時を更新し、欠落の印を外す
     pub async fn sync_dmm_games(
         &self,
         games: Vec<DmmSyncGameParam>,
@@ -48,6 +48,18 @@ where
 
         log::info!("sync_dmm_games unique count: {}", unique_games.len());
 
+        let store_ids = unique_games
+            .iter()
+            .map(|game| game.store_id.clone())
+            .collect::<Vec<_>>();
+        let existing = self
+            .find_existing_store_ids(SyncStore::Dmm, store_ids.clone())
+            .await?;
+        let unchanged_count = unique_games
+            .iter()
+            .filter(|game| existing.contains(&game.store_id))
+            .count() as u32;
+
         let requests = unique_games
             .iter()
             .map(|game| {
@@ -93,10 +105,12 @@ where
                     })
                 };
 
-                let parent_pack_dmm_key = game.parent_pack.as_ref().map(|parent| domain::work_parent_pack::ParentPackKey {
-                    store_id: parent.store_id.clone(),
-                    category: parent.category.clone(),
-                    subcategory: parent.subcategory.clone(),
+                let parent_pack_dmm_key = game.parent_pack.as_ref().map(|parent| {
+                    domain::work_parent_pack::ParentPackKey {
+                        store_id: parent.store_id.clone(),
+                        category: parent.category.clone(),
+                        subcategory: parent.subcategory.clone(),
+                    }
                 });
 
                 domain::service::work_registration::WorkRegistrationRequest {
@@ -118,9 +132,11 @@ where
         } else {
             self.registrar.register(requests).await?
         };
+        self.mark_seen(SyncStore::Dmm, store_ids).await?;
         Ok(SyncGamesSummary {
             success_count: results.len() as u32,
             new_count: results.iter().filter(|result| result.is_new_work).count() as u32,
+            unchanged_count,
         })
     }
 }
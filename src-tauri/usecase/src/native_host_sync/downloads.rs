@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use domain::{
+    game_engine::{detect_engine, DetectedEngine, GameEngine},
     game_matcher::config::{INSTALL_EXCLUDE_DIR_NAMES, INSTALL_HELPER_NAMES, INSTALL_PRIORITY_NAMES},
     repository::{
         manager::RepositoryManager,
@@ -23,11 +24,15 @@ use domain::{
 struct InstallCandidateRank {
     kind_score: i32,
     exact_name_score: i32,
+    engine_score: i32,
     helper_score: i32,
     stem_len: Reverse<usize>,
     path: Reverse<String>,
 }
 
+/// エンジンの目印はゲーム本体のフォルダから数階層以内にあるので、そこまでだけ見る
+const ENGINE_DETECT_MAX_DEPTH: usize = 4;
+
 pub struct DownloadsUseCase<U, R, FS, WL>
 where
     U: RepositoryManager<R> + Send + Sync + 'static,
@@ -265,6 +270,8 @@ where
             }
         };
 
+        let detected = self.detect_install_engine(install_dir);
+
         // 変換: 列挙結果から、主となる実行ファイル/ショートカットを 1 本選ぶ
         let mut best_task: Option<WorkLinkTask> = None;
         let mut best_rank: Option<InstallCandidateRank> = None;
@@ -284,7 +291,8 @@ where
             if Self::is_excluded_install_candidate(&task.src) {
                 continue;
             }
-            let rank = Self::rank_install_candidate(install_dir, &task.kind, &task.src);
+            let rank =
+                Self::rank_install_candidate(install_dir, &task.kind, &task.src, detected.as_ref());
             if best_rank.as_ref().map_or(true, |current| rank > *current) {
                 best_rank = Some(rank);
                 best_task = Some(task);
@@ -300,17 +308,45 @@ where
             return Ok(());
         };
 
-        self.record_install_metadata(work_id.clone(), &task.src).await;
+        self.record_install_metadata(work_id.clone(), &task.src, detected.map(|d| d.engine))
+            .await;
 
         // 実行: リンク作成を委譲
         self.linker.ensure_links(vec![task]).await?;
         Ok(())
     }
 
+    /// インストール先のファイル構成からエンジンを見分ける。列挙に失敗したら None
+    fn detect_install_engine(&self, install_dir: &Path) -> Option<DetectedEngine> {
+        let files = match self.fs.list_files(install_dir, ENGINE_DETECT_MAX_DEPTH) {
+            Ok(files) => files,
+            Err(e) => {
+                log::warn!(
+                    "failed to list files for engine detection: {} ({})",
+                    install_dir.display(),
+                    e
+                );
+                return None;
+            }
+        };
+        let relative = files
+            .iter()
+            .filter_map(|file| Self::relative_lower_path(install_dir, file))
+            .collect::<Vec<_>>();
+        detect_engine(&relative)
+    }
+
+    /// `detect_engine` に渡す形（`/` 区切り・小文字）の相対パス
+    fn relative_lower_path(install_dir: &Path, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(install_dir).ok()?;
+        Some(relative.to_string_lossy().replace('\\', "/").to_lowercase())
+    }
+
     fn rank_install_candidate(
         install_dir: &Path,
         kind: &domain::scan::CandidateKind,
         path: &Path,
+        detected: Option<&DetectedEngine>,
     ) -> InstallCandidateRank {
         let kind_score = match kind {
             domain::scan::CandidateKind::Exe => 2,
@@ -345,6 +381,7 @@ where
             .any(|name| file_name == *name);
         let file_name_is_helper = INSTALL_HELPER_NAMES
             .iter()
+            .chain(detected.map_or(&[][..], |d| d.engine.helper_exe_names()))
             .any(|name| file_name == *name);
 
         // 主exeは「インストール先ディレクトリ名」またはその直下の親フォルダ名と
@@ -364,6 +401,24 @@ where
             0
         };
 
+        // エンジンがわかれば、目印のあるフォルダ直下の exe を優先し、
+        // エンジンから起動 exe の名前までわかるものはさらに優先する。
+        let engine_score = match detected {
+            Some(detected) => {
+                let parent_dir = path
+                    .parent()
+                    .and_then(|parent| Self::relative_lower_path(install_dir, parent));
+                if parent_dir.as_deref() != Some(detected.base_dir.as_str()) {
+                    0
+                } else if detected.main_exe_stems.contains(&stem) {
+                    2
+                } else {
+                    1
+                }
+            }
+            None => 0,
+        };
+
         let helper_penalty = if file_name_is_helper {
             3
         } else {
@@ -373,6 +428,7 @@ where
         InstallCandidateRank {
             kind_score,
             exact_name_score,
+            engine_score,
             helper_score: -helper_penalty,
             stem_len: Reverse(stem.len()),
             path: Reverse(path_string),
@@ -387,7 +443,12 @@ where
             .any(|name| INSTALL_EXCLUDE_DIR_NAMES.iter().any(|exclude| name == *exclude))
     }
 
-    async fn record_install_metadata(&self, work_id: StrId<domain::works::Work>, original_path: &Path) {
+    async fn record_install_metadata(
+        &self,
+        work_id: StrId<domain::works::Work>,
+        original_path: &Path,
+        engine: Option<GameEngine>,
+    ) {
         if let Ok(meta) = std::fs::metadata(original_path) {
             let created = meta.created().ok();
             let modified = meta.modified().ok();
@@ -410,14 +471,22 @@ where
                         Box::pin(async move {
                             repos
                                 .work()
-                                .update_install_by_work_id(work_id, best_dt_local, original_path)
+                                .update_install_by_work_id(
+                                    work_id.clone(),
+                                    best_dt_local,
+                                    original_path,
+                                )
+                                .await?;
+                            repos
+                                .work()
+                                .update_install_engine_by_work_id(work_id, engine)
                                 .await
                         })
                     })
                     .await
                 {
                     log::warn!(
-                        "Failed to update install_at/original_path for work_id={}: {}",
+                        "Failed to update install_at/original_path/engine for work_id={}: {}",
                         work_id_value,
                         e
                     );
@@ -472,6 +541,12 @@ mod tests {
                 assert!(original_path.ends_with("game.exe"));
                 Box::pin(async { Ok::<_, anyhow::Error>(()) })
             });
+            work_repo
+                .expect_update_install_engine_by_work_id()
+                .returning(|_, engine| {
+                    assert_eq!(engine, None);
+                    Box::pin(async { Ok::<_, anyhow::Error>(()) })
+                });
         }
         {
             let mut work_download_path = repos.work_download_path.lock().await;
@@ -487,6 +562,7 @@ mod tests {
             let candidate = WorkCandidate::new(roots[0].join("game.exe"), CandidateKind::Exe);
             Ok(Box::new(vec![candidate].into_iter()))
         });
+        fs.expect_list_files().returning(|root, _| Ok(vec![root.join("game.exe")]));
         let fs = Arc::new(fs);
         let mut linker = MockWorkLinker::new();
         linker.expect_ensure_links().times(1).returning(|tasks| {
@@ -518,6 +594,8 @@ mod tests {
         struct Case<'a> {
             name: &'a str,
             candidates: Vec<(&'a str, CandidateKind)>,
+            /// エンジン判定用のファイル一覧（候補以外）
+            files: Vec<&'a str>,
             expected_file_name: Option<&'a str>,
         }
 
@@ -525,6 +603,7 @@ mod tests {
             Case {
                 name: "単一 exe はそのまま採用する",
                 candidates: vec![("game.exe", CandidateKind::Exe)],
+                files: vec![],
                 expected_file_name: Some("game.exe"),
             },
             Case {
@@ -533,6 +612,7 @@ mod tests {
                     ("notification_helper.exe", CandidateKind::Exe),
                     ("Game.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("Game.exe"),
             },
             Case {
@@ -541,6 +621,7 @@ mod tests {
                     ("notification_helper.exe", CandidateKind::Exe),
                     ("Game.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("Game.exe"),
             },
             Case {
@@ -552,6 +633,7 @@ mod tests {
                     ("StartData/TraceLog.exe", CandidateKind::Exe),
                     ("StartData/GameData/SiglusEngine.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("Start.exe"),
             },
             Case {
@@ -560,6 +642,7 @@ mod tests {
                     ("CYANBRAIN.exe", CandidateKind::Exe),
                     ("UnityCrashHandler64.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("CYANBRAIN.exe"),
             },
             Case {
@@ -568,6 +651,7 @@ mod tests {
                     ("lol.exe", CandidateKind::Exe),
                     ("エンジン設定.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("lol.exe"),
             },
             Case {
@@ -579,6 +663,7 @@ mod tests {
                     ("ハロー・レディ！/hellolady.exe", CandidateKind::Exe),
                     ("ハロー・レディ！/ハロー・レディ！.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("startup.exe"),
             },
             Case {
@@ -588,6 +673,7 @@ mod tests {
                     ("SETUP/もも☆プラ.eXe", CandidateKind::Exe),
                     ("Game.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("Game.exe"),
             },
             Case {
@@ -597,6 +683,7 @@ mod tests {
                     ("files/uninst.exe", CandidateKind::Exe),
                     ("files/zombie.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("setup.exe"),
             },
             Case {
@@ -605,13 +692,38 @@ mod tests {
                     ("realsister_.exe", CandidateKind::Exe),
                     ("SupportTools.exe", CandidateKind::Exe),
                 ],
+                files: vec![],
                 expected_file_name: Some("realsister_.exe"),
             },
+            Case {
+                name: "吉里吉里は xp3 と同じフォルダの exe を優先する",
+                candidates: vec![
+                    ("menu.exe", CandidateKind::Exe),
+                    ("game/hanasaku.exe", CandidateKind::Exe),
+                ],
+                files: vec!["game/data.xp3"],
+                expected_file_name: Some("hanasaku.exe"),
+            },
+            Case {
+                name: "Ren'Py は py と同じ名前の exe を採用し同梱の helper を後回しにする",
+                candidates: vec![
+                    ("zsync.exe", CandidateKind::Exe),
+                    ("Sakura.exe", CandidateKind::Exe),
+                    ("lib/py3-windows-x86_64/python.exe", CandidateKind::Exe),
+                ],
+                files: vec!["Sakura.py", "renpy/bootstrap.py"],
+                expected_file_name: Some("Sakura.exe"),
+            },
         ];
 
         for case in cases {
             let case_name = case.name;
             let candidates = case.candidates.clone();
+            let listed_files = candidates
+                .iter()
+                .map(|(rel, _)| *rel)
+                .chain(case.files.iter().copied())
+                .collect::<Vec<_>>();
             let expected_file_name = case.expected_file_name;
             let temp = TempDir::new().unwrap();
             let resolver_impl = Arc::new(TestResolver::new(temp.path().to_path_buf()));
@@ -642,6 +754,9 @@ mod tests {
                     .collect::<Vec<_>>();
                 Ok(Box::new(items.into_iter()))
             });
+            fs.expect_list_files().returning(move |root, _| {
+                Ok(listed_files.iter().map(|rel| root.join(rel)).collect())
+            });
             let fs = Arc::new(fs);
 
             let mut linker = MockWorkLinker::new();
@@ -1,10 +1,18 @@
 //! ネイティブホスト同期ユースケース: ストア(DMM/DLsite)のゲーム情報をコレクションへ同期する。
 //! - 既存マッピングがあればスキップして冪等性を保つ
 //! - EGS 情報があれば名称/詳細も upsert し、EGS マップを作成/更新する
+//! - 受け取ったストアの紐付けに最終確認日時を残し、ライブラリ全体の同期では見えなくなった紐付けに印を付ける
 
+use chrono::{DateTime, Local};
 use derive_new::new;
-use domain::repository::{manager::RepositoryManager, RepositoriesExt};
+use domain::repository::{
+    manager::RepositoryManager,
+    works::{DlsiteWorkRepository, DmmWorkRepository},
+    RepositoriesExt,
+};
 use domain::service::work_registration::WorkRegistrationService;
+use domain::sync_session::SyncStore;
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
@@ -16,6 +24,8 @@ pub mod downloads;
 pub struct SyncGamesSummary {
     pub success_count: u32,
     pub new_count: u32,
+    /// 同期前から紐付いていた件数
+    pub unchanged_count: u32,
 }
 
 /// 拡張から渡された image_url/thumbnail_url を保存に適したサムネイルURLへ正規化する
@@ -96,9 +106,85 @@ where
     R: RepositoriesExt + Send + Sync + 'static,
     RS: WorkRegistrationService + Send + Sync + 'static,
 {
-    #[allow(dead_code)]
     manager: Arc<M>,
     registrar: Arc<RS>,
     #[new(default)]
     _marker: PhantomData<R>,
 }
+
+impl<M, R, RS> NativeHostSyncUseCase<M, R, RS>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+    RS: WorkRegistrationService + Send + Sync + 'static,
+{
+    /// `store_ids` のうち、同期前から紐付いているもの
+    async fn find_existing_store_ids(
+        &self,
+        store: SyncStore,
+        store_ids: Vec<String>,
+    ) -> anyhow::Result<HashSet<String>> {
+        if store_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let existing = self
+            .manager
+            .run(|repos| {
+                Box::pin(async move {
+                    match store {
+                        SyncStore::Dmm => {
+                            repos.dmm_work().find_existing_store_ids(&store_ids).await
+                        }
+                        SyncStore::Dlsite => {
+                            repos
+                                .dlsite_work()
+                                .find_existing_store_ids(&store_ids)
+                                .await
+                        }
+                    }
+                })
+            })
+            .await?;
+        Ok(existing.into_iter().collect())
+    }
+
+    async fn mark_seen(&self, store: SyncStore, store_ids: Vec<String>) -> anyhow::Result<()> {
+        if store_ids.is_empty() {
+            return Ok(());
+        }
+        let seen_at = Local::now();
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    match store {
+                        SyncStore::Dmm => repos.dmm_work().mark_seen(&store_ids, seen_at).await,
+                        SyncStore::Dlsite => {
+                            repos.dlsite_work().mark_seen(&store_ids, seen_at).await
+                        }
+                    }
+                })
+            })
+            .await
+    }
+
+    /// ライブラリ全体の同期を終えたあとに呼ぶ。
+    /// `since`（同期の開始時刻）以降に一度も受け取らなかった紐付けに印を付け、その store_id を返す
+    pub async fn flag_missing_store_works(
+        &self,
+        store: SyncStore,
+        since: DateTime<Local>,
+    ) -> anyhow::Result<Vec<String>> {
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    match store {
+                        SyncStore::Dmm => repos.dmm_work().flag_missing_not_seen_since(since).await,
+                        SyncStore::Dlsite => {
+                            repos.dlsite_work().flag_missing_not_seen_since(since).await
+                        }
+                    }
+                })
+            })
+            .await
+    }
+}
@@ -0,0 +1,196 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use chrono::{Duration, Local};
+use derive_new::new;
+use domain::repository::{
+    manager::RepositoryManager, sync_session::SyncSessionRepository, RepositoriesExt,
+};
+use domain::sync_session::{
+    NewSyncSession, SyncSession, SyncSessionChunk, SyncSessionError, SyncSessionProgress, SyncStore,
+};
+use domain::StrId;
+
+/// commit の結果。`newly_committed` が false なら以前の commit の再送
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncSessionCommit {
+    pub progress: SyncSessionProgress,
+    pub newly_committed: bool,
+}
+
+/// 分割同期セッションの受付状況を管理する。チャンクの反映そのものは NativeHostSyncUseCase が行う
+#[derive(new)]
+pub struct NativeHostSyncSessionUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> NativeHostSyncSessionUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    /// セッションを開始する。同じ ID のセッションが残っていれば、受信済みチャンクごと返して再開する
+    pub async fn begin(&self, new_session: NewSyncSession) -> anyhow::Result<SyncSessionProgress> {
+        if new_session.total_chunks == 0 || new_session.total_chunks > SyncSession::MAX_CHUNKS {
+            return Err(SyncSessionError::InvalidTotalChunks {
+                value: new_session.total_chunks,
+                max: SyncSession::MAX_CHUNKS,
+            }
+            .into());
+        }
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    let stale_before = Local::now() - Duration::hours(SyncSession::RETENTION_HOURS);
+                    repos.sync_session().delete_stale(stale_before).await?;
+
+                    match repos.sync_session().get(&new_session.id).await? {
+                        Some(session) => {
+                            if session.store != new_session.store
+                                || session.total_chunks != new_session.total_chunks
+                            {
+                                return Err(SyncSessionError::Mismatch {
+                                    id: new_session.id.value.clone(),
+                                    detail: format!(
+                                        "started as store={} total_chunks={}",
+                                        session.store.as_str(),
+                                        session.total_chunks
+                                    ),
+                                }
+                                .into());
+                            }
+                            let chunks = repos.sync_session().list_chunks(&session.id).await?;
+                            Ok(SyncSessionProgress { session, chunks })
+                        }
+                        None => {
+                            repos.sync_session().insert(&new_session).await?;
+                            let session = repos
+                                .sync_session()
+                                .get(&new_session.id)
+                                .await?
+                                .ok_or_else(|| {
+                                    SyncSessionError::NotFound(new_session.id.value.clone())
+                                })?;
+                            Ok(SyncSessionProgress {
+                                session,
+                                chunks: vec![],
+                            })
+                        }
+                    }
+                })
+            })
+            .await
+    }
+
+    /// チャンクを反映してよいか確かめる。反映済みならその結果を返す
+    pub async fn check_chunk(
+        &self,
+        id: &StrId<SyncSession>,
+        store: SyncStore,
+        chunk_index: u32,
+    ) -> anyhow::Result<Option<SyncSessionChunk>> {
+        let id = id.clone();
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    let session = repos
+                        .sync_session()
+                        .get(&id)
+                        .await?
+                        .ok_or_else(|| SyncSessionError::NotFound(id.value.clone()))?;
+                    if session.store != store {
+                        return Err(SyncSessionError::Mismatch {
+                            id: id.value.clone(),
+                            detail: format!(
+                                "chunk for {} sent to a {} session",
+                                store.as_str(),
+                                session.store.as_str()
+                            ),
+                        }
+                        .into());
+                    }
+                    if chunk_index >= session.total_chunks {
+                        return Err(SyncSessionError::ChunkOutOfRange {
+                            index: chunk_index,
+                            total_chunks: session.total_chunks,
+                        }
+                        .into());
+                    }
+                    let recorded = repos
+                        .sync_session()
+                        .list_chunks(&id)
+                        .await?
+                        .into_iter()
+                        .find(|c| c.chunk_index == chunk_index);
+                    if recorded.is_none() && session.is_committed() {
+                        return Err(SyncSessionError::AlreadyCommitted(id.value.clone()).into());
+                    }
+                    Ok(recorded)
+                })
+            })
+            .await
+    }
+
+    pub async fn record_chunk(
+        &self,
+        id: &StrId<SyncSession>,
+        chunk: SyncSessionChunk,
+    ) -> anyhow::Result<()> {
+        let id = id.clone();
+        self.manager
+            .run(|repos| {
+                Box::pin(async move { repos.sync_session().insert_chunk(&id, &chunk).await })
+            })
+            .await
+    }
+
+    /// 全チャンクが揃っていればセッションを確定する
+    pub async fn commit(&self, id: &StrId<SyncSession>) -> anyhow::Result<SyncSessionCommit> {
+        let id = id.clone();
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    let session = repos
+                        .sync_session()
+                        .get(&id)
+                        .await?
+                        .ok_or_else(|| SyncSessionError::NotFound(id.value.clone()))?;
+                    let chunks = repos.sync_session().list_chunks(&id).await?;
+                    let progress = SyncSessionProgress { session, chunks };
+                    if progress.session.is_committed() {
+                        return Ok(SyncSessionCommit {
+                            progress,
+                            newly_committed: false,
+                        });
+                    }
+                    let missing = progress.missing_chunks();
+                    if !missing.is_empty() {
+                        return Err(SyncSessionError::Incomplete {
+                            id: id.value.clone(),
+                            missing,
+                        }
+                        .into());
+                    }
+                    repos.sync_session().mark_committed(&id).await?;
+                    let session = repos
+                        .sync_session()
+                        .get(&id)
+                        .await?
+                        .ok_or_else(|| SyncSessionError::NotFound(id.value.clone()))?;
+                    Ok(SyncSessionCommit {
+                        progress: SyncSessionProgress {
+                            session,
+                            chunks: progress.chunks,
+                        },
+                        newly_committed: true,
+                    })
+                })
+            })
+            .await
+    }
+}
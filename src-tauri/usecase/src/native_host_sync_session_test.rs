@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::sync::Arc;
+
+    use chrono::Local;
+    use domain::sync_session::{
+        NewSyncSession, SyncSession, SyncSessionChunk, SyncSessionError, SyncStore,
+    };
+    use domain::StrId;
+
+    use crate::native_host_sync_session::NativeHostSyncSessionUseCase;
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+
+    fn session(total_chunks: u32, committed: bool) -> SyncSession {
+        SyncSession {
+            id: StrId::new("sid".to_string()),
+            store: SyncStore::Dmm,
+            extension_id: "ext".to_string(),
+            total_chunks,
+            committed_at: committed.then(Local::now),
+            created_at: Local::now(),
+            updated_at: Local::now(),
+        }
+    }
+
+    fn new_session(total_chunks: u32) -> NewSyncSession {
+        NewSyncSession {
+            id: StrId::new("sid".to_string()),
+            store: SyncStore::Dmm,
+            extension_id: "ext".to_string(),
+            total_chunks,
+        }
+    }
+
+    fn chunk(chunk_index: u32) -> SyncSessionChunk {
+        SyncSessionChunk {
+            chunk_index,
+            game_count: 2,
+            success_count: 2,
+            new_count: 1,
+            unchanged_count: 1,
+            error_count: 0,
+            errors: vec![],
+        }
+    }
+
+    fn session_error(err: anyhow::Error) -> SyncSessionError {
+        err.downcast::<SyncSessionError>().unwrap()
+    }
+
+    #[tokio::test]
+    async fn begin_同じidのセッションが残っていれば受信済みチャンクを返す() {
+        let repos = TestRepositories::default();
+        {
+            let mut repo = repos.sync_session.lock().await;
+            repo.expect_delete_stale()
+                .times(1)
+                .returning(|_| Box::pin(async { Ok(0) }));
+            repo.expect_get()
+                .returning(|_| Box::pin(async { Ok(Some(session(3, false))) }));
+            repo.expect_list_chunks()
+                .returning(|_| Box::pin(async { Ok(vec![chunk(0), chunk(2)]) }));
+            repo.expect_insert().never();
+        }
+
+        let usecase =
+            NativeHostSyncSessionUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let progress = usecase.begin(new_session(3)).await.unwrap();
+
+        assert_eq!(progress.received_chunks(), vec![0, 2]);
+        assert_eq!(progress.missing_chunks(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn begin_チャンク数が違えば再開しない() {
+        let repos = TestRepositories::default();
+        {
+            let mut repo = repos.sync_session.lock().await;
+            repo.expect_delete_stale()
+                .returning(|_| Box::pin(async { Ok(0) }));
+            repo.expect_get()
+                .returning(|_| Box::pin(async { Ok(Some(session(3, false))) }));
+        }
+
+        let usecase =
+            NativeHostSyncSessionUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let err = usecase.begin(new_session(5)).await.unwrap_err();
+
+        assert!(matches!(
+            session_error(err),
+            SyncSessionError::Mismatch { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn begin_チャンク数0は拒否する() {
+        let usecase = NativeHostSyncSessionUseCase::new(Arc::new(TestRepositoryManager::new(
+            TestRepositories::default(),
+        )));
+        let err = usecase.begin(new_session(0)).await.unwrap_err();
+
+        assert!(matches!(
+            session_error(err),
+            SyncSessionError::InvalidTotalChunks { value: 0, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn check_chunk_反映済みなら記録を返し範囲外は拒否する() {
+        let repos = TestRepositories::default();
+        {
+            let mut repo = repos.sync_session.lock().await;
+            repo.expect_get()
+                .returning(|_| Box::pin(async { Ok(Some(session(2, false))) }));
+            repo.expect_list_chunks()
+                .returning(|_| Box::pin(async { Ok(vec![chunk(1)]) }));
+        }
+        let usecase =
+            NativeHostSyncSessionUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let id = StrId::new("sid".to_string());
+
+        assert_eq!(
+            usecase.check_chunk(&id, SyncStore::Dmm, 1).await.unwrap(),
+            Some(chunk(1))
+        );
+        assert_eq!(
+            usecase.check_chunk(&id, SyncStore::Dmm, 0).await.unwrap(),
+            None
+        );
+        let err = usecase
+            .check_chunk(&id, SyncStore::Dmm, 2)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            session_error(err),
+            SyncSessionError::ChunkOutOfRange {
+                index: 2,
+                total_chunks: 2
+            }
+        ));
+        let err = usecase
+            .check_chunk(&id, SyncStore::Dlsite, 0)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            session_error(err),
+            SyncSessionError::Mismatch { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn commit_チャンクが欠けていれば確定しない() {
+        let repos = TestRepositories::default();
+        {
+            let mut repo = repos.sync_session.lock().await;
+            repo.expect_get()
+                .returning(|_| Box::pin(async { Ok(Some(session(3, false))) }));
+            repo.expect_list_chunks()
+                .returning(|_| Box::pin(async { Ok(vec![chunk(0)]) }));
+            repo.expect_mark_committed().never();
+        }
+        let usecase =
+            NativeHostSyncSessionUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+
+        let err = usecase
+            .commit(&StrId::new("sid".to_string()))
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            session_error(err),
+            SyncSessionError::Incomplete {
+                id: "sid".to_string(),
+                missing: vec![1, 2],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn commit_確定済みの再送は集計だけ返す() {
+        let repos = TestRepositories::default();
+        {
+            let mut repo = repos.sync_session.lock().await;
+            repo.expect_get()
+                .returning(|_| Box::pin(async { Ok(Some(session(2, true))) }));
+            repo.expect_list_chunks()
+                .returning(|_| Box::pin(async { Ok(vec![chunk(0), chunk(1)]) }));
+            repo.expect_mark_committed().never();
+        }
+        let usecase =
+            NativeHostSyncSessionUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+
+        let committed = usecase
+            .commit(&StrId::new("sid".to_string()))
+            .await
+            .unwrap();
+
+        assert!(!committed.newly_committed);
+        assert_eq!(committed.progress.success_count(), 4);
+        assert_eq!(committed.progress.new_count(), 2);
+    }
+}
@@ -5,8 +5,7 @@ mod tests {
     use std::sync::Arc;
 
     use domain::service::work_registration::{
-        MockWorkRegistrationService, UniqueWorkKey, WorkRegistrationRequest,
-        WorkRegistrationResult,
+        MockWorkRegistrationService, UniqueWorkKey, WorkRegistrationRequest, WorkRegistrationResult,
     };
 
     use crate::native_host_sync::{
@@ -14,6 +13,33 @@ mod tests {
     };
     use crate::repositorymock::{TestRepositories, TestRepositoryManager};
 
+    /// 同期前の紐付け確認と、同期後の最終確認日時の更新を受け付ける
+    async fn expect_dmm_store_lookup(repos: &TestRepositories, existing: Vec<String>) {
+        let mut dmm_work = repos.dmm_work.lock().await;
+        dmm_work
+            .expect_find_existing_store_ids()
+            .returning(move |_| {
+                let existing = existing.clone();
+                Box::pin(async move { Ok(existing) })
+            });
+        dmm_work
+            .expect_mark_seen()
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+    }
+
+    async fn expect_dlsite_store_lookup(repos: &TestRepositories, existing: Vec<String>) {
+        let mut dlsite_work = repos.dlsite_work.lock().await;
+        dlsite_work
+            .expect_find_existing_store_ids()
+            .returning(move |_| {
+                let existing = existing.clone();
+                Box::pin(async move { Ok(existing) })
+            });
+        dlsite_work
+            .expect_mark_seen()
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+    }
+
     fn create_usecase<
         R: domain::service::work_registration::WorkRegistrationService + Send + Sync + 'static,
     >(
@@ -26,13 +52,12 @@ mod tests {
     #[tokio::test]
     async fn sync_dmm_games_親パックキーを保持したまま一回で登録できる() {
         let repos = TestRepositories::default();
+        expect_dmm_store_lookup(&repos, vec![]).await;
         let manager = Arc::new(TestRepositoryManager::new(repos));
 
         let mut mock_registrar = MockWorkRegistrationService::new();
-        mock_registrar
-            .expect_register()
-            .times(1)
-            .returning(|requests: Vec<WorkRegistrationRequest>| {
+        mock_registrar.expect_register().times(1).returning(
+            |requests: Vec<WorkRegistrationRequest>| {
                 assert_eq!(requests.len(), 1);
                 let resolved_keys = requests[0].keys.clone();
                 let parent_pack = requests[0].insert.parent_pack_dmm_key.clone();
@@ -52,7 +77,8 @@ mod tests {
                         is_new_work: true,
                     }])
                 })
-            });
+            },
+        );
 
         let usecase = create_usecase(manager, Arc::new(mock_registrar));
         let result = usecase
@@ -87,13 +113,12 @@ mod tests {
     #[tokio::test]
     async fn sync_dlsite_games_一回で登録できる() {
         let repos = TestRepositories::default();
+        expect_dlsite_store_lookup(&repos, vec![]).await;
         let manager = Arc::new(TestRepositoryManager::new(repos));
 
         let mut mock_registrar = MockWorkRegistrationService::new();
-        mock_registrar
-            .expect_register()
-            .times(1)
-            .returning(|requests: Vec<WorkRegistrationRequest>| {
+        mock_registrar.expect_register().times(1).returning(
+            |requests: Vec<WorkRegistrationRequest>| {
                 assert_eq!(requests.len(), 1);
                 let resolved_keys = requests[0].keys.clone();
                 assert!(matches!(resolved_keys[0], UniqueWorkKey::Dlsite { .. }));
@@ -105,7 +130,8 @@ mod tests {
                         is_new_work: true,
                     }])
                 })
-            });
+            },
+        );
 
         let usecase = create_usecase(manager, Arc::new(mock_registrar));
         let result = usecase
@@ -126,13 +152,12 @@ mod tests {
     #[tokio::test]
     async fn sync_dmm_games_既存作品の再同期はnew_countが0になる() {
         let repos = TestRepositories::default();
+        expect_dmm_store_lookup(&repos, vec!["sid".into()]).await;
         let manager = Arc::new(TestRepositoryManager::new(repos));
 
         let mut mock_registrar = MockWorkRegistrationService::new();
-        mock_registrar
-            .expect_register()
-            .times(1)
-            .returning(|requests: Vec<WorkRegistrationRequest>| {
+        mock_registrar.expect_register().times(1).returning(
+            |requests: Vec<WorkRegistrationRequest>| {
                 assert_eq!(requests.len(), 1);
                 let resolved_keys = requests[0].keys.clone();
                 Box::pin(async move {
@@ -142,7 +167,8 @@ mod tests {
                         is_new_work: false,
                     }])
                 })
-            });
+            },
+        );
 
         let usecase = create_usecase(manager, Arc::new(mock_registrar));
         let result = usecase
@@ -160,18 +186,18 @@ mod tests {
 
         assert_eq!(result.success_count, 1);
         assert_eq!(result.new_count, 0);
+        assert_eq!(result.unchanged_count, 1);
     }
 
     #[tokio::test]
     async fn sync_dlsite_games_重複入力を含んでもnew_countは過大計上しない() {
         let repos = TestRepositories::default();
+        expect_dlsite_store_lookup(&repos, vec![]).await;
         let manager = Arc::new(TestRepositoryManager::new(repos));
 
         let mut mock_registrar = MockWorkRegistrationService::new();
-        mock_registrar
-            .expect_register()
-            .times(1)
-            .returning(|requests: Vec<WorkRegistrationRequest>| {
+        mock_registrar.expect_register().times(1).returning(
+            |requests: Vec<WorkRegistrationRequest>| {
                 assert_eq!(requests.len(), 1);
                 let resolved_keys = requests[0].keys.clone();
                 Box::pin(async move {
@@ -181,7 +207,8 @@ mod tests {
                         is_new_work: true,
                     }])
                 })
-            });
+            },
+        );
 
         let usecase = create_usecase(manager, Arc::new(mock_registrar));
         let result = usecase
@@ -207,4 +234,81 @@ mod tests {
         assert_eq!(result.success_count, 1);
         assert_eq!(result.new_count, 1);
     }
+
+    #[tokio::test]
+    async fn sync_dlsite_games_受け取ったstore_idを確認済みにする() {
+        let repos = TestRepositories::default();
+        {
+            let mut dlsite_work = repos.dlsite_work.lock().await;
+            dlsite_work
+                .expect_find_existing_store_ids()
+                .returning(|_| Box::pin(async { Ok(vec!["rj-old".to_string()]) }));
+            dlsite_work
+                .expect_mark_seen()
+                .times(1)
+                .withf(|store_ids, _| store_ids == ["rj-old".to_string(), "rj-new".to_string()])
+                .returning(|_, _| Box::pin(async { Ok(()) }));
+        }
+        let manager = Arc::new(TestRepositoryManager::new(repos));
+
+        let mut mock_registrar = MockWorkRegistrationService::new();
+        mock_registrar.expect_register().times(1).returning(
+            |requests: Vec<WorkRegistrationRequest>| {
+                let results = requests
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, request)| WorkRegistrationResult {
+                        resolved_keys: request.keys,
+                        work_id: domain::StrId::new(format!("work-{i}")),
+                        is_new_work: i == 1,
+                    })
+                    .collect();
+                Box::pin(async move { Ok(results) })
+            },
+        );
+
+        let usecase = create_usecase(manager, Arc::new(mock_registrar));
+        let result = usecase
+            .sync_dlsite_games(
+                ["rj-old", "rj-new"]
+                    .into_iter()
+                    .map(|store_id| DlsiteSyncGameParam {
+                        store_id: store_id.into(),
+                        category: "game".into(),
+                        gamename: "Dlsite Game".into(),
+                        egs: None,
+                        image_url: String::new(),
+                    })
+                    .collect(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.success_count, 2);
+        assert_eq!(result.new_count, 1);
+        assert_eq!(result.unchanged_count, 1);
+    }
+
+    #[tokio::test]
+    async fn flag_missing_store_works_開始時刻以降に見えなかった紐付けを返す() {
+        let repos = TestRepositories::default();
+        let since = chrono::Local::now();
+        repos
+            .dmm_work
+            .lock()
+            .await
+            .expect_flag_missing_not_seen_since()
+            .times(1)
+            .withf(move |at| *at == since)
+            .returning(|_| Box::pin(async { Ok(vec!["SID-GONE".to_string()]) }));
+        let manager = Arc::new(TestRepositoryManager::new(repos));
+
+        let usecase = create_usecase(manager, Arc::new(MockWorkRegistrationService::new()));
+        let missing = usecase
+            .flag_missing_store_works(domain::sync_session::SyncStore::Dmm, since)
+            .await
+            .unwrap();
+
+        assert_eq!(missing, vec!["SID-GONE".to_string()]);
+    }
 }
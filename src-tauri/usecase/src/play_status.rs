@@ -0,0 +1,136 @@
+//! 作品ごとの遊んだ状況とルートの攻略の記録
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use derive_new::new;
+use domain::play_status::{
+    NewWorkRoute, PlayStateUpdate, PlayStatus, PlayStatusChange, WorkPlayState, WorkRoute,
+};
+use domain::repository::{
+    manager::RepositoryManager, work_play_state::WorkPlayStateRepository as _,
+    work_route::WorkRouteRepository as _, RepositoriesExt,
+};
+use domain::{Id, StrId};
+
+#[derive(new)]
+pub struct PlayStatusUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    #[new(default)]
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> PlayStatusUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    pub async fn get_state(&self, work_id: String) -> anyhow::Result<Option<WorkPlayState>> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move { repos.work_play_state().find_by_work_id(work_id).await })
+            })
+            .await
+    }
+
+    /// ステータスが変わったときだけ履歴を残す
+    pub async fn save_state(
+        &self,
+        work_id: String,
+        update: PlayStateUpdate,
+    ) -> anyhow::Result<WorkPlayState> {
+        let now = chrono::Local::now();
+        let state = WorkPlayState::apply(StrId::new(work_id), update, now.date_naive())?;
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    let previous = repos
+                        .work_play_state()
+                        .find_by_work_id(state.work_id.clone())
+                        .await?
+                        .map_or(PlayStatus::Unplayed, |s| s.status);
+                    repos.work_play_state().upsert(&state).await?;
+                    if previous != state.status {
+                        repos
+                            .work_play_state()
+                            .insert_status_change(state.work_id.clone(), state.status, now)
+                            .await?;
+                    }
+                    Ok(state)
+                })
+            })
+            .await
+    }
+
+    /// 新しい順
+    pub async fn list_status_changes(
+        &self,
+        work_id: String,
+    ) -> anyhow::Result<Vec<PlayStatusChange>> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    repos
+                        .work_play_state()
+                        .list_status_changes_by_work_id(work_id)
+                        .await
+                })
+            })
+            .await
+    }
+
+    pub async fn list_routes(&self, work_id: String) -> anyhow::Result<Vec<WorkRoute>> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| Box::pin(async move { repos.work_route().list_by_work_id(work_id).await }))
+            .await
+    }
+
+    pub async fn add_route(&self, work_id: String, name: String) -> anyhow::Result<Vec<WorkRoute>> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            anyhow::bail!("route name is empty");
+        }
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    repos
+                        .work_route()
+                        .insert(&NewWorkRoute {
+                            work_id: work_id.clone(),
+                            name,
+                        })
+                        .await?;
+                    repos.work_route().list_by_work_id(work_id).await
+                })
+            })
+            .await
+    }
+
+    pub async fn set_route_completed(&self, id: i32, completed: bool) -> anyhow::Result<()> {
+        let completed_at = completed.then(chrono::Local::now);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    repos
+                        .work_route()
+                        .update_completed_at(Id::new(id), completed_at)
+                        .await
+                })
+            })
+            .await
+    }
+
+    pub async fn delete_route(&self, id: i32) -> anyhow::Result<()> {
+        self.manager
+            .run(|repos| Box::pin(async move { repos.work_route().delete(Id::new(id)).await }))
+            .await
+    }
+}
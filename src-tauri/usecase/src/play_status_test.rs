@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::sync::Arc;
+
+    use domain::play_status::{PlayStateUpdate, PlayStatus, WorkPlayState};
+    use domain::{Id, StrId};
+
+    use crate::play_status::PlayStatusUseCase;
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+
+    fn usecase(
+        repos: &TestRepositories,
+    ) -> PlayStatusUseCase<TestRepositoryManager, TestRepositories> {
+        PlayStatusUseCase::new(Arc::new(TestRepositoryManager::new(repos.clone())))
+    }
+
+    fn update(status: PlayStatus) -> PlayStateUpdate {
+        PlayStateUpdate {
+            status,
+            score: Some(70),
+            started_on: None,
+            finished_on: None,
+        }
+    }
+
+    async fn expect_current(repos: &TestRepositories, status: Option<PlayStatus>) {
+        repos
+            .work_play_state
+            .lock()
+            .await
+            .expect_find_by_work_id()
+            .returning(move |work_id| {
+                let state = status.map(|status| WorkPlayState {
+                    work_id,
+                    status,
+                    score: None,
+                    started_on: None,
+                    finished_on: None,
+                });
+                Box::pin(async move { Ok(state) })
+            });
+    }
+
+    #[tokio::test]
+    async fn save_state_ステータスが変わると履歴を残す() {
+        let repos = TestRepositories::default();
+        expect_current(&repos, None).await;
+        {
+            let mut work_play_state = repos.work_play_state.lock().await;
+            work_play_state
+                .expect_upsert()
+                .withf(|state| {
+                    state.status == PlayStatus::Playing
+                        && state.score == Some(70)
+                        && state.started_on.is_some()
+                })
+                .times(1)
+                .returning(|_| Box::pin(async { Ok(()) }));
+            work_play_state
+                .expect_insert_status_change()
+                .withf(|work_id, status, _| work_id.value == "w" && *status == PlayStatus::Playing)
+                .times(1)
+                .returning(|_, _, _| Box::pin(async { Ok(Id::new(1)) }));
+        }
+
+        let state = usecase(&repos)
+            .save_state("w".into(), update(PlayStatus::Playing))
+            .await
+            .unwrap();
+
+        assert_eq!(state.work_id, StrId::new("w".into()));
+    }
+
+    #[tokio::test]
+    async fn save_state_点数だけの変更では履歴を残さない() {
+        let repos = TestRepositories::default();
+        expect_current(&repos, Some(PlayStatus::Finished)).await;
+        {
+            let mut work_play_state = repos.work_play_state.lock().await;
+            work_play_state
+                .expect_upsert()
+                .times(1)
+                .returning(|_| Box::pin(async { Ok(()) }));
+            work_play_state.expect_insert_status_change().never();
+        }
+
+        usecase(&repos)
+            .save_state("w".into(), update(PlayStatus::Finished))
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,241 @@
+//! 共有ページからの起動要求を設定に照らして判断し、その結果を記録する
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use domain::pubsub::{
+    PubSubEvent, PubSubService, RemoteLaunchConfirmationClosedPayload,
+    RemoteLaunchConfirmationPayload,
+};
+use domain::remote_launch::{
+    NewRemoteLaunchAudit, RemoteLaunchAudit, RemoteLaunchDecision, RemoteLaunchPolicy,
+    RemoteLaunchSettings, MAX_CONFIRM_TIMEOUT_SECONDS, MIN_CONFIRM_TIMEOUT_SECONDS,
+};
+use domain::repository::{
+    manager::RepositoryManager, remote_launch::RemoteLaunchRepository as _,
+    works::WorkRepository as _, RepositoriesExt,
+};
+use domain::StrId;
+use tokio::sync::oneshot;
+
+/// 起動要求に対する判断
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteLaunchAuthorization {
+    pub policy: RemoteLaunchPolicy,
+    pub decision: RemoteLaunchDecision,
+    /// 要求の時点の作品名。手元にない作品なら `None`
+    pub work_title: Option<String>,
+}
+
+struct PendingConfirmation {
+    work_id: String,
+    sender: oneshot::Sender<bool>,
+}
+
+pub struct RemoteLaunchUseCase<M, R, P>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+    P: PubSubService,
+{
+    manager: Arc<M>,
+    pubsub: P,
+    /// 答えを待っている確認。request_id ごと
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+    _marker: PhantomData<R>,
+}
+
+impl<M, R, P> RemoteLaunchUseCase<M, R, P>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+    P: PubSubService,
+{
+    pub fn new(manager: Arc<M>, pubsub: P) -> Self {
+        Self {
+            manager,
+            pubsub,
+            pending: Mutex::new(HashMap::new()),
+            _marker: PhantomData,
+        }
+    }
+
+    pub async fn get_settings(&self) -> anyhow::Result<RemoteLaunchSettings> {
+        self.manager
+            .run(|repos| Box::pin(async move { repos.remote_launch().get_settings().await }))
+            .await
+    }
+
+    /// 確認を待つ秒数は範囲に収め、許可リストは重複を除いて保存する
+    pub async fn set_settings(
+        &self,
+        settings: RemoteLaunchSettings,
+    ) -> anyhow::Result<RemoteLaunchSettings> {
+        let mut allowed_work_ids = settings
+            .allowed_work_ids
+            .into_iter()
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect::<Vec<_>>();
+        allowed_work_ids.sort();
+        allowed_work_ids.dedup();
+        let normalized = RemoteLaunchSettings {
+            policy: settings.policy,
+            allowed_work_ids,
+            confirm_timeout_seconds: settings
+                .confirm_timeout_seconds
+                .clamp(MIN_CONFIRM_TIMEOUT_SECONDS, MAX_CONFIRM_TIMEOUT_SECONDS),
+        };
+
+        self.manager
+            .run_in_transaction(|repos| {
+                let normalized = normalized.clone();
+                Box::pin(async move { repos.remote_launch().set_settings(&normalized).await })
+            })
+            .await?;
+        // 存在しない作品は保存されないので、保存後の値を返す
+        self.get_settings().await
+    }
+
+    /// 起動してよいかを判断する。`AskFirst` で許可リストにない作品は、
+    /// デスクトップでの答えか確認の時間切れまで待つ
+    pub async fn authorize(&self, work_id: &str) -> anyhow::Result<RemoteLaunchAuthorization> {
+        let settings = self.get_settings().await?;
+        let work_title = self.find_work_title(work_id).await?;
+
+        let decision = match settings.evaluate(work_id) {
+            Some(decision) => decision,
+            None => {
+                self.confirm(
+                    work_id,
+                    work_title.clone(),
+                    Duration::from_secs(settings.confirm_timeout_seconds as u64),
+                )
+                .await
+            }
+        };
+        Ok(RemoteLaunchAuthorization {
+            policy: settings.policy,
+            decision,
+            work_title,
+        })
+    }
+
+    /// 確認に答える。`always_allow` で許可すると、以後その作品は確認しない。
+    /// 既に時間切れなどで閉じた確認なら false を返す
+    pub async fn respond_confirmation(
+        &self,
+        request_id: &str,
+        approved: bool,
+        always_allow: bool,
+    ) -> anyhow::Result<bool> {
+        let pending = self
+            .pending
+            .lock()
+            .expect("poisoned remote launch confirmations")
+            .remove(request_id);
+        let Some(pending) = pending else {
+            return Ok(false);
+        };
+
+        if approved && always_allow {
+            let work_id = pending.work_id.clone();
+            self.manager
+                .run(|repos| {
+                    Box::pin(async move { repos.remote_launch().add_allowed_work(&work_id).await })
+                })
+                .await?;
+        }
+        Ok(pending.sender.send(approved).is_ok())
+    }
+
+    pub async fn record_audit(&self, audit: NewRemoteLaunchAudit) -> anyhow::Result<()> {
+        self.manager
+            .run(|repos| Box::pin(async move { repos.remote_launch().insert_audit(&audit).await }))
+            .await
+    }
+
+    /// 新しい順に返す
+    pub async fn list_audits(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> anyhow::Result<Vec<RemoteLaunchAudit>> {
+        self.manager
+            .run(|repos| {
+                Box::pin(async move { repos.remote_launch().list_audits(limit, offset).await })
+            })
+            .await
+    }
+
+    async fn find_work_title(&self, work_id: &str) -> anyhow::Result<Option<String>> {
+        let work_id = StrId::new(work_id.to_string());
+        let details = self
+            .manager
+            .run(|repos| {
+                Box::pin(async move { repos.work().find_details_by_work_id(work_id).await })
+            })
+            .await?;
+        Ok(details.map(|details| details.work.title))
+    }
+
+    async fn confirm(
+        &self,
+        work_id: &str,
+        work_title: Option<String>,
+        timeout: Duration,
+    ) -> RemoteLaunchDecision {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let (sender, receiver) = oneshot::channel();
+        self.pending
+            .lock()
+            .expect("poisoned remote launch confirmations")
+            .insert(
+                request_id.clone(),
+                PendingConfirmation {
+                    work_id: work_id.to_string(),
+                    sender,
+                },
+            );
+
+        // 画面に届かなくても時間切れで断るので、通知の失敗はログだけにする
+        if let Err(err) = self
+            .pubsub
+            .notify(PubSubEvent::RemoteLaunchConfirmationRequested(
+                RemoteLaunchConfirmationPayload::new(
+                    request_id.clone(),
+                    work_id.to_string(),
+                    work_title,
+                    timeout.as_secs() as i32,
+                ),
+            ))
+        {
+            log::warn!("failed to request remote launch confirmation: {err}");
+        }
+
+        let decision = match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(true)) => RemoteLaunchDecision::Approved,
+            Ok(Ok(false)) | Ok(Err(_)) => RemoteLaunchDecision::Rejected,
+            Err(_) => RemoteLaunchDecision::TimedOut,
+        };
+        self.pending
+            .lock()
+            .expect("poisoned remote launch confirmations")
+            .remove(&request_id);
+
+        if let Err(err) = self
+            .pubsub
+            .notify(PubSubEvent::RemoteLaunchConfirmationClosed(
+                RemoteLaunchConfirmationClosedPayload::new(
+                    request_id,
+                    decision.as_str().to_string(),
+                ),
+            ))
+        {
+            log::warn!("failed to close remote launch confirmation: {err}");
+        }
+        decision
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::sync::Arc;
+
+    use domain::pubsub::{PubSubEvent, PubSubService};
+    use domain::remote_launch::{RemoteLaunchDecision, RemoteLaunchPolicy, RemoteLaunchSettings};
+    use tokio::sync::mpsc;
+
+    use crate::remote_launch::RemoteLaunchUseCase;
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+
+    /// 通知をテストへ流す
+    struct ChannelPubSub(mpsc::UnboundedSender<PubSubEvent>);
+
+    impl PubSubService for ChannelPubSub {
+        fn notify(&self, event: PubSubEvent) -> Result<(), anyhow::Error> {
+            self.0.send(event)?;
+            Ok(())
+        }
+    }
+
+    type UseCase = RemoteLaunchUseCase<TestRepositoryManager, TestRepositories, ChannelPubSub>;
+
+    async fn usecase_with(
+        settings: RemoteLaunchSettings,
+    ) -> (
+        Arc<UseCase>,
+        TestRepositories,
+        mpsc::UnboundedReceiver<PubSubEvent>,
+    ) {
+        let repos = TestRepositories::default();
+        repos
+            .remote_launch
+            .lock()
+            .await
+            .expect_get_settings()
+            .returning(move || {
+                let settings = settings.clone();
+                Box::pin(async move { Ok(settings) })
+            });
+        repos
+            .work
+            .lock()
+            .await
+            .expect_find_details_by_work_id()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        let (tx, rx) = mpsc::unbounded_channel();
+        let usecase = Arc::new(RemoteLaunchUseCase::new(
+            Arc::new(TestRepositoryManager::new(repos.clone())),
+            ChannelPubSub(tx),
+        ));
+        (usecase, repos, rx)
+    }
+
+    fn ask_first(timeout_seconds: u32) -> RemoteLaunchSettings {
+        RemoteLaunchSettings {
+            policy: RemoteLaunchPolicy::AskFirst,
+            allowed_work_ids: vec!["allowed".into()],
+            confirm_timeout_seconds: timeout_seconds,
+        }
+    }
+
+    async fn requested_id(rx: &mut mpsc::UnboundedReceiver<PubSubEvent>) -> String {
+        match rx.recv().await {
+            Some(PubSubEvent::RemoteLaunchConfirmationRequested(payload)) => payload.request_id,
+            _ => panic!("確認の要求が通知されていない"),
+        }
+    }
+
+    #[tokio::test]
+    async fn authorize_disabledなら確認せずに断る() {
+        let (usecase, _repos, mut rx) = usecase_with(RemoteLaunchSettings {
+            policy: RemoteLaunchPolicy::Disabled,
+            ..ask_first(30)
+        })
+        .await;
+
+        let authorization = usecase.authorize("allowed").await.unwrap();
+        assert_eq!(authorization.policy, RemoteLaunchPolicy::Disabled);
+        assert_eq!(authorization.decision, RemoteLaunchDecision::Disabled);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn authorize_ask_firstでも許可リストの作品は確認しない() {
+        let (usecase, _repos, mut rx) = usecase_with(ask_first(30)).await;
+
+        let authorization = usecase.authorize("allowed").await.unwrap();
+        assert_eq!(authorization.decision, RemoteLaunchDecision::Allowlisted);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn authorize_確認で許可されたら起動してよい() {
+        let (usecase, _repos, mut rx) = usecase_with(ask_first(30)).await;
+
+        let handle = tokio::spawn({
+            let usecase = usecase.clone();
+            async move { usecase.authorize("other").await }
+        });
+        let request_id = requested_id(&mut rx).await;
+        assert!(usecase
+            .respond_confirmation(&request_id, true, false)
+            .await
+            .unwrap());
+
+        let authorization = handle.await.unwrap().unwrap();
+        assert_eq!(authorization.decision, RemoteLaunchDecision::Approved);
+        match rx.recv().await {
+            Some(PubSubEvent::RemoteLaunchConfirmationClosed(payload)) => {
+                assert_eq!(payload.request_id, request_id);
+                assert_eq!(payload.decision, "approved");
+            }
+            _ => panic!("確認を閉じる通知がない"),
+        }
+        // 閉じた確認には答えられない
+        assert!(!usecase
+            .respond_confirmation(&request_id, true, false)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn authorize_常に許可すると許可リストに加える() {
+        let (usecase, repos, mut rx) = usecase_with(ask_first(30)).await;
+        repos
+            .remote_launch
+            .lock()
+            .await
+            .expect_add_allowed_work()
+            .withf(|work_id| work_id == "other")
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let handle = tokio::spawn({
+            let usecase = usecase.clone();
+            async move { usecase.authorize("other").await }
+        });
+        let request_id = requested_id(&mut rx).await;
+        usecase
+            .respond_confirmation(&request_id, true, true)
+            .await
+            .unwrap();
+
+        let authorization = handle.await.unwrap().unwrap();
+        assert_eq!(authorization.decision, RemoteLaunchDecision::Approved);
+    }
+
+    #[tokio::test]
+    async fn authorize_答えがなければ時間切れで断る() {
+        let (usecase, _repos, mut rx) = usecase_with(ask_first(1)).await;
+
+        let authorization = usecase.authorize("other").await.unwrap();
+        assert_eq!(authorization.decision, RemoteLaunchDecision::TimedOut);
+        let request_id = requested_id(&mut rx).await;
+        assert!(!usecase
+            .respond_confirmation(&request_id, true, false)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn set_settings_待ち時間を範囲に収めて許可リストの重複を除く() {
+        let (usecase, repos, _rx) = usecase_with(ask_first(30)).await;
+        repos
+            .remote_launch
+            .lock()
+            .await
+            .expect_set_settings()
+            .withf(|settings| {
+                settings.confirm_timeout_seconds == 300
+                    && settings.allowed_work_ids == vec!["a".to_string(), "b".to_string()]
+            })
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        usecase
+            .set_settings(RemoteLaunchSettings {
+                policy: RemoteLaunchPolicy::Allowlist,
+                allowed_work_ids: vec![" b ".into(), "a".into(), "b".into(), "".into()],
+                confirm_timeout_seconds: 3600,
+            })
+            .await
+            .unwrap();
+    }
+}
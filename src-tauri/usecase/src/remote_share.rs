@@ -1,6 +1,8 @@
 use anyhow::Context;
-use reqwest::Client;
+use domain::remote_share::RemoteShareSyncError;
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::path::Path;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -37,6 +39,74 @@ impl RemoteShareWorkInput {
             None => format!("work:{}", self.work_id),
         }
     }
+
+    /// 送る内容のハッシュ。`thumbnail_fingerprint` はサムネイルの差し替えを拾うために混ぜる
+    pub fn content_hash(&self, thumbnail_fingerprint: Option<&str>) -> anyhow::Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(self)?);
+        if let Some(fingerprint) = thumbnail_fingerprint {
+            hasher.update(b"\0");
+            hasher.update(fingerprint.as_bytes());
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+}
+
+/// サムネイルのファイルサイズと更新日時。中身を読まずに差し替えを検知する
+pub fn thumbnail_fingerprint(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    Some(format!("{}:{}", metadata.len(), modified))
+}
+
+/// 作品と、その内容のハッシュ
+#[derive(Clone, Debug)]
+pub struct HashedRemoteShareWork {
+    pub work: RemoteShareWorkInput,
+    pub content_hash: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RemoteShareSyncMode {
+    /// 送った作品が端末の全作品。サーバーは送られなかった作品を消す
+    Full,
+    /// 変わった作品と消えた作品だけを送る
+    Delta,
+}
+
+/// 1 回の同期で送る内容
+#[derive(Clone, Debug)]
+pub struct RemoteShareSyncPlan {
+    pub mode: RemoteShareSyncMode,
+    /// 差分の起点。前回の同期でサーバーが返した last_synced_at
+    pub base_synced_at: Option<String>,
+    pub works: Vec<HashedRemoteShareWork>,
+    /// サーバーから消す作品（墓標）
+    pub removed_work_ids: Vec<String>,
+    /// 同期後にサーバーに残るはずの作品数。食い違えばサーバーが全件の送り直しを求める
+    pub total_count: usize,
+}
+
+impl RemoteShareSyncPlan {
+    pub fn full(works: Vec<HashedRemoteShareWork>) -> Self {
+        Self {
+            mode: RemoteShareSyncMode::Full,
+            base_synced_at: None,
+            total_count: works.len(),
+            works,
+            removed_work_ids: vec![],
+        }
+    }
+
+    fn work_inputs(&self) -> Vec<RemoteShareWorkInput> {
+        self.works.iter().map(|w| w.work.clone()).collect()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -60,6 +130,9 @@ pub struct RemoteShareUploadedImage {
 #[serde(rename_all = "camelCase")]
 struct PrepareSyncRequest {
     device_secret: String,
+    mode: RemoteShareSyncMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_synced_at: Option<String>,
     works: Vec<RemoteShareWorkInput>,
 }
 
@@ -74,7 +147,12 @@ struct PrepareSyncResponse {
 #[serde(rename_all = "camelCase")]
 struct CommitSyncRequest {
     device_secret: String,
+    mode: RemoteShareSyncMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_synced_at: Option<String>,
     works: Vec<RemoteShareWorkInput>,
+    removed_work_ids: Vec<String>,
+    total_count: usize,
     uploaded_images: Vec<RemoteShareUploadedImage>,
 }
 
@@ -83,7 +161,12 @@ struct CommitSyncRequest {
 pub struct CommitSyncResponse {
     pub device_id: String,
     pub synced_count: i32,
+    #[serde(default)]
+    pub removed_count: i32,
     pub last_synced_at: String,
+    /// 反映後の作品数が total_count と合わなかった
+    #[serde(default)]
+    pub resync_required: bool,
 }
 
 #[derive(Clone)]
@@ -132,7 +215,7 @@ impl RemoteShareUseCase {
         server_base_url: &str,
         device_id: &str,
         device_secret: &str,
-        works: Vec<RemoteShareWorkInput>,
+        plan: &RemoteShareSyncPlan,
     ) -> anyhow::Result<Vec<RemoteShareUploadTarget>> {
         let endpoint = format!(
             "{}/api/device/{}/works/sync/prepare",
@@ -144,11 +227,17 @@ impl RemoteShareUseCase {
             .post(endpoint)
             .json(&PrepareSyncRequest {
                 device_secret: device_secret.to_string(),
-                works,
+                mode: plan.mode,
+                base_synced_at: plan.base_synced_at.clone(),
+                works: plan.work_inputs(),
             })
             .send()
             .await?;
 
+        if response.status() == StatusCode::CONFLICT {
+            let body = response.text().await.unwrap_or_default();
+            return Err(RemoteShareSyncError::ResyncRequired(body).into());
+        }
         if !response.status().is_success() {
             let body = response.text().await.unwrap_or_default();
             return Err(anyhow::anyhow!("prepare sync failed: {}", body));
@@ -187,7 +276,7 @@ impl RemoteShareUseCase {
         server_base_url: &str,
         device_id: &str,
         device_secret: &str,
-        works: Vec<RemoteShareWorkInput>,
+        plan: &RemoteShareSyncPlan,
         uploaded_images: Vec<RemoteShareUploadedImage>,
     ) -> anyhow::Result<CommitSyncResponse> {
         let endpoint = format!(
@@ -200,18 +289,34 @@ impl RemoteShareUseCase {
             .post(endpoint)
             .json(&CommitSyncRequest {
                 device_secret: device_secret.to_string(),
-                works,
+                mode: plan.mode,
+                base_synced_at: plan.base_synced_at.clone(),
+                works: plan.work_inputs(),
+                removed_work_ids: plan.removed_work_ids.clone(),
+                total_count: plan.total_count,
                 uploaded_images,
             })
             .send()
             .await?;
 
+        if response.status() == StatusCode::CONFLICT {
+            let body = response.text().await.unwrap_or_default();
+            return Err(RemoteShareSyncError::ResyncRequired(body).into());
+        }
         if !response.status().is_success() {
             let body = response.text().await.unwrap_or_default();
             return Err(anyhow::anyhow!("commit sync failed: {}", body));
         }
 
-        Ok(response.json::<CommitSyncResponse>().await?)
+        let response = response.json::<CommitSyncResponse>().await?;
+        if response.resync_required {
+            return Err(RemoteShareSyncError::ResyncRequired(format!(
+                "server has a different number of works than {}",
+                plan.total_count
+            ))
+            .into());
+        }
+        Ok(response)
     }
 
     pub fn build_share_url(&self, server_base_url: &str, device_id: &str) -> anyhow::Result<String> {
@@ -268,4 +373,27 @@ mod tests {
 
         assert_eq!(work.dedupe_key(), "egs:123");
     }
+
+    #[test]
+    fn content_hash_内容かサムネイルが変われば変わる() {
+        let work = super::RemoteShareWorkInput {
+            work_id: "work-1".to_string(),
+            title: "Title".to_string(),
+            erogamescape_id: None,
+            official_url: None,
+            erogamescape_url: None,
+            seiya_url: None,
+            thumbnail: None,
+        };
+        let renamed = super::RemoteShareWorkInput {
+            title: "Title 2".to_string(),
+            ..work.clone()
+        };
+
+        let hash = work.content_hash(Some("10:1")).unwrap();
+        assert_eq!(hash, work.content_hash(Some("10:1")).unwrap());
+        assert_ne!(hash, renamed.content_hash(Some("10:1")).unwrap());
+        assert_ne!(hash, work.content_hash(Some("10:2")).unwrap());
+        assert_ne!(hash, work.content_hash(None).unwrap());
+    }
 }
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use derive_new::new;
+use domain::remote_share::{RemoteShareDelta, SyncedRemoteShareWork};
+use domain::repository::{
+    manager::RepositoryManager, remote_share_state::RemoteShareStateRepository, RepositoriesExt,
+};
+
+use crate::remote_share::{HashedRemoteShareWork, RemoteShareSyncMode, RemoteShareSyncPlan};
+
+/// リモート共有へ送った内容を手元に記録し、次に送る差分を決める
+#[derive(new)]
+pub struct RemoteShareSyncUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> RemoteShareSyncUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    /// 手元の全作品 `works` から今回送る内容を決める。
+    /// 前回の同期時刻か送った記録がなければ全件を送る
+    pub async fn plan(
+        &self,
+        device_id: &str,
+        base_synced_at: Option<String>,
+        works: Vec<HashedRemoteShareWork>,
+    ) -> anyhow::Result<RemoteShareSyncPlan> {
+        let device_id = device_id.to_string();
+        let states = self
+            .manager
+            .run(move |repos| {
+                Box::pin(async move {
+                    repos
+                        .remote_share_state()
+                        .delete_other_devices(&device_id)
+                        .await?;
+                    repos.remote_share_state().list(&device_id).await
+                })
+            })
+            .await?;
+
+        let base_synced_at = match base_synced_at {
+            Some(base_synced_at) if !states.is_empty() => base_synced_at,
+            _ => return Ok(RemoteShareSyncPlan::full(works)),
+        };
+
+        let current = works
+            .iter()
+            .map(|w| (w.work.work_id.clone(), w.content_hash.clone()))
+            .collect::<Vec<_>>();
+        let delta = RemoteShareDelta::plan(&current, &states);
+        let changed: HashSet<String> = delta.changed_work_ids.into_iter().collect();
+        let total_count = works.len();
+        Ok(RemoteShareSyncPlan {
+            mode: RemoteShareSyncMode::Delta,
+            base_synced_at: Some(base_synced_at),
+            works: works
+                .into_iter()
+                .filter(|w| changed.contains(&w.work.work_id))
+                .collect(),
+            removed_work_ids: delta.removed_work_ids,
+            total_count,
+        })
+    }
+
+    /// サーバーへの反映が済んだ `plan` を記録する。
+    /// `dirty_work_ids` はサムネイルを送れなかった作品で、次の同期でも送り直す
+    pub async fn record_synced(
+        &self,
+        device_id: &str,
+        plan: &RemoteShareSyncPlan,
+        dirty_work_ids: &HashSet<String>,
+    ) -> anyhow::Result<()> {
+        let device_id = device_id.to_string();
+        let synced = plan
+            .works
+            .iter()
+            .map(|w| SyncedRemoteShareWork {
+                work_id: w.work.work_id.clone(),
+                content_hash: w.content_hash.clone(),
+                dirty: dirty_work_ids.contains(&w.work.work_id),
+            })
+            .collect::<Vec<_>>();
+        let mode = plan.mode;
+        let removed_work_ids = plan.removed_work_ids.clone();
+        self.manager
+            .run(move |repos| {
+                Box::pin(async move {
+                    let removed = match mode {
+                        RemoteShareSyncMode::Delta => removed_work_ids,
+                        // 全件を送ったので、それ以外の記録はサーバーからも消えている
+                        RemoteShareSyncMode::Full => {
+                            let sent: HashSet<&str> =
+                                synced.iter().map(|w| w.work_id.as_str()).collect();
+                            repos
+                                .remote_share_state()
+                                .list(&device_id)
+                                .await?
+                                .into_iter()
+                                .filter(|s| !sent.contains(s.work_id.as_str()))
+                                .map(|s| s.work_id)
+                                .collect()
+                        }
+                    };
+                    repos
+                        .remote_share_state()
+                        .delete(&device_id, &removed)
+                        .await?;
+                    repos
+                        .remote_share_state()
+                        .upsert_synced(&device_id, &synced)
+                        .await
+                })
+            })
+            .await
+    }
+
+    /// サーバーと食い違ったときに、記録済みの作品をすべて送り直す対象にする
+    pub async fn mark_all_dirty(&self, device_id: &str) -> anyhow::Result<u64> {
+        let device_id = device_id.to_string();
+        self.manager
+            .run(move |repos| {
+                Box::pin(async move { repos.remote_share_state().mark_all_dirty(&device_id).await })
+            })
+            .await
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use chrono::Local;
+    use domain::remote_share::{RemoteShareWorkState, SyncedRemoteShareWork};
+
+    use crate::remote_share::{
+        HashedRemoteShareWork, RemoteShareSyncMode, RemoteShareSyncPlan, RemoteShareWorkInput,
+    };
+    use crate::remote_share_sync::RemoteShareSyncUseCase;
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+
+    fn hashed(work_id: &str, content_hash: &str) -> HashedRemoteShareWork {
+        HashedRemoteShareWork {
+            work: RemoteShareWorkInput {
+                work_id: work_id.into(),
+                title: format!("title {work_id}"),
+                erogamescape_id: None,
+                official_url: None,
+                erogamescape_url: None,
+                seiya_url: None,
+                thumbnail: None,
+            },
+            content_hash: content_hash.into(),
+        }
+    }
+
+    fn state(work_id: &str, content_hash: &str) -> RemoteShareWorkState {
+        RemoteShareWorkState {
+            work_id: work_id.into(),
+            content_hash: content_hash.into(),
+            dirty: false,
+            synced_at: Local::now(),
+        }
+    }
+
+    async fn expect_states(repos: &TestRepositories, states: Vec<RemoteShareWorkState>) {
+        let mut repo = repos.remote_share_state.lock().await;
+        repo.expect_delete_other_devices()
+            .returning(|_| Box::pin(async { Ok(0) }));
+        repo.expect_list().returning(move |_| {
+            let states = states.clone();
+            Box::pin(async move { Ok(states) })
+        });
+    }
+
+    #[tokio::test]
+    async fn plan_前回の同期時刻がなければ全件を送る() {
+        let repos = TestRepositories::default();
+        expect_states(&repos, vec![state("w1", "h1")]).await;
+
+        let usecase = RemoteShareSyncUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let plan = usecase
+            .plan(
+                "device-1",
+                None,
+                vec![hashed("w1", "h1"), hashed("w2", "h2")],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(plan.mode, RemoteShareSyncMode::Full);
+        assert_eq!(plan.works.len(), 2);
+        assert_eq!(plan.total_count, 2);
+        assert!(plan.removed_work_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn plan_変わった作品と消えた作品だけを送る() {
+        let repos = TestRepositories::default();
+        expect_states(
+            &repos,
+            vec![state("w1", "h1"), state("w2", "h2"), state("w3", "h3")],
+        )
+        .await;
+
+        let usecase = RemoteShareSyncUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let plan = usecase
+            .plan(
+                "device-1",
+                Some("2026-01-01T00:00:00.000Z".into()),
+                vec![
+                    hashed("w1", "h1"),
+                    hashed("w2", "h2-new"),
+                    hashed("w4", "h4"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(plan.mode, RemoteShareSyncMode::Delta);
+        assert_eq!(
+            plan.base_synced_at.as_deref(),
+            Some("2026-01-01T00:00:00.000Z")
+        );
+        let sent = plan
+            .works
+            .iter()
+            .map(|w| w.work.work_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(sent, vec!["w2", "w4"]);
+        assert_eq!(plan.removed_work_ids, vec!["w3".to_string()]);
+        assert_eq!(plan.total_count, 3);
+    }
+
+    #[tokio::test]
+    async fn record_synced_全件同期では送らなかった作品の記録を消す() {
+        let repos = TestRepositories::default();
+        {
+            let mut repo = repos.remote_share_state.lock().await;
+            repo.expect_list().returning(|_| {
+                Box::pin(async { Ok(vec![state("w1", "old"), state("gone", "h")]) })
+            });
+            repo.expect_delete()
+                .withf(|device_id, work_ids| device_id == "device-1" && work_ids == ["gone"])
+                .times(1)
+                .returning(|_, _| Box::pin(async { Ok(1) }));
+            repo.expect_upsert_synced()
+                .withf(|_, works| {
+                    works
+                        == [SyncedRemoteShareWork {
+                            work_id: "w1".into(),
+                            content_hash: "h1".into(),
+                            dirty: false,
+                        }]
+                })
+                .times(1)
+                .returning(|_, _| Box::pin(async { Ok(()) }));
+        }
+
+        let usecase = RemoteShareSyncUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        usecase
+            .record_synced(
+                "device-1",
+                &RemoteShareSyncPlan::full(vec![hashed("w1", "h1")]),
+                &HashSet::new(),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn record_synced_サムネイルを送れなかった作品は次も送る() {
+        let repos = TestRepositories::default();
+        {
+            let mut repo = repos.remote_share_state.lock().await;
+            repo.expect_list().times(0);
+            repo.expect_delete()
+                .withf(|_, work_ids| work_ids == ["w3"])
+                .times(1)
+                .returning(|_, _| Box::pin(async { Ok(1) }));
+            repo.expect_upsert_synced()
+                .withf(|_, works| {
+                    works
+                        .iter()
+                        .map(|w| (w.work_id.as_str(), w.dirty))
+                        .collect::<Vec<_>>()
+                        == vec![("w1", true), ("w2", false)]
+                })
+                .times(1)
+                .returning(|_, _| Box::pin(async { Ok(()) }));
+        }
+
+        let usecase = RemoteShareSyncUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let plan = RemoteShareSyncPlan {
+            mode: RemoteShareSyncMode::Delta,
+            base_synced_at: Some("2026-01-01T00:00:00.000Z".into()),
+            works: vec![hashed("w1", "h1"), hashed("w2", "h2")],
+            removed_work_ids: vec!["w3".into()],
+            total_count: 2,
+        };
+        usecase
+            .record_synced("device-1", &plan, &HashSet::from(["w1".to_string()]))
+            .await
+            .unwrap();
+    }
+}
@@ -9,6 +9,7 @@ mockall::mock! {
         type ExploredCacheRepo = domain::repository::explored_cache::MockExploredCacheRepository;
         type AllGameCacheRepo = domain::repository::all_game_cache::MockAllGameCacheRepository;
         type AppSettingsRepo = domain::repository::app_settings::MockAppSettingsRepository;
+        type ExtensionConfigRepo = domain::repository::extension_config::MockExtensionConfigRepository;
         type ImageQueueRepo = domain::repository::save_image_queue::MockImageSaveQueueRepository;
         type HostLogRepo = domain::repository::native_host_log::MockNativeHostLogRepository;
         type DmmWorkRepo = domain::repository::works::MockDmmWorkRepository;
@@ -20,11 +21,22 @@ mockall::mock! {
         type WorkLikeRepo = domain::repository::work_like::MockWorkLikeRepository;
         type WorkLinkPendingExeRepo = domain::work_link_pending_exe::MockWorkLinkPendingExeRepository;
         type ErogamescapeRepo = domain::repository::erogamescape::MockErogamescapeRepository;
+        type SyncSessionRepo = domain::repository::sync_session::MockSyncSessionRepository;
+        type RemoteShareStateRepo = domain::repository::remote_share_state::MockRemoteShareStateRepository;
+        type RemoteLaunchRepo = domain::repository::remote_launch::MockRemoteLaunchRepository;
+        type LaunchProfileRepo = domain::repository::launch_profile::MockLaunchProfileRepository;
+        type WineConfigRepo = domain::repository::wine_config::MockWineConfigRepository;
+        type SaveLocationRepo = domain::repository::save_location::MockSaveLocationRepository;
+        type WorkPlayStateRepo = domain::repository::work_play_state::MockWorkPlayStateRepository;
+        type WorkRouteRepo = domain::repository::work_route::MockWorkRouteRepository;
+        type WorkDuplicateRepo = domain::repository::work_duplicate::MockWorkDuplicateRepository;
+        type WorkOverrideRepo = domain::repository::work_override::MockWorkOverrideRepository;
         fn work(&self) -> domain::repository::works::MockWorkRepository;
         fn dmm_work(&self) -> domain::repository::works::MockDmmWorkRepository;
         fn dlsite_work(&self) -> domain::repository::works::MockDlsiteWorkRepository;
         fn all_game_cache(&self) -> domain::repository::all_game_cache::MockAllGameCacheRepository;
         fn app_settings(&self) -> domain::repository::app_settings::MockAppSettingsRepository;
+        fn extension_config(&self) -> domain::repository::extension_config::MockExtensionConfigRepository;
         fn explored_cache(&self) -> domain::repository::explored_cache::MockExploredCacheRepository;
         fn image_queue(&self) -> domain::repository::save_image_queue::MockImageSaveQueueRepository;
         fn host_log(&self) -> domain::repository::native_host_log::MockNativeHostLogRepository;
@@ -34,6 +46,16 @@ mockall::mock! {
         fn work_like(&self) -> domain::repository::work_like::MockWorkLikeRepository;
         fn work_link_pending_exe(&self) -> domain::work_link_pending_exe::MockWorkLinkPendingExeRepository;
         fn erogamescape(&self) -> domain::repository::erogamescape::MockErogamescapeRepository;
+        fn sync_session(&self) -> domain::repository::sync_session::MockSyncSessionRepository;
+        fn remote_share_state(&self) -> domain::repository::remote_share_state::MockRemoteShareStateRepository;
+        fn remote_launch(&self) -> domain::repository::remote_launch::MockRemoteLaunchRepository;
+        fn launch_profile(&self) -> domain::repository::launch_profile::MockLaunchProfileRepository;
+        fn wine_config(&self) -> domain::repository::wine_config::MockWineConfigRepository;
+        fn save_location(&self) -> domain::repository::save_location::MockSaveLocationRepository;
+        fn work_play_state(&self) -> domain::repository::work_play_state::MockWorkPlayStateRepository;
+        fn work_route(&self) -> domain::repository::work_route::MockWorkRouteRepository;
+        fn work_duplicate(&self) -> domain::repository::work_duplicate::MockWorkDuplicateRepository;
+        fn work_override(&self) -> domain::repository::work_override::MockWorkOverrideRepository;
     }
 }
 
@@ -43,6 +65,8 @@ pub struct TestRepositories {
     pub explored_cache: Arc<Mutex<domain::repository::explored_cache::MockExploredCacheRepository>>,
     pub all_game_cache: Arc<Mutex<domain::repository::all_game_cache::MockAllGameCacheRepository>>,
     pub app_settings: Arc<Mutex<domain::repository::app_settings::MockAppSettingsRepository>>,
+    pub extension_config:
+        Arc<Mutex<domain::repository::extension_config::MockExtensionConfigRepository>>,
     pub image_queue: Arc<Mutex<domain::repository::save_image_queue::MockImageSaveQueueRepository>>,
     pub host_log: Arc<Mutex<domain::repository::native_host_log::MockNativeHostLogRepository>>,
     pub dmm_work: Arc<Mutex<domain::repository::works::MockDmmWorkRepository>>,
@@ -57,6 +81,20 @@ pub struct TestRepositories {
     pub work_link_pending_exe:
         Arc<Mutex<domain::work_link_pending_exe::MockWorkLinkPendingExeRepository>>,
     pub erogamescape: Arc<Mutex<domain::repository::erogamescape::MockErogamescapeRepository>>,
+    pub sync_session: Arc<Mutex<domain::repository::sync_session::MockSyncSessionRepository>>,
+    pub remote_share_state:
+        Arc<Mutex<domain::repository::remote_share_state::MockRemoteShareStateRepository>>,
+    pub remote_launch: Arc<Mutex<domain::repository::remote_launch::MockRemoteLaunchRepository>>,
+    pub launch_profile: Arc<Mutex<domain::repository::launch_profile::MockLaunchProfileRepository>>,
+    pub wine_config: Arc<Mutex<domain::repository::wine_config::MockWineConfigRepository>>,
+    pub save_location: Arc<Mutex<domain::repository::save_location::MockSaveLocationRepository>>,
+    pub work_play_state:
+        Arc<Mutex<domain::repository::work_play_state::MockWorkPlayStateRepository>>,
+    pub work_route: Arc<Mutex<domain::repository::work_route::MockWorkRouteRepository>>,
+    pub work_duplicate:
+        Arc<Mutex<domain::repository::work_duplicate::MockWorkDuplicateRepository>>,
+    pub work_override:
+        Arc<Mutex<domain::repository::work_override::MockWorkOverrideRepository>>,
 }
 
 #[cfg(test)]
@@ -66,6 +104,7 @@ impl Default for TestRepositories {
             explored_cache: Arc::new(Mutex::new(Default::default())),
             all_game_cache: Arc::new(Mutex::new(Default::default())),
             app_settings: Arc::new(Mutex::new(Default::default())),
+            extension_config: Arc::new(Mutex::new(Default::default())),
             image_queue: Arc::new(Mutex::new(Default::default())),
             host_log: Arc::new(Mutex::new(Default::default())),
             dmm_work: Arc::new(Mutex::new(Default::default())),
@@ -77,6 +116,16 @@ impl Default for TestRepositories {
             work_like: Arc::new(Mutex::new(Default::default())),
             work_link_pending_exe: Arc::new(Mutex::new(Default::default())),
             erogamescape: Arc::new(Mutex::new(Default::default())),
+            sync_session: Arc::new(Mutex::new(Default::default())),
+            remote_share_state: Arc::new(Mutex::new(Default::default())),
+            remote_launch: Arc::new(Mutex::new(Default::default())),
+            launch_profile: Arc::new(Mutex::new(Default::default())),
+            wine_config: Arc::new(Mutex::new(Default::default())),
+            save_location: Arc::new(Mutex::new(Default::default())),
+            work_play_state: Arc::new(Mutex::new(Default::default())),
+            work_route: Arc::new(Mutex::new(Default::default())),
+            work_duplicate: Arc::new(Mutex::new(Default::default())),
+            work_override: Arc::new(Mutex::new(Default::default())),
         }
     }
 }
@@ -88,6 +137,7 @@ impl domain::repository::RepositoriesExt for TestRepositories {
     type DlsiteWorkRepo = TestRepositories;
     type AllGameCacheRepo = TestRepositories;
     type AppSettingsRepo = TestRepositories;
+    type ExtensionConfigRepo = TestRepositories;
     type ExploredCacheRepo = TestRepositories;
     type ImageQueueRepo = TestRepositories;
     type HostLogRepo = TestRepositories;
@@ -97,6 +147,16 @@ impl domain::repository::RepositoriesExt for TestRepositories {
     type WorkLnkRepo = TestRepositories;
     type WorkLikeRepo = TestRepositories;
     type WorkLinkPendingExeRepo = TestRepositories;
+    type SyncSessionRepo = TestRepositories;
+    type RemoteShareStateRepo = TestRepositories;
+    type RemoteLaunchRepo = TestRepositories;
+    type LaunchProfileRepo = TestRepositories;
+    type WineConfigRepo = TestRepositories;
+    type SaveLocationRepo = TestRepositories;
+    type WorkPlayStateRepo = TestRepositories;
+    type WorkRouteRepo = TestRepositories;
+    type WorkDuplicateRepo = TestRepositories;
+    type WorkOverrideRepo = TestRepositories;
     fn work(&self) -> Self::WorkRepo {
         self.clone()
     }
@@ -112,6 +172,39 @@ impl domain::repository::RepositoriesExt for TestRepositories {
     fn app_settings(&self) -> Self::AppSettingsRepo {
         self.clone()
     }
+    fn extension_config(&self) -> Self::ExtensionConfigRepo {
+        self.clone()
+    }
+    fn sync_session(&self) -> Self::SyncSessionRepo {
+        self.clone()
+    }
+    fn remote_share_state(&self) -> Self::RemoteShareStateRepo {
+        self.clone()
+    }
+    fn remote_launch(&self) -> Self::RemoteLaunchRepo {
+        self.clone()
+    }
+    fn launch_profile(&self) -> Self::LaunchProfileRepo {
+        self.clone()
+    }
+    fn wine_config(&self) -> Self::WineConfigRepo {
+        self.clone()
+    }
+    fn save_location(&self) -> Self::SaveLocationRepo {
+        self.clone()
+    }
+    fn work_play_state(&self) -> Self::WorkPlayStateRepo {
+        self.clone()
+    }
+    fn work_route(&self) -> Self::WorkRouteRepo {
+        self.clone()
+    }
+    fn work_duplicate(&self) -> Self::WorkDuplicateRepo {
+        self.clone()
+    }
+    fn work_override(&self) -> Self::WorkOverrideRepo {
+        self.clone()
+    }
     fn explored_cache(&self) -> Self::ExploredCacheRepo {
         self.clone()
     }
@@ -155,6 +248,18 @@ impl domain::repository::works::WorkRepository for TestRepositories {
     async fn list_all_details(&mut self) -> anyhow::Result<Vec<domain::works::WorkDetails>> {
         self.work.lock().await.list_all_details().await
     }
+    async fn query_details(
+        &mut self,
+        query: &domain::work_query::WorkQuery,
+    ) -> anyhow::Result<domain::work_query::WorkPage> {
+        self.work.lock().await.query_details(query).await
+    }
+    async fn count_facets(
+        &mut self,
+        filter: &domain::work_query::WorkListFilter,
+    ) -> anyhow::Result<domain::work_query::WorkFacets> {
+        self.work.lock().await.count_facets(filter).await
+    }
     async fn find_details_by_work_id(
         &mut self,
         work_id: domain::StrId<domain::works::Work>,
@@ -214,6 +319,30 @@ impl domain::repository::works::WorkRepository for TestRepositories {
             .await
     }
 
+    async fn list_work_ids_missing_thumbnail_variants(
+        &mut self,
+    ) -> anyhow::Result<Vec<domain::StrId<domain::works::Work>>> {
+        self.work
+            .lock()
+            .await
+            .list_work_ids_missing_thumbnail_variants()
+            .await
+    }
+
+    async fn replace_work_thumbnail_variants(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+        variants: Vec<domain::thumbnail::WorkThumbnailVariant>,
+        placeholder_color: Option<String>,
+        perceptual_hash: Option<u64>,
+    ) -> anyhow::Result<()> {
+        self.work
+            .lock()
+            .await
+            .replace_work_thumbnail_variants(work_id, variants, placeholder_color, perceptual_hash)
+            .await
+    }
+
     async fn update_last_play_at_by_work_id(
         &mut self,
         work_id: domain::StrId<domain::works::Work>,
@@ -237,6 +366,17 @@ impl domain::repository::works::WorkRepository for TestRepositories {
             .update_install_by_work_id(work_id, install_at, original_path)
             .await
     }
+    async fn update_install_engine_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+        engine: Option<domain::game_engine::GameEngine>,
+    ) -> anyhow::Result<()> {
+        self.work
+            .lock()
+            .await
+            .update_install_engine_by_work_id(work_id, engine)
+            .await
+    }
 }
 
 impl domain::repository::erogamescape::ErogamescapeRepository for TestRepositories {
@@ -297,6 +437,46 @@ impl domain::repository::works::DmmWorkRepository for TestRepositories {
     ) -> anyhow::Result<Option<domain::works::DmmWork>> {
         self.dmm_work.lock().await.find_by_work_id(work_id).await
     }
+    async fn count(&mut self) -> anyhow::Result<i64> {
+        self.dmm_work.lock().await.count().await
+    }
+    async fn find_existing_store_ids(
+        &mut self,
+        store_ids: &[String],
+    ) -> anyhow::Result<Vec<String>> {
+        self.dmm_work
+            .lock()
+            .await
+            .find_existing_store_ids(store_ids)
+            .await
+    }
+    async fn mark_seen(
+        &mut self,
+        store_ids: &[String],
+        seen_at: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<()> {
+        self.dmm_work
+            .lock()
+            .await
+            .mark_seen(store_ids, seen_at)
+            .await
+    }
+    async fn flag_missing_not_seen_since(
+        &mut self,
+        since: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<Vec<String>> {
+        self.dmm_work
+            .lock()
+            .await
+            .flag_missing_not_seen_since(since)
+            .await
+    }
+    async fn list_missing(&mut self) -> anyhow::Result<Vec<domain::works::MissingStoreWork>> {
+        self.dmm_work.lock().await.list_missing().await
+    }
+    async fn delete_missing(&mut self, store_ids: &[String]) -> anyhow::Result<u64> {
+        self.dmm_work.lock().await.delete_missing(store_ids).await
+    }
 }
 
 #[cfg(test)]
@@ -328,6 +508,50 @@ impl domain::repository::works::DlsiteWorkRepository for TestRepositories {
             .find_by_store_id(store_id)
             .await
     }
+    async fn count(&mut self) -> anyhow::Result<i64> {
+        self.dlsite_work.lock().await.count().await
+    }
+    async fn find_existing_store_ids(
+        &mut self,
+        store_ids: &[String],
+    ) -> anyhow::Result<Vec<String>> {
+        self.dlsite_work
+            .lock()
+            .await
+            .find_existing_store_ids(store_ids)
+            .await
+    }
+    async fn mark_seen(
+        &mut self,
+        store_ids: &[String],
+        seen_at: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<()> {
+        self.dlsite_work
+            .lock()
+            .await
+            .mark_seen(store_ids, seen_at)
+            .await
+    }
+    async fn flag_missing_not_seen_since(
+        &mut self,
+        since: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<Vec<String>> {
+        self.dlsite_work
+            .lock()
+            .await
+            .flag_missing_not_seen_since(since)
+            .await
+    }
+    async fn list_missing(&mut self) -> anyhow::Result<Vec<domain::works::MissingStoreWork>> {
+        self.dlsite_work.lock().await.list_missing().await
+    }
+    async fn delete_missing(&mut self, store_ids: &[String]) -> anyhow::Result<u64> {
+        self.dlsite_work
+            .lock()
+            .await
+            .delete_missing(store_ids)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -371,7 +595,11 @@ impl domain::repository::app_settings::AppSettingsRepository for TestRepositorie
         &mut self,
         settings: &domain::repository::app_settings::AppStorageSettings,
     ) -> anyhow::Result<()> {
-        self.app_settings.lock().await.set_storage_settings(settings).await
+        self.app_settings
+            .lock()
+            .await
+            .set_storage_settings(settings)
+            .await
     }
 }
 
@@ -407,6 +635,31 @@ impl domain::repository::save_image_queue::ImageSaveQueueRepository for TestRepo
     ) -> anyhow::Result<Vec<domain::save_image_queue::ImageSaveQueueRow>> {
         self.image_queue.lock().await.list(unfinished, limit).await
     }
+    async fn list_due(
+        &mut self,
+        limit: i64,
+    ) -> anyhow::Result<Vec<domain::save_image_queue::ImageSaveQueueRow>> {
+        self.image_queue.lock().await.list_due(limit).await
+    }
+    async fn list_dst_paths_not_succeeded(
+        &mut self,
+        preprocess: domain::save_image_queue::ImagePreprocess,
+    ) -> anyhow::Result<Vec<String>> {
+        self.image_queue
+            .lock()
+            .await
+            .list_dst_paths_not_succeeded(preprocess)
+            .await
+    }
+    async fn next_attempt_at(&mut self) -> anyhow::Result<Option<chrono::DateTime<chrono::Local>>> {
+        self.image_queue.lock().await.next_attempt_at().await
+    }
+    async fn list_failed(
+        &mut self,
+        limit: i64,
+    ) -> anyhow::Result<Vec<domain::save_image_queue::ImageSaveQueueRow>> {
+        self.image_queue.lock().await.list_failed(limit).await
+    }
     async fn count(&mut self, unfinished: bool) -> anyhow::Result<i64> {
         self.image_queue.lock().await.count(unfinished).await
     }
@@ -423,6 +676,30 @@ impl domain::repository::save_image_queue::ImageSaveQueueRepository for TestRepo
     ) -> anyhow::Result<()> {
         self.image_queue.lock().await.mark_failed(id, error).await
     }
+    async fn mark_retry(
+        &mut self,
+        id: domain::Id<domain::save_image_queue::ImageSaveQueueRow>,
+        error: &str,
+        delay: std::time::Duration,
+    ) -> anyhow::Result<()> {
+        self.image_queue
+            .lock()
+            .await
+            .mark_retry(id, error, delay)
+            .await
+    }
+    async fn requeue_failed(
+        &mut self,
+        ids: Vec<domain::Id<domain::save_image_queue::ImageSaveQueueRow>>,
+    ) -> anyhow::Result<u64> {
+        self.image_queue.lock().await.requeue_failed(ids).await
+    }
+    async fn discard_failed(
+        &mut self,
+        ids: Vec<domain::Id<domain::save_image_queue::ImageSaveQueueRow>>,
+    ) -> anyhow::Result<u64> {
+        self.image_queue.lock().await.discard_failed(ids).await
+    }
 }
 
 #[cfg(test)]
@@ -439,25 +716,35 @@ impl domain::repository::native_host_log::NativeHostLogRepository for TestReposi
             .insert_log(level, typ, message)
             .await
     }
+    async fn insert(&mut self, log: &domain::native_host_log::NewHostLog) -> anyhow::Result<()> {
+        self.host_log.lock().await.insert(log).await
+    }
     async fn list_logs(
         &mut self,
         limit: i64,
         offset: i64,
-        level: Option<domain::native_host_log::HostLogLevel>,
-        typ: Option<domain::native_host_log::HostLogType>,
+        filter: &domain::native_host_log::HostLogFilter,
     ) -> anyhow::Result<Vec<domain::native_host_log::NativeHostLogRow>> {
         self.host_log
             .lock()
             .await
-            .list_logs(limit, offset, level, typ)
+            .list_logs(limit, offset, filter)
             .await
     }
     async fn count_logs(
         &mut self,
-        level: Option<domain::native_host_log::HostLogLevel>,
-        typ: Option<domain::native_host_log::HostLogType>,
+        filter: &domain::native_host_log::HostLogFilter,
     ) -> anyhow::Result<i64> {
-        self.host_log.lock().await.count_logs(level, typ).await
+        self.host_log.lock().await.count_logs(filter).await
+    }
+    async fn delete_older_than(
+        &mut self,
+        before: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<u64> {
+        self.host_log.lock().await.delete_older_than(before).await
+    }
+    async fn delete_exceeding(&mut self, keep: i64) -> anyhow::Result<u64> {
+        self.host_log.lock().await.delete_exceeding(keep).await
     }
 }
 
@@ -650,3 +937,353 @@ impl TestRepositories {
         self.all_game_cache = Arc::new(tokio::sync::Mutex::new(repo));
     }
 }
+
+#[cfg(test)]
+impl domain::repository::extension_config::ExtensionConfigRepository for TestRepositories {
+    async fn get(&mut self) -> anyhow::Result<Option<domain::extension::ExtensionConfig>> {
+        self.extension_config.lock().await.get().await
+    }
+    async fn upsert(&mut self, config: &domain::extension::ExtensionConfig) -> anyhow::Result<()> {
+        self.extension_config.lock().await.upsert(config).await
+    }
+}
+
+#[cfg(test)]
+impl domain::repository::sync_session::SyncSessionRepository for TestRepositories {
+    async fn get(
+        &mut self,
+        id: &domain::StrId<domain::sync_session::SyncSession>,
+    ) -> anyhow::Result<Option<domain::sync_session::SyncSession>> {
+        self.sync_session.lock().await.get(id).await
+    }
+    async fn insert(
+        &mut self,
+        session: &domain::sync_session::NewSyncSession,
+    ) -> anyhow::Result<()> {
+        self.sync_session.lock().await.insert(session).await
+    }
+    async fn list_chunks(
+        &mut self,
+        id: &domain::StrId<domain::sync_session::SyncSession>,
+    ) -> anyhow::Result<Vec<domain::sync_session::SyncSessionChunk>> {
+        self.sync_session.lock().await.list_chunks(id).await
+    }
+    async fn insert_chunk(
+        &mut self,
+        id: &domain::StrId<domain::sync_session::SyncSession>,
+        chunk: &domain::sync_session::SyncSessionChunk,
+    ) -> anyhow::Result<()> {
+        self.sync_session.lock().await.insert_chunk(id, chunk).await
+    }
+    async fn mark_committed(
+        &mut self,
+        id: &domain::StrId<domain::sync_session::SyncSession>,
+    ) -> anyhow::Result<()> {
+        self.sync_session.lock().await.mark_committed(id).await
+    }
+    async fn delete_stale(
+        &mut self,
+        before: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<u64> {
+        self.sync_session.lock().await.delete_stale(before).await
+    }
+}
+
+#[cfg(test)]
+impl domain::repository::remote_share_state::RemoteShareStateRepository for TestRepositories {
+    async fn list(
+        &mut self,
+        device_id: &str,
+    ) -> anyhow::Result<Vec<domain::remote_share::RemoteShareWorkState>> {
+        self.remote_share_state.lock().await.list(device_id).await
+    }
+    async fn upsert_synced(
+        &mut self,
+        device_id: &str,
+        works: &[domain::remote_share::SyncedRemoteShareWork],
+    ) -> anyhow::Result<()> {
+        self.remote_share_state
+            .lock()
+            .await
+            .upsert_synced(device_id, works)
+            .await
+    }
+    async fn delete(&mut self, device_id: &str, work_ids: &[String]) -> anyhow::Result<u64> {
+        self.remote_share_state
+            .lock()
+            .await
+            .delete(device_id, work_ids)
+            .await
+    }
+    async fn mark_all_dirty(&mut self, device_id: &str) -> anyhow::Result<u64> {
+        self.remote_share_state
+            .lock()
+            .await
+            .mark_all_dirty(device_id)
+            .await
+    }
+    async fn delete_other_devices(&mut self, device_id: &str) -> anyhow::Result<u64> {
+        self.remote_share_state
+            .lock()
+            .await
+            .delete_other_devices(device_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+impl domain::repository::remote_launch::RemoteLaunchRepository for TestRepositories {
+    async fn get_settings(
+        &mut self,
+    ) -> anyhow::Result<domain::remote_launch::RemoteLaunchSettings> {
+        self.remote_launch.lock().await.get_settings().await
+    }
+    async fn set_settings(
+        &mut self,
+        settings: &domain::remote_launch::RemoteLaunchSettings,
+    ) -> anyhow::Result<()> {
+        self.remote_launch.lock().await.set_settings(settings).await
+    }
+    async fn add_allowed_work(&mut self, work_id: &str) -> anyhow::Result<()> {
+        self.remote_launch
+            .lock()
+            .await
+            .add_allowed_work(work_id)
+            .await
+    }
+    async fn insert_audit(
+        &mut self,
+        audit: &domain::remote_launch::NewRemoteLaunchAudit,
+    ) -> anyhow::Result<()> {
+        self.remote_launch.lock().await.insert_audit(audit).await
+    }
+    async fn list_audits(
+        &mut self,
+        limit: i64,
+        offset: i64,
+    ) -> anyhow::Result<Vec<domain::remote_launch::RemoteLaunchAudit>> {
+        self.remote_launch
+            .lock()
+            .await
+            .list_audits(limit, offset)
+            .await
+    }
+}
+
+impl domain::repository::launch_profile::LaunchProfileRepository for TestRepositories {
+    async fn list_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<Vec<domain::launch_profile::LaunchProfile>> {
+        self.launch_profile
+            .lock()
+            .await
+            .list_by_work_id(work_id)
+            .await
+    }
+    async fn find_by_id(
+        &mut self,
+        id: domain::Id<domain::launch_profile::LaunchProfile>,
+    ) -> anyhow::Result<Option<domain::launch_profile::LaunchProfile>> {
+        self.launch_profile.lock().await.find_by_id(id).await
+    }
+    async fn find_default_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<Option<domain::launch_profile::LaunchProfile>> {
+        self.launch_profile
+            .lock()
+            .await
+            .find_default_by_work_id(work_id)
+            .await
+    }
+    async fn insert(
+        &mut self,
+        profile: &domain::launch_profile::NewLaunchProfile,
+    ) -> anyhow::Result<domain::Id<domain::launch_profile::LaunchProfile>> {
+        self.launch_profile.lock().await.insert(profile).await
+    }
+    async fn update(
+        &mut self,
+        id: domain::Id<domain::launch_profile::LaunchProfile>,
+        profile: &domain::launch_profile::NewLaunchProfile,
+    ) -> anyhow::Result<()> {
+        self.launch_profile.lock().await.update(id, profile).await
+    }
+    async fn delete(
+        &mut self,
+        id: domain::Id<domain::launch_profile::LaunchProfile>,
+    ) -> anyhow::Result<()> {
+        self.launch_profile.lock().await.delete(id).await
+    }
+}
+
+impl domain::repository::wine_config::WineConfigRepository for TestRepositories {
+    async fn find_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<Option<domain::wine::WineConfig>> {
+        self.wine_config.lock().await.find_by_work_id(work_id).await
+    }
+    async fn upsert(&mut self, config: &domain::wine::WineConfig) -> anyhow::Result<()> {
+        self.wine_config.lock().await.upsert(config).await
+    }
+    async fn delete_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<()> {
+        self.wine_config.lock().await.delete_by_work_id(work_id).await
+    }
+}
+
+impl domain::repository::save_location::SaveLocationRepository for TestRepositories {
+    async fn list_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<Vec<domain::save_data::SaveLocation>> {
+        self.save_location
+            .lock()
+            .await
+            .list_by_work_id(work_id)
+            .await
+    }
+    async fn insert(
+        &mut self,
+        location: &domain::save_data::NewSaveLocation,
+    ) -> anyhow::Result<domain::Id<domain::save_data::SaveLocation>> {
+        self.save_location.lock().await.insert(location).await
+    }
+    async fn delete(
+        &mut self,
+        id: domain::Id<domain::save_data::SaveLocation>,
+    ) -> anyhow::Result<()> {
+        self.save_location.lock().await.delete(id).await
+    }
+    async fn delete_detected_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<()> {
+        self.save_location
+            .lock()
+            .await
+            .delete_detected_by_work_id(work_id)
+            .await
+    }
+}
+
+impl domain::repository::work_play_state::WorkPlayStateRepository for TestRepositories {
+    async fn find_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<Option<domain::play_status::WorkPlayState>> {
+        self.work_play_state
+            .lock()
+            .await
+            .find_by_work_id(work_id)
+            .await
+    }
+    async fn upsert(&mut self, state: &domain::play_status::WorkPlayState) -> anyhow::Result<()> {
+        self.work_play_state.lock().await.upsert(state).await
+    }
+    async fn insert_status_change(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+        status: domain::play_status::PlayStatus,
+        changed_at: chrono::DateTime<chrono::Local>,
+    ) -> anyhow::Result<domain::Id<domain::play_status::PlayStatusChange>> {
+        self.work_play_state
+            .lock()
+            .await
+            .insert_status_change(work_id, status, changed_at)
+            .await
+    }
+    async fn list_status_changes_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<Vec<domain::play_status::PlayStatusChange>> {
+        self.work_play_state
+            .lock()
+            .await
+            .list_status_changes_by_work_id(work_id)
+            .await
+    }
+}
+
+impl domain::repository::work_route::WorkRouteRepository for TestRepositories {
+    async fn list_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<Vec<domain::play_status::WorkRoute>> {
+        self.work_route.lock().await.list_by_work_id(work_id).await
+    }
+    async fn insert(
+        &mut self,
+        route: &domain::play_status::NewWorkRoute,
+    ) -> anyhow::Result<domain::Id<domain::play_status::WorkRoute>> {
+        self.work_route.lock().await.insert(route).await
+    }
+    async fn update_completed_at(
+        &mut self,
+        id: domain::Id<domain::play_status::WorkRoute>,
+        completed_at: Option<chrono::DateTime<chrono::Local>>,
+    ) -> anyhow::Result<()> {
+        self.work_route
+            .lock()
+            .await
+            .update_completed_at(id, completed_at)
+            .await
+    }
+    async fn delete(
+        &mut self,
+        id: domain::Id<domain::play_status::WorkRoute>,
+    ) -> anyhow::Result<()> {
+        self.work_route.lock().await.delete(id).await
+    }
+}
+
+impl domain::repository::work_duplicate::WorkDuplicateRepository for TestRepositories {
+    async fn list_duplicate_sources(
+        &mut self,
+    ) -> anyhow::Result<Vec<domain::work_duplicate::DuplicateSource>> {
+        self.work_duplicate.lock().await.list_duplicate_sources().await
+    }
+    async fn merge(
+        &mut self,
+        survivor: domain::StrId<domain::works::Work>,
+        duplicate: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<domain::work_duplicate::WorkMergeResult> {
+        self.work_duplicate
+            .lock()
+            .await
+            .merge(survivor, duplicate)
+            .await
+    }
+}
+
+impl domain::repository::work_override::WorkOverrideRepository for TestRepositories {
+    async fn get_by_work_id(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+    ) -> anyhow::Result<domain::work_override::WorkOverrides> {
+        self.work_override.lock().await.get_by_work_id(work_id).await
+    }
+    async fn set(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+        field: domain::work_override::WorkOverrideField,
+        value: &str,
+    ) -> anyhow::Result<()> {
+        self.work_override
+            .lock()
+            .await
+            .set(work_id, field, value)
+            .await
+    }
+    async fn clear(
+        &mut self,
+        work_id: domain::StrId<domain::works::Work>,
+        field: domain::work_override::WorkOverrideField,
+    ) -> anyhow::Result<()> {
+        self.work_override.lock().await.clear(work_id, field).await
+    }
+}
@@ -0,0 +1,408 @@
+//! 作品ごとのセーブデータの場所の管理と、zip でのバックアップ・復元
+
+use std::fs;
+use std::io::Write as _;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use derive_new::new;
+use domain::game_engine::GameEngine;
+use domain::repository::{
+    manager::RepositoryManager, save_location::SaveLocationRepository as _,
+    works::WorkRepository as _, RepositoriesExt,
+};
+use domain::save_data::{
+    detect_save_paths, expand_save_path, parse_save_rules, snapshot_file_name,
+    validate_save_location, NewSaveLocation, SaveLocation, SaveLocationSource, SaveRule,
+    SaveSnapshot, SnapshotManifest, SnapshotReason, INSTALL_DIR_PLACEHOLDER,
+    SNAPSHOT_MANIFEST_NAME,
+};
+use domain::service::save_path_resolver::SavePathResolver;
+use domain::{Id, StrId};
+
+#[derive(new)]
+pub struct SaveBackupUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    resolver: Arc<dyn SavePathResolver>,
+    #[new(default)]
+    _marker: PhantomData<R>,
+}
+
+/// セーブデータの場所を展開するのに使う、インストールの情報
+struct Install {
+    dir: PathBuf,
+    exe_name: String,
+    engine: Option<GameEngine>,
+}
+
+impl<M, R> SaveBackupUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    pub async fn list_locations(&self, work_id: String) -> anyhow::Result<Vec<SaveLocation>> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move { repos.save_location().list_by_work_id(work_id).await })
+            })
+            .await
+    }
+
+    /// インストール先から場所を見つけ直す。手動で足した場所は残す
+    pub async fn detect_locations(&self, work_id: String) -> anyhow::Result<Vec<SaveLocation>> {
+        let install = self
+            .find_install(&work_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("install path of work {} is unknown", work_id))?;
+        let paths = detect_save_paths(
+            &install.dir,
+            install.engine,
+            &install.exe_name,
+            &self.load_rules(),
+        );
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    repos
+                        .save_location()
+                        .delete_detected_by_work_id(work_id.clone())
+                        .await?;
+                    for path in paths {
+                        repos
+                            .save_location()
+                            .insert(&NewSaveLocation {
+                                work_id: work_id.clone(),
+                                path,
+                                source: SaveLocationSource::Detected,
+                            })
+                            .await?;
+                    }
+                    repos.save_location().list_by_work_id(work_id).await
+                })
+            })
+            .await
+    }
+
+    /// `{install}` や `%APPDATA%` のような環境変数を使って書ける
+    pub async fn add_location(
+        &self,
+        work_id: String,
+        path: String,
+    ) -> anyhow::Result<Vec<SaveLocation>> {
+        let path = path.trim().to_string();
+        if path.is_empty() {
+            anyhow::bail!("save location path is empty");
+        }
+        validate_save_location(&path)?;
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    repos
+                        .save_location()
+                        .insert(&NewSaveLocation {
+                            work_id: work_id.clone(),
+                            path,
+                            source: SaveLocationSource::Manual,
+                        })
+                        .await?;
+                    repos.save_location().list_by_work_id(work_id).await
+                })
+            })
+            .await
+    }
+
+    pub async fn delete_location(&self, id: i32) -> anyhow::Result<()> {
+        self.manager
+            .run(|repos| Box::pin(async move { repos.save_location().delete(Id::new(id)).await }))
+            .await
+    }
+
+    /// 登録済みの場所をまとめて1つの zip にする
+    pub async fn create_snapshot(
+        &self,
+        work_id: String,
+        reason: SnapshotReason,
+    ) -> anyhow::Result<SaveSnapshot> {
+        let locations = self
+            .list_locations(work_id.clone())
+            .await?
+            .into_iter()
+            .map(|location| location.path)
+            .collect::<Vec<_>>();
+        if locations.is_empty() {
+            anyhow::bail!("work {} has no save locations", work_id);
+        }
+        self.snapshot_locations(work_id, locations, reason).await
+    }
+
+    /// 新しい順に返す。読めない zip は飛ばす
+    pub fn list_snapshots(&self, work_id: String) -> anyhow::Result<Vec<SaveSnapshot>> {
+        let dir = PathBuf::from(self.resolver.save_backups_dir(&work_id));
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("zip") {
+                continue;
+            }
+            match read_snapshot(&path) {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(e) => log::warn!("failed to read save snapshot {}: {}", path.display(), e),
+            }
+        }
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.manifest.created_at));
+        Ok(snapshots)
+    }
+
+    /// zip に入っているファイルをそれぞれの場所へ上書きで書き戻す。zip にないファイルは消さない。
+    /// 書き戻す前の状態も自動でバックアップする
+    pub async fn restore_snapshot(&self, work_id: String, file_name: String) -> anyhow::Result<()> {
+        let archive_path = self.snapshot_path(&work_id, &file_name)?;
+        let snapshot = read_snapshot(&archive_path)?;
+        if snapshot.manifest.work_id != work_id {
+            anyhow::bail!("save snapshot {} belongs to another work", file_name);
+        }
+
+        let install = self.find_install(&work_id).await?;
+        let targets = resolve_locations(&snapshot.manifest.locations, install.as_ref());
+        // インストール先ごと書き換えるような場所は、手で編集した zip かもしれないので断る
+        if let Some(install) = install.as_ref() {
+            if let Some(target) = targets
+                .iter()
+                .flatten()
+                .find(|target| install.dir.starts_with(target))
+            {
+                anyhow::bail!(
+                    "save location {} contains the install directory",
+                    target.display()
+                );
+            }
+        }
+        if targets
+            .iter()
+            .any(|target| target.as_ref().is_some_and(|t| t.exists()))
+        {
+            self.snapshot_locations(
+                work_id,
+                snapshot.manifest.locations.clone(),
+                SnapshotReason::BeforeRestore,
+            )
+            .await?;
+        }
+
+        tokio::task::spawn_blocking(move || extract_snapshot(&archive_path, &targets)).await?
+    }
+
+    pub fn delete_snapshot(&self, work_id: String, file_name: String) -> anyhow::Result<()> {
+        Ok(fs::remove_file(self.snapshot_path(&work_id, &file_name)?)?)
+    }
+
+    async fn snapshot_locations(
+        &self,
+        work_id: String,
+        locations: Vec<String>,
+        reason: SnapshotReason,
+    ) -> anyhow::Result<SaveSnapshot> {
+        let install = self.find_install(&work_id).await?;
+        let sources = resolve_locations(&locations, install.as_ref());
+        let created_at = chrono::Local::now();
+        let archive_path = PathBuf::from(self.resolver.save_backups_dir(&work_id))
+            .join(snapshot_file_name(created_at, reason));
+        let manifest = SnapshotManifest {
+            work_id,
+            created_at,
+            reason,
+            locations,
+        };
+
+        let path = archive_path.clone();
+        tokio::task::spawn_blocking(move || write_snapshot(&path, &manifest, &sources)).await??;
+        read_snapshot(&archive_path)
+    }
+
+    /// バックアップフォルダの外を指せないように、ファイル名だけを受け付ける
+    fn snapshot_path(&self, work_id: &str, file_name: &str) -> anyhow::Result<PathBuf> {
+        if Path::new(file_name).file_name().and_then(|n| n.to_str()) != Some(file_name) {
+            anyhow::bail!("invalid save snapshot name: {}", file_name);
+        }
+        Ok(PathBuf::from(self.resolver.save_backups_dir(work_id)).join(file_name))
+    }
+
+    async fn find_install(&self, work_id: &str) -> anyhow::Result<Option<Install>> {
+        let work_id = StrId::new(work_id.to_string());
+        let details = self
+            .manager
+            .run(|repos| {
+                Box::pin(async move { repos.work().find_details_by_work_id(work_id).await })
+            })
+            .await?;
+        let Some(details) = details else {
+            return Ok(None);
+        };
+        let Some(original_path) = details.original_path else {
+            return Ok(None);
+        };
+        // 手動で登録した作品はショートカットのパスが入っているので、リンク先を使う
+        let exe_path = if original_path.to_lowercase().ends_with(".lnk") {
+            match domain::shortcut::read_metadata(&original_path) {
+                Ok(metadata) => metadata.path,
+                Err(e) => {
+                    log::warn!("failed to read shortcut {}: {}", original_path, e);
+                    original_path
+                }
+            }
+        } else {
+            original_path
+        };
+        let exe_path = PathBuf::from(exe_path);
+        let Some(dir) = exe_path.parent() else {
+            return Ok(None);
+        };
+        Ok(Some(Install {
+            dir: dir.to_path_buf(),
+            exe_name: exe_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            engine: details.engine,
+        }))
+    }
+
+    fn load_rules(&self) -> Vec<SaveRule> {
+        let path = self.resolver.save_rules_path();
+        let Ok(json) = fs::read_to_string(&path) else {
+            return vec![];
+        };
+        parse_save_rules(&json).unwrap_or_else(|e| {
+            log::warn!("failed to parse save rules {}: {}", path, e);
+            vec![]
+        })
+    }
+}
+
+/// 場所の書き方を実際のフォルダにする。インストール先がわからない `{install}` や知らない環境変数は None
+fn resolve_locations(locations: &[String], install: Option<&Install>) -> Vec<Option<PathBuf>> {
+    locations
+        .iter()
+        .map(|template| match install {
+            Some(install) => expand_save_path(template, &install.dir),
+            None if template.contains(INSTALL_DIR_PLACEHOLDER) => None,
+            None => expand_save_path(template, Path::new("")),
+        })
+        .collect()
+}
+
+fn write_snapshot(
+    archive_path: &Path,
+    manifest: &SnapshotManifest,
+    sources: &[Option<PathBuf>],
+) -> anyhow::Result<()> {
+    // 途中で失敗しても壊れた zip が一覧に出ないよう、別名で書いてから置き換える
+    let tmp_path = archive_path.with_extension("zip.tmp");
+    let file = fs::File::create(&tmp_path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(SNAPSHOT_MANIFEST_NAME, options)?;
+    zip.write_all(serde_json::to_string_pretty(manifest)?.as_bytes())?;
+    for (index, source) in sources.iter().enumerate() {
+        let Some(source) = source.as_ref().filter(|s| s.is_dir()) else {
+            log::warn!(
+                "save location {} is not found, skipped",
+                manifest.locations[index]
+            );
+            continue;
+        };
+        for entry in walkdir::WalkDir::new(source).min_depth(1) {
+            let entry = entry?;
+            let relative = entry
+                .path()
+                .strip_prefix(source)?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            let name = format!("{}/{}", index, relative);
+            if entry.file_type().is_dir() {
+                zip.add_directory(name, options)?;
+            } else if entry.file_type().is_file() {
+                zip.start_file(name, options)?;
+                std::io::copy(&mut fs::File::open(entry.path())?, &mut zip)?;
+            }
+        }
+    }
+    zip.finish()?;
+    fs::rename(&tmp_path, archive_path)?;
+    Ok(())
+}
+
+fn read_snapshot(archive_path: &Path) -> anyhow::Result<SaveSnapshot> {
+    let file = fs::File::open(archive_path)?;
+    let size = file.metadata()?.len();
+    let mut archive = zip::ZipArchive::new(file)?;
+    let manifest: SnapshotManifest =
+        serde_json::from_reader(archive.by_name(SNAPSHOT_MANIFEST_NAME)?)?;
+    Ok(SaveSnapshot {
+        file_name: archive_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: archive_path.to_string_lossy().to_string(),
+        size,
+        manifest,
+    })
+}
+
+fn extract_snapshot(archive_path: &Path, targets: &[Option<PathBuf>]) -> anyhow::Result<()> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(archive_path)?)?;
+    // `..` や絶対パスを含む名前は enclosed_name が None を返すので飛ばす
+    let entries = (0..archive.len())
+        .filter_map(|i| {
+            let entry = archive.by_index(i).ok()?;
+            let name = entry.enclosed_name()?.to_path_buf();
+            let index = name
+                .components()
+                .next()?
+                .as_os_str()
+                .to_str()?
+                .parse::<usize>()
+                .ok()?;
+            let relative = name.components().skip(1).collect::<PathBuf>();
+            Some((i, index, relative))
+        })
+        .collect::<Vec<_>>();
+
+    for (index, target) in targets.iter().enumerate() {
+        if target.is_none() && entries.iter().any(|(_, i, _)| *i == index) {
+            log::warn!("save location #{} cannot be resolved, skipped", index);
+        }
+    }
+
+    for (i, index, relative) in entries {
+        let Some(Some(target)) = targets.get(index) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let mut entry = archive.by_index(i)?;
+        let out_path = target.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+        } else {
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            std::io::copy(&mut entry, &mut fs::File::create(&out_path)?)?;
+        }
+    }
+    Ok(())
+}
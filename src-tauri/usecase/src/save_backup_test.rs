@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use domain::game_engine::GameEngine;
+    use domain::save_data::{SaveLocation, SaveLocationSource, SnapshotReason};
+    use domain::service::save_path_resolver::SavePathResolver;
+    use domain::works::{Work, WorkDetails};
+    use domain::{Id, StrId};
+    use tempfile::TempDir;
+
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+    use crate::save_backup::SaveBackupUseCase;
+
+    struct TestResolver {
+        root: PathBuf,
+    }
+
+    impl SavePathResolver for TestResolver {
+        fn root_dir(&self) -> String {
+            self.root.to_string_lossy().to_string()
+        }
+    }
+
+    fn usecase(
+        repos: &TestRepositories,
+        root: &Path,
+    ) -> SaveBackupUseCase<TestRepositoryManager, TestRepositories> {
+        SaveBackupUseCase::new(
+            Arc::new(TestRepositoryManager::new(repos.clone())),
+            Arc::new(TestResolver {
+                root: root.to_path_buf(),
+            }),
+        )
+    }
+
+    async fn expect_installed(repos: &TestRepositories, exe_path: &Path) {
+        let mut details = WorkDetails::new(
+            Work::new(StrId::new("w".into()), "W".into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        details.original_path = Some(exe_path.to_string_lossy().to_string());
+        details.engine = Some(GameEngine::Kirikiri);
+        repos
+            .work
+            .lock()
+            .await
+            .expect_find_details_by_work_id()
+            .returning(move |_| {
+                let details = details.clone();
+                Box::pin(async move { Ok(Some(details)) })
+            });
+    }
+
+    async fn expect_locations(repos: &TestRepositories, paths: &[&str]) {
+        let locations = paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| SaveLocation {
+                id: Id::new(i as i32 + 1),
+                work_id: StrId::new("w".into()),
+                path: path.to_string(),
+                source: SaveLocationSource::Detected,
+            })
+            .collect::<Vec<_>>();
+        repos
+            .save_location
+            .lock()
+            .await
+            .expect_list_by_work_id()
+            .returning(move |_| {
+                let locations = locations.clone();
+                Box::pin(async move { Ok(locations) })
+            });
+    }
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[tokio::test]
+    async fn detect_locations_エンジンの慣習から見つけた場所で置き換える() {
+        let temp = TempDir::new().unwrap();
+        let install_dir = temp.path().join("install");
+        write(&install_dir.join("data.xp3"), "");
+        write(&install_dir.join("SaveData/data0.bmp"), "");
+        let repos = TestRepositories::default();
+        expect_installed(&repos, &install_dir.join("game.exe")).await;
+        expect_locations(&repos, &["{install}/savedata"]).await;
+        {
+            let mut save_location = repos.save_location.lock().await;
+            save_location
+                .expect_delete_detected_by_work_id()
+                .times(1)
+                .returning(|_| Box::pin(async { Ok(()) }));
+            save_location
+                .expect_insert()
+                .withf(|location| {
+                    location.path == "{install}/savedata"
+                        && location.source == SaveLocationSource::Detected
+                })
+                .times(1)
+                .returning(|_| Box::pin(async { Ok(Id::new(1)) }));
+        }
+
+        let locations = usecase(&repos, &temp.path().join("root"))
+            .detect_locations("w".into())
+            .await
+            .unwrap();
+
+        assert_eq!(locations.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn restore_snapshot_バックアップした時点のファイルを書き戻し直前の状態も残す() {
+        let temp = TempDir::new().unwrap();
+        let install_dir = temp.path().join("install");
+        let save_file = install_dir.join("savedata/slot/data0.sav");
+        write(&save_file, "before");
+        let repos = TestRepositories::default();
+        expect_installed(&repos, &install_dir.join("game.exe")).await;
+        expect_locations(&repos, &["{install}/savedata", "{install}/missing"]).await;
+        let usecase = usecase(&repos, &temp.path().join("root"));
+
+        let snapshot = usecase
+            .create_snapshot("w".into(), SnapshotReason::Manual)
+            .await
+            .unwrap();
+        write(&save_file, "after");
+        write(&install_dir.join("savedata/new.sav"), "new");
+        usecase
+            .restore_snapshot("w".into(), snapshot.file_name.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read_to_string(&save_file).unwrap(), "before");
+        assert!(install_dir.join("savedata/new.sav").exists());
+        assert!(!install_dir.join("missing").exists());
+        let snapshots = usecase.list_snapshots("w".into()).unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].manifest.reason, SnapshotReason::BeforeRestore);
+        assert_eq!(snapshots[1].file_name, snapshot.file_name);
+    }
+
+    #[tokio::test]
+    async fn restore_snapshot_インストール先を含む場所には書き戻さない() {
+        let temp = TempDir::new().unwrap();
+        let games_dir = temp.path().join("games");
+        let install_dir = games_dir.join("install");
+        let exe = install_dir.join("game.exe");
+        write(&exe, "exe");
+        let repos = TestRepositories::default();
+        expect_installed(&repos, &exe).await;
+        expect_locations(&repos, &[&games_dir.to_string_lossy()]).await;
+        let usecase = usecase(&repos, &temp.path().join("root"));
+        let snapshot = usecase
+            .create_snapshot("w".into(), SnapshotReason::Manual)
+            .await
+            .unwrap();
+        write(&exe, "updated");
+
+        let result = usecase
+            .restore_snapshot("w".into(), snapshot.file_name)
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&exe).unwrap(), "updated");
+    }
+
+    #[tokio::test]
+    async fn add_location_広すぎる場所や外へ出る場所は登録しない() {
+        let temp = TempDir::new().unwrap();
+        let repos = TestRepositories::default();
+        repos.save_location.lock().await.expect_insert().never();
+        let usecase = usecase(&repos, temp.path());
+
+        for path in [
+            "{install}",
+            "%USERPROFILE%",
+            "C:\\",
+            "savedata",
+            "{install}/../..",
+        ] {
+            let result = usecase.add_location("w".into(), path.into()).await;
+            assert!(result.is_err(), "case: {path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn restore_snapshot_バックアップフォルダの外は指定できない() {
+        let temp = TempDir::new().unwrap();
+        let repos = TestRepositories::default();
+
+        let result = usecase(&repos, temp.path())
+            .restore_snapshot("w".into(), "../other/backup.zip".into())
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,66 @@
+//! ライブラリ全体の同期で見えなくなったストアの紐付けを確認・整理する
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use derive_new::new;
+use domain::repository::{
+    manager::RepositoryManager,
+    works::{DlsiteWorkRepository as _, DmmWorkRepository as _},
+    RepositoriesExt,
+};
+use domain::sync_session::SyncStore;
+use domain::works::MissingStoreWork;
+
+#[derive(new)]
+pub struct StoreLibraryUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    #[new(default)]
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> StoreLibraryUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    /// 欠落の印が付いた紐付け。新しく印が付いたものから並ぶ
+    pub async fn list_missing(&self, store: SyncStore) -> anyhow::Result<Vec<MissingStoreWork>> {
+        self.manager
+            .run(|repos| {
+                Box::pin(async move {
+                    match store {
+                        SyncStore::Dmm => repos.dmm_work().list_missing().await,
+                        SyncStore::Dlsite => repos.dlsite_work().list_missing().await,
+                    }
+                })
+            })
+            .await
+    }
+
+    /// 欠落の印が付いた紐付けを消す。作品そのものは残す。
+    /// 同期で再び見つかって印が外れたものは消さない
+    pub async fn prune_missing(
+        &self,
+        store: SyncStore,
+        store_ids: Vec<String>,
+    ) -> anyhow::Result<u64> {
+        if store_ids.is_empty() {
+            return Ok(0);
+        }
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    match store {
+                        SyncStore::Dmm => repos.dmm_work().delete_missing(&store_ids).await,
+                        SyncStore::Dlsite => repos.dlsite_work().delete_missing(&store_ids).await,
+                    }
+                })
+            })
+            .await
+    }
+}
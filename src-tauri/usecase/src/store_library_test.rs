@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::sync::Arc;
+
+    use chrono::Local;
+    use domain::sync_session::SyncStore;
+    use domain::works::MissingStoreWork;
+    use domain::StrId;
+
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+    use crate::store_library::StoreLibraryUseCase;
+
+    fn missing(store_id: &str) -> MissingStoreWork {
+        MissingStoreWork {
+            work_id: StrId::new(format!("work-{store_id}")),
+            title: "Refunded".into(),
+            store_id: store_id.into(),
+            category: "maniax".into(),
+            missing_since: Local::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn list_missing_ストアごとのリポジトリから取得する() {
+        let repos = TestRepositories::default();
+        repos
+            .dlsite_work
+            .lock()
+            .await
+            .expect_list_missing()
+            .times(1)
+            .returning(|| Box::pin(async { Ok(vec![missing("RJ002")]) }));
+        repos.dmm_work.lock().await.expect_list_missing().never();
+
+        let usecase = StoreLibraryUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let rows = usecase.list_missing(SyncStore::Dlsite).await.unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].store_id, "RJ002");
+    }
+
+    #[tokio::test]
+    async fn prune_missing_指定した紐付けを消す() {
+        let repos = TestRepositories::default();
+        repos
+            .dmm_work
+            .lock()
+            .await
+            .expect_delete_missing()
+            .times(1)
+            .withf(|store_ids| store_ids == ["SID-1".to_string(), "SID-2".to_string()])
+            .returning(|_| Box::pin(async { Ok(1) }));
+
+        let usecase = StoreLibraryUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let deleted = usecase
+            .prune_missing(SyncStore::Dmm, vec!["SID-1".into(), "SID-2".into()])
+            .await
+            .unwrap();
+
+        assert_eq!(deleted, 1);
+    }
+
+    #[tokio::test]
+    async fn prune_missing_空ならリポジトリを呼ばない() {
+        let repos = TestRepositories::default();
+        repos.dmm_work.lock().await.expect_delete_missing().never();
+
+        let usecase = StoreLibraryUseCase::new(Arc::new(TestRepositoryManager::new(repos)));
+        let deleted = usecase.prune_missing(SyncStore::Dmm, vec![]).await.unwrap();
+
+        assert_eq!(deleted, 0);
+    }
+}
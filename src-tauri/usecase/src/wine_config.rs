@@ -0,0 +1,79 @@
+//! 作品ごとの Wine の設定の編集
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use derive_new::new;
+use domain::launch_profile::LaunchEnvVar;
+use domain::repository::{
+    manager::RepositoryManager, wine_config::WineConfigRepository as _, RepositoriesExt,
+};
+use domain::wine::WineConfig;
+use domain::StrId;
+
+#[derive(new)]
+pub struct WineConfigUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    #[new(default)]
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> WineConfigUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    pub async fn get(&self, work_id: String) -> anyhow::Result<Option<WineConfig>> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move { repos.wine_config().find_by_work_id(work_id).await })
+            })
+            .await
+    }
+
+    pub async fn save(&self, config: WineConfig) -> anyhow::Result<()> {
+        let config = normalize(config);
+        config.validate()?;
+        self.manager
+            .run(|repos| Box::pin(async move { repos.wine_config().upsert(&config).await }))
+            .await
+    }
+
+    /// 消すと既定の prefix の `wine` で起動する
+    pub async fn delete(&self, work_id: String) -> anyhow::Result<()> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move { repos.wine_config().delete_by_work_id(work_id).await })
+            })
+            .await
+    }
+}
+
+fn normalize(config: WineConfig) -> WineConfig {
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    WineConfig {
+        work_id: config.work_id,
+        runner: config.runner,
+        runner_path: non_empty(config.runner_path),
+        prefix: non_empty(config.prefix),
+        env: config
+            .env
+            .into_iter()
+            .map(|var| LaunchEnvVar {
+                key: var.key.trim().to_string(),
+                value: var.value,
+            })
+            .filter(|var| !var.key.is_empty())
+            .collect(),
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::sync::Arc;
+
+    use domain::wine::{WineConfig, WineRunner};
+    use domain::StrId;
+
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+    use crate::wine_config::WineConfigUseCase;
+
+    fn usecase(
+        repos: &TestRepositories,
+    ) -> WineConfigUseCase<TestRepositoryManager, TestRepositories> {
+        WineConfigUseCase::new(Arc::new(TestRepositoryManager::new(repos.clone())))
+    }
+
+    #[tokio::test]
+    async fn save_空の値は未指定として保存する() {
+        let repos = TestRepositories::default();
+        repos
+            .wine_config
+            .lock()
+            .await
+            .expect_upsert()
+            .withf(|config| {
+                config.runner == WineRunner::Umu
+                    && config.runner_path.is_none()
+                    && config.prefix.as_deref() == Some("/prefixes/w")
+                    && config.env.is_empty()
+            })
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        usecase(&repos)
+            .save(WineConfig {
+                work_id: StrId::new("w".into()),
+                runner: WineRunner::Umu,
+                runner_path: Some(" ".into()),
+                prefix: Some(" /prefixes/w ".into()),
+                env: vec![domain::launch_profile::LaunchEnvVar {
+                    key: " ".into(),
+                    value: "ignored".into(),
+                }],
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn save_protonはスクリプトがなければ保存しない() {
+        let repos = TestRepositories::default();
+        let result = usecase(&repos)
+            .save(WineConfig {
+                work_id: StrId::new("w".into()),
+                runner: WineRunner::Proton,
+                runner_path: None,
+                prefix: Some("/compat/w".into()),
+                env: vec![],
+            })
+            .await;
+        assert!(result.is_err());
+    }
+}
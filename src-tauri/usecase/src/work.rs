@@ -1,20 +1,27 @@
 use std::sync::Arc;
 
 use derive_new::new;
+use domain::file::LnkMetadata;
+use domain::launch_profile::{LaunchCommand, LaunchProfile};
 use domain::repository::work_parent_packs::WorkParentPacksRepository as _;
 use domain::repository::works::DmmWorkRepository as _;
 use domain::repository::{
-    manager::RepositoryManager, work_like::WorkLikeRepository, work_lnk::WorkLnkRepository,
-    works::WorkRepository, RepositoriesExt,
+    launch_profile::LaunchProfileRepository as _, manager::RepositoryManager,
+    wine_config::WineConfigRepository as _, work_like::WorkLikeRepository,
+    work_lnk::WorkLnkRepository, works::WorkRepository, RepositoriesExt,
 };
 use domain::service::work_registration::{
     ImageApply, ImageSource, ImageStrategy, RegisterWorkPath, UniqueWorkKey, WorkInsert,
     WorkRegistrationService,
 };
 use domain::windows::{shell_link::ShellLink as ShellLinkTrait, WindowsExt};
+use domain::wine::WineConfig;
+use domain::work_query::{WorkFacets, WorkListFilter, WorkPage, WorkQuery};
 use domain::works::WorkDetails;
 use std::marker::PhantomData;
 
+use crate::launch_profile::remember_run_as_admin;
+
 #[derive(new)]
 pub struct WorkUseCase<M, R, W, RS>
 where
@@ -43,6 +50,29 @@ where
             .await
     }
 
+    /// 絞り込みに合う作品だけを返す
+    pub async fn list_details(&self, filter: &WorkListFilter) -> anyhow::Result<Vec<WorkDetails>> {
+        let query = WorkQuery {
+            filter: filter.clone(),
+            ..Default::default()
+        };
+        Ok(self.query_details(&query).await?.items)
+    }
+
+    pub async fn query_details(&self, query: &WorkQuery) -> anyhow::Result<WorkPage> {
+        let query = query.clone();
+        self.manager
+            .run(|repos| Box::pin(async move { repos.work().query_details(&query).await }))
+            .await
+    }
+
+    pub async fn count_facets(&self, filter: &WorkListFilter) -> anyhow::Result<WorkFacets> {
+        let filter = filter.clone();
+        self.manager
+            .run(|repos| Box::pin(async move { repos.work().count_facets(&filter).await }))
+            .await
+    }
+
     pub async fn find_details_by_work_id(
         &self,
         work_id: String,
@@ -68,32 +98,68 @@ where
         Ok(list.into_iter().map(|e| (e.id.value, e.lnk_path)).collect())
     }
 
+    /// `profile_id` を省くと作品の既定の起動プロファイルで起動する。
+    /// `is_run_as_admin` を指定すると、その権限を起動プロファイルに覚えてから起動する
     pub async fn launch_work(
         &self,
-        is_run_as_admin: bool,
         work_lnk_id: i32,
+        profile_id: Option<i32>,
+        is_run_as_admin: Option<bool>,
     ) -> anyhow::Result<Option<u32>> {
-        let lnk = self
+        let (lnk, profile, wine) = self
             .manager
-            .run(|repos| {
+            .run_in_transaction(|repos| {
                 Box::pin(async move {
-                    repos
+                    let lnk = repos
                         .work_lnk()
                         .find_by_id(domain::Id::new(work_lnk_id))
-                        .await
+                        .await?
+                        .ok_or(anyhow::anyhow!(format!(
+                            "work_lnk not found: {}",
+                            work_lnk_id
+                        )))?;
+                    let profile = match profile_id {
+                        Some(profile_id) => {
+                            let profile = repos
+                                .launch_profile()
+                                .find_by_id(domain::Id::new(profile_id))
+                                .await?
+                                .filter(|profile| profile.work_id == lnk.work_id)
+                                .ok_or(anyhow::anyhow!(format!(
+                                    "launch profile not found: {}",
+                                    profile_id
+                                )))?;
+                            Some(profile)
+                        }
+                        None => {
+                            repos
+                                .launch_profile()
+                                .find_default_by_work_id(lnk.work_id.clone())
+                                .await?
+                        }
+                    };
+                    let profile = match is_run_as_admin {
+                        Some(is_run_as_admin) => Some(
+                            remember_run_as_admin(
+                                &repos,
+                                lnk.work_id.clone(),
+                                profile,
+                                is_run_as_admin,
+                            )
+                            .await?,
+                        ),
+                        None => profile,
+                    };
+                    let wine = repos
+                        .wine_config()
+                        .find_by_work_id(lnk.work_id.clone())
+                        .await?;
+                    Ok((lnk, profile, wine))
                 })
             })
             .await?;
 
-        let lnk = lnk.ok_or(anyhow::anyhow!(format!(
-            "work_lnk not found: {}",
-            work_lnk_id
-        )))?;
-        // ShellLink 経由で .lnk を実行
-        let pid = self
-            .windows
-            .shell_link()
-            .execute_lnk(&lnk.lnk_path, is_run_as_admin)?;
+        let pid = self.execute(&lnk.lnk_path, profile.as_ref(), wine)?;
 
         // last_play_at を更新（起動成功時のみ）
         if pid.is_some() {
@@ -114,6 +180,57 @@ where
         Ok(pid)
     }
 
+    /// 引数やラッパーはショートカットに書き込めないので、リンク先を直接起動する。
+    /// ショートカットに書かれた引数と作業フォルダは引き継ぐ
+    fn execute(
+        &self,
+        lnk_path: &str,
+        profile: Option<&LaunchProfile>,
+        wine: Option<WineConfig>,
+    ) -> anyhow::Result<Option<u32>> {
+        let shell_link = self.windows.shell_link();
+        if let Some(wine) = wine {
+            // Wine の設定は作品ごとなので、リンク先にコマンドとして渡す
+            let link = self.resolve_lnk(lnk_path)?;
+            let command = LaunchCommand {
+                wine: Some(wine),
+                ..profile
+                    .map(|profile| profile.command(&link))
+                    .unwrap_or_else(|| LaunchCommand::from_link(&link))
+            };
+            return shell_link.execute_command(&command);
+        }
+        let Some(profile) = profile else {
+            return shell_link.execute_lnk(lnk_path, false);
+        };
+        if profile.uses_shortcut() {
+            return shell_link.execute_lnk(lnk_path, profile.run_as_admin);
+        }
+        if !lnk_path.to_lowercase().ends_with(".lnk") {
+            log::warn!(
+                "launch profile {} is not applicable to {}, opening it as is",
+                profile.name,
+                lnk_path
+            );
+            return shell_link.execute_lnk(lnk_path, profile.run_as_admin);
+        }
+
+        let link = self.resolve_lnk(lnk_path)?;
+        shell_link.execute_command(&profile.command(&link))
+    }
+
+    fn resolve_lnk(&self, lnk_path: &str) -> anyhow::Result<LnkMetadata> {
+        self.windows
+            .shell_link()
+            .get_lnk_metadatas(vec![lnk_path.to_string()])?
+            .remove(lnk_path)
+            .filter(|metadata| !metadata.path.is_empty())
+            .ok_or(anyhow::anyhow!(format!(
+                "failed to resolve shortcut target: {}",
+                lnk_path
+            )))
+    }
+
     pub async fn get_parent_dmm_pack_key(
         &self,
         work_id: String,
@@ -0,0 +1,117 @@
+//! 同じゲームが別々に登録された作品を見つけ、1 つにまとめる
+
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::Arc;
+
+use derive_new::new;
+use domain::game_matcher::GameMatcher;
+use domain::repository::{
+    manager::RepositoryManager, work_duplicate::WorkDuplicateRepository as _, RepositoriesExt,
+};
+use domain::service::save_path_resolver::SavePathResolver;
+use domain::thumbnail::{ThumbnailFormat, THUMBNAIL_VARIANT_WIDTHS};
+use domain::work_duplicate::{find_duplicate_candidates, DuplicateCandidate, WorkMergeResult};
+use domain::StrId;
+
+#[derive(new)]
+pub struct WorkDuplicateUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    matcher: Arc<dyn GameMatcher + Send + Sync>,
+    resolver: Arc<dyn SavePathResolver>,
+    #[new(default)]
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> WorkDuplicateUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    pub async fn find_duplicates(&self) -> anyhow::Result<Vec<DuplicateCandidate>> {
+        let sources = self
+            .manager
+            .run(|repos| {
+                Box::pin(async move { repos.work_duplicate().list_duplicate_sources().await })
+            })
+            .await?;
+        Ok(find_duplicate_candidates(&sources, self.matcher.as_ref()))
+    }
+
+    /// `duplicate` を `survivor` へまとめる。DB は 1 つのトランザクションで書き換え、
+    /// 画像ファイルはその後で移す（失敗しても記録だけ残して続ける）
+    pub async fn merge(
+        &self,
+        survivor: String,
+        duplicate: String,
+    ) -> anyhow::Result<WorkMergeResult> {
+        if survivor == duplicate {
+            anyhow::bail!("cannot merge a work into itself: {}", survivor);
+        }
+        let (survivor_id, duplicate_id) =
+            (StrId::new(survivor.clone()), StrId::new(duplicate.clone()));
+        let result = self
+            .manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    repos
+                        .work_duplicate()
+                        .merge(survivor_id, duplicate_id)
+                        .await
+                })
+            })
+            .await?;
+
+        let resolver = self.resolver.as_ref();
+        let mut files = vec![(
+            resolver.thumbnail_png_path(&duplicate),
+            resolver.thumbnail_png_path(&survivor),
+        )];
+        for width in THUMBNAIL_VARIANT_WIDTHS {
+            files.push((
+                resolver.thumbnail_variant_path(&duplicate, width, ThumbnailFormat::Webp),
+                resolver.thumbnail_variant_path(&survivor, width, ThumbnailFormat::Webp),
+            ));
+        }
+        for (from, to) in files {
+            if result.thumbnail_moved {
+                move_file(&from, &to);
+            } else {
+                remove_file(&from);
+            }
+        }
+        // アイコンは DB に記録していないので、生き残る側に無いときだけ移す
+        let (from, to) = (
+            resolver.icon_png_path(&duplicate),
+            resolver.icon_png_path(&survivor),
+        );
+        if Path::new(&to).exists() {
+            remove_file(&from);
+        } else {
+            move_file(&from, &to);
+        }
+        Ok(result)
+    }
+}
+
+fn move_file(from: &str, to: &str) {
+    if !Path::new(from).exists() {
+        return;
+    }
+    if let Err(e) = std::fs::rename(from, to) {
+        log::warn!("failed to move {} to {}: {}", from, to, e);
+    }
+}
+
+fn remove_file(path: &str) {
+    if !Path::new(path).exists() {
+        return;
+    }
+    if let Err(e) = std::fs::remove_file(path) {
+        log::warn!("failed to remove {}: {}", path, e);
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use domain::game_matcher::MockGameMatcher;
+    use domain::service::save_path_resolver::SavePathResolver;
+    use domain::thumbnail::ThumbnailFormat;
+    use domain::work_duplicate::WorkMergeResult;
+    use tempfile::TempDir;
+
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+    use crate::work_duplicate::WorkDuplicateUseCase;
+
+    struct TestResolver {
+        root: PathBuf,
+    }
+
+    impl SavePathResolver for TestResolver {
+        fn root_dir(&self) -> String {
+            self.root.to_string_lossy().to_string()
+        }
+    }
+
+    fn usecase(
+        repos: &TestRepositories,
+        root: &Path,
+    ) -> WorkDuplicateUseCase<TestRepositoryManager, TestRepositories> {
+        WorkDuplicateUseCase::new(
+            Arc::new(TestRepositoryManager::new(repos.clone())),
+            Arc::new(MockGameMatcher::new()),
+            Arc::new(TestResolver {
+                root: root.to_path_buf(),
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn merge_サムネイルを移したら画像ファイルも生き残る側の名前にする() {
+        let temp = TempDir::new().unwrap();
+        let resolver = TestResolver {
+            root: temp.path().to_path_buf(),
+        };
+        let repos = TestRepositories::default();
+        repos
+            .work_duplicate
+            .lock()
+            .await
+            .expect_merge()
+            .withf(|survivor, duplicate| survivor.value == "s" && duplicate.value == "d")
+            .times(1)
+            .returning(|_, _| {
+                Box::pin(async move {
+                    Ok(WorkMergeResult {
+                        thumbnail_moved: true,
+                    })
+                })
+            });
+        std::fs::write(resolver.thumbnail_png_path("d"), b"png").unwrap();
+        std::fs::write(
+            resolver.thumbnail_variant_path("d", 200, ThumbnailFormat::Webp),
+            b"webp",
+        )
+        .unwrap();
+        std::fs::write(resolver.icon_png_path("d"), b"dup icon").unwrap();
+        std::fs::write(resolver.icon_png_path("s"), b"icon").unwrap();
+
+        let result = usecase(&repos, temp.path())
+            .merge("s".into(), "d".into())
+            .await
+            .unwrap();
+
+        assert!(result.thumbnail_moved);
+        assert_eq!(
+            std::fs::read(resolver.thumbnail_png_path("s")).unwrap(),
+            b"png"
+        );
+        assert!(
+            Path::new(&resolver.thumbnail_variant_path("s", 200, ThumbnailFormat::Webp)).exists()
+        );
+        assert!(!Path::new(&resolver.thumbnail_png_path("d")).exists());
+        // 生き残る側のアイコンはそのまま
+        assert_eq!(std::fs::read(resolver.icon_png_path("s")).unwrap(), b"icon");
+        assert!(!Path::new(&resolver.icon_png_path("d")).exists());
+    }
+
+    #[tokio::test]
+    async fn merge_同じ作品同士はまとめない() {
+        let temp = TempDir::new().unwrap();
+        let repos = TestRepositories::default();
+        repos.work_duplicate.lock().await.expect_merge().times(0);
+
+        let result = usecase(&repos, temp.path())
+            .merge("s".into(), "s".into())
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,76 @@
+//! 利用者が直した作品名・読み・ブランド・発売日の管理
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use derive_new::new;
+use domain::repository::{
+    manager::RepositoryManager, work_override::WorkOverrideRepository as _, RepositoriesExt,
+};
+use domain::work_override::{WorkOverrideField, WorkOverrides};
+use domain::StrId;
+
+#[derive(new)]
+pub struct WorkOverrideUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    manager: Arc<M>,
+    #[new(default)]
+    _marker: PhantomData<R>,
+}
+
+impl<M, R> WorkOverrideUseCase<M, R>
+where
+    M: RepositoryManager<R>,
+    R: RepositoriesExt + Send + Sync + 'static,
+{
+    pub async fn get(&self, work_id: String) -> anyhow::Result<WorkOverrides> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run(|repos| {
+                Box::pin(async move { repos.work_override().get_by_work_id(work_id).await })
+            })
+            .await
+    }
+
+    /// 1 項目だけ直す。直した後の内容を返す
+    pub async fn set(
+        &self,
+        work_id: String,
+        field: WorkOverrideField,
+        value: String,
+    ) -> anyhow::Result<WorkOverrides> {
+        let value = field.normalize_value(&value)?;
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    repos
+                        .work_override()
+                        .set(work_id.clone(), field, &value)
+                        .await?;
+                    repos.work_override().get_by_work_id(work_id).await
+                })
+            })
+            .await
+    }
+
+    /// 1 項目だけ元の情報に戻す。戻した後の内容を返す
+    pub async fn clear(
+        &self,
+        work_id: String,
+        field: WorkOverrideField,
+    ) -> anyhow::Result<WorkOverrides> {
+        let work_id = StrId::new(work_id);
+        self.manager
+            .run_in_transaction(|repos| {
+                Box::pin(async move {
+                    repos.work_override().clear(work_id.clone(), field).await?;
+                    repos.work_override().get_by_work_id(work_id).await
+                })
+            })
+            .await
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::sync::Arc;
+
+    use chrono::NaiveDate;
+    use domain::work_override::{WorkOverrideField, WorkOverrides};
+
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+    use crate::work_override::WorkOverrideUseCase;
+
+    fn usecase(
+        repos: &TestRepositories,
+    ) -> WorkOverrideUseCase<TestRepositoryManager, TestRepositories> {
+        WorkOverrideUseCase::new(Arc::new(TestRepositoryManager::new(repos.clone())))
+    }
+
+    #[tokio::test]
+    async fn set_発売日は書式をそろえて保存する() {
+        let repos = TestRepositories::default();
+        {
+            let mut repo = repos.work_override.lock().await;
+            repo.expect_set()
+                .withf(|work_id, field, value| {
+                    work_id.value == "w"
+                        && *field == WorkOverrideField::Sellday
+                        && value == "2024-03-09"
+                })
+                .times(1)
+                .returning(|_, _, _| Box::pin(async { Ok(()) }));
+            repo.expect_get_by_work_id().returning(|_| {
+                Box::pin(async {
+                    Ok(WorkOverrides {
+                        sellday: NaiveDate::from_ymd_opt(2024, 3, 9),
+                        ..Default::default()
+                    })
+                })
+            });
+        }
+
+        let overrides = usecase(&repos)
+            .set("w".into(), WorkOverrideField::Sellday, "2024/3/9".into())
+            .await
+            .unwrap();
+
+        assert_eq!(overrides.sellday, NaiveDate::from_ymd_opt(2024, 3, 9));
+    }
+
+    #[tokio::test]
+    async fn set_読めない値は保存しない() {
+        let repos = TestRepositories::default();
+        repos.work_override.lock().await.expect_set().times(0);
+
+        let result = usecase(&repos)
+            .set("w".into(), WorkOverrideField::Sellday, "そのうち".into())
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -195,14 +195,16 @@ where
                     match res {
                         Ok(Ok(v)) => {
                             match &v {
-                                WorkCandidateOrResolvedWork::Candidate(c) => {
+                                WorkCandidateOrResolvedWork::Candidate(c, explanation) => {
                                     let path = c.path.to_string_lossy().to_string();
+                                    let explanation = explanation.clone().map(Into::into);
                                     let _ = pubsub.notify(PubSubEvent::ScanEnrichResult(
                                         EnrichResultPayload::new(
                                             "candidate".into(),
                                             path,
                                             None,
                                             None,
+                                            explanation,
                                         ),
                                     ));
                                 }
@@ -210,12 +212,14 @@ where
                                     let path = r.candidate.path.to_string_lossy().to_string();
                                     let title = r.title.clone();
                                     let egs_id = r.egs_id;
+                                    let explanation = r.explanation.clone().map(Into::into);
                                     let _ = pubsub.notify(PubSubEvent::ScanEnrichResult(
                                         EnrichResultPayload::new(
                                             "resolved".into(),
                                             path,
                                             Some(title),
                                             Some(egs_id),
+                                            explanation,
                                         ),
                                     ));
                                 }
@@ -232,7 +236,9 @@ where
         let explored = enriched
             .iter()
             .map(|v| match v {
-                WorkCandidateOrResolvedWork::Candidate(c) => c.path.to_string_lossy().to_string(),
+                WorkCandidateOrResolvedWork::Candidate(c, _) => {
+                    c.path.to_string_lossy().to_string()
+                }
                 WorkCandidateOrResolvedWork::Resolved(r) => {
                     r.candidate.path.to_string_lossy().to_string()
                 }
@@ -241,7 +247,7 @@ where
         let results = enriched
             .into_iter()
             .filter_map(|v| match v {
-                WorkCandidateOrResolvedWork::Candidate(_) => None,
+                WorkCandidateOrResolvedWork::Candidate(..) => None,
                 WorkCandidateOrResolvedWork::Resolved(r) => Some(r),
             })
             .collect();
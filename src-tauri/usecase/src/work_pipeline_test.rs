@@ -56,7 +56,7 @@ mod tests {
         let mut extractor = MockMetadataExtractor::new();
         extractor
             .expect_enrich()
-            .returning(|c| Ok(WorkCandidateOrResolvedWork::Candidate(c)));
+            .returning(|c| Ok(WorkCandidateOrResolvedWork::Candidate(c, None)));
         let extractor = Arc::new(extractor);
         let mut dedup = MockDuplicateResolver::new();
         dedup.expect_resolve().returning(|items| items);
@@ -96,7 +96,7 @@ mod tests {
         let mut extractor = MockMetadataExtractor::new();
         extractor.expect_enrich().returning(|c| {
             if c.path.to_string_lossy().contains("a.exe") {
-                Ok(WorkCandidateOrResolvedWork::Candidate(c))
+                Ok(WorkCandidateOrResolvedWork::Candidate(c, None))
             } else {
                 Ok(WorkCandidateOrResolvedWork::Resolved(ResolvedWork::new(
                     c,
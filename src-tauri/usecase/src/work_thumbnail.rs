@@ -1,7 +1,12 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use derive_new::new;
-use domain::repository::{manager::RepositoryManager, works::WorkRepository as _, RepositoriesExt};
+use domain::repository::{
+    manager::RepositoryManager, save_image_queue::ImageSaveQueueRepository as _,
+    works::WorkRepository as _, RepositoriesExt,
+};
+use domain::save_image_queue::{ImagePreprocess, ImageSrcType};
 use domain::service::save_path_resolver::SavePathResolver;
 use std::marker::PhantomData;
 
@@ -51,4 +56,56 @@ where
             .await?;
         Ok(updated)
     }
+
+    /// バリアントを記録していない作品のうち、PNG があるものについて、
+    /// その PNG からバリアントを作り直す項目を画像キューに積む。積んだ件数を返す。
+    /// 記録はキューの処理で行われるので、呼び出し側で drain する。
+    /// PNG が無い（まだ生成されていない）作品は次回に回す。
+    /// 既に積んである（処理待ちかデッドレターの）作品は積み直さない。
+    /// 壊れた PNG はキューの処理でデッドレターになり、以降は積まれない
+    pub async fn backfill_thumbnail_variants(&self) -> anyhow::Result<usize> {
+        let resolver = self.resolver.clone();
+        let enqueued = self
+            .manager
+            .run(|repos| {
+                let resolver = resolver.clone();
+                Box::pin(async move {
+                    let ids = repos
+                        .work()
+                        .list_work_ids_missing_thumbnail_variants()
+                        .await?;
+                    if ids.is_empty() {
+                        return Ok(0);
+                    }
+                    let queued: HashSet<String> = repos
+                        .image_queue()
+                        .list_dst_paths_not_succeeded(ImagePreprocess::ThumbnailVariants)
+                        .await?
+                        .into_iter()
+                        .collect();
+                    let mut enqueued: usize = 0;
+                    for id in ids.into_iter() {
+                        let png_path = resolver.thumbnail_png_path(&id.value);
+                        if queued.contains(&png_path)
+                            || !tokio::fs::try_exists(&png_path).await.unwrap_or(false)
+                        {
+                            continue;
+                        }
+                        repos
+                            .image_queue()
+                            .enqueue(
+                                &png_path,
+                                ImageSrcType::Path,
+                                &png_path,
+                                ImagePreprocess::ThumbnailVariants,
+                            )
+                            .await?;
+                        enqueued += 1;
+                    }
+                    Ok::<usize, anyhow::Error>(enqueued)
+                })
+            })
+            .await?;
+        Ok(enqueued)
+    }
 }
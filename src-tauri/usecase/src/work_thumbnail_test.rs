@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use domain::save_image_queue::{ImagePreprocess, ImageSrcType};
+    use domain::service::save_path_resolver::SavePathResolver;
+    use domain::StrId;
+    use tempfile::TempDir;
+
+    use crate::repositorymock::{TestRepositories, TestRepositoryManager};
+    use crate::work_thumbnail::WorkThumbnailUseCase;
+
+    struct TestResolver {
+        root: PathBuf,
+    }
+
+    impl SavePathResolver for TestResolver {
+        fn root_dir(&self) -> String {
+            self.root.to_string_lossy().to_string()
+        }
+    }
+
+    fn usecase(
+        repos: &TestRepositories,
+        root: &Path,
+    ) -> WorkThumbnailUseCase<TestRepositoryManager, TestRepositories> {
+        WorkThumbnailUseCase::new(
+            Arc::new(TestRepositoryManager::new(repos.clone())),
+            Arc::new(TestResolver {
+                root: root.to_path_buf(),
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn backfill_thumbnail_variants_積んである作品とpngが無い作品は積まない() {
+        let temp = TempDir::new().unwrap();
+        let resolver = TestResolver {
+            root: temp.path().to_path_buf(),
+        };
+        for id in ["new", "queued"] {
+            std::fs::write(resolver.thumbnail_png_path(id), b"png").unwrap();
+        }
+        let queued_path = resolver.thumbnail_png_path("queued");
+        let new_path = resolver.thumbnail_png_path("new");
+
+        let repos = TestRepositories::default();
+        repos
+            .work
+            .lock()
+            .await
+            .expect_list_work_ids_missing_thumbnail_variants()
+            .returning(|| {
+                Box::pin(async {
+                    Ok(vec![
+                        StrId::new("new".to_string()),
+                        StrId::new("queued".to_string()),
+                        StrId::new("no-png".to_string()),
+                    ])
+                })
+            });
+        {
+            let mut iq = repos.image_queue.lock().await;
+            iq.expect_list_dst_paths_not_succeeded()
+                .withf(|preprocess| matches!(preprocess, ImagePreprocess::ThumbnailVariants))
+                .returning(move |_| {
+                    let paths = vec![queued_path.clone()];
+                    Box::pin(async move { Ok(paths) })
+                });
+            iq.expect_enqueue()
+                .withf(move |src, src_type, dst, preprocess| {
+                    src == new_path
+                        && matches!(src_type, ImageSrcType::Path)
+                        && dst == new_path
+                        && matches!(preprocess, ImagePreprocess::ThumbnailVariants)
+                })
+                .times(1)
+                .returning(|_, _, _, _| Box::pin(async { Ok(domain::Id::new(1)) }));
+        }
+
+        let enqueued = usecase(&repos, temp.path())
+            .backfill_thumbnail_variants()
+            .await
+            .unwrap();
+
+        assert_eq!(enqueued, 1);
+    }
+}
@@ -2,21 +2,28 @@ import type { CreateQueryResult } from '@tanstack/svelte-query'
 import type { WorkDetailsVm } from '@/lib/command'
 import { get } from 'svelte/store'
 import { commandLaunchWork } from '@/lib/command'
+import { invalidateLaunchProfiles } from '@/lib/data/queries/launchProfiles'
 import { showErrorToast } from '@/lib/toast'
 import { localStorageWritable } from '@/lib/utils'
 import { startProcessMap } from '@/store/startProcessMap'
 
 export function useStart(workDetail: WorkDetailsVm, workLnkQuery: CreateQueryResult<[number, string][], Error>) {
-  const isAdminRecord = localStorageWritable<Record<string, boolean>>(
+  // 以前は権限の指定をここに覚えていた。起動プロファイルへ移したので、残っていれば一度だけ渡して消す
+  const legacyIsAdminRecord = localStorageWritable<Record<string, boolean>>(
     'play-admin-cache',
     {},
   )
 
-  const updateRunAsForWork = (workId: string, runAs: 'admin' | 'user') => {
-    isAdminRecord.update((v) => {
-      v[workId] = runAs === 'admin'
+  const takeLegacyIsAdmin = (workId: string) => {
+    const cache = get(legacyIsAdminRecord)[workId]
+    if (cache === undefined) {
+      return undefined
+    }
+    legacyIsAdminRecord.update((v) => {
+      delete v[workId]
       return v
     })
+    return cache
   }
   const updateStartProcess = (id: string, processId: number) => {
     startProcessMap.update((v) => {
@@ -25,34 +32,24 @@ export function useStart(workDetail: WorkDetailsVm, workLnkQuery: CreateQueryRes
     })
   }
 
-  const start = async (runAs: 'admin' | 'user' | 'default') => {
+  // runAs に指定があれば、その権限を既定の起動プロファイルに覚えて、次からの runAs === 'default' でも使う
+  const start = async (runAs: 'admin' | 'user' | 'default', profileId?: number) => {
     const workId = workDetail.id
     if (!workId) {
       throw new Error('workId is not set')
     }
-    // runAs に指定があれば、次からの runAs === 'default' ではその権限で実行する
-    if (runAs === 'admin' || runAs === 'user') {
-      updateRunAsForWork(workId, runAs)
-    }
 
-    // 過去に実行された権限の記録もなく、runAs も default の場合は user で実行する
-    let isAdmin = false
+    let isRunAsAdmin: boolean | undefined
     switch (runAs) {
       case 'admin':
-        isAdmin = true
+        isRunAsAdmin = true
         break
       case 'user':
-        isAdmin = false
+        isRunAsAdmin = false
         break
-      case 'default': {
-        if (workId) {
-          const cache = get(isAdminRecord)[workId]
-          if (cache) {
-            isAdmin = cache
-          }
-        }
+      case 'default':
+        isRunAsAdmin = profileId === undefined ? takeLegacyIsAdmin(workId) : undefined
         break
-      }
       default:
         throw new Error(`Invalid runAs: ${runAs satisfies never}`)
     }
@@ -64,7 +61,10 @@ export function useStart(workDetail: WorkDetailsVm, workLnkQuery: CreateQueryRes
         throw new Error('起動可能なショートカットが登録されていません')
       }
       const [lnkId] = list[0]
-      const processId = await commandLaunchWork(isAdmin, lnkId)
+      const processId = await commandLaunchWork(lnkId, { profileId, isRunAsAdmin })
+      if (isRunAsAdmin !== undefined) {
+        await invalidateLaunchProfiles(workId)
+      }
       if (processId) {
         updateStartProcess(workId, processId)
       }
@@ -53,13 +53,139 @@ export async function commandGetDefaultImportDirs() {
   return await invoke<string[]>('get_default_import_dirs', {})
 }
 
-export async function commandLaunchWork(isRunAsAdmin: boolean, workLnkId: number) {
+// profileId を省くと既定の起動プロファイルで起動する。isRunAsAdmin を指定するとその権限を覚える
+export async function commandLaunchWork(workLnkId: number, options: { profileId?: number, isRunAsAdmin?: boolean } = {}) {
   return await invoke<number | null>('launch_work', {
-    isRunAsAdmin,
     workLnkId,
+    profileId: options.profileId ?? null,
+    isRunAsAdmin: options.isRunAsAdmin ?? null,
   })
 }
 
+export interface LaunchEnvVarVm {
+  key: string
+  value: string
+}
+
+export interface LaunchWrapperVm {
+  path: string
+  // {target} と {args} を置き換える
+  arguments: string | null
+}
+
+export interface LaunchProfileInput {
+  workId: string
+  name: string
+  arguments: string | null
+  workingDir: string | null
+  env: LaunchEnvVarVm[]
+  runAsAdmin: boolean
+  wrapper: LaunchWrapperVm | null
+  isDefault: boolean
+}
+
+export interface LaunchProfileVm extends LaunchProfileInput {
+  id: number
+}
+
+// 既定のプロファイルが先頭
+export async function commandListLaunchProfiles(workId: string) {
+  return await invoke<LaunchProfileVm[]>('list_launch_profiles', { workId })
+}
+
+export async function commandSaveLaunchProfile(id: number | null, profile: LaunchProfileInput) {
+  return await invoke<number>('save_launch_profile', { id, profile })
+}
+
+export async function commandDeleteLaunchProfile(id: number) {
+  return await invoke<void>('delete_launch_profile', { id })
+}
+
+export async function commandSetDefaultLaunchProfile(id: number) {
+  return await invoke<void>('set_default_launch_profile', { id })
+}
+
+export type WineRunner = 'wine' | 'proton' | 'umu'
+
+export interface WineConfigVm {
+  workId: string
+  runner: WineRunner
+  runnerPath: string | null
+  prefix: string | null
+  env: LaunchEnvVarVm[]
+}
+
+export interface WineSettingsVm {
+  // Windows 版では false
+  available: boolean
+  config: WineConfigVm | null
+}
+
+export async function commandGetWineSettings(workId: string) {
+  return await invoke<WineSettingsVm>('get_wine_settings', { workId })
+}
+
+export async function commandSaveWineConfig(config: WineConfigVm) {
+  return await invoke<void>('save_wine_config', { config })
+}
+
+export async function commandDeleteWineConfig(workId: string) {
+  return await invoke<void>('delete_wine_config', { workId })
+}
+
+export type SaveLocationSource = 'detected' | 'manual'
+
+export interface SaveLocationVm {
+  id: number
+  workId: string
+  // `{install}` や `%APPDATA%` を含んだままの書き方
+  path: string
+  source: SaveLocationSource
+}
+
+export type SnapshotReason = 'manual' | 'before_restore'
+
+export interface SaveSnapshotVm {
+  fileName: string
+  path: string
+  size: number
+  createdAt: string
+  reason: SnapshotReason
+  locations: string[]
+}
+
+export async function commandListSaveLocations(workId: string) {
+  return await invoke<SaveLocationVm[]>('list_save_locations', { workId })
+}
+
+export async function commandDetectSaveLocations(workId: string) {
+  return await invoke<SaveLocationVm[]>('detect_save_locations', { workId })
+}
+
+export async function commandAddSaveLocation(workId: string, path: string) {
+  return await invoke<SaveLocationVm[]>('add_save_location', { workId, path })
+}
+
+export async function commandDeleteSaveLocation(id: number) {
+  return await invoke<void>('delete_save_location', { id })
+}
+
+export async function commandCreateSaveSnapshot(workId: string) {
+  return await invoke<SaveSnapshotVm>('create_save_snapshot', { workId })
+}
+
+export async function commandListSaveSnapshots(workId: string) {
+  return await invoke<SaveSnapshotVm[]>('list_save_snapshots', { workId })
+}
+
+export async function commandRestoreSaveSnapshot(workId: string, fileName: string) {
+  return await invoke<void>('restore_save_snapshot', { workId, fileName })
+}
+
+export async function commandDeleteSaveSnapshot(workId: string, fileName: string) {
+  return await invoke<void>('delete_save_snapshot', { workId, fileName })
+}
+
 export async function commandListWorkLnks(workId: string) {
   return await invoke<[number, string][]>('list_work_lnks', { workId })
 }
@@ -207,15 +333,29 @@ export interface HostLogItem {
   level: number
   typ: number
   message: string
+  request_id: string | null
+  duration_ms: number | null
+  item_count: number | null
+  error_count: number | null
   created_at: string
 }
 export interface HostLogsResponse {
   items: HostLogItem[]
   total: number
 }
-export async function commandGetNativeHostLogs(req: { limit?: number, offset?: number, level?: number, typ?: number }) {
+export interface HostLogFilterParams {
+  level?: number
+  typ?: number
+  request_id?: string
+  since?: string
+  until?: string
+}
+export async function commandGetNativeHostLogs(req: { limit?: number, offset?: number } & HostLogFilterParams) {
   return await invoke<HostLogsResponse>('get_native_host_logs', { request: req })
 }
+export async function commandExportNativeHostLogs(req: { path: string } & HostLogFilterParams) {
+  return await invoke<number>('export_native_host_logs', { request: req })
+}
 
 export interface RegistryKeyInfo {
   browser: string
@@ -233,15 +373,145 @@ export async function commandRemoveRegistryKeys() {
 }
 
 // WorkDetails
-export interface WorkDetailsVm { id: string, title: string, dmm?: { id: number, storeId: string, category: string, subcategory: string, parentPack?: { storeId: string, category: string, subcategory: string } | null }, dlsite?: { id: number, storeId: string, category: string }, erogamescapeId?: number | null, erogamescapeInformation?: { gamenameRuby: string, brandname: string, brandnameRuby: string, sellday: string, isNukige: boolean }, icon?: { path: string } | null, thumbnail?: { path: string, width?: number, height?: number } | null, latestDownloadPath?: { id: number, workId: string, downloadPath: string } | null, originalPath?: string | null, likeAt?: string | null, installAt?: string | null, lastPlayAt?: string | null, registeredAt?: string | null }
-export async function commandGetWorkDetailsAll() {
-  return await invoke<WorkDetailsVm[]>('get_work_details_all')
+export type GameEngine = 'kirikiri' | 'siglus_engine' | 'bgi' | 'cat_system2' | 'artemis' | 'renpy' | 'rpg_maker_mv' | 'rpg_maker_mz' | 'rgss' | 'unity' | 'tyrano_script' | 'n_scripter' | 'yuris' | 'live_maker' | 'qlie'
+export interface ThumbnailVariantVm { path: string, width: number, height: number, format: 'png' | 'webp' }
+export type PlayStatus = 'unplayed' | 'playing' | 'finished' | 'dropped' | 'on_hold'
+// 日付は YYYY-MM-DD
+export interface WorkPlayStateVm { status: PlayStatus, score: number | null, startedOn: string | null, finishedOn: string | null }
+
+export interface WorkDetailsVm { id: string, title: string, dmm?: { id: number, storeId: string, category: string, subcategory: string, parentPack?: { storeId: string, category: string, subcategory: string } | null }, dlsite?: { id: number, storeId: string, category: string }, erogamescapeId?: number | null, erogamescapeInformation?: { gamenameRuby: string, brandname: string, brandnameRuby: string, sellday: string, isNukige: boolean }, icon?: { path: string } | null, thumbnail?: { path: string, width?: number, height?: number, variants: ThumbnailVariantVm[], placeholderColor?: string | null } | null, latestDownloadPath?: { id: number, workId: string, downloadPath: string } | null, originalPath?: string | null, engine?: GameEngine | null, likeAt?: string | null, installAt?: string | null, lastPlayAt?: string | null, registeredAt?: string | null, playState?: WorkPlayStateVm | null, overrides: WorkOverridesVm }
+// 空の項目では絞り込まない
+export type WorkStore = 'dmm' | 'dlsite' | 'erogamescape_only'
+// 空の項目では絞り込まない。日付は YYYY-MM-DD で両端を含む
+export interface WorkListFilterVm {
+  stores?: WorkStore[]
+  liked?: boolean | null
+  installed?: boolean | null
+  nukige?: boolean | null
+  brands?: string[]
+  selldayFrom?: string | null
+  selldayTo?: string | null
+  parentPack?: { storeId: string, category: string, subcategory: string } | null
+  playStatuses?: PlayStatus[]
+  minScore?: number | null
+  maxScore?: number | null
+}
+export async function commandGetWorkDetailsAll(filter?: WorkListFilterVm) {
+  return await invoke<WorkDetailsVm[]>('get_work_details_all', { filter: filter ?? null })
+}
+
+export type WorkSortKey = 'title_ruby' | 'sellday' | 'last_play_at' | 'registered_at' | 'install_at'
+export interface WorkCursorVm { sortValue: string, workId: string }
+// 値のない作品は向きによらず最後に並ぶ。limit を省くと残りをすべて返す
+export interface WorkQueryVm {
+  filter?: WorkListFilterVm
+  sortKey?: WorkSortKey
+  sortDirection?: 'asc' | 'desc'
+  after?: WorkCursorVm | null
+  limit?: number | null
+}
+export interface WorkPageVm { items: WorkDetailsVm[], nextCursor: WorkCursorVm | null }
+export async function commandQueryWorkDetails(query: WorkQueryVm) {
+  return await invoke<WorkPageVm>('query_work_details', { query })
+}
+
+export interface FacetCountVm<T> { value: T, count: number }
+export interface BoolFacetCountVm { yes: number, no: number }
+// 各項目の件数は、その項目自身の絞り込みを外して数える
+export interface WorkFacetsVm {
+  total: number
+  stores: FacetCountVm<WorkStore>[]
+  liked: BoolFacetCountVm
+  installed: BoolFacetCountVm
+  nukige: BoolFacetCountVm
+  playStatuses: FacetCountVm<PlayStatus>[]
+  brands: FacetCountVm<string>[]
+}
+export async function commandGetWorkFacets(filter?: WorkListFilterVm) {
+  return await invoke<WorkFacetsVm>('get_work_facets', { filter: filter ?? null })
 }
 
 export async function commandGetWorkDetailsByWorkId(workId: string) {
   return await invoke<WorkDetailsVm | null>('get_work_details_by_work_id', { workId })
 }
 
+// Play Status
+export interface PlayStatusChangeVm { id: number, status: PlayStatus, changedAt: string }
+export interface WorkRouteVm { id: number, workId: string, name: string, completedAt: string | null }
+// 日付を null にすると、ステータスに合わせて今日が入る
+export interface PlayStateInput { status: PlayStatus, score: number | null, startedOn: string | null, finishedOn: string | null }
+
+export async function commandGetWorkPlayState(workId: string) {
+  return await invoke<WorkPlayStateVm | null>('get_work_play_state', { workId })
+}
+
+export async function commandSaveWorkPlayState(workId: string, state: PlayStateInput) {
+  return await invoke<WorkPlayStateVm>('save_work_play_state', { workId, state })
+}
+
+export async function commandListWorkPlayStatusChanges(workId: string) {
+  return await invoke<PlayStatusChangeVm[]>('list_work_play_status_changes', { workId })
+}
+
+export async function commandListWorkRoutes(workId: string) {
+  return await invoke<WorkRouteVm[]>('list_work_routes', { workId })
+}
+
+export async function commandAddWorkRoute(workId: string, name: string) {
+  return await invoke<WorkRouteVm[]>('add_work_route', { workId, name })
+}
+
+export async function commandSetWorkRouteCompleted(id: number, completed: boolean) {
+  return await invoke<void>('set_work_route_completed', { id, completed })
+}
+
+export async function commandDeleteWorkRoute(id: number) {
+  return await invoke<void>('delete_work_route', { id })
+}
+
+// Duplicate Works
+export type DuplicateReasonVm
+  = | { type: 'sameErogamescapeId', id: number }
+    | { type: 'similarTitle', score: number }
+    | { type: 'similarThumbnail', distance: number }
+
+export interface DuplicateCandidateVm {
+  workIds: [string, string]
+  reasons: DuplicateReasonVm[]
+}
+
+export interface WorkMergeResultVm { thumbnailMoved: boolean }
+
+export async function commandFindDuplicateWorks() {
+  return await invoke<DuplicateCandidateVm[]>('find_duplicate_works')
+}
+
+export async function commandMergeWorks(survivorWorkId: string, duplicateWorkId: string) {
+  return await invoke<WorkMergeResultVm>('merge_works', { survivorWorkId, duplicateWorkId })
+}
+
+// Work Overrides
+// null の項目は同期や取り込みで得た情報を使う。WorkDetailsVm の title などには反映済み
+export interface WorkOverridesVm {
+  title?: string | null
+  titleRuby?: string | null
+  brandname?: string | null
+  sellday?: string | null
+}
+export type WorkOverrideField = 'title' | 'title_ruby' | 'brandname' | 'sellday'
+
+export async function commandGetWorkOverrides(workId: string) {
+  return await invoke<WorkOverridesVm>('get_work_overrides', { workId })
+}
+
+export async function commandSetWorkOverride(workId: string, field: WorkOverrideField, value: string) {
+  return await invoke<WorkOverridesVm>('set_work_override', { workId, field, value })
+}
+
+export async function commandClearWorkOverride(workId: string, field: WorkOverrideField) {
+  return await invoke<WorkOverridesVm>('clear_work_override', { workId, field })
+}
+
 // Work Paths
 export interface WorkLnkVm { id: number, lnkPath: string }
 export interface WorkPathsVm { lnks: WorkLnkVm[] }
@@ -257,10 +527,38 @@ export interface ImageSaveQueueRowVm {
   dstPath: string
   preprocess: number
   lastError?: string | null
+  attemptCount: number
+  nextAttemptAt?: string | null
+  failedAt?: string | null
 }
 export async function commandGetImageSaveQueue(req?: { limit?: number, status?: 'unfinished' | 'finished' }) {
   return await invoke<ImageSaveQueueRowVm[]>('get_image_save_queue', req ? { request: req } : {})
 }
+export async function commandGetImageSaveQueueFailed(limit?: number) {
+  return await invoke<ImageSaveQueueRowVm[]>('get_image_save_queue_failed', { limit })
+}
+export async function commandRequeueImageSaveQueueItems(ids: number[]) {
+  return await invoke<number>('requeue_image_save_queue_items', { ids })
+}
+export async function commandDiscardImageSaveQueueItems(ids: number[]) {
+  return await invoke<number>('discard_image_save_queue_items', { ids })
+}
+
+// Store works missing from a full-library sync
+export type StoreKind = 'dmm' | 'dlsite'
+export interface MissingStoreWorkVm {
+  workId: string
+  title: string
+  storeId: string
+  category: string
+  missingSince: string
+}
+export async function commandGetMissingStoreWorks(store: StoreKind) {
+  return await invoke<MissingStoreWorkVm[]>('get_missing_store_works', { store })
+}
+export async function commandPruneMissingStoreWorks(store: StoreKind, storeIds: string[]) {
+  return await invoke<number>('prune_missing_store_works', { store, storeIds })
+}
 
 // Backfill for missing thumbnail sizes
 export async function commandBackfillThumbnailSizes() {
@@ -307,6 +605,42 @@ export async function commandGetRemoteShareUrl() {
   return await invoke<string>('get_remote_share_url')
 }
 
+export type RemoteLaunchPolicy = 'disabled' | 'allowlist' | 'ask_first'
+
+export interface RemoteLaunchSettingsVm {
+  policy: RemoteLaunchPolicy
+  allowedWorkIds: string[]
+  confirmTimeoutSeconds: number
+}
+
+export interface RemoteLaunchAuditVm {
+  id: number
+  workId: string
+  workTitle: string | null
+  policy: RemoteLaunchPolicy
+  decision: string
+  status: string
+  errorMessage: string | null
+  requestedAt: string
+  finishedAt: string
+}
+
+export async function commandGetRemoteLaunchSettings() {
+  return await invoke<RemoteLaunchSettingsVm>('get_remote_launch_settings')
+}
+
+export async function commandSetRemoteLaunchSettings(settings: RemoteLaunchSettingsVm) {
+  return await invoke<RemoteLaunchSettingsVm>('set_remote_launch_settings', { settings })
+}
+
+export async function commandRespondRemoteLaunchConfirmation(requestId: string, approved: boolean, alwaysAllow: boolean) {
+  return await invoke<boolean>('respond_remote_launch_confirmation', { requestId, approved, alwaysAllow })
+}
+
+export async function commandGetRemoteLaunchAudits(limit?: number, offset?: number) {
+  return await invoke<RemoteLaunchAuditVm[]>('get_remote_launch_audits', { limit, offset })
+}
+
 // Process pending exe links (work_link_pending_exe)
 export async function commandProcessPendingExeLinks() {
   return await invoke<void>('process_pending_exe_links')
@@ -1,6 +1,12 @@
 import type { ImageSaveQueueRowVm } from '@/lib/command'
-import { createQuery } from '@tanstack/svelte-query'
-import { commandGetImageSaveQueue } from '@/lib/command'
+import { createMutation, createQuery } from '@tanstack/svelte-query'
+import {
+  commandDiscardImageSaveQueueItems,
+  commandGetImageSaveQueue,
+  commandGetImageSaveQueueFailed,
+  commandRequeueImageSaveQueueItems,
+} from '@/lib/command'
+import { queryClient } from '@/lib/data/queryClient'
 import { queryKeys } from '@/lib/data/queryKeys'
 
 export function useImageQueueQuery(unfinished: boolean) {
@@ -11,3 +17,28 @@ export function useImageQueueQuery(unfinished: boolean) {
       : commandGetImageSaveQueue({ limit: 500, status: 'finished' }),
   })
 }
+
+export function useImageQueueFailedQuery() {
+  return createQuery<ImageSaveQueueRowVm[]>({
+    queryKey: queryKeys.imageQueue.failed(),
+    queryFn: () => commandGetImageSaveQueueFailed(500),
+  })
+}
+
+async function invalidateImageQueue() {
+  await queryClient.invalidateQueries({ queryKey: ['imageQueue'] })
+}
+
+export function useRequeueImageQueueMutation() {
+  return createMutation<number, Error, number[]>({
+    mutationFn: ids => commandRequeueImageSaveQueueItems(ids),
+    onSuccess: invalidateImageQueue,
+  })
+}
+
+export function useDiscardImageQueueMutation() {
+  return createMutation<number, Error, number[]>({
+    mutationFn: ids => commandDiscardImageSaveQueueItems(ids),
+    onSuccess: invalidateImageQueue,
+  })
+}
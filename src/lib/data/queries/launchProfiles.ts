@@ -0,0 +1,42 @@
+import type { LaunchProfileInput, LaunchProfileVm } from '@/lib/command'
+import { createMutation, createQuery } from '@tanstack/svelte-query'
+import {
+  commandDeleteLaunchProfile,
+  commandListLaunchProfiles,
+  commandSaveLaunchProfile,
+  commandSetDefaultLaunchProfile,
+} from '@/lib/command'
+import { queryClient } from '@/lib/data/queryClient'
+import { queryKeys } from '@/lib/data/queryKeys'
+
+export function useLaunchProfilesQuery(workId: string) {
+  return createQuery<LaunchProfileVm[]>({
+    queryKey: queryKeys.launchProfiles.byWorkId(workId),
+    queryFn: () => commandListLaunchProfiles(workId),
+  })
+}
+
+export async function invalidateLaunchProfiles(workId: string) {
+  await queryClient.invalidateQueries({ queryKey: queryKeys.launchProfiles.byWorkId(workId) })
+}
+
+export function useSaveLaunchProfileMutation(workId: string) {
+  return createMutation<number, Error, { id: number | null, profile: LaunchProfileInput }>({
+    mutationFn: ({ id, profile }) => commandSaveLaunchProfile(id, profile),
+    onSuccess: () => invalidateLaunchProfiles(workId),
+  })
+}
+
+export function useDeleteLaunchProfileMutation(workId: string) {
+  return createMutation<void, Error, number>({
+    mutationFn: id => commandDeleteLaunchProfile(id),
+    onSuccess: () => invalidateLaunchProfiles(workId),
+  })
+}
+
+export function useSetDefaultLaunchProfileMutation(workId: string) {
+  return createMutation<void, Error, number>({
+    mutationFn: id => commandSetDefaultLaunchProfile(id),
+    onSuccess: () => invalidateLaunchProfiles(workId),
+  })
+}
@@ -0,0 +1,32 @@
+import type { RemoteLaunchAuditVm, RemoteLaunchSettingsVm } from '@/lib/command'
+import { createMutation, createQuery } from '@tanstack/svelte-query'
+import {
+  commandGetRemoteLaunchAudits,
+  commandGetRemoteLaunchSettings,
+  commandSetRemoteLaunchSettings,
+} from '@/lib/command'
+import { queryClient } from '@/lib/data/queryClient'
+import { queryKeys } from '@/lib/data/queryKeys'
+
+export function useRemoteLaunchSettingsQuery() {
+  return createQuery<RemoteLaunchSettingsVm>({
+    queryKey: queryKeys.remoteLaunch.settings(),
+    queryFn: () => commandGetRemoteLaunchSettings(),
+  })
+}
+
+export function useRemoteLaunchSettingsMutation() {
+  return createMutation<RemoteLaunchSettingsVm, Error, RemoteLaunchSettingsVm>({
+    mutationFn: input => commandSetRemoteLaunchSettings(input),
+    onSuccess: async () => {
+      await queryClient.invalidateQueries({ queryKey: queryKeys.remoteLaunch.settings() })
+    },
+  })
+}
+
+export function useRemoteLaunchAuditsQuery(limit = 20) {
+  return createQuery<RemoteLaunchAuditVm[]>({
+    queryKey: queryKeys.remoteLaunch.audits(),
+    queryFn: () => commandGetRemoteLaunchAudits(limit, 0),
+  })
+}
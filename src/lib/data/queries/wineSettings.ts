@@ -0,0 +1,30 @@
+import type { WineConfigVm, WineSettingsVm } from '@/lib/command'
+import { createMutation, createQuery } from '@tanstack/svelte-query'
+import { commandDeleteWineConfig, commandGetWineSettings, commandSaveWineConfig } from '@/lib/command'
+import { queryClient } from '@/lib/data/queryClient'
+import { queryKeys } from '@/lib/data/queryKeys'
+
+export function useWineSettingsQuery(workId: string) {
+  return createQuery<WineSettingsVm>({
+    queryKey: queryKeys.wineSettings.byWorkId(workId),
+    queryFn: () => commandGetWineSettings(workId),
+  })
+}
+
+async function invalidateWineSettings(workId: string) {
+  await queryClient.invalidateQueries({ queryKey: queryKeys.wineSettings.byWorkId(workId) })
+}
+
+export function useSaveWineConfigMutation(workId: string) {
+  return createMutation<void, Error, WineConfigVm>({
+    mutationFn: config => commandSaveWineConfig(config),
+    onSuccess: () => invalidateWineSettings(workId),
+  })
+}
+
+export function useDeleteWineConfigMutation(workId: string) {
+  return createMutation<void, Error, void>({
+    mutationFn: () => commandDeleteWineConfig(workId),
+    onSuccess: () => invalidateWineSettings(workId),
+  })
+}
@@ -7,9 +7,16 @@ export const queryKeys = {
     all: () => ['workLnk'] as const,
     byId: (id: string) => ['workLnk', id] as const,
   },
+  launchProfiles: {
+    byWorkId: (workId: string) => ['launchProfiles', workId] as const,
+  },
+  wineSettings: {
+    byWorkId: (workId: string) => ['wineSettings', workId] as const,
+  },
   imageQueue: {
     unfinished: () => ['imageQueue', 'unfinished'] as const,
     finished: () => ['imageQueue', 'finished'] as const,
+    failed: () => ['imageQueue', 'failed'] as const,
   },
   storagePaths: {
     all: () => ['storagePaths'] as const,
@@ -18,4 +25,8 @@ export const queryKeys = {
     settings: () => ['remoteShare', 'settings'] as const,
     url: () => ['remoteShare', 'url'] as const,
   },
+  remoteLaunch: {
+    settings: () => ['remoteLaunch', 'settings'] as const,
+    audits: () => ['remoteLaunch', 'audits'] as const,
+  },
 }
@@ -28,6 +28,8 @@ export type {
   ProgressLivePayload,
   ProgressPayload,
   PubSubEvent,
+  RemoteLaunchConfirmationClosedPayload,
+  RemoteLaunchConfirmationPayload,
   ScanCandidateDiscoveredPayload,
   ScanExploreFinishedPayload,
   ScanLogPayload,
@@ -36,6 +36,7 @@ export interface EnrichResultPayload {
 	path: string;
 	title?: string;
 	egsId?: number;
+	explanation?: MatchExplanationPayload;
 }
 
 export interface ExtensionConnectionPayload {
@@ -62,6 +63,37 @@ export interface ImageQueueWorkerStatusPayload {
 	totalCount?: number;
 }
 
+export interface MatchCandidateExplanationPayload {
+	egsId: number;
+	gamename: string;
+	score: number;
+	rule: string;
+	querySource: string;
+	query: string;
+	normalizedQuery: string;
+	normalizedGamename: string;
+	ngramOverlap?: number;
+	distance?: number;
+}
+
+export interface MatchPrunedCandidatePayload {
+	egsId: number;
+	gamename: string;
+	querySource: string;
+	query: string;
+	reason: string;
+	ngramOverlap?: number;
+	ngramRequired?: number;
+	distance?: number;
+	maxDistance?: number;
+	score?: number;
+}
+
+export interface MatchExplanationPayload {
+	candidates: MatchCandidateExplanationPayload[];
+	pruned: MatchPrunedCandidatePayload[];
+}
+
 export interface ProgressLivePayload {
 	max?: number;
 }
@@ -70,6 +102,18 @@ export interface ProgressPayload {
 	message: string;
 }
 
+export interface RemoteLaunchConfirmationClosedPayload {
+	requestId: string;
+	decision: string;
+}
+
+export interface RemoteLaunchConfirmationPayload {
+	requestId: string;
+	workId: string;
+	workTitle?: string;
+	timeoutSeconds: number;
+}
+
 export interface ScanCandidateDiscoveredPayload {
 	count: number;
 	path: string;
@@ -127,5 +171,7 @@ export type PubSubEvent =
 	| { type: "appSignal:showMessage", payload: AppSignalPayload }
 	| { type: "appSignal:showErrorMessage", payload: AppSignalPayload }
 	| { type: "appSignal:refetchWork", payload: AppSignalPayload }
-	| { type: "appSignal:refetchWorks", payload: AppSignalPayload };
+	| { type: "appSignal:refetchWorks", payload: AppSignalPayload }
+	| { type: "remoteLaunchConfirmationRequested", payload: RemoteLaunchConfirmationPayload }
+	| { type: "remoteLaunchConfirmationClosed", payload: RemoteLaunchConfirmationClosedPayload };
 
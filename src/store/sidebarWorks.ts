@@ -1,3 +1,4 @@
+import type { GameEngine, PlayStatus, ThumbnailVariantVm } from '@/lib/command'
 import { commandGetWorkDetailsAll, commandUpdateWorkLike } from '@/lib/command'
 import { createWritable } from '@/lib/utils'
 
@@ -5,7 +6,7 @@ export interface SidebarWorkItem {
   id: string
   title: string
   icon?: { path: string }
-  thumbnail?: { path: string, width?: number, height?: number }
+  thumbnail?: { path: string, width?: number, height?: number, variants?: ThumbnailVariantVm[], placeholderColor?: string | null }
   likeAt?: string | null
   installAt?: string | null
   lastPlayAt?: string | null
@@ -17,6 +18,9 @@ export interface SidebarWorkItem {
   sellday?: string
   isNukige?: boolean
   hasPath?: boolean
+  engine?: GameEngine | null
+  playStatus?: PlayStatus
+  score?: number | null
 }
 
 export interface SidebarWorkItemsWithLabel {
@@ -39,12 +43,16 @@ function createSidebarWorks() {
         installAt: v.installAt ?? null,
         lastPlayAt: v.lastPlayAt ?? null,
         registeredAt: v.registeredAt ?? null,
-        gamenameRuby: v.erogamescapeInformation?.gamenameRuby,
-        brandname: v.erogamescapeInformation?.brandname,
+        // 批評空間に紐づいていない作品でも、直した読み・ブランド・発売日は使う
+        gamenameRuby: v.overrides.titleRuby ?? v.erogamescapeInformation?.gamenameRuby,
+        brandname: v.overrides.brandname ?? v.erogamescapeInformation?.brandname,
         brandnameRuby: v.erogamescapeInformation?.brandnameRuby,
-        sellday: v.erogamescapeInformation?.sellday,
+        sellday: v.overrides.sellday ?? v.erogamescapeInformation?.sellday,
         isNukige: v.erogamescapeInformation?.isNukige,
         hasPath: !!v.latestDownloadPath?.downloadPath,
+        engine: v.engine ?? null,
+        playStatus: v.playState?.status ?? 'unplayed',
+        score: v.playState?.score ?? null,
       })),
     )
   }